@@ -0,0 +1,82 @@
+//! Counts heap allocations made while running a chatty task.
+//!
+//! Uses a counting global allocator, so it lives in its own bench binary to
+//! keep the timing benchmarks unaffected. Run on two revisions to compare:
+//!
+//! ```sh
+//! cargo bench --bench allocations
+//! ```
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use tcrm_task::tasks::{async_tokio::spawner::TaskSpawner, config::TaskConfig, event::TaskEvent};
+use tokio::sync::mpsc;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const LINES: u64 = 100_000;
+
+async fn run_chatty_task() -> u64 {
+    let (tx, mut rx) = mpsc::channel(1024);
+    let config = if cfg!(windows) {
+        TaskConfig::new("powershell").args(vec!["-Command".to_string(), format!("1..{LINES}")])
+    } else {
+        TaskConfig::new("seq").args(vec!["1".to_string(), LINES.to_string()])
+    };
+    let mut spawner = TaskSpawner::new("chatty_task".to_string(), config);
+    spawner.start_direct(tx).await.unwrap();
+
+    let mut lines = 0;
+    while let Some(event) = rx.recv().await {
+        if matches!(event, TaskEvent::Output { .. }) {
+            lines += 1;
+        }
+    }
+    lines
+}
+
+fn main() {
+    // `cargo test --all-targets` also runs this binary, without `--bench`
+    if !std::env::args().any(|arg| arg == "--bench") {
+        return;
+    }
+
+    let rt = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    // Warm up so one-time runtime allocations are not counted
+    rt.block_on(run_chatty_task());
+
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let lines = rt.block_on(run_chatty_task());
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+
+    assert_eq!(lines, LINES);
+    #[allow(clippy::cast_precision_loss)]
+    let per_line = allocations as f64 / lines as f64;
+    println!("chatty_task: {allocations} allocations for {lines} lines ({per_line:.2} per line)");
+}
@@ -0,0 +1,95 @@
+//! Latency of a timer task sharing the runtime with a chatty task.
+//!
+//! Ticks every millisecond on a single-threaded runtime while a child prints
+//! 200k lines, and reports how late the ticks fire, with the default watcher
+//! and with `TaskConfig::yield_every_n_lines`:
+//!
+//! ```sh
+//! cargo bench --bench coop_latency
+//! ```
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use tcrm_task::tasks::{async_tokio::spawner::TaskSpawner, event::TaskEvent};
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+mod support;
+
+use support::{TaskConfig, commands};
+
+const LINES: u64 = 200_000;
+const TICK: Duration = Duration::from_millis(1);
+const ROUNDS: usize = 5;
+
+/// Lateness of each tick until `stop` is set
+async fn tick_lateness(stop: Arc<AtomicBool>) -> Vec<Duration> {
+    let mut lateness = Vec::new();
+    while !stop.load(Ordering::Relaxed) {
+        let deadline = Instant::now() + TICK;
+        tokio::time::sleep_until(deadline).await;
+        lateness.push(Instant::now().saturating_duration_since(deadline));
+    }
+    lateness
+}
+
+/// Runs the chatty task next to the ticker, returning the lateness of all ticks
+async fn run(config: TaskConfig) -> Vec<Duration> {
+    let stop = Arc::new(AtomicBool::new(false));
+    let ticker = tokio::spawn(tick_lateness(stop.clone()));
+
+    let (tx, mut rx) = mpsc::channel(1024);
+    let mut spawner = TaskSpawner::new("chatty_task".to_string(), config);
+    spawner.start_direct(tx).await.unwrap();
+    let mut lines = 0;
+    while let Some(event) = rx.recv().await {
+        if matches!(event, TaskEvent::Output { .. }) {
+            lines += 1;
+        }
+    }
+    assert_eq!(lines, LINES);
+
+    stop.store(true, Ordering::Relaxed);
+    ticker.await.unwrap()
+}
+
+/// The value below which `percent` of the sorted `values` fall
+fn percentile(values: &[Duration], percent: usize) -> Duration {
+    values[(values.len() * percent / 100).min(values.len() - 1)]
+}
+
+fn main() {
+    // `cargo test --all-targets` also runs this binary, without `--bench`
+    if !std::env::args().any(|arg| arg == "--bench") {
+        return;
+    }
+
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    let configs = [
+        ("default", commands::lines(LINES)),
+        (
+            "yield_every_n_lines(16)",
+            commands::lines(LINES).yield_every_n_lines(16),
+        ),
+    ];
+    for (name, config) in configs {
+        let mut lateness = Vec::new();
+        for _ in 0..ROUNDS {
+            lateness.extend(rt.block_on(run(config.clone())));
+        }
+        lateness.sort();
+        println!(
+            "{name}: {} ticks, p50 {:?}, p99 {:?}, max {:?}",
+            lateness.len(),
+            percentile(&lateness, 50),
+            percentile(&lateness, 99),
+            lateness[lateness.len() - 1],
+        );
+    }
+}
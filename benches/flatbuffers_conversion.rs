@@ -1,9 +1,10 @@
 use criterion::{Criterion, criterion_group, criterion_main};
 use std::collections::HashMap;
 use std::hint::black_box;
+use std::time::SystemTime;
 use tcrm_task::tasks::{
     config::{StreamSource, TaskConfig},
-    error::TaskError,
+    error::{ChannelKind, ChannelOp, TaskError},
     event::{TaskEvent, TaskEventStopReason, TaskTerminateReason},
     state::TaskState,
 };
@@ -34,6 +35,7 @@ fn bench_config_serialization(c: &mut Criterion) {
         ready_indicator: Some("READY".to_string()),
         ready_indicator_source: Some(StreamSource::Stdout),
         use_process_group: Some(true),
+        ..Default::default()
     };
 
     c.bench_function("config_to_flatbuffers", |b| {
@@ -66,7 +68,11 @@ fn bench_error_conversion(c: &mut Criterion) {
     let errors = vec![
         TaskError::IO("IO error benchmark".to_string()),
         TaskError::Handle("Handle error benchmark".to_string()),
-        TaskError::Channel("Channel error benchmark".to_string()),
+        TaskError::Channel {
+            kind: ChannelKind::Event,
+            op: ChannelOp::Send,
+            detail: "Channel error benchmark".to_string(),
+        },
         TaskError::InvalidConfiguration("Invalid config benchmark".to_string()),
     ];
 
@@ -86,23 +92,37 @@ fn bench_error_conversion(c: &mut Criterion) {
 fn bench_event_serialization(c: &mut Criterion) {
     let events = vec![
         TaskEvent::Started {
-            task_name: "benchmark_task".to_string(),
+            task_name: "benchmark_task".into(),
+            process_id: 4242,
+            temp_dir: None,
+            working_dir: None,
         },
         TaskEvent::Output {
-            task_name: "benchmark_task".to_string(),
+            task_name: "benchmark_task".into(),
             line: "This is a benchmark output line".to_string(),
             src: StreamSource::Stdout,
+            seq: 0,
+            truncated: false,
+            timestamp: SystemTime::now(),
         },
         TaskEvent::Ready {
-            task_name: "benchmark_task".to_string(),
+            task_name: "benchmark_task".into(),
         },
         TaskEvent::Stopped {
-            task_name: "benchmark_task".to_string(),
+            task_name: "benchmark_task".into(),
             exit_code: Some(0),
+            signal: None,
             reason: TaskEventStopReason::Finished,
+            was_ready: false,
+            duration_ms: 1500,
+            expected_duration_ms: None,
+            exit_meaning: None,
+            from_cache: false,
+            stdout_sha256: None,
+            stderr_sha256: None,
         },
         TaskEvent::Error {
-            task_name: "benchmark_task".to_string(),
+            task_name: "benchmark_task".into(),
             error: TaskError::IO("Benchmark error".to_string()),
         },
     ];
@@ -142,7 +162,7 @@ fn bench_state_conversion(c: &mut Criterion) {
 
     let terminate_reasons = vec![
         TaskTerminateReason::Timeout,
-        TaskTerminateReason::Cleanup,
+        TaskTerminateReason::Cleanup { message: None },
         TaskTerminateReason::DependenciesFinished,
     ];
 
@@ -0,0 +1,39 @@
+//! Portable task configurations for benchmarks and latency tests.
+//!
+//! Each helper uses a shell one-liner available on both Windows and Unix, so
+//! the benchmarks and the integration tests that reuse these helpers run on
+//! either platform without a separate helper binary.
+
+#![allow(dead_code)]
+
+use super::TaskConfig;
+
+/// A command that exits immediately without output.
+pub fn trivial() -> TaskConfig {
+    if cfg!(windows) {
+        TaskConfig::new("cmd").args(["/C", "exit 0"])
+    } else {
+        TaskConfig::new("true")
+    }
+}
+
+/// A command that prints the numbers `1..=count`, one per line.
+pub fn lines(count: u64) -> TaskConfig {
+    if cfg!(windows) {
+        TaskConfig::new("powershell").args(["-Command".to_string(), format!("1..{count}")])
+    } else {
+        TaskConfig::new("seq").args(["1".to_string(), count.to_string()])
+    }
+}
+
+/// A command that sleeps for `secs` seconds without output.
+pub fn sleep(secs: u64) -> TaskConfig {
+    if cfg!(windows) {
+        TaskConfig::new("powershell").args([
+            "-Command".to_string(),
+            format!("Start-Sleep -Seconds {secs}"),
+        ])
+    } else {
+        TaskConfig::new("sleep").args([secs.to_string()])
+    }
+}
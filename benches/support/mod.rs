@@ -0,0 +1,8 @@
+//! Helpers shared by the benchmarks.
+//!
+//! `commands` is also compiled into the crate's integration tests, so it
+//! takes `TaskConfig` from its parent module instead of naming the crate.
+
+pub use tcrm_task::tasks::config::TaskConfig;
+
+pub mod commands;
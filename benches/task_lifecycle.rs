@@ -0,0 +1,170 @@
+//! End-to-end task lifecycle benchmarks.
+//!
+//! These spawn real processes: spawn latency up to `TaskEvent::Started`, output
+//! throughput of a 100k-line child (also per output buffer size, and with a
+//! ready indicator found at once or never), and termination latency of a
+//! sleeping child.
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use tcrm_task::tasks::{
+    async_tokio::spawner::TaskSpawner,
+    event::{TaskEvent, TaskTerminateReason},
+};
+use tokio::sync::mpsc;
+
+mod support;
+
+use support::commands;
+
+const LINES: u64 = 100_000;
+
+/// Receives events until `Stopped`, returning the number of output lines.
+async fn drain(rx: &mut mpsc::Receiver<TaskEvent>) -> u64 {
+    let mut lines = 0;
+    while let Some(event) = rx.recv().await {
+        match event {
+            TaskEvent::Output { .. } => lines += 1,
+            TaskEvent::Stopped { .. } => break,
+            _ => {}
+        }
+    }
+    lines
+}
+
+fn bench_spawn_latency(c: &mut Criterion) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let mut group = c.benchmark_group("lifecycle");
+    group.sample_size(20);
+
+    // Time from calling `start_direct` until `Started` is received
+    group.bench_function("start_to_started", |b| {
+        b.iter_custom(|iters| {
+            rt.block_on(async {
+                let mut total = Duration::ZERO;
+                for _ in 0..iters {
+                    let (tx, mut rx) = mpsc::channel(64);
+                    let mut spawner = TaskSpawner::new("trivial".to_string(), commands::trivial());
+
+                    let start = Instant::now();
+                    spawner.start_direct(tx).await.unwrap();
+                    let event = rx.recv().await;
+                    total += start.elapsed();
+
+                    assert!(matches!(event, Some(TaskEvent::Started { .. })));
+                    drain(&mut rx).await;
+                }
+                total
+            })
+        })
+    });
+    group.finish();
+}
+
+fn bench_event_throughput(c: &mut Criterion) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let mut group = c.benchmark_group("lifecycle");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(LINES));
+
+    group.bench_function("lines_100k", |b| {
+        b.iter(|| {
+            rt.block_on(async {
+                let (tx, mut rx) = mpsc::channel(1024);
+                let mut spawner = TaskSpawner::new("lines".to_string(), commands::lines(LINES));
+                spawner.start_direct(tx).await.unwrap();
+
+                let lines = drain(&mut rx).await;
+                assert_eq!(lines, LINES);
+                black_box(lines)
+            })
+        })
+    });
+
+    // Read buffer size trades syscalls per line against memory per task
+    for bytes in [1024, 64 * 1024] {
+        group.bench_with_input(
+            BenchmarkId::new("lines_100k_output_buffer", bytes),
+            &bytes,
+            |b, &bytes| {
+                b.iter(|| {
+                    rt.block_on(async {
+                        let (tx, mut rx) = mpsc::channel(1024);
+                        let config = commands::lines(LINES).output_buffer_bytes(bytes);
+                        let mut spawner = TaskSpawner::new("lines".to_string(), config);
+                        spawner.start_direct(tx).await.unwrap();
+
+                        let lines = drain(&mut rx).await;
+                        assert_eq!(lines, LINES);
+                        black_box(lines)
+                    })
+                })
+            },
+        );
+    }
+
+    // Once the indicator matched on the first line, later lines skip the matcher
+    // and should cost as much as without an indicator
+    for (name, indicator) in [("first_line", "1"), ("never", "not in output")] {
+        group.bench_with_input(
+            BenchmarkId::new("lines_100k_ready", name),
+            &indicator,
+            |b, &indicator| {
+                b.iter(|| {
+                    rt.block_on(async {
+                        let (tx, mut rx) = mpsc::channel(1024);
+                        let config = commands::lines(LINES).ready_indicator(indicator);
+                        let mut spawner = TaskSpawner::new("lines".to_string(), config);
+                        spawner.start_direct(tx).await.unwrap();
+
+                        let lines = drain(&mut rx).await;
+                        assert_eq!(lines, LINES);
+                        black_box(lines)
+                    })
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_terminate_latency(c: &mut Criterion) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let mut group = c.benchmark_group("lifecycle");
+    group.sample_size(10);
+
+    // Time from sending the terminate signal until `Stopped` is received
+    group.bench_function("terminate_to_stopped", |b| {
+        b.iter_custom(|iters| {
+            rt.block_on(async {
+                let mut total = Duration::ZERO;
+                for _ in 0..iters {
+                    let (tx, mut rx) = mpsc::channel(64);
+                    let mut spawner = TaskSpawner::new("sleep".to_string(), commands::sleep(30));
+                    spawner.start_direct(tx).await.unwrap();
+                    assert!(matches!(rx.recv().await, Some(TaskEvent::Started { .. })));
+
+                    let start = Instant::now();
+                    spawner
+                        .send_terminate_signal(TaskTerminateReason::UserRequested { message: None })
+                        .await
+                        .unwrap();
+                    drain(&mut rx).await;
+                    total += start.elapsed();
+                }
+                total
+            })
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    lifecycle_benches,
+    bench_spawn_latency,
+    bench_event_throughput,
+    bench_terminate_latency,
+);
+
+criterion_main!(lifecycle_benches);
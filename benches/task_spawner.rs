@@ -91,7 +91,7 @@ fn bench_task_execution_lightweight(c: &mut Criterion) {
             )
             .timeout_ms(5000);
 
-        b.iter(|| black_box(config.validate().unwrap()))
+        b.iter(|| black_box(config.validate()).unwrap())
     });
 
     c.bench_function("task_spawner_creation_with_validation", |b| {
@@ -218,6 +218,112 @@ fn bench_actual_process_execution(c: &mut Criterion) {
     });
 }
 
+// Spawns a child printing 1M lines and counts the Output events it produces,
+// reporting lines/second through the output watcher, with and without a
+// ready indicator to scan for. Run on two revisions to compare throughput.
+// Disabled by default like the other process benchmarks
+#[allow(dead_code)]
+fn bench_output_throughput(c: &mut Criterion) {
+    const LINES: u64 = 1_000_000;
+
+    let mut group = c.benchmark_group("output_throughput");
+    group.sample_size(10);
+    group.throughput(criterion::Throughput::Elements(LINES));
+    for (name, ready_indicator, capacity) in [
+        ("child_1m_lines", None, 1024),
+        // Indicator never matches, so every line is scanned
+        ("child_1m_lines_with_ready_indicator", Some("NEVER_READY"), 1024),
+        // Channel rarely fills, so sends stay on the `try_send` fast path
+        ("child_1m_lines_large_channel", None, 65536),
+    ] {
+        group.bench_function(name, |b| {
+            let rt = tokio::runtime::Runtime::new().unwrap();
+            b.iter(|| {
+                rt.block_on(async {
+                    let (tx, mut rx) = mpsc::channel(capacity);
+
+                    let mut config = if cfg!(windows) {
+                        TaskConfig::new("powershell")
+                            .args(vec!["-Command".to_string(), format!("1..{LINES}")])
+                    } else {
+                        TaskConfig::new("seq").args(vec!["1".to_string(), LINES.to_string()])
+                    };
+                    if let Some(indicator) = ready_indicator {
+                        config = config.ready_indicator(indicator.to_string());
+                    }
+
+                    let mut spawner = TaskSpawner::new("throughput_process".to_string(), config);
+                    spawner.start_direct(tx).await.unwrap();
+
+                    let mut lines = 0u64;
+                    while let Some(event) = rx.recv().await {
+                        if matches!(event, tcrm_task::tasks::event::TaskEvent::Output { .. }) {
+                            lines += 1;
+                        }
+                    }
+                    assert_eq!(lines, LINES);
+                    black_box(lines)
+                })
+            })
+        });
+    }
+    group.finish();
+}
+
+// Counts the tokio tasks alive right after `start_direct` for a task using every
+// watcher (stdout, stderr, stdin, timeout), then measures launching short tasks.
+// Run on two revisions to compare per-task scheduler overhead.
+#[allow(dead_code)]
+fn bench_watcher_tasks(c: &mut Criterion) {
+    fn config() -> TaskConfig {
+        let config = if cfg!(windows) {
+            TaskConfig::new("cmd").args(vec!["/C".to_string(), "exit 0".to_string()])
+        } else {
+            TaskConfig::new("true")
+        };
+        config.timeout_ms(10_000).enable_stdin(true)
+    }
+
+    async fn run_task() -> usize {
+        let (tx, mut rx) = mpsc::channel(100);
+        let (_stdin_tx, stdin_rx) = mpsc::channel(1);
+        let mut spawner =
+            TaskSpawner::new("watcher_task".to_string(), config()).set_stdin(stdin_rx);
+
+        let before = tokio::runtime::Handle::current().metrics().num_alive_tasks();
+        spawner.start_direct(tx).await.unwrap();
+        let spawned = tokio::runtime::Handle::current().metrics().num_alive_tasks() - before;
+
+        while let Some(event) = rx.recv().await {
+            if matches!(event, tcrm_task::tasks::event::TaskEvent::Stopped { .. }) {
+                break;
+            }
+        }
+        spawned
+    }
+
+    // Single-threaded so no watcher can finish before it is counted
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let spawned = rt.block_on(run_task());
+    println!("watcher_tasks: {spawned} tokio tasks spawned per run");
+
+    let mut group = c.benchmark_group("watcher_tasks");
+    group.sample_size(10);
+    group.bench_function("run_100_short_tasks", |b| {
+        b.iter(|| {
+            rt.block_on(async {
+                for _ in 0..100 {
+                    black_box(run_task().await);
+                }
+            })
+        })
+    });
+    group.finish();
+}
+
 criterion_group!(
     task_performance_benches,
     bench_task_spawner_creation,
@@ -226,6 +332,8 @@ criterion_group!(
     bench_concurrent_task_creation,
     bench_memory_usage_patterns,
     // bench_actual_process_execution, // Uncomment for full process execution benchmarks
+    // bench_output_throughput, // Uncomment to measure output pipeline throughput
+    // bench_watcher_tasks, // Uncomment to count and time per-task watcher tasks
 );
 
 criterion_main!(task_performance_benches);
@@ -99,8 +99,12 @@ async fn test_process_behavior(
     while let Some(event) = event_rx.recv().await {
         events_received += 1;
         match event {
-            TaskEvent::Started { task_name } => {
-                println!("   🚀 Task started: {}", task_name);
+            TaskEvent::Started {
+                task_name,
+                process_id,
+                ..
+            } => {
+                println!("   🚀 Task started: {} (pid {})", task_name, process_id);
             }
             TaskEvent::Ready { task_name } => {
                 println!("   ✅ Task ready: {}", task_name);
@@ -109,13 +113,83 @@ async fn test_process_behavior(
                 task_name,
                 line,
                 src,
+                ..
+            } => {
+                println!("   📤 Output [{}]({:?}): {}", task_name, src, line);
+            }
+            TaskEvent::OutputJson {
+                task_name,
+                line,
+                src,
+                ..
             } => {
                 println!("   📤 Output [{}]({:?}): {}", task_name, src, line);
             }
+            TaskEvent::OutputChunk {
+                task_name,
+                data,
+                src,
+            } => {
+                println!("   📤 Output [{}]({:?}): {} bytes", task_name, src, data.len());
+            }
+            TaskEvent::OutputRepeated {
+                task_name,
+                line,
+                src,
+                count,
+            } => {
+                println!(
+                    "   🔁 Output [{}]({:?}) repeated {} more times: {}",
+                    task_name, src, count, line
+                );
+            }
+            TaskEvent::OutputSuppressed {
+                task_name,
+                src,
+                count,
+                bytes,
+            } => {
+                println!(
+                    "   🔇 Output [{}]({:?}) suppressed {} lines ({} bytes)",
+                    task_name, src, count, bytes
+                );
+            }
+            TaskEvent::Triggered {
+                task_name,
+                name,
+                line,
+                src,
+            } => {
+                println!(
+                    "   🎯 Trigger {} [{}]({:?}): {}",
+                    name, task_name, src, line
+                );
+            }
+            TaskEvent::RunningLong {
+                task_name,
+                expected_ms,
+                elapsed_ms,
+            } => {
+                println!(
+                    "   🐢 Task running long: {} - {}ms, expected {}ms",
+                    task_name, elapsed_ms, expected_ms
+                );
+            }
+            TaskEvent::MemoryWatermarkExceeded {
+                task_name,
+                rss_bytes,
+                watermark_bytes,
+            } => {
+                println!(
+                    "   🐘 Task memory above watermark: {} - {} bytes, watermark {} bytes",
+                    task_name, rss_bytes, watermark_bytes
+                );
+            }
             TaskEvent::Stopped {
                 task_name,
                 exit_code,
                 reason,
+                ..
             } => {
                 println!(
                     "   🛑 Task stopped: {} - Exit: {:?}, Reason: {:?}",
@@ -123,6 +197,40 @@ async fn test_process_behavior(
                 );
                 break;
             }
+            TaskEvent::SpawnRetry {
+                task_name,
+                attempt,
+                error,
+            } => {
+                println!(
+                    "   🔁 Spawn retry: {} - attempt {} failed: {}",
+                    task_name, attempt, error
+                );
+            }
+            TaskEvent::ScriptStep { task_name, index } => {
+                println!(
+                    "   ⌨️  Script step: {} - step {} answered",
+                    task_name, index
+                );
+            }
+            TaskEvent::Unhealthy {
+                task_name,
+                consecutive_failures,
+            } => {
+                println!(
+                    "   🩺 Unhealthy: {} - {} health probes failed",
+                    task_name, consecutive_failures
+                );
+            }
+            TaskEvent::SlowConsumer {
+                task_name,
+                avg_wait_ms,
+            } => {
+                println!(
+                    "   🐌 Slow consumer: {} - events waited {}ms on average",
+                    task_name, avg_wait_ms
+                );
+            }
             TaskEvent::Error { task_name, error } => {
                 println!("   ❌ Task error: {} - {}", task_name, error);
                 break;
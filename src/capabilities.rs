@@ -0,0 +1,247 @@
+//! What this build of the crate supports on the running platform.
+//!
+//! Most controls of a task depend on the operating system, and some on
+//! process group management or on optional features. [`capabilities`]
+//! gathers them in one report, so a UI can hide the controls that would only
+//! return an error.
+
+/// Features this build supports on the running platform
+///
+/// Returned by [`capabilities`]. Each flag tells whether the matching API
+/// works here; the `_detail` strings say how it is implemented, or why it is
+/// unavailable.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Operating system, as in [`std::env::consts::OS`]
+    pub os: String,
+    /// Tasks can be spawned, which requires the `tokio` feature
+    pub spawn: bool,
+    /// Tasks can read stdin, with `TaskConfig::enable_stdin`
+    pub stdin: bool,
+    /// Process groups can be created, with `TaskConfig::use_process_group`
+    pub process_groups: bool,
+    /// How process groups are implemented, or why they are unavailable
+    pub process_groups_detail: String,
+    /// Process groups can be named, with `TaskConfig::job_object_name`
+    pub named_process_groups: bool,
+    /// Tasks can be interrupted, with `TaskSpawner::send_interrupt_signal`
+    pub interrupt: bool,
+    /// How interrupts are sent, or why they are unavailable
+    pub interrupt_detail: String,
+    /// Tasks can be paused and resumed, with `TaskSpawner::pause_process`
+    pub pause_resume: bool,
+    /// How tasks are paused, or why they cannot be
+    pub pause_resume_detail: String,
+    /// Tasks can be asked to shut down before they are killed, with
+    /// `TaskConfig::graceful_shutdown_ms`
+    pub graceful_shutdown: bool,
+    /// How tasks are asked to shut down, or why they cannot be
+    pub graceful_shutdown_detail: String,
+    /// Children can be killed when this process dies, with
+    /// `TaskConfig::kill_child_on_parent_death`
+    pub kill_child_on_parent_death: bool,
+    /// `TaskSpawner::process_tree` lists processes
+    pub process_tree: bool,
+    /// Resident memory is sampled for `TaskConfig::memory_watermark_bytes`
+    pub memory_watermark: bool,
+    /// A process ID reused by another process is detected before signaling it
+    pub pid_reuse_detection: bool,
+    /// Output lines can be parsed as JSON, with `TaskConfig::parse_json_lines`,
+    /// which requires the `serde` feature
+    pub parse_json_lines: bool,
+    /// Ready indicators can be regular expressions, with
+    /// `TaskConfig::ready_indicator_regex`, which requires the `regex` feature
+    pub ready_indicator_regex: bool,
+    /// Output streams can be hashed, with `TaskConfig::hash_output`, which
+    /// requires the `sha2` feature
+    pub hash_output: bool,
+    /// Configurations and events can be encoded as FlatBuffers, with the
+    /// `flatbuffers` feature
+    pub flatbuffers: bool,
+    /// Structured logs are emitted, with the `tracing` feature
+    pub tracing: bool,
+}
+
+/// Reports what this build supports on the running platform
+///
+/// Computed from the target and the enabled features, except for process
+/// groups: on Windows, a Job Object is created and closed to check that this
+/// process may create one. Controls that need process groups on Windows, like
+/// interrupting or pausing a task, are reported as unavailable when the check
+/// fails.
+///
+/// # Examples
+///
+/// ```rust
+/// let capabilities = tcrm_task::capabilities();
+/// if !capabilities.pause_resume {
+///     println!("Pause unavailable: {}", capabilities.pause_resume_detail);
+/// }
+/// assert_eq!(capabilities.os, std::env::consts::OS);
+/// ```
+pub fn capabilities() -> Capabilities {
+    let spawn = cfg!(feature = "tokio");
+    let process_groups = process_groups();
+    let groups = process_groups.is_ok();
+    let (interrupt, interrupt_detail) = control(spawn, groups, "SIGINT", "CTRL_BREAK_EVENT");
+    let (pause_resume, pause_resume_detail) = control(
+        spawn,
+        groups,
+        "SIGSTOP and SIGCONT",
+        "Suspends the threads of the Job Object",
+    );
+    let (graceful_shutdown, graceful_shutdown_detail) = control(
+        spawn,
+        groups,
+        "SIGTERM, then SIGKILL",
+        "CTRL_BREAK_EVENT, then terminates the Job Object",
+    );
+    let linux_or_windows = cfg!(any(target_os = "linux", target_os = "android", windows));
+    Capabilities {
+        os: std::env::consts::OS.to_string(),
+        spawn,
+        stdin: spawn,
+        process_groups: groups,
+        process_groups_detail: process_groups.unwrap_or_else(|reason| reason),
+        named_process_groups: cfg!(windows) && groups,
+        interrupt,
+        interrupt_detail,
+        pause_resume,
+        pause_resume_detail,
+        graceful_shutdown,
+        graceful_shutdown_detail,
+        kill_child_on_parent_death: spawn
+            && (cfg!(any(target_os = "linux", target_os = "android")) || (cfg!(windows) && groups)),
+        process_tree: spawn && linux_or_windows,
+        memory_watermark: spawn && linux_or_windows,
+        pid_reuse_detection: spawn && linux_or_windows,
+        parse_json_lines: cfg!(feature = "serde"),
+        ready_indicator_regex: cfg!(feature = "regex"),
+        hash_output: cfg!(feature = "sha2"),
+        flatbuffers: cfg!(feature = "flatbuffers"),
+        tracing: cfg!(feature = "tracing"),
+    }
+}
+
+/// How process groups are implemented, or why they are unavailable
+fn process_groups() -> Result<String, String> {
+    #[cfg(feature = "tokio")]
+    {
+        use crate::tasks::async_tokio::process_group::ProcessGroup;
+
+        ProcessGroup::probe().map_err(|e| e.to_string())?;
+        if cfg!(windows) {
+            Ok("Job Object".to_string())
+        } else {
+            Ok("POSIX process group".to_string())
+        }
+    }
+    #[cfg(not(feature = "tokio"))]
+    {
+        Err("Requires the tokio feature".to_string())
+    }
+}
+
+/// Whether a control of running tasks is available, and how it works
+///
+/// On Windows, controls go through the Job Object or console process group of
+/// the task, so they need process groups.
+fn control(spawn: bool, process_groups: bool, unix: &str, windows: &str) -> (bool, String) {
+    if !spawn {
+        (false, "Requires the tokio feature".to_string())
+    } else if cfg!(unix) {
+        (true, unix.to_string())
+    } else if cfg!(windows) && process_groups {
+        (true, format!("{windows}, for tasks with a process group"))
+    } else if cfg!(windows) {
+        (false, "Requires process groups on Windows".to_string())
+    } else {
+        (false, "Not available on this platform".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_build_features() {
+        let capabilities = capabilities();
+        assert_eq!(capabilities.os, std::env::consts::OS);
+        assert_eq!(capabilities.spawn, cfg!(feature = "tokio"));
+        assert_eq!(capabilities.stdin, cfg!(feature = "tokio"));
+        assert_eq!(capabilities.parse_json_lines, cfg!(feature = "serde"));
+        assert_eq!(capabilities.ready_indicator_regex, cfg!(feature = "regex"));
+        assert_eq!(capabilities.hash_output, cfg!(feature = "sha2"));
+        assert_eq!(capabilities.flatbuffers, cfg!(feature = "flatbuffers"));
+        assert_eq!(capabilities.tracing, cfg!(feature = "tracing"));
+    }
+
+    #[cfg(all(feature = "tokio", any(target_os = "linux", target_os = "android")))]
+    #[test]
+    fn linux_supports_every_control() {
+        let capabilities = capabilities();
+        assert!(capabilities.process_groups);
+        assert_eq!(capabilities.process_groups_detail, "POSIX process group");
+        assert!(!capabilities.named_process_groups);
+        assert!(capabilities.interrupt);
+        assert!(capabilities.pause_resume);
+        assert!(capabilities.graceful_shutdown);
+        assert!(capabilities.kill_child_on_parent_death);
+        assert!(capabilities.process_tree);
+        assert!(capabilities.memory_watermark);
+        assert!(capabilities.pid_reuse_detection);
+    }
+
+    #[cfg(all(feature = "tokio", windows))]
+    #[test]
+    fn windows_supports_every_control_with_job_objects() {
+        let capabilities = capabilities();
+        assert!(capabilities.process_groups);
+        assert_eq!(capabilities.process_groups_detail, "Job Object");
+        assert!(capabilities.named_process_groups);
+        assert!(capabilities.interrupt);
+        assert!(capabilities.pause_resume);
+        assert!(capabilities.graceful_shutdown);
+        assert!(capabilities.kill_child_on_parent_death);
+        assert!(capabilities.process_tree);
+    }
+
+    #[cfg(all(feature = "tokio", target_os = "macos"))]
+    #[test]
+    fn macos_lacks_proc_based_features() {
+        let capabilities = capabilities();
+        assert!(capabilities.process_groups);
+        assert!(capabilities.interrupt);
+        assert!(capabilities.pause_resume);
+        assert!(capabilities.graceful_shutdown);
+        assert!(!capabilities.kill_child_on_parent_death);
+        assert!(!capabilities.process_tree);
+        assert!(!capabilities.memory_watermark);
+    }
+
+    #[cfg(not(feature = "tokio"))]
+    #[test]
+    fn nothing_runs_without_tokio() {
+        let capabilities = capabilities();
+        assert!(!capabilities.process_groups);
+        assert!(!capabilities.interrupt);
+        assert!(!capabilities.pause_resume);
+        assert!(!capabilities.graceful_shutdown);
+        assert!(!capabilities.process_tree);
+        assert_eq!(
+            capabilities.process_groups_detail,
+            "Requires the tokio feature"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_as_flat_object() {
+        let value = serde_json::to_value(capabilities()).unwrap();
+        assert_eq!(value["os"], std::env::consts::OS);
+        assert!(value["pause_resume"].is_boolean());
+        assert!(value["pause_resume_detail"].is_string());
+    }
+}
@@ -0,0 +1,111 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{
+    flatbuffers::{
+        conversion::{FromFlatbuffers, ToFlatbuffers, ToFlatbuffersUnion, error::ConversionError},
+        tcrm_task_generated,
+    },
+    tasks::{
+        audit::AuditRecord,
+        event::{TaskEventStopReason, TaskTerminateReason},
+    },
+};
+
+/// Milliseconds since the Unix epoch, 0 for earlier times
+pub(super) fn to_unix_ms(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, |since| {
+        u64::try_from(since.as_millis()).unwrap_or(u64::MAX)
+    })
+}
+
+pub(super) fn from_unix_ms(ms: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_millis(ms)
+}
+
+impl FromFlatbuffers<tcrm_task_generated::tcrm::task::AuditRecord<'_>> for AuditRecord {
+    fn from_flatbuffers(
+        fb_record: tcrm_task_generated::tcrm::task::AuditRecord<'_>,
+    ) -> Result<Self, ConversionError> {
+        let strings = |vec: Option<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<&str>>>| {
+            vec.map(|vec| vec.iter().map(std::string::ToString::to_string).collect())
+                .unwrap_or_default()
+        };
+
+        let reason =
+            TaskEventStopReason::from_flatbuffers((fb_record.reason_type(), fb_record.reason()))?;
+        let terminate_requests = fb_record
+            .terminate_requests()
+            .map(|reasons| {
+                reasons
+                    .iter()
+                    .map(TaskTerminateReason::try_from)
+                    .collect::<Result<_, _>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(AuditRecord {
+            task_name: fb_record.task_name().to_string(),
+            command: fb_record.command().to_string(),
+            args: strings(fb_record.args()),
+            working_dir: fb_record.working_dir().to_string(),
+            env_keys: strings(fb_record.env_keys()),
+            process_id: fb_record.process_id(),
+            started_at: from_unix_ms(fb_record.started_at_ms()),
+            stopped_at: from_unix_ms(fb_record.stopped_at_ms()),
+            exit_code: fb_record.exit_code(),
+            signal: fb_record.signal(),
+            reason,
+            output_bytes: fb_record.output_bytes(),
+            terminate_requests,
+        })
+    }
+}
+
+impl<'a> ToFlatbuffers<'a> for AuditRecord {
+    type Output = flatbuffers::WIPOffset<tcrm_task_generated::tcrm::task::AuditRecord<'a>>;
+
+    fn to_flatbuffers(&self, builder: &mut flatbuffers::FlatBufferBuilder<'a>) -> Self::Output {
+        let task_name_offset = builder.create_string(&self.task_name);
+        let command_offset = builder.create_string(&self.command);
+        let working_dir_offset = builder.create_string(&self.working_dir);
+
+        let args_offsets: Vec<_> = self.args.iter().map(|s| builder.create_string(s)).collect();
+        let args_vec = builder.create_vector(&args_offsets);
+        let env_key_offsets: Vec<_> = self
+            .env_keys
+            .iter()
+            .map(|s| builder.create_string(s))
+            .collect();
+        let env_keys_vec = builder.create_vector(&env_key_offsets);
+
+        let (reason_type, reason_offset) = self.reason.to_flatbuffers_union(builder);
+        let terminate_requests: Vec<tcrm_task_generated::tcrm::task::TaskTerminateReason> = self
+            .terminate_requests
+            .iter()
+            .cloned()
+            .map(Into::into)
+            .collect();
+        let terminate_requests_vec = builder.create_vector(&terminate_requests);
+
+        tcrm_task_generated::tcrm::task::AuditRecord::create(
+            builder,
+            &tcrm_task_generated::tcrm::task::AuditRecordArgs {
+                task_name: Some(task_name_offset),
+                command: Some(command_offset),
+                args: Some(args_vec),
+                working_dir: Some(working_dir_offset),
+                env_keys: Some(env_keys_vec),
+                process_id: self.process_id,
+                started_at_ms: to_unix_ms(self.started_at),
+                stopped_at_ms: to_unix_ms(self.stopped_at),
+                exit_code: self.exit_code,
+                signal: self.signal,
+                reason_type,
+                reason: Some(reason_offset),
+                output_bytes: self.output_bytes,
+                terminate_requests: Some(terminate_requests_vec),
+            },
+        )
+    }
+}
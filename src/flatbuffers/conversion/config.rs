@@ -5,7 +5,7 @@ use crate::{
         conversion::{FromFlatbuffers, ToFlatbuffers, error::ConversionError},
         tcrm_task_generated,
     },
-    tasks::config::{StreamSource, TaskConfig},
+    tasks::config::{OutputTrigger, ReadyMatchMode, StreamSource, TaskConfig, TriggerAction},
 };
 
 impl TryFrom<tcrm_task_generated::tcrm::task::StreamSource> for StreamSource {
@@ -30,6 +30,118 @@ impl From<StreamSource> for tcrm_task_generated::tcrm::task::StreamSource {
     }
 }
 
+impl TryFrom<tcrm_task_generated::tcrm::task::ReadyMatchMode> for ReadyMatchMode {
+    type Error = ConversionError;
+
+    fn try_from(
+        fb_mode: tcrm_task_generated::tcrm::task::ReadyMatchMode,
+    ) -> Result<Self, Self::Error> {
+        match fb_mode {
+            tcrm_task_generated::tcrm::task::ReadyMatchMode::Contains => {
+                Ok(ReadyMatchMode::Contains)
+            }
+            tcrm_task_generated::tcrm::task::ReadyMatchMode::StartsWith => {
+                Ok(ReadyMatchMode::StartsWith)
+            }
+            tcrm_task_generated::tcrm::task::ReadyMatchMode::ExactLine => {
+                Ok(ReadyMatchMode::ExactLine)
+            }
+            _ => Err(ConversionError::InvalidReadyMatchMode(fb_mode.0)),
+        }
+    }
+}
+impl From<ReadyMatchMode> for tcrm_task_generated::tcrm::task::ReadyMatchMode {
+    fn from(mode: ReadyMatchMode) -> Self {
+        match mode {
+            ReadyMatchMode::Contains => tcrm_task_generated::tcrm::task::ReadyMatchMode::Contains,
+            ReadyMatchMode::StartsWith => {
+                tcrm_task_generated::tcrm::task::ReadyMatchMode::StartsWith
+            }
+            ReadyMatchMode::ExactLine => tcrm_task_generated::tcrm::task::ReadyMatchMode::ExactLine,
+        }
+    }
+}
+
+impl FromFlatbuffers<tcrm_task_generated::tcrm::task::OutputTrigger<'_>> for OutputTrigger {
+    fn from_flatbuffers(
+        fb_trigger: tcrm_task_generated::tcrm::task::OutputTrigger<'_>,
+    ) -> Result<Self, ConversionError> {
+        let action = match fb_trigger.action() {
+            tcrm_task_generated::tcrm::task::OutputTriggerAction::Ready => TriggerAction::Ready,
+            tcrm_task_generated::tcrm::task::OutputTriggerAction::FailTask => {
+                TriggerAction::FailTask
+            }
+            tcrm_task_generated::tcrm::task::OutputTriggerAction::EmitEvent => {
+                let name = fb_trigger
+                    .event_name()
+                    .ok_or(ConversionError::MissingRequiredField(
+                        "OutputTrigger.event_name",
+                    ))?;
+                TriggerAction::EmitEvent(name.to_string())
+            }
+            tcrm_task_generated::tcrm::task::OutputTriggerAction::Complete => {
+                TriggerAction::Complete
+            }
+            other => return Err(ConversionError::InvalidTriggerAction(other.0)),
+        };
+        Ok(OutputTrigger {
+            pattern: fb_trigger.pattern().to_string(),
+            source: StreamSource::try_from(fb_trigger.source())?,
+            action,
+        })
+    }
+}
+
+impl<'a> ToFlatbuffers<'a> for OutputTrigger {
+    type Output = flatbuffers::WIPOffset<tcrm_task_generated::tcrm::task::OutputTrigger<'a>>;
+
+    fn to_flatbuffers(&self, builder: &mut flatbuffers::FlatBufferBuilder<'a>) -> Self::Output {
+        let pattern_offset = builder.create_string(&self.pattern);
+        let (action, event_name_offset) = match &self.action {
+            TriggerAction::Ready => (
+                tcrm_task_generated::tcrm::task::OutputTriggerAction::Ready,
+                None,
+            ),
+            TriggerAction::FailTask => (
+                tcrm_task_generated::tcrm::task::OutputTriggerAction::FailTask,
+                None,
+            ),
+            TriggerAction::EmitEvent(name) => (
+                tcrm_task_generated::tcrm::task::OutputTriggerAction::EmitEvent,
+                Some(builder.create_string(name)),
+            ),
+            TriggerAction::Complete => (
+                tcrm_task_generated::tcrm::task::OutputTriggerAction::Complete,
+                None,
+            ),
+        };
+        tcrm_task_generated::tcrm::task::OutputTrigger::create(
+            builder,
+            &tcrm_task_generated::tcrm::task::OutputTriggerArgs {
+                pattern: Some(pattern_offset),
+                source: self.source.clone().into(),
+                action,
+                event_name: event_name_offset,
+            },
+        )
+    }
+}
+
+/// Converts the output triggers of a FlatBuffers config
+fn output_triggers_from_flatbuffers(
+    fb_config: &tcrm_task_generated::tcrm::task::TaskConfig<'_>,
+) -> Result<Option<Vec<OutputTrigger>>, ConversionError> {
+    fb_config
+        .output_triggers()
+        .map(|triggers| {
+            triggers
+                .iter()
+                .map(OutputTrigger::from_flatbuffers)
+                .collect()
+        })
+        .transpose()
+}
+
 impl<'a> TryFrom<tcrm_task_generated::tcrm::task::TaskConfig<'a>> for TaskConfig {
     type Error = ConversionError;
 
@@ -53,7 +165,12 @@ impl<'a> TryFrom<tcrm_task_generated::tcrm::task::TaskConfig<'a>> for TaskConfig
         let ready_indicator = fb_config
             .ready_indicator()
             .map(std::string::ToString::to_string);
+        let ready_indicator_regex = fb_config
+            .ready_indicator_regex()
+            .map(std::string::ToString::to_string);
         let ready_indicator_source = fb_config.ready_indicator_source().try_into().ok();
+        let ready_match_mode = fb_config.ready_match_mode().try_into().ok();
+        let output_triggers = output_triggers_from_flatbuffers(&fb_config)?;
 
         Ok(TaskConfig {
             command,
@@ -69,8 +186,12 @@ impl<'a> TryFrom<tcrm_task_generated::tcrm::task::TaskConfig<'a>> for TaskConfig
             },
             enable_stdin: Some(fb_config.enable_stdin()),
             ready_indicator,
+            ready_indicator_regex,
             ready_indicator_source,
+            ready_match_mode,
             use_process_group: Some(fb_config.use_process_group()),
+            output_triggers,
+            ..Default::default()
         })
     }
 }
@@ -116,14 +237,19 @@ impl FromFlatbuffers<tcrm_task_generated::tcrm::task::TaskConfig<'_>> for TaskCo
         let ready_indicator = fb_config
             .ready_indicator()
             .map(std::string::ToString::to_string);
+        let ready_indicator_regex = fb_config
+            .ready_indicator_regex()
+            .map(std::string::ToString::to_string);
         let ready_indicator_source =
             Some(StreamSource::try_from(fb_config.ready_indicator_source())?);
+        let ready_match_mode = Some(ReadyMatchMode::try_from(fb_config.ready_match_mode())?);
 
         let use_process_group = if fb_config.use_process_group() {
             Some(true)
         } else {
             None
         };
+        let output_triggers = output_triggers_from_flatbuffers(&fb_config)?;
         Ok(TaskConfig {
             command,
             args,
@@ -132,8 +258,12 @@ impl FromFlatbuffers<tcrm_task_generated::tcrm::task::TaskConfig<'_>> for TaskCo
             timeout_ms,
             enable_stdin,
             ready_indicator,
+            ready_indicator_regex,
             ready_indicator_source,
+            ready_match_mode,
             use_process_group,
+            output_triggers,
+            ..Default::default()
         })
     }
 }
@@ -175,6 +305,18 @@ impl<'a> ToFlatbuffers<'a> for TaskConfig {
             .ready_indicator
             .as_ref()
             .map(|s| builder.create_string(s));
+        let ready_indicator_regex_offset = self
+            .ready_indicator_regex
+            .as_ref()
+            .map(|s| builder.create_string(s));
+
+        let output_triggers_vec = self.output_triggers.as_ref().map(|triggers| {
+            let trigger_offsets: Vec<_> = triggers
+                .iter()
+                .map(|trigger| trigger.to_flatbuffers(builder))
+                .collect();
+            builder.create_vector(&trigger_offsets)
+        });
 
         // Build TaskConfig table
         tcrm_task_generated::tcrm::task::TaskConfig::create(
@@ -192,7 +334,10 @@ impl<'a> ToFlatbuffers<'a> for TaskConfig {
                     .clone()
                     .unwrap_or_default()
                     .into(),
+                ready_match_mode: self.ready_match_mode.unwrap_or_default().into(),
                 use_process_group: self.use_process_group.unwrap_or_default(),
+                output_triggers: output_triggers_vec,
+                ready_indicator_regex: ready_indicator_regex_offset,
             },
         )
     }
@@ -3,7 +3,7 @@ use crate::{
         conversion::{FromFlatbuffers, ToFlatbuffers},
         tcrm_task_generated,
     },
-    tasks::error::TaskError,
+    tasks::error::{ChannelKind, ChannelOp, SpawnDiagnosis, TaskError},
 };
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -16,8 +16,14 @@ pub enum ConversionError {
     InvalidTaskEventStopReasonType(i8),
     InvalidTaskEventType(i8),
     InvalidTaskErrorType(i8),
+    InvalidSpawnDiagnosisKind(i8),
+    InvalidChannelKind(i8),
+    InvalidChannelOp(i8),
+    InvalidTriggerAction(i8),
+    InvalidReadyMatchMode(i8),
     MissingRequiredField(&'static str),
     FlatBuffersError(String),
+    InvalidJson(String),
 }
 
 impl std::fmt::Display for ConversionError {
@@ -40,12 +46,28 @@ impl std::fmt::Display for ConversionError {
             ConversionError::InvalidTaskErrorType(val) => {
                 write!(f, "Invalid TaskErrorType value: {val}")
             }
+            ConversionError::InvalidSpawnDiagnosisKind(val) => {
+                write!(f, "Invalid SpawnDiagnosisKind value: {val}")
+            }
+            ConversionError::InvalidChannelKind(val) => {
+                write!(f, "Invalid ChannelKind value: {val}")
+            }
+            ConversionError::InvalidChannelOp(val) => {
+                write!(f, "Invalid ChannelOp value: {val}")
+            }
+            ConversionError::InvalidTriggerAction(val) => {
+                write!(f, "Invalid OutputTriggerAction value: {val}")
+            }
+            ConversionError::InvalidReadyMatchMode(val) => {
+                write!(f, "Invalid ReadyMatchMode value: {val}")
+            }
             ConversionError::MissingRequiredField(field) => {
                 write!(f, "Missing required field: {field}")
             }
             ConversionError::FlatBuffersError(msg) => {
                 write!(f, "FlatBuffers error: {msg}")
             }
+            ConversionError::InvalidJson(msg) => write!(f, "Invalid JSON output line: {msg}"),
         }
     }
 }
@@ -63,36 +85,138 @@ impl FromFlatbuffers<tcrm_task_generated::tcrm::task::TaskError<'_>> for TaskErr
             tcrm_task_generated::tcrm::task::TaskErrorType::Handle => {
                 Ok(TaskError::Handle(message))
             }
-            tcrm_task_generated::tcrm::task::TaskErrorType::Channel => {
-                Ok(TaskError::Channel(message))
-            }
+            tcrm_task_generated::tcrm::task::TaskErrorType::Channel => Ok(TaskError::Channel {
+                kind: fb_error.channel_kind().try_into()?,
+                op: fb_error.channel_op().try_into()?,
+                detail: message,
+            }),
             tcrm_task_generated::tcrm::task::TaskErrorType::InvalidConfiguration => {
                 Ok(TaskError::InvalidConfiguration(message))
             }
+            tcrm_task_generated::tcrm::task::TaskErrorType::Spawn => Ok(TaskError::Spawn {
+                message,
+                diagnosis: SpawnDiagnosis::from_flatbuffers(fb_error)?,
+            }),
+            tcrm_task_generated::tcrm::task::TaskErrorType::CommandNotFound => {
+                Ok(TaskError::CommandNotFound {
+                    command: message,
+                    searched_path: fb_error.searched_path().map(str::to_string),
+                })
+            }
+            tcrm_task_generated::tcrm::task::TaskErrorType::PreExec => Ok(TaskError::PreExec {
+                step: fb_error
+                    .pre_exec_step()
+                    .ok_or(ConversionError::MissingRequiredField("pre_exec_step"))?
+                    .to_string(),
+                error: message,
+            }),
             _ => Err(ConversionError::InvalidTaskErrorType(kind.0)),
         }
     }
 }
 
+impl FromFlatbuffers<tcrm_task_generated::tcrm::task::TaskError<'_>> for SpawnDiagnosis {
+    fn from_flatbuffers(
+        fb_error: tcrm_task_generated::tcrm::task::TaskError<'_>,
+    ) -> Result<Self, ConversionError> {
+        use tcrm_task_generated::tcrm::task::SpawnDiagnosisKind;
+        let path = fb_error
+            .diagnosis_path()
+            .ok_or(ConversionError::MissingRequiredField("diagnosis_path"))?
+            .to_string();
+        let detail = || {
+            fb_error
+                .diagnosis_detail()
+                .map(str::to_string)
+                .ok_or(ConversionError::MissingRequiredField("diagnosis_detail"))
+        };
+
+        match fb_error.diagnosis() {
+            SpawnDiagnosisKind::IsDirectory => Ok(SpawnDiagnosis::IsDirectory { path }),
+            SpawnDiagnosisKind::NotExecutable => Ok(SpawnDiagnosis::NotExecutable { path }),
+            SpawnDiagnosisKind::MissingInterpreter => Ok(SpawnDiagnosis::MissingInterpreter {
+                path,
+                interpreter: detail()?,
+            }),
+            SpawnDiagnosisKind::WrongArchitecture => Ok(SpawnDiagnosis::WrongArchitecture {
+                path,
+                architecture: detail()?,
+            }),
+            kind => Err(ConversionError::InvalidSpawnDiagnosisKind(kind.0)),
+        }
+    }
+}
+
 impl<'a> ToFlatbuffers<'a> for TaskError {
     type Output = flatbuffers::WIPOffset<tcrm_task_generated::tcrm::task::TaskError<'a>>;
 
     fn to_flatbuffers(&self, builder: &mut flatbuffers::FlatBufferBuilder<'a>) -> Self::Output {
+        use tcrm_task_generated::tcrm::task::SpawnDiagnosisKind;
         let message = match self {
             TaskError::IO(msg)
             | TaskError::Handle(msg)
-            | TaskError::Channel(msg)
-            | TaskError::InvalidConfiguration(msg) => msg,
+            | TaskError::Channel { detail: msg, .. }
+            | TaskError::InvalidConfiguration(msg)
+            | TaskError::Spawn { message: msg, .. }
+            | TaskError::CommandNotFound { command: msg, .. }
+            | TaskError::PreExec { error: msg, .. } => msg,
         };
         let msg_offset = builder.create_string(message);
 
         let kind = match self {
             TaskError::IO(_) => tcrm_task_generated::tcrm::task::TaskErrorType::IO,
             TaskError::Handle(_) => tcrm_task_generated::tcrm::task::TaskErrorType::Handle,
-            TaskError::Channel(_) => tcrm_task_generated::tcrm::task::TaskErrorType::Channel,
+            TaskError::Channel { .. } => tcrm_task_generated::tcrm::task::TaskErrorType::Channel,
             TaskError::InvalidConfiguration(_) => {
                 tcrm_task_generated::tcrm::task::TaskErrorType::InvalidConfiguration
             }
+            TaskError::Spawn { .. } => tcrm_task_generated::tcrm::task::TaskErrorType::Spawn,
+            TaskError::CommandNotFound { .. } => {
+                tcrm_task_generated::tcrm::task::TaskErrorType::CommandNotFound
+            }
+            TaskError::PreExec { .. } => tcrm_task_generated::tcrm::task::TaskErrorType::PreExec,
+        };
+
+        let (diagnosis, path, detail) = match self {
+            TaskError::Spawn { diagnosis, .. } => match diagnosis {
+                SpawnDiagnosis::IsDirectory { path } => {
+                    (SpawnDiagnosisKind::IsDirectory, Some(path), None)
+                }
+                SpawnDiagnosis::NotExecutable { path } => {
+                    (SpawnDiagnosisKind::NotExecutable, Some(path), None)
+                }
+                SpawnDiagnosis::MissingInterpreter { path, interpreter } => (
+                    SpawnDiagnosisKind::MissingInterpreter,
+                    Some(path),
+                    Some(interpreter),
+                ),
+                SpawnDiagnosis::WrongArchitecture { path, architecture } => (
+                    SpawnDiagnosisKind::WrongArchitecture,
+                    Some(path),
+                    Some(architecture),
+                ),
+            },
+            _ => (SpawnDiagnosisKind::None, None, None),
+        };
+        let (channel_kind, channel_op) = match self {
+            TaskError::Channel { kind, op, .. } => ((*kind).into(), (*op).into()),
+            _ => (
+                tcrm_task_generated::tcrm::task::ChannelKind::None,
+                tcrm_task_generated::tcrm::task::ChannelOp::None,
+            ),
+        };
+        let diagnosis_path = path.map(|path| builder.create_string(path));
+        let diagnosis_detail = detail.map(|detail| builder.create_string(detail));
+        let searched_path = match self {
+            TaskError::CommandNotFound {
+                searched_path: Some(path),
+                ..
+            } => Some(builder.create_string(path)),
+            _ => None,
+        };
+        let pre_exec_step = match self {
+            TaskError::PreExec { step, .. } => Some(builder.create_string(step)),
+            _ => None,
         };
 
         tcrm_task_generated::tcrm::task::TaskError::create(
@@ -100,7 +224,62 @@ impl<'a> ToFlatbuffers<'a> for TaskError {
             &tcrm_task_generated::tcrm::task::TaskErrorArgs {
                 kind,
                 message: Some(msg_offset),
+                diagnosis,
+                diagnosis_path,
+                diagnosis_detail,
+                channel_kind,
+                channel_op,
+                searched_path,
+                pre_exec_step,
             },
         )
     }
 }
+
+impl TryFrom<tcrm_task_generated::tcrm::task::ChannelKind> for ChannelKind {
+    type Error = ConversionError;
+
+    fn try_from(
+        fb_kind: tcrm_task_generated::tcrm::task::ChannelKind,
+    ) -> Result<Self, Self::Error> {
+        match fb_kind {
+            tcrm_task_generated::tcrm::task::ChannelKind::Event => Ok(ChannelKind::Event),
+            tcrm_task_generated::tcrm::task::ChannelKind::Terminate => Ok(ChannelKind::Terminate),
+            tcrm_task_generated::tcrm::task::ChannelKind::Stdin => Ok(ChannelKind::Stdin),
+            tcrm_task_generated::tcrm::task::ChannelKind::Internal => Ok(ChannelKind::Internal),
+            _ => Err(ConversionError::InvalidChannelKind(fb_kind.0)),
+        }
+    }
+}
+impl From<ChannelKind> for tcrm_task_generated::tcrm::task::ChannelKind {
+    fn from(kind: ChannelKind) -> Self {
+        match kind {
+            ChannelKind::Event => tcrm_task_generated::tcrm::task::ChannelKind::Event,
+            ChannelKind::Terminate => tcrm_task_generated::tcrm::task::ChannelKind::Terminate,
+            ChannelKind::Stdin => tcrm_task_generated::tcrm::task::ChannelKind::Stdin,
+            ChannelKind::Internal => tcrm_task_generated::tcrm::task::ChannelKind::Internal,
+        }
+    }
+}
+
+impl TryFrom<tcrm_task_generated::tcrm::task::ChannelOp> for ChannelOp {
+    type Error = ConversionError;
+
+    fn try_from(fb_op: tcrm_task_generated::tcrm::task::ChannelOp) -> Result<Self, Self::Error> {
+        match fb_op {
+            tcrm_task_generated::tcrm::task::ChannelOp::Send => Ok(ChannelOp::Send),
+            tcrm_task_generated::tcrm::task::ChannelOp::Recv => Ok(ChannelOp::Recv),
+            tcrm_task_generated::tcrm::task::ChannelOp::Closed => Ok(ChannelOp::Closed),
+            _ => Err(ConversionError::InvalidChannelOp(fb_op.0)),
+        }
+    }
+}
+impl From<ChannelOp> for tcrm_task_generated::tcrm::task::ChannelOp {
+    fn from(op: ChannelOp) -> Self {
+        match op {
+            ChannelOp::Send => tcrm_task_generated::tcrm::task::ChannelOp::Send,
+            ChannelOp::Recv => tcrm_task_generated::tcrm::task::ChannelOp::Recv,
+            ChannelOp::Closed => tcrm_task_generated::tcrm::task::ChannelOp::Closed,
+        }
+    }
+}
@@ -2,6 +2,7 @@ use crate::tasks::event::TaskTerminateReason;
 
 use crate::flatbuffers::conversion::ConversionError;
 use crate::flatbuffers::conversion::FromFlatbuffers;
+use crate::flatbuffers::conversion::audit::{from_unix_ms, to_unix_ms};
 use crate::tasks::error::TaskError;
 use crate::{
     flatbuffers::{
@@ -23,33 +24,64 @@ impl<'a>
             flatbuffers::Table<'a>,
         ),
     ) -> Result<Self, ConversionError> {
+        use tcrm_task_generated::tcrm::task::TaskEventStopReason as FbStopReason;
+
         let disc = input.0.0; // .0 to get the u8 discriminant
-        match disc {
-            0 => Ok(TaskEventStopReason::Finished),
-            1 => {
-                // Error
+        match input.0 {
+            FbStopReason::Finished => Ok(TaskEventStopReason::Finished),
+            FbStopReason::Error => {
                 let error_reason = unsafe {
                     tcrm_task_generated::tcrm::task::ErrorStopReason::init_from_table(input.1)
                 };
                 let msg = error_reason.message().to_string();
                 Ok(TaskEventStopReason::Error(msg))
             }
-            2 => Ok(TaskEventStopReason::Terminated(
+            FbStopReason::StartupFailure => {
+                let error_reason = unsafe {
+                    tcrm_task_generated::tcrm::task::ErrorStopReason::init_from_table(input.1)
+                };
+                let msg = error_reason.message().to_string();
+                Ok(TaskEventStopReason::StartupFailure(msg))
+            }
+            FbStopReason::TerminatedTimeout => Ok(TaskEventStopReason::Terminated(
                 TaskTerminateReason::Timeout,
             )),
-            3 => Ok(TaskEventStopReason::Terminated(
-                TaskTerminateReason::Cleanup,
+            FbStopReason::TerminatedCleanup => Ok(TaskEventStopReason::Terminated(
+                TaskTerminateReason::Cleanup {
+                    message: terminated_message(input.1),
+                },
             )),
-            4 => Ok(TaskEventStopReason::Terminated(
+            FbStopReason::TerminatedDependenciesFinished => Ok(TaskEventStopReason::Terminated(
                 TaskTerminateReason::DependenciesFinished,
             )),
-            5 => Ok(TaskEventStopReason::Terminated(
-                TaskTerminateReason::UserRequested,
+            FbStopReason::TerminatedUserRequested => Ok(TaskEventStopReason::Terminated(
+                TaskTerminateReason::UserRequested {
+                    message: terminated_message(input.1),
+                },
+            )),
+            FbStopReason::TerminatedCompletionIndicator => Ok(TaskEventStopReason::Terminated(
+                TaskTerminateReason::CompletionIndicator,
+            )),
+            FbStopReason::TerminatedScriptTimeout => Ok(TaskEventStopReason::Terminated(
+                TaskTerminateReason::ScriptTimeout,
+            )),
+            FbStopReason::TerminatedUnhealthy => Ok(TaskEventStopReason::Terminated(
+                TaskTerminateReason::Unhealthy,
             )),
             _ => Err(ConversionError::InvalidTaskEventStopReasonType(disc as i8)),
         }
     }
 }
+/// Reads the message of a `TerminatedStopReason`
+///
+/// Buffers written before the message was added hold an empty `DummyTable`
+/// instead, which reads as no message.
+fn terminated_message(table: flatbuffers::Table<'_>) -> Option<String> {
+    let reason =
+        unsafe { tcrm_task_generated::tcrm::task::TerminatedStopReason::init_from_table(table) };
+    reason.message().map(str::to_string)
+}
+
 impl<'a> FromFlatbuffers<tcrm_task_generated::tcrm::task::TaskEvent<'a>> for TaskEvent {
     fn from_flatbuffers(
         fb_event: tcrm_task_generated::tcrm::task::TaskEvent<'a>,
@@ -60,37 +92,122 @@ impl<'a> FromFlatbuffers<tcrm_task_generated::tcrm::task::TaskEvent<'a>> for Tas
                 let started = fb_event
                     .event_as_started()
                     .ok_or(ConversionError::MissingRequiredField("StartedEvent"))?;
-                let task_name = started.task_name().to_string();
-                Ok(TaskEvent::Started { task_name })
+                let task_name = started.task_name().into();
+                Ok(TaskEvent::Started {
+                    task_name,
+                    process_id: started.process_id(),
+                    temp_dir: started.temp_dir().map(str::to_string),
+                    working_dir: started.working_dir().map(str::to_string),
+                })
             }
             TaskEventUnion::Output => {
                 let output = fb_event
                     .event_as_output()
                     .ok_or(ConversionError::MissingRequiredField("OutputEvent"))?;
-                let task_name = output.task_name().to_string();
-                let line = output.line().to_string();
+                let task_name = output.task_name().into();
+                let seq = output.seq();
                 let src = output
                     .src()
                     .try_into()
                     .map_err(|_| ConversionError::InvalidStreamSource(output.src().0))?;
+                let line = output.line().to_string();
+                if output.is_json() {
+                    #[cfg(feature = "serde")]
+                    let value = serde_json::from_str(&line)
+                        .map_err(|e| ConversionError::InvalidJson(e.to_string()))?;
+                    return Ok(TaskEvent::OutputJson {
+                        task_name,
+                        line,
+                        #[cfg(feature = "serde")]
+                        value,
+                        src,
+                        seq,
+                        timestamp: from_unix_ms(output.timestamp_ms()),
+                    });
+                }
                 Ok(TaskEvent::Output {
                     task_name,
                     line,
                     src,
+                    seq,
+                    truncated: output.truncated(),
+                    timestamp: from_unix_ms(output.timestamp_ms()),
+                })
+            }
+            TaskEventUnion::OutputChunk => {
+                let chunk = fb_event
+                    .event_as_output_chunk()
+                    .ok_or(ConversionError::MissingRequiredField("OutputChunkEvent"))?;
+                let src = chunk
+                    .src()
+                    .try_into()
+                    .map_err(|_| ConversionError::InvalidStreamSource(chunk.src().0))?;
+                Ok(TaskEvent::OutputChunk {
+                    task_name: chunk.task_name().into(),
+                    data: chunk
+                        .data()
+                        .map(|data| data.bytes().to_vec())
+                        .unwrap_or_default(),
+                    src,
+                })
+            }
+            TaskEventUnion::OutputRepeated => {
+                let repeated = fb_event
+                    .event_as_output_repeated()
+                    .ok_or(ConversionError::MissingRequiredField("OutputRepeatedEvent"))?;
+                let src = repeated
+                    .src()
+                    .try_into()
+                    .map_err(|_| ConversionError::InvalidStreamSource(repeated.src().0))?;
+                Ok(TaskEvent::OutputRepeated {
+                    task_name: repeated.task_name().into(),
+                    line: repeated.line().to_string(),
+                    src,
+                    count: repeated.count(),
+                })
+            }
+            TaskEventUnion::OutputSuppressed => {
+                let suppressed = fb_event.event_as_output_suppressed().ok_or(
+                    ConversionError::MissingRequiredField("OutputSuppressedEvent"),
+                )?;
+                let src = suppressed
+                    .src()
+                    .try_into()
+                    .map_err(|_| ConversionError::InvalidStreamSource(suppressed.src().0))?;
+                Ok(TaskEvent::OutputSuppressed {
+                    task_name: suppressed.task_name().into(),
+                    src,
+                    count: suppressed.count(),
+                    bytes: suppressed.bytes(),
+                })
+            }
+            TaskEventUnion::Triggered => {
+                let triggered = fb_event
+                    .event_as_triggered()
+                    .ok_or(ConversionError::MissingRequiredField("TriggeredEvent"))?;
+                let src = triggered
+                    .src()
+                    .try_into()
+                    .map_err(|_| ConversionError::InvalidStreamSource(triggered.src().0))?;
+                Ok(TaskEvent::Triggered {
+                    task_name: triggered.task_name().into(),
+                    name: triggered.name().to_string(),
+                    line: triggered.line().to_string(),
+                    src,
                 })
             }
             TaskEventUnion::Ready => {
                 let ready = fb_event
                     .event_as_ready()
                     .ok_or(ConversionError::MissingRequiredField("ReadyEvent"))?;
-                let task_name = ready.task_name().to_string();
+                let task_name = ready.task_name().into();
                 Ok(TaskEvent::Ready { task_name })
             }
             TaskEventUnion::Stopped => {
                 let stopped = fb_event
                     .event_as_stopped()
                     .ok_or(ConversionError::MissingRequiredField("StoppedEvent"))?;
-                let task_name = stopped.task_name().to_string();
+                let task_name = stopped.task_name().into();
                 let exit_code = Some(stopped.exit_code());
                 let fb_reason_type = stopped.reason_type();
                 let fb_reason_table = stopped.reason();
@@ -99,14 +216,79 @@ impl<'a> FromFlatbuffers<tcrm_task_generated::tcrm::task::TaskEvent<'a>> for Tas
                 Ok(TaskEvent::Stopped {
                     task_name,
                     exit_code,
+                    signal: stopped.signal(),
                     reason,
+                    was_ready: stopped.was_ready(),
+                    duration_ms: stopped.duration_ms(),
+                    expected_duration_ms: stopped.expected_duration_ms(),
+                    exit_meaning: stopped.exit_meaning().map(str::to_string),
+                    from_cache: stopped.from_cache(),
+                    stdout_sha256: stopped.stdout_sha256().map(str::to_string),
+                    stderr_sha256: stopped.stderr_sha256().map(str::to_string),
+                })
+            }
+            TaskEventUnion::RunningLong => {
+                let running_long = fb_event
+                    .event_as_running_long()
+                    .ok_or(ConversionError::MissingRequiredField("RunningLongEvent"))?;
+                Ok(TaskEvent::RunningLong {
+                    task_name: running_long.task_name().into(),
+                    expected_ms: running_long.expected_ms(),
+                    elapsed_ms: running_long.elapsed_ms(),
+                })
+            }
+            TaskEventUnion::MemoryWatermarkExceeded => {
+                let watermark = fb_event.event_as_memory_watermark_exceeded().ok_or(
+                    ConversionError::MissingRequiredField("MemoryWatermarkExceededEvent"),
+                )?;
+                Ok(TaskEvent::MemoryWatermarkExceeded {
+                    task_name: watermark.task_name().into(),
+                    rss_bytes: watermark.rss_bytes(),
+                    watermark_bytes: watermark.watermark_bytes(),
+                })
+            }
+            TaskEventUnion::SpawnRetry => {
+                let retry = fb_event
+                    .event_as_spawn_retry()
+                    .ok_or(ConversionError::MissingRequiredField("SpawnRetryEvent"))?;
+                Ok(TaskEvent::SpawnRetry {
+                    task_name: retry.task_name().into(),
+                    attempt: retry.attempt(),
+                    error: retry.error().to_string(),
+                })
+            }
+            TaskEventUnion::ScriptStep => {
+                let step = fb_event
+                    .event_as_script_step()
+                    .ok_or(ConversionError::MissingRequiredField("ScriptStepEvent"))?;
+                Ok(TaskEvent::ScriptStep {
+                    task_name: step.task_name().into(),
+                    index: step.index(),
+                })
+            }
+            TaskEventUnion::Unhealthy => {
+                let unhealthy = fb_event
+                    .event_as_unhealthy()
+                    .ok_or(ConversionError::MissingRequiredField("UnhealthyEvent"))?;
+                Ok(TaskEvent::Unhealthy {
+                    task_name: unhealthy.task_name().into(),
+                    consecutive_failures: unhealthy.consecutive_failures(),
+                })
+            }
+            TaskEventUnion::SlowConsumer => {
+                let slow = fb_event
+                    .event_as_slow_consumer()
+                    .ok_or(ConversionError::MissingRequiredField("SlowConsumerEvent"))?;
+                Ok(TaskEvent::SlowConsumer {
+                    task_name: slow.task_name().into(),
+                    avg_wait_ms: slow.avg_wait_ms(),
                 })
             }
             TaskEventUnion::Error => {
                 let error_event = fb_event
                     .event_as_error()
                     .ok_or(ConversionError::MissingRequiredField("ErrorEvent"))?;
-                let task_name = error_event.task_name().to_string();
+                let task_name = error_event.task_name().into();
                 let fb_error = error_event.error();
                 let error = TaskError::from_flatbuffers(fb_error)?;
                 Ok(TaskEvent::Error { task_name, error })
@@ -124,12 +306,23 @@ impl<'a> ToFlatbuffers<'a> for TaskEvent {
         builder: &mut flatbuffers::FlatBufferBuilder<'a>,
     ) -> <Self as ToFlatbuffers<'a>>::Output {
         let (event_type, event_offset) = match self {
-            TaskEvent::Started { task_name } => {
+            TaskEvent::Started {
+                task_name,
+                process_id,
+                temp_dir,
+                working_dir,
+            } => {
                 let name_offset = builder.create_string(task_name);
+                let temp_dir_offset = temp_dir.as_deref().map(|dir| builder.create_string(dir));
+                let working_dir_offset =
+                    working_dir.as_deref().map(|dir| builder.create_string(dir));
                 let started = tcrm_task_generated::tcrm::task::StartedEvent::create(
                     builder,
                     &tcrm_task_generated::tcrm::task::StartedEventArgs {
                         task_name: Some(name_offset),
+                        process_id: *process_id,
+                        temp_dir: temp_dir_offset,
+                        working_dir: working_dir_offset,
                     },
                 );
                 (
@@ -141,6 +334,37 @@ impl<'a> ToFlatbuffers<'a> for TaskEvent {
                 task_name,
                 line,
                 src,
+                seq,
+                truncated,
+                timestamp,
+            } => {
+                let name_offset = builder.create_string(task_name);
+                let line_offset = builder.create_string(line);
+                let fb_src: tcrm_task_generated::tcrm::task::StreamSource = src.clone().into();
+                let output = tcrm_task_generated::tcrm::task::OutputEvent::create(
+                    builder,
+                    &tcrm_task_generated::tcrm::task::OutputEventArgs {
+                        task_name: Some(name_offset),
+                        line: Some(line_offset),
+                        src: fb_src,
+                        is_json: false,
+                        seq: *seq,
+                        truncated: *truncated,
+                        timestamp_ms: to_unix_ms(*timestamp),
+                    },
+                );
+                (
+                    tcrm_task_generated::tcrm::task::TaskEventUnion::Output,
+                    output.as_union_value(),
+                )
+            }
+            TaskEvent::OutputJson {
+                task_name,
+                line,
+                src,
+                seq,
+                timestamp,
+                ..
             } => {
                 let name_offset = builder.create_string(task_name);
                 let line_offset = builder.create_string(line);
@@ -151,6 +375,10 @@ impl<'a> ToFlatbuffers<'a> for TaskEvent {
                         task_name: Some(name_offset),
                         line: Some(line_offset),
                         src: fb_src,
+                        is_json: true,
+                        seq: *seq,
+                        truncated: false,
+                        timestamp_ms: to_unix_ms(*timestamp),
                     },
                 );
                 (
@@ -158,6 +386,27 @@ impl<'a> ToFlatbuffers<'a> for TaskEvent {
                     output.as_union_value(),
                 )
             }
+            TaskEvent::OutputChunk {
+                task_name,
+                data,
+                src,
+            } => {
+                let name_offset = builder.create_string(task_name);
+                let data_offset = builder.create_vector(data);
+                let fb_src: tcrm_task_generated::tcrm::task::StreamSource = src.clone().into();
+                let chunk = tcrm_task_generated::tcrm::task::OutputChunkEvent::create(
+                    builder,
+                    &tcrm_task_generated::tcrm::task::OutputChunkEventArgs {
+                        task_name: Some(name_offset),
+                        data: Some(data_offset),
+                        src: fb_src,
+                    },
+                );
+                (
+                    tcrm_task_generated::tcrm::task::TaskEventUnion::OutputChunk,
+                    chunk.as_union_value(),
+                )
+            }
             TaskEvent::Ready { task_name } => {
                 let name_offset = builder.create_string(task_name);
                 let ready = tcrm_task_generated::tcrm::task::ReadyEvent::create(
@@ -174,17 +423,42 @@ impl<'a> ToFlatbuffers<'a> for TaskEvent {
             TaskEvent::Stopped {
                 task_name,
                 exit_code,
+                signal,
                 reason,
+                was_ready,
+                duration_ms,
+                expected_duration_ms,
+                exit_meaning,
+                from_cache,
+                stdout_sha256,
+                stderr_sha256,
             } => {
                 let name_offset = builder.create_string(task_name);
-                let (_, stop_reason_offset) = reason.to_flatbuffers_union(builder);
+                let (stop_reason_type, stop_reason_offset) = reason.to_flatbuffers_union(builder);
+                let exit_meaning_offset = exit_meaning
+                    .as_deref()
+                    .map(|meaning| builder.create_string(meaning));
+                let stdout_sha256_offset = stdout_sha256
+                    .as_deref()
+                    .map(|digest| builder.create_string(digest));
+                let stderr_sha256_offset = stderr_sha256
+                    .as_deref()
+                    .map(|digest| builder.create_string(digest));
                 let stopped = tcrm_task_generated::tcrm::task::StoppedEvent::create(
                     builder,
                     &tcrm_task_generated::tcrm::task::StoppedEventArgs {
                         task_name: Some(name_offset),
                         exit_code: exit_code.unwrap_or(0),
-                        reason_type: tcrm_task_generated::tcrm::task::TaskEventStopReason::Finished,
+                        reason_type: stop_reason_type,
                         reason: Some(stop_reason_offset),
+                        was_ready: *was_ready,
+                        duration_ms: *duration_ms,
+                        expected_duration_ms: *expected_duration_ms,
+                        exit_meaning: exit_meaning_offset,
+                        from_cache: *from_cache,
+                        stdout_sha256: stdout_sha256_offset,
+                        stderr_sha256: stderr_sha256_offset,
+                        signal: *signal,
                     },
                 );
                 (
@@ -192,6 +466,182 @@ impl<'a> ToFlatbuffers<'a> for TaskEvent {
                     stopped.as_union_value(),
                 )
             }
+            TaskEvent::OutputRepeated {
+                task_name,
+                line,
+                src,
+                count,
+            } => {
+                let name_offset = builder.create_string(task_name);
+                let line_offset = builder.create_string(line);
+                let fb_src: tcrm_task_generated::tcrm::task::StreamSource = src.clone().into();
+                let repeated = tcrm_task_generated::tcrm::task::OutputRepeatedEvent::create(
+                    builder,
+                    &tcrm_task_generated::tcrm::task::OutputRepeatedEventArgs {
+                        task_name: Some(name_offset),
+                        line: Some(line_offset),
+                        src: fb_src,
+                        count: *count,
+                    },
+                );
+                (
+                    tcrm_task_generated::tcrm::task::TaskEventUnion::OutputRepeated,
+                    repeated.as_union_value(),
+                )
+            }
+            TaskEvent::OutputSuppressed {
+                task_name,
+                src,
+                count,
+                bytes,
+            } => {
+                let name_offset = builder.create_string(task_name);
+                let fb_src: tcrm_task_generated::tcrm::task::StreamSource = src.clone().into();
+                let suppressed = tcrm_task_generated::tcrm::task::OutputSuppressedEvent::create(
+                    builder,
+                    &tcrm_task_generated::tcrm::task::OutputSuppressedEventArgs {
+                        task_name: Some(name_offset),
+                        src: fb_src,
+                        count: *count,
+                        bytes: *bytes,
+                    },
+                );
+                (
+                    tcrm_task_generated::tcrm::task::TaskEventUnion::OutputSuppressed,
+                    suppressed.as_union_value(),
+                )
+            }
+            TaskEvent::Triggered {
+                task_name,
+                name,
+                line,
+                src,
+            } => {
+                let task_name_offset = builder.create_string(task_name);
+                let name_offset = builder.create_string(name);
+                let line_offset = builder.create_string(line);
+                let fb_src: tcrm_task_generated::tcrm::task::StreamSource = src.clone().into();
+                let triggered = tcrm_task_generated::tcrm::task::TriggeredEvent::create(
+                    builder,
+                    &tcrm_task_generated::tcrm::task::TriggeredEventArgs {
+                        task_name: Some(task_name_offset),
+                        name: Some(name_offset),
+                        line: Some(line_offset),
+                        src: fb_src,
+                    },
+                );
+                (
+                    tcrm_task_generated::tcrm::task::TaskEventUnion::Triggered,
+                    triggered.as_union_value(),
+                )
+            }
+            TaskEvent::RunningLong {
+                task_name,
+                expected_ms,
+                elapsed_ms,
+            } => {
+                let name_offset = builder.create_string(task_name);
+                let running_long = tcrm_task_generated::tcrm::task::RunningLongEvent::create(
+                    builder,
+                    &tcrm_task_generated::tcrm::task::RunningLongEventArgs {
+                        task_name: Some(name_offset),
+                        expected_ms: *expected_ms,
+                        elapsed_ms: *elapsed_ms,
+                    },
+                );
+                (
+                    tcrm_task_generated::tcrm::task::TaskEventUnion::RunningLong,
+                    running_long.as_union_value(),
+                )
+            }
+            TaskEvent::MemoryWatermarkExceeded {
+                task_name,
+                rss_bytes,
+                watermark_bytes,
+            } => {
+                let name_offset = builder.create_string(task_name);
+                let watermark =
+                    tcrm_task_generated::tcrm::task::MemoryWatermarkExceededEvent::create(
+                        builder,
+                        &tcrm_task_generated::tcrm::task::MemoryWatermarkExceededEventArgs {
+                            task_name: Some(name_offset),
+                            rss_bytes: *rss_bytes,
+                            watermark_bytes: *watermark_bytes,
+                        },
+                    );
+                (
+                    tcrm_task_generated::tcrm::task::TaskEventUnion::MemoryWatermarkExceeded,
+                    watermark.as_union_value(),
+                )
+            }
+            TaskEvent::SpawnRetry {
+                task_name,
+                attempt,
+                error,
+            } => {
+                let name_offset = builder.create_string(task_name);
+                let error_offset = builder.create_string(error);
+                let retry = tcrm_task_generated::tcrm::task::SpawnRetryEvent::create(
+                    builder,
+                    &tcrm_task_generated::tcrm::task::SpawnRetryEventArgs {
+                        task_name: Some(name_offset),
+                        attempt: *attempt,
+                        error: Some(error_offset),
+                    },
+                );
+                (
+                    tcrm_task_generated::tcrm::task::TaskEventUnion::SpawnRetry,
+                    retry.as_union_value(),
+                )
+            }
+            TaskEvent::ScriptStep { task_name, index } => {
+                let name_offset = builder.create_string(task_name);
+                let step = tcrm_task_generated::tcrm::task::ScriptStepEvent::create(
+                    builder,
+                    &tcrm_task_generated::tcrm::task::ScriptStepEventArgs {
+                        task_name: Some(name_offset),
+                        index: *index,
+                    },
+                );
+                (
+                    tcrm_task_generated::tcrm::task::TaskEventUnion::ScriptStep,
+                    step.as_union_value(),
+                )
+            }
+            TaskEvent::Unhealthy {
+                task_name,
+                consecutive_failures,
+            } => {
+                let name_offset = builder.create_string(task_name);
+                let unhealthy = tcrm_task_generated::tcrm::task::UnhealthyEvent::create(
+                    builder,
+                    &tcrm_task_generated::tcrm::task::UnhealthyEventArgs {
+                        task_name: Some(name_offset),
+                        consecutive_failures: *consecutive_failures,
+                    },
+                );
+                (
+                    tcrm_task_generated::tcrm::task::TaskEventUnion::Unhealthy,
+                    unhealthy.as_union_value(),
+                )
+            }
+            TaskEvent::SlowConsumer {
+                task_name,
+                avg_wait_ms,
+            } => {
+                let name_offset = builder.create_string(task_name);
+                let slow = tcrm_task_generated::tcrm::task::SlowConsumerEvent::create(
+                    builder,
+                    &tcrm_task_generated::tcrm::task::SlowConsumerEventArgs {
+                        task_name: Some(name_offset),
+                        avg_wait_ms: *avg_wait_ms,
+                    },
+                );
+                (
+                    tcrm_task_generated::tcrm::task::TaskEventUnion::SlowConsumer,
+                    slow.as_union_value(),
+                )
+            }
             TaskEvent::Error { task_name, error } => {
                 let name_offset = builder.create_string(task_name);
                 let error_offset = error.to_flatbuffers(builder);
@@ -257,6 +707,19 @@ impl<'a> ToFlatbuffersUnion<'a, tcrm_task_generated::tcrm::task::TaskEventStopRe
                     error_reason.as_union_value(),
                 )
             }
+            TaskEventStopReason::StartupFailure(message) => {
+                let msg_offset = builder.create_string(message);
+                let error_reason = tcrm_task_generated::tcrm::task::ErrorStopReason::create(
+                    builder,
+                    &tcrm_task_generated::tcrm::task::ErrorStopReasonArgs {
+                        message: Some(msg_offset),
+                    },
+                );
+                (
+                    tcrm_task_generated::tcrm::task::TaskEventStopReason::StartupFailure,
+                    error_reason.as_union_value(),
+                )
+            }
         }
     }
 }
@@ -6,9 +6,11 @@
 //!
 //! # Available Conversions
 //!
+//! - **audit**: `AuditRecord` summaries of task executions
 //! - **config**: `TaskConfig` and related configuration types
 //! - **error**: `TaskError` and error handling types  
 //! - **event**: `TaskEvent` and all event variants
+//! - **plan**: `ExecutionPlan` dry-run results
 //! - **state**: `TaskState` and `TaskTerminateReason` types
 //!
 //! # Conversion Pattern
@@ -36,9 +38,11 @@
 //! This ensures type-safe, efficient serialization with proper error handling
 //! for malformed data or version mismatches.
 
+pub mod audit;
 pub mod config;
 pub mod error;
 pub mod event;
+pub mod plan;
 pub mod state;
 
 #[cfg(test)]
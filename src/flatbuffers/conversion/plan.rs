@@ -0,0 +1,88 @@
+use crate::{
+    flatbuffers::{
+        conversion::{FromFlatbuffers, ToFlatbuffers, error::ConversionError},
+        tcrm_task_generated,
+    },
+    tasks::plan::ExecutionPlan,
+};
+
+impl FromFlatbuffers<tcrm_task_generated::tcrm::task::ExecutionPlan<'_>> for ExecutionPlan {
+    fn from_flatbuffers(
+        fb_plan: tcrm_task_generated::tcrm::task::ExecutionPlan<'_>,
+    ) -> Result<Self, ConversionError> {
+        let args = fb_plan
+            .args()
+            .map(|args_vec| {
+                args_vec
+                    .iter()
+                    .map(std::string::ToString::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let env_overrides = fb_plan
+            .env_overrides()
+            .map(|env_vec| {
+                env_vec
+                    .iter()
+                    .map(|entry| (entry.key().to_string(), entry.value().to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(ExecutionPlan {
+            command: fb_plan.command().to_string(),
+            program: fb_plan.program().to_string(),
+            args,
+            working_dir: fb_plan.working_dir().to_string(),
+            env_overrides,
+            timeout_ms: fb_plan.timeout_ms(),
+            use_process_group: fb_plan.use_process_group(),
+            enable_stdin: fb_plan.enable_stdin(),
+        })
+    }
+}
+
+impl<'a> ToFlatbuffers<'a> for ExecutionPlan {
+    type Output = flatbuffers::WIPOffset<tcrm_task_generated::tcrm::task::ExecutionPlan<'a>>;
+
+    fn to_flatbuffers(&self, builder: &mut flatbuffers::FlatBufferBuilder<'a>) -> Self::Output {
+        let command_offset = builder.create_string(&self.command);
+        let program_offset = builder.create_string(&self.program);
+        let working_dir_offset = builder.create_string(&self.working_dir);
+
+        let args_offsets: Vec<_> = self.args.iter().map(|s| builder.create_string(s)).collect();
+        let args_vec = builder.create_vector(&args_offsets);
+
+        let env_offsets: Vec<_> = self
+            .env_overrides
+            .iter()
+            .map(|(k, v)| {
+                let k_off = builder.create_string(k);
+                let v_off = builder.create_string(v);
+                tcrm_task_generated::tcrm::task::EnvEntry::create(
+                    builder,
+                    &tcrm_task_generated::tcrm::task::EnvEntryArgs {
+                        key: Some(k_off),
+                        value: Some(v_off),
+                    },
+                )
+            })
+            .collect();
+        let env_vec = builder.create_vector(&env_offsets);
+
+        tcrm_task_generated::tcrm::task::ExecutionPlan::create(
+            builder,
+            &tcrm_task_generated::tcrm::task::ExecutionPlanArgs {
+                command: Some(command_offset),
+                program: Some(program_offset),
+                args: Some(args_vec),
+                working_dir: Some(working_dir_offset),
+                env_overrides: Some(env_vec),
+                timeout_ms: self.timeout_ms,
+                use_process_group: self.use_process_group,
+                enable_stdin: self.enable_stdin,
+            },
+        )
+    }
+}
@@ -44,31 +44,53 @@ impl TryFrom<tcrm_task_generated::tcrm::task::TaskTerminateReason> for TaskTermi
                 Ok(TaskTerminateReason::Timeout)
             }
             tcrm_task_generated::tcrm::task::TaskTerminateReason::Cleanup => {
-                Ok(TaskTerminateReason::Cleanup)
+                Ok(TaskTerminateReason::Cleanup { message: None })
             }
             tcrm_task_generated::tcrm::task::TaskTerminateReason::DependenciesFinished => {
                 Ok(TaskTerminateReason::DependenciesFinished)
             }
+            tcrm_task_generated::tcrm::task::TaskTerminateReason::UserRequested => {
+                Ok(TaskTerminateReason::UserRequested { message: None })
+            }
+            tcrm_task_generated::tcrm::task::TaskTerminateReason::CompletionIndicator => {
+                Ok(TaskTerminateReason::CompletionIndicator)
+            }
+            tcrm_task_generated::tcrm::task::TaskTerminateReason::ScriptTimeout => {
+                Ok(TaskTerminateReason::ScriptTimeout)
+            }
+            tcrm_task_generated::tcrm::task::TaskTerminateReason::Unhealthy => {
+                Ok(TaskTerminateReason::Unhealthy)
+            }
             _ => Err(ConversionError::InvalidTaskTerminateReasonType(fb_reason.0)),
         }
     }
 }
 
+/// Drops the message of `Cleanup` and `UserRequested`, which the enum cannot hold
 impl From<TaskTerminateReason> for tcrm_task_generated::tcrm::task::TaskTerminateReason {
     fn from(reason: TaskTerminateReason) -> Self {
         match reason {
             TaskTerminateReason::Timeout => {
                 tcrm_task_generated::tcrm::task::TaskTerminateReason::Timeout
             }
-            TaskTerminateReason::Cleanup => {
+            TaskTerminateReason::Cleanup { .. } => {
                 tcrm_task_generated::tcrm::task::TaskTerminateReason::Cleanup
             }
             TaskTerminateReason::DependenciesFinished => {
                 tcrm_task_generated::tcrm::task::TaskTerminateReason::DependenciesFinished
             }
-            TaskTerminateReason::UserRequested => {
+            TaskTerminateReason::UserRequested { .. } => {
                 tcrm_task_generated::tcrm::task::TaskTerminateReason::UserRequested
             }
+            TaskTerminateReason::CompletionIndicator => {
+                tcrm_task_generated::tcrm::task::TaskTerminateReason::CompletionIndicator
+            }
+            TaskTerminateReason::ScriptTimeout => {
+                tcrm_task_generated::tcrm::task::TaskTerminateReason::ScriptTimeout
+            }
+            TaskTerminateReason::Unhealthy => {
+                tcrm_task_generated::tcrm::task::TaskTerminateReason::Unhealthy
+            }
         }
     }
 }
@@ -94,10 +116,11 @@ impl<'a> ToFlatbuffersUnion<'a, tcrm_task_generated::tcrm::task::TaskEventStopRe
                     r.as_union_value(),
                 )
             }
-            TaskTerminateReason::Cleanup => {
-                let r = tcrm_task_generated::tcrm::task::DummyTable::create(
+            TaskTerminateReason::Cleanup { message } => {
+                let message = message.as_deref().map(|m| builder.create_string(m));
+                let r = tcrm_task_generated::tcrm::task::TerminatedStopReason::create(
                     builder,
-                    &tcrm_task_generated::tcrm::task::DummyTableArgs {},
+                    &tcrm_task_generated::tcrm::task::TerminatedStopReasonArgs { message },
                 );
                 (
                     tcrm_task_generated::tcrm::task::TaskEventStopReason::TerminatedCleanup,
@@ -114,13 +137,44 @@ impl<'a> ToFlatbuffersUnion<'a, tcrm_task_generated::tcrm::task::TaskEventStopRe
                             r.as_union_value(),
                         )
             }
-            TaskTerminateReason::UserRequested => {
+            TaskTerminateReason::UserRequested { message } => {
+                let message = message.as_deref().map(|m| builder.create_string(m));
+                let r = tcrm_task_generated::tcrm::task::TerminatedStopReason::create(
+                    builder,
+                    &tcrm_task_generated::tcrm::task::TerminatedStopReasonArgs { message },
+                );
+                (
+                    tcrm_task_generated::tcrm::task::TaskEventStopReason::TerminatedUserRequested,
+                    r.as_union_value(),
+                )
+            }
+            TaskTerminateReason::CompletionIndicator => {
                 let r = tcrm_task_generated::tcrm::task::DummyTable::create(
                     builder,
                     &tcrm_task_generated::tcrm::task::DummyTableArgs {},
                 );
                 (
-                    tcrm_task_generated::tcrm::task::TaskEventStopReason::TerminatedUserRequested,
+                    tcrm_task_generated::tcrm::task::TaskEventStopReason::TerminatedCompletionIndicator,
+                    r.as_union_value(),
+                )
+            }
+            TaskTerminateReason::ScriptTimeout => {
+                let r = tcrm_task_generated::tcrm::task::DummyTable::create(
+                    builder,
+                    &tcrm_task_generated::tcrm::task::DummyTableArgs {},
+                );
+                (
+                    tcrm_task_generated::tcrm::task::TaskEventStopReason::TerminatedScriptTimeout,
+                    r.as_union_value(),
+                )
+            }
+            TaskTerminateReason::Unhealthy => {
+                let r = tcrm_task_generated::tcrm::task::DummyTable::create(
+                    builder,
+                    &tcrm_task_generated::tcrm::task::DummyTableArgs {},
+                );
+                (
+                    tcrm_task_generated::tcrm::task::TaskEventStopReason::TerminatedUnhealthy,
                     r.as_union_value(),
                 )
             }
@@ -0,0 +1,81 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::{
+    flatbuffers::{
+        conversion::{FromFlatbuffers, ToFlatbuffers},
+        tcrm_task_generated,
+    },
+    tasks::{
+        audit::AuditRecord,
+        event::{TaskEventStopReason, TaskTerminateReason},
+    },
+};
+
+fn roundtrip_record(record: &AuditRecord) -> AuditRecord {
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let offset = record.to_flatbuffers(&mut builder);
+    builder.finish(offset, None);
+    let bytes = builder.finished_data();
+
+    let fb_record =
+        flatbuffers::root::<tcrm_task_generated::tcrm::task::AuditRecord>(bytes).unwrap();
+    AuditRecord::from_flatbuffers(fb_record).unwrap()
+}
+
+#[test]
+fn roundtrip() {
+    let started_at = UNIX_EPOCH + Duration::from_millis(1_792_143_000_005);
+    let record = AuditRecord {
+        task_name: "deploy".to_string(),
+        command: "/usr/bin/curl".to_string(),
+        args: vec!["--token".to_string(), "[REDACTED]".to_string()],
+        working_dir: "/srv/app".to_string(),
+        env_keys: vec!["API_TOKEN".to_string(), "RUST_LOG".to_string()],
+        process_id: Some(4242),
+        started_at,
+        stopped_at: started_at + Duration::from_millis(1500),
+        exit_code: None,
+        signal: None,
+        reason: TaskEventStopReason::Terminated(TaskTerminateReason::UserRequested {
+            message: None,
+        }),
+        output_bytes: 12_345,
+        terminate_requests: vec![
+            TaskTerminateReason::UserRequested { message: None },
+            TaskTerminateReason::Cleanup { message: None },
+        ],
+    };
+
+    assert_eq!(roundtrip_record(&record), record);
+}
+
+#[test]
+fn roundtrip_exit_code_and_signal() {
+    for (exit_code, signal, reason) in [
+        (Some(0), None, TaskEventStopReason::Finished),
+        (Some(2), None, TaskEventStopReason::Finished),
+        (
+            None,
+            Some(9),
+            TaskEventStopReason::Error("Process killed by signal SIGKILL (9)".to_string()),
+        ),
+    ] {
+        let record = AuditRecord {
+            task_name: "build".to_string(),
+            command: "make".to_string(),
+            args: vec![],
+            working_dir: "/src".to_string(),
+            env_keys: vec![],
+            process_id: None,
+            started_at: UNIX_EPOCH,
+            stopped_at: UNIX_EPOCH + Duration::from_secs(60),
+            exit_code,
+            signal,
+            reason,
+            output_bytes: 0,
+            terminate_requests: vec![],
+        };
+
+        assert_eq!(roundtrip_record(&record), record);
+    }
+}
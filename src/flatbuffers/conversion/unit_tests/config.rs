@@ -2,10 +2,10 @@ use std::collections::HashMap;
 
 use crate::{
     flatbuffers::{
-        conversion::{ConversionError, ToFlatbuffers},
+        conversion::{ConversionError, FromFlatbuffers, ToFlatbuffers},
         tcrm_task_generated,
     },
-    tasks::config::{StreamSource, TaskConfig},
+    tasks::config::{OutputTrigger, ReadyMatchMode, StreamSource, TaskConfig, TriggerAction},
 };
 
 #[test]
@@ -178,6 +178,130 @@ fn unicode_strings() {
     assert_eq!(converted.ready_indicator.unwrap(), "准备就绪(พร้อมทำงาน)");
 }
 
+#[test]
+fn output_triggers_roundtrip() {
+    let config = TaskConfig::new("test")
+        .output_trigger(OutputTrigger::new(
+            "Listening",
+            StreamSource::Stdout,
+            TriggerAction::Ready,
+        ))
+        .output_trigger(OutputTrigger::new(
+            "FATAL",
+            StreamSource::Stderr,
+            TriggerAction::FailTask,
+        ))
+        .output_trigger(OutputTrigger::new(
+            "Reloaded",
+            StreamSource::Stdout,
+            TriggerAction::EmitEvent("reload".to_string()),
+        ))
+        .exit_on_output_match("ALL DONE");
+
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let fb_config = config.to_flatbuffers(&mut builder);
+    builder.finish(fb_config, None);
+    let bytes = builder.finished_data();
+    let fb_config =
+        flatbuffers::root::<tcrm_task_generated::tcrm::task::TaskConfig>(bytes).unwrap();
+
+    assert_eq!(
+        TaskConfig::try_from(fb_config).unwrap().output_triggers,
+        config.output_triggers
+    );
+    assert_eq!(
+        TaskConfig::from_flatbuffers(fb_config)
+            .unwrap()
+            .output_triggers,
+        config.output_triggers
+    );
+}
+
+#[test]
+fn ready_match_mode_roundtrip() {
+    for mode in [
+        ReadyMatchMode::Contains,
+        ReadyMatchMode::StartsWith,
+        ReadyMatchMode::ExactLine,
+    ] {
+        let config = TaskConfig::new("test")
+            .ready_indicator("Listening on")
+            .ready_match_mode(mode);
+
+        let mut builder = flatbuffers::FlatBufferBuilder::new();
+        let fb_config = config.to_flatbuffers(&mut builder);
+        builder.finish(fb_config, None);
+        let bytes = builder.finished_data();
+        let fb_config =
+            flatbuffers::root::<tcrm_task_generated::tcrm::task::TaskConfig>(bytes).unwrap();
+
+        assert_eq!(
+            TaskConfig::try_from(fb_config).unwrap().ready_match_mode,
+            Some(mode)
+        );
+        assert_eq!(
+            TaskConfig::from_flatbuffers(fb_config)
+                .unwrap()
+                .ready_match_mode,
+            Some(mode)
+        );
+    }
+
+    let result = ReadyMatchMode::try_from(tcrm_task_generated::tcrm::task::ReadyMatchMode(99));
+    assert!(matches!(
+        result,
+        Err(ConversionError::InvalidReadyMatchMode(99))
+    ));
+}
+
+#[test]
+fn ready_indicator_regex_roundtrip() {
+    let config = TaskConfig::new("test")
+        .ready_indicator_regex(r"^Listening on \S+:\d+$")
+        .ready_indicator_source(StreamSource::Stderr);
+
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let fb_config = config.to_flatbuffers(&mut builder);
+    builder.finish(fb_config, None);
+    let bytes = builder.finished_data();
+    let fb_config =
+        flatbuffers::root::<tcrm_task_generated::tcrm::task::TaskConfig>(bytes).unwrap();
+
+    for restored in [
+        TaskConfig::try_from(fb_config).unwrap(),
+        TaskConfig::from_flatbuffers(fb_config).unwrap(),
+    ] {
+        assert_eq!(restored.ready_indicator_regex, config.ready_indicator_regex);
+        assert_eq!(restored.ready_indicator, None);
+        assert_eq!(restored.ready_indicator_source, Some(StreamSource::Stderr));
+    }
+}
+
+#[test]
+fn emit_event_trigger_requires_event_name() {
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let pattern = builder.create_string("Reloaded");
+    let fb_trigger = tcrm_task_generated::tcrm::task::OutputTrigger::create(
+        &mut builder,
+        &tcrm_task_generated::tcrm::task::OutputTriggerArgs {
+            pattern: Some(pattern),
+            action: tcrm_task_generated::tcrm::task::OutputTriggerAction::EmitEvent,
+            ..Default::default()
+        },
+    );
+    builder.finish(fb_trigger, None);
+    let bytes = builder.finished_data();
+    let fb_trigger =
+        flatbuffers::root::<tcrm_task_generated::tcrm::task::OutputTrigger>(bytes).unwrap();
+
+    assert!(matches!(
+        OutputTrigger::from_flatbuffers(fb_trigger),
+        Err(ConversionError::MissingRequiredField(
+            "OutputTrigger.event_name"
+        ))
+    ));
+}
+
 #[test]
 fn stream_source_invalid() {
     let invalid_source = tcrm_task_generated::tcrm::task::StreamSource(99);
@@ -213,6 +337,7 @@ fn stress_test() {
         ready_indicator: Some("STRESS_READY".to_string()),
         ready_indicator_source: Some(StreamSource::Stderr),
         use_process_group: Some(true),
+        ..Default::default()
     };
 
     let mut builder = flatbuffers::FlatBufferBuilder::new();
@@ -3,7 +3,7 @@ use crate::{
         conversion::{ConversionError, FromFlatbuffers, ToFlatbuffers},
         tcrm_task_generated,
     },
-    tasks::error::TaskError,
+    tasks::error::{ChannelKind, ChannelOp, SpawnDiagnosis, TaskError},
 };
 
 #[test]
@@ -11,7 +11,11 @@ fn roundtrip() {
     let test_cases = vec![
         TaskError::IO("io error message".to_string()),
         TaskError::Handle("handle error message".to_string()),
-        TaskError::Channel("channel error message".to_string()),
+        TaskError::Channel {
+            kind: ChannelKind::Event,
+            op: ChannelOp::Send,
+            detail: "channel error message".to_string(),
+        },
         TaskError::InvalidConfiguration("invalid config message".to_string()),
     ];
 
@@ -33,7 +37,9 @@ fn roundtrip() {
         match (&original_error, &converted_error) {
             (TaskError::IO(orig), TaskError::IO(conv)) => assert_eq!(orig, conv),
             (TaskError::Handle(orig), TaskError::Handle(conv)) => assert_eq!(orig, conv),
-            (TaskError::Channel(orig), TaskError::Channel(conv)) => assert_eq!(orig, conv),
+            (TaskError::Channel { .. }, TaskError::Channel { .. }) => {
+                assert_eq!(original_error, converted_error)
+            }
             (TaskError::InvalidConfiguration(orig), TaskError::InvalidConfiguration(conv)) => {
                 assert_eq!(orig, conv)
             }
@@ -47,7 +53,11 @@ fn roundtrip() {
 
 #[test]
 fn direct_read() {
-    let error = TaskError::Channel("direct_channel_error".to_string());
+    let error = TaskError::Channel {
+        kind: ChannelKind::Terminate,
+        op: ChannelOp::Closed,
+        detail: "direct_channel_error".to_string(),
+    };
     let mut builder = flatbuffers::FlatBufferBuilder::new();
     let fb_error = error.to_flatbuffers(&mut builder);
     builder.finish(fb_error, None);
@@ -58,6 +68,14 @@ fn direct_read() {
         tcrm_task_generated::tcrm::task::TaskErrorType::Channel
     );
     assert_eq!(fb.message().unwrap(), "direct_channel_error");
+    assert_eq!(
+        fb.channel_kind(),
+        tcrm_task_generated::tcrm::task::ChannelKind::Terminate
+    );
+    assert_eq!(
+        fb.channel_op(),
+        tcrm_task_generated::tcrm::task::ChannelOp::Closed
+    );
 }
 
 #[test]
@@ -72,7 +90,11 @@ fn direct_read_all_error_types() {
             tcrm_task_generated::tcrm::task::TaskErrorType::Handle,
         ),
         (
-            TaskError::Channel("channel test".to_string()),
+            TaskError::Channel {
+                kind: ChannelKind::Stdin,
+                op: ChannelOp::Recv,
+                detail: "channel test".to_string(),
+            },
             tcrm_task_generated::tcrm::task::TaskErrorType::Channel,
         ),
         (
@@ -112,7 +134,11 @@ fn unicode_message() {
 
 #[test]
 fn empty_message() {
-    let error = TaskError::Channel("".to_string());
+    let error = TaskError::Channel {
+        kind: ChannelKind::Internal,
+        op: ChannelOp::Send,
+        detail: "".to_string(),
+    };
     let mut builder = flatbuffers::FlatBufferBuilder::new();
     let fb_error = error.to_flatbuffers(&mut builder);
     builder.finish(fb_error, None);
@@ -122,8 +148,8 @@ fn empty_message() {
     assert_eq!(fb.message().unwrap(), "");
 
     let converted = TaskError::from_flatbuffers(fb).unwrap();
-    if let TaskError::Channel(msg) = converted {
-        assert_eq!(msg, "");
+    if let TaskError::Channel { detail, .. } = converted {
+        assert_eq!(detail, "");
     } else {
         panic!("Expected Channel error");
     }
@@ -165,6 +191,9 @@ fn conversion_error_display() {
         ConversionError::InvalidTaskEventStopReasonType(55),
         ConversionError::InvalidTaskEventType(44),
         ConversionError::InvalidTaskErrorType(33),
+        ConversionError::InvalidSpawnDiagnosisKind(22),
+        ConversionError::InvalidChannelKind(11),
+        ConversionError::InvalidChannelOp(10),
         ConversionError::MissingRequiredField("test_field"),
     ];
 
@@ -174,3 +203,164 @@ fn conversion_error_display() {
         assert!(display_str.contains("Invalid") || display_str.contains("Missing"));
     }
 }
+
+#[test]
+fn spawn_error_roundtrip() {
+    let diagnoses = vec![
+        SpawnDiagnosis::IsDirectory {
+            path: "/usr/bin".to_string(),
+        },
+        SpawnDiagnosis::NotExecutable {
+            path: "/tmp/script.sh".to_string(),
+        },
+        SpawnDiagnosis::MissingInterpreter {
+            path: "/tmp/script.py".to_string(),
+            interpreter: "/usr/bin/python9".to_string(),
+        },
+        SpawnDiagnosis::WrongArchitecture {
+            path: "/tmp/server".to_string(),
+            architecture: "aarch64".to_string(),
+        },
+    ];
+
+    for diagnosis in diagnoses {
+        let error = TaskError::Spawn {
+            message: "Permission denied (os error 13)".to_string(),
+            diagnosis,
+        };
+        let mut builder = flatbuffers::FlatBufferBuilder::new();
+        let fb_error = error.to_flatbuffers(&mut builder);
+        builder.finish(fb_error, None);
+        let bytes = builder.finished_data();
+        let fb = flatbuffers::root::<tcrm_task_generated::tcrm::task::TaskError>(bytes).unwrap();
+
+        assert_eq!(
+            fb.kind(),
+            tcrm_task_generated::tcrm::task::TaskErrorType::Spawn
+        );
+        assert_eq!(TaskError::from_flatbuffers(fb).unwrap(), error);
+    }
+}
+
+#[test]
+fn spawn_error_without_diagnosis_is_rejected() {
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let message = builder.create_string("Permission denied");
+    let path = builder.create_string("/tmp/script.sh");
+    let fb_error = tcrm_task_generated::tcrm::task::TaskError::create(
+        &mut builder,
+        &tcrm_task_generated::tcrm::task::TaskErrorArgs {
+            kind: tcrm_task_generated::tcrm::task::TaskErrorType::Spawn,
+            message: Some(message),
+            diagnosis_path: Some(path),
+            ..Default::default()
+        },
+    );
+    builder.finish(fb_error, None);
+    let bytes = builder.finished_data();
+    let fb = flatbuffers::root::<tcrm_task_generated::tcrm::task::TaskError>(bytes).unwrap();
+
+    assert!(matches!(
+        TaskError::from_flatbuffers(fb),
+        Err(ConversionError::InvalidSpawnDiagnosisKind(0))
+    ));
+}
+
+#[test]
+fn command_not_found_roundtrip() {
+    for searched_path in [Some("/usr/local/bin:/usr/bin".to_string()), None] {
+        let error = TaskError::CommandNotFound {
+            command: "cargo".to_string(),
+            searched_path,
+        };
+        let mut builder = flatbuffers::FlatBufferBuilder::new();
+        let fb_error = error.to_flatbuffers(&mut builder);
+        builder.finish(fb_error, None);
+        let bytes = builder.finished_data();
+        let fb = flatbuffers::root::<tcrm_task_generated::tcrm::task::TaskError>(bytes).unwrap();
+
+        assert_eq!(
+            fb.kind(),
+            tcrm_task_generated::tcrm::task::TaskErrorType::CommandNotFound
+        );
+        assert_eq!(fb.message(), Some("cargo"));
+        assert_eq!(TaskError::from_flatbuffers(fb).unwrap(), error);
+    }
+}
+
+#[test]
+fn pre_exec_roundtrip() {
+    let error = TaskError::PreExec {
+        step: "setsid".to_string(),
+        error: "EPERM".to_string(),
+    };
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let fb_error = error.to_flatbuffers(&mut builder);
+    builder.finish(fb_error, None);
+    let bytes = builder.finished_data();
+    let fb = flatbuffers::root::<tcrm_task_generated::tcrm::task::TaskError>(bytes).unwrap();
+
+    assert_eq!(
+        fb.kind(),
+        tcrm_task_generated::tcrm::task::TaskErrorType::PreExec
+    );
+    assert_eq!(fb.pre_exec_step(), Some("setsid"));
+    assert_eq!(fb.message(), Some("EPERM"));
+    assert_eq!(TaskError::from_flatbuffers(fb).unwrap(), error);
+}
+
+#[test]
+fn channel_error_roundtrip() {
+    let kinds = [
+        ChannelKind::Event,
+        ChannelKind::Terminate,
+        ChannelKind::Stdin,
+        ChannelKind::Internal,
+    ];
+    let ops = [ChannelOp::Send, ChannelOp::Recv, ChannelOp::Closed];
+
+    for kind in kinds {
+        for op in ops {
+            let error = TaskError::Channel {
+                kind,
+                op,
+                detail: "Event channel closed while sending TaskEvent::Started".to_string(),
+            };
+            let mut builder = flatbuffers::FlatBufferBuilder::new();
+            let fb_error = error.to_flatbuffers(&mut builder);
+            builder.finish(fb_error, None);
+            let bytes = builder.finished_data();
+            let fb =
+                flatbuffers::root::<tcrm_task_generated::tcrm::task::TaskError>(bytes).unwrap();
+
+            assert_eq!(
+                fb.kind(),
+                tcrm_task_generated::tcrm::task::TaskErrorType::Channel
+            );
+            assert_eq!(TaskError::from_flatbuffers(fb).unwrap(), error);
+        }
+    }
+}
+
+#[test]
+fn channel_error_without_kind_is_rejected() {
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let message = builder.create_string("Event channel closed");
+    let fb_error = tcrm_task_generated::tcrm::task::TaskError::create(
+        &mut builder,
+        &tcrm_task_generated::tcrm::task::TaskErrorArgs {
+            kind: tcrm_task_generated::tcrm::task::TaskErrorType::Channel,
+            message: Some(message),
+            channel_op: tcrm_task_generated::tcrm::task::ChannelOp::Closed,
+            ..Default::default()
+        },
+    );
+    builder.finish(fb_error, None);
+    let bytes = builder.finished_data();
+    let fb = flatbuffers::root::<tcrm_task_generated::tcrm::task::TaskError>(bytes).unwrap();
+
+    assert!(matches!(
+        TaskError::from_flatbuffers(fb),
+        Err(ConversionError::InvalidChannelKind(0))
+    ));
+}
@@ -1,12 +1,20 @@
+use std::time::{Duration, UNIX_EPOCH};
+
 use crate::{
     flatbuffers::conversion::{FromFlatbuffers, ToFlatbuffers},
-    tasks::{config::StreamSource, event::TaskEvent},
+    tasks::{
+        config::StreamSource,
+        event::{TaskEvent, TaskEventStopReason, TaskTerminateReason},
+    },
 };
 
 #[test]
 fn event_started_roundtrip() {
     let event = TaskEvent::Started {
-        task_name: "test_task".to_string(),
+        task_name: "test_task".into(),
+        process_id: 4242,
+        temp_dir: Some("/tmp/tcrm-1-0".to_string()),
+        working_dir: Some("/srv/app/build".to_string()),
     };
 
     let mut builder = flatbuffers::FlatBufferBuilder::new();
@@ -26,9 +34,12 @@ fn event_started_roundtrip() {
 #[test]
 fn event_output_roundtrip() {
     let event = TaskEvent::Output {
-        task_name: "test_task".to_string(),
+        task_name: "test_task".into(),
         line: "Hello, World!".to_string(),
         src: StreamSource::Stdout,
+        seq: 7,
+        truncated: true,
+        timestamp: UNIX_EPOCH + Duration::from_millis(1_792_143_000_005),
     };
 
     let mut builder = flatbuffers::FlatBufferBuilder::new();
@@ -45,3 +56,309 @@ fn event_output_roundtrip() {
     let roundtripped = TaskEvent::from_flatbuffers(fb_event).unwrap();
     assert_eq!(event, roundtripped);
 }
+#[test]
+fn event_output_json_roundtrip() {
+    let event = TaskEvent::OutputJson {
+        task_name: "test_task".into(),
+        line: r#"{"reason": "build-finished", "success": true}"#.to_string(),
+        #[cfg(feature = "serde")]
+        value: serde_json::json!({"reason": "build-finished", "success": true}),
+        src: StreamSource::Stdout,
+        seq: 3,
+        timestamp: UNIX_EPOCH + Duration::from_millis(1_792_143_000_005),
+    };
+
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let fb_event = event.to_flatbuffers(&mut builder);
+    builder.finish(fb_event, None);
+
+    let bytes = builder.finished_data();
+    let fb_event =
+        flatbuffers::root::<crate::flatbuffers::tcrm_task_generated::tcrm::task::TaskEvent>(bytes)
+            .unwrap();
+    let output = fb_event.event_as_output().unwrap();
+    assert!(output.is_json());
+    assert_eq!(output.timestamp_ms(), 1_792_143_000_005);
+    let roundtripped = TaskEvent::from_flatbuffers(fb_event).unwrap();
+    assert_eq!(event, roundtripped);
+}
+#[test]
+fn event_output_repeated_roundtrip() {
+    let event = TaskEvent::OutputRepeated {
+        task_name: "test_task".into(),
+        line: "connection refused".to_string(),
+        src: StreamSource::Stderr,
+        count: 9997,
+    };
+
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let fb_event = event.to_flatbuffers(&mut builder);
+    builder.finish(fb_event, None);
+
+    let bytes = builder.finished_data();
+    let fb_event =
+        flatbuffers::root::<crate::flatbuffers::tcrm_task_generated::tcrm::task::TaskEvent>(bytes)
+            .unwrap();
+    let roundtripped = TaskEvent::from_flatbuffers(fb_event).unwrap();
+    assert_eq!(event, roundtripped);
+}
+#[test]
+fn event_output_suppressed_roundtrip() {
+    let event = TaskEvent::OutputSuppressed {
+        task_name: "test_task".into(),
+        src: StreamSource::Stderr,
+        count: 49_900,
+        bytes: 338_894,
+    };
+
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let fb_event = event.to_flatbuffers(&mut builder);
+    builder.finish(fb_event, None);
+
+    let bytes = builder.finished_data();
+    let fb_event =
+        flatbuffers::root::<crate::flatbuffers::tcrm_task_generated::tcrm::task::TaskEvent>(bytes)
+            .unwrap();
+    let roundtripped = TaskEvent::from_flatbuffers(fb_event).unwrap();
+    assert_eq!(event, roundtripped);
+}
+#[test]
+fn event_triggered_roundtrip() {
+    let event = TaskEvent::Triggered {
+        task_name: "test_task".into(),
+        name: "reload".to_string(),
+        line: "Configuration reloaded".to_string(),
+        src: StreamSource::Stdout,
+    };
+
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let fb_event = event.to_flatbuffers(&mut builder);
+    builder.finish(fb_event, None);
+
+    let bytes = builder.finished_data();
+    let fb_event =
+        flatbuffers::root::<crate::flatbuffers::tcrm_task_generated::tcrm::task::TaskEvent>(bytes)
+            .unwrap();
+    let roundtripped = TaskEvent::from_flatbuffers(fb_event).unwrap();
+    assert_eq!(event, roundtripped);
+}
+#[test]
+fn event_running_long_roundtrip() {
+    let event = TaskEvent::RunningLong {
+        task_name: "test_task".into(),
+        expected_ms: 30_000,
+        elapsed_ms: 60_001,
+    };
+
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let fb_event = event.to_flatbuffers(&mut builder);
+    builder.finish(fb_event, None);
+
+    let bytes = builder.finished_data();
+    let fb_event =
+        flatbuffers::root::<crate::flatbuffers::tcrm_task_generated::tcrm::task::TaskEvent>(bytes)
+            .unwrap();
+    let roundtripped = TaskEvent::from_flatbuffers(fb_event).unwrap();
+    assert_eq!(event, roundtripped);
+}
+#[test]
+fn event_memory_watermark_exceeded_roundtrip() {
+    let event = TaskEvent::MemoryWatermarkExceeded {
+        task_name: "test_task".into(),
+        rss_bytes: 3 << 30,
+        watermark_bytes: 2 << 30,
+    };
+
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let fb_event = event.to_flatbuffers(&mut builder);
+    builder.finish(fb_event, None);
+
+    let bytes = builder.finished_data();
+    let fb_event =
+        flatbuffers::root::<crate::flatbuffers::tcrm_task_generated::tcrm::task::TaskEvent>(bytes)
+            .unwrap();
+    let roundtripped = TaskEvent::from_flatbuffers(fb_event).unwrap();
+    assert_eq!(event, roundtripped);
+}
+#[test]
+fn event_spawn_retry_roundtrip() {
+    let event = TaskEvent::SpawnRetry {
+        task_name: "test_task".into(),
+        attempt: 2,
+        error: "Access is denied. (os error 5)".to_string(),
+    };
+
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let fb_event = event.to_flatbuffers(&mut builder);
+    builder.finish(fb_event, None);
+
+    let bytes = builder.finished_data();
+    let fb_event =
+        flatbuffers::root::<crate::flatbuffers::tcrm_task_generated::tcrm::task::TaskEvent>(bytes)
+            .unwrap();
+    let roundtripped = TaskEvent::from_flatbuffers(fb_event).unwrap();
+    assert_eq!(event, roundtripped);
+}
+#[test]
+fn event_script_step_roundtrip() {
+    let event = TaskEvent::ScriptStep {
+        task_name: "test_task".into(),
+        index: 3,
+    };
+
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let fb_event = event.to_flatbuffers(&mut builder);
+    builder.finish(fb_event, None);
+
+    let bytes = builder.finished_data();
+    let fb_event =
+        flatbuffers::root::<crate::flatbuffers::tcrm_task_generated::tcrm::task::TaskEvent>(bytes)
+            .unwrap();
+    let roundtripped = TaskEvent::from_flatbuffers(fb_event).unwrap();
+    assert_eq!(event, roundtripped);
+}
+#[test]
+fn event_slow_consumer_roundtrip() {
+    let event = TaskEvent::SlowConsumer {
+        task_name: "test_task".into(),
+        avg_wait_ms: 42,
+    };
+
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let fb_event = event.to_flatbuffers(&mut builder);
+    builder.finish(fb_event, None);
+
+    let bytes = builder.finished_data();
+    let fb_event =
+        flatbuffers::root::<crate::flatbuffers::tcrm_task_generated::tcrm::task::TaskEvent>(bytes)
+            .unwrap();
+    let roundtripped = TaskEvent::from_flatbuffers(fb_event).unwrap();
+    assert_eq!(event, roundtripped);
+}
+
+#[test]
+fn event_output_chunk_roundtrip() {
+    let event = TaskEvent::OutputChunk {
+        task_name: "test_task".into(),
+        data: vec![0x00, 0xff, b'\n', 0xc3, 0x28],
+        src: StreamSource::Stderr,
+    };
+
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let fb_event = event.to_flatbuffers(&mut builder);
+    builder.finish(fb_event, None);
+
+    let bytes = builder.finished_data();
+    let fb_event =
+        flatbuffers::root::<crate::flatbuffers::tcrm_task_generated::tcrm::task::TaskEvent>(bytes)
+            .unwrap();
+    let roundtripped = TaskEvent::from_flatbuffers(fb_event).unwrap();
+    assert_eq!(event, roundtripped);
+}
+
+#[test]
+fn event_unhealthy_roundtrip() {
+    let event = TaskEvent::Unhealthy {
+        task_name: "test_task".into(),
+        consecutive_failures: 3,
+    };
+
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let fb_event = event.to_flatbuffers(&mut builder);
+    builder.finish(fb_event, None);
+
+    let bytes = builder.finished_data();
+    let fb_event =
+        flatbuffers::root::<crate::flatbuffers::tcrm_task_generated::tcrm::task::TaskEvent>(bytes)
+            .unwrap();
+    let roundtripped = TaskEvent::from_flatbuffers(fb_event).unwrap();
+    assert_eq!(event, roundtripped);
+}
+#[test]
+fn event_stopped_roundtrip() {
+    let reasons = [
+        TaskEventStopReason::Finished,
+        TaskEventStopReason::Terminated(TaskTerminateReason::UserRequested { message: None }),
+        TaskEventStopReason::Terminated(TaskTerminateReason::UserRequested {
+            message: Some("stopped by alice: redeploying".to_string()),
+        }),
+        TaskEventStopReason::Terminated(TaskTerminateReason::Cleanup { message: None }),
+        TaskEventStopReason::Terminated(TaskTerminateReason::Cleanup {
+            message: Some("shutting down".to_string()),
+        }),
+        TaskEventStopReason::Terminated(TaskTerminateReason::CompletionIndicator),
+        TaskEventStopReason::Terminated(TaskTerminateReason::ScriptTimeout),
+        TaskEventStopReason::Terminated(TaskTerminateReason::Unhealthy),
+        TaskEventStopReason::Error("Process killed by signal SIGKILL (9)".to_string()),
+        TaskEventStopReason::StartupFailure(
+            "Process exited during startup (exit status: 127)".to_string(),
+        ),
+    ];
+    for (reason, was_ready) in reasons
+        .into_iter()
+        .zip([true, false, true, false, true, false, false, true, true, false])
+    {
+        let event = TaskEvent::Stopped {
+            task_name: "test_task".into(),
+            exit_code: Some(1),
+            signal: (!was_ready).then_some(9),
+            reason,
+            was_ready,
+            duration_ms: 1500,
+            expected_duration_ms: was_ready.then_some(1000),
+            exit_meaning: (!was_ready).then(|| "test failure".to_string()),
+            from_cache: !was_ready,
+            stdout_sha256: was_ready.then(|| "ab".repeat(32)),
+            stderr_sha256: None,
+        };
+
+        let mut builder = flatbuffers::FlatBufferBuilder::new();
+        let fb_event = event.to_flatbuffers(&mut builder);
+        builder.finish(fb_event, None);
+
+        let bytes = builder.finished_data();
+        let fb_event =
+            flatbuffers::root::<crate::flatbuffers::tcrm_task_generated::tcrm::task::TaskEvent>(
+                bytes,
+            )
+            .unwrap();
+        let roundtripped = TaskEvent::from_flatbuffers(fb_event).unwrap();
+        assert_eq!(event, roundtripped);
+    }
+}
+
+#[test]
+fn event_stopped_without_terminate_message_decodes() {
+    use crate::flatbuffers::tcrm_task_generated::tcrm::task as fb;
+
+    // Written as before `TerminatedStopReason`, with an empty `DummyTable`
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let task_name = builder.create_string("test_task");
+    let reason = fb::DummyTable::create(&mut builder, &fb::DummyTableArgs {});
+    let stopped = fb::StoppedEvent::create(
+        &mut builder,
+        &fb::StoppedEventArgs {
+            task_name: Some(task_name),
+            reason_type: fb::TaskEventStopReason::TerminatedUserRequested,
+            reason: Some(reason.as_union_value()),
+            ..Default::default()
+        },
+    );
+    let event = fb::TaskEvent::create(
+        &mut builder,
+        &fb::TaskEventArgs {
+            event_type: fb::TaskEventUnion::Stopped,
+            event: Some(stopped.as_union_value()),
+        },
+    );
+    builder.finish(event, None);
+
+    let fb_event = flatbuffers::root::<fb::TaskEvent>(builder.finished_data()).unwrap();
+    let TaskEvent::Stopped { reason, .. } = TaskEvent::from_flatbuffers(fb_event).unwrap() else {
+        panic!("Expected a Stopped event");
+    };
+    assert_eq!(
+        reason,
+        TaskEventStopReason::Terminated(TaskTerminateReason::UserRequested { message: None })
+    );
+}
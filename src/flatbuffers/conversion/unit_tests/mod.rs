@@ -1,4 +1,6 @@
+mod audit;
 mod config;
 mod error;
 mod event;
+mod plan;
 mod state;
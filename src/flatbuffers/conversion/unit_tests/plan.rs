@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+
+use crate::{
+    flatbuffers::{
+        conversion::{FromFlatbuffers, ToFlatbuffers},
+        tcrm_task_generated,
+    },
+    tasks::plan::ExecutionPlan,
+};
+
+fn roundtrip_plan(plan: &ExecutionPlan) -> ExecutionPlan {
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let offset = plan.to_flatbuffers(&mut builder);
+    builder.finish(offset, None);
+    let bytes = builder.finished_data();
+
+    let fb_plan =
+        flatbuffers::root::<tcrm_task_generated::tcrm::task::ExecutionPlan>(bytes).unwrap();
+    ExecutionPlan::from_flatbuffers(fb_plan).unwrap()
+}
+
+#[test]
+fn roundtrip() {
+    let plan = ExecutionPlan {
+        command: "echo".to_string(),
+        program: "/usr/bin/echo".to_string(),
+        args: vec!["hello".to_string(), "world".to_string()],
+        working_dir: "/tmp".to_string(),
+        env_overrides: HashMap::from([
+            ("GREETING".to_string(), "hi".to_string()),
+            ("API_TOKEN".to_string(), "[REDACTED]".to_string()),
+        ]),
+        timeout_ms: Some(5000),
+        use_process_group: true,
+        enable_stdin: true,
+    };
+
+    assert_eq!(roundtrip_plan(&plan), plan);
+}
+
+#[test]
+fn roundtrip_minimal() {
+    let plan = ExecutionPlan {
+        command: "./run".to_string(),
+        program: "/srv/app/run".to_string(),
+        args: vec![],
+        working_dir: "/srv/app".to_string(),
+        env_overrides: HashMap::new(),
+        timeout_ms: None,
+        use_process_group: false,
+        enable_stdin: false,
+    };
+
+    assert_eq!(roundtrip_plan(&plan), plan);
+}
@@ -24,8 +24,12 @@ fn roundtrip() {
 fn terminate_reason_roundtrip() {
     let test_cases = vec![
         TaskTerminateReason::Timeout,
-        TaskTerminateReason::Cleanup,
+        TaskTerminateReason::Cleanup { message: None },
         TaskTerminateReason::DependenciesFinished,
+        TaskTerminateReason::UserRequested { message: None },
+        TaskTerminateReason::CompletionIndicator,
+        TaskTerminateReason::ScriptTimeout,
+        TaskTerminateReason::Unhealthy,
     ];
 
     for original_reason in test_cases {
@@ -40,8 +44,11 @@ fn terminate_reason_roundtrip() {
 fn terminate_reason_to_flatbuffers_terminated() {
     let reasons = vec![
         TaskTerminateReason::Timeout,
-        TaskTerminateReason::Cleanup,
+        TaskTerminateReason::Cleanup { message: None },
         TaskTerminateReason::DependenciesFinished,
+        TaskTerminateReason::CompletionIndicator,
+        TaskTerminateReason::ScriptTimeout,
+        TaskTerminateReason::Unhealthy,
     ];
 
     for reason in reasons {
@@ -55,7 +62,7 @@ fn terminate_reason_to_flatbuffers_terminated() {
                     tcrm_task_generated::tcrm::task::TaskEventStopReason::TerminatedTimeout
                 );
             }
-            TaskTerminateReason::Cleanup => {
+            TaskTerminateReason::Cleanup { .. } => {
                 assert_eq!(
                     stop_reason,
                     tcrm_task_generated::tcrm::task::TaskEventStopReason::TerminatedCleanup
@@ -67,12 +74,30 @@ fn terminate_reason_to_flatbuffers_terminated() {
                         tcrm_task_generated::tcrm::task::TaskEventStopReason::TerminatedDependenciesFinished
                     );
             }
-            TaskTerminateReason::UserRequested => {
+            TaskTerminateReason::UserRequested { .. } => {
                 assert_eq!(
                     stop_reason,
                     tcrm_task_generated::tcrm::task::TaskEventStopReason::TerminatedUserRequested
                 );
             }
+            TaskTerminateReason::CompletionIndicator => {
+                assert_eq!(
+                    stop_reason,
+                    tcrm_task_generated::tcrm::task::TaskEventStopReason::TerminatedCompletionIndicator
+                );
+            }
+            TaskTerminateReason::ScriptTimeout => {
+                assert_eq!(
+                    stop_reason,
+                    tcrm_task_generated::tcrm::task::TaskEventStopReason::TerminatedScriptTimeout
+                );
+            }
+            TaskTerminateReason::Unhealthy => {
+                assert_eq!(
+                    stop_reason,
+                    tcrm_task_generated::tcrm::task::TaskEventStopReason::TerminatedUnhealthy
+                );
+            }
         }
     }
 }
@@ -133,6 +133,213 @@ pub mod tcrm {
             since = "2.0.0",
             note = "Use associated constants instead. This will no longer be generated in 2021."
         )]
+        pub const ENUM_MIN_OUTPUT_TRIGGER_ACTION: i8 = 0;
+        #[deprecated(
+            since = "2.0.0",
+            note = "Use associated constants instead. This will no longer be generated in 2021."
+        )]
+        pub const ENUM_MAX_OUTPUT_TRIGGER_ACTION: i8 = 3;
+        #[deprecated(
+            since = "2.0.0",
+            note = "Use associated constants instead. This will no longer be generated in 2021."
+        )]
+        #[allow(non_camel_case_types)]
+        pub const ENUM_VALUES_OUTPUT_TRIGGER_ACTION: [OutputTriggerAction; 4] = [
+            OutputTriggerAction::Ready,
+            OutputTriggerAction::FailTask,
+            OutputTriggerAction::EmitEvent,
+            OutputTriggerAction::Complete,
+        ];
+
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+        #[repr(transparent)]
+        pub struct OutputTriggerAction(pub i8);
+        #[allow(non_upper_case_globals)]
+        impl OutputTriggerAction {
+            pub const Ready: Self = Self(0);
+            pub const FailTask: Self = Self(1);
+            pub const EmitEvent: Self = Self(2);
+            pub const Complete: Self = Self(3);
+
+            pub const ENUM_MIN: i8 = 0;
+            pub const ENUM_MAX: i8 = 3;
+            pub const ENUM_VALUES: &'static [Self] = &[
+                Self::Ready,
+                Self::FailTask,
+                Self::EmitEvent,
+                Self::Complete,
+            ];
+            /// Returns the variant's name or "" if unknown.
+            pub fn variant_name(self) -> Option<&'static str> {
+                match self {
+                    Self::Ready => Some("Ready"),
+                    Self::FailTask => Some("FailTask"),
+                    Self::EmitEvent => Some("EmitEvent"),
+                    Self::Complete => Some("Complete"),
+                    _ => None,
+                }
+            }
+        }
+        impl core::fmt::Debug for OutputTriggerAction {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                if let Some(name) = self.variant_name() {
+                    f.write_str(name)
+                } else {
+                    f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+                }
+            }
+        }
+        impl<'a> flatbuffers::Follow<'a> for OutputTriggerAction {
+            type Inner = Self;
+            #[inline]
+            unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+                unsafe {
+                    let b = flatbuffers::read_scalar_at::<i8>(buf, loc);
+                    Self(b)
+                }
+            }
+        }
+
+        impl flatbuffers::Push for OutputTriggerAction {
+            type Output = OutputTriggerAction;
+            #[inline]
+            unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+                unsafe {
+                    flatbuffers::emplace_scalar::<i8>(dst, self.0);
+                }
+            }
+        }
+
+        impl flatbuffers::EndianScalar for OutputTriggerAction {
+            type Scalar = i8;
+            #[inline]
+            fn to_little_endian(self) -> i8 {
+                self.0.to_le()
+            }
+            #[inline]
+            #[allow(clippy::wrong_self_convention)]
+            fn from_little_endian(v: i8) -> Self {
+                let b = i8::from_le(v);
+                Self(b)
+            }
+        }
+
+        impl<'a> flatbuffers::Verifiable for OutputTriggerAction {
+            #[inline]
+            fn run_verifier(
+                v: &mut flatbuffers::Verifier,
+                pos: usize,
+            ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+                use self::flatbuffers::Verifiable;
+                i8::run_verifier(v, pos)
+            }
+        }
+
+        impl flatbuffers::SimpleToVerifyInSlice for OutputTriggerAction {}
+        #[deprecated(
+            since = "2.0.0",
+            note = "Use associated constants instead. This will no longer be generated in 2021."
+        )]
+        pub const ENUM_MIN_READY_MATCH_MODE: i8 = 0;
+        #[deprecated(
+            since = "2.0.0",
+            note = "Use associated constants instead. This will no longer be generated in 2021."
+        )]
+        pub const ENUM_MAX_READY_MATCH_MODE: i8 = 2;
+        #[deprecated(
+            since = "2.0.0",
+            note = "Use associated constants instead. This will no longer be generated in 2021."
+        )]
+        #[allow(non_camel_case_types)]
+        pub const ENUM_VALUES_READY_MATCH_MODE: [ReadyMatchMode; 3] = [
+            ReadyMatchMode::Contains,
+            ReadyMatchMode::StartsWith,
+            ReadyMatchMode::ExactLine,
+        ];
+
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+        #[repr(transparent)]
+        pub struct ReadyMatchMode(pub i8);
+        #[allow(non_upper_case_globals)]
+        impl ReadyMatchMode {
+            pub const Contains: Self = Self(0);
+            pub const StartsWith: Self = Self(1);
+            pub const ExactLine: Self = Self(2);
+
+            pub const ENUM_MIN: i8 = 0;
+            pub const ENUM_MAX: i8 = 2;
+            pub const ENUM_VALUES: &'static [Self] =
+                &[Self::Contains, Self::StartsWith, Self::ExactLine];
+            /// Returns the variant's name or "" if unknown.
+            pub fn variant_name(self) -> Option<&'static str> {
+                match self {
+                    Self::Contains => Some("Contains"),
+                    Self::StartsWith => Some("StartsWith"),
+                    Self::ExactLine => Some("ExactLine"),
+                    _ => None,
+                }
+            }
+        }
+        impl core::fmt::Debug for ReadyMatchMode {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                if let Some(name) = self.variant_name() {
+                    f.write_str(name)
+                } else {
+                    f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+                }
+            }
+        }
+        impl<'a> flatbuffers::Follow<'a> for ReadyMatchMode {
+            type Inner = Self;
+            #[inline]
+            unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+                unsafe {
+                    let b = flatbuffers::read_scalar_at::<i8>(buf, loc);
+                    Self(b)
+                }
+            }
+        }
+
+        impl flatbuffers::Push for ReadyMatchMode {
+            type Output = ReadyMatchMode;
+            #[inline]
+            unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+                unsafe {
+                    flatbuffers::emplace_scalar::<i8>(dst, self.0);
+                }
+            }
+        }
+
+        impl flatbuffers::EndianScalar for ReadyMatchMode {
+            type Scalar = i8;
+            #[inline]
+            fn to_little_endian(self) -> i8 {
+                self.0.to_le()
+            }
+            #[inline]
+            #[allow(clippy::wrong_self_convention)]
+            fn from_little_endian(v: i8) -> Self {
+                let b = i8::from_le(v);
+                Self(b)
+            }
+        }
+
+        impl<'a> flatbuffers::Verifiable for ReadyMatchMode {
+            #[inline]
+            fn run_verifier(
+                v: &mut flatbuffers::Verifier,
+                pos: usize,
+            ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+                use self::flatbuffers::Verifiable;
+                i8::run_verifier(v, pos)
+            }
+        }
+
+        impl flatbuffers::SimpleToVerifyInSlice for ReadyMatchMode {}
+        #[deprecated(
+            since = "2.0.0",
+            note = "Use associated constants instead. This will no longer be generated in 2021."
+        )]
         pub const ENUM_MIN_TASK_STATE: i8 = 0;
         #[deprecated(
             since = "2.0.0",
@@ -249,17 +456,18 @@ pub mod tcrm {
             since = "2.0.0",
             note = "Use associated constants instead. This will no longer be generated in 2021."
         )]
-        pub const ENUM_MAX_TASK_ERROR_TYPE: i8 = 3;
+        pub const ENUM_MAX_TASK_ERROR_TYPE: i8 = 4;
         #[deprecated(
             since = "2.0.0",
             note = "Use associated constants instead. This will no longer be generated in 2021."
         )]
         #[allow(non_camel_case_types)]
-        pub const ENUM_VALUES_TASK_ERROR_TYPE: [TaskErrorType; 4] = [
+        pub const ENUM_VALUES_TASK_ERROR_TYPE: [TaskErrorType; 5] = [
             TaskErrorType::IO,
             TaskErrorType::Handle,
             TaskErrorType::Channel,
             TaskErrorType::InvalidConfiguration,
+            TaskErrorType::Spawn,
         ];
 
         #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
@@ -271,14 +479,20 @@ pub mod tcrm {
             pub const Handle: Self = Self(1);
             pub const Channel: Self = Self(2);
             pub const InvalidConfiguration: Self = Self(3);
+            pub const Spawn: Self = Self(4);
+            pub const CommandNotFound: Self = Self(5);
+            pub const PreExec: Self = Self(6);
 
             pub const ENUM_MIN: i8 = 0;
-            pub const ENUM_MAX: i8 = 3;
+            pub const ENUM_MAX: i8 = 6;
             pub const ENUM_VALUES: &'static [Self] = &[
                 Self::IO,
                 Self::Handle,
                 Self::Channel,
                 Self::InvalidConfiguration,
+                Self::Spawn,
+                Self::CommandNotFound,
+                Self::PreExec,
             ];
             /// Returns the variant's name or "" if unknown.
             pub fn variant_name(self) -> Option<&'static str> {
@@ -287,6 +501,9 @@ pub mod tcrm {
                     Self::Handle => Some("Handle"),
                     Self::Channel => Some("Channel"),
                     Self::InvalidConfiguration => Some("InvalidConfiguration"),
+                    Self::Spawn => Some("Spawn"),
+                    Self::CommandNotFound => Some("CommandNotFound"),
+                    Self::PreExec => Some("PreExec"),
                     _ => None,
                 }
             }
@@ -351,54 +568,58 @@ pub mod tcrm {
             since = "2.0.0",
             note = "Use associated constants instead. This will no longer be generated in 2021."
         )]
-        pub const ENUM_MIN_TASK_TERMINATE_REASON: i8 = 0;
+        pub const ENUM_MIN_SPAWN_DIAGNOSIS_KIND: i8 = 0;
         #[deprecated(
             since = "2.0.0",
             note = "Use associated constants instead. This will no longer be generated in 2021."
         )]
-        pub const ENUM_MAX_TASK_TERMINATE_REASON: i8 = 3;
+        pub const ENUM_MAX_SPAWN_DIAGNOSIS_KIND: i8 = 4;
         #[deprecated(
             since = "2.0.0",
             note = "Use associated constants instead. This will no longer be generated in 2021."
         )]
         #[allow(non_camel_case_types)]
-        pub const ENUM_VALUES_TASK_TERMINATE_REASON: [TaskTerminateReason; 4] = [
-            TaskTerminateReason::Timeout,
-            TaskTerminateReason::Cleanup,
-            TaskTerminateReason::DependenciesFinished,
-            TaskTerminateReason::UserRequested,
+        pub const ENUM_VALUES_SPAWN_DIAGNOSIS_KIND: [SpawnDiagnosisKind; 5] = [
+            SpawnDiagnosisKind::None,
+            SpawnDiagnosisKind::IsDirectory,
+            SpawnDiagnosisKind::NotExecutable,
+            SpawnDiagnosisKind::MissingInterpreter,
+            SpawnDiagnosisKind::WrongArchitecture,
         ];
 
         #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
         #[repr(transparent)]
-        pub struct TaskTerminateReason(pub i8);
+        pub struct SpawnDiagnosisKind(pub i8);
         #[allow(non_upper_case_globals)]
-        impl TaskTerminateReason {
-            pub const Timeout: Self = Self(0);
-            pub const Cleanup: Self = Self(1);
-            pub const DependenciesFinished: Self = Self(2);
-            pub const UserRequested: Self = Self(3);
+        impl SpawnDiagnosisKind {
+            pub const None: Self = Self(0);
+            pub const IsDirectory: Self = Self(1);
+            pub const NotExecutable: Self = Self(2);
+            pub const MissingInterpreter: Self = Self(3);
+            pub const WrongArchitecture: Self = Self(4);
 
             pub const ENUM_MIN: i8 = 0;
-            pub const ENUM_MAX: i8 = 3;
+            pub const ENUM_MAX: i8 = 4;
             pub const ENUM_VALUES: &'static [Self] = &[
-                Self::Timeout,
-                Self::Cleanup,
-                Self::DependenciesFinished,
-                Self::UserRequested,
+                Self::None,
+                Self::IsDirectory,
+                Self::NotExecutable,
+                Self::MissingInterpreter,
+                Self::WrongArchitecture,
             ];
             /// Returns the variant's name or "" if unknown.
             pub fn variant_name(self) -> Option<&'static str> {
                 match self {
-                    Self::Timeout => Some("Timeout"),
-                    Self::Cleanup => Some("Cleanup"),
-                    Self::DependenciesFinished => Some("DependenciesFinished"),
-                    Self::UserRequested => Some("UserRequested"),
+                    Self::None => Some("None"),
+                    Self::IsDirectory => Some("IsDirectory"),
+                    Self::NotExecutable => Some("NotExecutable"),
+                    Self::MissingInterpreter => Some("MissingInterpreter"),
+                    Self::WrongArchitecture => Some("WrongArchitecture"),
                     _ => None,
                 }
             }
         }
-        impl core::fmt::Debug for TaskTerminateReason {
+        impl core::fmt::Debug for SpawnDiagnosisKind {
             fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
                 if let Some(name) = self.variant_name() {
                     f.write_str(name)
@@ -407,7 +628,7 @@ pub mod tcrm {
                 }
             }
         }
-        impl<'a> flatbuffers::Follow<'a> for TaskTerminateReason {
+        impl<'a> flatbuffers::Follow<'a> for SpawnDiagnosisKind {
             type Inner = Self;
             #[inline]
             unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
@@ -418,8 +639,8 @@ pub mod tcrm {
             }
         }
 
-        impl flatbuffers::Push for TaskTerminateReason {
-            type Output = TaskTerminateReason;
+        impl flatbuffers::Push for SpawnDiagnosisKind {
+            type Output = SpawnDiagnosisKind;
             #[inline]
             unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
                 unsafe {
@@ -428,7 +649,7 @@ pub mod tcrm {
             }
         }
 
-        impl flatbuffers::EndianScalar for TaskTerminateReason {
+        impl flatbuffers::EndianScalar for SpawnDiagnosisKind {
             type Scalar = i8;
             #[inline]
             fn to_little_endian(self) -> i8 {
@@ -442,7 +663,7 @@ pub mod tcrm {
             }
         }
 
-        impl<'a> flatbuffers::Verifiable for TaskTerminateReason {
+        impl<'a> flatbuffers::Verifiable for SpawnDiagnosisKind {
             #[inline]
             fn run_verifier(
                 v: &mut flatbuffers::Verifier,
@@ -453,71 +674,63 @@ pub mod tcrm {
             }
         }
 
-        impl flatbuffers::SimpleToVerifyInSlice for TaskTerminateReason {}
+        impl flatbuffers::SimpleToVerifyInSlice for SpawnDiagnosisKind {}
         #[deprecated(
             since = "2.0.0",
             note = "Use associated constants instead. This will no longer be generated in 2021."
         )]
-        pub const ENUM_MIN_TASK_EVENT_STOP_REASON: u8 = 0;
+        pub const ENUM_MIN_CHANNEL_KIND: i8 = 0;
         #[deprecated(
             since = "2.0.0",
             note = "Use associated constants instead. This will no longer be generated in 2021."
         )]
-        pub const ENUM_MAX_TASK_EVENT_STOP_REASON: u8 = 6;
+        pub const ENUM_MAX_CHANNEL_KIND: i8 = 4;
         #[deprecated(
             since = "2.0.0",
             note = "Use associated constants instead. This will no longer be generated in 2021."
         )]
         #[allow(non_camel_case_types)]
-        pub const ENUM_VALUES_TASK_EVENT_STOP_REASON: [TaskEventStopReason; 7] = [
-            TaskEventStopReason::NONE,
-            TaskEventStopReason::Finished,
-            TaskEventStopReason::TerminatedTimeout,
-            TaskEventStopReason::TerminatedCleanup,
-            TaskEventStopReason::TerminatedDependenciesFinished,
-            TaskEventStopReason::TerminatedUserRequested,
-            TaskEventStopReason::Error,
+        pub const ENUM_VALUES_CHANNEL_KIND: [ChannelKind; 5] = [
+            ChannelKind::None,
+            ChannelKind::Event,
+            ChannelKind::Terminate,
+            ChannelKind::Stdin,
+            ChannelKind::Internal,
         ];
 
         #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
         #[repr(transparent)]
-        pub struct TaskEventStopReason(pub u8);
+        pub struct ChannelKind(pub i8);
         #[allow(non_upper_case_globals)]
-        impl TaskEventStopReason {
-            pub const NONE: Self = Self(0);
-            pub const Finished: Self = Self(1);
-            pub const TerminatedTimeout: Self = Self(2);
-            pub const TerminatedCleanup: Self = Self(3);
-            pub const TerminatedDependenciesFinished: Self = Self(4);
-            pub const TerminatedUserRequested: Self = Self(5);
-            pub const Error: Self = Self(6);
+        impl ChannelKind {
+            pub const None: Self = Self(0);
+            pub const Event: Self = Self(1);
+            pub const Terminate: Self = Self(2);
+            pub const Stdin: Self = Self(3);
+            pub const Internal: Self = Self(4);
 
-            pub const ENUM_MIN: u8 = 0;
-            pub const ENUM_MAX: u8 = 6;
+            pub const ENUM_MIN: i8 = 0;
+            pub const ENUM_MAX: i8 = 4;
             pub const ENUM_VALUES: &'static [Self] = &[
-                Self::NONE,
-                Self::Finished,
-                Self::TerminatedTimeout,
-                Self::TerminatedCleanup,
-                Self::TerminatedDependenciesFinished,
-                Self::TerminatedUserRequested,
-                Self::Error,
+                Self::None,
+                Self::Event,
+                Self::Terminate,
+                Self::Stdin,
+                Self::Internal,
             ];
             /// Returns the variant's name or "" if unknown.
             pub fn variant_name(self) -> Option<&'static str> {
                 match self {
-                    Self::NONE => Some("NONE"),
-                    Self::Finished => Some("Finished"),
-                    Self::TerminatedTimeout => Some("TerminatedTimeout"),
-                    Self::TerminatedCleanup => Some("TerminatedCleanup"),
-                    Self::TerminatedDependenciesFinished => Some("TerminatedDependenciesFinished"),
-                    Self::TerminatedUserRequested => Some("TerminatedUserRequested"),
-                    Self::Error => Some("Error"),
+                    Self::None => Some("None"),
+                    Self::Event => Some("Event"),
+                    Self::Terminate => Some("Terminate"),
+                    Self::Stdin => Some("Stdin"),
+                    Self::Internal => Some("Internal"),
                     _ => None,
                 }
             }
         }
-        impl core::fmt::Debug for TaskEventStopReason {
+        impl core::fmt::Debug for ChannelKind {
             fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
                 if let Some(name) = self.variant_name() {
                     f.write_str(name)
@@ -526,115 +739,105 @@ pub mod tcrm {
                 }
             }
         }
-        impl<'a> flatbuffers::Follow<'a> for TaskEventStopReason {
+        impl<'a> flatbuffers::Follow<'a> for ChannelKind {
             type Inner = Self;
             #[inline]
             unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
                 unsafe {
-                    let b = flatbuffers::read_scalar_at::<u8>(buf, loc);
+                    let b = flatbuffers::read_scalar_at::<i8>(buf, loc);
                     Self(b)
                 }
             }
         }
 
-        impl flatbuffers::Push for TaskEventStopReason {
-            type Output = TaskEventStopReason;
+        impl flatbuffers::Push for ChannelKind {
+            type Output = ChannelKind;
             #[inline]
             unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
                 unsafe {
-                    flatbuffers::emplace_scalar::<u8>(dst, self.0);
+                    flatbuffers::emplace_scalar::<i8>(dst, self.0);
                 }
             }
         }
 
-        impl flatbuffers::EndianScalar for TaskEventStopReason {
-            type Scalar = u8;
+        impl flatbuffers::EndianScalar for ChannelKind {
+            type Scalar = i8;
             #[inline]
-            fn to_little_endian(self) -> u8 {
+            fn to_little_endian(self) -> i8 {
                 self.0.to_le()
             }
             #[inline]
             #[allow(clippy::wrong_self_convention)]
-            fn from_little_endian(v: u8) -> Self {
-                let b = u8::from_le(v);
+            fn from_little_endian(v: i8) -> Self {
+                let b = i8::from_le(v);
                 Self(b)
             }
         }
 
-        impl<'a> flatbuffers::Verifiable for TaskEventStopReason {
+        impl<'a> flatbuffers::Verifiable for ChannelKind {
             #[inline]
             fn run_verifier(
                 v: &mut flatbuffers::Verifier,
                 pos: usize,
             ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
                 use self::flatbuffers::Verifiable;
-                u8::run_verifier(v, pos)
+                i8::run_verifier(v, pos)
             }
         }
 
-        impl flatbuffers::SimpleToVerifyInSlice for TaskEventStopReason {}
-        pub struct TaskEventStopReasonUnionTableOffset {}
-
+        impl flatbuffers::SimpleToVerifyInSlice for ChannelKind {}
         #[deprecated(
             since = "2.0.0",
             note = "Use associated constants instead. This will no longer be generated in 2021."
         )]
-        pub const ENUM_MIN_TASK_EVENT_UNION: u8 = 0;
+        pub const ENUM_MIN_CHANNEL_OP: i8 = 0;
         #[deprecated(
             since = "2.0.0",
             note = "Use associated constants instead. This will no longer be generated in 2021."
         )]
-        pub const ENUM_MAX_TASK_EVENT_UNION: u8 = 5;
+        pub const ENUM_MAX_CHANNEL_OP: i8 = 3;
         #[deprecated(
             since = "2.0.0",
             note = "Use associated constants instead. This will no longer be generated in 2021."
         )]
         #[allow(non_camel_case_types)]
-        pub const ENUM_VALUES_TASK_EVENT_UNION: [TaskEventUnion; 6] = [
-            TaskEventUnion::NONE,
-            TaskEventUnion::Started,
-            TaskEventUnion::Output,
-            TaskEventUnion::Ready,
-            TaskEventUnion::Stopped,
-            TaskEventUnion::Error,
+        pub const ENUM_VALUES_CHANNEL_OP: [ChannelOp; 4] = [
+            ChannelOp::None,
+            ChannelOp::Send,
+            ChannelOp::Recv,
+            ChannelOp::Closed,
         ];
 
         #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
         #[repr(transparent)]
-        pub struct TaskEventUnion(pub u8);
+        pub struct ChannelOp(pub i8);
         #[allow(non_upper_case_globals)]
-        impl TaskEventUnion {
-            pub const NONE: Self = Self(0);
-            pub const Started: Self = Self(1);
-            pub const Output: Self = Self(2);
-            pub const Ready: Self = Self(3);
-            pub const Stopped: Self = Self(4);
-            pub const Error: Self = Self(5);
+        impl ChannelOp {
+            pub const None: Self = Self(0);
+            pub const Send: Self = Self(1);
+            pub const Recv: Self = Self(2);
+            pub const Closed: Self = Self(3);
 
-            pub const ENUM_MIN: u8 = 0;
-            pub const ENUM_MAX: u8 = 5;
+            pub const ENUM_MIN: i8 = 0;
+            pub const ENUM_MAX: i8 = 3;
             pub const ENUM_VALUES: &'static [Self] = &[
-                Self::NONE,
-                Self::Started,
-                Self::Output,
-                Self::Ready,
-                Self::Stopped,
-                Self::Error,
+                Self::None,
+                Self::Send,
+                Self::Recv,
+                Self::Closed,
             ];
             /// Returns the variant's name or "" if unknown.
             pub fn variant_name(self) -> Option<&'static str> {
                 match self {
-                    Self::NONE => Some("NONE"),
-                    Self::Started => Some("Started"),
-                    Self::Output => Some("Output"),
-                    Self::Ready => Some("Ready"),
-                    Self::Stopped => Some("Stopped"),
-                    Self::Error => Some("Error"),
+                    Self::None => Some("None"),
+                    Self::Send => Some("Send"),
+                    Self::Recv => Some("Recv"),
+                    Self::Closed => Some("Closed"),
                     _ => None,
                 }
             }
         }
-        impl core::fmt::Debug for TaskEventUnion {
+        impl core::fmt::Debug for ChannelOp {
             fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
                 if let Some(name) = self.variant_name() {
                     f.write_str(name)
@@ -643,212 +846,633 @@ pub mod tcrm {
                 }
             }
         }
-        impl<'a> flatbuffers::Follow<'a> for TaskEventUnion {
+        impl<'a> flatbuffers::Follow<'a> for ChannelOp {
             type Inner = Self;
             #[inline]
             unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
                 unsafe {
-                    let b = flatbuffers::read_scalar_at::<u8>(buf, loc);
+                    let b = flatbuffers::read_scalar_at::<i8>(buf, loc);
                     Self(b)
                 }
             }
         }
 
-        impl flatbuffers::Push for TaskEventUnion {
-            type Output = TaskEventUnion;
+        impl flatbuffers::Push for ChannelOp {
+            type Output = ChannelOp;
             #[inline]
             unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
                 unsafe {
-                    flatbuffers::emplace_scalar::<u8>(dst, self.0);
+                    flatbuffers::emplace_scalar::<i8>(dst, self.0);
                 }
             }
         }
 
-        impl flatbuffers::EndianScalar for TaskEventUnion {
-            type Scalar = u8;
+        impl flatbuffers::EndianScalar for ChannelOp {
+            type Scalar = i8;
             #[inline]
-            fn to_little_endian(self) -> u8 {
+            fn to_little_endian(self) -> i8 {
                 self.0.to_le()
             }
             #[inline]
             #[allow(clippy::wrong_self_convention)]
-            fn from_little_endian(v: u8) -> Self {
-                let b = u8::from_le(v);
+            fn from_little_endian(v: i8) -> Self {
+                let b = i8::from_le(v);
                 Self(b)
             }
         }
 
-        impl<'a> flatbuffers::Verifiable for TaskEventUnion {
+        impl<'a> flatbuffers::Verifiable for ChannelOp {
             #[inline]
             fn run_verifier(
                 v: &mut flatbuffers::Verifier,
                 pos: usize,
             ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
                 use self::flatbuffers::Verifiable;
-                u8::run_verifier(v, pos)
+                i8::run_verifier(v, pos)
             }
         }
 
-        impl flatbuffers::SimpleToVerifyInSlice for TaskEventUnion {}
-        pub struct TaskEventUnionUnionTableOffset {}
-
-        pub enum TaskConfigOffset {}
-        #[derive(Copy, Clone, PartialEq)]
+        impl flatbuffers::SimpleToVerifyInSlice for ChannelOp {}
+        #[deprecated(
+            since = "2.0.0",
+            note = "Use associated constants instead. This will no longer be generated in 2021."
+        )]
+        pub const ENUM_MIN_TASK_TERMINATE_REASON: i8 = 0;
+        #[deprecated(
+            since = "2.0.0",
+            note = "Use associated constants instead. This will no longer be generated in 2021."
+        )]
+        pub const ENUM_MAX_TASK_TERMINATE_REASON: i8 = 4;
+        #[deprecated(
+            since = "2.0.0",
+            note = "Use associated constants instead. This will no longer be generated in 2021."
+        )]
+        #[allow(non_camel_case_types)]
+        pub const ENUM_VALUES_TASK_TERMINATE_REASON: [TaskTerminateReason; 7] = [
+            TaskTerminateReason::Timeout,
+            TaskTerminateReason::Cleanup,
+            TaskTerminateReason::DependenciesFinished,
+            TaskTerminateReason::UserRequested,
+            TaskTerminateReason::CompletionIndicator,
+            TaskTerminateReason::ScriptTimeout,
+            TaskTerminateReason::Unhealthy,
+        ];
 
-        pub struct TaskConfig<'a> {
-            pub _tab: flatbuffers::Table<'a>,
-        }
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+        #[repr(transparent)]
+        pub struct TaskTerminateReason(pub i8);
+        #[allow(non_upper_case_globals)]
+        impl TaskTerminateReason {
+            pub const Timeout: Self = Self(0);
+            pub const Cleanup: Self = Self(1);
+            pub const DependenciesFinished: Self = Self(2);
+            pub const UserRequested: Self = Self(3);
+            pub const CompletionIndicator: Self = Self(4);
+            pub const ScriptTimeout: Self = Self(5);
+            pub const Unhealthy: Self = Self(6);
 
-        impl<'a> flatbuffers::Follow<'a> for TaskConfig<'a> {
-            type Inner = TaskConfig<'a>;
-            #[inline]
-            unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
-                unsafe {
-                    Self {
-                        _tab: flatbuffers::Table::new(buf, loc),
-                    }
+            pub const ENUM_MIN: i8 = 0;
+            pub const ENUM_MAX: i8 = 6;
+            pub const ENUM_VALUES: &'static [Self] = &[
+                Self::Timeout,
+                Self::Cleanup,
+                Self::DependenciesFinished,
+                Self::UserRequested,
+                Self::CompletionIndicator,
+                Self::ScriptTimeout,
+                Self::Unhealthy,
+            ];
+            /// Returns the variant's name or "" if unknown.
+            pub fn variant_name(self) -> Option<&'static str> {
+                match self {
+                    Self::Timeout => Some("Timeout"),
+                    Self::Cleanup => Some("Cleanup"),
+                    Self::DependenciesFinished => Some("DependenciesFinished"),
+                    Self::UserRequested => Some("UserRequested"),
+                    Self::CompletionIndicator => Some("CompletionIndicator"),
+                    Self::ScriptTimeout => Some("ScriptTimeout"),
+                    Self::Unhealthy => Some("Unhealthy"),
+                    _ => None,
                 }
             }
         }
-
-        impl<'a> TaskConfig<'a> {
-            pub const VT_COMMAND: flatbuffers::VOffsetT = 4;
-            pub const VT_ARGS: flatbuffers::VOffsetT = 6;
-            pub const VT_WORKING_DIR: flatbuffers::VOffsetT = 8;
-            pub const VT_ENV: flatbuffers::VOffsetT = 10;
-            pub const VT_TIMEOUT_MS: flatbuffers::VOffsetT = 12;
-            pub const VT_ENABLE_STDIN: flatbuffers::VOffsetT = 14;
-            pub const VT_READY_INDICATOR: flatbuffers::VOffsetT = 16;
-            pub const VT_READY_INDICATOR_SOURCE: flatbuffers::VOffsetT = 18;
-            pub const VT_USE_PROCESS_GROUP: flatbuffers::VOffsetT = 20;
-
-            #[inline]
-            pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-                TaskConfig { _tab: table }
-            }
-            #[allow(unused_mut)]
-            pub fn create<
-                'bldr: 'args,
-                'args: 'mut_bldr,
-                'mut_bldr,
-                A: flatbuffers::Allocator + 'bldr,
-            >(
-                _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
-                args: &'args TaskConfigArgs<'args>,
-            ) -> flatbuffers::WIPOffset<TaskConfig<'bldr>> {
-                let mut builder = TaskConfigBuilder::new(_fbb);
-                builder.add_timeout_ms(args.timeout_ms);
-                if let Some(x) = args.ready_indicator {
-                    builder.add_ready_indicator(x);
-                }
-                if let Some(x) = args.env {
-                    builder.add_env(x);
-                }
-                if let Some(x) = args.working_dir {
-                    builder.add_working_dir(x);
-                }
-                if let Some(x) = args.args {
-                    builder.add_args(x);
-                }
-                if let Some(x) = args.command {
-                    builder.add_command(x);
-                }
-                builder.add_use_process_group(args.use_process_group);
-                builder.add_ready_indicator_source(args.ready_indicator_source);
-                builder.add_enable_stdin(args.enable_stdin);
-                builder.finish()
-            }
-
-            #[inline]
-            pub fn command(&self) -> &'a str {
-                // Safety:
-                // Created from valid Table for this object
-                // which contains a valid value in this slot
-                unsafe {
-                    self._tab
-                        .get::<flatbuffers::ForwardsUOffset<&str>>(TaskConfig::VT_COMMAND, None)
-                        .unwrap()
-                }
-            }
-            #[inline]
-            pub fn args(
-                &self,
-            ) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>>
-            {
-                // Safety:
-                // Created from valid Table for this object
-                // which contains a valid value in this slot
-                unsafe {
-                    self._tab.get::<flatbuffers::ForwardsUOffset<
-                        flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>,
-                    >>(TaskConfig::VT_ARGS, None)
+        impl core::fmt::Debug for TaskTerminateReason {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                if let Some(name) = self.variant_name() {
+                    f.write_str(name)
+                } else {
+                    f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
                 }
             }
+        }
+        impl<'a> flatbuffers::Follow<'a> for TaskTerminateReason {
+            type Inner = Self;
             #[inline]
-            pub fn working_dir(&self) -> Option<&'a str> {
-                // Safety:
-                // Created from valid Table for this object
-                // which contains a valid value in this slot
+            unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
                 unsafe {
-                    self._tab
-                        .get::<flatbuffers::ForwardsUOffset<&str>>(TaskConfig::VT_WORKING_DIR, None)
+                    let b = flatbuffers::read_scalar_at::<i8>(buf, loc);
+                    Self(b)
                 }
             }
+        }
+
+        impl flatbuffers::Push for TaskTerminateReason {
+            type Output = TaskTerminateReason;
             #[inline]
-            pub fn env(
-                &self,
-            ) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<EnvEntry<'a>>>>
-            {
-                // Safety:
-                // Created from valid Table for this object
-                // which contains a valid value in this slot
+            unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
                 unsafe {
-                    self._tab.get::<flatbuffers::ForwardsUOffset<
-                        flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<EnvEntry>>,
-                    >>(TaskConfig::VT_ENV, None)
+                    flatbuffers::emplace_scalar::<i8>(dst, self.0);
                 }
             }
+        }
+
+        impl flatbuffers::EndianScalar for TaskTerminateReason {
+            type Scalar = i8;
             #[inline]
-            pub fn timeout_ms(&self) -> u64 {
-                // Safety:
-                // Created from valid Table for this object
-                // which contains a valid value in this slot
-                unsafe {
-                    self._tab
-                        .get::<u64>(TaskConfig::VT_TIMEOUT_MS, Some(0))
-                        .unwrap()
-                }
+            fn to_little_endian(self) -> i8 {
+                self.0.to_le()
             }
             #[inline]
-            pub fn enable_stdin(&self) -> bool {
-                // Safety:
-                // Created from valid Table for this object
-                // which contains a valid value in this slot
-                unsafe {
-                    self._tab
-                        .get::<bool>(TaskConfig::VT_ENABLE_STDIN, Some(false))
-                        .unwrap()
-                }
+            #[allow(clippy::wrong_self_convention)]
+            fn from_little_endian(v: i8) -> Self {
+                let b = i8::from_le(v);
+                Self(b)
             }
+        }
+
+        impl<'a> flatbuffers::Verifiable for TaskTerminateReason {
             #[inline]
-            pub fn ready_indicator(&self) -> Option<&'a str> {
-                // Safety:
-                // Created from valid Table for this object
-                // which contains a valid value in this slot
-                unsafe {
-                    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(
-                        TaskConfig::VT_READY_INDICATOR,
-                        None,
-                    )
-                }
+            fn run_verifier(
+                v: &mut flatbuffers::Verifier,
+                pos: usize,
+            ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+                use self::flatbuffers::Verifiable;
+                i8::run_verifier(v, pos)
             }
-            #[inline]
-            pub fn ready_indicator_source(&self) -> StreamSource {
-                // Safety:
-                // Created from valid Table for this object
-                // which contains a valid value in this slot
-                unsafe {
-                    self._tab
+        }
+
+        impl flatbuffers::SimpleToVerifyInSlice for TaskTerminateReason {}
+        #[deprecated(
+            since = "2.0.0",
+            note = "Use associated constants instead. This will no longer be generated in 2021."
+        )]
+        pub const ENUM_MIN_TASK_EVENT_STOP_REASON: u8 = 0;
+        #[deprecated(
+            since = "2.0.0",
+            note = "Use associated constants instead. This will no longer be generated in 2021."
+        )]
+        pub const ENUM_MAX_TASK_EVENT_STOP_REASON: u8 = 8;
+        #[deprecated(
+            since = "2.0.0",
+            note = "Use associated constants instead. This will no longer be generated in 2021."
+        )]
+        #[allow(non_camel_case_types)]
+        pub const ENUM_VALUES_TASK_EVENT_STOP_REASON: [TaskEventStopReason; 11] = [
+            TaskEventStopReason::NONE,
+            TaskEventStopReason::Finished,
+            TaskEventStopReason::TerminatedTimeout,
+            TaskEventStopReason::TerminatedCleanup,
+            TaskEventStopReason::TerminatedDependenciesFinished,
+            TaskEventStopReason::TerminatedUserRequested,
+            TaskEventStopReason::Error,
+            TaskEventStopReason::StartupFailure,
+            TaskEventStopReason::TerminatedCompletionIndicator,
+            TaskEventStopReason::TerminatedScriptTimeout,
+            TaskEventStopReason::TerminatedUnhealthy,
+        ];
+
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+        #[repr(transparent)]
+        pub struct TaskEventStopReason(pub u8);
+        #[allow(non_upper_case_globals)]
+        impl TaskEventStopReason {
+            pub const NONE: Self = Self(0);
+            pub const Finished: Self = Self(1);
+            pub const TerminatedTimeout: Self = Self(2);
+            pub const TerminatedCleanup: Self = Self(3);
+            pub const TerminatedDependenciesFinished: Self = Self(4);
+            pub const TerminatedUserRequested: Self = Self(5);
+            pub const Error: Self = Self(6);
+            pub const StartupFailure: Self = Self(7);
+            pub const TerminatedCompletionIndicator: Self = Self(8);
+            pub const TerminatedScriptTimeout: Self = Self(9);
+            pub const TerminatedUnhealthy: Self = Self(10);
+
+            pub const ENUM_MIN: u8 = 0;
+            pub const ENUM_MAX: u8 = 10;
+            pub const ENUM_VALUES: &'static [Self] = &[
+                Self::NONE,
+                Self::Finished,
+                Self::TerminatedTimeout,
+                Self::TerminatedCleanup,
+                Self::TerminatedDependenciesFinished,
+                Self::TerminatedUserRequested,
+                Self::Error,
+                Self::StartupFailure,
+                Self::TerminatedCompletionIndicator,
+                Self::TerminatedScriptTimeout,
+                Self::TerminatedUnhealthy,
+            ];
+            /// Returns the variant's name or "" if unknown.
+            pub fn variant_name(self) -> Option<&'static str> {
+                match self {
+                    Self::NONE => Some("NONE"),
+                    Self::Finished => Some("Finished"),
+                    Self::TerminatedTimeout => Some("TerminatedTimeout"),
+                    Self::TerminatedCleanup => Some("TerminatedCleanup"),
+                    Self::TerminatedDependenciesFinished => Some("TerminatedDependenciesFinished"),
+                    Self::TerminatedUserRequested => Some("TerminatedUserRequested"),
+                    Self::Error => Some("Error"),
+                    Self::StartupFailure => Some("StartupFailure"),
+                    Self::TerminatedCompletionIndicator => Some("TerminatedCompletionIndicator"),
+                    Self::TerminatedScriptTimeout => Some("TerminatedScriptTimeout"),
+                    Self::TerminatedUnhealthy => Some("TerminatedUnhealthy"),
+                    _ => None,
+                }
+            }
+        }
+        impl core::fmt::Debug for TaskEventStopReason {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                if let Some(name) = self.variant_name() {
+                    f.write_str(name)
+                } else {
+                    f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+                }
+            }
+        }
+        impl<'a> flatbuffers::Follow<'a> for TaskEventStopReason {
+            type Inner = Self;
+            #[inline]
+            unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+                unsafe {
+                    let b = flatbuffers::read_scalar_at::<u8>(buf, loc);
+                    Self(b)
+                }
+            }
+        }
+
+        impl flatbuffers::Push for TaskEventStopReason {
+            type Output = TaskEventStopReason;
+            #[inline]
+            unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+                unsafe {
+                    flatbuffers::emplace_scalar::<u8>(dst, self.0);
+                }
+            }
+        }
+
+        impl flatbuffers::EndianScalar for TaskEventStopReason {
+            type Scalar = u8;
+            #[inline]
+            fn to_little_endian(self) -> u8 {
+                self.0.to_le()
+            }
+            #[inline]
+            #[allow(clippy::wrong_self_convention)]
+            fn from_little_endian(v: u8) -> Self {
+                let b = u8::from_le(v);
+                Self(b)
+            }
+        }
+
+        impl<'a> flatbuffers::Verifiable for TaskEventStopReason {
+            #[inline]
+            fn run_verifier(
+                v: &mut flatbuffers::Verifier,
+                pos: usize,
+            ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+                use self::flatbuffers::Verifiable;
+                u8::run_verifier(v, pos)
+            }
+        }
+
+        impl flatbuffers::SimpleToVerifyInSlice for TaskEventStopReason {}
+        pub struct TaskEventStopReasonUnionTableOffset {}
+
+        #[deprecated(
+            since = "2.0.0",
+            note = "Use associated constants instead. This will no longer be generated in 2021."
+        )]
+        pub const ENUM_MIN_TASK_EVENT_UNION: u8 = 0;
+        #[deprecated(
+            since = "2.0.0",
+            note = "Use associated constants instead. This will no longer be generated in 2021."
+        )]
+        pub const ENUM_MAX_TASK_EVENT_UNION: u8 = 10;
+        #[deprecated(
+            since = "2.0.0",
+            note = "Use associated constants instead. This will no longer be generated in 2021."
+        )]
+        #[allow(non_camel_case_types)]
+        pub const ENUM_VALUES_TASK_EVENT_UNION: [TaskEventUnion; 16] = [
+            TaskEventUnion::NONE,
+            TaskEventUnion::Started,
+            TaskEventUnion::Output,
+            TaskEventUnion::Ready,
+            TaskEventUnion::Stopped,
+            TaskEventUnion::Error,
+            TaskEventUnion::RunningLong,
+            TaskEventUnion::OutputRepeated,
+            TaskEventUnion::Triggered,
+            TaskEventUnion::OutputSuppressed,
+            TaskEventUnion::MemoryWatermarkExceeded,
+            TaskEventUnion::SpawnRetry,
+            TaskEventUnion::ScriptStep,
+            TaskEventUnion::Unhealthy,
+            TaskEventUnion::SlowConsumer,
+            TaskEventUnion::OutputChunk,
+        ];
+
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+        #[repr(transparent)]
+        pub struct TaskEventUnion(pub u8);
+        #[allow(non_upper_case_globals)]
+        impl TaskEventUnion {
+            pub const NONE: Self = Self(0);
+            pub const Started: Self = Self(1);
+            pub const Output: Self = Self(2);
+            pub const Ready: Self = Self(3);
+            pub const Stopped: Self = Self(4);
+            pub const Error: Self = Self(5);
+            pub const RunningLong: Self = Self(6);
+            pub const OutputRepeated: Self = Self(7);
+            pub const Triggered: Self = Self(8);
+            pub const OutputSuppressed: Self = Self(9);
+            pub const MemoryWatermarkExceeded: Self = Self(10);
+            pub const SpawnRetry: Self = Self(11);
+            pub const ScriptStep: Self = Self(12);
+            pub const Unhealthy: Self = Self(13);
+            pub const SlowConsumer: Self = Self(14);
+            pub const OutputChunk: Self = Self(15);
+
+            pub const ENUM_MIN: u8 = 0;
+            pub const ENUM_MAX: u8 = 15;
+            pub const ENUM_VALUES: &'static [Self] = &[
+                Self::NONE,
+                Self::Started,
+                Self::Output,
+                Self::Ready,
+                Self::Stopped,
+                Self::Error,
+                Self::RunningLong,
+                Self::OutputRepeated,
+                Self::Triggered,
+                Self::OutputSuppressed,
+                Self::MemoryWatermarkExceeded,
+                Self::SpawnRetry,
+                Self::ScriptStep,
+                Self::Unhealthy,
+                Self::SlowConsumer,
+                Self::OutputChunk,
+            ];
+            /// Returns the variant's name or "" if unknown.
+            pub fn variant_name(self) -> Option<&'static str> {
+                match self {
+                    Self::NONE => Some("NONE"),
+                    Self::Started => Some("Started"),
+                    Self::Output => Some("Output"),
+                    Self::Ready => Some("Ready"),
+                    Self::Stopped => Some("Stopped"),
+                    Self::Error => Some("Error"),
+                    Self::RunningLong => Some("RunningLong"),
+                    Self::OutputRepeated => Some("OutputRepeated"),
+                    Self::Triggered => Some("Triggered"),
+                    Self::OutputSuppressed => Some("OutputSuppressed"),
+                    Self::MemoryWatermarkExceeded => Some("MemoryWatermarkExceeded"),
+                    Self::SpawnRetry => Some("SpawnRetry"),
+                    Self::ScriptStep => Some("ScriptStep"),
+                    Self::Unhealthy => Some("Unhealthy"),
+                    Self::SlowConsumer => Some("SlowConsumer"),
+                    Self::OutputChunk => Some("OutputChunk"),
+                    _ => None,
+                }
+            }
+        }
+        impl core::fmt::Debug for TaskEventUnion {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                if let Some(name) = self.variant_name() {
+                    f.write_str(name)
+                } else {
+                    f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+                }
+            }
+        }
+        impl<'a> flatbuffers::Follow<'a> for TaskEventUnion {
+            type Inner = Self;
+            #[inline]
+            unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+                unsafe {
+                    let b = flatbuffers::read_scalar_at::<u8>(buf, loc);
+                    Self(b)
+                }
+            }
+        }
+
+        impl flatbuffers::Push for TaskEventUnion {
+            type Output = TaskEventUnion;
+            #[inline]
+            unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+                unsafe {
+                    flatbuffers::emplace_scalar::<u8>(dst, self.0);
+                }
+            }
+        }
+
+        impl flatbuffers::EndianScalar for TaskEventUnion {
+            type Scalar = u8;
+            #[inline]
+            fn to_little_endian(self) -> u8 {
+                self.0.to_le()
+            }
+            #[inline]
+            #[allow(clippy::wrong_self_convention)]
+            fn from_little_endian(v: u8) -> Self {
+                let b = u8::from_le(v);
+                Self(b)
+            }
+        }
+
+        impl<'a> flatbuffers::Verifiable for TaskEventUnion {
+            #[inline]
+            fn run_verifier(
+                v: &mut flatbuffers::Verifier,
+                pos: usize,
+            ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+                use self::flatbuffers::Verifiable;
+                u8::run_verifier(v, pos)
+            }
+        }
+
+        impl flatbuffers::SimpleToVerifyInSlice for TaskEventUnion {}
+        pub struct TaskEventUnionUnionTableOffset {}
+
+        pub enum TaskConfigOffset {}
+        #[derive(Copy, Clone, PartialEq)]
+
+        pub struct TaskConfig<'a> {
+            pub _tab: flatbuffers::Table<'a>,
+        }
+
+        impl<'a> flatbuffers::Follow<'a> for TaskConfig<'a> {
+            type Inner = TaskConfig<'a>;
+            #[inline]
+            unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+                unsafe {
+                    Self {
+                        _tab: flatbuffers::Table::new(buf, loc),
+                    }
+                }
+            }
+        }
+
+        impl<'a> TaskConfig<'a> {
+            pub const VT_COMMAND: flatbuffers::VOffsetT = 4;
+            pub const VT_ARGS: flatbuffers::VOffsetT = 6;
+            pub const VT_WORKING_DIR: flatbuffers::VOffsetT = 8;
+            pub const VT_ENV: flatbuffers::VOffsetT = 10;
+            pub const VT_TIMEOUT_MS: flatbuffers::VOffsetT = 12;
+            pub const VT_ENABLE_STDIN: flatbuffers::VOffsetT = 14;
+            pub const VT_READY_INDICATOR: flatbuffers::VOffsetT = 16;
+            pub const VT_READY_INDICATOR_SOURCE: flatbuffers::VOffsetT = 18;
+            pub const VT_USE_PROCESS_GROUP: flatbuffers::VOffsetT = 20;
+            pub const VT_OUTPUT_TRIGGERS: flatbuffers::VOffsetT = 22;
+            pub const VT_READY_MATCH_MODE: flatbuffers::VOffsetT = 24;
+            pub const VT_READY_INDICATOR_REGEX: flatbuffers::VOffsetT = 26;
+
+            #[inline]
+            pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+                TaskConfig { _tab: table }
+            }
+            #[allow(unused_mut)]
+            pub fn create<
+                'bldr: 'args,
+                'args: 'mut_bldr,
+                'mut_bldr,
+                A: flatbuffers::Allocator + 'bldr,
+            >(
+                _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+                args: &'args TaskConfigArgs<'args>,
+            ) -> flatbuffers::WIPOffset<TaskConfig<'bldr>> {
+                let mut builder = TaskConfigBuilder::new(_fbb);
+                builder.add_timeout_ms(args.timeout_ms);
+                if let Some(x) = args.ready_indicator_regex {
+                    builder.add_ready_indicator_regex(x);
+                }
+                if let Some(x) = args.output_triggers {
+                    builder.add_output_triggers(x);
+                }
+                if let Some(x) = args.ready_indicator {
+                    builder.add_ready_indicator(x);
+                }
+                if let Some(x) = args.env {
+                    builder.add_env(x);
+                }
+                if let Some(x) = args.working_dir {
+                    builder.add_working_dir(x);
+                }
+                if let Some(x) = args.args {
+                    builder.add_args(x);
+                }
+                if let Some(x) = args.command {
+                    builder.add_command(x);
+                }
+                builder.add_ready_match_mode(args.ready_match_mode);
+                builder.add_use_process_group(args.use_process_group);
+                builder.add_ready_indicator_source(args.ready_indicator_source);
+                builder.add_enable_stdin(args.enable_stdin);
+                builder.finish()
+            }
+
+            #[inline]
+            pub fn command(&self) -> &'a str {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(TaskConfig::VT_COMMAND, None)
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn args(
+                &self,
+            ) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>>
+            {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab.get::<flatbuffers::ForwardsUOffset<
+                        flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>,
+                    >>(TaskConfig::VT_ARGS, None)
+                }
+            }
+            #[inline]
+            pub fn working_dir(&self) -> Option<&'a str> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(TaskConfig::VT_WORKING_DIR, None)
+                }
+            }
+            #[inline]
+            pub fn env(
+                &self,
+            ) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<EnvEntry<'a>>>>
+            {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab.get::<flatbuffers::ForwardsUOffset<
+                        flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<EnvEntry>>,
+                    >>(TaskConfig::VT_ENV, None)
+                }
+            }
+            #[inline]
+            pub fn timeout_ms(&self) -> u64 {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<u64>(TaskConfig::VT_TIMEOUT_MS, Some(0))
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn enable_stdin(&self) -> bool {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<bool>(TaskConfig::VT_ENABLE_STDIN, Some(false))
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn ready_indicator(&self) -> Option<&'a str> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(
+                        TaskConfig::VT_READY_INDICATOR,
+                        None,
+                    )
+                }
+            }
+            #[inline]
+            pub fn ready_indicator_source(&self) -> StreamSource {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
                         .get::<StreamSource>(
                             TaskConfig::VT_READY_INDICATOR_SOURCE,
                             Some(StreamSource::Stdout),
@@ -857,19 +1481,3691 @@ pub mod tcrm {
                 }
             }
             #[inline]
-            pub fn use_process_group(&self) -> bool {
+            pub fn use_process_group(&self) -> bool {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<bool>(TaskConfig::VT_USE_PROCESS_GROUP, Some(false))
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn output_triggers(
+                &self,
+            ) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<OutputTrigger<'a>>>>
+            {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab.get::<flatbuffers::ForwardsUOffset<
+                        flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<OutputTrigger>>,
+                    >>(TaskConfig::VT_OUTPUT_TRIGGERS, None)
+                }
+            }
+            #[inline]
+            pub fn ready_match_mode(&self) -> ReadyMatchMode {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<ReadyMatchMode>(
+                            TaskConfig::VT_READY_MATCH_MODE,
+                            Some(ReadyMatchMode::Contains),
+                        )
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn ready_indicator_regex(&self) -> Option<&'a str> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(
+                        TaskConfig::VT_READY_INDICATOR_REGEX,
+                        None,
+                    )
+                }
+            }
+        }
+
+        impl flatbuffers::Verifiable for TaskConfig<'_> {
+            #[inline]
+            fn run_verifier(
+                v: &mut flatbuffers::Verifier,
+                pos: usize,
+            ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+                use self::flatbuffers::Verifiable;
+                v.visit_table(pos)?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "command",
+                        Self::VT_COMMAND,
+                        true,
+                    )?
+                    .visit_field::<flatbuffers::ForwardsUOffset<
+                        flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<&'_ str>>,
+                    >>("args", Self::VT_ARGS, false)?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "working_dir",
+                        Self::VT_WORKING_DIR,
+                        false,
+                    )?
+                    .visit_field::<flatbuffers::ForwardsUOffset<
+                        flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<EnvEntry>>,
+                    >>("env", Self::VT_ENV, false)?
+                    .visit_field::<u64>("timeout_ms", Self::VT_TIMEOUT_MS, false)?
+                    .visit_field::<bool>("enable_stdin", Self::VT_ENABLE_STDIN, false)?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "ready_indicator",
+                        Self::VT_READY_INDICATOR,
+                        false,
+                    )?
+                    .visit_field::<StreamSource>(
+                        "ready_indicator_source",
+                        Self::VT_READY_INDICATOR_SOURCE,
+                        false,
+                    )?
+                    .visit_field::<bool>("use_process_group", Self::VT_USE_PROCESS_GROUP, false)?
+                    .visit_field::<flatbuffers::ForwardsUOffset<
+                        flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<OutputTrigger>>,
+                    >>("output_triggers", Self::VT_OUTPUT_TRIGGERS, false)?
+                    .visit_field::<ReadyMatchMode>(
+                        "ready_match_mode",
+                        Self::VT_READY_MATCH_MODE,
+                        false,
+                    )?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "ready_indicator_regex",
+                        Self::VT_READY_INDICATOR_REGEX,
+                        false,
+                    )?
+                    .finish();
+                Ok(())
+            }
+        }
+        pub struct TaskConfigArgs<'a> {
+            pub command: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub args: Option<
+                flatbuffers::WIPOffset<
+                    flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>,
+                >,
+            >,
+            pub working_dir: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub env: Option<
+                flatbuffers::WIPOffset<
+                    flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<EnvEntry<'a>>>,
+                >,
+            >,
+            pub timeout_ms: u64,
+            pub enable_stdin: bool,
+            pub ready_indicator: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub ready_indicator_source: StreamSource,
+            pub use_process_group: bool,
+            pub output_triggers: Option<
+                flatbuffers::WIPOffset<
+                    flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<OutputTrigger<'a>>>,
+                >,
+            >,
+            pub ready_match_mode: ReadyMatchMode,
+            pub ready_indicator_regex: Option<flatbuffers::WIPOffset<&'a str>>,
+        }
+        impl<'a> Default for TaskConfigArgs<'a> {
+            #[inline]
+            fn default() -> Self {
+                TaskConfigArgs {
+                    command: None, // required field
+                    args: None,
+                    working_dir: None,
+                    env: None,
+                    timeout_ms: 0,
+                    enable_stdin: false,
+                    ready_indicator: None,
+                    ready_indicator_source: StreamSource::Stdout,
+                    use_process_group: false,
+                    output_triggers: None,
+                    ready_match_mode: ReadyMatchMode::Contains,
+                    ready_indicator_regex: None,
+                }
+            }
+        }
+
+        pub struct TaskConfigBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+            fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+        }
+        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> TaskConfigBuilder<'a, 'b, A> {
+            #[inline]
+            pub fn add_command(&mut self, command: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_
+                    .push_slot_always::<flatbuffers::WIPOffset<_>>(TaskConfig::VT_COMMAND, command);
+            }
+            #[inline]
+            pub fn add_args(
+                &mut self,
+                args: flatbuffers::WIPOffset<
+                    flatbuffers::Vector<'b, flatbuffers::ForwardsUOffset<&'b str>>,
+                >,
+            ) {
+                self.fbb_
+                    .push_slot_always::<flatbuffers::WIPOffset<_>>(TaskConfig::VT_ARGS, args);
+            }
+            #[inline]
+            pub fn add_working_dir(&mut self, working_dir: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    TaskConfig::VT_WORKING_DIR,
+                    working_dir,
+                );
+            }
+            #[inline]
+            pub fn add_env(
+                &mut self,
+                env: flatbuffers::WIPOffset<
+                    flatbuffers::Vector<'b, flatbuffers::ForwardsUOffset<EnvEntry<'b>>>,
+                >,
+            ) {
+                self.fbb_
+                    .push_slot_always::<flatbuffers::WIPOffset<_>>(TaskConfig::VT_ENV, env);
+            }
+            #[inline]
+            pub fn add_timeout_ms(&mut self, timeout_ms: u64) {
+                self.fbb_
+                    .push_slot::<u64>(TaskConfig::VT_TIMEOUT_MS, timeout_ms, 0);
+            }
+            #[inline]
+            pub fn add_enable_stdin(&mut self, enable_stdin: bool) {
+                self.fbb_
+                    .push_slot::<bool>(TaskConfig::VT_ENABLE_STDIN, enable_stdin, false);
+            }
+            #[inline]
+            pub fn add_ready_indicator(
+                &mut self,
+                ready_indicator: flatbuffers::WIPOffset<&'b str>,
+            ) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    TaskConfig::VT_READY_INDICATOR,
+                    ready_indicator,
+                );
+            }
+            #[inline]
+            pub fn add_ready_indicator_source(&mut self, ready_indicator_source: StreamSource) {
+                self.fbb_.push_slot::<StreamSource>(
+                    TaskConfig::VT_READY_INDICATOR_SOURCE,
+                    ready_indicator_source,
+                    StreamSource::Stdout,
+                );
+            }
+            #[inline]
+            pub fn add_use_process_group(&mut self, use_process_group: bool) {
+                self.fbb_.push_slot::<bool>(
+                    TaskConfig::VT_USE_PROCESS_GROUP,
+                    use_process_group,
+                    false,
+                );
+            }
+            #[inline]
+            pub fn add_output_triggers(
+                &mut self,
+                output_triggers: flatbuffers::WIPOffset<
+                    flatbuffers::Vector<'b, flatbuffers::ForwardsUOffset<OutputTrigger<'b>>>,
+                >,
+            ) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    TaskConfig::VT_OUTPUT_TRIGGERS,
+                    output_triggers,
+                );
+            }
+            #[inline]
+            pub fn add_ready_match_mode(&mut self, ready_match_mode: ReadyMatchMode) {
+                self.fbb_.push_slot::<ReadyMatchMode>(
+                    TaskConfig::VT_READY_MATCH_MODE,
+                    ready_match_mode,
+                    ReadyMatchMode::Contains,
+                );
+            }
+            #[inline]
+            pub fn add_ready_indicator_regex(
+                &mut self,
+                ready_indicator_regex: flatbuffers::WIPOffset<&'b str>,
+            ) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    TaskConfig::VT_READY_INDICATOR_REGEX,
+                    ready_indicator_regex,
+                );
+            }
+            #[inline]
+            pub fn new(
+                _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            ) -> TaskConfigBuilder<'a, 'b, A> {
+                let start = _fbb.start_table();
+                TaskConfigBuilder {
+                    fbb_: _fbb,
+                    start_: start,
+                }
+            }
+            #[inline]
+            pub fn finish(self) -> flatbuffers::WIPOffset<TaskConfig<'a>> {
+                let o = self.fbb_.end_table(self.start_);
+                self.fbb_.required(o, TaskConfig::VT_COMMAND, "command");
+                flatbuffers::WIPOffset::new(o.value())
+            }
+        }
+
+        impl core::fmt::Debug for TaskConfig<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let mut ds = f.debug_struct("TaskConfig");
+                ds.field("command", &self.command());
+                ds.field("args", &self.args());
+                ds.field("working_dir", &self.working_dir());
+                ds.field("env", &self.env());
+                ds.field("timeout_ms", &self.timeout_ms());
+                ds.field("enable_stdin", &self.enable_stdin());
+                ds.field("ready_indicator", &self.ready_indicator());
+                ds.field("ready_indicator_source", &self.ready_indicator_source());
+                ds.field("use_process_group", &self.use_process_group());
+                ds.field("output_triggers", &self.output_triggers());
+                ds.field("ready_match_mode", &self.ready_match_mode());
+                ds.field("ready_indicator_regex", &self.ready_indicator_regex());
+                ds.finish()
+            }
+        }
+        pub enum EnvEntryOffset {}
+        #[derive(Copy, Clone, PartialEq)]
+
+        pub struct EnvEntry<'a> {
+            pub _tab: flatbuffers::Table<'a>,
+        }
+
+        impl<'a> flatbuffers::Follow<'a> for EnvEntry<'a> {
+            type Inner = EnvEntry<'a>;
+            #[inline]
+            unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+                unsafe {
+                    Self {
+                        _tab: flatbuffers::Table::new(buf, loc),
+                    }
+                }
+            }
+        }
+
+        impl<'a> EnvEntry<'a> {
+            pub const VT_KEY: flatbuffers::VOffsetT = 4;
+            pub const VT_VALUE: flatbuffers::VOffsetT = 6;
+
+            #[inline]
+            pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+                EnvEntry { _tab: table }
+            }
+            #[allow(unused_mut)]
+            pub fn create<
+                'bldr: 'args,
+                'args: 'mut_bldr,
+                'mut_bldr,
+                A: flatbuffers::Allocator + 'bldr,
+            >(
+                _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+                args: &'args EnvEntryArgs<'args>,
+            ) -> flatbuffers::WIPOffset<EnvEntry<'bldr>> {
+                let mut builder = EnvEntryBuilder::new(_fbb);
+                if let Some(x) = args.value {
+                    builder.add_value(x);
+                }
+                if let Some(x) = args.key {
+                    builder.add_key(x);
+                }
+                builder.finish()
+            }
+
+            #[inline]
+            pub fn key(&self) -> &'a str {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(EnvEntry::VT_KEY, None)
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn value(&self) -> &'a str {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(EnvEntry::VT_VALUE, None)
+                        .unwrap()
+                }
+            }
+        }
+
+        impl flatbuffers::Verifiable for EnvEntry<'_> {
+            #[inline]
+            fn run_verifier(
+                v: &mut flatbuffers::Verifier,
+                pos: usize,
+            ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+                use self::flatbuffers::Verifiable;
+                v.visit_table(pos)?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>("key", Self::VT_KEY, true)?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "value",
+                        Self::VT_VALUE,
+                        true,
+                    )?
+                    .finish();
+                Ok(())
+            }
+        }
+        pub struct EnvEntryArgs<'a> {
+            pub key: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub value: Option<flatbuffers::WIPOffset<&'a str>>,
+        }
+        impl<'a> Default for EnvEntryArgs<'a> {
+            #[inline]
+            fn default() -> Self {
+                EnvEntryArgs {
+                    key: None,   // required field
+                    value: None, // required field
+                }
+            }
+        }
+
+        pub struct EnvEntryBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+            fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+        }
+        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> EnvEntryBuilder<'a, 'b, A> {
+            #[inline]
+            pub fn add_key(&mut self, key: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_
+                    .push_slot_always::<flatbuffers::WIPOffset<_>>(EnvEntry::VT_KEY, key);
+            }
+            #[inline]
+            pub fn add_value(&mut self, value: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_
+                    .push_slot_always::<flatbuffers::WIPOffset<_>>(EnvEntry::VT_VALUE, value);
+            }
+            #[inline]
+            pub fn new(
+                _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            ) -> EnvEntryBuilder<'a, 'b, A> {
+                let start = _fbb.start_table();
+                EnvEntryBuilder {
+                    fbb_: _fbb,
+                    start_: start,
+                }
+            }
+            #[inline]
+            pub fn finish(self) -> flatbuffers::WIPOffset<EnvEntry<'a>> {
+                let o = self.fbb_.end_table(self.start_);
+                self.fbb_.required(o, EnvEntry::VT_KEY, "key");
+                self.fbb_.required(o, EnvEntry::VT_VALUE, "value");
+                flatbuffers::WIPOffset::new(o.value())
+            }
+        }
+
+        impl core::fmt::Debug for EnvEntry<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let mut ds = f.debug_struct("EnvEntry");
+                ds.field("key", &self.key());
+                ds.field("value", &self.value());
+                ds.finish()
+            }
+        }
+        pub enum OutputTriggerOffset {}
+        #[derive(Copy, Clone, PartialEq)]
+
+        pub struct OutputTrigger<'a> {
+            pub _tab: flatbuffers::Table<'a>,
+        }
+
+        impl<'a> flatbuffers::Follow<'a> for OutputTrigger<'a> {
+            type Inner = OutputTrigger<'a>;
+            #[inline]
+            unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+                unsafe {
+                    Self {
+                        _tab: flatbuffers::Table::new(buf, loc),
+                    }
+                }
+            }
+        }
+
+        impl<'a> OutputTrigger<'a> {
+            pub const VT_PATTERN: flatbuffers::VOffsetT = 4;
+            pub const VT_SOURCE: flatbuffers::VOffsetT = 6;
+            pub const VT_ACTION: flatbuffers::VOffsetT = 8;
+            pub const VT_EVENT_NAME: flatbuffers::VOffsetT = 10;
+
+            #[inline]
+            pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+                OutputTrigger { _tab: table }
+            }
+            #[allow(unused_mut)]
+            pub fn create<
+                'bldr: 'args,
+                'args: 'mut_bldr,
+                'mut_bldr,
+                A: flatbuffers::Allocator + 'bldr,
+            >(
+                _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+                args: &'args OutputTriggerArgs<'args>,
+            ) -> flatbuffers::WIPOffset<OutputTrigger<'bldr>> {
+                let mut builder = OutputTriggerBuilder::new(_fbb);
+                if let Some(x) = args.event_name {
+                    builder.add_event_name(x);
+                }
+                if let Some(x) = args.pattern {
+                    builder.add_pattern(x);
+                }
+                builder.add_action(args.action);
+                builder.add_source(args.source);
+                builder.finish()
+            }
+
+            #[inline]
+            pub fn pattern(&self) -> &'a str {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(OutputTrigger::VT_PATTERN, None)
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn source(&self) -> StreamSource {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<StreamSource>(OutputTrigger::VT_SOURCE, Some(StreamSource::Stdout))
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn action(&self) -> OutputTriggerAction {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<OutputTriggerAction>(
+                            OutputTrigger::VT_ACTION,
+                            Some(OutputTriggerAction::Ready),
+                        )
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn event_name(&self) -> Option<&'a str> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(OutputTrigger::VT_EVENT_NAME, None)
+                }
+            }
+        }
+
+        impl flatbuffers::Verifiable for OutputTrigger<'_> {
+            #[inline]
+            fn run_verifier(
+                v: &mut flatbuffers::Verifier,
+                pos: usize,
+            ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+                use self::flatbuffers::Verifiable;
+                v.visit_table(pos)?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "pattern",
+                        Self::VT_PATTERN,
+                        true,
+                    )?
+                    .visit_field::<StreamSource>("source", Self::VT_SOURCE, false)?
+                    .visit_field::<OutputTriggerAction>("action", Self::VT_ACTION, false)?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "event_name",
+                        Self::VT_EVENT_NAME,
+                        false,
+                    )?
+                    .finish();
+                Ok(())
+            }
+        }
+        pub struct OutputTriggerArgs<'a> {
+            pub pattern: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub source: StreamSource,
+            pub action: OutputTriggerAction,
+            pub event_name: Option<flatbuffers::WIPOffset<&'a str>>,
+        }
+        impl<'a> Default for OutputTriggerArgs<'a> {
+            #[inline]
+            fn default() -> Self {
+                OutputTriggerArgs {
+                    pattern: None, // required field
+                    source: StreamSource::Stdout,
+                    action: OutputTriggerAction::Ready,
+                    event_name: None,
+                }
+            }
+        }
+
+        pub struct OutputTriggerBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+            fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+        }
+        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> OutputTriggerBuilder<'a, 'b, A> {
+            #[inline]
+            pub fn add_pattern(&mut self, pattern: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_
+                    .push_slot_always::<flatbuffers::WIPOffset<_>>(OutputTrigger::VT_PATTERN, pattern);
+            }
+            #[inline]
+            pub fn add_source(&mut self, source: StreamSource) {
+                self.fbb_.push_slot::<StreamSource>(
+                    OutputTrigger::VT_SOURCE,
+                    source,
+                    StreamSource::Stdout,
+                );
+            }
+            #[inline]
+            pub fn add_action(&mut self, action: OutputTriggerAction) {
+                self.fbb_.push_slot::<OutputTriggerAction>(
+                    OutputTrigger::VT_ACTION,
+                    action,
+                    OutputTriggerAction::Ready,
+                );
+            }
+            #[inline]
+            pub fn add_event_name(&mut self, event_name: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    OutputTrigger::VT_EVENT_NAME,
+                    event_name,
+                );
+            }
+            #[inline]
+            pub fn new(
+                _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            ) -> OutputTriggerBuilder<'a, 'b, A> {
+                let start = _fbb.start_table();
+                OutputTriggerBuilder {
+                    fbb_: _fbb,
+                    start_: start,
+                }
+            }
+            #[inline]
+            pub fn finish(self) -> flatbuffers::WIPOffset<OutputTrigger<'a>> {
+                let o = self.fbb_.end_table(self.start_);
+                self.fbb_.required(o, OutputTrigger::VT_PATTERN, "pattern");
+                flatbuffers::WIPOffset::new(o.value())
+            }
+        }
+
+        impl core::fmt::Debug for OutputTrigger<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let mut ds = f.debug_struct("OutputTrigger");
+                ds.field("pattern", &self.pattern());
+                ds.field("source", &self.source());
+                ds.field("action", &self.action());
+                ds.field("event_name", &self.event_name());
+                ds.finish()
+            }
+        }
+        pub enum ExecutionPlanOffset {}
+        #[derive(Copy, Clone, PartialEq)]
+
+        pub struct ExecutionPlan<'a> {
+            pub _tab: flatbuffers::Table<'a>,
+        }
+
+        impl<'a> flatbuffers::Follow<'a> for ExecutionPlan<'a> {
+            type Inner = ExecutionPlan<'a>;
+            #[inline]
+            unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+                unsafe {
+                    Self {
+                        _tab: flatbuffers::Table::new(buf, loc),
+                    }
+                }
+            }
+        }
+
+        impl<'a> ExecutionPlan<'a> {
+            pub const VT_COMMAND: flatbuffers::VOffsetT = 4;
+            pub const VT_PROGRAM: flatbuffers::VOffsetT = 6;
+            pub const VT_ARGS: flatbuffers::VOffsetT = 8;
+            pub const VT_WORKING_DIR: flatbuffers::VOffsetT = 10;
+            pub const VT_ENV_OVERRIDES: flatbuffers::VOffsetT = 12;
+            pub const VT_TIMEOUT_MS: flatbuffers::VOffsetT = 14;
+            pub const VT_USE_PROCESS_GROUP: flatbuffers::VOffsetT = 16;
+            pub const VT_ENABLE_STDIN: flatbuffers::VOffsetT = 18;
+
+            #[inline]
+            pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+                ExecutionPlan { _tab: table }
+            }
+            #[allow(unused_mut)]
+            pub fn create<
+                'bldr: 'args,
+                'args: 'mut_bldr,
+                'mut_bldr,
+                A: flatbuffers::Allocator + 'bldr,
+            >(
+                _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+                args: &'args ExecutionPlanArgs<'args>,
+            ) -> flatbuffers::WIPOffset<ExecutionPlan<'bldr>> {
+                let mut builder = ExecutionPlanBuilder::new(_fbb);
+                if let Some(x) = args.timeout_ms {
+                    builder.add_timeout_ms(x);
+                }
+                if let Some(x) = args.env_overrides {
+                    builder.add_env_overrides(x);
+                }
+                if let Some(x) = args.working_dir {
+                    builder.add_working_dir(x);
+                }
+                if let Some(x) = args.args {
+                    builder.add_args(x);
+                }
+                if let Some(x) = args.program {
+                    builder.add_program(x);
+                }
+                if let Some(x) = args.command {
+                    builder.add_command(x);
+                }
+                builder.add_enable_stdin(args.enable_stdin);
+                builder.add_use_process_group(args.use_process_group);
+                builder.finish()
+            }
+
+            #[inline]
+            pub fn command(&self) -> &'a str {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(ExecutionPlan::VT_COMMAND, None)
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn program(&self) -> &'a str {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(ExecutionPlan::VT_PROGRAM, None)
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn args(
+                &self,
+            ) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>>
+            {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab.get::<flatbuffers::ForwardsUOffset<
+                        flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>,
+                    >>(ExecutionPlan::VT_ARGS, None)
+                }
+            }
+            #[inline]
+            pub fn working_dir(&self) -> &'a str {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(
+                            ExecutionPlan::VT_WORKING_DIR,
+                            None,
+                        )
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn env_overrides(
+                &self,
+            ) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<EnvEntry<'a>>>>
+            {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab.get::<flatbuffers::ForwardsUOffset<
+                        flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<EnvEntry>>,
+                    >>(ExecutionPlan::VT_ENV_OVERRIDES, None)
+                }
+            }
+            #[inline]
+            pub fn timeout_ms(&self) -> Option<u64> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe { self._tab.get::<u64>(ExecutionPlan::VT_TIMEOUT_MS, None) }
+            }
+            #[inline]
+            pub fn use_process_group(&self) -> bool {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<bool>(ExecutionPlan::VT_USE_PROCESS_GROUP, Some(false))
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn enable_stdin(&self) -> bool {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<bool>(ExecutionPlan::VT_ENABLE_STDIN, Some(false))
+                        .unwrap()
+                }
+            }
+        }
+
+        impl flatbuffers::Verifiable for ExecutionPlan<'_> {
+            #[inline]
+            fn run_verifier(
+                v: &mut flatbuffers::Verifier,
+                pos: usize,
+            ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+                use self::flatbuffers::Verifiable;
+                v.visit_table(pos)?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "command",
+                        Self::VT_COMMAND,
+                        true,
+                    )?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "program",
+                        Self::VT_PROGRAM,
+                        true,
+                    )?
+                    .visit_field::<flatbuffers::ForwardsUOffset<
+                        flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<&'_ str>>,
+                    >>("args", Self::VT_ARGS, false)?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "working_dir",
+                        Self::VT_WORKING_DIR,
+                        true,
+                    )?
+                    .visit_field::<flatbuffers::ForwardsUOffset<
+                        flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<EnvEntry>>,
+                    >>("env_overrides", Self::VT_ENV_OVERRIDES, false)?
+                    .visit_field::<u64>("timeout_ms", Self::VT_TIMEOUT_MS, false)?
+                    .visit_field::<bool>("use_process_group", Self::VT_USE_PROCESS_GROUP, false)?
+                    .visit_field::<bool>("enable_stdin", Self::VT_ENABLE_STDIN, false)?
+                    .finish();
+                Ok(())
+            }
+        }
+        pub struct ExecutionPlanArgs<'a> {
+            pub command: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub program: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub args: Option<
+                flatbuffers::WIPOffset<
+                    flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>,
+                >,
+            >,
+            pub working_dir: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub env_overrides: Option<
+                flatbuffers::WIPOffset<
+                    flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<EnvEntry<'a>>>,
+                >,
+            >,
+            pub timeout_ms: Option<u64>,
+            pub use_process_group: bool,
+            pub enable_stdin: bool,
+        }
+        impl<'a> Default for ExecutionPlanArgs<'a> {
+            #[inline]
+            fn default() -> Self {
+                ExecutionPlanArgs {
+                    command: None,     // required field
+                    program: None,     // required field
+                    args: None,
+                    working_dir: None, // required field
+                    env_overrides: None,
+                    timeout_ms: None,
+                    use_process_group: false,
+                    enable_stdin: false,
+                }
+            }
+        }
+
+        pub struct ExecutionPlanBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+            fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+        }
+        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> ExecutionPlanBuilder<'a, 'b, A> {
+            #[inline]
+            pub fn add_command(&mut self, command: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    ExecutionPlan::VT_COMMAND,
+                    command,
+                );
+            }
+            #[inline]
+            pub fn add_program(&mut self, program: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    ExecutionPlan::VT_PROGRAM,
+                    program,
+                );
+            }
+            #[inline]
+            pub fn add_args(
+                &mut self,
+                args: flatbuffers::WIPOffset<
+                    flatbuffers::Vector<'b, flatbuffers::ForwardsUOffset<&'b str>>,
+                >,
+            ) {
+                self.fbb_
+                    .push_slot_always::<flatbuffers::WIPOffset<_>>(ExecutionPlan::VT_ARGS, args);
+            }
+            #[inline]
+            pub fn add_working_dir(&mut self, working_dir: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    ExecutionPlan::VT_WORKING_DIR,
+                    working_dir,
+                );
+            }
+            #[inline]
+            pub fn add_env_overrides(
+                &mut self,
+                env_overrides: flatbuffers::WIPOffset<
+                    flatbuffers::Vector<'b, flatbuffers::ForwardsUOffset<EnvEntry<'b>>>,
+                >,
+            ) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    ExecutionPlan::VT_ENV_OVERRIDES,
+                    env_overrides,
+                );
+            }
+            #[inline]
+            pub fn add_timeout_ms(&mut self, timeout_ms: u64) {
+                self.fbb_
+                    .push_slot_always::<u64>(ExecutionPlan::VT_TIMEOUT_MS, timeout_ms);
+            }
+            #[inline]
+            pub fn add_use_process_group(&mut self, use_process_group: bool) {
+                self.fbb_.push_slot::<bool>(
+                    ExecutionPlan::VT_USE_PROCESS_GROUP,
+                    use_process_group,
+                    false,
+                );
+            }
+            #[inline]
+            pub fn add_enable_stdin(&mut self, enable_stdin: bool) {
+                self.fbb_
+                    .push_slot::<bool>(ExecutionPlan::VT_ENABLE_STDIN, enable_stdin, false);
+            }
+            #[inline]
+            pub fn new(
+                _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            ) -> ExecutionPlanBuilder<'a, 'b, A> {
+                let start = _fbb.start_table();
+                ExecutionPlanBuilder {
+                    fbb_: _fbb,
+                    start_: start,
+                }
+            }
+            #[inline]
+            pub fn finish(self) -> flatbuffers::WIPOffset<ExecutionPlan<'a>> {
+                let o = self.fbb_.end_table(self.start_);
+                self.fbb_.required(o, ExecutionPlan::VT_COMMAND, "command");
+                self.fbb_.required(o, ExecutionPlan::VT_PROGRAM, "program");
+                self.fbb_
+                    .required(o, ExecutionPlan::VT_WORKING_DIR, "working_dir");
+                flatbuffers::WIPOffset::new(o.value())
+            }
+        }
+
+        impl core::fmt::Debug for ExecutionPlan<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let mut ds = f.debug_struct("ExecutionPlan");
+                ds.field("command", &self.command());
+                ds.field("program", &self.program());
+                ds.field("args", &self.args());
+                ds.field("working_dir", &self.working_dir());
+                ds.field("env_overrides", &self.env_overrides());
+                ds.field("timeout_ms", &self.timeout_ms());
+                ds.field("use_process_group", &self.use_process_group());
+                ds.field("enable_stdin", &self.enable_stdin());
+                ds.finish()
+            }
+        }
+        pub enum AuditRecordOffset {}
+        #[derive(Copy, Clone, PartialEq)]
+
+        pub struct AuditRecord<'a> {
+            pub _tab: flatbuffers::Table<'a>,
+        }
+
+        impl<'a> flatbuffers::Follow<'a> for AuditRecord<'a> {
+            type Inner = AuditRecord<'a>;
+            #[inline]
+            unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+                unsafe {
+                    Self {
+                        _tab: flatbuffers::Table::new(buf, loc),
+                    }
+                }
+            }
+        }
+
+        impl<'a> AuditRecord<'a> {
+            pub const VT_TASK_NAME: flatbuffers::VOffsetT = 4;
+            pub const VT_COMMAND: flatbuffers::VOffsetT = 6;
+            pub const VT_ARGS: flatbuffers::VOffsetT = 8;
+            pub const VT_WORKING_DIR: flatbuffers::VOffsetT = 10;
+            pub const VT_ENV_KEYS: flatbuffers::VOffsetT = 12;
+            pub const VT_PROCESS_ID: flatbuffers::VOffsetT = 14;
+            pub const VT_STARTED_AT_MS: flatbuffers::VOffsetT = 16;
+            pub const VT_STOPPED_AT_MS: flatbuffers::VOffsetT = 18;
+            pub const VT_EXIT_CODE: flatbuffers::VOffsetT = 20;
+            pub const VT_SIGNAL: flatbuffers::VOffsetT = 22;
+            pub const VT_REASON_TYPE: flatbuffers::VOffsetT = 24;
+            pub const VT_REASON: flatbuffers::VOffsetT = 26;
+            pub const VT_OUTPUT_BYTES: flatbuffers::VOffsetT = 28;
+            pub const VT_TERMINATE_REQUESTS: flatbuffers::VOffsetT = 30;
+
+            #[inline]
+            pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+                AuditRecord { _tab: table }
+            }
+            #[allow(unused_mut)]
+            pub fn create<
+                'bldr: 'args,
+                'args: 'mut_bldr,
+                'mut_bldr,
+                A: flatbuffers::Allocator + 'bldr,
+            >(
+                _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+                args: &'args AuditRecordArgs<'args>,
+            ) -> flatbuffers::WIPOffset<AuditRecord<'bldr>> {
+                let mut builder = AuditRecordBuilder::new(_fbb);
+                builder.add_output_bytes(args.output_bytes);
+                builder.add_stopped_at_ms(args.stopped_at_ms);
+                builder.add_started_at_ms(args.started_at_ms);
+                if let Some(x) = args.terminate_requests {
+                    builder.add_terminate_requests(x);
+                }
+                if let Some(x) = args.reason {
+                    builder.add_reason(x);
+                }
+                if let Some(x) = args.signal {
+                    builder.add_signal(x);
+                }
+                if let Some(x) = args.exit_code {
+                    builder.add_exit_code(x);
+                }
+                if let Some(x) = args.process_id {
+                    builder.add_process_id(x);
+                }
+                if let Some(x) = args.env_keys {
+                    builder.add_env_keys(x);
+                }
+                if let Some(x) = args.working_dir {
+                    builder.add_working_dir(x);
+                }
+                if let Some(x) = args.args {
+                    builder.add_args(x);
+                }
+                if let Some(x) = args.command {
+                    builder.add_command(x);
+                }
+                if let Some(x) = args.task_name {
+                    builder.add_task_name(x);
+                }
+                builder.add_reason_type(args.reason_type);
+                builder.finish()
+            }
+
+            #[inline]
+            pub fn task_name(&self) -> &'a str {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(AuditRecord::VT_TASK_NAME, None)
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn command(&self) -> &'a str {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(AuditRecord::VT_COMMAND, None)
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn args(
+                &self,
+            ) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>>
+            {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab.get::<flatbuffers::ForwardsUOffset<
+                        flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>,
+                    >>(AuditRecord::VT_ARGS, None)
+                }
+            }
+            #[inline]
+            pub fn working_dir(&self) -> &'a str {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(AuditRecord::VT_WORKING_DIR, None)
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn env_keys(
+                &self,
+            ) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>>
+            {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab.get::<flatbuffers::ForwardsUOffset<
+                        flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>,
+                    >>(AuditRecord::VT_ENV_KEYS, None)
+                }
+            }
+            #[inline]
+            pub fn process_id(&self) -> Option<u32> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe { self._tab.get::<u32>(AuditRecord::VT_PROCESS_ID, None) }
+            }
+            #[inline]
+            pub fn started_at_ms(&self) -> u64 {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<u64>(AuditRecord::VT_STARTED_AT_MS, Some(0))
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn stopped_at_ms(&self) -> u64 {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<u64>(AuditRecord::VT_STOPPED_AT_MS, Some(0))
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn exit_code(&self) -> Option<i32> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe { self._tab.get::<i32>(AuditRecord::VT_EXIT_CODE, None) }
+            }
+            #[inline]
+            pub fn signal(&self) -> Option<i32> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe { self._tab.get::<i32>(AuditRecord::VT_SIGNAL, None) }
+            }
+            #[inline]
+            pub fn reason_type(&self) -> TaskEventStopReason {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<TaskEventStopReason>(
+                            AuditRecord::VT_REASON_TYPE,
+                            Some(TaskEventStopReason::NONE),
+                        )
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn reason(&self) -> flatbuffers::Table<'a> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<flatbuffers::Table<'a>>>(
+                            AuditRecord::VT_REASON,
+                            None,
+                        )
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn output_bytes(&self) -> u64 {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<u64>(AuditRecord::VT_OUTPUT_BYTES, Some(0))
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn terminate_requests(&self) -> Option<flatbuffers::Vector<'a, TaskTerminateReason>> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab.get::<flatbuffers::ForwardsUOffset<
+                        flatbuffers::Vector<'a, TaskTerminateReason>,
+                    >>(AuditRecord::VT_TERMINATE_REQUESTS, None)
+                }
+            }
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn reason_as_finished(&self) -> Option<DummyTable<'a>> {
+                if self.reason_type() == TaskEventStopReason::Finished {
+                    let u = self.reason();
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    Some(unsafe { DummyTable::init_from_table(u) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn reason_as_terminated_timeout(&self) -> Option<DummyTable<'a>> {
+                if self.reason_type() == TaskEventStopReason::TerminatedTimeout {
+                    let u = self.reason();
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    Some(unsafe { DummyTable::init_from_table(u) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn reason_as_terminated_cleanup(&self) -> Option<TerminatedStopReason<'a>> {
+                if self.reason_type() == TaskEventStopReason::TerminatedCleanup {
+                    let u = self.reason();
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    Some(unsafe { TerminatedStopReason::init_from_table(u) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn reason_as_terminated_dependencies_finished(&self) -> Option<DummyTable<'a>> {
+                if self.reason_type() == TaskEventStopReason::TerminatedDependenciesFinished {
+                    let u = self.reason();
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    Some(unsafe { DummyTable::init_from_table(u) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn reason_as_terminated_user_requested(&self) -> Option<TerminatedStopReason<'a>> {
+                if self.reason_type() == TaskEventStopReason::TerminatedUserRequested {
+                    let u = self.reason();
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    Some(unsafe { TerminatedStopReason::init_from_table(u) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn reason_as_error(&self) -> Option<ErrorStopReason<'a>> {
+                if self.reason_type() == TaskEventStopReason::Error {
+                    let u = self.reason();
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    Some(unsafe { ErrorStopReason::init_from_table(u) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn reason_as_startup_failure(&self) -> Option<ErrorStopReason<'a>> {
+                if self.reason_type() == TaskEventStopReason::StartupFailure {
+                    let u = self.reason();
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    Some(unsafe { ErrorStopReason::init_from_table(u) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn reason_as_terminated_completion_indicator(&self) -> Option<DummyTable<'a>> {
+                if self.reason_type() == TaskEventStopReason::TerminatedCompletionIndicator {
+                    let u = self.reason();
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    Some(unsafe { DummyTable::init_from_table(u) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn reason_as_terminated_script_timeout(&self) -> Option<DummyTable<'a>> {
+                if self.reason_type() == TaskEventStopReason::TerminatedScriptTimeout {
+                    let u = self.reason();
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    Some(unsafe { DummyTable::init_from_table(u) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn reason_as_terminated_unhealthy(&self) -> Option<DummyTable<'a>> {
+                if self.reason_type() == TaskEventStopReason::TerminatedUnhealthy {
+                    let u = self.reason();
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    Some(unsafe { DummyTable::init_from_table(u) })
+                } else {
+                    None
+                }
+            }
+        }
+
+        impl flatbuffers::Verifiable for AuditRecord<'_> {
+            #[inline]
+            fn run_verifier(
+                v: &mut flatbuffers::Verifier,
+                pos: usize,
+            ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+                use self::flatbuffers::Verifiable;
+                v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("task_name", Self::VT_TASK_NAME, true)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("command", Self::VT_COMMAND, true)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<&'_ str>>>>("args", Self::VT_ARGS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("working_dir", Self::VT_WORKING_DIR, true)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<&'_ str>>>>("env_keys", Self::VT_ENV_KEYS, false)?
+     .visit_field::<u32>("process_id", Self::VT_PROCESS_ID, false)?
+     .visit_field::<u64>("started_at_ms", Self::VT_STARTED_AT_MS, false)?
+     .visit_field::<u64>("stopped_at_ms", Self::VT_STOPPED_AT_MS, false)?
+     .visit_field::<i32>("exit_code", Self::VT_EXIT_CODE, false)?
+     .visit_field::<i32>("signal", Self::VT_SIGNAL, false)?
+     .visit_union::<TaskEventStopReason, _>("reason_type", Self::VT_REASON_TYPE, "reason", Self::VT_REASON, true, |key, v, pos| {
+        match key {
+          TaskEventStopReason::Finished => v.verify_union_variant::<flatbuffers::ForwardsUOffset<DummyTable>>("TaskEventStopReason::Finished", pos),
+          TaskEventStopReason::TerminatedTimeout => v.verify_union_variant::<flatbuffers::ForwardsUOffset<DummyTable>>("TaskEventStopReason::TerminatedTimeout", pos),
+          TaskEventStopReason::TerminatedCleanup => v.verify_union_variant::<flatbuffers::ForwardsUOffset<TerminatedStopReason>>("TaskEventStopReason::TerminatedCleanup", pos),
+          TaskEventStopReason::TerminatedDependenciesFinished => v.verify_union_variant::<flatbuffers::ForwardsUOffset<DummyTable>>("TaskEventStopReason::TerminatedDependenciesFinished", pos),
+          TaskEventStopReason::TerminatedUserRequested => v.verify_union_variant::<flatbuffers::ForwardsUOffset<TerminatedStopReason>>("TaskEventStopReason::TerminatedUserRequested", pos),
+          TaskEventStopReason::Error => v.verify_union_variant::<flatbuffers::ForwardsUOffset<ErrorStopReason>>("TaskEventStopReason::Error", pos),
+          TaskEventStopReason::StartupFailure => v.verify_union_variant::<flatbuffers::ForwardsUOffset<ErrorStopReason>>("TaskEventStopReason::StartupFailure", pos),
+          TaskEventStopReason::TerminatedCompletionIndicator => v.verify_union_variant::<flatbuffers::ForwardsUOffset<DummyTable>>("TaskEventStopReason::TerminatedCompletionIndicator", pos),
+          TaskEventStopReason::TerminatedScriptTimeout => v.verify_union_variant::<flatbuffers::ForwardsUOffset<DummyTable>>("TaskEventStopReason::TerminatedScriptTimeout", pos),
+          TaskEventStopReason::TerminatedUnhealthy => v.verify_union_variant::<flatbuffers::ForwardsUOffset<DummyTable>>("TaskEventStopReason::TerminatedUnhealthy", pos),
+          _ => Ok(()),
+        }
+     })?
+     .visit_field::<u64>("output_bytes", Self::VT_OUTPUT_BYTES, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, TaskTerminateReason>>>("terminate_requests", Self::VT_TERMINATE_REQUESTS, false)?
+     .finish();
+                Ok(())
+            }
+        }
+        pub struct AuditRecordArgs<'a> {
+            pub task_name: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub command: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub args: Option<
+                flatbuffers::WIPOffset<
+                    flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>,
+                >,
+            >,
+            pub working_dir: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub env_keys: Option<
+                flatbuffers::WIPOffset<
+                    flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>,
+                >,
+            >,
+            pub process_id: Option<u32>,
+            pub started_at_ms: u64,
+            pub stopped_at_ms: u64,
+            pub exit_code: Option<i32>,
+            pub signal: Option<i32>,
+            pub reason_type: TaskEventStopReason,
+            pub reason: Option<flatbuffers::WIPOffset<flatbuffers::UnionWIPOffset>>,
+            pub output_bytes: u64,
+            pub terminate_requests:
+                Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, TaskTerminateReason>>>,
+        }
+        impl<'a> Default for AuditRecordArgs<'a> {
+            #[inline]
+            fn default() -> Self {
+                AuditRecordArgs {
+                    task_name: None,   // required field
+                    command: None,     // required field
+                    args: None,
+                    working_dir: None, // required field
+                    env_keys: None,
+                    process_id: None,
+                    started_at_ms: 0,
+                    stopped_at_ms: 0,
+                    exit_code: None,
+                    signal: None,
+                    reason_type: TaskEventStopReason::NONE,
+                    reason: None, // required field
+                    output_bytes: 0,
+                    terminate_requests: None,
+                }
+            }
+        }
+
+        pub struct AuditRecordBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+            fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+        }
+        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> AuditRecordBuilder<'a, 'b, A> {
+            #[inline]
+            pub fn add_task_name(&mut self, task_name: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    AuditRecord::VT_TASK_NAME,
+                    task_name,
+                );
+            }
+            #[inline]
+            pub fn add_command(&mut self, command: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    AuditRecord::VT_COMMAND,
+                    command,
+                );
+            }
+            #[inline]
+            pub fn add_args(
+                &mut self,
+                args: flatbuffers::WIPOffset<
+                    flatbuffers::Vector<'b, flatbuffers::ForwardsUOffset<&'b str>>,
+                >,
+            ) {
+                self.fbb_
+                    .push_slot_always::<flatbuffers::WIPOffset<_>>(AuditRecord::VT_ARGS, args);
+            }
+            #[inline]
+            pub fn add_working_dir(&mut self, working_dir: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    AuditRecord::VT_WORKING_DIR,
+                    working_dir,
+                );
+            }
+            #[inline]
+            pub fn add_env_keys(
+                &mut self,
+                env_keys: flatbuffers::WIPOffset<
+                    flatbuffers::Vector<'b, flatbuffers::ForwardsUOffset<&'b str>>,
+                >,
+            ) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    AuditRecord::VT_ENV_KEYS,
+                    env_keys,
+                );
+            }
+            #[inline]
+            pub fn add_process_id(&mut self, process_id: u32) {
+                self.fbb_
+                    .push_slot_always::<u32>(AuditRecord::VT_PROCESS_ID, process_id);
+            }
+            #[inline]
+            pub fn add_started_at_ms(&mut self, started_at_ms: u64) {
+                self.fbb_
+                    .push_slot::<u64>(AuditRecord::VT_STARTED_AT_MS, started_at_ms, 0);
+            }
+            #[inline]
+            pub fn add_stopped_at_ms(&mut self, stopped_at_ms: u64) {
+                self.fbb_
+                    .push_slot::<u64>(AuditRecord::VT_STOPPED_AT_MS, stopped_at_ms, 0);
+            }
+            #[inline]
+            pub fn add_exit_code(&mut self, exit_code: i32) {
+                self.fbb_
+                    .push_slot_always::<i32>(AuditRecord::VT_EXIT_CODE, exit_code);
+            }
+            #[inline]
+            pub fn add_signal(&mut self, signal: i32) {
+                self.fbb_
+                    .push_slot_always::<i32>(AuditRecord::VT_SIGNAL, signal);
+            }
+            #[inline]
+            pub fn add_reason_type(&mut self, reason_type: TaskEventStopReason) {
+                self.fbb_.push_slot::<TaskEventStopReason>(
+                    AuditRecord::VT_REASON_TYPE,
+                    reason_type,
+                    TaskEventStopReason::NONE,
+                );
+            }
+            #[inline]
+            pub fn add_reason(
+                &mut self,
+                reason: flatbuffers::WIPOffset<flatbuffers::UnionWIPOffset>,
+            ) {
+                self.fbb_
+                    .push_slot_always::<flatbuffers::WIPOffset<_>>(AuditRecord::VT_REASON, reason);
+            }
+            #[inline]
+            pub fn add_output_bytes(&mut self, output_bytes: u64) {
+                self.fbb_
+                    .push_slot::<u64>(AuditRecord::VT_OUTPUT_BYTES, output_bytes, 0);
+            }
+            #[inline]
+            pub fn add_terminate_requests(
+                &mut self,
+                terminate_requests: flatbuffers::WIPOffset<
+                    flatbuffers::Vector<'b, TaskTerminateReason>,
+                >,
+            ) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    AuditRecord::VT_TERMINATE_REQUESTS,
+                    terminate_requests,
+                );
+            }
+            #[inline]
+            pub fn new(
+                _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            ) -> AuditRecordBuilder<'a, 'b, A> {
+                let start = _fbb.start_table();
+                AuditRecordBuilder {
+                    fbb_: _fbb,
+                    start_: start,
+                }
+            }
+            #[inline]
+            pub fn finish(self) -> flatbuffers::WIPOffset<AuditRecord<'a>> {
+                let o = self.fbb_.end_table(self.start_);
+                self.fbb_
+                    .required(o, AuditRecord::VT_TASK_NAME, "task_name");
+                self.fbb_.required(o, AuditRecord::VT_COMMAND, "command");
+                self.fbb_
+                    .required(o, AuditRecord::VT_WORKING_DIR, "working_dir");
+                self.fbb_.required(o, AuditRecord::VT_REASON, "reason");
+                flatbuffers::WIPOffset::new(o.value())
+            }
+        }
+
+        impl core::fmt::Debug for AuditRecord<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let mut ds = f.debug_struct("AuditRecord");
+                ds.field("task_name", &self.task_name());
+                ds.field("command", &self.command());
+                ds.field("args", &self.args());
+                ds.field("working_dir", &self.working_dir());
+                ds.field("env_keys", &self.env_keys());
+                ds.field("process_id", &self.process_id());
+                ds.field("started_at_ms", &self.started_at_ms());
+                ds.field("stopped_at_ms", &self.stopped_at_ms());
+                ds.field("exit_code", &self.exit_code());
+                ds.field("signal", &self.signal());
+                ds.field("reason_type", &self.reason_type());
+                match self.reason_type() {
+                    TaskEventStopReason::Finished => {
+                        if let Some(x) = self.reason_as_finished() {
+                            ds.field("reason", &x)
+                        } else {
+                            ds.field(
+                                "reason",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventStopReason::TerminatedTimeout => {
+                        if let Some(x) = self.reason_as_terminated_timeout() {
+                            ds.field("reason", &x)
+                        } else {
+                            ds.field(
+                                "reason",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventStopReason::TerminatedCleanup => {
+                        if let Some(x) = self.reason_as_terminated_cleanup() {
+                            ds.field("reason", &x)
+                        } else {
+                            ds.field(
+                                "reason",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventStopReason::TerminatedDependenciesFinished => {
+                        if let Some(x) = self.reason_as_terminated_dependencies_finished() {
+                            ds.field("reason", &x)
+                        } else {
+                            ds.field(
+                                "reason",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventStopReason::TerminatedUserRequested => {
+                        if let Some(x) = self.reason_as_terminated_user_requested() {
+                            ds.field("reason", &x)
+                        } else {
+                            ds.field(
+                                "reason",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventStopReason::Error => {
+                        if let Some(x) = self.reason_as_error() {
+                            ds.field("reason", &x)
+                        } else {
+                            ds.field(
+                                "reason",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventStopReason::StartupFailure => {
+                        if let Some(x) = self.reason_as_startup_failure() {
+                            ds.field("reason", &x)
+                        } else {
+                            ds.field(
+                                "reason",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventStopReason::TerminatedCompletionIndicator => {
+                        if let Some(x) = self.reason_as_terminated_completion_indicator() {
+                            ds.field("reason", &x)
+                        } else {
+                            ds.field(
+                                "reason",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventStopReason::TerminatedScriptTimeout => {
+                        if let Some(x) = self.reason_as_terminated_script_timeout() {
+                            ds.field("reason", &x)
+                        } else {
+                            ds.field(
+                                "reason",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventStopReason::TerminatedUnhealthy => {
+                        if let Some(x) = self.reason_as_terminated_unhealthy() {
+                            ds.field("reason", &x)
+                        } else {
+                            ds.field(
+                                "reason",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    _ => {
+                        let x: Option<()> = None;
+                        ds.field("reason", &x)
+                    }
+                };
+                ds.field("output_bytes", &self.output_bytes());
+                ds.field("terminate_requests", &self.terminate_requests());
+                ds.finish()
+            }
+        }
+        pub enum TaskErrorOffset {}
+        #[derive(Copy, Clone, PartialEq)]
+
+        pub struct TaskError<'a> {
+            pub _tab: flatbuffers::Table<'a>,
+        }
+
+        impl<'a> flatbuffers::Follow<'a> for TaskError<'a> {
+            type Inner = TaskError<'a>;
+            #[inline]
+            unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+                unsafe {
+                    Self {
+                        _tab: flatbuffers::Table::new(buf, loc),
+                    }
+                }
+            }
+        }
+
+        impl<'a> TaskError<'a> {
+            pub const VT_KIND: flatbuffers::VOffsetT = 4;
+            pub const VT_MESSAGE: flatbuffers::VOffsetT = 6;
+            pub const VT_DIAGNOSIS: flatbuffers::VOffsetT = 8;
+            pub const VT_DIAGNOSIS_PATH: flatbuffers::VOffsetT = 10;
+            pub const VT_DIAGNOSIS_DETAIL: flatbuffers::VOffsetT = 12;
+            pub const VT_CHANNEL_KIND: flatbuffers::VOffsetT = 14;
+            pub const VT_CHANNEL_OP: flatbuffers::VOffsetT = 16;
+            pub const VT_SEARCHED_PATH: flatbuffers::VOffsetT = 18;
+            pub const VT_PRE_EXEC_STEP: flatbuffers::VOffsetT = 20;
+
+            #[inline]
+            pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+                TaskError { _tab: table }
+            }
+            #[allow(unused_mut)]
+            pub fn create<
+                'bldr: 'args,
+                'args: 'mut_bldr,
+                'mut_bldr,
+                A: flatbuffers::Allocator + 'bldr,
+            >(
+                _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+                args: &'args TaskErrorArgs<'args>,
+            ) -> flatbuffers::WIPOffset<TaskError<'bldr>> {
+                let mut builder = TaskErrorBuilder::new(_fbb);
+                if let Some(x) = args.pre_exec_step {
+                    builder.add_pre_exec_step(x);
+                }
+                if let Some(x) = args.searched_path {
+                    builder.add_searched_path(x);
+                }
+                if let Some(x) = args.diagnosis_detail {
+                    builder.add_diagnosis_detail(x);
+                }
+                if let Some(x) = args.diagnosis_path {
+                    builder.add_diagnosis_path(x);
+                }
+                if let Some(x) = args.message {
+                    builder.add_message(x);
+                }
+                builder.add_channel_op(args.channel_op);
+                builder.add_channel_kind(args.channel_kind);
+                builder.add_diagnosis(args.diagnosis);
+                builder.add_kind(args.kind);
+                builder.finish()
+            }
+
+            #[inline]
+            pub fn kind(&self) -> TaskErrorType {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<TaskErrorType>(TaskError::VT_KIND, Some(TaskErrorType::IO))
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn message(&self) -> Option<&'a str> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(TaskError::VT_MESSAGE, None)
+                }
+            }
+            #[inline]
+            pub fn diagnosis(&self) -> SpawnDiagnosisKind {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<SpawnDiagnosisKind>(
+                            TaskError::VT_DIAGNOSIS,
+                            Some(SpawnDiagnosisKind::None),
+                        )
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn diagnosis_path(&self) -> Option<&'a str> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(
+                        TaskError::VT_DIAGNOSIS_PATH,
+                        None,
+                    )
+                }
+            }
+            #[inline]
+            pub fn diagnosis_detail(&self) -> Option<&'a str> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(
+                        TaskError::VT_DIAGNOSIS_DETAIL,
+                        None,
+                    )
+                }
+            }
+            #[inline]
+            pub fn channel_kind(&self) -> ChannelKind {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<ChannelKind>(TaskError::VT_CHANNEL_KIND, Some(ChannelKind::None))
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn channel_op(&self) -> ChannelOp {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<ChannelOp>(TaskError::VT_CHANNEL_OP, Some(ChannelOp::None))
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn searched_path(&self) -> Option<&'a str> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(
+                        TaskError::VT_SEARCHED_PATH,
+                        None,
+                    )
+                }
+            }
+            #[inline]
+            pub fn pre_exec_step(&self) -> Option<&'a str> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(
+                        TaskError::VT_PRE_EXEC_STEP,
+                        None,
+                    )
+                }
+            }
+        }
+
+        impl flatbuffers::Verifiable for TaskError<'_> {
+            #[inline]
+            fn run_verifier(
+                v: &mut flatbuffers::Verifier,
+                pos: usize,
+            ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+                use self::flatbuffers::Verifiable;
+                v.visit_table(pos)?
+                    .visit_field::<TaskErrorType>("kind", Self::VT_KIND, false)?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "message",
+                        Self::VT_MESSAGE,
+                        false,
+                    )?
+                    .visit_field::<SpawnDiagnosisKind>("diagnosis", Self::VT_DIAGNOSIS, false)?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "diagnosis_path",
+                        Self::VT_DIAGNOSIS_PATH,
+                        false,
+                    )?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "diagnosis_detail",
+                        Self::VT_DIAGNOSIS_DETAIL,
+                        false,
+                    )?
+                    .visit_field::<ChannelKind>("channel_kind", Self::VT_CHANNEL_KIND, false)?
+                    .visit_field::<ChannelOp>("channel_op", Self::VT_CHANNEL_OP, false)?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "searched_path",
+                        Self::VT_SEARCHED_PATH,
+                        false,
+                    )?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "pre_exec_step",
+                        Self::VT_PRE_EXEC_STEP,
+                        false,
+                    )?
+                    .finish();
+                Ok(())
+            }
+        }
+        pub struct TaskErrorArgs<'a> {
+            pub kind: TaskErrorType,
+            pub message: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub diagnosis: SpawnDiagnosisKind,
+            pub diagnosis_path: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub diagnosis_detail: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub channel_kind: ChannelKind,
+            pub channel_op: ChannelOp,
+            pub searched_path: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub pre_exec_step: Option<flatbuffers::WIPOffset<&'a str>>,
+        }
+        impl<'a> Default for TaskErrorArgs<'a> {
+            #[inline]
+            fn default() -> Self {
+                TaskErrorArgs {
+                    kind: TaskErrorType::IO,
+                    message: None,
+                    diagnosis: SpawnDiagnosisKind::None,
+                    diagnosis_path: None,
+                    diagnosis_detail: None,
+                    channel_kind: ChannelKind::None,
+                    channel_op: ChannelOp::None,
+                    searched_path: None,
+                    pre_exec_step: None,
+                }
+            }
+        }
+
+        pub struct TaskErrorBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+            fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+        }
+        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> TaskErrorBuilder<'a, 'b, A> {
+            #[inline]
+            pub fn add_kind(&mut self, kind: TaskErrorType) {
+                self.fbb_
+                    .push_slot::<TaskErrorType>(TaskError::VT_KIND, kind, TaskErrorType::IO);
+            }
+            #[inline]
+            pub fn add_message(&mut self, message: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_
+                    .push_slot_always::<flatbuffers::WIPOffset<_>>(TaskError::VT_MESSAGE, message);
+            }
+            #[inline]
+            pub fn add_diagnosis(&mut self, diagnosis: SpawnDiagnosisKind) {
+                self.fbb_.push_slot::<SpawnDiagnosisKind>(
+                    TaskError::VT_DIAGNOSIS,
+                    diagnosis,
+                    SpawnDiagnosisKind::None,
+                );
+            }
+            #[inline]
+            pub fn add_diagnosis_path(&mut self, diagnosis_path: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    TaskError::VT_DIAGNOSIS_PATH,
+                    diagnosis_path,
+                );
+            }
+            #[inline]
+            pub fn add_diagnosis_detail(
+                &mut self,
+                diagnosis_detail: flatbuffers::WIPOffset<&'b str>,
+            ) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    TaskError::VT_DIAGNOSIS_DETAIL,
+                    diagnosis_detail,
+                );
+            }
+            #[inline]
+            pub fn add_channel_kind(&mut self, channel_kind: ChannelKind) {
+                self.fbb_.push_slot::<ChannelKind>(
+                    TaskError::VT_CHANNEL_KIND,
+                    channel_kind,
+                    ChannelKind::None,
+                );
+            }
+            #[inline]
+            pub fn add_channel_op(&mut self, channel_op: ChannelOp) {
+                self.fbb_.push_slot::<ChannelOp>(
+                    TaskError::VT_CHANNEL_OP,
+                    channel_op,
+                    ChannelOp::None,
+                );
+            }
+            #[inline]
+            pub fn add_searched_path(&mut self, searched_path: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    TaskError::VT_SEARCHED_PATH,
+                    searched_path,
+                );
+            }
+            #[inline]
+            pub fn add_pre_exec_step(&mut self, pre_exec_step: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    TaskError::VT_PRE_EXEC_STEP,
+                    pre_exec_step,
+                );
+            }
+            #[inline]
+            pub fn new(
+                _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            ) -> TaskErrorBuilder<'a, 'b, A> {
+                let start = _fbb.start_table();
+                TaskErrorBuilder {
+                    fbb_: _fbb,
+                    start_: start,
+                }
+            }
+            #[inline]
+            pub fn finish(self) -> flatbuffers::WIPOffset<TaskError<'a>> {
+                let o = self.fbb_.end_table(self.start_);
+                flatbuffers::WIPOffset::new(o.value())
+            }
+        }
+
+        impl core::fmt::Debug for TaskError<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let mut ds = f.debug_struct("TaskError");
+                ds.field("kind", &self.kind());
+                ds.field("message", &self.message());
+                ds.field("diagnosis", &self.diagnosis());
+                ds.field("diagnosis_path", &self.diagnosis_path());
+                ds.field("diagnosis_detail", &self.diagnosis_detail());
+                ds.field("channel_kind", &self.channel_kind());
+                ds.field("channel_op", &self.channel_op());
+                ds.field("searched_path", &self.searched_path());
+                ds.field("pre_exec_step", &self.pre_exec_step());
+                ds.finish()
+            }
+        }
+        pub enum DummyTableOffset {}
+        #[derive(Copy, Clone, PartialEq)]
+
+        pub struct DummyTable<'a> {
+            pub _tab: flatbuffers::Table<'a>,
+        }
+
+        impl<'a> flatbuffers::Follow<'a> for DummyTable<'a> {
+            type Inner = DummyTable<'a>;
+            #[inline]
+            unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+                unsafe {
+                    Self {
+                        _tab: flatbuffers::Table::new(buf, loc),
+                    }
+                }
+            }
+        }
+
+        impl<'a> DummyTable<'a> {
+            #[inline]
+            pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+                DummyTable { _tab: table }
+            }
+            #[allow(unused_mut)]
+            pub fn create<
+                'bldr: 'args,
+                'args: 'mut_bldr,
+                'mut_bldr,
+                A: flatbuffers::Allocator + 'bldr,
+            >(
+                _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+                _args: &'args DummyTableArgs,
+            ) -> flatbuffers::WIPOffset<DummyTable<'bldr>> {
+                let mut builder = DummyTableBuilder::new(_fbb);
+                builder.finish()
+            }
+        }
+
+        impl flatbuffers::Verifiable for DummyTable<'_> {
+            #[inline]
+            fn run_verifier(
+                v: &mut flatbuffers::Verifier,
+                pos: usize,
+            ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+                use self::flatbuffers::Verifiable;
+                v.visit_table(pos)?.finish();
+                Ok(())
+            }
+        }
+        pub struct DummyTableArgs {}
+        impl<'a> Default for DummyTableArgs {
+            #[inline]
+            fn default() -> Self {
+                DummyTableArgs {}
+            }
+        }
+
+        pub struct DummyTableBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+            fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+        }
+        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> DummyTableBuilder<'a, 'b, A> {
+            #[inline]
+            pub fn new(
+                _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            ) -> DummyTableBuilder<'a, 'b, A> {
+                let start = _fbb.start_table();
+                DummyTableBuilder {
+                    fbb_: _fbb,
+                    start_: start,
+                }
+            }
+            #[inline]
+            pub fn finish(self) -> flatbuffers::WIPOffset<DummyTable<'a>> {
+                let o = self.fbb_.end_table(self.start_);
+                flatbuffers::WIPOffset::new(o.value())
+            }
+        }
+
+        impl core::fmt::Debug for DummyTable<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let mut ds = f.debug_struct("DummyTable");
+                ds.finish()
+            }
+        }
+        pub enum ErrorStopReasonOffset {}
+        #[derive(Copy, Clone, PartialEq)]
+
+        pub struct ErrorStopReason<'a> {
+            pub _tab: flatbuffers::Table<'a>,
+        }
+
+        impl<'a> flatbuffers::Follow<'a> for ErrorStopReason<'a> {
+            type Inner = ErrorStopReason<'a>;
+            #[inline]
+            unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+                unsafe {
+                    Self {
+                        _tab: flatbuffers::Table::new(buf, loc),
+                    }
+                }
+            }
+        }
+
+        impl<'a> ErrorStopReason<'a> {
+            pub const VT_MESSAGE: flatbuffers::VOffsetT = 4;
+
+            #[inline]
+            pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+                ErrorStopReason { _tab: table }
+            }
+            #[allow(unused_mut)]
+            pub fn create<
+                'bldr: 'args,
+                'args: 'mut_bldr,
+                'mut_bldr,
+                A: flatbuffers::Allocator + 'bldr,
+            >(
+                _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+                args: &'args ErrorStopReasonArgs<'args>,
+            ) -> flatbuffers::WIPOffset<ErrorStopReason<'bldr>> {
+                let mut builder = ErrorStopReasonBuilder::new(_fbb);
+                if let Some(x) = args.message {
+                    builder.add_message(x);
+                }
+                builder.finish()
+            }
+
+            #[inline]
+            pub fn message(&self) -> &'a str {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(
+                            ErrorStopReason::VT_MESSAGE,
+                            None,
+                        )
+                        .unwrap()
+                }
+            }
+        }
+
+        impl flatbuffers::Verifiable for ErrorStopReason<'_> {
+            #[inline]
+            fn run_verifier(
+                v: &mut flatbuffers::Verifier,
+                pos: usize,
+            ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+                use self::flatbuffers::Verifiable;
+                v.visit_table(pos)?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "message",
+                        Self::VT_MESSAGE,
+                        true,
+                    )?
+                    .finish();
+                Ok(())
+            }
+        }
+        pub struct ErrorStopReasonArgs<'a> {
+            pub message: Option<flatbuffers::WIPOffset<&'a str>>,
+        }
+        impl<'a> Default for ErrorStopReasonArgs<'a> {
+            #[inline]
+            fn default() -> Self {
+                ErrorStopReasonArgs {
+                    message: None, // required field
+                }
+            }
+        }
+
+        pub struct ErrorStopReasonBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+            fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+        }
+        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> ErrorStopReasonBuilder<'a, 'b, A> {
+            #[inline]
+            pub fn add_message(&mut self, message: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    ErrorStopReason::VT_MESSAGE,
+                    message,
+                );
+            }
+            #[inline]
+            pub fn new(
+                _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            ) -> ErrorStopReasonBuilder<'a, 'b, A> {
+                let start = _fbb.start_table();
+                ErrorStopReasonBuilder {
+                    fbb_: _fbb,
+                    start_: start,
+                }
+            }
+            #[inline]
+            pub fn finish(self) -> flatbuffers::WIPOffset<ErrorStopReason<'a>> {
+                let o = self.fbb_.end_table(self.start_);
+                self.fbb_
+                    .required(o, ErrorStopReason::VT_MESSAGE, "message");
+                flatbuffers::WIPOffset::new(o.value())
+            }
+        }
+
+        impl core::fmt::Debug for ErrorStopReason<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let mut ds = f.debug_struct("ErrorStopReason");
+                ds.field("message", &self.message());
+                ds.finish()
+            }
+        }
+        pub enum TerminatedStopReasonOffset {}
+        #[derive(Copy, Clone, PartialEq)]
+
+        pub struct TerminatedStopReason<'a> {
+            pub _tab: flatbuffers::Table<'a>,
+        }
+
+        impl<'a> flatbuffers::Follow<'a> for TerminatedStopReason<'a> {
+            type Inner = TerminatedStopReason<'a>;
+            #[inline]
+            unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+                unsafe {
+                    Self {
+                        _tab: flatbuffers::Table::new(buf, loc),
+                    }
+                }
+            }
+        }
+
+        impl<'a> TerminatedStopReason<'a> {
+            pub const VT_MESSAGE: flatbuffers::VOffsetT = 4;
+
+            #[inline]
+            pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+                TerminatedStopReason { _tab: table }
+            }
+            #[allow(unused_mut)]
+            pub fn create<
+                'bldr: 'args,
+                'args: 'mut_bldr,
+                'mut_bldr,
+                A: flatbuffers::Allocator + 'bldr,
+            >(
+                _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+                args: &'args TerminatedStopReasonArgs<'args>,
+            ) -> flatbuffers::WIPOffset<TerminatedStopReason<'bldr>> {
+                let mut builder = TerminatedStopReasonBuilder::new(_fbb);
+                if let Some(x) = args.message {
+                    builder.add_message(x);
+                }
+                builder.finish()
+            }
+
+            #[inline]
+            pub fn message(&self) -> Option<&'a str> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(
+                        TerminatedStopReason::VT_MESSAGE,
+                        None,
+                    )
+                }
+            }
+        }
+
+        impl flatbuffers::Verifiable for TerminatedStopReason<'_> {
+            #[inline]
+            fn run_verifier(
+                v: &mut flatbuffers::Verifier,
+                pos: usize,
+            ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+                use self::flatbuffers::Verifiable;
+                v.visit_table(pos)?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "message",
+                        Self::VT_MESSAGE,
+                        false,
+                    )?
+                    .finish();
+                Ok(())
+            }
+        }
+        pub struct TerminatedStopReasonArgs<'a> {
+            pub message: Option<flatbuffers::WIPOffset<&'a str>>,
+        }
+        impl<'a> Default for TerminatedStopReasonArgs<'a> {
+            #[inline]
+            fn default() -> Self {
+                TerminatedStopReasonArgs { message: None }
+            }
+        }
+
+        pub struct TerminatedStopReasonBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+            fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+        }
+        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> TerminatedStopReasonBuilder<'a, 'b, A> {
+            #[inline]
+            pub fn add_message(&mut self, message: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    TerminatedStopReason::VT_MESSAGE,
+                    message,
+                );
+            }
+            #[inline]
+            pub fn new(
+                _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            ) -> TerminatedStopReasonBuilder<'a, 'b, A> {
+                let start = _fbb.start_table();
+                TerminatedStopReasonBuilder {
+                    fbb_: _fbb,
+                    start_: start,
+                }
+            }
+            #[inline]
+            pub fn finish(self) -> flatbuffers::WIPOffset<TerminatedStopReason<'a>> {
+                let o = self.fbb_.end_table(self.start_);
+                flatbuffers::WIPOffset::new(o.value())
+            }
+        }
+
+        impl core::fmt::Debug for TerminatedStopReason<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let mut ds = f.debug_struct("TerminatedStopReason");
+                ds.field("message", &self.message());
+                ds.finish()
+            }
+        }
+        pub enum StartedEventOffset {}
+        #[derive(Copy, Clone, PartialEq)]
+
+        pub struct StartedEvent<'a> {
+            pub _tab: flatbuffers::Table<'a>,
+        }
+
+        impl<'a> flatbuffers::Follow<'a> for StartedEvent<'a> {
+            type Inner = StartedEvent<'a>;
+            #[inline]
+            unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+                unsafe {
+                    Self {
+                        _tab: flatbuffers::Table::new(buf, loc),
+                    }
+                }
+            }
+        }
+
+        impl<'a> StartedEvent<'a> {
+            pub const VT_TASK_NAME: flatbuffers::VOffsetT = 4;
+            pub const VT_PROCESS_ID: flatbuffers::VOffsetT = 6;
+            pub const VT_TEMP_DIR: flatbuffers::VOffsetT = 8;
+            pub const VT_WORKING_DIR: flatbuffers::VOffsetT = 10;
+
+            #[inline]
+            pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+                StartedEvent { _tab: table }
+            }
+            #[allow(unused_mut)]
+            pub fn create<
+                'bldr: 'args,
+                'args: 'mut_bldr,
+                'mut_bldr,
+                A: flatbuffers::Allocator + 'bldr,
+            >(
+                _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+                args: &'args StartedEventArgs<'args>,
+            ) -> flatbuffers::WIPOffset<StartedEvent<'bldr>> {
+                let mut builder = StartedEventBuilder::new(_fbb);
+                if let Some(x) = args.working_dir {
+                    builder.add_working_dir(x);
+                }
+                if let Some(x) = args.temp_dir {
+                    builder.add_temp_dir(x);
+                }
+                builder.add_process_id(args.process_id);
+                if let Some(x) = args.task_name {
+                    builder.add_task_name(x);
+                }
+                builder.finish()
+            }
+
+            #[inline]
+            pub fn task_name(&self) -> &'a str {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(StartedEvent::VT_TASK_NAME, None)
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn process_id(&self) -> u32 {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<u32>(StartedEvent::VT_PROCESS_ID, Some(0))
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn temp_dir(&self) -> Option<&'a str> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(StartedEvent::VT_TEMP_DIR, None)
+                }
+            }
+            #[inline]
+            pub fn working_dir(&self) -> Option<&'a str> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(
+                        StartedEvent::VT_WORKING_DIR,
+                        None,
+                    )
+                }
+            }
+        }
+
+        impl flatbuffers::Verifiable for StartedEvent<'_> {
+            #[inline]
+            fn run_verifier(
+                v: &mut flatbuffers::Verifier,
+                pos: usize,
+            ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+                use self::flatbuffers::Verifiable;
+                v.visit_table(pos)?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "task_name",
+                        Self::VT_TASK_NAME,
+                        true,
+                    )?
+                    .visit_field::<u32>("process_id", Self::VT_PROCESS_ID, false)?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "temp_dir",
+                        Self::VT_TEMP_DIR,
+                        false,
+                    )?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "working_dir",
+                        Self::VT_WORKING_DIR,
+                        false,
+                    )?
+                    .finish();
+                Ok(())
+            }
+        }
+        pub struct StartedEventArgs<'a> {
+            pub task_name: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub process_id: u32,
+            pub temp_dir: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub working_dir: Option<flatbuffers::WIPOffset<&'a str>>,
+        }
+        impl<'a> Default for StartedEventArgs<'a> {
+            #[inline]
+            fn default() -> Self {
+                StartedEventArgs {
+                    task_name: None, // required field
+                    process_id: 0,
+                    temp_dir: None,
+                    working_dir: None,
+                }
+            }
+        }
+
+        pub struct StartedEventBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+            fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+        }
+        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> StartedEventBuilder<'a, 'b, A> {
+            #[inline]
+            pub fn add_task_name(&mut self, task_name: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    StartedEvent::VT_TASK_NAME,
+                    task_name,
+                );
+            }
+            #[inline]
+            pub fn add_process_id(&mut self, process_id: u32) {
+                self.fbb_
+                    .push_slot::<u32>(StartedEvent::VT_PROCESS_ID, process_id, 0);
+            }
+            #[inline]
+            pub fn add_temp_dir(&mut self, temp_dir: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    StartedEvent::VT_TEMP_DIR,
+                    temp_dir,
+                );
+            }
+            #[inline]
+            pub fn add_working_dir(&mut self, working_dir: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    StartedEvent::VT_WORKING_DIR,
+                    working_dir,
+                );
+            }
+            #[inline]
+            pub fn new(
+                _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            ) -> StartedEventBuilder<'a, 'b, A> {
+                let start = _fbb.start_table();
+                StartedEventBuilder {
+                    fbb_: _fbb,
+                    start_: start,
+                }
+            }
+            #[inline]
+            pub fn finish(self) -> flatbuffers::WIPOffset<StartedEvent<'a>> {
+                let o = self.fbb_.end_table(self.start_);
+                self.fbb_
+                    .required(o, StartedEvent::VT_TASK_NAME, "task_name");
+                flatbuffers::WIPOffset::new(o.value())
+            }
+        }
+
+        impl core::fmt::Debug for StartedEvent<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let mut ds = f.debug_struct("StartedEvent");
+                ds.field("task_name", &self.task_name());
+                ds.field("process_id", &self.process_id());
+                ds.field("temp_dir", &self.temp_dir());
+                ds.field("working_dir", &self.working_dir());
+                ds.finish()
+            }
+        }
+        pub enum OutputEventOffset {}
+        #[derive(Copy, Clone, PartialEq)]
+
+        pub struct OutputEvent<'a> {
+            pub _tab: flatbuffers::Table<'a>,
+        }
+
+        impl<'a> flatbuffers::Follow<'a> for OutputEvent<'a> {
+            type Inner = OutputEvent<'a>;
+            #[inline]
+            unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+                unsafe {
+                    Self {
+                        _tab: flatbuffers::Table::new(buf, loc),
+                    }
+                }
+            }
+        }
+
+        impl<'a> OutputEvent<'a> {
+            pub const VT_TASK_NAME: flatbuffers::VOffsetT = 4;
+            pub const VT_LINE: flatbuffers::VOffsetT = 6;
+            pub const VT_SRC: flatbuffers::VOffsetT = 8;
+            pub const VT_IS_JSON: flatbuffers::VOffsetT = 10;
+            pub const VT_SEQ: flatbuffers::VOffsetT = 12;
+            pub const VT_TRUNCATED: flatbuffers::VOffsetT = 14;
+            pub const VT_TIMESTAMP_MS: flatbuffers::VOffsetT = 16;
+
+            #[inline]
+            pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+                OutputEvent { _tab: table }
+            }
+            #[allow(unused_mut)]
+            pub fn create<
+                'bldr: 'args,
+                'args: 'mut_bldr,
+                'mut_bldr,
+                A: flatbuffers::Allocator + 'bldr,
+            >(
+                _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+                args: &'args OutputEventArgs<'args>,
+            ) -> flatbuffers::WIPOffset<OutputEvent<'bldr>> {
+                let mut builder = OutputEventBuilder::new(_fbb);
+                builder.add_timestamp_ms(args.timestamp_ms);
+                builder.add_seq(args.seq);
+                if let Some(x) = args.line {
+                    builder.add_line(x);
+                }
+                if let Some(x) = args.task_name {
+                    builder.add_task_name(x);
+                }
+                builder.add_truncated(args.truncated);
+                builder.add_is_json(args.is_json);
+                builder.add_src(args.src);
+                builder.finish()
+            }
+
+            #[inline]
+            pub fn task_name(&self) -> &'a str {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(OutputEvent::VT_TASK_NAME, None)
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn line(&self) -> &'a str {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(OutputEvent::VT_LINE, None)
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn src(&self) -> StreamSource {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<StreamSource>(OutputEvent::VT_SRC, Some(StreamSource::Stdout))
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn is_json(&self) -> bool {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<bool>(OutputEvent::VT_IS_JSON, Some(false))
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn seq(&self) -> u64 {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe { self._tab.get::<u64>(OutputEvent::VT_SEQ, Some(0)).unwrap() }
+            }
+            #[inline]
+            pub fn truncated(&self) -> bool {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<bool>(OutputEvent::VT_TRUNCATED, Some(false))
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn timestamp_ms(&self) -> u64 {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<u64>(OutputEvent::VT_TIMESTAMP_MS, Some(0))
+                        .unwrap()
+                }
+            }
+        }
+
+        impl flatbuffers::Verifiable for OutputEvent<'_> {
+            #[inline]
+            fn run_verifier(
+                v: &mut flatbuffers::Verifier,
+                pos: usize,
+            ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+                use self::flatbuffers::Verifiable;
+                v.visit_table(pos)?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "task_name",
+                        Self::VT_TASK_NAME,
+                        true,
+                    )?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>("line", Self::VT_LINE, true)?
+                    .visit_field::<StreamSource>("src", Self::VT_SRC, false)?
+                    .visit_field::<bool>("is_json", Self::VT_IS_JSON, false)?
+                    .visit_field::<u64>("seq", Self::VT_SEQ, false)?
+                    .visit_field::<bool>("truncated", Self::VT_TRUNCATED, false)?
+                    .visit_field::<u64>("timestamp_ms", Self::VT_TIMESTAMP_MS, false)?
+                    .finish();
+                Ok(())
+            }
+        }
+        pub struct OutputEventArgs<'a> {
+            pub task_name: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub line: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub src: StreamSource,
+            pub is_json: bool,
+            pub seq: u64,
+            pub truncated: bool,
+            pub timestamp_ms: u64,
+        }
+        impl<'a> Default for OutputEventArgs<'a> {
+            #[inline]
+            fn default() -> Self {
+                OutputEventArgs {
+                    task_name: None, // required field
+                    line: None,      // required field
+                    src: StreamSource::Stdout,
+                    is_json: false,
+                    seq: 0,
+                    truncated: false,
+                    timestamp_ms: 0,
+                }
+            }
+        }
+
+        pub struct OutputEventBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+            fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+        }
+        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> OutputEventBuilder<'a, 'b, A> {
+            #[inline]
+            pub fn add_task_name(&mut self, task_name: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    OutputEvent::VT_TASK_NAME,
+                    task_name,
+                );
+            }
+            #[inline]
+            pub fn add_line(&mut self, line: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_
+                    .push_slot_always::<flatbuffers::WIPOffset<_>>(OutputEvent::VT_LINE, line);
+            }
+            #[inline]
+            pub fn add_src(&mut self, src: StreamSource) {
+                self.fbb_
+                    .push_slot::<StreamSource>(OutputEvent::VT_SRC, src, StreamSource::Stdout);
+            }
+            #[inline]
+            pub fn add_is_json(&mut self, is_json: bool) {
+                self.fbb_
+                    .push_slot::<bool>(OutputEvent::VT_IS_JSON, is_json, false);
+            }
+            #[inline]
+            pub fn add_seq(&mut self, seq: u64) {
+                self.fbb_.push_slot::<u64>(OutputEvent::VT_SEQ, seq, 0);
+            }
+            #[inline]
+            pub fn add_truncated(&mut self, truncated: bool) {
+                self.fbb_
+                    .push_slot::<bool>(OutputEvent::VT_TRUNCATED, truncated, false);
+            }
+            #[inline]
+            pub fn add_timestamp_ms(&mut self, timestamp_ms: u64) {
+                self.fbb_
+                    .push_slot::<u64>(OutputEvent::VT_TIMESTAMP_MS, timestamp_ms, 0);
+            }
+            #[inline]
+            pub fn new(
+                _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            ) -> OutputEventBuilder<'a, 'b, A> {
+                let start = _fbb.start_table();
+                OutputEventBuilder {
+                    fbb_: _fbb,
+                    start_: start,
+                }
+            }
+            #[inline]
+            pub fn finish(self) -> flatbuffers::WIPOffset<OutputEvent<'a>> {
+                let o = self.fbb_.end_table(self.start_);
+                self.fbb_
+                    .required(o, OutputEvent::VT_TASK_NAME, "task_name");
+                self.fbb_.required(o, OutputEvent::VT_LINE, "line");
+                flatbuffers::WIPOffset::new(o.value())
+            }
+        }
+
+        impl core::fmt::Debug for OutputEvent<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let mut ds = f.debug_struct("OutputEvent");
+                ds.field("task_name", &self.task_name());
+                ds.field("line", &self.line());
+                ds.field("src", &self.src());
+                ds.field("is_json", &self.is_json());
+                ds.field("seq", &self.seq());
+                ds.field("truncated", &self.truncated());
+                ds.field("timestamp_ms", &self.timestamp_ms());
+                ds.finish()
+            }
+        }
+        pub enum ReadyEventOffset {}
+        #[derive(Copy, Clone, PartialEq)]
+
+        pub struct ReadyEvent<'a> {
+            pub _tab: flatbuffers::Table<'a>,
+        }
+
+        impl<'a> flatbuffers::Follow<'a> for ReadyEvent<'a> {
+            type Inner = ReadyEvent<'a>;
+            #[inline]
+            unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+                unsafe {
+                    Self {
+                        _tab: flatbuffers::Table::new(buf, loc),
+                    }
+                }
+            }
+        }
+
+        impl<'a> ReadyEvent<'a> {
+            pub const VT_TASK_NAME: flatbuffers::VOffsetT = 4;
+
+            #[inline]
+            pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+                ReadyEvent { _tab: table }
+            }
+            #[allow(unused_mut)]
+            pub fn create<
+                'bldr: 'args,
+                'args: 'mut_bldr,
+                'mut_bldr,
+                A: flatbuffers::Allocator + 'bldr,
+            >(
+                _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+                args: &'args ReadyEventArgs<'args>,
+            ) -> flatbuffers::WIPOffset<ReadyEvent<'bldr>> {
+                let mut builder = ReadyEventBuilder::new(_fbb);
+                if let Some(x) = args.task_name {
+                    builder.add_task_name(x);
+                }
+                builder.finish()
+            }
+
+            #[inline]
+            pub fn task_name(&self) -> &'a str {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(ReadyEvent::VT_TASK_NAME, None)
+                        .unwrap()
+                }
+            }
+        }
+
+        impl flatbuffers::Verifiable for ReadyEvent<'_> {
+            #[inline]
+            fn run_verifier(
+                v: &mut flatbuffers::Verifier,
+                pos: usize,
+            ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+                use self::flatbuffers::Verifiable;
+                v.visit_table(pos)?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "task_name",
+                        Self::VT_TASK_NAME,
+                        true,
+                    )?
+                    .finish();
+                Ok(())
+            }
+        }
+        pub struct ReadyEventArgs<'a> {
+            pub task_name: Option<flatbuffers::WIPOffset<&'a str>>,
+        }
+        impl<'a> Default for ReadyEventArgs<'a> {
+            #[inline]
+            fn default() -> Self {
+                ReadyEventArgs {
+                    task_name: None, // required field
+                }
+            }
+        }
+
+        pub struct ReadyEventBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+            fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+        }
+        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> ReadyEventBuilder<'a, 'b, A> {
+            #[inline]
+            pub fn add_task_name(&mut self, task_name: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    ReadyEvent::VT_TASK_NAME,
+                    task_name,
+                );
+            }
+            #[inline]
+            pub fn new(
+                _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            ) -> ReadyEventBuilder<'a, 'b, A> {
+                let start = _fbb.start_table();
+                ReadyEventBuilder {
+                    fbb_: _fbb,
+                    start_: start,
+                }
+            }
+            #[inline]
+            pub fn finish(self) -> flatbuffers::WIPOffset<ReadyEvent<'a>> {
+                let o = self.fbb_.end_table(self.start_);
+                self.fbb_.required(o, ReadyEvent::VT_TASK_NAME, "task_name");
+                flatbuffers::WIPOffset::new(o.value())
+            }
+        }
+
+        impl core::fmt::Debug for ReadyEvent<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let mut ds = f.debug_struct("ReadyEvent");
+                ds.field("task_name", &self.task_name());
+                ds.finish()
+            }
+        }
+        pub enum StoppedEventOffset {}
+        #[derive(Copy, Clone, PartialEq)]
+
+        pub struct StoppedEvent<'a> {
+            pub _tab: flatbuffers::Table<'a>,
+        }
+
+        impl<'a> flatbuffers::Follow<'a> for StoppedEvent<'a> {
+            type Inner = StoppedEvent<'a>;
+            #[inline]
+            unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+                unsafe {
+                    Self {
+                        _tab: flatbuffers::Table::new(buf, loc),
+                    }
+                }
+            }
+        }
+
+        impl<'a> StoppedEvent<'a> {
+            pub const VT_TASK_NAME: flatbuffers::VOffsetT = 4;
+            pub const VT_EXIT_CODE: flatbuffers::VOffsetT = 6;
+            pub const VT_REASON_TYPE: flatbuffers::VOffsetT = 8;
+            pub const VT_REASON: flatbuffers::VOffsetT = 10;
+            pub const VT_WAS_READY: flatbuffers::VOffsetT = 12;
+            pub const VT_DURATION_MS: flatbuffers::VOffsetT = 14;
+            pub const VT_EXPECTED_DURATION_MS: flatbuffers::VOffsetT = 16;
+            pub const VT_EXIT_MEANING: flatbuffers::VOffsetT = 18;
+            pub const VT_FROM_CACHE: flatbuffers::VOffsetT = 20;
+            pub const VT_STDOUT_SHA256: flatbuffers::VOffsetT = 22;
+            pub const VT_STDERR_SHA256: flatbuffers::VOffsetT = 24;
+            pub const VT_SIGNAL: flatbuffers::VOffsetT = 26;
+
+            #[inline]
+            pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+                StoppedEvent { _tab: table }
+            }
+            #[allow(unused_mut)]
+            pub fn create<
+                'bldr: 'args,
+                'args: 'mut_bldr,
+                'mut_bldr,
+                A: flatbuffers::Allocator + 'bldr,
+            >(
+                _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+                args: &'args StoppedEventArgs<'args>,
+            ) -> flatbuffers::WIPOffset<StoppedEvent<'bldr>> {
+                let mut builder = StoppedEventBuilder::new(_fbb);
+                if let Some(x) = args.expected_duration_ms {
+                    builder.add_expected_duration_ms(x);
+                }
+                builder.add_duration_ms(args.duration_ms);
+                if let Some(x) = args.signal {
+                    builder.add_signal(x);
+                }
+                if let Some(x) = args.stderr_sha256 {
+                    builder.add_stderr_sha256(x);
+                }
+                if let Some(x) = args.stdout_sha256 {
+                    builder.add_stdout_sha256(x);
+                }
+                if let Some(x) = args.exit_meaning {
+                    builder.add_exit_meaning(x);
+                }
+                if let Some(x) = args.reason {
+                    builder.add_reason(x);
+                }
+                builder.add_exit_code(args.exit_code);
+                if let Some(x) = args.task_name {
+                    builder.add_task_name(x);
+                }
+                builder.add_from_cache(args.from_cache);
+                builder.add_was_ready(args.was_ready);
+                builder.add_reason_type(args.reason_type);
+                builder.finish()
+            }
+
+            #[inline]
+            pub fn task_name(&self) -> &'a str {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(StoppedEvent::VT_TASK_NAME, None)
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn exit_code(&self) -> i32 {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<i32>(StoppedEvent::VT_EXIT_CODE, Some(0))
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn reason_type(&self) -> TaskEventStopReason {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<TaskEventStopReason>(
+                            StoppedEvent::VT_REASON_TYPE,
+                            Some(TaskEventStopReason::NONE),
+                        )
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn reason(&self) -> flatbuffers::Table<'a> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<flatbuffers::Table<'a>>>(
+                            StoppedEvent::VT_REASON,
+                            None,
+                        )
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn was_ready(&self) -> bool {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<bool>(StoppedEvent::VT_WAS_READY, Some(false))
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn duration_ms(&self) -> u64 {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<u64>(StoppedEvent::VT_DURATION_MS, Some(0))
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn expected_duration_ms(&self) -> Option<u64> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<u64>(StoppedEvent::VT_EXPECTED_DURATION_MS, None)
+                }
+            }
+            #[inline]
+            pub fn exit_meaning(&self) -> Option<&'a str> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(
+                        StoppedEvent::VT_EXIT_MEANING,
+                        None,
+                    )
+                }
+            }
+            #[inline]
+            pub fn from_cache(&self) -> bool {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<bool>(StoppedEvent::VT_FROM_CACHE, Some(false))
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn stdout_sha256(&self) -> Option<&'a str> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(
+                        StoppedEvent::VT_STDOUT_SHA256,
+                        None,
+                    )
+                }
+            }
+            #[inline]
+            pub fn stderr_sha256(&self) -> Option<&'a str> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(
+                        StoppedEvent::VT_STDERR_SHA256,
+                        None,
+                    )
+                }
+            }
+            #[inline]
+            pub fn signal(&self) -> Option<i32> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe { self._tab.get::<i32>(StoppedEvent::VT_SIGNAL, None) }
+            }
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn reason_as_finished(&self) -> Option<DummyTable<'a>> {
+                if self.reason_type() == TaskEventStopReason::Finished {
+                    let u = self.reason();
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    Some(unsafe { DummyTable::init_from_table(u) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn reason_as_terminated_timeout(&self) -> Option<DummyTable<'a>> {
+                if self.reason_type() == TaskEventStopReason::TerminatedTimeout {
+                    let u = self.reason();
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    Some(unsafe { DummyTable::init_from_table(u) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn reason_as_terminated_cleanup(&self) -> Option<TerminatedStopReason<'a>> {
+                if self.reason_type() == TaskEventStopReason::TerminatedCleanup {
+                    let u = self.reason();
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    Some(unsafe { TerminatedStopReason::init_from_table(u) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn reason_as_terminated_dependencies_finished(&self) -> Option<DummyTable<'a>> {
+                if self.reason_type() == TaskEventStopReason::TerminatedDependenciesFinished {
+                    let u = self.reason();
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    Some(unsafe { DummyTable::init_from_table(u) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn reason_as_terminated_user_requested(&self) -> Option<TerminatedStopReason<'a>> {
+                if self.reason_type() == TaskEventStopReason::TerminatedUserRequested {
+                    let u = self.reason();
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    Some(unsafe { TerminatedStopReason::init_from_table(u) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn reason_as_error(&self) -> Option<ErrorStopReason<'a>> {
+                if self.reason_type() == TaskEventStopReason::Error {
+                    let u = self.reason();
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    Some(unsafe { ErrorStopReason::init_from_table(u) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn reason_as_startup_failure(&self) -> Option<ErrorStopReason<'a>> {
+                if self.reason_type() == TaskEventStopReason::StartupFailure {
+                    let u = self.reason();
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    Some(unsafe { ErrorStopReason::init_from_table(u) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn reason_as_terminated_completion_indicator(&self) -> Option<DummyTable<'a>> {
+                if self.reason_type() == TaskEventStopReason::TerminatedCompletionIndicator {
+                    let u = self.reason();
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    Some(unsafe { DummyTable::init_from_table(u) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn reason_as_terminated_script_timeout(&self) -> Option<DummyTable<'a>> {
+                if self.reason_type() == TaskEventStopReason::TerminatedScriptTimeout {
+                    let u = self.reason();
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    Some(unsafe { DummyTable::init_from_table(u) })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn reason_as_terminated_unhealthy(&self) -> Option<DummyTable<'a>> {
+                if self.reason_type() == TaskEventStopReason::TerminatedUnhealthy {
+                    let u = self.reason();
+                    // Safety:
+                    // Created from a valid Table for this object
+                    // Which contains a valid union in this slot
+                    Some(unsafe { DummyTable::init_from_table(u) })
+                } else {
+                    None
+                }
+            }
+        }
+
+        impl flatbuffers::Verifiable for StoppedEvent<'_> {
+            #[inline]
+            fn run_verifier(
+                v: &mut flatbuffers::Verifier,
+                pos: usize,
+            ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+                use self::flatbuffers::Verifiable;
+                v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("task_name", Self::VT_TASK_NAME, true)?
+     .visit_field::<i32>("exit_code", Self::VT_EXIT_CODE, false)?
+     .visit_union::<TaskEventStopReason, _>("reason_type", Self::VT_REASON_TYPE, "reason", Self::VT_REASON, true, |key, v, pos| {
+        match key {
+          TaskEventStopReason::Finished => v.verify_union_variant::<flatbuffers::ForwardsUOffset<DummyTable>>("TaskEventStopReason::Finished", pos),
+          TaskEventStopReason::TerminatedTimeout => v.verify_union_variant::<flatbuffers::ForwardsUOffset<DummyTable>>("TaskEventStopReason::TerminatedTimeout", pos),
+          TaskEventStopReason::TerminatedCleanup => v.verify_union_variant::<flatbuffers::ForwardsUOffset<TerminatedStopReason>>("TaskEventStopReason::TerminatedCleanup", pos),
+          TaskEventStopReason::TerminatedDependenciesFinished => v.verify_union_variant::<flatbuffers::ForwardsUOffset<DummyTable>>("TaskEventStopReason::TerminatedDependenciesFinished", pos),
+          TaskEventStopReason::TerminatedUserRequested => v.verify_union_variant::<flatbuffers::ForwardsUOffset<TerminatedStopReason>>("TaskEventStopReason::TerminatedUserRequested", pos),
+          TaskEventStopReason::Error => v.verify_union_variant::<flatbuffers::ForwardsUOffset<ErrorStopReason>>("TaskEventStopReason::Error", pos),
+          TaskEventStopReason::StartupFailure => v.verify_union_variant::<flatbuffers::ForwardsUOffset<ErrorStopReason>>("TaskEventStopReason::StartupFailure", pos),
+          TaskEventStopReason::TerminatedCompletionIndicator => v.verify_union_variant::<flatbuffers::ForwardsUOffset<DummyTable>>("TaskEventStopReason::TerminatedCompletionIndicator", pos),
+          TaskEventStopReason::TerminatedScriptTimeout => v.verify_union_variant::<flatbuffers::ForwardsUOffset<DummyTable>>("TaskEventStopReason::TerminatedScriptTimeout", pos),
+          TaskEventStopReason::TerminatedUnhealthy => v.verify_union_variant::<flatbuffers::ForwardsUOffset<DummyTable>>("TaskEventStopReason::TerminatedUnhealthy", pos),
+          _ => Ok(()),
+        }
+     })?
+     .visit_field::<bool>("was_ready", Self::VT_WAS_READY, false)?
+     .visit_field::<u64>("duration_ms", Self::VT_DURATION_MS, false)?
+     .visit_field::<u64>("expected_duration_ms", Self::VT_EXPECTED_DURATION_MS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("exit_meaning", Self::VT_EXIT_MEANING, false)?
+     .visit_field::<bool>("from_cache", Self::VT_FROM_CACHE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("stdout_sha256", Self::VT_STDOUT_SHA256, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("stderr_sha256", Self::VT_STDERR_SHA256, false)?
+     .visit_field::<i32>("signal", Self::VT_SIGNAL, false)?
+     .finish();
+                Ok(())
+            }
+        }
+        pub struct StoppedEventArgs<'a> {
+            pub task_name: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub exit_code: i32,
+            pub reason_type: TaskEventStopReason,
+            pub reason: Option<flatbuffers::WIPOffset<flatbuffers::UnionWIPOffset>>,
+            pub was_ready: bool,
+            pub duration_ms: u64,
+            pub expected_duration_ms: Option<u64>,
+            pub exit_meaning: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub from_cache: bool,
+            pub stdout_sha256: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub stderr_sha256: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub signal: Option<i32>,
+        }
+        impl<'a> Default for StoppedEventArgs<'a> {
+            #[inline]
+            fn default() -> Self {
+                StoppedEventArgs {
+                    task_name: None, // required field
+                    exit_code: 0,
+                    reason_type: TaskEventStopReason::NONE,
+                    reason: None, // required field
+                    was_ready: false,
+                    duration_ms: 0,
+                    expected_duration_ms: None,
+                    exit_meaning: None,
+                    from_cache: false,
+                    stdout_sha256: None,
+                    stderr_sha256: None,
+                    signal: None,
+                }
+            }
+        }
+
+        pub struct StoppedEventBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+            fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+        }
+        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> StoppedEventBuilder<'a, 'b, A> {
+            #[inline]
+            pub fn add_task_name(&mut self, task_name: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    StoppedEvent::VT_TASK_NAME,
+                    task_name,
+                );
+            }
+            #[inline]
+            pub fn add_exit_code(&mut self, exit_code: i32) {
+                self.fbb_
+                    .push_slot::<i32>(StoppedEvent::VT_EXIT_CODE, exit_code, 0);
+            }
+            #[inline]
+            pub fn add_reason_type(&mut self, reason_type: TaskEventStopReason) {
+                self.fbb_.push_slot::<TaskEventStopReason>(
+                    StoppedEvent::VT_REASON_TYPE,
+                    reason_type,
+                    TaskEventStopReason::NONE,
+                );
+            }
+            #[inline]
+            pub fn add_reason(
+                &mut self,
+                reason: flatbuffers::WIPOffset<flatbuffers::UnionWIPOffset>,
+            ) {
+                self.fbb_
+                    .push_slot_always::<flatbuffers::WIPOffset<_>>(StoppedEvent::VT_REASON, reason);
+            }
+            #[inline]
+            pub fn add_was_ready(&mut self, was_ready: bool) {
+                self.fbb_
+                    .push_slot::<bool>(StoppedEvent::VT_WAS_READY, was_ready, false);
+            }
+            #[inline]
+            pub fn add_duration_ms(&mut self, duration_ms: u64) {
+                self.fbb_
+                    .push_slot::<u64>(StoppedEvent::VT_DURATION_MS, duration_ms, 0);
+            }
+            #[inline]
+            pub fn add_expected_duration_ms(&mut self, expected_duration_ms: u64) {
+                self.fbb_.push_slot_always::<u64>(
+                    StoppedEvent::VT_EXPECTED_DURATION_MS,
+                    expected_duration_ms,
+                );
+            }
+            #[inline]
+            pub fn add_exit_meaning(&mut self, exit_meaning: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    StoppedEvent::VT_EXIT_MEANING,
+                    exit_meaning,
+                );
+            }
+            #[inline]
+            pub fn add_from_cache(&mut self, from_cache: bool) {
+                self.fbb_
+                    .push_slot::<bool>(StoppedEvent::VT_FROM_CACHE, from_cache, false);
+            }
+            #[inline]
+            pub fn add_stdout_sha256(&mut self, stdout_sha256: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    StoppedEvent::VT_STDOUT_SHA256,
+                    stdout_sha256,
+                );
+            }
+            #[inline]
+            pub fn add_stderr_sha256(&mut self, stderr_sha256: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    StoppedEvent::VT_STDERR_SHA256,
+                    stderr_sha256,
+                );
+            }
+            #[inline]
+            pub fn add_signal(&mut self, signal: i32) {
+                self.fbb_
+                    .push_slot_always::<i32>(StoppedEvent::VT_SIGNAL, signal);
+            }
+            #[inline]
+            pub fn new(
+                _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            ) -> StoppedEventBuilder<'a, 'b, A> {
+                let start = _fbb.start_table();
+                StoppedEventBuilder {
+                    fbb_: _fbb,
+                    start_: start,
+                }
+            }
+            #[inline]
+            pub fn finish(self) -> flatbuffers::WIPOffset<StoppedEvent<'a>> {
+                let o = self.fbb_.end_table(self.start_);
+                self.fbb_
+                    .required(o, StoppedEvent::VT_TASK_NAME, "task_name");
+                self.fbb_.required(o, StoppedEvent::VT_REASON, "reason");
+                flatbuffers::WIPOffset::new(o.value())
+            }
+        }
+
+        impl core::fmt::Debug for StoppedEvent<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let mut ds = f.debug_struct("StoppedEvent");
+                ds.field("task_name", &self.task_name());
+                ds.field("exit_code", &self.exit_code());
+                ds.field("reason_type", &self.reason_type());
+                match self.reason_type() {
+                    TaskEventStopReason::Finished => {
+                        if let Some(x) = self.reason_as_finished() {
+                            ds.field("reason", &x)
+                        } else {
+                            ds.field(
+                                "reason",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventStopReason::TerminatedTimeout => {
+                        if let Some(x) = self.reason_as_terminated_timeout() {
+                            ds.field("reason", &x)
+                        } else {
+                            ds.field(
+                                "reason",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventStopReason::TerminatedCleanup => {
+                        if let Some(x) = self.reason_as_terminated_cleanup() {
+                            ds.field("reason", &x)
+                        } else {
+                            ds.field(
+                                "reason",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventStopReason::TerminatedDependenciesFinished => {
+                        if let Some(x) = self.reason_as_terminated_dependencies_finished() {
+                            ds.field("reason", &x)
+                        } else {
+                            ds.field(
+                                "reason",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventStopReason::TerminatedUserRequested => {
+                        if let Some(x) = self.reason_as_terminated_user_requested() {
+                            ds.field("reason", &x)
+                        } else {
+                            ds.field(
+                                "reason",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventStopReason::Error => {
+                        if let Some(x) = self.reason_as_error() {
+                            ds.field("reason", &x)
+                        } else {
+                            ds.field(
+                                "reason",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventStopReason::StartupFailure => {
+                        if let Some(x) = self.reason_as_startup_failure() {
+                            ds.field("reason", &x)
+                        } else {
+                            ds.field(
+                                "reason",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventStopReason::TerminatedCompletionIndicator => {
+                        if let Some(x) = self.reason_as_terminated_completion_indicator() {
+                            ds.field("reason", &x)
+                        } else {
+                            ds.field(
+                                "reason",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventStopReason::TerminatedScriptTimeout => {
+                        if let Some(x) = self.reason_as_terminated_script_timeout() {
+                            ds.field("reason", &x)
+                        } else {
+                            ds.field(
+                                "reason",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventStopReason::TerminatedUnhealthy => {
+                        if let Some(x) = self.reason_as_terminated_unhealthy() {
+                            ds.field("reason", &x)
+                        } else {
+                            ds.field(
+                                "reason",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    _ => {
+                        let x: Option<()> = None;
+                        ds.field("reason", &x)
+                    }
+                };
+                ds.field("was_ready", &self.was_ready());
+                ds.field("duration_ms", &self.duration_ms());
+                ds.field("expected_duration_ms", &self.expected_duration_ms());
+                ds.field("exit_meaning", &self.exit_meaning());
+                ds.field("from_cache", &self.from_cache());
+                ds.field("stdout_sha256", &self.stdout_sha256());
+                ds.field("stderr_sha256", &self.stderr_sha256());
+                ds.field("signal", &self.signal());
+                ds.finish()
+            }
+        }
+        pub enum RunningLongEventOffset {}
+        #[derive(Copy, Clone, PartialEq)]
+
+        pub struct RunningLongEvent<'a> {
+            pub _tab: flatbuffers::Table<'a>,
+        }
+
+        impl<'a> flatbuffers::Follow<'a> for RunningLongEvent<'a> {
+            type Inner = RunningLongEvent<'a>;
+            #[inline]
+            unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+                unsafe {
+                    Self {
+                        _tab: flatbuffers::Table::new(buf, loc),
+                    }
+                }
+            }
+        }
+
+        impl<'a> RunningLongEvent<'a> {
+            pub const VT_TASK_NAME: flatbuffers::VOffsetT = 4;
+            pub const VT_EXPECTED_MS: flatbuffers::VOffsetT = 6;
+            pub const VT_ELAPSED_MS: flatbuffers::VOffsetT = 8;
+
+            #[inline]
+            pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+                RunningLongEvent { _tab: table }
+            }
+            #[allow(unused_mut)]
+            pub fn create<
+                'bldr: 'args,
+                'args: 'mut_bldr,
+                'mut_bldr,
+                A: flatbuffers::Allocator + 'bldr,
+            >(
+                _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+                args: &'args RunningLongEventArgs<'args>,
+            ) -> flatbuffers::WIPOffset<RunningLongEvent<'bldr>> {
+                let mut builder = RunningLongEventBuilder::new(_fbb);
+                builder.add_elapsed_ms(args.elapsed_ms);
+                builder.add_expected_ms(args.expected_ms);
+                if let Some(x) = args.task_name {
+                    builder.add_task_name(x);
+                }
+                builder.finish()
+            }
+
+            #[inline]
+            pub fn task_name(&self) -> &'a str {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(
+                            RunningLongEvent::VT_TASK_NAME,
+                            None,
+                        )
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn expected_ms(&self) -> u64 {
                 // Safety:
                 // Created from valid Table for this object
                 // which contains a valid value in this slot
                 unsafe {
                     self._tab
-                        .get::<bool>(TaskConfig::VT_USE_PROCESS_GROUP, Some(false))
+                        .get::<u64>(RunningLongEvent::VT_EXPECTED_MS, Some(0))
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn elapsed_ms(&self) -> u64 {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<u64>(RunningLongEvent::VT_ELAPSED_MS, Some(0))
                         .unwrap()
                 }
             }
         }
 
-        impl flatbuffers::Verifiable for TaskConfig<'_> {
+        impl flatbuffers::Verifiable for RunningLongEvent<'_> {
             #[inline]
             fn run_verifier(
                 v: &mut flatbuffers::Verifier,
@@ -878,189 +5174,91 @@ pub mod tcrm {
                 use self::flatbuffers::Verifiable;
                 v.visit_table(pos)?
                     .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
-                        "command",
-                        Self::VT_COMMAND,
+                        "task_name",
+                        Self::VT_TASK_NAME,
                         true,
                     )?
-                    .visit_field::<flatbuffers::ForwardsUOffset<
-                        flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<&'_ str>>,
-                    >>("args", Self::VT_ARGS, false)?
-                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
-                        "working_dir",
-                        Self::VT_WORKING_DIR,
-                        false,
-                    )?
-                    .visit_field::<flatbuffers::ForwardsUOffset<
-                        flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<EnvEntry>>,
-                    >>("env", Self::VT_ENV, false)?
-                    .visit_field::<u64>("timeout_ms", Self::VT_TIMEOUT_MS, false)?
-                    .visit_field::<bool>("enable_stdin", Self::VT_ENABLE_STDIN, false)?
-                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
-                        "ready_indicator",
-                        Self::VT_READY_INDICATOR,
-                        false,
-                    )?
-                    .visit_field::<StreamSource>(
-                        "ready_indicator_source",
-                        Self::VT_READY_INDICATOR_SOURCE,
-                        false,
-                    )?
-                    .visit_field::<bool>("use_process_group", Self::VT_USE_PROCESS_GROUP, false)?
+                    .visit_field::<u64>("expected_ms", Self::VT_EXPECTED_MS, false)?
+                    .visit_field::<u64>("elapsed_ms", Self::VT_ELAPSED_MS, false)?
                     .finish();
                 Ok(())
             }
-        }
-        pub struct TaskConfigArgs<'a> {
-            pub command: Option<flatbuffers::WIPOffset<&'a str>>,
-            pub args: Option<
-                flatbuffers::WIPOffset<
-                    flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<&'a str>>,
-                >,
-            >,
-            pub working_dir: Option<flatbuffers::WIPOffset<&'a str>>,
-            pub env: Option<
-                flatbuffers::WIPOffset<
-                    flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<EnvEntry<'a>>>,
-                >,
-            >,
-            pub timeout_ms: u64,
-            pub enable_stdin: bool,
-            pub ready_indicator: Option<flatbuffers::WIPOffset<&'a str>>,
-            pub ready_indicator_source: StreamSource,
-            pub use_process_group: bool,
-        }
-        impl<'a> Default for TaskConfigArgs<'a> {
-            #[inline]
-            fn default() -> Self {
-                TaskConfigArgs {
-                    command: None, // required field
-                    args: None,
-                    working_dir: None,
-                    env: None,
-                    timeout_ms: 0,
-                    enable_stdin: false,
-                    ready_indicator: None,
-                    ready_indicator_source: StreamSource::Stdout,
-                    use_process_group: false,
+        }
+        pub struct RunningLongEventArgs<'a> {
+            pub task_name: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub expected_ms: u64,
+            pub elapsed_ms: u64,
+        }
+        impl<'a> Default for RunningLongEventArgs<'a> {
+            #[inline]
+            fn default() -> Self {
+                RunningLongEventArgs {
+                    task_name: None, // required field
+                    expected_ms: 0,
+                    elapsed_ms: 0,
                 }
             }
         }
 
-        pub struct TaskConfigBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+        pub struct RunningLongEventBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
             fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
             start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
         }
-        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> TaskConfigBuilder<'a, 'b, A> {
-            #[inline]
-            pub fn add_command(&mut self, command: flatbuffers::WIPOffset<&'b str>) {
-                self.fbb_
-                    .push_slot_always::<flatbuffers::WIPOffset<_>>(TaskConfig::VT_COMMAND, command);
-            }
-            #[inline]
-            pub fn add_args(
-                &mut self,
-                args: flatbuffers::WIPOffset<
-                    flatbuffers::Vector<'b, flatbuffers::ForwardsUOffset<&'b str>>,
-                >,
-            ) {
-                self.fbb_
-                    .push_slot_always::<flatbuffers::WIPOffset<_>>(TaskConfig::VT_ARGS, args);
-            }
+        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> RunningLongEventBuilder<'a, 'b, A> {
             #[inline]
-            pub fn add_working_dir(&mut self, working_dir: flatbuffers::WIPOffset<&'b str>) {
+            pub fn add_task_name(&mut self, task_name: flatbuffers::WIPOffset<&'b str>) {
                 self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
-                    TaskConfig::VT_WORKING_DIR,
-                    working_dir,
+                    RunningLongEvent::VT_TASK_NAME,
+                    task_name,
                 );
             }
             #[inline]
-            pub fn add_env(
-                &mut self,
-                env: flatbuffers::WIPOffset<
-                    flatbuffers::Vector<'b, flatbuffers::ForwardsUOffset<EnvEntry<'b>>>,
-                >,
-            ) {
-                self.fbb_
-                    .push_slot_always::<flatbuffers::WIPOffset<_>>(TaskConfig::VT_ENV, env);
-            }
-            #[inline]
-            pub fn add_timeout_ms(&mut self, timeout_ms: u64) {
+            pub fn add_expected_ms(&mut self, expected_ms: u64) {
                 self.fbb_
-                    .push_slot::<u64>(TaskConfig::VT_TIMEOUT_MS, timeout_ms, 0);
+                    .push_slot::<u64>(RunningLongEvent::VT_EXPECTED_MS, expected_ms, 0);
             }
             #[inline]
-            pub fn add_enable_stdin(&mut self, enable_stdin: bool) {
+            pub fn add_elapsed_ms(&mut self, elapsed_ms: u64) {
                 self.fbb_
-                    .push_slot::<bool>(TaskConfig::VT_ENABLE_STDIN, enable_stdin, false);
-            }
-            #[inline]
-            pub fn add_ready_indicator(
-                &mut self,
-                ready_indicator: flatbuffers::WIPOffset<&'b str>,
-            ) {
-                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
-                    TaskConfig::VT_READY_INDICATOR,
-                    ready_indicator,
-                );
-            }
-            #[inline]
-            pub fn add_ready_indicator_source(&mut self, ready_indicator_source: StreamSource) {
-                self.fbb_.push_slot::<StreamSource>(
-                    TaskConfig::VT_READY_INDICATOR_SOURCE,
-                    ready_indicator_source,
-                    StreamSource::Stdout,
-                );
-            }
-            #[inline]
-            pub fn add_use_process_group(&mut self, use_process_group: bool) {
-                self.fbb_.push_slot::<bool>(
-                    TaskConfig::VT_USE_PROCESS_GROUP,
-                    use_process_group,
-                    false,
-                );
+                    .push_slot::<u64>(RunningLongEvent::VT_ELAPSED_MS, elapsed_ms, 0);
             }
             #[inline]
             pub fn new(
                 _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
-            ) -> TaskConfigBuilder<'a, 'b, A> {
+            ) -> RunningLongEventBuilder<'a, 'b, A> {
                 let start = _fbb.start_table();
-                TaskConfigBuilder {
+                RunningLongEventBuilder {
                     fbb_: _fbb,
                     start_: start,
                 }
             }
             #[inline]
-            pub fn finish(self) -> flatbuffers::WIPOffset<TaskConfig<'a>> {
+            pub fn finish(self) -> flatbuffers::WIPOffset<RunningLongEvent<'a>> {
                 let o = self.fbb_.end_table(self.start_);
-                self.fbb_.required(o, TaskConfig::VT_COMMAND, "command");
+                self.fbb_
+                    .required(o, RunningLongEvent::VT_TASK_NAME, "task_name");
                 flatbuffers::WIPOffset::new(o.value())
             }
         }
 
-        impl core::fmt::Debug for TaskConfig<'_> {
+        impl core::fmt::Debug for RunningLongEvent<'_> {
             fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                let mut ds = f.debug_struct("TaskConfig");
-                ds.field("command", &self.command());
-                ds.field("args", &self.args());
-                ds.field("working_dir", &self.working_dir());
-                ds.field("env", &self.env());
-                ds.field("timeout_ms", &self.timeout_ms());
-                ds.field("enable_stdin", &self.enable_stdin());
-                ds.field("ready_indicator", &self.ready_indicator());
-                ds.field("ready_indicator_source", &self.ready_indicator_source());
-                ds.field("use_process_group", &self.use_process_group());
+                let mut ds = f.debug_struct("RunningLongEvent");
+                ds.field("task_name", &self.task_name());
+                ds.field("expected_ms", &self.expected_ms());
+                ds.field("elapsed_ms", &self.elapsed_ms());
                 ds.finish()
             }
         }
-        pub enum EnvEntryOffset {}
+        pub enum OutputRepeatedEventOffset {}
         #[derive(Copy, Clone, PartialEq)]
 
-        pub struct EnvEntry<'a> {
+        pub struct OutputRepeatedEvent<'a> {
             pub _tab: flatbuffers::Table<'a>,
         }
 
-        impl<'a> flatbuffers::Follow<'a> for EnvEntry<'a> {
-            type Inner = EnvEntry<'a>;
+        impl<'a> flatbuffers::Follow<'a> for OutputRepeatedEvent<'a> {
+            type Inner = OutputRepeatedEvent<'a>;
             #[inline]
             unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
                 unsafe {
@@ -1071,13 +5269,15 @@ pub mod tcrm {
             }
         }
 
-        impl<'a> EnvEntry<'a> {
-            pub const VT_KEY: flatbuffers::VOffsetT = 4;
-            pub const VT_VALUE: flatbuffers::VOffsetT = 6;
+        impl<'a> OutputRepeatedEvent<'a> {
+            pub const VT_TASK_NAME: flatbuffers::VOffsetT = 4;
+            pub const VT_LINE: flatbuffers::VOffsetT = 6;
+            pub const VT_SRC: flatbuffers::VOffsetT = 8;
+            pub const VT_COUNT: flatbuffers::VOffsetT = 10;
 
             #[inline]
             pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-                EnvEntry { _tab: table }
+                OutputRepeatedEvent { _tab: table }
             }
             #[allow(unused_mut)]
             pub fn create<
@@ -1087,43 +5287,76 @@ pub mod tcrm {
                 A: flatbuffers::Allocator + 'bldr,
             >(
                 _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
-                args: &'args EnvEntryArgs<'args>,
-            ) -> flatbuffers::WIPOffset<EnvEntry<'bldr>> {
-                let mut builder = EnvEntryBuilder::new(_fbb);
-                if let Some(x) = args.value {
-                    builder.add_value(x);
+                args: &'args OutputRepeatedEventArgs<'args>,
+            ) -> flatbuffers::WIPOffset<OutputRepeatedEvent<'bldr>> {
+                let mut builder = OutputRepeatedEventBuilder::new(_fbb);
+                builder.add_count(args.count);
+                if let Some(x) = args.line {
+                    builder.add_line(x);
                 }
-                if let Some(x) = args.key {
-                    builder.add_key(x);
+                if let Some(x) = args.task_name {
+                    builder.add_task_name(x);
                 }
+                builder.add_src(args.src);
                 builder.finish()
             }
 
             #[inline]
-            pub fn key(&self) -> &'a str {
+            pub fn task_name(&self) -> &'a str {
                 // Safety:
                 // Created from valid Table for this object
                 // which contains a valid value in this slot
                 unsafe {
                     self._tab
-                        .get::<flatbuffers::ForwardsUOffset<&str>>(EnvEntry::VT_KEY, None)
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(
+                            OutputRepeatedEvent::VT_TASK_NAME,
+                            None,
+                        )
                         .unwrap()
                 }
             }
             #[inline]
-            pub fn value(&self) -> &'a str {
+            pub fn line(&self) -> &'a str {
                 // Safety:
                 // Created from valid Table for this object
                 // which contains a valid value in this slot
                 unsafe {
                     self._tab
-                        .get::<flatbuffers::ForwardsUOffset<&str>>(EnvEntry::VT_VALUE, None)
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(
+                            OutputRepeatedEvent::VT_LINE,
+                            None,
+                        )
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn src(&self) -> StreamSource {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<StreamSource>(
+                            OutputRepeatedEvent::VT_SRC,
+                            Some(StreamSource::Stdout),
+                        )
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn count(&self) -> u64 {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<u64>(OutputRepeatedEvent::VT_COUNT, Some(0))
                         .unwrap()
                 }
             }
         }
 
-        impl flatbuffers::Verifiable for EnvEntry<'_> {
+        impl flatbuffers::Verifiable for OutputRepeatedEvent<'_> {
             #[inline]
             fn run_verifier(
                 v: &mut flatbuffers::Verifier,
@@ -1131,81 +5364,105 @@ pub mod tcrm {
             ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
                 use self::flatbuffers::Verifiable;
                 v.visit_table(pos)?
-                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>("key", Self::VT_KEY, true)?
                     .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
-                        "value",
-                        Self::VT_VALUE,
+                        "task_name",
+                        Self::VT_TASK_NAME,
                         true,
                     )?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>("line", Self::VT_LINE, true)?
+                    .visit_field::<StreamSource>("src", Self::VT_SRC, false)?
+                    .visit_field::<u64>("count", Self::VT_COUNT, false)?
                     .finish();
                 Ok(())
             }
         }
-        pub struct EnvEntryArgs<'a> {
-            pub key: Option<flatbuffers::WIPOffset<&'a str>>,
-            pub value: Option<flatbuffers::WIPOffset<&'a str>>,
+        pub struct OutputRepeatedEventArgs<'a> {
+            pub task_name: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub line: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub src: StreamSource,
+            pub count: u64,
         }
-        impl<'a> Default for EnvEntryArgs<'a> {
+        impl<'a> Default for OutputRepeatedEventArgs<'a> {
             #[inline]
             fn default() -> Self {
-                EnvEntryArgs {
-                    key: None,   // required field
-                    value: None, // required field
+                OutputRepeatedEventArgs {
+                    task_name: None, // required field
+                    line: None,      // required field
+                    src: StreamSource::Stdout,
+                    count: 0,
                 }
             }
         }
 
-        pub struct EnvEntryBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+        pub struct OutputRepeatedEventBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
             fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
             start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
         }
-        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> EnvEntryBuilder<'a, 'b, A> {
+        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> OutputRepeatedEventBuilder<'a, 'b, A> {
             #[inline]
-            pub fn add_key(&mut self, key: flatbuffers::WIPOffset<&'b str>) {
+            pub fn add_task_name(&mut self, task_name: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    OutputRepeatedEvent::VT_TASK_NAME,
+                    task_name,
+                );
+            }
+            #[inline]
+            pub fn add_line(&mut self, line: flatbuffers::WIPOffset<&'b str>) {
                 self.fbb_
-                    .push_slot_always::<flatbuffers::WIPOffset<_>>(EnvEntry::VT_KEY, key);
+                    .push_slot_always::<flatbuffers::WIPOffset<_>>(OutputRepeatedEvent::VT_LINE, line);
             }
             #[inline]
-            pub fn add_value(&mut self, value: flatbuffers::WIPOffset<&'b str>) {
+            pub fn add_src(&mut self, src: StreamSource) {
+                self.fbb_.push_slot::<StreamSource>(
+                    OutputRepeatedEvent::VT_SRC,
+                    src,
+                    StreamSource::Stdout,
+                );
+            }
+            #[inline]
+            pub fn add_count(&mut self, count: u64) {
                 self.fbb_
-                    .push_slot_always::<flatbuffers::WIPOffset<_>>(EnvEntry::VT_VALUE, value);
+                    .push_slot::<u64>(OutputRepeatedEvent::VT_COUNT, count, 0);
             }
             #[inline]
             pub fn new(
                 _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
-            ) -> EnvEntryBuilder<'a, 'b, A> {
+            ) -> OutputRepeatedEventBuilder<'a, 'b, A> {
                 let start = _fbb.start_table();
-                EnvEntryBuilder {
+                OutputRepeatedEventBuilder {
                     fbb_: _fbb,
                     start_: start,
                 }
             }
             #[inline]
-            pub fn finish(self) -> flatbuffers::WIPOffset<EnvEntry<'a>> {
+            pub fn finish(self) -> flatbuffers::WIPOffset<OutputRepeatedEvent<'a>> {
                 let o = self.fbb_.end_table(self.start_);
-                self.fbb_.required(o, EnvEntry::VT_KEY, "key");
-                self.fbb_.required(o, EnvEntry::VT_VALUE, "value");
+                self.fbb_
+                    .required(o, OutputRepeatedEvent::VT_TASK_NAME, "task_name");
+                self.fbb_.required(o, OutputRepeatedEvent::VT_LINE, "line");
                 flatbuffers::WIPOffset::new(o.value())
             }
         }
 
-        impl core::fmt::Debug for EnvEntry<'_> {
+        impl core::fmt::Debug for OutputRepeatedEvent<'_> {
             fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                let mut ds = f.debug_struct("EnvEntry");
-                ds.field("key", &self.key());
-                ds.field("value", &self.value());
+                let mut ds = f.debug_struct("OutputRepeatedEvent");
+                ds.field("task_name", &self.task_name());
+                ds.field("line", &self.line());
+                ds.field("src", &self.src());
+                ds.field("count", &self.count());
                 ds.finish()
             }
         }
-        pub enum TaskErrorOffset {}
+        pub enum TriggeredEventOffset {}
         #[derive(Copy, Clone, PartialEq)]
 
-        pub struct TaskError<'a> {
+        pub struct TriggeredEvent<'a> {
             pub _tab: flatbuffers::Table<'a>,
         }
 
-        impl<'a> flatbuffers::Follow<'a> for TaskError<'a> {
-            type Inner = TaskError<'a>;
+        impl<'a> flatbuffers::Follow<'a> for TriggeredEvent<'a> {
+            type Inner = TriggeredEvent<'a>;
             #[inline]
             unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
                 unsafe {
@@ -1216,13 +5473,15 @@ pub mod tcrm {
             }
         }
 
-        impl<'a> TaskError<'a> {
-            pub const VT_KIND: flatbuffers::VOffsetT = 4;
-            pub const VT_MESSAGE: flatbuffers::VOffsetT = 6;
+        impl<'a> TriggeredEvent<'a> {
+            pub const VT_TASK_NAME: flatbuffers::VOffsetT = 4;
+            pub const VT_NAME: flatbuffers::VOffsetT = 6;
+            pub const VT_LINE: flatbuffers::VOffsetT = 8;
+            pub const VT_SRC: flatbuffers::VOffsetT = 10;
 
             #[inline]
             pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-                TaskError { _tab: table }
+                TriggeredEvent { _tab: table }
             }
             #[allow(unused_mut)]
             pub fn create<
@@ -1232,40 +5491,69 @@ pub mod tcrm {
                 A: flatbuffers::Allocator + 'bldr,
             >(
                 _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
-                args: &'args TaskErrorArgs<'args>,
-            ) -> flatbuffers::WIPOffset<TaskError<'bldr>> {
-                let mut builder = TaskErrorBuilder::new(_fbb);
-                if let Some(x) = args.message {
-                    builder.add_message(x);
+                args: &'args TriggeredEventArgs<'args>,
+            ) -> flatbuffers::WIPOffset<TriggeredEvent<'bldr>> {
+                let mut builder = TriggeredEventBuilder::new(_fbb);
+                if let Some(x) = args.line {
+                    builder.add_line(x);
                 }
-                builder.add_kind(args.kind);
+                if let Some(x) = args.name {
+                    builder.add_name(x);
+                }
+                if let Some(x) = args.task_name {
+                    builder.add_task_name(x);
+                }
+                builder.add_src(args.src);
                 builder.finish()
             }
 
             #[inline]
-            pub fn kind(&self) -> TaskErrorType {
+            pub fn task_name(&self) -> &'a str {
                 // Safety:
                 // Created from valid Table for this object
                 // which contains a valid value in this slot
                 unsafe {
                     self._tab
-                        .get::<TaskErrorType>(TaskError::VT_KIND, Some(TaskErrorType::IO))
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(TriggeredEvent::VT_TASK_NAME, None)
                         .unwrap()
                 }
             }
             #[inline]
-            pub fn message(&self) -> Option<&'a str> {
+            pub fn name(&self) -> &'a str {
                 // Safety:
                 // Created from valid Table for this object
                 // which contains a valid value in this slot
                 unsafe {
                     self._tab
-                        .get::<flatbuffers::ForwardsUOffset<&str>>(TaskError::VT_MESSAGE, None)
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(TriggeredEvent::VT_NAME, None)
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn line(&self) -> &'a str {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(TriggeredEvent::VT_LINE, None)
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn src(&self) -> StreamSource {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<StreamSource>(TriggeredEvent::VT_SRC, Some(StreamSource::Stdout))
+                        .unwrap()
                 }
             }
         }
 
-        impl flatbuffers::Verifiable for TaskError<'_> {
+        impl flatbuffers::Verifiable for TriggeredEvent<'_> {
             #[inline]
             fn run_verifier(
                 v: &mut flatbuffers::Verifier,
@@ -1273,79 +5561,105 @@ pub mod tcrm {
             ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
                 use self::flatbuffers::Verifiable;
                 v.visit_table(pos)?
-                    .visit_field::<TaskErrorType>("kind", Self::VT_KIND, false)?
                     .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
-                        "message",
-                        Self::VT_MESSAGE,
-                        false,
+                        "task_name",
+                        Self::VT_TASK_NAME,
+                        true,
                     )?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>("name", Self::VT_NAME, true)?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>("line", Self::VT_LINE, true)?
+                    .visit_field::<StreamSource>("src", Self::VT_SRC, false)?
                     .finish();
                 Ok(())
             }
         }
-        pub struct TaskErrorArgs<'a> {
-            pub kind: TaskErrorType,
-            pub message: Option<flatbuffers::WIPOffset<&'a str>>,
+        pub struct TriggeredEventArgs<'a> {
+            pub task_name: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub name: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub line: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub src: StreamSource,
         }
-        impl<'a> Default for TaskErrorArgs<'a> {
+        impl<'a> Default for TriggeredEventArgs<'a> {
             #[inline]
             fn default() -> Self {
-                TaskErrorArgs {
-                    kind: TaskErrorType::IO,
-                    message: None,
+                TriggeredEventArgs {
+                    task_name: None, // required field
+                    name: None,      // required field
+                    line: None,      // required field
+                    src: StreamSource::Stdout,
                 }
             }
         }
 
-        pub struct TaskErrorBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+        pub struct TriggeredEventBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
             fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
             start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
         }
-        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> TaskErrorBuilder<'a, 'b, A> {
+        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> TriggeredEventBuilder<'a, 'b, A> {
             #[inline]
-            pub fn add_kind(&mut self, kind: TaskErrorType) {
+            pub fn add_task_name(&mut self, task_name: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    TriggeredEvent::VT_TASK_NAME,
+                    task_name,
+                );
+            }
+            #[inline]
+            pub fn add_name(&mut self, name: flatbuffers::WIPOffset<&'b str>) {
                 self.fbb_
-                    .push_slot::<TaskErrorType>(TaskError::VT_KIND, kind, TaskErrorType::IO);
+                    .push_slot_always::<flatbuffers::WIPOffset<_>>(TriggeredEvent::VT_NAME, name);
             }
             #[inline]
-            pub fn add_message(&mut self, message: flatbuffers::WIPOffset<&'b str>) {
+            pub fn add_line(&mut self, line: flatbuffers::WIPOffset<&'b str>) {
                 self.fbb_
-                    .push_slot_always::<flatbuffers::WIPOffset<_>>(TaskError::VT_MESSAGE, message);
+                    .push_slot_always::<flatbuffers::WIPOffset<_>>(TriggeredEvent::VT_LINE, line);
+            }
+            #[inline]
+            pub fn add_src(&mut self, src: StreamSource) {
+                self.fbb_.push_slot::<StreamSource>(
+                    TriggeredEvent::VT_SRC,
+                    src,
+                    StreamSource::Stdout,
+                );
             }
             #[inline]
             pub fn new(
                 _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
-            ) -> TaskErrorBuilder<'a, 'b, A> {
+            ) -> TriggeredEventBuilder<'a, 'b, A> {
                 let start = _fbb.start_table();
-                TaskErrorBuilder {
+                TriggeredEventBuilder {
                     fbb_: _fbb,
                     start_: start,
                 }
             }
             #[inline]
-            pub fn finish(self) -> flatbuffers::WIPOffset<TaskError<'a>> {
+            pub fn finish(self) -> flatbuffers::WIPOffset<TriggeredEvent<'a>> {
                 let o = self.fbb_.end_table(self.start_);
+                self.fbb_.required(o, TriggeredEvent::VT_TASK_NAME, "task_name");
+                self.fbb_.required(o, TriggeredEvent::VT_NAME, "name");
+                self.fbb_.required(o, TriggeredEvent::VT_LINE, "line");
                 flatbuffers::WIPOffset::new(o.value())
             }
         }
 
-        impl core::fmt::Debug for TaskError<'_> {
+        impl core::fmt::Debug for TriggeredEvent<'_> {
             fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                let mut ds = f.debug_struct("TaskError");
-                ds.field("kind", &self.kind());
-                ds.field("message", &self.message());
+                let mut ds = f.debug_struct("TriggeredEvent");
+                ds.field("task_name", &self.task_name());
+                ds.field("name", &self.name());
+                ds.field("line", &self.line());
+                ds.field("src", &self.src());
                 ds.finish()
             }
         }
-        pub enum DummyTableOffset {}
+        pub enum OutputSuppressedEventOffset {}
         #[derive(Copy, Clone, PartialEq)]
 
-        pub struct DummyTable<'a> {
+        pub struct OutputSuppressedEvent<'a> {
             pub _tab: flatbuffers::Table<'a>,
         }
 
-        impl<'a> flatbuffers::Follow<'a> for DummyTable<'a> {
-            type Inner = DummyTable<'a>;
+        impl<'a> flatbuffers::Follow<'a> for OutputSuppressedEvent<'a> {
+            type Inner = OutputSuppressedEvent<'a>;
             #[inline]
             unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
                 unsafe {
@@ -1356,10 +5670,15 @@ pub mod tcrm {
             }
         }
 
-        impl<'a> DummyTable<'a> {
+        impl<'a> OutputSuppressedEvent<'a> {
+            pub const VT_TASK_NAME: flatbuffers::VOffsetT = 4;
+            pub const VT_SRC: flatbuffers::VOffsetT = 6;
+            pub const VT_COUNT: flatbuffers::VOffsetT = 8;
+            pub const VT_BYTES: flatbuffers::VOffsetT = 10;
+
             #[inline]
             pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-                DummyTable { _tab: table }
+                OutputSuppressedEvent { _tab: table }
             }
             #[allow(unused_mut)]
             pub fn create<
@@ -1369,69 +5688,176 @@ pub mod tcrm {
                 A: flatbuffers::Allocator + 'bldr,
             >(
                 _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
-                _args: &'args DummyTableArgs,
-            ) -> flatbuffers::WIPOffset<DummyTable<'bldr>> {
-                let mut builder = DummyTableBuilder::new(_fbb);
+                args: &'args OutputSuppressedEventArgs<'args>,
+            ) -> flatbuffers::WIPOffset<OutputSuppressedEvent<'bldr>> {
+                let mut builder = OutputSuppressedEventBuilder::new(_fbb);
+                builder.add_bytes(args.bytes);
+                builder.add_count(args.count);
+                if let Some(x) = args.task_name {
+                    builder.add_task_name(x);
+                }
+                builder.add_src(args.src);
                 builder.finish()
             }
+
+            #[inline]
+            pub fn task_name(&self) -> &'a str {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(
+                            OutputSuppressedEvent::VT_TASK_NAME,
+                            None,
+                        )
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn src(&self) -> StreamSource {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<StreamSource>(
+                            OutputSuppressedEvent::VT_SRC,
+                            Some(StreamSource::Stdout),
+                        )
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn count(&self) -> u64 {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<u64>(OutputSuppressedEvent::VT_COUNT, Some(0))
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn bytes(&self) -> u64 {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<u64>(OutputSuppressedEvent::VT_BYTES, Some(0))
+                        .unwrap()
+                }
+            }
         }
 
-        impl flatbuffers::Verifiable for DummyTable<'_> {
+        impl flatbuffers::Verifiable for OutputSuppressedEvent<'_> {
             #[inline]
             fn run_verifier(
                 v: &mut flatbuffers::Verifier,
                 pos: usize,
             ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
                 use self::flatbuffers::Verifiable;
-                v.visit_table(pos)?.finish();
+                v.visit_table(pos)?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "task_name",
+                        Self::VT_TASK_NAME,
+                        true,
+                    )?
+                    .visit_field::<StreamSource>("src", Self::VT_SRC, false)?
+                    .visit_field::<u64>("count", Self::VT_COUNT, false)?
+                    .visit_field::<u64>("bytes", Self::VT_BYTES, false)?
+                    .finish();
                 Ok(())
             }
         }
-        pub struct DummyTableArgs {}
-        impl<'a> Default for DummyTableArgs {
+        pub struct OutputSuppressedEventArgs<'a> {
+            pub task_name: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub src: StreamSource,
+            pub count: u64,
+            pub bytes: u64,
+        }
+        impl<'a> Default for OutputSuppressedEventArgs<'a> {
             #[inline]
             fn default() -> Self {
-                DummyTableArgs {}
+                OutputSuppressedEventArgs {
+                    task_name: None, // required field
+                    src: StreamSource::Stdout,
+                    count: 0,
+                    bytes: 0,
+                }
             }
         }
 
-        pub struct DummyTableBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+        pub struct OutputSuppressedEventBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
             fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
             start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
         }
-        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> DummyTableBuilder<'a, 'b, A> {
+        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> OutputSuppressedEventBuilder<'a, 'b, A> {
+            #[inline]
+            pub fn add_task_name(&mut self, task_name: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    OutputSuppressedEvent::VT_TASK_NAME,
+                    task_name,
+                );
+            }
+            #[inline]
+            pub fn add_src(&mut self, src: StreamSource) {
+                self.fbb_.push_slot::<StreamSource>(
+                    OutputSuppressedEvent::VT_SRC,
+                    src,
+                    StreamSource::Stdout,
+                );
+            }
+            #[inline]
+            pub fn add_count(&mut self, count: u64) {
+                self.fbb_
+                    .push_slot::<u64>(OutputSuppressedEvent::VT_COUNT, count, 0);
+            }
+            #[inline]
+            pub fn add_bytes(&mut self, bytes: u64) {
+                self.fbb_
+                    .push_slot::<u64>(OutputSuppressedEvent::VT_BYTES, bytes, 0);
+            }
             #[inline]
             pub fn new(
                 _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
-            ) -> DummyTableBuilder<'a, 'b, A> {
+            ) -> OutputSuppressedEventBuilder<'a, 'b, A> {
                 let start = _fbb.start_table();
-                DummyTableBuilder {
+                OutputSuppressedEventBuilder {
                     fbb_: _fbb,
                     start_: start,
                 }
             }
             #[inline]
-            pub fn finish(self) -> flatbuffers::WIPOffset<DummyTable<'a>> {
+            pub fn finish(self) -> flatbuffers::WIPOffset<OutputSuppressedEvent<'a>> {
                 let o = self.fbb_.end_table(self.start_);
+                self.fbb_
+                    .required(o, OutputSuppressedEvent::VT_TASK_NAME, "task_name");
                 flatbuffers::WIPOffset::new(o.value())
             }
         }
 
-        impl core::fmt::Debug for DummyTable<'_> {
+        impl core::fmt::Debug for OutputSuppressedEvent<'_> {
             fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                let mut ds = f.debug_struct("DummyTable");
+                let mut ds = f.debug_struct("OutputSuppressedEvent");
+                ds.field("task_name", &self.task_name());
+                ds.field("src", &self.src());
+                ds.field("count", &self.count());
+                ds.field("bytes", &self.bytes());
                 ds.finish()
             }
         }
-        pub enum ErrorStopReasonOffset {}
+        pub enum MemoryWatermarkExceededEventOffset {}
         #[derive(Copy, Clone, PartialEq)]
 
-        pub struct ErrorStopReason<'a> {
+        pub struct MemoryWatermarkExceededEvent<'a> {
             pub _tab: flatbuffers::Table<'a>,
         }
 
-        impl<'a> flatbuffers::Follow<'a> for ErrorStopReason<'a> {
-            type Inner = ErrorStopReason<'a>;
+        impl<'a> flatbuffers::Follow<'a> for MemoryWatermarkExceededEvent<'a> {
+            type Inner = MemoryWatermarkExceededEvent<'a>;
             #[inline]
             unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
                 unsafe {
@@ -1442,12 +5868,14 @@ pub mod tcrm {
             }
         }
 
-        impl<'a> ErrorStopReason<'a> {
-            pub const VT_MESSAGE: flatbuffers::VOffsetT = 4;
+        impl<'a> MemoryWatermarkExceededEvent<'a> {
+            pub const VT_TASK_NAME: flatbuffers::VOffsetT = 4;
+            pub const VT_RSS_BYTES: flatbuffers::VOffsetT = 6;
+            pub const VT_WATERMARK_BYTES: flatbuffers::VOffsetT = 8;
 
             #[inline]
             pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-                ErrorStopReason { _tab: table }
+                MemoryWatermarkExceededEvent { _tab: table }
             }
             #[allow(unused_mut)]
             pub fn create<
@@ -1457,32 +5885,56 @@ pub mod tcrm {
                 A: flatbuffers::Allocator + 'bldr,
             >(
                 _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
-                args: &'args ErrorStopReasonArgs<'args>,
-            ) -> flatbuffers::WIPOffset<ErrorStopReason<'bldr>> {
-                let mut builder = ErrorStopReasonBuilder::new(_fbb);
-                if let Some(x) = args.message {
-                    builder.add_message(x);
+                args: &'args MemoryWatermarkExceededEventArgs<'args>,
+            ) -> flatbuffers::WIPOffset<MemoryWatermarkExceededEvent<'bldr>> {
+                let mut builder = MemoryWatermarkExceededEventBuilder::new(_fbb);
+                builder.add_watermark_bytes(args.watermark_bytes);
+                builder.add_rss_bytes(args.rss_bytes);
+                if let Some(x) = args.task_name {
+                    builder.add_task_name(x);
                 }
                 builder.finish()
             }
 
             #[inline]
-            pub fn message(&self) -> &'a str {
+            pub fn task_name(&self) -> &'a str {
                 // Safety:
                 // Created from valid Table for this object
                 // which contains a valid value in this slot
                 unsafe {
                     self._tab
                         .get::<flatbuffers::ForwardsUOffset<&str>>(
-                            ErrorStopReason::VT_MESSAGE,
+                            MemoryWatermarkExceededEvent::VT_TASK_NAME,
                             None,
                         )
                         .unwrap()
                 }
             }
+            #[inline]
+            pub fn rss_bytes(&self) -> u64 {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<u64>(MemoryWatermarkExceededEvent::VT_RSS_BYTES, Some(0))
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn watermark_bytes(&self) -> u64 {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<u64>(MemoryWatermarkExceededEvent::VT_WATERMARK_BYTES, Some(0))
+                        .unwrap()
+                }
+            }
         }
 
-        impl flatbuffers::Verifiable for ErrorStopReason<'_> {
+        impl flatbuffers::Verifiable for MemoryWatermarkExceededEvent<'_> {
             #[inline]
             fn run_verifier(
                 v: &mut flatbuffers::Verifier,
@@ -1491,73 +5943,97 @@ pub mod tcrm {
                 use self::flatbuffers::Verifiable;
                 v.visit_table(pos)?
                     .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
-                        "message",
-                        Self::VT_MESSAGE,
+                        "task_name",
+                        Self::VT_TASK_NAME,
                         true,
                     )?
+                    .visit_field::<u64>("rss_bytes", Self::VT_RSS_BYTES, false)?
+                    .visit_field::<u64>("watermark_bytes", Self::VT_WATERMARK_BYTES, false)?
                     .finish();
                 Ok(())
             }
         }
-        pub struct ErrorStopReasonArgs<'a> {
-            pub message: Option<flatbuffers::WIPOffset<&'a str>>,
+        pub struct MemoryWatermarkExceededEventArgs<'a> {
+            pub task_name: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub rss_bytes: u64,
+            pub watermark_bytes: u64,
         }
-        impl<'a> Default for ErrorStopReasonArgs<'a> {
+        impl<'a> Default for MemoryWatermarkExceededEventArgs<'a> {
             #[inline]
             fn default() -> Self {
-                ErrorStopReasonArgs {
-                    message: None, // required field
+                MemoryWatermarkExceededEventArgs {
+                    task_name: None, // required field
+                    rss_bytes: 0,
+                    watermark_bytes: 0,
                 }
             }
         }
 
-        pub struct ErrorStopReasonBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+        pub struct MemoryWatermarkExceededEventBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
             fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
             start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
         }
-        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> ErrorStopReasonBuilder<'a, 'b, A> {
+        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> MemoryWatermarkExceededEventBuilder<'a, 'b, A> {
             #[inline]
-            pub fn add_message(&mut self, message: flatbuffers::WIPOffset<&'b str>) {
+            pub fn add_task_name(&mut self, task_name: flatbuffers::WIPOffset<&'b str>) {
                 self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
-                    ErrorStopReason::VT_MESSAGE,
-                    message,
+                    MemoryWatermarkExceededEvent::VT_TASK_NAME,
+                    task_name,
+                );
+            }
+            #[inline]
+            pub fn add_rss_bytes(&mut self, rss_bytes: u64) {
+                self.fbb_.push_slot::<u64>(
+                    MemoryWatermarkExceededEvent::VT_RSS_BYTES,
+                    rss_bytes,
+                    0,
+                );
+            }
+            #[inline]
+            pub fn add_watermark_bytes(&mut self, watermark_bytes: u64) {
+                self.fbb_.push_slot::<u64>(
+                    MemoryWatermarkExceededEvent::VT_WATERMARK_BYTES,
+                    watermark_bytes,
+                    0,
                 );
             }
             #[inline]
             pub fn new(
                 _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
-            ) -> ErrorStopReasonBuilder<'a, 'b, A> {
+            ) -> MemoryWatermarkExceededEventBuilder<'a, 'b, A> {
                 let start = _fbb.start_table();
-                ErrorStopReasonBuilder {
+                MemoryWatermarkExceededEventBuilder {
                     fbb_: _fbb,
                     start_: start,
                 }
             }
             #[inline]
-            pub fn finish(self) -> flatbuffers::WIPOffset<ErrorStopReason<'a>> {
+            pub fn finish(self) -> flatbuffers::WIPOffset<MemoryWatermarkExceededEvent<'a>> {
                 let o = self.fbb_.end_table(self.start_);
                 self.fbb_
-                    .required(o, ErrorStopReason::VT_MESSAGE, "message");
+                    .required(o, MemoryWatermarkExceededEvent::VT_TASK_NAME, "task_name");
                 flatbuffers::WIPOffset::new(o.value())
             }
         }
 
-        impl core::fmt::Debug for ErrorStopReason<'_> {
+        impl core::fmt::Debug for MemoryWatermarkExceededEvent<'_> {
             fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                let mut ds = f.debug_struct("ErrorStopReason");
-                ds.field("message", &self.message());
+                let mut ds = f.debug_struct("MemoryWatermarkExceededEvent");
+                ds.field("task_name", &self.task_name());
+                ds.field("rss_bytes", &self.rss_bytes());
+                ds.field("watermark_bytes", &self.watermark_bytes());
                 ds.finish()
             }
         }
-        pub enum StartedEventOffset {}
+        pub enum SpawnRetryEventOffset {}
         #[derive(Copy, Clone, PartialEq)]
 
-        pub struct StartedEvent<'a> {
+        pub struct SpawnRetryEvent<'a> {
             pub _tab: flatbuffers::Table<'a>,
         }
 
-        impl<'a> flatbuffers::Follow<'a> for StartedEvent<'a> {
-            type Inner = StartedEvent<'a>;
+        impl<'a> flatbuffers::Follow<'a> for SpawnRetryEvent<'a> {
+            type Inner = SpawnRetryEvent<'a>;
             #[inline]
             unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
                 unsafe {
@@ -1568,12 +6044,14 @@ pub mod tcrm {
             }
         }
 
-        impl<'a> StartedEvent<'a> {
+        impl<'a> SpawnRetryEvent<'a> {
             pub const VT_TASK_NAME: flatbuffers::VOffsetT = 4;
+            pub const VT_ATTEMPT: flatbuffers::VOffsetT = 6;
+            pub const VT_ERROR: flatbuffers::VOffsetT = 8;
 
             #[inline]
             pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-                StartedEvent { _tab: table }
+                SpawnRetryEvent { _tab: table }
             }
             #[allow(unused_mut)]
             pub fn create<
@@ -1583,9 +6061,13 @@ pub mod tcrm {
                 A: flatbuffers::Allocator + 'bldr,
             >(
                 _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
-                args: &'args StartedEventArgs<'args>,
-            ) -> flatbuffers::WIPOffset<StartedEvent<'bldr>> {
-                let mut builder = StartedEventBuilder::new(_fbb);
+                args: &'args SpawnRetryEventArgs<'args>,
+            ) -> flatbuffers::WIPOffset<SpawnRetryEvent<'bldr>> {
+                let mut builder = SpawnRetryEventBuilder::new(_fbb);
+                if let Some(x) = args.error {
+                    builder.add_error(x);
+                }
+                builder.add_attempt(args.attempt);
                 if let Some(x) = args.task_name {
                     builder.add_task_name(x);
                 }
@@ -1599,13 +6081,38 @@ pub mod tcrm {
                 // which contains a valid value in this slot
                 unsafe {
                     self._tab
-                        .get::<flatbuffers::ForwardsUOffset<&str>>(StartedEvent::VT_TASK_NAME, None)
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(
+                            SpawnRetryEvent::VT_TASK_NAME,
+                            None,
+                        )
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn attempt(&self) -> u32 {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<u32>(SpawnRetryEvent::VT_ATTEMPT, Some(0))
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn error(&self) -> &'a str {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(SpawnRetryEvent::VT_ERROR, None)
                         .unwrap()
                 }
             }
         }
 
-        impl flatbuffers::Verifiable for StartedEvent<'_> {
+        impl flatbuffers::Verifiable for SpawnRetryEvent<'_> {
             #[inline]
             fn run_verifier(
                 v: &mut flatbuffers::Verifier,
@@ -1618,69 +6125,92 @@ pub mod tcrm {
                         Self::VT_TASK_NAME,
                         true,
                     )?
+                    .visit_field::<u32>("attempt", Self::VT_ATTEMPT, false)?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "error",
+                        Self::VT_ERROR,
+                        true,
+                    )?
                     .finish();
                 Ok(())
             }
         }
-        pub struct StartedEventArgs<'a> {
+        pub struct SpawnRetryEventArgs<'a> {
             pub task_name: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub attempt: u32,
+            pub error: Option<flatbuffers::WIPOffset<&'a str>>,
         }
-        impl<'a> Default for StartedEventArgs<'a> {
+        impl<'a> Default for SpawnRetryEventArgs<'a> {
             #[inline]
             fn default() -> Self {
-                StartedEventArgs {
+                SpawnRetryEventArgs {
                     task_name: None, // required field
+                    attempt: 0,
+                    error: None, // required field
                 }
             }
         }
 
-        pub struct StartedEventBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+        pub struct SpawnRetryEventBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
             fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
             start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
         }
-        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> StartedEventBuilder<'a, 'b, A> {
+        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> SpawnRetryEventBuilder<'a, 'b, A> {
             #[inline]
             pub fn add_task_name(&mut self, task_name: flatbuffers::WIPOffset<&'b str>) {
                 self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
-                    StartedEvent::VT_TASK_NAME,
+                    SpawnRetryEvent::VT_TASK_NAME,
                     task_name,
                 );
             }
             #[inline]
+            pub fn add_attempt(&mut self, attempt: u32) {
+                self.fbb_
+                    .push_slot::<u32>(SpawnRetryEvent::VT_ATTEMPT, attempt, 0);
+            }
+            #[inline]
+            pub fn add_error(&mut self, error: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_
+                    .push_slot_always::<flatbuffers::WIPOffset<_>>(SpawnRetryEvent::VT_ERROR, error);
+            }
+            #[inline]
             pub fn new(
                 _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
-            ) -> StartedEventBuilder<'a, 'b, A> {
+            ) -> SpawnRetryEventBuilder<'a, 'b, A> {
                 let start = _fbb.start_table();
-                StartedEventBuilder {
+                SpawnRetryEventBuilder {
                     fbb_: _fbb,
                     start_: start,
                 }
             }
             #[inline]
-            pub fn finish(self) -> flatbuffers::WIPOffset<StartedEvent<'a>> {
+            pub fn finish(self) -> flatbuffers::WIPOffset<SpawnRetryEvent<'a>> {
                 let o = self.fbb_.end_table(self.start_);
                 self.fbb_
-                    .required(o, StartedEvent::VT_TASK_NAME, "task_name");
+                    .required(o, SpawnRetryEvent::VT_TASK_NAME, "task_name");
+                self.fbb_.required(o, SpawnRetryEvent::VT_ERROR, "error");
                 flatbuffers::WIPOffset::new(o.value())
             }
         }
 
-        impl core::fmt::Debug for StartedEvent<'_> {
+        impl core::fmt::Debug for SpawnRetryEvent<'_> {
             fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                let mut ds = f.debug_struct("StartedEvent");
+                let mut ds = f.debug_struct("SpawnRetryEvent");
                 ds.field("task_name", &self.task_name());
+                ds.field("attempt", &self.attempt());
+                ds.field("error", &self.error());
                 ds.finish()
             }
         }
-        pub enum OutputEventOffset {}
+        pub enum ScriptStepEventOffset {}
         #[derive(Copy, Clone, PartialEq)]
 
-        pub struct OutputEvent<'a> {
+        pub struct ScriptStepEvent<'a> {
             pub _tab: flatbuffers::Table<'a>,
         }
 
-        impl<'a> flatbuffers::Follow<'a> for OutputEvent<'a> {
-            type Inner = OutputEvent<'a>;
+        impl<'a> flatbuffers::Follow<'a> for ScriptStepEvent<'a> {
+            type Inner = ScriptStepEvent<'a>;
             #[inline]
             unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
                 unsafe {
@@ -1691,14 +6221,13 @@ pub mod tcrm {
             }
         }
 
-        impl<'a> OutputEvent<'a> {
+        impl<'a> ScriptStepEvent<'a> {
             pub const VT_TASK_NAME: flatbuffers::VOffsetT = 4;
-            pub const VT_LINE: flatbuffers::VOffsetT = 6;
-            pub const VT_SRC: flatbuffers::VOffsetT = 8;
+            pub const VT_INDEX: flatbuffers::VOffsetT = 6;
 
             #[inline]
             pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-                OutputEvent { _tab: table }
+                ScriptStepEvent { _tab: table }
             }
             #[allow(unused_mut)]
             pub fn create<
@@ -1708,16 +6237,13 @@ pub mod tcrm {
                 A: flatbuffers::Allocator + 'bldr,
             >(
                 _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
-                args: &'args OutputEventArgs<'args>,
-            ) -> flatbuffers::WIPOffset<OutputEvent<'bldr>> {
-                let mut builder = OutputEventBuilder::new(_fbb);
-                if let Some(x) = args.line {
-                    builder.add_line(x);
-                }
+                args: &'args ScriptStepEventArgs<'args>,
+            ) -> flatbuffers::WIPOffset<ScriptStepEvent<'bldr>> {
+                let mut builder = ScriptStepEventBuilder::new(_fbb);
+                builder.add_index(args.index);
                 if let Some(x) = args.task_name {
                     builder.add_task_name(x);
                 }
-                builder.add_src(args.src);
                 builder.finish()
             }
 
@@ -1728,35 +6254,27 @@ pub mod tcrm {
                 // which contains a valid value in this slot
                 unsafe {
                     self._tab
-                        .get::<flatbuffers::ForwardsUOffset<&str>>(OutputEvent::VT_TASK_NAME, None)
-                        .unwrap()
-                }
-            }
-            #[inline]
-            pub fn line(&self) -> &'a str {
-                // Safety:
-                // Created from valid Table for this object
-                // which contains a valid value in this slot
-                unsafe {
-                    self._tab
-                        .get::<flatbuffers::ForwardsUOffset<&str>>(OutputEvent::VT_LINE, None)
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(
+                            ScriptStepEvent::VT_TASK_NAME,
+                            None,
+                        )
                         .unwrap()
                 }
             }
             #[inline]
-            pub fn src(&self) -> StreamSource {
+            pub fn index(&self) -> u32 {
                 // Safety:
                 // Created from valid Table for this object
                 // which contains a valid value in this slot
                 unsafe {
                     self._tab
-                        .get::<StreamSource>(OutputEvent::VT_SRC, Some(StreamSource::Stdout))
+                        .get::<u32>(ScriptStepEvent::VT_INDEX, Some(0))
                         .unwrap()
                 }
             }
         }
 
-        impl flatbuffers::Verifiable for OutputEvent<'_> {
+        impl flatbuffers::Verifiable for ScriptStepEvent<'_> {
             #[inline]
             fn run_verifier(
                 v: &mut flatbuffers::Verifier,
@@ -1769,88 +6287,78 @@ pub mod tcrm {
                         Self::VT_TASK_NAME,
                         true,
                     )?
-                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>("line", Self::VT_LINE, true)?
-                    .visit_field::<StreamSource>("src", Self::VT_SRC, false)?
+                    .visit_field::<u32>("index", Self::VT_INDEX, false)?
                     .finish();
                 Ok(())
             }
         }
-        pub struct OutputEventArgs<'a> {
+        pub struct ScriptStepEventArgs<'a> {
             pub task_name: Option<flatbuffers::WIPOffset<&'a str>>,
-            pub line: Option<flatbuffers::WIPOffset<&'a str>>,
-            pub src: StreamSource,
+            pub index: u32,
         }
-        impl<'a> Default for OutputEventArgs<'a> {
+        impl<'a> Default for ScriptStepEventArgs<'a> {
             #[inline]
             fn default() -> Self {
-                OutputEventArgs {
+                ScriptStepEventArgs {
                     task_name: None, // required field
-                    line: None,      // required field
-                    src: StreamSource::Stdout,
+                    index: 0,
                 }
             }
         }
 
-        pub struct OutputEventBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+        pub struct ScriptStepEventBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
             fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
             start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
         }
-        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> OutputEventBuilder<'a, 'b, A> {
+        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> ScriptStepEventBuilder<'a, 'b, A> {
             #[inline]
             pub fn add_task_name(&mut self, task_name: flatbuffers::WIPOffset<&'b str>) {
                 self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
-                    OutputEvent::VT_TASK_NAME,
+                    ScriptStepEvent::VT_TASK_NAME,
                     task_name,
                 );
             }
             #[inline]
-            pub fn add_line(&mut self, line: flatbuffers::WIPOffset<&'b str>) {
-                self.fbb_
-                    .push_slot_always::<flatbuffers::WIPOffset<_>>(OutputEvent::VT_LINE, line);
-            }
-            #[inline]
-            pub fn add_src(&mut self, src: StreamSource) {
+            pub fn add_index(&mut self, index: u32) {
                 self.fbb_
-                    .push_slot::<StreamSource>(OutputEvent::VT_SRC, src, StreamSource::Stdout);
+                    .push_slot::<u32>(ScriptStepEvent::VT_INDEX, index, 0);
             }
             #[inline]
             pub fn new(
                 _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
-            ) -> OutputEventBuilder<'a, 'b, A> {
+            ) -> ScriptStepEventBuilder<'a, 'b, A> {
                 let start = _fbb.start_table();
-                OutputEventBuilder {
+                ScriptStepEventBuilder {
                     fbb_: _fbb,
                     start_: start,
                 }
             }
             #[inline]
-            pub fn finish(self) -> flatbuffers::WIPOffset<OutputEvent<'a>> {
+            pub fn finish(self) -> flatbuffers::WIPOffset<ScriptStepEvent<'a>> {
                 let o = self.fbb_.end_table(self.start_);
                 self.fbb_
-                    .required(o, OutputEvent::VT_TASK_NAME, "task_name");
-                self.fbb_.required(o, OutputEvent::VT_LINE, "line");
+                    .required(o, ScriptStepEvent::VT_TASK_NAME, "task_name");
                 flatbuffers::WIPOffset::new(o.value())
             }
         }
 
-        impl core::fmt::Debug for OutputEvent<'_> {
+        impl core::fmt::Debug for ScriptStepEvent<'_> {
             fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                let mut ds = f.debug_struct("OutputEvent");
+                let mut ds = f.debug_struct("ScriptStepEvent");
                 ds.field("task_name", &self.task_name());
-                ds.field("line", &self.line());
-                ds.field("src", &self.src());
+                ds.field("index", &self.index());
                 ds.finish()
             }
         }
-        pub enum ReadyEventOffset {}
+        pub enum UnhealthyEventOffset {}
         #[derive(Copy, Clone, PartialEq)]
 
-        pub struct ReadyEvent<'a> {
+        pub struct UnhealthyEvent<'a> {
             pub _tab: flatbuffers::Table<'a>,
         }
 
-        impl<'a> flatbuffers::Follow<'a> for ReadyEvent<'a> {
-            type Inner = ReadyEvent<'a>;
+        impl<'a> flatbuffers::Follow<'a> for UnhealthyEvent<'a> {
+            type Inner = UnhealthyEvent<'a>;
             #[inline]
             unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
                 unsafe {
@@ -1861,12 +6369,13 @@ pub mod tcrm {
             }
         }
 
-        impl<'a> ReadyEvent<'a> {
+        impl<'a> UnhealthyEvent<'a> {
             pub const VT_TASK_NAME: flatbuffers::VOffsetT = 4;
+            pub const VT_CONSECUTIVE_FAILURES: flatbuffers::VOffsetT = 6;
 
             #[inline]
             pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-                ReadyEvent { _tab: table }
+                UnhealthyEvent { _tab: table }
             }
             #[allow(unused_mut)]
             pub fn create<
@@ -1876,9 +6385,10 @@ pub mod tcrm {
                 A: flatbuffers::Allocator + 'bldr,
             >(
                 _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
-                args: &'args ReadyEventArgs<'args>,
-            ) -> flatbuffers::WIPOffset<ReadyEvent<'bldr>> {
-                let mut builder = ReadyEventBuilder::new(_fbb);
+                args: &'args UnhealthyEventArgs<'args>,
+            ) -> flatbuffers::WIPOffset<UnhealthyEvent<'bldr>> {
+                let mut builder = UnhealthyEventBuilder::new(_fbb);
+                builder.add_consecutive_failures(args.consecutive_failures);
                 if let Some(x) = args.task_name {
                     builder.add_task_name(x);
                 }
@@ -1892,13 +6402,27 @@ pub mod tcrm {
                 // which contains a valid value in this slot
                 unsafe {
                     self._tab
-                        .get::<flatbuffers::ForwardsUOffset<&str>>(ReadyEvent::VT_TASK_NAME, None)
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(
+                            UnhealthyEvent::VT_TASK_NAME,
+                            None,
+                        )
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn consecutive_failures(&self) -> u32 {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<u32>(UnhealthyEvent::VT_CONSECUTIVE_FAILURES, Some(0))
                         .unwrap()
                 }
             }
         }
 
-        impl flatbuffers::Verifiable for ReadyEvent<'_> {
+        impl flatbuffers::Verifiable for UnhealthyEvent<'_> {
             #[inline]
             fn run_verifier(
                 v: &mut flatbuffers::Verifier,
@@ -1911,68 +6435,85 @@ pub mod tcrm {
                         Self::VT_TASK_NAME,
                         true,
                     )?
+                    .visit_field::<u32>(
+                        "consecutive_failures",
+                        Self::VT_CONSECUTIVE_FAILURES,
+                        false,
+                    )?
                     .finish();
                 Ok(())
             }
         }
-        pub struct ReadyEventArgs<'a> {
+        pub struct UnhealthyEventArgs<'a> {
             pub task_name: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub consecutive_failures: u32,
         }
-        impl<'a> Default for ReadyEventArgs<'a> {
+        impl<'a> Default for UnhealthyEventArgs<'a> {
             #[inline]
             fn default() -> Self {
-                ReadyEventArgs {
+                UnhealthyEventArgs {
                     task_name: None, // required field
+                    consecutive_failures: 0,
                 }
             }
         }
 
-        pub struct ReadyEventBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+        pub struct UnhealthyEventBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
             fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
             start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
         }
-        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> ReadyEventBuilder<'a, 'b, A> {
+        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> UnhealthyEventBuilder<'a, 'b, A> {
             #[inline]
             pub fn add_task_name(&mut self, task_name: flatbuffers::WIPOffset<&'b str>) {
                 self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
-                    ReadyEvent::VT_TASK_NAME,
+                    UnhealthyEvent::VT_TASK_NAME,
                     task_name,
                 );
             }
             #[inline]
+            pub fn add_consecutive_failures(&mut self, consecutive_failures: u32) {
+                self.fbb_.push_slot::<u32>(
+                    UnhealthyEvent::VT_CONSECUTIVE_FAILURES,
+                    consecutive_failures,
+                    0,
+                );
+            }
+            #[inline]
             pub fn new(
                 _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
-            ) -> ReadyEventBuilder<'a, 'b, A> {
+            ) -> UnhealthyEventBuilder<'a, 'b, A> {
                 let start = _fbb.start_table();
-                ReadyEventBuilder {
+                UnhealthyEventBuilder {
                     fbb_: _fbb,
                     start_: start,
                 }
             }
             #[inline]
-            pub fn finish(self) -> flatbuffers::WIPOffset<ReadyEvent<'a>> {
+            pub fn finish(self) -> flatbuffers::WIPOffset<UnhealthyEvent<'a>> {
                 let o = self.fbb_.end_table(self.start_);
-                self.fbb_.required(o, ReadyEvent::VT_TASK_NAME, "task_name");
+                self.fbb_
+                    .required(o, UnhealthyEvent::VT_TASK_NAME, "task_name");
                 flatbuffers::WIPOffset::new(o.value())
             }
         }
 
-        impl core::fmt::Debug for ReadyEvent<'_> {
+        impl core::fmt::Debug for UnhealthyEvent<'_> {
             fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                let mut ds = f.debug_struct("ReadyEvent");
+                let mut ds = f.debug_struct("UnhealthyEvent");
                 ds.field("task_name", &self.task_name());
+                ds.field("consecutive_failures", &self.consecutive_failures());
                 ds.finish()
             }
         }
-        pub enum StoppedEventOffset {}
+        pub enum SlowConsumerEventOffset {}
         #[derive(Copy, Clone, PartialEq)]
 
-        pub struct StoppedEvent<'a> {
+        pub struct SlowConsumerEvent<'a> {
             pub _tab: flatbuffers::Table<'a>,
         }
 
-        impl<'a> flatbuffers::Follow<'a> for StoppedEvent<'a> {
-            type Inner = StoppedEvent<'a>;
+        impl<'a> flatbuffers::Follow<'a> for SlowConsumerEvent<'a> {
+            type Inner = SlowConsumerEvent<'a>;
             #[inline]
             unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
                 unsafe {
@@ -1983,15 +6524,13 @@ pub mod tcrm {
             }
         }
 
-        impl<'a> StoppedEvent<'a> {
+        impl<'a> SlowConsumerEvent<'a> {
             pub const VT_TASK_NAME: flatbuffers::VOffsetT = 4;
-            pub const VT_EXIT_CODE: flatbuffers::VOffsetT = 6;
-            pub const VT_REASON_TYPE: flatbuffers::VOffsetT = 8;
-            pub const VT_REASON: flatbuffers::VOffsetT = 10;
+            pub const VT_AVG_WAIT_MS: flatbuffers::VOffsetT = 6;
 
             #[inline]
             pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-                StoppedEvent { _tab: table }
+                SlowConsumerEvent { _tab: table }
             }
             #[allow(unused_mut)]
             pub fn create<
@@ -2001,156 +6540,44 @@ pub mod tcrm {
                 A: flatbuffers::Allocator + 'bldr,
             >(
                 _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
-                args: &'args StoppedEventArgs<'args>,
-            ) -> flatbuffers::WIPOffset<StoppedEvent<'bldr>> {
-                let mut builder = StoppedEventBuilder::new(_fbb);
-                if let Some(x) = args.reason {
-                    builder.add_reason(x);
-                }
-                builder.add_exit_code(args.exit_code);
+                args: &'args SlowConsumerEventArgs<'args>,
+            ) -> flatbuffers::WIPOffset<SlowConsumerEvent<'bldr>> {
+                let mut builder = SlowConsumerEventBuilder::new(_fbb);
+                builder.add_avg_wait_ms(args.avg_wait_ms);
                 if let Some(x) = args.task_name {
                     builder.add_task_name(x);
                 }
-                builder.add_reason_type(args.reason_type);
-                builder.finish()
-            }
-
-            #[inline]
-            pub fn task_name(&self) -> &'a str {
-                // Safety:
-                // Created from valid Table for this object
-                // which contains a valid value in this slot
-                unsafe {
-                    self._tab
-                        .get::<flatbuffers::ForwardsUOffset<&str>>(StoppedEvent::VT_TASK_NAME, None)
-                        .unwrap()
-                }
-            }
-            #[inline]
-            pub fn exit_code(&self) -> i32 {
-                // Safety:
-                // Created from valid Table for this object
-                // which contains a valid value in this slot
-                unsafe {
-                    self._tab
-                        .get::<i32>(StoppedEvent::VT_EXIT_CODE, Some(0))
-                        .unwrap()
-                }
-            }
-            #[inline]
-            pub fn reason_type(&self) -> TaskEventStopReason {
-                // Safety:
-                // Created from valid Table for this object
-                // which contains a valid value in this slot
-                unsafe {
-                    self._tab
-                        .get::<TaskEventStopReason>(
-                            StoppedEvent::VT_REASON_TYPE,
-                            Some(TaskEventStopReason::NONE),
-                        )
-                        .unwrap()
-                }
-            }
-            #[inline]
-            pub fn reason(&self) -> flatbuffers::Table<'a> {
-                // Safety:
-                // Created from valid Table for this object
-                // which contains a valid value in this slot
-                unsafe {
-                    self._tab
-                        .get::<flatbuffers::ForwardsUOffset<flatbuffers::Table<'a>>>(
-                            StoppedEvent::VT_REASON,
-                            None,
-                        )
-                        .unwrap()
-                }
-            }
-            #[inline]
-            #[allow(non_snake_case)]
-            pub fn reason_as_finished(&self) -> Option<DummyTable<'a>> {
-                if self.reason_type() == TaskEventStopReason::Finished {
-                    let u = self.reason();
-                    // Safety:
-                    // Created from a valid Table for this object
-                    // Which contains a valid union in this slot
-                    Some(unsafe { DummyTable::init_from_table(u) })
-                } else {
-                    None
-                }
-            }
-
-            #[inline]
-            #[allow(non_snake_case)]
-            pub fn reason_as_terminated_timeout(&self) -> Option<DummyTable<'a>> {
-                if self.reason_type() == TaskEventStopReason::TerminatedTimeout {
-                    let u = self.reason();
-                    // Safety:
-                    // Created from a valid Table for this object
-                    // Which contains a valid union in this slot
-                    Some(unsafe { DummyTable::init_from_table(u) })
-                } else {
-                    None
-                }
-            }
-
-            #[inline]
-            #[allow(non_snake_case)]
-            pub fn reason_as_terminated_cleanup(&self) -> Option<DummyTable<'a>> {
-                if self.reason_type() == TaskEventStopReason::TerminatedCleanup {
-                    let u = self.reason();
-                    // Safety:
-                    // Created from a valid Table for this object
-                    // Which contains a valid union in this slot
-                    Some(unsafe { DummyTable::init_from_table(u) })
-                } else {
-                    None
-                }
-            }
-
-            #[inline]
-            #[allow(non_snake_case)]
-            pub fn reason_as_terminated_dependencies_finished(&self) -> Option<DummyTable<'a>> {
-                if self.reason_type() == TaskEventStopReason::TerminatedDependenciesFinished {
-                    let u = self.reason();
-                    // Safety:
-                    // Created from a valid Table for this object
-                    // Which contains a valid union in this slot
-                    Some(unsafe { DummyTable::init_from_table(u) })
-                } else {
-                    None
-                }
-            }
-
-            #[inline]
-            #[allow(non_snake_case)]
-            pub fn reason_as_terminated_user_requested(&self) -> Option<DummyTable<'a>> {
-                if self.reason_type() == TaskEventStopReason::TerminatedUserRequested {
-                    let u = self.reason();
-                    // Safety:
-                    // Created from a valid Table for this object
-                    // Which contains a valid union in this slot
-                    Some(unsafe { DummyTable::init_from_table(u) })
-                } else {
-                    None
-                }
+                builder.finish()
             }
 
             #[inline]
-            #[allow(non_snake_case)]
-            pub fn reason_as_error(&self) -> Option<ErrorStopReason<'a>> {
-                if self.reason_type() == TaskEventStopReason::Error {
-                    let u = self.reason();
-                    // Safety:
-                    // Created from a valid Table for this object
-                    // Which contains a valid union in this slot
-                    Some(unsafe { ErrorStopReason::init_from_table(u) })
-                } else {
-                    None
+            pub fn task_name(&self) -> &'a str {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(
+                            SlowConsumerEvent::VT_TASK_NAME,
+                            None,
+                        )
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn avg_wait_ms(&self) -> u64 {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<u64>(SlowConsumerEvent::VT_AVG_WAIT_MS, Some(0))
+                        .unwrap()
                 }
             }
         }
 
-        impl flatbuffers::Verifiable for StoppedEvent<'_> {
+        impl flatbuffers::Verifiable for SlowConsumerEvent<'_> {
             #[inline]
             fn run_verifier(
                 v: &mut flatbuffers::Verifier,
@@ -2158,166 +6585,249 @@ pub mod tcrm {
             ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
                 use self::flatbuffers::Verifiable;
                 v.visit_table(pos)?
-     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("task_name", Self::VT_TASK_NAME, true)?
-     .visit_field::<i32>("exit_code", Self::VT_EXIT_CODE, false)?
-     .visit_union::<TaskEventStopReason, _>("reason_type", Self::VT_REASON_TYPE, "reason", Self::VT_REASON, true, |key, v, pos| {
-        match key {
-          TaskEventStopReason::Finished => v.verify_union_variant::<flatbuffers::ForwardsUOffset<DummyTable>>("TaskEventStopReason::Finished", pos),
-          TaskEventStopReason::TerminatedTimeout => v.verify_union_variant::<flatbuffers::ForwardsUOffset<DummyTable>>("TaskEventStopReason::TerminatedTimeout", pos),
-          TaskEventStopReason::TerminatedCleanup => v.verify_union_variant::<flatbuffers::ForwardsUOffset<DummyTable>>("TaskEventStopReason::TerminatedCleanup", pos),
-          TaskEventStopReason::TerminatedDependenciesFinished => v.verify_union_variant::<flatbuffers::ForwardsUOffset<DummyTable>>("TaskEventStopReason::TerminatedDependenciesFinished", pos),
-          TaskEventStopReason::TerminatedUserRequested => v.verify_union_variant::<flatbuffers::ForwardsUOffset<DummyTable>>("TaskEventStopReason::TerminatedUserRequested", pos),
-          TaskEventStopReason::Error => v.verify_union_variant::<flatbuffers::ForwardsUOffset<ErrorStopReason>>("TaskEventStopReason::Error", pos),
-          _ => Ok(()),
-        }
-     })?
-     .finish();
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "task_name",
+                        Self::VT_TASK_NAME,
+                        true,
+                    )?
+                    .visit_field::<u64>("avg_wait_ms", Self::VT_AVG_WAIT_MS, false)?
+                    .finish();
                 Ok(())
             }
         }
-        pub struct StoppedEventArgs<'a> {
+        pub struct SlowConsumerEventArgs<'a> {
             pub task_name: Option<flatbuffers::WIPOffset<&'a str>>,
-            pub exit_code: i32,
-            pub reason_type: TaskEventStopReason,
-            pub reason: Option<flatbuffers::WIPOffset<flatbuffers::UnionWIPOffset>>,
+            pub avg_wait_ms: u64,
         }
-        impl<'a> Default for StoppedEventArgs<'a> {
+        impl<'a> Default for SlowConsumerEventArgs<'a> {
             #[inline]
             fn default() -> Self {
-                StoppedEventArgs {
+                SlowConsumerEventArgs {
                     task_name: None, // required field
-                    exit_code: 0,
-                    reason_type: TaskEventStopReason::NONE,
-                    reason: None, // required field
+                    avg_wait_ms: 0,
                 }
             }
         }
 
-        pub struct StoppedEventBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+        pub struct SlowConsumerEventBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
             fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
             start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
         }
-        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> StoppedEventBuilder<'a, 'b, A> {
+        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> SlowConsumerEventBuilder<'a, 'b, A> {
             #[inline]
             pub fn add_task_name(&mut self, task_name: flatbuffers::WIPOffset<&'b str>) {
                 self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
-                    StoppedEvent::VT_TASK_NAME,
+                    SlowConsumerEvent::VT_TASK_NAME,
                     task_name,
                 );
             }
             #[inline]
-            pub fn add_exit_code(&mut self, exit_code: i32) {
-                self.fbb_
-                    .push_slot::<i32>(StoppedEvent::VT_EXIT_CODE, exit_code, 0);
-            }
-            #[inline]
-            pub fn add_reason_type(&mut self, reason_type: TaskEventStopReason) {
-                self.fbb_.push_slot::<TaskEventStopReason>(
-                    StoppedEvent::VT_REASON_TYPE,
-                    reason_type,
-                    TaskEventStopReason::NONE,
-                );
-            }
-            #[inline]
-            pub fn add_reason(
-                &mut self,
-                reason: flatbuffers::WIPOffset<flatbuffers::UnionWIPOffset>,
-            ) {
+            pub fn add_avg_wait_ms(&mut self, avg_wait_ms: u64) {
                 self.fbb_
-                    .push_slot_always::<flatbuffers::WIPOffset<_>>(StoppedEvent::VT_REASON, reason);
+                    .push_slot::<u64>(SlowConsumerEvent::VT_AVG_WAIT_MS, avg_wait_ms, 0);
             }
             #[inline]
             pub fn new(
                 _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
-            ) -> StoppedEventBuilder<'a, 'b, A> {
+            ) -> SlowConsumerEventBuilder<'a, 'b, A> {
                 let start = _fbb.start_table();
-                StoppedEventBuilder {
+                SlowConsumerEventBuilder {
                     fbb_: _fbb,
                     start_: start,
                 }
             }
             #[inline]
-            pub fn finish(self) -> flatbuffers::WIPOffset<StoppedEvent<'a>> {
+            pub fn finish(self) -> flatbuffers::WIPOffset<SlowConsumerEvent<'a>> {
                 let o = self.fbb_.end_table(self.start_);
                 self.fbb_
-                    .required(o, StoppedEvent::VT_TASK_NAME, "task_name");
-                self.fbb_.required(o, StoppedEvent::VT_REASON, "reason");
+                    .required(o, SlowConsumerEvent::VT_TASK_NAME, "task_name");
                 flatbuffers::WIPOffset::new(o.value())
             }
         }
 
-        impl core::fmt::Debug for StoppedEvent<'_> {
+        impl core::fmt::Debug for SlowConsumerEvent<'_> {
             fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-                let mut ds = f.debug_struct("StoppedEvent");
+                let mut ds = f.debug_struct("SlowConsumerEvent");
                 ds.field("task_name", &self.task_name());
-                ds.field("exit_code", &self.exit_code());
-                ds.field("reason_type", &self.reason_type());
-                match self.reason_type() {
-                    TaskEventStopReason::Finished => {
-                        if let Some(x) = self.reason_as_finished() {
-                            ds.field("reason", &x)
-                        } else {
-                            ds.field(
-                                "reason",
-                                &"InvalidFlatbuffer: Union discriminant does not match value.",
-                            )
-                        }
-                    }
-                    TaskEventStopReason::TerminatedTimeout => {
-                        if let Some(x) = self.reason_as_terminated_timeout() {
-                            ds.field("reason", &x)
-                        } else {
-                            ds.field(
-                                "reason",
-                                &"InvalidFlatbuffer: Union discriminant does not match value.",
-                            )
-                        }
-                    }
-                    TaskEventStopReason::TerminatedCleanup => {
-                        if let Some(x) = self.reason_as_terminated_cleanup() {
-                            ds.field("reason", &x)
-                        } else {
-                            ds.field(
-                                "reason",
-                                &"InvalidFlatbuffer: Union discriminant does not match value.",
-                            )
-                        }
-                    }
-                    TaskEventStopReason::TerminatedDependenciesFinished => {
-                        if let Some(x) = self.reason_as_terminated_dependencies_finished() {
-                            ds.field("reason", &x)
-                        } else {
-                            ds.field(
-                                "reason",
-                                &"InvalidFlatbuffer: Union discriminant does not match value.",
-                            )
-                        }
-                    }
-                    TaskEventStopReason::TerminatedUserRequested => {
-                        if let Some(x) = self.reason_as_terminated_user_requested() {
-                            ds.field("reason", &x)
-                        } else {
-                            ds.field(
-                                "reason",
-                                &"InvalidFlatbuffer: Union discriminant does not match value.",
-                            )
-                        }
-                    }
-                    TaskEventStopReason::Error => {
-                        if let Some(x) = self.reason_as_error() {
-                            ds.field("reason", &x)
-                        } else {
-                            ds.field(
-                                "reason",
-                                &"InvalidFlatbuffer: Union discriminant does not match value.",
-                            )
-                        }
-                    }
-                    _ => {
-                        let x: Option<()> = None;
-                        ds.field("reason", &x)
+                ds.field("avg_wait_ms", &self.avg_wait_ms());
+                ds.finish()
+            }
+        }
+        pub enum OutputChunkEventOffset {}
+        #[derive(Copy, Clone, PartialEq)]
+
+        pub struct OutputChunkEvent<'a> {
+            pub _tab: flatbuffers::Table<'a>,
+        }
+
+        impl<'a> flatbuffers::Follow<'a> for OutputChunkEvent<'a> {
+            type Inner = OutputChunkEvent<'a>;
+            #[inline]
+            unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+                unsafe {
+                    Self {
+                        _tab: flatbuffers::Table::new(buf, loc),
                     }
-                };
+                }
+            }
+        }
+
+        impl<'a> OutputChunkEvent<'a> {
+            pub const VT_TASK_NAME: flatbuffers::VOffsetT = 4;
+            pub const VT_DATA: flatbuffers::VOffsetT = 6;
+            pub const VT_SRC: flatbuffers::VOffsetT = 8;
+
+            #[inline]
+            pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+                OutputChunkEvent { _tab: table }
+            }
+            #[allow(unused_mut)]
+            pub fn create<
+                'bldr: 'args,
+                'args: 'mut_bldr,
+                'mut_bldr,
+                A: flatbuffers::Allocator + 'bldr,
+            >(
+                _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+                args: &'args OutputChunkEventArgs<'args>,
+            ) -> flatbuffers::WIPOffset<OutputChunkEvent<'bldr>> {
+                let mut builder = OutputChunkEventBuilder::new(_fbb);
+                if let Some(x) = args.data {
+                    builder.add_data(x);
+                }
+                if let Some(x) = args.task_name {
+                    builder.add_task_name(x);
+                }
+                builder.add_src(args.src);
+                builder.finish()
+            }
+
+            #[inline]
+            pub fn task_name(&self) -> &'a str {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<&str>>(
+                            OutputChunkEvent::VT_TASK_NAME,
+                            None,
+                        )
+                        .unwrap()
+                }
+            }
+            #[inline]
+            pub fn data(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(
+                            OutputChunkEvent::VT_DATA,
+                            None,
+                        )
+                }
+            }
+            #[inline]
+            pub fn src(&self) -> StreamSource {
+                // Safety:
+                // Created from valid Table for this object
+                // which contains a valid value in this slot
+                unsafe {
+                    self._tab
+                        .get::<StreamSource>(OutputChunkEvent::VT_SRC, Some(StreamSource::Stdout))
+                        .unwrap()
+                }
+            }
+        }
+
+        impl flatbuffers::Verifiable for OutputChunkEvent<'_> {
+            #[inline]
+            fn run_verifier(
+                v: &mut flatbuffers::Verifier,
+                pos: usize,
+            ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+                use self::flatbuffers::Verifiable;
+                v.visit_table(pos)?
+                    .visit_field::<flatbuffers::ForwardsUOffset<&str>>(
+                        "task_name",
+                        Self::VT_TASK_NAME,
+                        true,
+                    )?
+                    .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>(
+                        "data",
+                        Self::VT_DATA,
+                        false,
+                    )?
+                    .visit_field::<StreamSource>("src", Self::VT_SRC, false)?
+                    .finish();
+                Ok(())
+            }
+        }
+        pub struct OutputChunkEventArgs<'a> {
+            pub task_name: Option<flatbuffers::WIPOffset<&'a str>>,
+            pub data: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+            pub src: StreamSource,
+        }
+        impl<'a> Default for OutputChunkEventArgs<'a> {
+            #[inline]
+            fn default() -> Self {
+                OutputChunkEventArgs {
+                    task_name: None, // required field
+                    data: None,
+                    src: StreamSource::Stdout,
+                }
+            }
+        }
+
+        pub struct OutputChunkEventBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+            fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+        }
+        impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> OutputChunkEventBuilder<'a, 'b, A> {
+            #[inline]
+            pub fn add_task_name(&mut self, task_name: flatbuffers::WIPOffset<&'b str>) {
+                self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(
+                    OutputChunkEvent::VT_TASK_NAME,
+                    task_name,
+                );
+            }
+            #[inline]
+            pub fn add_data(&mut self, data: flatbuffers::WIPOffset<flatbuffers::Vector<'b, u8>>) {
+                self.fbb_
+                    .push_slot_always::<flatbuffers::WIPOffset<_>>(OutputChunkEvent::VT_DATA, data);
+            }
+            #[inline]
+            pub fn add_src(&mut self, src: StreamSource) {
+                self.fbb_
+                    .push_slot::<StreamSource>(OutputChunkEvent::VT_SRC, src, StreamSource::Stdout);
+            }
+            #[inline]
+            pub fn new(
+                _fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+            ) -> OutputChunkEventBuilder<'a, 'b, A> {
+                let start = _fbb.start_table();
+                OutputChunkEventBuilder {
+                    fbb_: _fbb,
+                    start_: start,
+                }
+            }
+            #[inline]
+            pub fn finish(self) -> flatbuffers::WIPOffset<OutputChunkEvent<'a>> {
+                let o = self.fbb_.end_table(self.start_);
+                self.fbb_
+                    .required(o, OutputChunkEvent::VT_TASK_NAME, "task_name");
+                flatbuffers::WIPOffset::new(o.value())
+            }
+        }
+
+        impl core::fmt::Debug for OutputChunkEvent<'_> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let mut ds = f.debug_struct("OutputChunkEvent");
+                ds.field("task_name", &self.task_name());
+                ds.field("data", &self.data());
+                ds.field("src", &self.src());
                 ds.finish()
             }
         }
@@ -2618,6 +7128,158 @@ pub mod tcrm {
                     None
                 }
             }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn event_as_running_long(&self) -> Option<RunningLongEvent<'a>> {
+                if self.event_type() == TaskEventUnion::RunningLong {
+                    self.event().map(|t| {
+                        // Safety:
+                        // Created from a valid Table for this object
+                        // Which contains a valid union in this slot
+                        unsafe { RunningLongEvent::init_from_table(t) }
+                    })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn event_as_output_repeated(&self) -> Option<OutputRepeatedEvent<'a>> {
+                if self.event_type() == TaskEventUnion::OutputRepeated {
+                    self.event().map(|t| {
+                        // Safety:
+                        // Created from a valid Table for this object
+                        // Which contains a valid union in this slot
+                        unsafe { OutputRepeatedEvent::init_from_table(t) }
+                    })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn event_as_triggered(&self) -> Option<TriggeredEvent<'a>> {
+                if self.event_type() == TaskEventUnion::Triggered {
+                    self.event().map(|t| {
+                        // Safety:
+                        // Created from a valid Table for this object
+                        // Which contains a valid union in this slot
+                        unsafe { TriggeredEvent::init_from_table(t) }
+                    })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn event_as_output_suppressed(&self) -> Option<OutputSuppressedEvent<'a>> {
+                if self.event_type() == TaskEventUnion::OutputSuppressed {
+                    self.event().map(|t| {
+                        // Safety:
+                        // Created from a valid Table for this object
+                        // Which contains a valid union in this slot
+                        unsafe { OutputSuppressedEvent::init_from_table(t) }
+                    })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn event_as_memory_watermark_exceeded(
+                &self,
+            ) -> Option<MemoryWatermarkExceededEvent<'a>> {
+                if self.event_type() == TaskEventUnion::MemoryWatermarkExceeded {
+                    self.event().map(|t| {
+                        // Safety:
+                        // Created from a valid Table for this object
+                        // Which contains a valid union in this slot
+                        unsafe { MemoryWatermarkExceededEvent::init_from_table(t) }
+                    })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn event_as_spawn_retry(&self) -> Option<SpawnRetryEvent<'a>> {
+                if self.event_type() == TaskEventUnion::SpawnRetry {
+                    self.event().map(|t| {
+                        // Safety:
+                        // Created from a valid Table for this object
+                        // Which contains a valid union in this slot
+                        unsafe { SpawnRetryEvent::init_from_table(t) }
+                    })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn event_as_script_step(&self) -> Option<ScriptStepEvent<'a>> {
+                if self.event_type() == TaskEventUnion::ScriptStep {
+                    self.event().map(|t| {
+                        // Safety:
+                        // Created from a valid Table for this object
+                        // Which contains a valid union in this slot
+                        unsafe { ScriptStepEvent::init_from_table(t) }
+                    })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn event_as_unhealthy(&self) -> Option<UnhealthyEvent<'a>> {
+                if self.event_type() == TaskEventUnion::Unhealthy {
+                    self.event().map(|t| {
+                        // Safety:
+                        // Created from a valid Table for this object
+                        // Which contains a valid union in this slot
+                        unsafe { UnhealthyEvent::init_from_table(t) }
+                    })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn event_as_slow_consumer(&self) -> Option<SlowConsumerEvent<'a>> {
+                if self.event_type() == TaskEventUnion::SlowConsumer {
+                    self.event().map(|t| {
+                        // Safety:
+                        // Created from a valid Table for this object
+                        // Which contains a valid union in this slot
+                        unsafe { SlowConsumerEvent::init_from_table(t) }
+                    })
+                } else {
+                    None
+                }
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            pub fn event_as_output_chunk(&self) -> Option<OutputChunkEvent<'a>> {
+                if self.event_type() == TaskEventUnion::OutputChunk {
+                    self.event().map(|t| {
+                        // Safety:
+                        // Created from a valid Table for this object
+                        // Which contains a valid union in this slot
+                        unsafe { OutputChunkEvent::init_from_table(t) }
+                    })
+                } else {
+                    None
+                }
+            }
         }
 
         impl flatbuffers::Verifiable for TaskEvent<'_> {
@@ -2660,6 +7322,56 @@ pub mod tcrm {
                                     "TaskEventUnion::Error",
                                     pos,
                                 ),
+                            TaskEventUnion::RunningLong => v
+                                .verify_union_variant::<flatbuffers::ForwardsUOffset<RunningLongEvent>>(
+                                    "TaskEventUnion::RunningLong",
+                                    pos,
+                                ),
+                            TaskEventUnion::OutputRepeated => v
+                                .verify_union_variant::<flatbuffers::ForwardsUOffset<OutputRepeatedEvent>>(
+                                    "TaskEventUnion::OutputRepeated",
+                                    pos,
+                                ),
+                            TaskEventUnion::Triggered => v
+                                .verify_union_variant::<flatbuffers::ForwardsUOffset<TriggeredEvent>>(
+                                    "TaskEventUnion::Triggered",
+                                    pos,
+                                ),
+                            TaskEventUnion::OutputSuppressed => v
+                                .verify_union_variant::<flatbuffers::ForwardsUOffset<OutputSuppressedEvent>>(
+                                    "TaskEventUnion::OutputSuppressed",
+                                    pos,
+                                ),
+                            TaskEventUnion::MemoryWatermarkExceeded => v
+                                .verify_union_variant::<flatbuffers::ForwardsUOffset<MemoryWatermarkExceededEvent>>(
+                                    "TaskEventUnion::MemoryWatermarkExceeded",
+                                    pos,
+                                ),
+                            TaskEventUnion::SpawnRetry => v
+                                .verify_union_variant::<flatbuffers::ForwardsUOffset<SpawnRetryEvent>>(
+                                    "TaskEventUnion::SpawnRetry",
+                                    pos,
+                                ),
+                            TaskEventUnion::ScriptStep => v
+                                .verify_union_variant::<flatbuffers::ForwardsUOffset<ScriptStepEvent>>(
+                                    "TaskEventUnion::ScriptStep",
+                                    pos,
+                                ),
+                            TaskEventUnion::Unhealthy => v
+                                .verify_union_variant::<flatbuffers::ForwardsUOffset<UnhealthyEvent>>(
+                                    "TaskEventUnion::Unhealthy",
+                                    pos,
+                                ),
+                            TaskEventUnion::SlowConsumer => v
+                                .verify_union_variant::<flatbuffers::ForwardsUOffset<SlowConsumerEvent>>(
+                                    "TaskEventUnion::SlowConsumer",
+                                    pos,
+                                ),
+                            TaskEventUnion::OutputChunk => v
+                                .verify_union_variant::<flatbuffers::ForwardsUOffset<OutputChunkEvent>>(
+                                    "TaskEventUnion::OutputChunk",
+                                    pos,
+                                ),
                             _ => Ok(()),
                         },
                     )?
@@ -2774,6 +7486,106 @@ pub mod tcrm {
                             )
                         }
                     }
+                    TaskEventUnion::RunningLong => {
+                        if let Some(x) = self.event_as_running_long() {
+                            ds.field("event", &x)
+                        } else {
+                            ds.field(
+                                "event",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventUnion::OutputRepeated => {
+                        if let Some(x) = self.event_as_output_repeated() {
+                            ds.field("event", &x)
+                        } else {
+                            ds.field(
+                                "event",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventUnion::Triggered => {
+                        if let Some(x) = self.event_as_triggered() {
+                            ds.field("event", &x)
+                        } else {
+                            ds.field(
+                                "event",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventUnion::OutputSuppressed => {
+                        if let Some(x) = self.event_as_output_suppressed() {
+                            ds.field("event", &x)
+                        } else {
+                            ds.field(
+                                "event",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventUnion::MemoryWatermarkExceeded => {
+                        if let Some(x) = self.event_as_memory_watermark_exceeded() {
+                            ds.field("event", &x)
+                        } else {
+                            ds.field(
+                                "event",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventUnion::SpawnRetry => {
+                        if let Some(x) = self.event_as_spawn_retry() {
+                            ds.field("event", &x)
+                        } else {
+                            ds.field(
+                                "event",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventUnion::ScriptStep => {
+                        if let Some(x) = self.event_as_script_step() {
+                            ds.field("event", &x)
+                        } else {
+                            ds.field(
+                                "event",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventUnion::Unhealthy => {
+                        if let Some(x) = self.event_as_unhealthy() {
+                            ds.field("event", &x)
+                        } else {
+                            ds.field(
+                                "event",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventUnion::SlowConsumer => {
+                        if let Some(x) = self.event_as_slow_consumer() {
+                            ds.field("event", &x)
+                        } else {
+                            ds.field(
+                                "event",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
+                    TaskEventUnion::OutputChunk => {
+                        if let Some(x) = self.event_as_output_chunk() {
+                            ds.field("event", &x)
+                        } else {
+                            ds.field(
+                                "event",
+                                &"InvalidFlatbuffer: Union discriminant does not match value.",
+                            )
+                        }
+                    }
                     _ => {
                         let x: Option<()> = None;
                         ds.field("event", &x)
@@ -14,15 +14,19 @@
 //!
 //! ## Quick Start
 //!
-//! ```rust
+#![cfg_attr(feature = "tokio", doc = "```rust")]
+#![cfg_attr(not(feature = "tokio"), doc = "```ignore")]
 //! use tcrm_task::tasks::{config::TaskConfig, async_tokio::spawner::TaskSpawner};
 //! use tokio::sync::mpsc;
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //!     // Create a simple command configuration
-//!     let config = TaskConfig::new("cmd")
-//!         .args(["/C", "echo", "Hello, World!"]);
+//!     let config = if cfg!(windows) {
+//!         TaskConfig::new("cmd").args(["/C", "echo", "Hello, World!"])
+//!     } else {
+//!         TaskConfig::new("echo").args(["Hello, World!"])
+//!     };
 //!
 //!     // Validate the configuration
 //!     config.validate()?;
@@ -46,14 +50,19 @@
 //!
 //! ### Long-running Process with Ready Indicator
 //!
-//! ```rust
+#![cfg_attr(feature = "tokio", doc = "```rust")]
+#![cfg_attr(not(feature = "tokio"), doc = "```ignore")]
 //! use tcrm_task::tasks::{config::{TaskConfig, StreamSource}, async_tokio::spawner::TaskSpawner};
 //! use tokio::sync::mpsc;
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
-//!     let config = TaskConfig::new("cmd")
-//!         .args(["/C", "echo", "Server listening on"])
+//!     let command = if cfg!(windows) {
+//!         TaskConfig::new("cmd").args(["/C", "echo", "Server listening on"])
+//!     } else {
+//!         TaskConfig::new("echo").args(["Server listening on"])
+//!     };
+//!     let config = command
 //!         .ready_indicator("Server listening on")
 //!         .ready_indicator_source(StreamSource::Stdout)
 //!         .timeout_ms(30000);
@@ -77,7 +86,8 @@
 //!
 //! ### Process with Environment Variables and Working Directory
 //!
-//! ```rust
+#![cfg_attr(feature = "tokio", doc = "```rust")]
+#![cfg_attr(not(feature = "tokio"), doc = "```ignore")]
 //! use tcrm_task::tasks::{config::TaskConfig, async_tokio::spawner::TaskSpawner};
 //! use std::collections::HashMap;
 //!
@@ -87,9 +97,13 @@
 //!     env.insert("RUST_LOG".to_string(), "debug".to_string());
 //!     env.insert("APP_ENV".to_string(), "production".to_string());
 //!
-//!     let config = TaskConfig::new("cmd")
-//!         .args(["/C", "dir"])
-//!         .working_dir("C:\\")
+//!     let (command, root) = if cfg!(windows) {
+//!         (TaskConfig::new("cmd").args(["/C", "dir"]), "C:\\")
+//!     } else {
+//!         (TaskConfig::new("ls"), "/")
+//!     };
+//!     let config = command
+//!         .working_dir(root)
 //!         .env(env)
 //!         .timeout_ms(300000); // 5 minutes
 //!
@@ -110,11 +124,16 @@
 //!
 //! ## Optional Features
 //!
+//! - `tokio` (default): Enable `tasks::async_tokio`, which spawns and manages processes.
+//!   Without it, only configuration, validation, events and states are built.
 //! - `serde`: Enable serialization support for all types
 //! - `flatbuffers`: Enable `FlatBuffers` serialization for high-performance scenarios
 //! - `tracing`: Enable structured logging integration
 
+mod capabilities;
 #[cfg(feature = "flatbuffers")]
 pub mod flatbuffers;
 pub mod helper;
 pub mod tasks;
+
+pub use capabilities::{Capabilities, capabilities};
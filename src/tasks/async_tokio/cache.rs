@@ -0,0 +1,350 @@
+//! Caching of task results for idempotent commands.
+//!
+//! For build-system-like usage, `TaskSpawner::start_cached` looks up the
+//! [`CacheKey`] of the task configuration in a [`CacheLayer`] before spawning.
+//! On a hit, the cached result is replayed as events and no process is
+//! spawned; on a miss, the task runs as with `start_direct` and its result is
+//! stored if it succeeded. [`MemoryCache`] keeps results in memory; persistent
+//! caches can implement [`CacheLayer`] outside this crate.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Mutex, PoisonError},
+};
+
+use crate::tasks::{
+    async_tokio::{startup_output::CapturedLine, temp_dir::DEFAULT_TEMP_DIR_PREFIX},
+    config::TaskConfig,
+};
+
+/// FNV-1a 64-bit offset basis
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+/// FNV-1a 64-bit prime
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Stable hash of what a task configuration runs
+///
+/// Covers the command, its arguments, the configured environment variables,
+/// whether the inherited environment is cleared and which variables are kept,
+/// and the working directory as configured (not canonicalized), including
+/// working directory candidates and a temporary working directory. The values
+/// of inherited variables and the files the command reads are not covered, so
+/// a cached result is only correct for commands whose result depends on
+/// nothing else.
+///
+/// The hash is FNV-1a over the length-prefixed fields, so it is the same
+/// across builds and platforms and can key a persistent cache.
+///
+/// # Examples
+/// ```rust
+/// use tcrm_task::tasks::{async_tokio::cache::CacheKey, config::TaskConfig};
+///
+/// let a = TaskConfig::new("cargo").args(["build"]).env([("A", "1"), ("B", "2")]);
+/// let b = TaskConfig::new("cargo").args(["build"]).env([("B", "2"), ("A", "1")]);
+/// assert_eq!(CacheKey::of(&a), CacheKey::of(&b));
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CacheKey(pub u64);
+
+impl CacheKey {
+    /// Computes the key of `config`
+    #[must_use]
+    pub fn of(config: &TaskConfig) -> Self {
+        let mut hasher = Fnv1a(FNV_OFFSET_BASIS);
+        hasher.write_str(&config.command);
+
+        let args = config.args.as_deref().unwrap_or_default();
+        hasher.write_len(args.len());
+        for arg in args {
+            hasher.write_str(arg);
+        }
+
+        let mut env: Vec<_> = config.env.iter().flatten().collect();
+        env.sort_unstable();
+        hasher.write_len(env.len());
+        for (key, value) in env {
+            hasher.write_str(key);
+            hasher.write_str(value);
+        }
+
+        match &config.working_dir {
+            Some(dir) => {
+                hasher.write(&[1]);
+                hasher.write_str(dir);
+            }
+            None => hasher.write(&[0]),
+        }
+        // Only hashed when set, keeping the keys of earlier configurations
+        if let Some(candidates) = &config.working_dir_candidates {
+            hasher.write_len(candidates.len());
+            for dir in candidates {
+                hasher.write_str(dir);
+            }
+        }
+        // Later settings are tagged, so one cannot hash like another
+        if config.clear_env.unwrap_or_default() {
+            hasher.write_str("clear_env");
+            let mut names: Vec<_> = config.inherit_env_vars.iter().flatten().collect();
+            names.sort_unstable();
+            names.dedup();
+            hasher.write_len(names.len());
+            for name in names {
+                hasher.write_str(name);
+            }
+        }
+        if let Some(temp) = &config.temp_working_dir {
+            hasher.write_str("temp_working_dir");
+            hasher.write_str(temp.prefix.as_deref().unwrap_or(DEFAULT_TEMP_DIR_PREFIX));
+        }
+        Self(hasher.0)
+    }
+}
+
+impl fmt::Display for CacheKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+/// FNV-1a hasher, kept local as `std`'s hashers are not stable across releases
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_len(&mut self, len: usize) {
+        self.write(&(len as u64).to_le_bytes());
+    }
+
+    fn write_str(&mut self, value: &str) {
+        self.write_len(value.len());
+        self.write(value.as_bytes());
+    }
+}
+
+/// Result of a successful run, stored in a [`CacheLayer`]
+///
+/// Only runs that exited on their own with exit code 0 are stored, so a hit
+/// replays `TaskEvent::Stopped` with exit code 0 and `TaskEventStopReason::Finished`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedResult {
+    /// Time from spawning the process until it stopped, in milliseconds
+    pub duration_ms: u64,
+    /// Output lines replayed as `TaskEvent::Output` on a hit
+    ///
+    /// Empty unless output replay was requested when the result was stored.
+    pub output: Vec<CapturedLine>,
+    /// Digest of stdout replayed in `TaskEvent::Stopped`, see `TaskConfig::hash_output`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stdout_sha256: Option<String>,
+    /// Digest of stderr replayed in `TaskEvent::Stopped`, see `TaskConfig::hash_output`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stderr_sha256: Option<String>,
+}
+
+/// Storage for task results, consulted by `TaskSpawner::start_cached`
+///
+/// Implementations are shared between tasks, possibly running concurrently,
+/// and are called from async code, so both methods should return quickly.
+pub trait CacheLayer: Send + Sync {
+    /// Returns the result stored for `key`, if any
+    fn get(&self, key: CacheKey) -> Option<CachedResult>;
+
+    /// Stores the result of a successful run, replacing any previous one
+    fn put(&self, key: CacheKey, result: CachedResult);
+}
+
+/// In-memory [`CacheLayer`], living as long as the value itself
+///
+/// # Examples
+/// ```rust,no_run
+/// use std::sync::Arc;
+///
+/// use tcrm_task::tasks::{
+///     async_tokio::{cache::MemoryCache, spawner::TaskSpawner},
+///     config::TaskConfig,
+/// };
+/// use tokio::sync::mpsc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let cache = Arc::new(MemoryCache::new());
+///     let config = TaskConfig::new("protoc").args(["--rust_out=gen", "api.proto"]);
+///
+///     let (tx, _rx) = mpsc::channel(100);
+///     let mut spawner = TaskSpawner::new("codegen".to_string(), config);
+///     match spawner.start_cached(tx, cache.clone(), false).await? {
+///         Some(pid) => println!("Running as process {pid}"),
+///         None => println!("Replayed from cache"),
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct MemoryCache {
+    results: Mutex<HashMap<CacheKey, CachedResult>>,
+}
+
+impl MemoryCache {
+    /// Create an empty cache
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of stored results
+    pub fn len(&self) -> usize {
+        self.results
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Whether no result is stored
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all stored results
+    pub fn clear(&self) {
+        self.results
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+}
+
+impl CacheLayer for MemoryCache {
+    fn get(&self, key: CacheKey) -> Option<CachedResult> {
+        self.results
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&key)
+            .cloned()
+    }
+
+    fn put(&self, key: CacheKey, result: CachedResult) {
+        self.results
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(key, result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Key of the configuration in `key_is_stable`
+    const KEY: u64 = 0x1bfc_97da_be46_1b7c;
+
+    #[test]
+    fn key_is_stable() {
+        let config = TaskConfig::new("cargo")
+            .args(["build", "--release"])
+            .working_dir("/src")
+            .env([("RUSTFLAGS", "-Dwarnings")]);
+
+        // Persistent caches rely on keys never changing
+        assert_eq!(CacheKey::of(&config), CacheKey(KEY));
+        assert_eq!(CacheKey(KEY).to_string(), "1bfc97dabe461b7c");
+    }
+
+    #[test]
+    fn key_covers_command_args_env_and_working_dir() {
+        let base = TaskConfig::new("cargo").args(["build"]);
+        let key = CacheKey::of(&base);
+
+        let changed = [
+            TaskConfig::new("cargo2").args(["build"]),
+            TaskConfig::new("cargo").args(["build", "--release"]),
+            TaskConfig::new("cargo").args(["buil", "d"]),
+            base.clone().env([("A", "1")]),
+            base.clone().working_dir("/src"),
+            base.clone().temp_working_dir(true),
+        ];
+        for config in changed {
+            assert_ne!(CacheKey::of(&config), key, "{config:?}");
+        }
+        // Settings that do not change what runs keep the key
+        assert_eq!(CacheKey::of(&base.clone().timeout_ms(1000)), key);
+        assert_eq!(CacheKey::of(&base.env(Vec::<(String, String)>::new())), key);
+    }
+
+    #[test]
+    fn key_covers_cleared_environment() {
+        let base = TaskConfig::new("cargo").args(["build"]);
+        let cleared = base.clone().clear_env(true);
+        let keys = [
+            CacheKey::of(&base),
+            CacheKey::of(&cleared),
+            CacheKey::of(&cleared.clone().inherit_env_vars(["PATH"])),
+            CacheKey::of(&cleared.clone().inherit_env_vars(["PATH", "HOME"])),
+        ];
+        for (i, key) in keys.iter().enumerate() {
+            assert!(!keys[..i].contains(key), "{i}");
+        }
+
+        // The allowlist is a set of names
+        assert_eq!(
+            CacheKey::of(&cleared.clone().inherit_env_vars(["HOME", "PATH", "HOME"])),
+            keys[3]
+        );
+        assert_eq!(CacheKey::of(&base.clone().clear_env(false)), keys[0]);
+    }
+
+    #[test]
+    fn key_covers_temp_working_dir_prefix() {
+        let base = TaskConfig::new("make").temp_working_dir(true);
+        assert_eq!(
+            CacheKey::of(
+                &base
+                    .clone()
+                    .temp_working_dir_prefix(DEFAULT_TEMP_DIR_PREFIX)
+            ),
+            CacheKey::of(&base)
+        );
+        assert_ne!(
+            CacheKey::of(&base.clone().temp_working_dir_prefix("build-")),
+            CacheKey::of(&base)
+        );
+        assert_eq!(
+            CacheKey::of(&base.clone().keep_temp_dir_on_failure(true)),
+            CacheKey::of(&base)
+        );
+    }
+
+    #[test]
+    fn memory_cache_replaces_results() {
+        let cache = MemoryCache::new();
+        let key = CacheKey(1);
+        assert!(cache.is_empty());
+        assert_eq!(cache.get(key), None);
+
+        for duration_ms in [10, 20] {
+            cache.put(
+                key,
+                CachedResult {
+                    duration_ms,
+                    output: Vec::new(),
+                    stdout_sha256: None,
+                    stderr_sha256: None,
+                },
+            );
+        }
+
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get(key).map(|result| result.duration_ms), Some(20));
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
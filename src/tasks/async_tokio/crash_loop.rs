@@ -0,0 +1,130 @@
+//! Crash-loop detection for restarted tasks.
+//!
+//! A [`CrashLoopDetector`] is fed how each attempt of a task stopped and
+//! reports a crash loop once the task died shortly after starting for a
+//! number of consecutive attempts, so a broken binary is not restarted
+//! forever. The attempts are kept as [`AttemptRecord`]s for reporting.
+//!
+//! The detector only tracks attempts; whoever restarts the task decides to
+//! stop once [`CrashLoopDetector::record`] returns `true`.
+
+/// Most attempts kept in the history of a [`CrashLoopDetector`]
+const MAX_HISTORY: usize = 64;
+
+/// Thresholds of a [`CrashLoopDetector`]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrashLoopPolicy {
+    /// An attempt that stops within this many milliseconds of starting counts
+    /// as a crash, unless it exited with code 0 (default: 1000)
+    pub min_uptime_ms: u64,
+    /// Consecutive crashes after which the task is in a crash loop (default: 5)
+    ///
+    /// 0 is treated as 1.
+    pub max_consecutive: u32,
+}
+
+impl Default for CrashLoopPolicy {
+    fn default() -> Self {
+        CrashLoopPolicy {
+            min_uptime_ms: 1000,
+            max_consecutive: 5,
+        }
+    }
+}
+
+/// How one attempt of a task stopped
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttemptRecord {
+    /// Exit code, as in `TaskEvent::Stopped`
+    pub exit_code: Option<i32>,
+    /// Time from spawning the process until it stopped, in milliseconds
+    pub duration_ms: u64,
+}
+
+/// Tracks consecutive short-lived attempts of a task
+///
+/// # Examples
+///
+/// ```rust
+/// use tcrm_task::tasks::async_tokio::crash_loop::{CrashLoopDetector, CrashLoopPolicy};
+///
+/// let mut detector = CrashLoopDetector::new(CrashLoopPolicy {
+///     min_uptime_ms: 500,
+///     max_consecutive: 3,
+/// });
+/// assert!(!detector.record(Some(1), 20));
+/// assert!(!detector.record(Some(1), 15));
+/// assert!(detector.record(Some(1), 18));
+/// assert!(detector.is_crash_loop());
+/// assert_eq!(detector.history().len(), 3);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CrashLoopDetector {
+    policy: CrashLoopPolicy,
+    history: Vec<AttemptRecord>,
+    consecutive: u32,
+}
+
+impl CrashLoopDetector {
+    /// Create a detector with the given thresholds
+    #[must_use]
+    pub fn new(policy: CrashLoopPolicy) -> Self {
+        CrashLoopDetector {
+            policy,
+            history: Vec::new(),
+            consecutive: 0,
+        }
+    }
+
+    /// Record how an attempt stopped
+    ///
+    /// An attempt that ran for at least `min_uptime_ms`, or exited with code
+    /// 0, resets the count of consecutive crashes.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the task is in a crash loop and should not be restarted.
+    pub fn record(&mut self, exit_code: Option<i32>, duration_ms: u64) -> bool {
+        if self.history.len() == MAX_HISTORY {
+            self.history.remove(0);
+        }
+        self.history.push(AttemptRecord {
+            exit_code,
+            duration_ms,
+        });
+        if duration_ms < self.policy.min_uptime_ms && exit_code != Some(0) {
+            self.consecutive = self.consecutive.saturating_add(1);
+        } else {
+            self.consecutive = 0;
+        }
+        self.is_crash_loop()
+    }
+
+    /// Whether the last `max_consecutive` attempts all crashed
+    #[must_use]
+    pub fn is_crash_loop(&self) -> bool {
+        self.consecutive >= self.policy.max_consecutive.max(1)
+    }
+
+    /// Number of crashes since the last attempt that did not crash
+    #[must_use]
+    pub fn consecutive_crashes(&self) -> u32 {
+        self.consecutive
+    }
+
+    /// The recorded attempts, oldest first
+    ///
+    /// Keeps the last 64 attempts.
+    #[must_use]
+    pub fn history(&self) -> &[AttemptRecord] {
+        &self.history
+    }
+
+    /// The thresholds of this detector
+    #[must_use]
+    pub fn policy(&self) -> CrashLoopPolicy {
+        self.policy
+    }
+}
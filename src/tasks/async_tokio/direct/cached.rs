@@ -0,0 +1,182 @@
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use tokio::runtime::Handle;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+use crate::tasks::async_tokio::cache::{CacheKey, CacheLayer, CachedResult};
+use crate::tasks::async_tokio::spawner::TaskSpawner;
+use crate::tasks::async_tokio::startup_output::CapturedLine;
+use crate::tasks::error::TaskError;
+use crate::tasks::event::{TaskEvent, TaskEventStopReason};
+use crate::tasks::state::TaskState;
+
+impl TaskSpawner {
+    /// Start the task, or replay its cached result if its configuration already ran successfully
+    ///
+    /// Computes the [`CacheKey`] of the configuration and looks it up in `cache`
+    /// before spawning anything:
+    ///
+    /// - **Hit**: no process is spawned. `TaskEvent::Started` is sent with
+    ///   process ID 0, then the cached output lines as `TaskEvent::Output`, then
+    ///   `TaskEvent::Stopped` with `from_cache: true`, exit code 0 and the
+    ///   duration of the cached run. The task is `Finished` when this returns.
+    /// - **Miss**: the task runs as with `start_direct`. Its events are forwarded
+    ///   to `event_tx` through a separate tokio task, which stores the result in
+    ///   `cache` before forwarding `TaskEvent::Stopped` if the process exited on
+    ///   its own with exit code 0.
+    ///
+    /// An invalid configuration is never looked up, so it fails as with `start_direct`.
+    ///
+    /// # Arguments
+    ///
+    /// * `event_tx` - Channel sender for receiving task events in real-time
+    /// * `cache` - Where results are looked up and stored
+    /// * `replay_output` - Whether output lines are stored on a miss, to be
+    ///   replayed on later hits. The lines of the run are kept in memory until it stops.
+    ///   Chunks of `OutputMode::Bytes` are not stored.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Some(process_id))` - The task was started, as with `start_direct`
+    /// - `Ok(None)` - The result was replayed from the cache; no process exists
+    /// - `Err(TaskError)` - The error `start_direct` would return
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TaskError`] if the task has already been started, or for
+    /// any error of [`start_direct`](Self::start_direct) on a miss.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, event_tx, cache), fields(task_name = %self.task_name)))]
+    pub async fn start_cached(
+        &mut self,
+        event_tx: mpsc::Sender<TaskEvent>,
+        cache: Arc<dyn CacheLayer>,
+        replay_output: bool,
+    ) -> Result<Option<u32>, TaskError> {
+        let key = CacheKey::of(&self.config);
+        if self.config.validate().is_ok()
+            && let Some(result) = cache.get(key)
+        {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(%key, "Replaying cached result");
+            self.replay_cached(result, event_tx).await?;
+            return Ok(None);
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(%key, "No cached result, starting task");
+        let (run_tx, run_rx) = mpsc::channel(event_tx.max_capacity());
+        {
+            let _runtime = self.runtime.as_ref().map(Handle::enter);
+            self.watchers.spawn(
+                "cache",
+                forward_and_store(run_rx, event_tx, cache, key, replay_output),
+            );
+        }
+        self.start_direct(run_tx).await.map(Some)
+    }
+
+    /// Sends the events of a cached run and marks the task finished
+    async fn replay_cached(
+        &mut self,
+        result: CachedResult,
+        event_tx: mpsc::Sender<TaskEvent>,
+    ) -> Result<(), TaskError> {
+        self.begin_start().await?;
+        self.running_at = Some(Instant::now());
+        self.update_state(TaskState::Running).await;
+
+        let mut events = vec![TaskEvent::Started {
+            task_name: self.task_name.clone(),
+            process_id: 0,
+            temp_dir: None,
+            working_dir: None,
+        }];
+        let replayed_at = SystemTime::now();
+        events.extend(
+            (0..)
+                .zip(result.output)
+                .map(|(seq, CapturedLine { src, line })| TaskEvent::Output {
+                    task_name: self.task_name.clone(),
+                    line,
+                    src,
+                    seq,
+                    // Not cached, a truncated line is replayed as it was sent
+                    truncated: false,
+                    // Neither is the time a line was read
+                    timestamp: replayed_at,
+                }),
+        );
+        let exit_meaning = self.config.exit_code_meaning(0).map(str::to_string);
+        self.exit_meaning.write().await.clone_from(&exit_meaning);
+        events.push(TaskEvent::Stopped {
+            task_name: self.task_name.clone(),
+            exit_code: Some(0),
+            signal: None,
+            exit_meaning,
+            reason: TaskEventStopReason::Finished,
+            was_ready: false,
+            duration_ms: result.duration_ms,
+            expected_duration_ms: self.config.expected_duration_ms,
+            from_cache: true,
+            stdout_sha256: result.stdout_sha256,
+            stderr_sha256: result.stderr_sha256,
+        });
+        for event in events {
+            if event_tx.send(event).await.is_err() {
+                #[cfg(feature = "tracing")]
+                tracing::warn!("Event channel closed while replaying cached events");
+                break;
+            }
+        }
+
+        self.update_state(TaskState::Finished).await;
+        *self.finished_at.write().await = Some(Instant::now());
+        Ok(())
+    }
+}
+
+/// Forwards the events of a run on a cache miss, storing its result if it succeeded
+///
+/// The result is stored before `TaskEvent::Stopped` is forwarded, so a run
+/// started after receiving it hits the cache. Keeps draining `run_rx` after
+/// `event_tx` is closed, so the result is stored either way.
+async fn forward_and_store(
+    mut run_rx: mpsc::Receiver<TaskEvent>,
+    event_tx: mpsc::Sender<TaskEvent>,
+    cache: Arc<dyn CacheLayer>,
+    key: CacheKey,
+    replay_output: bool,
+) {
+    let mut output = Vec::new();
+    while let Some(event) = run_rx.recv().await {
+        match &event {
+            TaskEvent::Output { line, src, .. } if replay_output => output.push(CapturedLine {
+                src: src.clone(),
+                line: line.clone(),
+            }),
+            TaskEvent::Stopped {
+                exit_code: Some(0),
+                reason: TaskEventStopReason::Finished,
+                duration_ms,
+                stdout_sha256,
+                stderr_sha256,
+                ..
+            } => cache.put(
+                key,
+                CachedResult {
+                    duration_ms: *duration_ms,
+                    output: std::mem::take(&mut output),
+                    stdout_sha256: stdout_sha256.clone(),
+                    stderr_sha256: stderr_sha256.clone(),
+                },
+            ),
+            _ => {}
+        }
+        if event_tx.send(event).await.is_err() {
+            #[cfg(feature = "tracing")]
+            tracing::debug!("Event channel closed while forwarding events of a cached task");
+        }
+    }
+}
@@ -1,39 +1,265 @@
-use std::process::Stdio;
+use std::{collections::HashMap, process::Stdio};
 
 use tokio::process::Command;
 
-use crate::tasks::config::TaskConfig;
+#[cfg(unix)]
+use crate::tasks::async_tokio::pre_exec::PreExecSteps;
+#[cfg(any(unix, windows))]
+use crate::tasks::config::TaskPriority;
+use crate::tasks::config::{StreamSource, TaskConfig};
+#[cfg(unix)]
+use crate::tasks::error::TaskError;
 
 /// Configures a `tokio::process::Command` based on the provided `TaskConfig`.
 ///
-/// Sets arguments, working directory, environment, and stdio options.
+/// Sets arguments, working directory, environment and stdio options.
+///
+/// Only streams that will be watched are piped; uncaptured output and unused
+/// stdin go to the null device. With `stdio_inherit`, all three streams are
+/// inherited from the current process instead.
 ///
 /// # Arguments
 ///
 /// * `cmd` - The command to configure.
 /// * `config` - The task configuration to apply.
-pub(crate) fn setup_command(cmd: &mut Command, config: &TaskConfig) {
+/// * `pipe_stdin` - Whether a stdin watcher will feed the process.
+pub(crate) fn setup_command(cmd: &mut Command, config: &TaskConfig, pipe_stdin: bool) {
     // Setup additional arguments
     if let Some(args) = &config.args {
         cmd.args(args);
     }
 
     // Setup working directory with validation
-    if let Some(dir) = &config.working_dir {
+    if let Ok(Some(dir)) = config.resolve_working_dir() {
         cmd.current_dir(dir);
     }
 
-    // Setup environment variables
+    // Setup environment variables, on top of the allowed ones if the environment is cleared
+    if config.clear_env.unwrap_or_default() {
+        cmd.env_clear();
+        for name in config.inherit_env_vars.iter().flatten() {
+            if let Some(value) = std::env::var_os(name) {
+                cmd.env(name, value);
+            }
+        }
+    }
     if let Some(envs) = &config.env {
         cmd.envs(envs);
     }
 
     // Setup stdio
-    cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).stdin(
-        if config.enable_stdin.unwrap_or(false) {
-            Stdio::piped()
-        } else {
-            Stdio::null()
-        },
-    );
+    if config.stdio_inherit.unwrap_or_default() {
+        cmd.stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .stdin(Stdio::inherit());
+        return;
+    }
+    let stdio = |piped: bool| if piped { Stdio::piped() } else { Stdio::null() };
+    cmd.stdout(stdio(config.is_stream_captured(&StreamSource::Stdout)))
+        .stderr(stdio(config.is_stream_captured(&StreamSource::Stderr)))
+        .stdin(stdio(pipe_stdin));
+}
+
+/// Makes the kernel kill a process spawned from `cmd` when its parent dies
+///
+/// Registered as the `parent_death_signal` step in `steps`, next to the
+/// others such as the `setsid` of the process group. If the parent died
+/// before the signal was set, the child is no longer ours and spawning fails
+/// instead.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn set_parent_death_signal(cmd: &mut Command, steps: &mut PreExecSteps) {
+    let parent = std::process::id();
+    unsafe {
+        steps.register(cmd, "parent_death_signal", move || {
+            if libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL) == -1 {
+                return Err(std::io::Error::last_os_error());
+            }
+            // Reparented to init or a subreaper: the parent is already gone
+            if u32::try_from(libc::getppid()) != Ok(parent) {
+                return Err(std::io::Error::from_raw_os_error(libc::ESRCH));
+            }
+            Ok(())
+        });
+    }
+}
+
+/// Sets the nice value of a process spawned from `cmd` to that of `priority`
+///
+/// Registered as the `priority` step in `steps`, so a nice value the process
+/// may not take, such as a negative one without privileges, fails the spawn.
+/// See [`priority_error`] for how that failure is reported.
+#[cfg(unix)]
+pub(crate) fn set_priority(cmd: &mut Command, steps: &mut PreExecSteps, priority: TaskPriority) {
+    let nice = priority.nice_value();
+    unsafe {
+        steps.register(cmd, "priority", move || {
+            if libc::setpriority(libc::PRIO_PROCESS, 0, nice) == -1 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+}
+
+/// Reports a failed `priority` step as `TaskError::Handle`
+///
+/// A priority that cannot be applied is a failure to set up the process, as
+/// on Windows, rather than one of the steps that prepare it. Other errors are
+/// returned unchanged.
+#[cfg(unix)]
+pub(crate) fn priority_error(error: TaskError) -> TaskError {
+    match error {
+        TaskError::PreExec { step, error } if step == "priority" => {
+            TaskError::Handle(format!("Failed to set priority: {error}"))
+        }
+        error => error,
+    }
+}
+
+/// Applies the priority and CPU affinity of `config` to a spawned process
+///
+/// Returns a message naming the setting that could not be applied.
+#[cfg(windows)]
+pub(crate) fn apply_scheduling(
+    child: &tokio::process::Child,
+    config: &TaskConfig,
+) -> Result<(), String> {
+    if let Some(priority) = config.priority {
+        set_priority_class(child, priority)
+            .map_err(|e| format!("Failed to set priority class: {e}"))?;
+    }
+    if let Some(cores) = &config.cpu_affinity {
+        set_affinity_mask(child, cores).map_err(|e| format!("Failed to set CPU affinity: {e}"))?;
+    }
+    Ok(())
+}
+
+/// Sets the priority class of a spawned process to that of `priority`
+#[cfg(windows)]
+fn set_priority_class(
+    child: &tokio::process::Child,
+    priority: TaskPriority,
+) -> std::io::Result<()> {
+    use windows::Win32::Foundation::HANDLE;
+    use windows::Win32::System::Threading::{
+        ABOVE_NORMAL_PRIORITY_CLASS, BELOW_NORMAL_PRIORITY_CLASS, IDLE_PRIORITY_CLASS,
+        NORMAL_PRIORITY_CLASS, SetPriorityClass,
+    };
+
+    let Some(handle) = child.raw_handle() else {
+        return Err(std::io::Error::other("Process has already exited"));
+    };
+    let class = match priority {
+        TaskPriority::Low => IDLE_PRIORITY_CLASS,
+        TaskPriority::BelowNormal => BELOW_NORMAL_PRIORITY_CLASS,
+        TaskPriority::Normal => NORMAL_PRIORITY_CLASS,
+        TaskPriority::High => ABOVE_NORMAL_PRIORITY_CLASS,
+    };
+    unsafe { SetPriorityClass(HANDLE(handle), class) }.map_err(std::io::Error::from)
+}
+
+/// Pins a process spawned from `cmd` to `cores`
+///
+/// Registered as the `cpu_affinity` step in `steps`. The CPU set is built
+/// here, so the step only makes the system call.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn set_cpu_affinity(cmd: &mut Command, steps: &mut PreExecSteps, cores: &[usize]) {
+    // SAFETY: `cpu_set_t` is a plain bit mask, valid when zeroed
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    for &core in cores {
+        // Validation keeps cores below the available parallelism
+        unsafe { libc::CPU_SET(core, &mut set) };
+    }
+    unsafe {
+        steps.register(cmd, "cpu_affinity", move || {
+            if libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) == -1 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+}
+
+/// Pins a spawned process to `cores`
+///
+/// Only the first 64 cores (32 on 32-bit Windows) of the processor group can
+/// be named in an affinity mask; higher indices are an error.
+#[cfg(windows)]
+fn set_affinity_mask(child: &tokio::process::Child, cores: &[usize]) -> std::io::Result<()> {
+    use windows::Win32::Foundation::HANDLE;
+    use windows::Win32::System::Threading::SetProcessAffinityMask;
+
+    let mut mask = 0_usize;
+    for &core in cores {
+        let bit = u32::try_from(core)
+            .ok()
+            .and_then(|core| 1_usize.checked_shl(core))
+            .ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("Core {core} cannot be set in an affinity mask"),
+                )
+            })?;
+        mask |= bit;
+    }
+    let Some(handle) = child.raw_handle() else {
+        return Err(std::io::Error::other("Process has already exited"));
+    };
+    unsafe { SetProcessAffinityMask(HANDLE(handle), mask) }.map_err(std::io::Error::from)
+}
+
+/// Returns the environment a process spawned from `cmd` receives
+///
+/// Starts from the environment inherited from the current process, or an empty
+/// one if `clear_env` was applied to `cmd`, and applies the variables set or
+/// removed on `cmd`. Names and values that are not valid Unicode are converted
+/// lossily. On Windows, where variable names are case-insensitive, a variable
+/// set on `cmd` replaces an inherited one that differs only in case.
+pub(crate) fn env_snapshot(cmd: &Command, clear_env: bool) -> HashMap<String, String> {
+    let mut env: HashMap<String, String> = if clear_env {
+        HashMap::new()
+    } else {
+        std::env::vars_os()
+            .map(|(key, value)| {
+                (
+                    key.to_string_lossy().into_owned(),
+                    value.to_string_lossy().into_owned(),
+                )
+            })
+            .collect()
+    };
+    for (key, value) in cmd.as_std().get_envs() {
+        let key = key.to_string_lossy().into_owned();
+        if cfg!(windows) {
+            env.retain(|existing, _| !existing.eq_ignore_ascii_case(&key));
+        }
+        match value {
+            Some(value) => env.insert(key, value.to_string_lossy().into_owned()),
+            None => env.remove(&key),
+        };
+    }
+    env
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_priority_step_is_a_handle_error() {
+        let error = TaskError::PreExec {
+            step: "priority".to_string(),
+            error: "EACCES".to_string(),
+        };
+        assert_eq!(
+            priority_error(error),
+            TaskError::Handle("Failed to set priority: EACCES".to_string())
+        );
+
+        let other = TaskError::PreExec {
+            step: "setsid".to_string(),
+            error: "EPERM".to_string(),
+        };
+        assert_eq!(priority_error(other.clone()), other);
+    }
 }
@@ -0,0 +1,228 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use tokio::process::Command;
+
+use crate::tasks::async_tokio::direct::command::env_snapshot;
+use crate::tasks::async_tokio::direct::plan::{path_var, program_candidates};
+use crate::tasks::error::SpawnDiagnosis;
+
+/// Number of bytes read from the start of the file to inspect its header
+///
+/// Covers a `#!` line and the ELF header. The PE header of common Windows
+/// executables starts within the first few hundred bytes.
+const HEADER_LEN: usize = 1024;
+
+/// Diagnoses why `cmd` failed to spawn, from the file its command resolves to
+///
+/// Resolves the command the same way as [`TaskSpawner::plan`](crate::tasks::async_tokio::spawner::TaskSpawner::plan),
+/// using the environment and working directory of `cmd`.
+pub(crate) fn diagnose_spawn(
+    cmd: &Command,
+    working_dir: Option<&str>,
+    clear_env: bool,
+) -> Option<SpawnDiagnosis> {
+    let working_dir = working_dir
+        .map_or_else(std::env::current_dir, |dir| Ok(PathBuf::from(dir)))
+        .and_then(std::fs::canonicalize)
+        .ok()?;
+    let env = env_snapshot(cmd, clear_env);
+    let command = cmd.as_std().get_program().to_string_lossy();
+    diagnose(&command, &working_dir, path_var(&env))
+}
+
+/// Diagnoses why `command` cannot be executed
+///
+/// Inspects the first existing file `command` resolves to. Returns `None` if
+/// no such file exists or nothing wrong with it was found.
+pub(crate) fn diagnose(
+    command: &str,
+    working_dir: &Path,
+    path_var: Option<&str>,
+) -> Option<SpawnDiagnosis> {
+    let path = program_candidates(command, working_dir, path_var)
+        .into_iter()
+        .find(|candidate| candidate.exists())?;
+    diagnose_file(&path)
+}
+
+/// Diagnoses why the file at `path` cannot be executed
+pub(crate) fn diagnose_file(path: &Path) -> Option<SpawnDiagnosis> {
+    let display = path.to_string_lossy().into_owned();
+    let metadata = path.metadata().ok()?;
+    if metadata.is_dir() {
+        return Some(SpawnDiagnosis::IsDirectory { path: display });
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if metadata.permissions().mode() & 0o111 == 0 {
+            return Some(SpawnDiagnosis::NotExecutable { path: display });
+        }
+    }
+
+    let header = read_header(path)?;
+    #[cfg(unix)]
+    if let Some(interpreter) = shebang_interpreter(&header)
+        && !Path::new(&interpreter).exists()
+    {
+        return Some(SpawnDiagnosis::MissingInterpreter {
+            path: display,
+            interpreter,
+        });
+    }
+    if let Some(architecture) = elf_architecture(&header).or_else(|| pe_architecture(&header)) {
+        if architecture != std::env::consts::ARCH {
+            return Some(SpawnDiagnosis::WrongArchitecture {
+                path: display,
+                architecture: architecture.to_string(),
+            });
+        }
+        return None;
+    }
+    #[cfg(windows)]
+    if !header.starts_with(b"MZ") && !is_batch_script(path) {
+        return Some(SpawnDiagnosis::NotExecutable { path: display });
+    }
+    None
+}
+
+/// Reads up to `HEADER_LEN` bytes from the start of the file
+fn read_header(path: &Path) -> Option<Vec<u8>> {
+    let file = std::fs::File::open(path).ok()?;
+    let mut header = Vec::with_capacity(HEADER_LEN);
+    file.take(HEADER_LEN as u64).read_to_end(&mut header).ok()?;
+    Some(header)
+}
+
+/// Returns the interpreter named on the `#!` line of a script
+#[cfg(unix)]
+fn shebang_interpreter(header: &[u8]) -> Option<String> {
+    let line = header.strip_prefix(b"#!")?;
+    let line = line.split(|&b| b == b'\n').next()?;
+    let line = String::from_utf8_lossy(line);
+    line.split_whitespace().next().map(str::to_owned)
+}
+
+/// Returns the architecture of an ELF executable, named like `std::env::consts::ARCH`
+fn elf_architecture(header: &[u8]) -> Option<&'static str> {
+    if header.len() < 20 || !header.starts_with(b"\x7fELF") {
+        return None;
+    }
+    let is_64_bit = header[4] == 2;
+    let machine = match header[5] {
+        1 => u16::from_le_bytes([header[18], header[19]]),
+        2 => u16::from_be_bytes([header[18], header[19]]),
+        _ => return None,
+    };
+    Some(match machine {
+        0x03 => "x86",
+        0x08 if is_64_bit => "mips64",
+        0x08 => "mips",
+        0x14 => "powerpc",
+        0x15 => "powerpc64",
+        0x16 => "s390x",
+        0x28 => "arm",
+        0x3E => "x86_64",
+        0xB7 => "aarch64",
+        0xF3 if is_64_bit => "riscv64",
+        0xF3 => "riscv32",
+        0x102 => "loongarch64",
+        _ => return None,
+    })
+}
+
+/// Returns the architecture of a PE executable, named like `std::env::consts::ARCH`
+fn pe_architecture(header: &[u8]) -> Option<&'static str> {
+    if !header.starts_with(b"MZ") {
+        return None;
+    }
+    let offset = u32::from_le_bytes(header.get(0x3C..0x40)?.try_into().ok()?) as usize;
+    let pe = header.get(offset..offset.checked_add(6)?)?;
+    if !pe.starts_with(b"PE\0\0") {
+        return None;
+    }
+    Some(match u16::from_le_bytes([pe[4], pe[5]]) {
+        0x014C => "x86",
+        0x01C0 | 0x01C4 => "arm",
+        0x8664 => "x86_64",
+        0xAA64 => "aarch64",
+        _ => return None,
+    })
+}
+
+/// Whether `path` is a script `cmd.exe` runs when it is spawned
+#[cfg(windows)]
+fn is_batch_script(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| {
+        extension.eq_ignore_ascii_case("bat") || extension.eq_ignore_ascii_case("cmd")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds the start of an ELF header for `machine`
+    fn elf_header(class: u8, data: u8, machine: u16) -> Vec<u8> {
+        let mut header = b"\x7fELF".to_vec();
+        header.extend([class, data, 1]);
+        header.resize(18, 0);
+        if data == 2 {
+            header.extend(machine.to_be_bytes());
+        } else {
+            header.extend(machine.to_le_bytes());
+        }
+        header.resize(64, 0);
+        header
+    }
+
+    /// Builds the start of a PE file for `machine`
+    fn pe_header(machine: u16) -> Vec<u8> {
+        let mut header = b"MZ".to_vec();
+        header.resize(0x3C, 0);
+        header.extend(0x80_u32.to_le_bytes());
+        header.resize(0x80, 0);
+        header.extend(b"PE\0\0");
+        header.extend(machine.to_le_bytes());
+        header
+    }
+
+    #[test]
+    fn elf_machine_is_mapped_to_arch() {
+        assert_eq!(elf_architecture(&elf_header(2, 1, 0x3E)), Some("x86_64"));
+        assert_eq!(elf_architecture(&elf_header(2, 1, 0xB7)), Some("aarch64"));
+        assert_eq!(elf_architecture(&elf_header(1, 1, 0x03)), Some("x86"));
+        assert_eq!(elf_architecture(&elf_header(2, 2, 0x16)), Some("s390x"));
+        assert_eq!(elf_architecture(&elf_header(2, 1, 0xF3)), Some("riscv64"));
+        assert_eq!(elf_architecture(&elf_header(2, 1, 0xFFFF)), None);
+        assert_eq!(elf_architecture(b"\x7fELF"), None);
+        assert_eq!(elf_architecture(b"#!/bin/sh\n"), None);
+    }
+
+    #[test]
+    fn pe_machine_is_mapped_to_arch() {
+        assert_eq!(pe_architecture(&pe_header(0x8664)), Some("x86_64"));
+        assert_eq!(pe_architecture(&pe_header(0xAA64)), Some("aarch64"));
+        assert_eq!(pe_architecture(&pe_header(0x014C)), Some("x86"));
+        assert_eq!(pe_architecture(&pe_header(0x1234)), None);
+        // PE header offset past the end of the header
+        assert_eq!(pe_architecture(&pe_header(0x8664)[..0x82]), None);
+        assert_eq!(pe_architecture(b"MZ"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn shebang_interpreter_is_first_word() {
+        assert_eq!(
+            shebang_interpreter(b"#!/usr/bin/env python3\nprint()"),
+            Some("/usr/bin/env".to_string())
+        );
+        assert_eq!(
+            shebang_interpreter(b"#! /bin/sh -e\n"),
+            Some("/bin/sh".to_string())
+        );
+        assert_eq!(shebang_interpreter(b"echo hi\n"), None);
+        assert_eq!(shebang_interpreter(b"#!\n"), None);
+    }
+}
@@ -1,2 +1,5 @@
+#[cfg(unix)]
+mod pooled;
 mod setup_command;
 mod start;
+mod support;
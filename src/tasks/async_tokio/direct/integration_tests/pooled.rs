@@ -0,0 +1,233 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio::sync::mpsc;
+
+use crate::tasks::{
+    async_tokio::{
+        pooled::{ShellPool, ShellPoolConfig},
+        spawner::TaskSpawner,
+    },
+    config::{StreamSource, TaskConfig},
+    error::TaskError,
+    event::{TaskEvent, TaskEventStopReason},
+};
+
+/// What a run produced: output lines per stream and the exit code
+#[derive(Debug, PartialEq)]
+struct RunResult {
+    stdout: Vec<String>,
+    stderr: Vec<String>,
+    exit_code: Option<i32>,
+}
+
+/// Collects the events of a run, checking they start with `Started` and end with `Stopped`
+async fn collect(mut rx: mpsc::Receiver<TaskEvent>) -> (RunResult, u32, TaskEventStopReason) {
+    let mut events = vec![];
+    while let Some(event) = tokio::time::timeout(Duration::from_secs(10), rx.recv())
+        .await
+        .expect("Run should finish")
+    {
+        events.push(event);
+    }
+
+    let Some(TaskEvent::Started { process_id, .. }) = events.first() else {
+        panic!("Run should start with Started: {events:?}");
+    };
+    let Some(TaskEvent::Stopped {
+        exit_code, reason, ..
+    }) = events.last()
+    else {
+        panic!("Run should end with Stopped: {events:?}");
+    };
+    let mut result = RunResult {
+        stdout: vec![],
+        stderr: vec![],
+        exit_code: *exit_code,
+    };
+    for event in &events {
+        if let TaskEvent::Output { line, src, .. } = event {
+            match src {
+                StreamSource::Stdout => result.stdout.push(line.clone()),
+                StreamSource::Stderr => result.stderr.push(line.clone()),
+            }
+        }
+    }
+    (result, *process_id, reason.clone())
+}
+
+async fn run_pooled(pool: &ShellPool, command: &str) -> (RunResult, u32, TaskEventStopReason) {
+    let (tx, rx) = mpsc::channel(100);
+    let exit_code = pool.run("pooled", command, tx).await.unwrap();
+    let collected = collect(rx).await;
+    assert_eq!(exit_code, collected.0.exit_code);
+    collected
+}
+
+async fn run_direct(command: &str) -> RunResult {
+    let config = TaskConfig::new("sh").args(["-c", command]);
+    let mut spawner = TaskSpawner::new("direct".to_string(), config);
+    let (tx, rx) = mpsc::channel(100);
+    spawner.start_direct(tx).await.unwrap();
+    collect(rx).await.0
+}
+
+#[tokio::test]
+async fn matches_direct_path() {
+    let pool = ShellPool::new(ShellPoolConfig::new().size(1))
+        .await
+        .unwrap();
+    let commands = [
+        "echo hello",
+        "printf 'a\\nb\\n\\nc\\n'",
+        "echo out; echo err >&2; echo out2",
+        "printf 'no newline'",
+        "printf 'no newline' >&2",
+        "exit 3",
+        "false",
+        "true",
+        "cat",
+        "echo 'single '\\''quoted'\\'' text'",
+        "echo \"double $((1 + 2))\"",
+        "for i in 1 2 3; do echo $i; done",
+        "echo first\necho second",
+        "sh -c 'exit 7'",
+        "echo 'héllo wörld'",
+    ];
+    for command in commands {
+        let (pooled, _, reason) = run_pooled(&pool, command).await;
+        assert_eq!(reason, TaskEventStopReason::Finished, "{command}");
+        assert_eq!(pooled, run_direct(command).await, "{command}");
+    }
+}
+
+#[tokio::test]
+async fn shells_are_reused() {
+    let pool = ShellPool::new(ShellPoolConfig::new().size(1))
+        .await
+        .unwrap();
+    let (_, first, _) = run_pooled(&pool, "true").await;
+    let (result, second, _) = run_pooled(&pool, "echo $$").await;
+
+    assert_ne!(first, 0);
+    assert_eq!(first, second);
+    assert_eq!(result.stdout, [first.to_string()]);
+    assert_eq!(pool.idle_count(), 1);
+}
+
+#[tokio::test]
+async fn state_changes_do_not_reach_later_commands() {
+    let pool = ShellPool::new(ShellPoolConfig::new().size(1).working_dir("/"))
+        .await
+        .unwrap();
+    run_pooled(&pool, "cd /tmp; export POOLED_VAR=1; exit 1").await;
+    let (result, _, _) = run_pooled(&pool, "pwd; echo ${POOLED_VAR:-unset}").await;
+    assert_eq!(result.stdout, ["/", "unset"]);
+}
+
+#[tokio::test]
+async fn syntax_errors_fail_the_command_only() {
+    let pool = ShellPool::new(ShellPoolConfig::new().size(1))
+        .await
+        .unwrap();
+    let (result, _, reason) = run_pooled(&pool, "echo \"unterminated").await;
+    assert_eq!(reason, TaskEventStopReason::Finished);
+    assert_eq!(result.exit_code, Some(2));
+    assert_eq!(result.stderr.len(), 1);
+
+    let (result, _, _) = run_pooled(&pool, "echo ok").await;
+    assert_eq!(result.stdout, ["ok"]);
+}
+
+#[tokio::test]
+async fn pool_env_and_working_dir_apply() {
+    let pool = ShellPool::new(
+        ShellPoolConfig::new()
+            .size(1)
+            .program("/bin/sh")
+            .working_dir("/tmp")
+            .env([("POOL_GREETING", "hello")]),
+    )
+    .await
+    .unwrap();
+
+    let (result, _, _) = run_pooled(&pool, "echo $POOL_GREETING; pwd").await;
+    assert_eq!(result.stdout, ["hello", "/tmp"]);
+
+    let (tx, mut rx) = mpsc::channel(100);
+    pool.run("pooled", "true", tx).await.unwrap();
+    let Some(TaskEvent::Started { working_dir, .. }) = rx.recv().await else {
+        panic!("Run should start with Started");
+    };
+    assert_eq!(working_dir.as_deref(), Some("/tmp"));
+}
+
+#[tokio::test]
+async fn dead_shell_is_replaced() {
+    let pool = ShellPool::new(ShellPoolConfig::new().size(1))
+        .await
+        .unwrap();
+    let (result, killed, reason) = run_pooled(&pool, "echo before; kill -9 $$").await;
+
+    assert_eq!(result.stdout, ["before"]);
+    assert_eq!(result.exit_code, None);
+    assert!(matches!(reason, TaskEventStopReason::Error(_)));
+    assert_eq!(pool.idle_count(), 0);
+
+    let (result, pid, _) = run_pooled(&pool, "echo after").await;
+    assert_ne!(pid, killed);
+    assert_eq!(result.stdout, ["after"]);
+    assert_eq!(pool.idle_count(), 1);
+}
+
+#[tokio::test]
+async fn busy_pool_spawns_extra_shells() {
+    let pool = Arc::new(
+        ShellPool::new(ShellPoolConfig::new().size(1))
+            .await
+            .unwrap(),
+    );
+    let runs: Vec<_> = (0..3)
+        .map(|i| {
+            let pool = pool.clone();
+            tokio::spawn(async move {
+                let (tx, rx) = mpsc::channel(100);
+                let command = format!("sleep 0.2; echo {i}");
+                let exit_code = pool.run("busy", &command, tx).await.unwrap();
+                (exit_code, collect(rx).await.0.stdout)
+            })
+        })
+        .collect();
+
+    for (i, run) in runs.into_iter().enumerate() {
+        let (exit_code, stdout) = run.await.unwrap();
+        assert_eq!(exit_code, Some(0));
+        assert_eq!(stdout, [i.to_string()]);
+    }
+    assert_eq!(pool.idle_count(), 1);
+}
+
+#[tokio::test]
+async fn closed_channel_keeps_shell_usable() {
+    let pool = ShellPool::new(ShellPoolConfig::new().size(1))
+        .await
+        .unwrap();
+    let (tx, rx) = mpsc::channel(1);
+    drop(rx);
+    let exit_code = pool.run("pooled", "seq 1 100; exit 4", tx).await.unwrap();
+    assert_eq!(exit_code, Some(4));
+
+    let (result, _, _) = run_pooled(&pool, "echo ok").await;
+    assert_eq!(result.stdout, ["ok"]);
+}
+
+#[tokio::test]
+async fn invalid_pools_are_rejected() {
+    assert!(matches!(
+        ShellPool::new(ShellPoolConfig::new().size(0)).await,
+        Err(TaskError::InvalidConfiguration(_))
+    ));
+    assert!(matches!(
+        ShellPool::new(ShellPoolConfig::new().program("/nonexistent/shell")).await,
+        Err(TaskError::IO(_))
+    ));
+}
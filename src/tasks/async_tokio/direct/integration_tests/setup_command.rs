@@ -20,7 +20,7 @@ async fn echo_with_args() {
         .args(["hello_test"])
         .use_process_group(false);
 
-    setup_command(&mut cmd, &config);
+    setup_command(&mut cmd, &config, false);
     let output = cmd.output().await.expect("Failed to run echo");
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(
@@ -34,7 +34,7 @@ async fn echo_with_env() {
     #[cfg(windows)]
     let mut cmd = Command::new("cmd");
     #[cfg(unix)]
-    let mut cmd = Command::new("echo");
+    let mut cmd = Command::new("sh");
 
     let mut envs = HashMap::new();
     envs.insert("FOO".to_string(), "BAR_TEST".to_string());
@@ -45,12 +45,12 @@ async fn echo_with_env() {
         .args(["/C", "echo %FOO%"])
         .use_process_group(false);
     #[cfg(unix)]
-    let config = TaskConfig::new("echo")
+    let config = TaskConfig::new("sh")
         .env(envs)
-        .args(["$FOO"])
+        .args(["-c", "echo $FOO"])
         .use_process_group(false);
 
-    setup_command(&mut cmd, &config);
+    setup_command(&mut cmd, &config, false);
 
     let output = cmd.output().await.expect("Failed to run echo env");
     let stdout = String::from_utf8_lossy(&output.stdout);
@@ -74,7 +74,7 @@ async fn stdin_enabled_accepts_input() {
         .enable_stdin(true)
         .use_process_group(false);
 
-    setup_command(&mut cmd, &config);
+    setup_command(&mut cmd, &config, true);
 
     let mut child = cmd.spawn().expect("Failed to spawn");
     let mut stdin = child.stdin.take().expect("No stdin");
@@ -108,7 +108,7 @@ async fn stdin_disabled_no_input() {
         .enable_stdin(false)
         .use_process_group(false);
 
-    setup_command(&mut cmd, &config);
+    setup_command(&mut cmd, &config, false);
     let child = cmd.spawn().expect("Failed to spawn");
     // Should not have stdin available
     assert!(child.stdin.is_none(), "stdin should be None when disabled");
@@ -0,0 +1,169 @@
+use std::{path::Path, time::Duration};
+
+use tokio::sync::mpsc;
+
+use crate::tasks::{
+    async_tokio::spawner::{TaskSpawner, TerminateOutcome},
+    audit::AuditRecord,
+    config::TaskConfig,
+    event::{TaskEvent, TaskEventStopReason, TaskTerminateReason},
+};
+
+/// Receives events until the task has stopped, returning the process ID and the stop reason
+async fn wait_for_stop(rx: &mut mpsc::Receiver<TaskEvent>) -> (u32, TaskEventStopReason) {
+    let mut process_id = None;
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while let Some(event) = rx.recv().await {
+            match event {
+                TaskEvent::Started {
+                    process_id: pid, ..
+                } => process_id = Some(pid),
+                TaskEvent::Stopped { reason, .. } => return (process_id.unwrap(), reason),
+                _ => {}
+            }
+        }
+        panic!("Event channel closed before Stopped");
+    })
+    .await
+    .expect("Task should stop within the timeout")
+}
+
+/// Checks the parts of the record that do not depend on how the run ended
+fn assert_lifecycle(audit: &AuditRecord, process_id: u32, reason: &TaskEventStopReason) {
+    assert_eq!(audit.task_name, "audit");
+    assert!(Path::new(&audit.command).is_absolute(), "{}", audit.command);
+    assert_eq!(audit.process_id, Some(process_id));
+    assert_eq!(&audit.reason, reason);
+    assert!(audit.started_at <= audit.stopped_at);
+}
+
+#[tokio::test]
+async fn successful_run_is_recorded_with_redacted_args() {
+    let working_dir = std::fs::canonicalize(std::env::temp_dir()).unwrap();
+    let config = TaskConfig::new("sh")
+        .args([
+            "-c",
+            "printf hello; echo oops >&2",
+            "audit",
+            "--token",
+            "s3cr3t",
+        ])
+        .working_dir(working_dir.to_str().unwrap())
+        .env([("RUST_LOG", "debug"), ("API_TOKEN", "abc")]);
+    let mut spawner = TaskSpawner::new("audit".to_string(), config);
+    assert!(spawner.audit().await.is_none());
+
+    let (tx, mut rx) = mpsc::channel(100);
+    spawner.start_direct(tx).await.unwrap();
+    let (process_id, reason) = wait_for_stop(&mut rx).await;
+
+    let audit = spawner
+        .audit()
+        .await
+        .expect("Audit record set once Stopped is received");
+    assert_lifecycle(&audit, process_id, &reason);
+    assert!(audit.command.ends_with("/sh"), "{}", audit.command);
+    assert_eq!(
+        audit.args,
+        [
+            "-c",
+            "printf hello; echo oops >&2",
+            "audit",
+            "--token",
+            "[REDACTED]"
+        ]
+    );
+    assert_eq!(audit.working_dir, working_dir.to_string_lossy());
+    assert_eq!(audit.env_keys, ["API_TOKEN", "RUST_LOG"]);
+    assert_eq!(audit.exit_code, Some(0));
+    assert_eq!(audit.signal, None);
+    assert_eq!(audit.reason, TaskEventStopReason::Finished);
+    assert_eq!(
+        audit.output_bytes,
+        "hello".len() as u64 + "oops".len() as u64
+    );
+    assert!(audit.terminate_requests.is_empty());
+}
+
+#[tokio::test]
+async fn timed_out_run_is_recorded_without_terminate_requests() {
+    let config = TaskConfig::new("sleep").args(["10"]).timeout_ms(200);
+    let mut spawner = TaskSpawner::new("audit".to_string(), config);
+
+    let (tx, mut rx) = mpsc::channel(100);
+    spawner.start_direct(tx).await.unwrap();
+    let (process_id, reason) = wait_for_stop(&mut rx).await;
+
+    let audit = spawner.audit().await.unwrap();
+    assert_lifecycle(&audit, process_id, &reason);
+    assert_eq!(
+        audit.reason,
+        TaskEventStopReason::Terminated(TaskTerminateReason::Timeout)
+    );
+    assert_eq!(audit.exit_code, None);
+    assert_eq!(audit.signal, Some(9));
+    assert_eq!(audit.output_bytes, 0);
+    assert!(audit.terminate_requests.is_empty());
+    let ran_for = audit.stopped_at.duration_since(audit.started_at).unwrap();
+    assert!(ran_for >= Duration::from_millis(200), "{ran_for:?}");
+}
+
+#[tokio::test]
+async fn user_terminated_run_records_every_terminate_request() {
+    let config = TaskConfig::new("sleep").args(["10"]);
+    let mut spawner = TaskSpawner::new("audit".to_string(), config);
+
+    let (tx, mut rx) = mpsc::channel(100);
+    spawner.start_direct(tx).await.unwrap();
+    spawner
+        .send_terminate_signal(TaskTerminateReason::UserRequested { message: None })
+        .await
+        .unwrap();
+    // Already terminating, still received
+    let outcome = spawner
+        .send_terminate_signal(TaskTerminateReason::Cleanup { message: None })
+        .await
+        .unwrap();
+    assert_eq!(outcome, TerminateOutcome::AlreadyTerminating);
+    let (process_id, reason) = wait_for_stop(&mut rx).await;
+
+    let audit = spawner.audit().await.unwrap();
+    assert_lifecycle(&audit, process_id, &reason);
+    assert_eq!(
+        audit.reason,
+        TaskEventStopReason::Terminated(TaskTerminateReason::UserRequested { message: None })
+    );
+    assert_eq!(audit.exit_code, None);
+    assert_eq!(
+        audit.terminate_requests,
+        [
+            TaskTerminateReason::UserRequested { message: None },
+            TaskTerminateReason::Cleanup { message: None }
+        ]
+    );
+}
+
+#[tokio::test]
+async fn killing_signal_is_recorded() {
+    let config = TaskConfig::new("sh").args(["-c", "kill -9 $$"]);
+    let mut spawner = TaskSpawner::new("audit".to_string(), config);
+
+    let (tx, mut rx) = mpsc::channel(100);
+    spawner.start_direct(tx).await.unwrap();
+    let (process_id, reason) = wait_for_stop(&mut rx).await;
+
+    let audit = spawner.audit().await.unwrap();
+    assert_lifecycle(&audit, process_id, &reason);
+    assert_eq!(audit.exit_code, None);
+    assert_eq!(audit.signal, Some(9));
+}
+
+#[tokio::test]
+async fn failed_spawn_has_no_audit_record() {
+    let config = TaskConfig::new("tcrm_task_missing_command_for_audit");
+    let mut spawner = TaskSpawner::new("audit".to_string(), config);
+
+    let (tx, _rx) = mpsc::channel(100);
+    assert!(spawner.start_direct(tx).await.is_err());
+    assert!(spawner.audit().await.is_none());
+}
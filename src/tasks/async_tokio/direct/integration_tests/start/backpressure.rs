@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use crate::tasks::config::TaskConfig;
+use crate::tasks::{async_tokio::spawner::TaskSpawner, event::TaskEvent};
+
+#[cfg(unix)]
+#[tokio::test]
+async fn chatty_child_exits_with_unpolled_receiver() {
+    use nix::sys::signal::kill;
+    use nix::unistd::Pid;
+
+    // Output is far larger than a pipe buffer, so a blocked watcher would block the child
+    let config = TaskConfig::new("seq")
+        .args(["1", "50000"])
+        .event_send_timeout_ms(20)
+        .use_process_group(false);
+
+    // Receiver is kept alive but never polled while the child runs
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(1);
+    let mut spawner = TaskSpawner::new("chatty_task".to_string(), config);
+    let pid = spawner.start_direct(tx).await.unwrap();
+
+    let exited = tokio::time::timeout(Duration::from_secs(10), async {
+        while kill(Pid::from_raw(pid as i32), None).is_ok() {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await;
+    assert!(exited.is_ok(), "Child should exit even if events are not consumed");
+    assert!(
+        spawner.get_task_info().await.dropped_events > 0,
+        "Undelivered output should be counted as dropped"
+    );
+
+    let mut stopped = false;
+    while let Some(event) = rx.recv().await {
+        if let TaskEvent::Stopped { exit_code, .. } = event {
+            assert_eq!(exit_code, Some(0));
+            stopped = true;
+        }
+    }
+    assert!(stopped, "Stopped should still be delivered once the consumer resumes");
+}
+
+/// Counts the `SlowConsumer` events of a task printing 100 lines, received
+/// with `delay` per event
+#[cfg(unix)]
+async fn slow_consumer_warnings(delay: Duration) -> (usize, TaskSpawner) {
+    let config = TaskConfig::new("seq")
+        .args(["1", "100"])
+        .slow_consumer_threshold_ms(5)
+        .use_process_group(false);
+
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(1);
+    let mut spawner = TaskSpawner::new("slow_consumer".to_string(), config);
+    spawner.start_direct(tx).await.unwrap();
+
+    let mut warnings = 0;
+    while let Some(event) = rx.recv().await {
+        if let TaskEvent::SlowConsumer { avg_wait_ms, .. } = event {
+            assert!(avg_wait_ms >= 5);
+            warnings += 1;
+        }
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+    (warnings, spawner)
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn slow_consumer_is_warned_once() {
+    let (warnings, spawner) = slow_consumer_warnings(Duration::from_millis(15)).await;
+    assert_eq!(warnings, 1);
+
+    let info = spawner.get_task_info().await;
+    assert!(info.slow_event_sends > 0);
+    assert!(info.event_send_wait > Duration::ZERO);
+    assert_eq!(info.event_channel_high_water, 1);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn fast_consumer_is_not_warned() {
+    let (warnings, _) = slow_consumer_warnings(Duration::ZERO).await;
+    assert_eq!(warnings, 0);
+}
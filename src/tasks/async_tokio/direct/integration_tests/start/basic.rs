@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 use tokio::sync::mpsc;
 use tokio::time::timeout;
@@ -22,32 +22,46 @@ async fn echo_command() {
 
     let mut spawner = TaskSpawner::new("echo_task".to_string(), config);
 
-    let result = spawner.start_direct(tx).await;
-    assert!(result.is_ok());
+    let before_start = SystemTime::now();
+    let pid = spawner.start_direct(tx).await.unwrap();
 
     let mut started = false;
     let mut stopped = false;
     while let Some(event) = rx.recv().await {
         match event {
-            TaskEvent::Started { task_name } => {
-                assert_eq!(task_name, "echo_task");
+            TaskEvent::Started {
+                task_name,
+                process_id,
+                temp_dir,
+                working_dir,
+            } => {
+                assert_eq!(temp_dir, None);
+                assert_eq!(working_dir, None);
+                assert_eq!(&*task_name, "echo_task");
+                assert_eq!(process_id, pid);
                 started = true;
             }
             TaskEvent::Output {
                 task_name,
                 line,
                 src,
+                seq,
+                truncated,
+                timestamp,
             } => {
-                assert_eq!(task_name, "echo_task");
+                assert_eq!(&*task_name, "echo_task");
                 assert_eq!(line, "hello");
                 assert_eq!(src, StreamSource::Stdout);
+                assert_eq!(seq, 0);
+                assert!(!truncated);
+                assert!(before_start <= timestamp && timestamp <= SystemTime::now());
             }
             TaskEvent::Stopped {
                 task_name,
                 exit_code,
-                reason: _,
+                ..
             } => {
-                assert_eq!(task_name, "echo_task");
+                assert_eq!(&*task_name, "echo_task");
                 assert_eq!(exit_code, Some(0));
                 stopped = true;
             }
@@ -58,6 +72,29 @@ async fn echo_command() {
     assert!(started);
     assert!(stopped);
 }
+
+#[tokio::test]
+async fn started_is_sent_before_start_returns() {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(1024);
+    #[cfg(windows)]
+    let config = TaskConfig::new("powershell")
+        .args(["-Command", "echo hello"])
+        .use_process_group(false);
+    #[cfg(unix)]
+    let config = TaskConfig::new("echo")
+        .args(["hello"])
+        .use_process_group(false);
+
+    let mut spawner = TaskSpawner::new("pid_task".to_string(), config);
+    let pid = spawner.start_direct(tx).await.unwrap();
+
+    // No waiting: the event is already queued
+    match rx.try_recv() {
+        Ok(TaskEvent::Started { process_id, .. }) => assert_eq!(process_id, pid),
+        other => panic!("Expected Started as the first event, got {other:?}"),
+    }
+    while rx.recv().await.is_some() {}
+}
 #[tokio::test]
 async fn env_echo() {
     let (tx, mut rx) = mpsc::channel(100);
@@ -120,7 +157,7 @@ async fn invalid_empty_command() {
     let mut error_event = false;
     while let Some(event) = rx.recv().await {
         if let TaskEvent::Error { task_name, error } = event {
-            assert_eq!(task_name, "bad_task");
+            assert_eq!(&*task_name, "bad_task");
             assert!(matches!(error, TaskError::InvalidConfiguration(_)));
             error_event = true;
         }
@@ -142,17 +179,19 @@ async fn command_not_found() {
     let mut spawner = TaskSpawner::new("error_task".to_string(), config);
 
     let result = spawner.start_direct(tx).await;
-    assert!(matches!(result, Err(TaskError::IO(_))));
+    let expected = TaskError::CommandNotFound {
+        command: "non_existent_command".to_string(),
+        searched_path: std::env::var("PATH").ok(),
+    };
+    assert_eq!(result, Err(expected.clone()));
+    assert_eq!(
+        expected.to_string(),
+        "Command not found: non_existent_command"
+    );
 
     if let Some(TaskEvent::Error { task_name, error }) = rx.recv().await {
-        assert_eq!(task_name, "error_task");
-        assert!(matches!(error, TaskError::IO(_)));
-        if let TaskError::IO(msg) = error {
-            #[cfg(windows)]
-            assert!(msg.contains("not found") || msg.contains("cannot find"));
-            #[cfg(unix)]
-            assert!(msg.contains("No such file or directory"));
-        }
+        assert_eq!(&*task_name, "error_task");
+        assert_eq!(error, expected);
     } else {
         panic!("Expected TaskEvent::Error");
     }
@@ -169,7 +208,7 @@ async fn invalid_working_directory() {
     assert!(matches!(result, Err(TaskError::InvalidConfiguration(_))));
 
     if let Some(TaskEvent::Error { task_name, error }) = rx.recv().await {
-        assert_eq!(task_name, "working_dir_task");
+        assert_eq!(&*task_name, "working_dir_task");
         assert!(matches!(error, TaskError::InvalidConfiguration(_)));
     } else {
         panic!("Expected TaskEvent::Error");
@@ -287,14 +326,45 @@ async fn test_task_state_transitions() {
     let result = spawner.start_direct(tx).await;
     assert!(result.is_ok(), "Task should start successfully");
 
-    // Wait a bit for state changes
-    tokio::time::sleep(Duration::from_millis(100)).await;
+    let report = spawner.wait_for_quiescence(Duration::from_secs(5)).await;
+    assert!(report.is_quiescent(), "{report:?}");
+
+    assert_eq!(spawner.get_state().await, TaskState::Finished);
+}
+
+#[tokio::test]
+async fn watchers_release_shared_config() {
+    let (tx, mut rx) = mpsc::channel(100);
 
-    // State should have changed from Pending
-    let final_state = spawner.get_state().await;
-    assert_ne!(
-        final_state,
-        TaskState::Pending,
-        "State should have changed from Pending"
+    #[cfg(windows)]
+    let config = TaskConfig::new("cmd")
+        .args(["/C", "echo test"])
+        .ready_indicator("test")
+        .timeout_ms(5000);
+    #[cfg(unix)]
+    let config = TaskConfig::new("echo")
+        .args(["test"])
+        .ready_indicator("test")
+        .timeout_ms(5000);
+
+    let mut spawner = TaskSpawner::new("shared_config".to_string(), config);
+    assert_eq!(std::sync::Arc::strong_count(&spawner.config), 1);
+
+    spawner.start_direct(tx).await.unwrap();
+    assert!(std::sync::Arc::strong_count(&spawner.config) > 1);
+
+    while let Some(event) = rx.recv().await {
+        if matches!(event, TaskEvent::Stopped { .. }) {
+            break;
+        }
+    }
+
+    // The wait watcher drops its reference right after sending Stopped
+    let report = spawner.wait_for_quiescence(Duration::from_secs(5)).await;
+    assert!(report.is_quiescent(), "{report:?}");
+    assert_eq!(
+        std::sync::Arc::strong_count(&spawner.config),
+        1,
+        "Watchers kept the task config alive"
     );
 }
@@ -0,0 +1,116 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use crate::tasks::async_tokio::direct::integration_tests::support::commands;
+use crate::tasks::{
+    async_tokio::{
+        cache::{CacheKey, CacheLayer, MemoryCache},
+        spawner::TaskSpawner,
+    },
+    config::TaskConfig,
+    event::{TaskEvent, TaskEventStopReason},
+    state::TaskState,
+};
+
+/// Runs the task through the cache to completion and returns its pid and events
+async fn run_cached(
+    spawner: &mut TaskSpawner,
+    cache: &Arc<MemoryCache>,
+    replay_output: bool,
+) -> (Option<u32>, Vec<TaskEvent>) {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(100);
+    let pid = spawner
+        .start_cached(tx, cache.clone(), replay_output)
+        .await
+        .unwrap();
+
+    let mut events = vec![];
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while let Some(event) = rx.recv().await {
+            events.push(event);
+        }
+    })
+    .await
+    .expect("Task should stop within the timeout");
+    (pid, events)
+}
+
+fn output_lines(events: &[TaskEvent]) -> Vec<String> {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            TaskEvent::Output { line, .. } => Some(line.trim_end().to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn second_run_is_replayed_without_spawning() {
+    let cache = Arc::new(MemoryCache::new());
+
+    let mut first = TaskSpawner::new("seq".to_string(), commands::lines(3));
+    let (pid, events) = run_cached(&mut first, &cache, true).await;
+    assert!(pid.is_some());
+    assert!(matches!(
+        events.last(),
+        Some(TaskEvent::Stopped {
+            from_cache: false,
+            ..
+        })
+    ));
+    assert_eq!(cache.len(), 1);
+
+    let mut second = TaskSpawner::new("seq".to_string(), commands::lines(3));
+    let (pid, events) = run_cached(&mut second, &cache, true).await;
+
+    assert_eq!(pid, None);
+    assert_eq!(second.get_process_id().await, None);
+    assert_eq!(second.get_state().await, TaskState::Finished);
+    assert!(matches!(
+        events.first(),
+        Some(TaskEvent::Started { process_id: 0, .. })
+    ));
+    assert_eq!(output_lines(&events), ["1", "2", "3"]);
+    assert!(matches!(
+        events.last(),
+        Some(TaskEvent::Stopped {
+            exit_code: Some(0),
+            reason: TaskEventStopReason::Finished,
+            from_cache: true,
+            ..
+        })
+    ));
+}
+
+#[tokio::test]
+async fn output_is_not_stored_without_replay() {
+    let cache = Arc::new(MemoryCache::new());
+    let config = commands::lines(2);
+
+    let mut spawner = TaskSpawner::new("seq".to_string(), config.clone());
+    let (_, events) = run_cached(&mut spawner, &cache, false).await;
+    assert_eq!(output_lines(&events), ["1", "2"]);
+
+    let cached = cache.get(CacheKey::of(&config)).unwrap();
+    assert!(cached.output.is_empty());
+}
+
+#[tokio::test]
+async fn failed_run_is_not_stored() {
+    #[cfg(windows)]
+    let config = TaskConfig::new("cmd").args(["/C", "exit 1"]);
+    #[cfg(unix)]
+    let config = TaskConfig::new("sh").args(["-c", "exit 1"]);
+    let cache = Arc::new(MemoryCache::new());
+
+    for _ in 0..2 {
+        let mut spawner = TaskSpawner::new("failing".to_string(), config.clone());
+        let (pid, _) = run_cached(&mut spawner, &cache, false).await;
+        assert!(pid.is_some());
+    }
+
+    assert!(cache.is_empty());
+}
@@ -0,0 +1,109 @@
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use crate::tasks::{
+    async_tokio::spawner::{ChannelClosedPolicy, TaskSpawner, TerminateOutcome},
+    audit::AuditRecord,
+    config::TaskConfig,
+    event::{TaskEventStopReason, TaskTerminateReason},
+    state::TaskState,
+};
+
+/// Writes a line once the receiver is gone, then another after the reader had time to react
+const TWO_LINES: &str = "sleep 0.3; echo one; sleep 0.3; echo two";
+
+/// Starts the task and drops the event receiver right away
+async fn start_without_receiver(spawner: &mut TaskSpawner) {
+    let (tx, rx) = mpsc::channel(100);
+    spawner.start_direct(tx).await.unwrap();
+    drop(rx);
+}
+
+/// Polls until the task has stopped, returning its audit record
+async fn wait_for_audit(spawner: &TaskSpawner) -> AuditRecord {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let Some(audit) = spawner.audit().await {
+                return audit;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .expect("Task should stop within the timeout")
+}
+
+#[tokio::test]
+async fn continue_silently_stops_reading_output() {
+    let config = TaskConfig::new("sh").args(["-c", TWO_LINES]);
+    let mut spawner = TaskSpawner::new("channel_closed".to_string(), config);
+    start_without_receiver(&mut spawner).await;
+
+    let audit = wait_for_audit(&spawner).await;
+    // stdout is no longer read, so the second line gets SIGPIPE
+    assert!(
+        matches!(audit.reason, TaskEventStopReason::Error(_)),
+        "{:?}",
+        audit.reason
+    );
+    assert_eq!(audit.signal, Some(libc::SIGPIPE));
+    assert_eq!(audit.output_bytes, "one".len() as u64);
+    assert_eq!(spawner.get_state().await, TaskState::Finished);
+}
+
+#[tokio::test]
+async fn terminate_task_stops_silent_process_with_cleanup() {
+    let config = TaskConfig::new("sleep").args(["10"]);
+    let mut spawner = TaskSpawner::new("channel_closed".to_string(), config)
+        .set_on_channel_closed(ChannelClosedPolicy::TerminateTask);
+    start_without_receiver(&mut spawner).await;
+
+    let audit = wait_for_audit(&spawner).await;
+    assert_eq!(
+        audit.reason,
+        TaskEventStopReason::Terminated(TaskTerminateReason::Cleanup { message: None })
+    );
+    assert_eq!(audit.exit_code, None);
+    assert!(audit.terminate_requests.is_empty());
+    let ran_for = audit.stopped_at.duration_since(audit.started_at).unwrap();
+    assert!(ran_for < Duration::from_secs(5), "{ran_for:?}");
+    assert_eq!(spawner.get_state().await, TaskState::Finished);
+    let outcome = spawner
+        .send_terminate_signal(TaskTerminateReason::UserRequested { message: None })
+        .await
+        .unwrap();
+    assert_eq!(outcome, TerminateOutcome::AlreadyFinished);
+}
+
+#[tokio::test]
+async fn detach_and_continue_reads_output_to_the_end() {
+    let config = TaskConfig::new("sh").args(["-c", TWO_LINES]);
+    let mut spawner = TaskSpawner::new("channel_closed".to_string(), config)
+        .set_on_channel_closed(ChannelClosedPolicy::DetachAndContinue);
+    start_without_receiver(&mut spawner).await;
+
+    let audit = wait_for_audit(&spawner).await;
+    assert_eq!(audit.reason, TaskEventStopReason::Finished);
+    assert_eq!(audit.exit_code, Some(0));
+    assert_eq!(audit.signal, None);
+    assert_eq!(audit.output_bytes, "onetwo".len() as u64);
+    assert_eq!(spawner.get_state().await, TaskState::Finished);
+}
+
+#[tokio::test]
+async fn detach_and_continue_still_stops_on_trigger() {
+    let config = TaskConfig::new("sh")
+        .args(["-c", "sleep 0.3; echo stop; sleep 10"])
+        .exit_on_output_match("stop");
+    let mut spawner = TaskSpawner::new("channel_closed".to_string(), config)
+        .set_on_channel_closed(ChannelClosedPolicy::DetachAndContinue);
+    start_without_receiver(&mut spawner).await;
+
+    let audit = wait_for_audit(&spawner).await;
+    assert_eq!(
+        audit.reason,
+        TaskEventStopReason::Terminated(TaskTerminateReason::CompletionIndicator)
+    );
+    assert_eq!(spawner.get_state().await, TaskState::Finished);
+}
@@ -0,0 +1,83 @@
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use crate::tasks::{async_tokio::spawner::TaskSpawner, config::TaskConfig, event::TaskEvent};
+
+/// Returns `true` while the process exists and is not a zombie
+fn is_running(pid: u32) -> bool {
+    match std::fs::read_to_string(format!("/proc/{pid}/stat")) {
+        Ok(stat) => stat
+            .rsplit(") ")
+            .next()
+            .is_some_and(|rest| !rest.starts_with('Z')),
+        Err(_) => false,
+    }
+}
+
+async fn wait_until_gone(pid: u32) -> bool {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while is_running(pid) {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .is_ok()
+}
+
+/// Aborts the wait watcher, as a runtime shutdown would
+async fn abort_wait_watcher(spawner: &mut TaskSpawner) {
+    let handle = spawner.watchers.abort_handle("wait").unwrap();
+    handle.abort();
+    while !handle.is_finished() {
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+}
+
+#[tokio::test]
+async fn aborted_watcher_group_terminated_on_drop() {
+    // The shell prints the pid of a grandchild in its process group
+    let config = TaskConfig::new("sh").args(["-c", "sleep 30 & echo $!; wait"]);
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(64);
+    let mut spawner = TaskSpawner::new("stray".to_string(), config);
+    spawner.start_direct(tx).await.unwrap();
+
+    let grandchild = loop {
+        match rx.recv().await {
+            Some(TaskEvent::Output { line, .. }) => break line.trim().parse::<u32>().unwrap(),
+            Some(_) => {}
+            None => panic!("Task stopped before printing the grandchild pid"),
+        }
+    };
+    assert!(is_running(grandchild));
+
+    abort_wait_watcher(&mut spawner).await;
+    // Killing the shell leaves the grandchild running until the spawner is dropped
+    assert!(is_running(grandchild));
+    drop(spawner);
+
+    assert!(
+        wait_until_gone(grandchild).await,
+        "Grandchild {grandchild} outlived the spawner"
+    );
+}
+
+#[tokio::test]
+async fn detached_process_survives_aborted_watcher() {
+    let config = TaskConfig::new("sleep").args(["30"]);
+    let (tx, _rx) = mpsc::channel::<TaskEvent>(64);
+    let mut spawner = TaskSpawner::new("detached".to_string(), config).set_cleanup_on_drop(false);
+    let pid = spawner.start_direct(tx).await.unwrap();
+
+    abort_wait_watcher(&mut spawner).await;
+    drop(spawner);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(is_running(pid), "Detached process {pid} was killed");
+
+    nix::sys::signal::kill(
+        nix::unistd::Pid::from_raw(pid.cast_signed()),
+        nix::sys::signal::Signal::SIGKILL,
+    )
+    .unwrap();
+    assert!(wait_until_gone(pid).await);
+}
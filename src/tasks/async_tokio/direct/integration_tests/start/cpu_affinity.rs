@@ -0,0 +1,88 @@
+use tokio::sync::mpsc;
+
+use crate::tasks::{
+    async_tokio::spawner::TaskSpawner, config::TaskConfig, error::TaskError,
+    event::TaskTerminateReason,
+};
+
+/// Cores a running process may run on, from the `Cpus_allowed` mask in
+/// `/proc/<pid>/status`
+fn cpus_allowed(pid: &str) -> Vec<usize> {
+    let status = std::fs::read_to_string(format!("/proc/{pid}/status")).unwrap();
+    let mask = status
+        .lines()
+        .find_map(|line| line.strip_prefix("Cpus_allowed:"))
+        .unwrap();
+    // Hex digits, most significant first, in comma separated groups
+    let digits: Vec<u32> = mask
+        .trim()
+        .chars()
+        .filter(|c| *c != ',')
+        .map(|c| c.to_digit(16).unwrap())
+        .collect();
+    digits
+        .iter()
+        .rev()
+        .enumerate()
+        .flat_map(|(i, digit)| {
+            (0..4)
+                .filter(move |bit| digit & (1 << bit) != 0)
+                .map(move |bit| i * 4 + bit)
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn child_runs_on_the_given_cores() {
+    // This process may already be pinned, so only pick cores it is allowed
+    // that also pass validation
+    let available = std::thread::available_parallelism().unwrap().get();
+    let allowed: Vec<usize> = cpus_allowed("self")
+        .into_iter()
+        .filter(|core| *core < available)
+        .collect();
+    let cases: Vec<Vec<usize>> = [1, 2]
+        .into_iter()
+        .filter(|count| allowed.len() >= *count)
+        .map(|count| allowed[..count].to_vec())
+        .collect();
+
+    for cores in cases {
+        let config = TaskConfig::new("sleep")
+            .args(["5"])
+            .cpu_affinity(cores.clone());
+        let mut spawner = TaskSpawner::new("affinity".to_string(), config);
+        let (tx, _rx) = mpsc::channel(64);
+
+        let pid = spawner.start_direct(tx).await.unwrap();
+
+        assert_eq!(cpus_allowed(&pid.to_string()), cores);
+        spawner
+            .send_terminate_signal(TaskTerminateReason::Cleanup { message: None })
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn cores_the_process_may_not_use_are_rejected_on_start() {
+    // Only a core that passes validation but is outside this process's mask
+    let allowed = cpus_allowed("self");
+    let available = std::thread::available_parallelism().unwrap().get();
+    let Some(core) = (0..available).find(|core| !allowed.contains(core)) else {
+        return;
+    };
+    let config = TaskConfig::new("sleep").args(["5"]).cpu_affinity([core]);
+    let mut spawner = TaskSpawner::new("affinity".to_string(), config);
+    let (tx, _rx) = mpsc::channel(64);
+
+    let error = spawner.start_direct(tx).await.unwrap_err();
+
+    assert_eq!(
+        error,
+        TaskError::PreExec {
+            step: "cpu_affinity".to_string(),
+            error: "EINVAL".to_string(),
+        }
+    );
+}
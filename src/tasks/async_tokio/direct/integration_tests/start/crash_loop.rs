@@ -0,0 +1,66 @@
+use tokio::sync::mpsc;
+
+use crate::tasks::{
+    async_tokio::{
+        crash_loop::{CrashLoopDetector, CrashLoopPolicy},
+        spawner::TaskSpawner,
+    },
+    config::TaskConfig,
+    event::TaskEvent,
+};
+
+/// A command that exits with code 1 immediately
+fn exits_immediately() -> TaskConfig {
+    if cfg!(windows) {
+        TaskConfig::new("cmd").args(["/C", "exit 1"])
+    } else {
+        TaskConfig::new("sh").args(["-c", "exit 1"])
+    }
+}
+
+/// Runs one attempt to completion and returns its exit code and duration
+async fn run_attempt(attempt: u32) -> (Option<i32>, u64) {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(64);
+    let mut spawner = TaskSpawner::new(format!("attempt_{attempt}"), exits_immediately());
+    spawner.start_direct(tx).await.unwrap();
+    while let Some(event) = rx.recv().await {
+        if let TaskEvent::Stopped {
+            exit_code,
+            duration_ms,
+            ..
+        } = event
+        {
+            return (exit_code, duration_ms);
+        }
+    }
+    panic!("Attempt {attempt} did not send Stopped");
+}
+
+#[tokio::test]
+async fn immediate_failures_abort_restarts_early() {
+    const MAX_RETRIES: u32 = 5;
+    let policy = CrashLoopPolicy {
+        min_uptime_ms: 5_000,
+        max_consecutive: 3,
+    };
+    let mut detector = CrashLoopDetector::new(policy);
+
+    let mut attempts = 0;
+    for attempt in 0..=MAX_RETRIES {
+        attempts += 1;
+        let (exit_code, duration_ms) = run_attempt(attempt).await;
+        if detector.record(exit_code, duration_ms) {
+            break;
+        }
+    }
+
+    assert_eq!(attempts, policy.max_consecutive);
+    assert!(detector.is_crash_loop());
+    let history = detector.history();
+    assert!(history.len() >= policy.max_consecutive as usize);
+    assert!(
+        history
+            .iter()
+            .all(|record| record.exit_code == Some(1) && record.duration_ms < policy.min_uptime_ms)
+    );
+}
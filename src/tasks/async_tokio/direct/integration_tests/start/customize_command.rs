@@ -0,0 +1,37 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use tokio::sync::mpsc;
+
+use crate::tasks::{async_tokio::spawner::TaskSpawner, config::TaskConfig, event::TaskEvent};
+
+#[tokio::test]
+async fn customizer_sets_env_seen_by_child() {
+    #[cfg(windows)]
+    let config = TaskConfig::new("cmd").args(["/C", "echo %TCRM_CUSTOM%"]);
+    #[cfg(unix)]
+    let config = TaskConfig::new("sh").args(["-c", "echo $TCRM_CUSTOM"]);
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let mut spawner =
+        TaskSpawner::new("customized".to_string(), config).customize_command(move |cmd| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            cmd.env("TCRM_CUSTOM", "from-customizer");
+        });
+
+    spawner.plan().unwrap();
+    assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(64);
+    spawner.start_direct(tx).await.unwrap();
+    let mut lines = vec![];
+    while let Some(event) = rx.recv().await {
+        if let TaskEvent::Output { line, .. } = event {
+            lines.push(line.trim().to_string());
+        }
+    }
+    assert_eq!(lines, ["from-customizer"]);
+    assert_eq!(calls.load(Ordering::Relaxed), 2);
+}
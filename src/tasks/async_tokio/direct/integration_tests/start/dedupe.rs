@@ -0,0 +1,76 @@
+use crate::tasks::async_tokio::direct::integration_tests::support::collect_events;
+use crate::tasks::{config::TaskConfig, event::TaskEvent};
+
+const REPEATS: u64 = 10_000;
+
+/// A command that prints the same line `REPEATS` times, then `done`
+fn error_loop() -> TaskConfig {
+    #[cfg(windows)]
+    let config = TaskConfig::new("powershell").args([
+        "-Command".to_string(),
+        format!("1..{REPEATS} | ForEach-Object {{ 'connection refused' }}; 'done'"),
+    ]);
+    #[cfg(unix)]
+    let config = TaskConfig::new("sh").args([
+        "-c".to_string(),
+        format!("yes 'connection refused' | head -n {REPEATS}; echo done"),
+    ]);
+    config
+}
+
+#[tokio::test]
+async fn error_loop_is_collapsed() {
+    let events = collect_events(error_loop().dedupe_output(60_000, 3)).await;
+
+    let outputs: Vec<_> = events
+        .iter()
+        .filter_map(|event| match event {
+            TaskEvent::Output { line, .. } => Some(line.trim_end()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        outputs,
+        [
+            "connection refused",
+            "connection refused",
+            "connection refused",
+            "done"
+        ]
+    );
+
+    let repeated: Vec<_> = events
+        .iter()
+        .filter_map(|event| match event {
+            TaskEvent::OutputRepeated { line, count, .. } => Some((line.trim_end(), *count)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(repeated, [("connection refused", REPEATS - 3)]);
+
+    // The summary comes before the line that ended the run
+    let summary_at = events
+        .iter()
+        .position(|event| matches!(event, TaskEvent::OutputRepeated { .. }))
+        .unwrap();
+    assert!(matches!(
+        &events[summary_at + 1],
+        TaskEvent::Output { line, .. } if line.trim_end() == "done"
+    ));
+}
+
+#[tokio::test]
+async fn every_line_is_sent_without_dedupe() {
+    let events = collect_events(error_loop()).await;
+
+    let outputs = events
+        .iter()
+        .filter(|event| matches!(event, TaskEvent::Output { .. }))
+        .count();
+    assert_eq!(outputs as u64, REPEATS + 1);
+    assert!(
+        !events
+            .iter()
+            .any(|event| matches!(event, TaskEvent::OutputRepeated { .. }))
+    );
+}
@@ -0,0 +1,47 @@
+use tokio::sync::mpsc;
+
+use crate::tasks::config::TaskConfig;
+use crate::tasks::{async_tokio::spawner::TaskSpawner, event::TaskEvent};
+
+const LINES: usize = 10_000;
+
+#[tokio::test]
+async fn fast_exit_delivers_all_output_before_stopped() {
+    for run in 0..10 {
+        // A small channel keeps the output watcher behind the process exit
+        let (tx, mut rx) = mpsc::channel::<TaskEvent>(16);
+        #[cfg(windows)]
+        let config = TaskConfig::new("powershell")
+            .args(["-Command", "1..10000"])
+            .use_process_group(false);
+        #[cfg(unix)]
+        let config = TaskConfig::new("seq")
+            .args(["1", "10000"])
+            .use_process_group(false);
+
+        let mut spawner = TaskSpawner::new("drain_task".to_string(), config);
+        spawner.start_direct(tx).await.unwrap();
+
+        let mut outputs = 0;
+        let mut last_line = String::new();
+        let mut stopped = false;
+        while let Some(event) = rx.recv().await {
+            match event {
+                TaskEvent::Output { line, .. } => {
+                    assert!(!stopped, "Output received after Stopped");
+                    outputs += 1;
+                    last_line = line;
+                }
+                TaskEvent::Stopped { exit_code, .. } => {
+                    assert_eq!(exit_code, Some(0));
+                    stopped = true;
+                }
+                _ => {}
+            }
+        }
+
+        assert!(stopped);
+        assert_eq!(outputs, LINES, "Run {run} lost output lines");
+        assert_eq!(last_line, LINES.to_string());
+    }
+}
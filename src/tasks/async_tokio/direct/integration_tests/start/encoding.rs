@@ -0,0 +1,54 @@
+use crate::tasks::async_tokio::direct::integration_tests::support::run_output;
+use crate::tasks::config::{OutputEncoding, TaskConfig};
+
+/// Writes `bytes` to stdout through `printf` octal escapes
+#[cfg(unix)]
+fn printf_bytes(bytes: &[u8]) -> TaskConfig {
+    let format: String = bytes.iter().map(|b| format!("\\{b:03o}")).collect();
+    TaskConfig::new("printf").args([format])
+}
+
+#[cfg(unix)]
+fn utf16le(text: &str) -> Vec<u8> {
+    text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn utf16le_output_is_decoded_into_lines() {
+    let config = printf_bytes(&utf16le("h\u{00e9}llo\r\n\u{1f600} world\n"))
+        .output_encoding(OutputEncoding::Utf16Le);
+
+    assert_eq!(
+        run_output(config).await,
+        ["h\u{00e9}llo", "\u{1f600} world"]
+    );
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn auto_detects_utf16le_bom() {
+    let mut bytes = vec![0xFF, 0xFE];
+    bytes.extend(utf16le("first\nsecond\n"));
+    let config = printf_bytes(&bytes).output_encoding(OutputEncoding::Auto);
+
+    assert_eq!(run_output(config).await, ["first", "second"]);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn auto_without_bom_reads_utf8() {
+    let config = printf_bytes("plain \u{00e9}\n".as_bytes()).output_encoding(OutputEncoding::Auto);
+
+    assert_eq!(run_output(config).await, ["plain \u{00e9}"]);
+}
+
+#[cfg(windows)]
+#[tokio::test]
+async fn cmd_unicode_output_is_decoded() {
+    let config = TaskConfig::new("cmd")
+        .args(["/U", "/C", "echo hello"])
+        .output_encoding(OutputEncoding::Utf16Le);
+
+    assert_eq!(run_output(config).await, ["hello"]);
+}
@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+use tokio::sync::mpsc;
+
+use crate::tasks::{
+    async_tokio::spawner::TaskSpawner, config::TaskConfig, event::TaskEvent,
+    validator::REDACTED_ENV_VALUE,
+};
+
+/// Runs `env` and returns the spawner with the variables the child printed
+async fn run_env(config: TaskConfig) -> (TaskSpawner, HashMap<String, String>) {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(1024);
+    let mut spawner = TaskSpawner::new("env_snapshot".to_string(), config);
+    spawner.start_direct(tx).await.unwrap();
+
+    let mut printed = HashMap::new();
+    while let Some(event) = rx.recv().await {
+        if let TaskEvent::Output { line, .. } = event
+            && let Some((key, value)) = line.split_once('=')
+        {
+            printed.insert(key.to_string(), value.to_string());
+        }
+    }
+    (spawner, printed)
+}
+
+#[tokio::test]
+async fn snapshot_matches_child_environment() {
+    let config = TaskConfig::new("env")
+        .env([("TCRM_SNAPSHOT", "configured"), ("HOME", "/overridden")])
+        .capture_env_snapshot(true);
+
+    let (spawner, printed) = run_env(config).await;
+    let snapshot = spawner.spawned_env().unwrap();
+
+    assert_eq!(snapshot["TCRM_SNAPSHOT"], "configured");
+    assert_eq!(snapshot["HOME"], "/overridden");
+    // Multi-line values span several output lines, so only single-line ones are compared
+    for (key, value) in snapshot.iter().filter(|(_, value)| !value.contains('\n')) {
+        assert_eq!(
+            printed.get(key),
+            Some(value),
+            "{key} differs from the child"
+        );
+    }
+    assert!(printed.keys().all(|key| snapshot.contains_key(key)));
+}
+
+#[tokio::test]
+async fn redacted_snapshot_masks_sensitive_values() {
+    let config = TaskConfig::new("env")
+        .env([("TCRM_API_TOKEN", "s3cr3t"), ("TCRM_PLAIN", "visible")])
+        .capture_env_snapshot(true);
+
+    let (spawner, _) = run_env(config).await;
+    let redacted = spawner.spawned_env_redacted().unwrap();
+
+    assert_eq!(redacted["TCRM_API_TOKEN"], REDACTED_ENV_VALUE);
+    assert_eq!(redacted["TCRM_PLAIN"], "visible");
+    assert_eq!(spawner.spawned_env().unwrap()["TCRM_API_TOKEN"], "s3cr3t");
+}
+
+#[tokio::test]
+async fn cleared_env_keeps_only_allowed_variables() {
+    // HOME stands in for a secret of the parent, set in every test environment
+    assert!(std::env::var_os("HOME").is_some());
+    let config = TaskConfig::new("env")
+        .clear_env(true)
+        .inherit_env_vars(["PATH", "TCRM_UNSET_IN_PARENT"])
+        .env([("TCRM_CONFIGURED", "yes")])
+        .capture_env_snapshot(true);
+
+    let (spawner, printed) = run_env(config).await;
+
+    let mut keys: Vec<_> = printed.keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    assert_eq!(keys, ["PATH", "TCRM_CONFIGURED"]);
+    assert_eq!(printed["PATH"], std::env::var("PATH").unwrap());
+    assert_eq!(spawner.spawned_env(), Some(&printed));
+}
+
+#[tokio::test]
+async fn no_snapshot_unless_enabled() {
+    let (spawner, printed) = run_env(TaskConfig::new("env")).await;
+
+    assert!(!printed.is_empty());
+    assert!(spawner.spawned_env().is_none());
+    assert!(spawner.spawned_env_redacted().is_none());
+}
@@ -0,0 +1,139 @@
+use tokio::sync::mpsc;
+
+use crate::tasks::config::TaskConfig;
+use crate::tasks::{
+    async_tokio::spawner::TaskSpawner,
+    event::{TaskEvent, TaskEventStopReason},
+};
+
+async fn run_until_stopped(
+    spawner: &mut TaskSpawner,
+    on_started: impl FnOnce(u32),
+) -> (Option<i32>, Option<i32>, TaskEventStopReason) {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(100);
+    let pid = spawner.start_direct(tx).await.unwrap();
+    on_started(pid);
+
+    while let Some(event) = rx.recv().await {
+        if let TaskEvent::Stopped {
+            exit_code,
+            signal,
+            reason,
+            ..
+        } = event
+        {
+            return (exit_code, signal, reason);
+        }
+    }
+    panic!("Channel closed without a Stopped event");
+}
+
+#[tokio::test]
+async fn non_zero_exit_is_finished_with_code() {
+    #[cfg(windows)]
+    let config = TaskConfig::new("cmd").args(["/C", "exit 3"]);
+    #[cfg(unix)]
+    let config = TaskConfig::new("sh").args(["-c", "exit 3"]);
+
+    let mut spawner = TaskSpawner::new("exit_code_task".to_string(), config);
+    let (exit_code, signal, reason) = run_until_stopped(&mut spawner, |_| {}).await;
+
+    assert_eq!(exit_code, Some(3));
+    assert_eq!(signal, None);
+    assert_eq!(reason, TaskEventStopReason::Finished);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn external_sigkill_reports_signal_without_exit_code() {
+    use nix::{
+        sys::signal::{Signal, kill},
+        unistd::Pid,
+    };
+
+    let config = TaskConfig::new("sleep").args(["30"]);
+    let mut spawner = TaskSpawner::new("sigkill_task".to_string(), config);
+    let (exit_code, signal, reason) = run_until_stopped(&mut spawner, |pid| {
+        #[allow(clippy::cast_possible_wrap)]
+        kill(Pid::from_raw(pid as i32), Signal::SIGKILL).unwrap();
+    })
+    .await;
+
+    assert_eq!(exit_code, None);
+    assert_eq!(signal, Some(9));
+    assert_eq!(
+        reason,
+        TaskEventStopReason::Error("Process killed by signal SIGKILL (9)".to_string())
+    );
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn self_inflicted_sigterm_reports_signal_without_exit_code() {
+    let config = TaskConfig::new("sh")
+        .args(["-c", "kill -TERM $$"])
+        .use_process_group(false);
+    let mut spawner = TaskSpawner::new("sigterm_task".to_string(), config);
+    let (exit_code, signal, reason) = run_until_stopped(&mut spawner, |_| {}).await;
+
+    assert_eq!(exit_code, None);
+    assert_eq!(signal, Some(15));
+    assert_eq!(
+        reason,
+        TaskEventStopReason::Error("Process killed by signal SIGTERM (15)".to_string())
+    );
+}
+
+#[tokio::test]
+async fn mapped_exit_code_reports_meaning() {
+    #[cfg(windows)]
+    let command = TaskConfig::new("cmd").args(["/C", "exit 101"]);
+    #[cfg(unix)]
+    let command = TaskConfig::new("sh").args(["-c", "exit 101"]);
+    let config = command.exit_code_meanings([(101, "test failure"), (2, "usage error")]);
+
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(100);
+    let mut spawner = TaskSpawner::new("exit_meaning_task".to_string(), config);
+    spawner.start_direct(tx).await.unwrap();
+
+    let mut exit_meaning = None;
+    while let Some(event) = rx.recv().await {
+        if let TaskEvent::Stopped {
+            exit_code,
+            exit_meaning: meaning,
+            ..
+        } = event
+        {
+            assert_eq!(exit_code, Some(101));
+            exit_meaning = meaning;
+            break;
+        }
+    }
+
+    assert_eq!(exit_meaning.as_deref(), Some("test failure"));
+    assert_eq!(
+        spawner.get_task_info().await.exit_meaning.as_deref(),
+        Some("test failure")
+    );
+}
+
+#[tokio::test]
+async fn unmapped_exit_code_has_no_meaning() {
+    #[cfg(windows)]
+    let command = TaskConfig::new("cmd").args(["/C", "exit 3"]);
+    #[cfg(unix)]
+    let command = TaskConfig::new("sh").args(["-c", "exit 3"]);
+    let config = command.exit_code_meanings([(101, "test failure")]);
+
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(100);
+    let mut spawner = TaskSpawner::new("exit_meaning_task".to_string(), config);
+    spawner.start_direct(tx).await.unwrap();
+
+    while let Some(event) = rx.recv().await {
+        if let TaskEvent::Stopped { exit_meaning, .. } = event {
+            assert_eq!(exit_meaning, None);
+            break;
+        }
+    }
+    assert_eq!(spawner.get_task_info().await.exit_meaning, None);
+}
@@ -0,0 +1,201 @@
+use std::time::{Duration, Instant};
+
+use tokio::sync::mpsc;
+
+use crate::tasks::{
+    async_tokio::spawner::TaskSpawner,
+    config::{StreamSource, TaskConfig},
+    event::{TaskEvent, TaskEventStopReason, TaskTerminateReason},
+};
+
+/// Traps SIGTERM, prints a cleanup message and exits with 0
+const TRAP_SCRIPT: &str =
+    "trap 'echo cleaning up; exit 0' TERM; echo ready; while :; do sleep 0.1; done";
+
+/// How a terminated task ended
+#[derive(Debug)]
+struct Stopped {
+    lines: Vec<String>,
+    exit_code: Option<i32>,
+    reason: TaskEventStopReason,
+    signal: Option<i32>,
+    /// Time from the termination request to `Stopped`
+    took: Duration,
+}
+
+/// Terminates the task once it printed "ready" and collects its stdout until stopped
+///
+/// Stderr is left out: the shell may report there that a child was terminated.
+async fn terminate_when_ready(config: TaskConfig) -> Stopped {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(100);
+    let mut spawner = TaskSpawner::new("graceful".to_string(), config);
+    spawner.start_direct(tx).await.unwrap();
+
+    let mut lines = vec![];
+    let mut terminated_at = None;
+    let stopped = tokio::time::timeout(Duration::from_secs(10), async {
+        while let Some(event) = rx.recv().await {
+            match event {
+                TaskEvent::Output {
+                    line,
+                    src: StreamSource::Stdout,
+                    ..
+                } => {
+                    if &*line == "ready" {
+                        terminated_at = Some(Instant::now());
+                        spawner
+                            .send_terminate_signal(TaskTerminateReason::UserRequested {
+                                message: None,
+                            })
+                            .await
+                            .unwrap();
+                    }
+                    lines.push(line.to_string());
+                }
+                TaskEvent::Stopped {
+                    exit_code, reason, ..
+                } => return (exit_code, reason),
+                _ => {}
+            }
+        }
+        panic!("Channel closed without a Stopped event");
+    })
+    .await
+    .expect("Task should stop within the timeout");
+
+    let audit = spawner.audit().await.unwrap();
+    Stopped {
+        lines,
+        exit_code: stopped.0,
+        reason: stopped.1,
+        signal: audit.signal,
+        took: terminated_at.unwrap().elapsed(),
+    }
+}
+
+#[tokio::test]
+async fn trapping_process_group_exits_cleanly() {
+    let config = TaskConfig::new("sh")
+        .args(["-c", TRAP_SCRIPT])
+        .graceful_shutdown_ms(5_000);
+
+    let stopped = terminate_when_ready(config).await;
+
+    assert_eq!(stopped.lines, ["ready", "cleaning up"]);
+    assert_eq!(stopped.exit_code, Some(0));
+    assert_eq!(
+        stopped.reason,
+        TaskEventStopReason::Terminated(TaskTerminateReason::UserRequested { message: None })
+    );
+    assert_eq!(stopped.signal, None);
+    assert!(stopped.took < Duration::from_secs(5), "{stopped:?}");
+}
+
+#[tokio::test]
+async fn trapping_process_without_group_exits_cleanly() {
+    let config = TaskConfig::new("sh")
+        .args(["-c", TRAP_SCRIPT])
+        .use_process_group(false)
+        .graceful_shutdown_ms(5_000);
+
+    let stopped = terminate_when_ready(config).await;
+
+    assert_eq!(stopped.lines, ["ready", "cleaning up"]);
+    assert_eq!(stopped.exit_code, Some(0));
+    assert_eq!(stopped.signal, None);
+}
+
+#[tokio::test]
+async fn untrapped_sigterm_is_reported() {
+    let config = TaskConfig::new("sh")
+        .args(["-c", "echo ready; while :; do sleep 0.1; done"])
+        .graceful_shutdown_ms(5_000);
+
+    let stopped = terminate_when_ready(config).await;
+
+    assert_eq!(stopped.exit_code, None);
+    assert_eq!(
+        stopped.reason,
+        TaskEventStopReason::Terminated(TaskTerminateReason::UserRequested { message: None })
+    );
+    assert_eq!(stopped.signal, Some(libc::SIGTERM));
+}
+
+#[tokio::test]
+async fn ignored_sigterm_escalates_to_kill_after_grace() {
+    let config = TaskConfig::new("sh")
+        .args([
+            "-c",
+            "trap '' TERM; echo ready; while :; do sleep 0.1; done",
+        ])
+        .graceful_shutdown_ms(300);
+
+    let stopped = terminate_when_ready(config).await;
+
+    assert_eq!(stopped.exit_code, None);
+    assert_eq!(
+        stopped.reason,
+        TaskEventStopReason::Terminated(TaskTerminateReason::UserRequested { message: None })
+    );
+    assert_eq!(stopped.signal, Some(libc::SIGKILL));
+    assert!(stopped.took >= Duration::from_millis(300), "{stopped:?}");
+}
+
+#[tokio::test]
+async fn without_grace_period_task_is_killed() {
+    let config = TaskConfig::new("sh").args(["-c", TRAP_SCRIPT]);
+
+    let stopped = terminate_when_ready(config).await;
+
+    assert_eq!(stopped.lines, ["ready"]);
+    assert_eq!(stopped.exit_code, None);
+    assert_eq!(stopped.signal, Some(libc::SIGKILL));
+}
+
+#[tokio::test]
+async fn without_grace_period_process_without_group_is_killed() {
+    let config = TaskConfig::new("sh")
+        .args(["-c", TRAP_SCRIPT])
+        .use_process_group(false);
+
+    let stopped = terminate_when_ready(config).await;
+
+    assert_eq!(stopped.lines, ["ready"]);
+    assert_eq!(stopped.exit_code, None);
+    assert_eq!(stopped.signal, Some(libc::SIGKILL));
+}
+
+#[tokio::test]
+async fn timeout_keeps_its_reason() {
+    let config = TaskConfig::new("sh")
+        .args(["-c", TRAP_SCRIPT])
+        .timeout_ms(300)
+        .graceful_shutdown_ms(5_000);
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(100);
+    let mut spawner = TaskSpawner::new("graceful".to_string(), config);
+    spawner.start_direct(tx).await.unwrap();
+
+    let mut lines = vec![];
+    while let Some(event) = rx.recv().await {
+        match event {
+            TaskEvent::Output {
+                line,
+                src: StreamSource::Stdout,
+                ..
+            } => lines.push(line.to_string()),
+            TaskEvent::Stopped {
+                exit_code, reason, ..
+            } => {
+                assert_eq!(lines, ["ready", "cleaning up"]);
+                assert_eq!(exit_code, Some(0));
+                assert_eq!(
+                    reason,
+                    TaskEventStopReason::Terminated(TaskTerminateReason::Timeout)
+                );
+                return;
+            }
+            _ => {}
+        }
+    }
+    panic!("Channel closed without a Stopped event");
+}
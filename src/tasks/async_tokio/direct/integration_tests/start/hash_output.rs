@@ -0,0 +1,81 @@
+use sha2::{Digest, Sha256};
+use tokio::sync::mpsc;
+
+use crate::tasks::{
+    async_tokio::spawner::TaskSpawner,
+    config::{OutputEncoding, TaskConfig},
+    event::TaskEvent,
+};
+
+/// Runs the task to completion and returns the digests reported in `Stopped`
+async fn run_digests(config: TaskConfig) -> (Option<String>, Option<String>) {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(100);
+    let mut spawner = TaskSpawner::new("hash_output".to_string(), config);
+    spawner.start_direct(tx).await.unwrap();
+
+    let mut digests = None;
+    while let Some(event) = rx.recv().await {
+        if let TaskEvent::Stopped {
+            stdout_sha256,
+            stderr_sha256,
+            ..
+        } = event
+        {
+            digests = Some((stdout_sha256, stderr_sha256));
+        }
+    }
+    digests.expect("Stopped event")
+}
+
+/// Hashes the stdout of `sh -c script`, run outside of the spawner
+fn independent_digest(script: &str) -> String {
+    let output = std::process::Command::new("sh")
+        .args(["-c", script])
+        .output()
+        .unwrap();
+    format!("{:x}", Sha256::digest(&output.stdout))
+}
+
+#[tokio::test]
+async fn stdout_digest_matches_independent_hash() {
+    let script = "printf 'line one\\r\\nline two\\n'; seq 1 2000; printf 'no newline'";
+    let config = TaskConfig::new("sh").args(["-c", script]).hash_output(true);
+
+    let (stdout, stderr) = run_digests(config).await;
+    assert_eq!(stdout, Some(independent_digest(script)));
+    assert_eq!(
+        stderr.as_deref(),
+        Some("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+    );
+}
+
+#[tokio::test]
+async fn digest_covers_raw_bytes_before_decoding() {
+    let script = "printf 'h\\000i\\000\\n\\000'";
+    let config = TaskConfig::new("sh")
+        .args(["-c", script])
+        .output_encoding(OutputEncoding::Utf16Le)
+        .hash_output(true);
+
+    let (stdout, _) = run_digests(config).await;
+    assert_eq!(stdout, Some(independent_digest(script)));
+}
+
+#[tokio::test]
+async fn uncaptured_stream_has_no_digest() {
+    let config = TaskConfig::new("sh")
+        .args(["-c", "echo out; echo err >&2"])
+        .capture_stdout(false)
+        .hash_output(true);
+
+    let (stdout, stderr) = run_digests(config).await;
+    assert_eq!(stdout, None);
+    assert_eq!(stderr, Some(format!("{:x}", Sha256::digest(b"err\n"))));
+}
+
+#[tokio::test]
+async fn no_digest_unless_enabled() {
+    let config = TaskConfig::new("sh").args(["-c", "echo out"]);
+
+    assert_eq!(run_digests(config).await, (None, None));
+}
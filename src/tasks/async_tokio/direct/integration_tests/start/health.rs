@@ -0,0 +1,177 @@
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU32, Ordering},
+    },
+    time::Duration,
+};
+
+use tokio::sync::mpsc;
+
+use crate::tasks::{
+    async_tokio::{
+        health::{HealthCheck, HealthCheckMode, TaskStatusInfo, UnhealthyAction},
+        spawner::{TaskSpawner, TerminateOutcome},
+    },
+    config::TaskConfig,
+    event::{TaskEvent, TaskEventStopReason, TaskTerminateReason},
+    state::TaskState,
+};
+
+/// Becomes ready, then goes silent
+const SILENT_AFTER_READY: &str = "echo ready; sleep 10";
+
+/// Runs `script` with a ready indicator and the given health check
+fn spawner(script: &str, check: HealthCheck) -> TaskSpawner {
+    let config = TaskConfig::new("sh")
+        .args(["-c", script])
+        .ready_indicator("ready");
+    TaskSpawner::new("health".to_string(), config).set_health_check(check)
+}
+
+/// Receives events until `TaskEvent::Unhealthy`, returning its consecutive failures
+async fn wait_for_unhealthy(rx: &mut mpsc::Receiver<TaskEvent>) -> u32 {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while let Some(event) = rx.recv().await {
+            match event {
+                TaskEvent::Unhealthy {
+                    consecutive_failures,
+                    ..
+                } => return consecutive_failures,
+                TaskEvent::Stopped { reason, .. } => panic!("Stopped before Unhealthy: {reason:?}"),
+                _ => {}
+            }
+        }
+        panic!("Event channel closed before Unhealthy");
+    })
+    .await
+    .expect("Task should become unhealthy within the timeout")
+}
+
+/// Receives events until the task has stopped, returning the number of
+/// `TaskEvent::Unhealthy` received and the stop reason
+async fn wait_for_stop(rx: &mut mpsc::Receiver<TaskEvent>) -> (usize, TaskEventStopReason) {
+    let mut unhealthy = 0;
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while let Some(event) = rx.recv().await {
+            match event {
+                TaskEvent::Unhealthy { .. } => unhealthy += 1,
+                TaskEvent::Stopped { reason, .. } => return (unhealthy, reason),
+                _ => {}
+            }
+        }
+        panic!("Event channel closed before Stopped");
+    })
+    .await
+    .expect("Task should stop within the timeout")
+}
+
+#[tokio::test]
+async fn silent_ready_task_is_reported_unhealthy() {
+    let check = HealthCheck::new(100, HealthCheckMode::OutputActivity).failure_threshold(2);
+    let mut spawner = spawner(SILENT_AFTER_READY, check);
+    let (tx, mut rx) = mpsc::channel(100);
+    spawner.start_direct(tx).await.unwrap();
+
+    assert_eq!(wait_for_unhealthy(&mut rx).await, 2);
+    assert!(!spawner.is_healthy());
+    // Reported only: the task keeps running
+    assert_eq!(spawner.get_state().await, TaskState::Ready);
+
+    spawner
+        .send_terminate_signal(TaskTerminateReason::UserRequested { message: None })
+        .await
+        .unwrap();
+    let (unhealthy, reason) = wait_for_stop(&mut rx).await;
+    assert_eq!(unhealthy, 0);
+    assert_eq!(
+        reason,
+        TaskEventStopReason::Terminated(TaskTerminateReason::UserRequested { message: None })
+    );
+}
+
+#[tokio::test]
+async fn unhealthy_task_is_terminated() {
+    let check = HealthCheck::new(100, HealthCheckMode::OutputActivity)
+        .failure_threshold(2)
+        .unhealthy_action(UnhealthyAction::Terminate);
+    let mut spawner = spawner(SILENT_AFTER_READY, check);
+    let (tx, mut rx) = mpsc::channel(100);
+    spawner.start_direct(tx).await.unwrap();
+
+    let (unhealthy, reason) = wait_for_stop(&mut rx).await;
+    assert_eq!(unhealthy, 1);
+    assert_eq!(
+        reason,
+        TaskEventStopReason::Terminated(TaskTerminateReason::Unhealthy)
+    );
+    assert!(!spawner.is_healthy());
+    assert_eq!(
+        spawner
+            .send_terminate_signal(TaskTerminateReason::UserRequested { message: None })
+            .await
+            .unwrap(),
+        TerminateOutcome::AlreadyFinished
+    );
+}
+
+#[tokio::test]
+async fn active_task_stays_healthy() {
+    let check = HealthCheck::new(200, HealthCheckMode::OutputActivity)
+        .failure_threshold(1)
+        .unhealthy_action(UnhealthyAction::Terminate);
+    let mut spawner = spawner(
+        "echo ready; for i in 1 2 3 4 5 6 7 8 9 10 11 12; do sleep 0.05; echo tick; done",
+        check,
+    );
+    let (tx, mut rx) = mpsc::channel(100);
+    spawner.start_direct(tx).await.unwrap();
+
+    let (unhealthy, reason) = wait_for_stop(&mut rx).await;
+    assert_eq!(unhealthy, 0);
+    assert_eq!(reason, TaskEventStopReason::Finished);
+    assert!(spawner.is_healthy());
+}
+
+#[tokio::test]
+async fn custom_probe_recovers_after_failures() {
+    let probes = Arc::new(AtomicU32::new(0));
+    let last_status = Arc::new(Mutex::new(None));
+    let probe = {
+        let probes = probes.clone();
+        let last_status = last_status.clone();
+        HealthCheckMode::Custom(Arc::new(move |status: &TaskStatusInfo| {
+            *last_status.lock().unwrap() = Some(status.clone());
+            // Fails twice, then passes
+            probes.fetch_add(1, Ordering::Relaxed) >= 2
+        }))
+    };
+    let mut spawner = spawner(
+        SILENT_AFTER_READY,
+        HealthCheck::new(100, probe).failure_threshold(2),
+    );
+    let (tx, mut rx) = mpsc::channel(100);
+    let pid = spawner.start_direct(tx).await.unwrap();
+
+    assert_eq!(wait_for_unhealthy(&mut rx).await, 2);
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while !spawner.is_healthy() {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .expect("Task should be healthy again once the probe passes");
+
+    let status = last_status.lock().unwrap().clone().unwrap();
+    assert_eq!(status.process_id, Some(pid));
+    assert_eq!(status.state, TaskState::Ready);
+    assert_eq!(status.output_bytes, "ready".len() as u64);
+    assert!(status.uptime >= Duration::from_millis(300), "{status:?}");
+
+    spawner
+        .send_terminate_signal(TaskTerminateReason::UserRequested { message: None })
+        .await
+        .unwrap();
+    let (unhealthy, _) = wait_for_stop(&mut rx).await;
+    assert_eq!(unhealthy, 0);
+}
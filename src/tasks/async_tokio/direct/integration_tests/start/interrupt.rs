@@ -0,0 +1,108 @@
+use tokio::sync::mpsc;
+
+use crate::tasks::config::TaskConfig;
+use crate::tasks::{
+    async_tokio::spawner::TaskSpawner,
+    error::TaskError,
+    event::{TaskEvent, TaskEventStopReason},
+};
+
+/// Traps SIGINT, prints a cleanup message and exits with 130
+const TRAP_SCRIPT: &str =
+    "trap 'echo cleaning up; exit 130' INT; echo ready; while :; do sleep 0.1; done";
+
+/// Interrupts the task once it printed "ready" and collects its output until stopped
+async fn interrupt_when_ready(config: TaskConfig) -> (Vec<String>, Option<i32>, TaskEventStopReason) {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(100);
+    let mut spawner = TaskSpawner::new("interrupt".to_string(), config);
+    spawner.start_direct(tx).await.unwrap();
+
+    let mut lines = vec![];
+    while let Some(event) = rx.recv().await {
+        match event {
+            TaskEvent::Output { line, .. } => {
+                if &*line == "ready" {
+                    spawner.send_interrupt_signal().await.unwrap();
+                }
+                lines.push(line.to_string());
+            }
+            TaskEvent::Stopped {
+                exit_code, reason, ..
+            } => return (lines, exit_code, reason),
+            _ => {}
+        }
+    }
+    panic!("Channel closed without a Stopped event");
+}
+
+#[tokio::test]
+async fn interrupt_lets_process_group_clean_up() {
+    let config = TaskConfig::new("sh").args(["-c", TRAP_SCRIPT]);
+
+    let (lines, exit_code, reason) = interrupt_when_ready(config).await;
+
+    assert_eq!(lines, ["ready", "cleaning up"]);
+    assert_eq!(exit_code, Some(130));
+    assert_eq!(reason, TaskEventStopReason::Finished);
+}
+
+#[tokio::test]
+async fn interrupt_without_process_group_signals_the_process() {
+    let config = TaskConfig::new("sh")
+        .args(["-c", TRAP_SCRIPT])
+        .use_process_group(false);
+
+    let (lines, exit_code, reason) = interrupt_when_ready(config).await;
+
+    assert_eq!(lines, ["ready", "cleaning up"]);
+    assert_eq!(exit_code, Some(130));
+    assert_eq!(reason, TaskEventStopReason::Finished);
+}
+
+#[tokio::test]
+async fn interrupt_before_start_is_an_error() {
+    let spawner = TaskSpawner::new("interrupt".to_string(), TaskConfig::new("sh"));
+
+    let result = spawner.send_interrupt_signal().await;
+
+    assert!(matches!(result, Err(TaskError::Handle(_))));
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn interrupt_is_not_sent_to_reused_process_id() {
+    use crate::tasks::{async_tokio::process_start, event::TaskTerminateReason};
+
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(100);
+    let config = TaskConfig::new("sh").args(["-c", TRAP_SCRIPT]);
+    let mut spawner = TaskSpawner::new("interrupt".to_string(), config);
+    spawner.start_direct(tx).await.unwrap();
+    // As if the process exited and its ID went to one started at another time
+    spawner.process_start_time = process_start::start_time(1);
+
+    let mut lines = vec![];
+    while let Some(event) = rx.recv().await {
+        match event {
+            TaskEvent::Output { line, .. } => {
+                if &*line == "ready" {
+                    spawner.send_interrupt_signal().await.unwrap();
+                    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+                    spawner
+                        .send_terminate_signal(TaskTerminateReason::Cleanup { message: None })
+                        .await
+                        .unwrap();
+                }
+                lines.push(line.to_string());
+            }
+            TaskEvent::Stopped { reason, .. } => {
+                assert_eq!(
+                    reason,
+                    TaskEventStopReason::Terminated(TaskTerminateReason::Cleanup { message: None })
+                );
+                break;
+            }
+            _ => {}
+        }
+    }
+    assert_eq!(lines, ["ready"]);
+}
@@ -0,0 +1,94 @@
+//! Rough latency bounds, catching regressions far beyond noise.
+//!
+//! `benches/task_lifecycle.rs` measures the same paths precisely.
+
+use std::time::{Duration, Instant};
+
+use tokio::sync::mpsc;
+
+use crate::tasks::async_tokio::direct::integration_tests::support::commands;
+use crate::tasks::{
+    async_tokio::spawner::TaskSpawner,
+    event::{TaskEvent, TaskEventStopReason, TaskTerminateReason},
+};
+
+const BOUND: Duration = Duration::from_secs(2);
+
+#[tokio::test]
+async fn started_is_sent_promptly() {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(64);
+    let mut spawner = TaskSpawner::new("trivial".to_string(), commands::trivial());
+
+    let start = Instant::now();
+    spawner.start_direct(tx).await.unwrap();
+    let event = rx.recv().await;
+    let elapsed = start.elapsed();
+
+    assert!(matches!(event, Some(TaskEvent::Started { .. })));
+    assert!(elapsed < BOUND, "Started took {elapsed:?}");
+}
+
+#[tokio::test]
+async fn launch_after_prepare_is_prompt() {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(64);
+    let mut spawner = TaskSpawner::new("trivial".to_string(), commands::trivial());
+    let prepared = spawner.prepare().await.unwrap();
+
+    let start = Instant::now();
+    prepared.launch(tx).await.unwrap();
+    let elapsed = start.elapsed();
+
+    assert!(matches!(rx.recv().await, Some(TaskEvent::Started { .. })));
+    assert!(elapsed < BOUND, "Launch took {elapsed:?}");
+}
+
+#[tokio::test]
+async fn terminate_stops_sleeping_child_promptly() {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(64);
+    let mut spawner = TaskSpawner::new("sleep".to_string(), commands::sleep(30));
+    spawner.start_direct(tx).await.unwrap();
+    assert!(matches!(rx.recv().await, Some(TaskEvent::Started { .. })));
+
+    let start = Instant::now();
+    spawner
+        .send_terminate_signal(TaskTerminateReason::UserRequested { message: None })
+        .await
+        .unwrap();
+    let mut reason = None;
+    while let Some(event) = rx.recv().await {
+        if let TaskEvent::Stopped { reason: r, .. } = event {
+            reason = Some(r);
+            break;
+        }
+    }
+    let elapsed = start.elapsed();
+
+    assert_eq!(
+        reason,
+        Some(TaskEventStopReason::Terminated(
+            TaskTerminateReason::UserRequested { message: None }
+        ))
+    );
+    assert!(elapsed < BOUND, "Termination took {elapsed:?}");
+}
+
+#[tokio::test]
+async fn lines_are_delivered_at_a_reasonable_rate() {
+    const LINES: u64 = 100_000;
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(1024);
+    let mut spawner = TaskSpawner::new("lines".to_string(), commands::lines(LINES));
+
+    let start = Instant::now();
+    spawner.start_direct(tx).await.unwrap();
+    let mut lines = 0;
+    while let Some(event) = rx.recv().await {
+        if matches!(event, TaskEvent::Output { .. }) {
+            lines += 1;
+        }
+    }
+    let elapsed = start.elapsed();
+
+    assert_eq!(lines, LINES);
+    // Generous for debug builds and slow CI machines
+    assert!(elapsed < Duration::from_secs(20), "{LINES} lines took {elapsed:?}");
+}
@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use crate::tasks::{
+    async_tokio::spawner::TaskSpawner,
+    config::TaskConfig,
+    event::{TaskEvent, TaskEventStopReason},
+};
+
+/// Prints a 10 MB line without a newline until its end, then a short line
+fn huge_line() -> TaskConfig {
+    TaskConfig::new("sh").args([
+        "-c",
+        "head -c 10485760 /dev/zero | tr '\\0' x; echo; echo after",
+    ])
+}
+
+/// Runs the task to completion and returns its output lines with their truncation flag
+async fn run_lines(config: TaskConfig) -> Vec<(String, bool)> {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(100);
+    let mut spawner = TaskSpawner::new("long_lines".to_string(), config);
+    spawner.start_direct(tx).await.unwrap();
+
+    tokio::time::timeout(Duration::from_secs(30), async {
+        let mut lines = vec![];
+        while let Some(event) = rx.recv().await {
+            match event {
+                TaskEvent::Output {
+                    line, truncated, ..
+                } => lines.push((line, truncated)),
+                TaskEvent::Stopped { reason, .. } => {
+                    assert_eq!(reason, TaskEventStopReason::Finished);
+                }
+                _ => {}
+            }
+        }
+        lines
+    })
+    .await
+    .expect("Task should complete within the timeout")
+}
+
+#[tokio::test]
+async fn huge_line_is_truncated_to_the_default() {
+    let lines = run_lines(huge_line()).await;
+
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0].0.len(), 1024 * 1024);
+    assert!(lines[0].0.bytes().all(|b| b == b'x'));
+    assert!(lines[0].1);
+    assert_eq!(lines[1], ("after".to_string(), false));
+}
+
+#[tokio::test]
+async fn huge_line_is_truncated_to_the_configured_length() {
+    let lines = run_lines(huge_line().max_output_line_bytes(1000)).await;
+
+    assert_eq!(
+        lines,
+        [("x".repeat(1000), true), ("after".to_string(), false)]
+    );
+}
+
+#[tokio::test]
+async fn output_bytes_count_the_discarded_end() {
+    let config = huge_line().max_output_line_bytes(1000);
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(100);
+    let mut spawner = TaskSpawner::new("long_lines".to_string(), config);
+    spawner.start_direct(tx).await.unwrap();
+    while rx.recv().await.is_some() {}
+
+    let audit = spawner.audit().await.unwrap();
+    assert_eq!(audit.output_bytes, 10 * 1024 * 1024 + "after".len() as u64);
+}
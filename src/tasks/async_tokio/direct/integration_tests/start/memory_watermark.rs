@@ -0,0 +1,65 @@
+use crate::tasks::async_tokio::direct::integration_tests::support::{collect_events, commands};
+use crate::tasks::{
+    config::TaskConfig,
+    event::{TaskEvent, TaskEventStopReason},
+};
+
+const WATERMARK: u64 = 16 << 20;
+
+/// Holds 30 MB in a shell variable, frees it, then holds it again
+fn allocate_twice() -> TaskConfig {
+    let hold = r#"x=$(head -c 30000000 /dev/zero | tr "\0" a); sleep 0.4"#;
+    TaskConfig::new("sh")
+        .args([
+            "-c".to_string(),
+            format!("{hold}; unset x; sleep 0.4; {hold}"),
+        ])
+        .memory_watermark_bytes(WATERMARK)
+        .sample_interval_ms(20)
+}
+
+fn crossings(events: &[TaskEvent]) -> Vec<u64> {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            TaskEvent::MemoryWatermarkExceeded {
+                rss_bytes,
+                watermark_bytes,
+                ..
+            } => {
+                assert_eq!(*watermark_bytes, WATERMARK);
+                Some(*rss_bytes)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn reports_each_crossing_once_without_terminating() {
+    for use_process_group in [true, false] {
+        let events = collect_events(allocate_twice().use_process_group(use_process_group)).await;
+
+        let crossings = crossings(&events);
+        assert_eq!(crossings.len(), 2, "{events:?}");
+        assert!(crossings.iter().all(|rss| *rss >= WATERMARK));
+        let Some(TaskEvent::Stopped {
+            reason, exit_code, ..
+        }) = events.last()
+        else {
+            panic!("Stopped should be the last event: {events:?}");
+        };
+        assert_eq!(*reason, TaskEventStopReason::Finished);
+        assert_eq!(*exit_code, Some(0));
+    }
+}
+
+#[tokio::test]
+async fn usage_below_watermark_is_not_reported() {
+    let config = commands::sleep(1)
+        .memory_watermark_bytes(1 << 30)
+        .sample_interval_ms(10);
+
+    let events = collect_events(config).await;
+    assert!(crossings(&events).is_empty(), "{events:?}");
+}
@@ -1,5 +1,80 @@
+#[cfg(unix)]
+mod audit;
+mod backpressure;
 mod basic;
+mod cache;
+#[cfg(target_os = "linux")]
+mod cpu_affinity;
+#[cfg(unix)]
+mod channel_closed;
+#[cfg(unix)]
+mod cleanup_on_drop;
+mod crash_loop;
+mod customize_command;
+mod dedupe;
+mod drain;
+mod encoding;
+#[cfg(unix)]
+mod env_snapshot;
+mod exit_status;
+#[cfg(unix)]
+mod graceful_shutdown;
+#[cfg(all(unix, feature = "sha2"))]
+mod hash_output;
+#[cfg(unix)]
+mod health;
+#[cfg(unix)]
+mod interrupt;
+mod latency;
+#[cfg(unix)]
+mod long_lines;
+#[cfg(target_os = "linux")]
+mod memory_watermark;
+mod on_spawned;
+#[cfg(unix)]
+mod output_mode;
+mod output_tail;
+mod output_triggers;
+#[cfg(target_os = "linux")]
+mod parent_death;
+#[cfg(unix)]
+mod pause;
+#[cfg(unix)]
+mod plan;
+#[cfg(unix)]
+mod pre_exec;
+mod prepare;
+#[cfg(target_os = "linux")]
+mod priority;
 mod process_id;
+#[cfg(target_os = "linux")]
+mod process_tree;
+mod quiescence;
 mod ready;
+#[cfg(unix)]
+mod run_to_completion;
+mod running_long;
+mod runtime;
+mod spawn_diagnosis;
+#[cfg(unix)]
+mod spawn_retry;
+mod startup_failure;
+mod startup_output;
+mod stdio_inherit;
 mod stdin;
+mod stdin_script;
+mod stream;
+mod streams;
+#[cfg(unix)]
+mod structured_concurrency;
+mod task_group;
+mod temp_dir;
+#[cfg(unix)]
+mod terminate;
+mod throttle;
+#[cfg(unix)]
+mod timestamps;
 mod timeout;
+#[cfg(unix)]
+mod transform;
+mod working_dir;
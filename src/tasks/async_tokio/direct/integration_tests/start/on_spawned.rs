@@ -0,0 +1,127 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, AtomicU32, Ordering},
+};
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use crate::tasks::async_tokio::direct::integration_tests::support::commands;
+use crate::tasks::{
+    async_tokio::{hook::OnSpawnedHook, spawner::TaskSpawner},
+    error::TaskError,
+    event::{TaskEvent, TaskEventStopReason},
+    state::TaskState,
+};
+
+#[tokio::test]
+async fn hook_runs_before_started_and_output() {
+    let hook_pid = Arc::new(AtomicU32::new(0));
+    let hook_done = Arc::new(AtomicBool::new(false));
+    let hook = {
+        let hook_pid = hook_pid.clone();
+        let hook_done = hook_done.clone();
+        OnSpawnedHook::new(move |pid| {
+            let hook_pid = hook_pid.clone();
+            let hook_done = hook_done.clone();
+            async move {
+                hook_pid.store(pid, Ordering::SeqCst);
+                // Gives the child time to write its output before the hook returns
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                hook_done.store(true, Ordering::SeqCst);
+                Ok(())
+            }
+        })
+    };
+
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(100);
+    let mut spawner =
+        TaskSpawner::new("on_spawned".to_string(), commands::lines(3)).set_on_spawned(hook);
+    let pid = spawner.start_direct(tx).await.unwrap();
+    assert_eq!(hook_pid.load(Ordering::SeqCst), pid);
+
+    let mut events = vec![];
+    while let Some(event) = rx.recv().await {
+        assert!(
+            hook_done.load(Ordering::SeqCst),
+            "{event:?} sent before the hook finished"
+        );
+        events.push(event);
+    }
+    assert!(matches!(events.first(), Some(TaskEvent::Started { .. })));
+    let output = events
+        .iter()
+        .filter(|event| matches!(event, TaskEvent::Output { .. }))
+        .count();
+    assert_eq!(output, 3, "{events:?}");
+    assert!(matches!(
+        events.last(),
+        Some(TaskEvent::Stopped {
+            reason: TaskEventStopReason::Finished,
+            ..
+        })
+    ));
+}
+
+#[tokio::test]
+async fn hook_failure_kills_child_and_reports_error_then_stopped() {
+    let hook_pid = Arc::new(AtomicU32::new(0));
+    let hook = {
+        let hook_pid = hook_pid.clone();
+        OnSpawnedHook::new(move |pid| {
+            hook_pid.store(pid, Ordering::SeqCst);
+            async { Err(TaskError::Handle("registration refused".to_string())) }
+        })
+    };
+
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(100);
+    let mut spawner =
+        TaskSpawner::new("on_spawned".to_string(), commands::sleep(10)).set_on_spawned(hook);
+    let started = std::time::Instant::now();
+    let result = spawner.start_direct(tx).await;
+
+    assert_eq!(
+        result,
+        Err(TaskError::Handle("registration refused".to_string()))
+    );
+    assert!(started.elapsed() < Duration::from_secs(5));
+
+    let mut events = vec![];
+    while let Some(event) = rx.recv().await {
+        events.push(event);
+    }
+    assert_eq!(events.len(), 2, "{events:?}");
+    assert!(matches!(
+        &events[0],
+        TaskEvent::Error {
+            error: TaskError::Handle(msg),
+            ..
+        } if msg == "registration refused"
+    ));
+    match &events[1] {
+        TaskEvent::Stopped {
+            exit_code, reason, ..
+        } => {
+            assert_eq!(*exit_code, None);
+            assert!(
+                matches!(reason, TaskEventStopReason::Error(msg) if msg.contains("registration refused"))
+            );
+        }
+        other => panic!("Expected Stopped, got {other:?}"),
+    }
+
+    assert_eq!(spawner.get_state().await, TaskState::Finished);
+    assert_eq!(spawner.get_process_id().await, None);
+
+    #[cfg(unix)]
+    {
+        use nix::sys::signal::kill;
+        use nix::unistd::Pid;
+
+        let pid = hook_pid.load(Ordering::SeqCst);
+        assert!(
+            kill(Pid::from_raw(pid.cast_signed()), None).is_err(),
+            "Child should have been killed and reaped"
+        );
+    }
+}
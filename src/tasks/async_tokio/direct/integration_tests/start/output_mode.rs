@@ -0,0 +1,72 @@
+use tokio::sync::mpsc;
+
+use crate::tasks::{
+    async_tokio::spawner::TaskSpawner,
+    config::{OutputMode, StreamSource, TaskConfig},
+    event::TaskEvent,
+};
+
+/// Runs the task to completion and returns its output chunks
+///
+/// Fails if a line is sent as `TaskEvent::Output`.
+async fn run_chunks(config: TaskConfig) -> Vec<(Vec<u8>, StreamSource)> {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(100);
+    let mut spawner = TaskSpawner::new("output_mode".to_string(), config);
+    spawner.start_direct(tx).await.unwrap();
+
+    let mut chunks = vec![];
+    while let Some(event) = rx.recv().await {
+        match event {
+            TaskEvent::OutputChunk { data, src, .. } => chunks.push((data, src)),
+            TaskEvent::Output { line, .. } => panic!("Unexpected output line {line:?}"),
+            _ => {}
+        }
+    }
+    chunks
+}
+
+fn concat(chunks: &[(Vec<u8>, StreamSource)], stream: &StreamSource) -> Vec<u8> {
+    chunks
+        .iter()
+        .filter(|(_, src)| src == stream)
+        .flat_map(|(data, _)| data.iter().copied())
+        .collect()
+}
+
+#[tokio::test]
+async fn binary_output_is_sent_unchanged() {
+    // NUL, invalid UTF-8, CRLF and no trailing newline
+    let bytes = [0x00, 0xff, 0xfe, b'a', b'\r', b'\n', 0xc3, 0x28, b'z'];
+    let format: String = bytes.iter().map(|b| format!("\\{b:03o}")).collect();
+    let config = TaskConfig::new("printf")
+        .args([format])
+        .output_mode(OutputMode::Bytes);
+
+    let chunks = run_chunks(config).await;
+    assert!(chunks.iter().all(|(data, _)| !data.is_empty()));
+    assert_eq!(concat(&chunks, &StreamSource::Stdout), bytes);
+}
+
+#[tokio::test]
+async fn chunks_are_bounded_by_the_buffer_size() {
+    let config = TaskConfig::new("head")
+        .args(["-c", "100000", "/dev/zero"])
+        .output_buffer_bytes(4096)
+        .output_mode(OutputMode::Bytes);
+
+    let chunks = run_chunks(config).await;
+    assert!(chunks.len() >= 100_000 / 4096);
+    assert!(chunks.iter().all(|(data, _)| data.len() <= 4096));
+    assert_eq!(concat(&chunks, &StreamSource::Stdout), vec![0; 100_000]);
+}
+
+#[tokio::test]
+async fn stderr_chunks_name_their_stream() {
+    let config = TaskConfig::new("sh")
+        .args(["-c", "printf out; printf err >&2"])
+        .output_mode(OutputMode::Bytes);
+
+    let chunks = run_chunks(config).await;
+    assert_eq!(concat(&chunks, &StreamSource::Stdout), b"out");
+    assert_eq!(concat(&chunks, &StreamSource::Stderr), b"err");
+}
@@ -0,0 +1,41 @@
+use tokio::sync::mpsc;
+
+use crate::tasks::async_tokio::direct::integration_tests::support::commands;
+use crate::tasks::{async_tokio::spawner::TaskSpawner, config::StreamSource, event::TaskEvent};
+
+#[tokio::test]
+async fn tail_keeps_last_lines_after_exit() {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(1024);
+    let config = commands::lines(100).retain_output_lines(10);
+    let mut spawner = TaskSpawner::new("tail_task".to_string(), config);
+    spawner.start_direct(tx).await.unwrap();
+    // The consumer discards every event
+    while rx.recv().await.is_some() {}
+
+    let tail = spawner.tail(20);
+    let lines: Vec<&str> = tail.iter().map(|retained| retained.line.trim()).collect();
+    let expected: Vec<String> = (91..=100).map(|n| n.to_string()).collect();
+    assert_eq!(lines, expected);
+    assert!(
+        tail.iter()
+            .all(|retained| retained.src == StreamSource::Stdout)
+    );
+    assert!(tail.is_sorted_by_key(|retained| retained.timestamp));
+
+    let last: Vec<String> = spawner
+        .tail(3)
+        .into_iter()
+        .map(|retained| retained.line.trim().to_string())
+        .collect();
+    assert_eq!(last, ["98", "99", "100"]);
+}
+
+#[tokio::test]
+async fn tail_is_empty_without_retention() {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(1024);
+    let mut spawner = TaskSpawner::new("no_tail_task".to_string(), commands::lines(10));
+    spawner.start_direct(tx).await.unwrap();
+    while rx.recv().await.is_some() {}
+
+    assert!(spawner.tail(10).is_empty());
+}
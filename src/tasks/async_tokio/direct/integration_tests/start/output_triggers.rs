@@ -0,0 +1,247 @@
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use crate::tasks::{
+    async_tokio::spawner::TaskSpawner,
+    config::{OutputTrigger, ReadyMatchMode, StreamSource, TaskConfig, TriggerAction},
+    event::{TaskEvent, TaskEventStopReason, TaskTerminateReason},
+};
+
+/// Runs a shell script to completion and returns all its events
+async fn run(script: &str, config: impl FnOnce(TaskConfig) -> TaskConfig) -> Vec<TaskEvent> {
+    #[cfg(windows)]
+    let base = TaskConfig::new("cmd").args(["/C", script]);
+    #[cfg(unix)]
+    let base = TaskConfig::new("sh").args(["-c", script]);
+
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(100);
+    let mut spawner = TaskSpawner::new("triggers".to_string(), config(base));
+    spawner.start_direct(tx).await.unwrap();
+
+    let mut events = vec![];
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while let Some(event) = rx.recv().await {
+            events.push(event);
+        }
+    })
+    .await
+    .expect("Task should stop within the timeout");
+    events
+}
+
+#[tokio::test]
+async fn ready_on_stdout_and_failure_on_stderr() {
+    #[cfg(windows)]
+    let script = "echo FATAL only on stdout& echo listening& ping -n 2 127.0.0.1 >nul& echo FATAL: secret=hunter2 1>&2& ping -n 30 127.0.0.1 >nul";
+    #[cfg(unix)]
+    let script = "echo FATAL only on stdout; echo listening; sleep 0.2; echo FATAL: secret=hunter2 >&2; sleep 30";
+
+    let events = run(script, |config| {
+        config
+            .output_trigger(OutputTrigger::new(
+                "listening",
+                StreamSource::Stdout,
+                TriggerAction::Ready,
+            ))
+            .output_trigger(OutputTrigger::new(
+                "FATAL",
+                StreamSource::Stderr,
+                TriggerAction::FailTask,
+            ))
+    })
+    .await;
+
+    assert!(
+        events
+            .iter()
+            .any(|event| matches!(event, TaskEvent::Ready { .. }))
+    );
+    match events.last() {
+        Some(TaskEvent::Stopped {
+            exit_code,
+            reason: TaskEventStopReason::Error(message),
+            was_ready,
+            ..
+        }) => {
+            assert_eq!(*exit_code, None);
+            assert!(*was_ready);
+            assert_eq!(
+                message,
+                "Output matched failure pattern \"FATAL\" on Stderr"
+            );
+        }
+        other => panic!("Expected an Error stop, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn completion_indicator_stops_a_task_that_does_not_exit() {
+    #[cfg(windows)]
+    let script = "echo working& echo ALL DONE& ping -n 3600 127.0.0.1 >nul";
+    #[cfg(unix)]
+    let script = "echo working; echo ALL DONE; sleep 3600";
+
+    for (success, expected_code) in [(true, Some(0)), (false, None)] {
+        let events = run(script, |config| {
+            config
+                .exit_on_output_match("ALL DONE")
+                .completion_success(success)
+        })
+        .await;
+
+        let lines: Vec<&str> = events
+            .iter()
+            .filter_map(|event| match event {
+                TaskEvent::Output { line, .. } => Some(line.trim()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(lines, ["working", "ALL DONE"]);
+        match events.last() {
+            Some(TaskEvent::Stopped {
+                exit_code,
+                reason: TaskEventStopReason::Terminated(TaskTerminateReason::CompletionIndicator),
+                ..
+            }) => assert_eq!(*exit_code, expected_code, "success: {success}"),
+            other => panic!("Expected a CompletionIndicator stop, got {other:?}"),
+        }
+    }
+}
+
+#[tokio::test]
+async fn emit_event_follows_each_matching_line() {
+    #[cfg(windows)]
+    let script = "echo starting& echo config reloaded& echo serving& echo config reloaded";
+    #[cfg(unix)]
+    let script = "echo starting; echo config reloaded; echo serving; echo config reloaded";
+
+    let events = run(script, |config| {
+        config.output_trigger(OutputTrigger::new(
+            "reloaded",
+            StreamSource::Stdout,
+            TriggerAction::EmitEvent("reload".to_string()),
+        ))
+    })
+    .await;
+
+    let sequence: Vec<String> = events
+        .iter()
+        .filter_map(|event| match event {
+            TaskEvent::Output { line, .. } => Some(line.trim_end().to_string()),
+            TaskEvent::Triggered {
+                name, line, src, ..
+            } => {
+                assert_eq!(*src, StreamSource::Stdout);
+                Some(format!("{name}: {}", line.trim_end()))
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        sequence,
+        [
+            "starting",
+            "config reloaded",
+            "reload: config reloaded",
+            "serving",
+            "config reloaded",
+            "reload: config reloaded",
+        ]
+    );
+    assert!(matches!(
+        events.last(),
+        Some(TaskEvent::Stopped {
+            exit_code: Some(0),
+            reason: TaskEventStopReason::Finished,
+            ..
+        })
+    ));
+}
+
+#[tokio::test]
+async fn legacy_ready_indicator_combines_with_triggers() {
+    #[cfg(windows)]
+    let script = "echo server ready 1>&2";
+    #[cfg(unix)]
+    let script = "echo server ready >&2";
+
+    let events = run(script, |config| {
+        config
+            .ready_indicator("ready")
+            .ready_indicator_source(StreamSource::Stderr)
+            .output_trigger(OutputTrigger::new(
+                "server",
+                StreamSource::Stderr,
+                TriggerAction::EmitEvent("server".to_string()),
+            ))
+    })
+    .await;
+
+    let ready = events
+        .iter()
+        .filter(|event| matches!(event, TaskEvent::Ready { .. }))
+        .count();
+    let triggered = events
+        .iter()
+        .filter(|event| matches!(event, TaskEvent::Triggered { name, .. } if name == "server"))
+        .count();
+    assert_eq!((ready, triggered), (1, 1));
+}
+
+#[tokio::test]
+async fn anchored_match_ignores_lines_that_only_contain_the_indicator() {
+    #[cfg(windows)]
+    let script = "echo config: wait_for=Listening& echo Listening on 8080";
+    #[cfg(unix)]
+    let script = "echo config: wait_for=Listening; echo Listening on 8080";
+
+    for (mode, ready_on) in [
+        (ReadyMatchMode::Contains, Some("config: wait_for=Listening")),
+        (ReadyMatchMode::StartsWith, Some("Listening on 8080")),
+        (ReadyMatchMode::ExactLine, None),
+    ] {
+        let events = run(script, |config| {
+            config.ready_indicator("Listening").ready_match_mode(mode)
+        })
+        .await;
+
+        // The line read right before Ready is the one that matched
+        let ready_at = events
+            .iter()
+            .position(|event| matches!(event, TaskEvent::Ready { .. }));
+        let matched = ready_at.and_then(|index| match &events[index - 1] {
+            TaskEvent::Output { line, .. } => Some(line.trim_end()),
+            _ => None,
+        });
+        assert_eq!(matched, ready_on, "{mode:?}");
+    }
+}
+
+#[tokio::test]
+async fn anchored_match_applies_to_failure_triggers() {
+    #[cfg(windows)]
+    let script = "echo warning: FATAL errors are logged 1>&2";
+    #[cfg(unix)]
+    let script = "echo warning: FATAL errors are logged >&2";
+
+    let events = run(script, |config| {
+        config
+            .output_trigger(OutputTrigger::new(
+                "FATAL",
+                StreamSource::Stderr,
+                TriggerAction::FailTask,
+            ))
+            .ready_match_mode(ReadyMatchMode::StartsWith)
+    })
+    .await;
+
+    match events.last() {
+        Some(TaskEvent::Stopped {
+            exit_code,
+            reason: TaskEventStopReason::Finished,
+            ..
+        }) => assert_eq!(*exit_code, Some(0)),
+        other => panic!("Expected a Finished stop, got {other:?}"),
+    }
+}
@@ -0,0 +1,69 @@
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use crate::tasks::{async_tokio::spawner::TaskSpawner, config::TaskConfig, event::TaskEvent};
+
+/// Returns `true` while the process exists and is not a zombie
+fn is_running(pid: u32) -> bool {
+    match std::fs::read_to_string(format!("/proc/{pid}/stat")) {
+        Ok(stat) => stat
+            .rsplit(") ")
+            .next()
+            .is_some_and(|rest| !rest.starts_with('Z')),
+        Err(_) => false,
+    }
+}
+
+/// Starts `sleep 30` from a thread with its own runtime, which then exits
+///
+/// The process is detached, so only the parent-death signal can kill it.
+fn spawn_from_exiting_thread(kill_on_parent_death: bool) -> u32 {
+    std::thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let config = TaskConfig::new("sleep")
+                .args(["30"])
+                .use_process_group(false)
+                .kill_child_on_parent_death(kill_on_parent_death);
+            let (tx, _rx) = mpsc::channel::<TaskEvent>(64);
+            let mut spawner =
+                TaskSpawner::new("orphan".to_string(), config).set_cleanup_on_drop(false);
+            spawner.start_direct(tx).await.unwrap()
+        })
+    })
+    .join()
+    .unwrap()
+}
+
+#[tokio::test]
+async fn child_dies_with_spawning_thread() {
+    let pid = spawn_from_exiting_thread(true);
+
+    let gone = tokio::time::timeout(Duration::from_secs(5), async {
+        while is_running(pid) {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .is_ok();
+    assert!(gone, "Process {pid} outlived the thread that spawned it");
+}
+
+#[tokio::test]
+async fn child_outlives_spawning_thread_by_default() {
+    let pid = spawn_from_exiting_thread(false);
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let running = is_running(pid);
+    unsafe {
+        libc::kill(pid as libc::pid_t, libc::SIGKILL);
+    }
+    assert!(
+        running,
+        "Process {pid} was killed without parent-death signal"
+    );
+}
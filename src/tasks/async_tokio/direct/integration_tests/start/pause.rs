@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use tokio::{sync::mpsc, time::Instant};
+
+use crate::tasks::{
+    async_tokio::spawner::TaskSpawner,
+    config::TaskConfig,
+    error::TaskError,
+    event::{TaskEvent, TaskEventStopReason, TaskTerminateReason},
+};
+
+/// Runs for 0.8 s of its own time, finishing at 2.2 s when paused for 1.5 s at 0.5 s
+///
+/// The first `sleep` ends during the pause, so only the second one is
+/// delayed by it.
+fn paused_mid_run(timeout_excludes_paused: bool) -> TaskConfig {
+    TaskConfig::new("sh")
+        .args(["-c", "sleep 0.6; sleep 0.2"])
+        .timeout_ms(1000)
+        .timeout_excludes_paused(timeout_excludes_paused)
+}
+
+/// Receives events until the task has stopped, returning the stop reason
+async fn wait_for_stop(rx: &mut mpsc::Receiver<TaskEvent>) -> TaskEventStopReason {
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while let Some(event) = rx.recv().await {
+            if let TaskEvent::Stopped { reason, .. } = event {
+                return reason;
+            }
+        }
+        panic!("Event channel closed before Stopped");
+    })
+    .await
+    .expect("Task should stop within the timeout")
+}
+
+#[tokio::test]
+async fn paused_time_is_left_out_of_timeout() {
+    let mut spawner = TaskSpawner::new("pause".to_string(), paused_mid_run(true));
+    let (tx, mut rx) = mpsc::channel(100);
+    let started = Instant::now();
+    spawner.start_direct(tx).await.unwrap();
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    spawner.pause_process().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    spawner.resume_process().await.unwrap();
+
+    assert_eq!(wait_for_stop(&mut rx).await, TaskEventStopReason::Finished);
+    assert!(started.elapsed() >= Duration::from_millis(2100));
+}
+
+#[tokio::test]
+async fn paused_time_counts_by_default() {
+    let mut spawner = TaskSpawner::new("pause".to_string(), paused_mid_run(false));
+    let (tx, mut rx) = mpsc::channel(100);
+    spawner.start_direct(tx).await.unwrap();
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    spawner.pause_process().await.unwrap();
+
+    assert_eq!(
+        wait_for_stop(&mut rx).await,
+        TaskEventStopReason::Terminated(TaskTerminateReason::Timeout)
+    );
+}
+
+#[tokio::test]
+async fn external_stop_is_left_out_of_timeout() {
+    let config = paused_mid_run(true).use_process_group(false);
+    let mut spawner = TaskSpawner::new("pause".to_string(), config);
+    let (tx, mut rx) = mpsc::channel(100);
+    let pid = spawner.start_direct(tx).await.unwrap();
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    unsafe { libc::kill(pid.cast_signed(), libc::SIGSTOP) };
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    unsafe { libc::kill(pid.cast_signed(), libc::SIGCONT) };
+
+    assert_eq!(wait_for_stop(&mut rx).await, TaskEventStopReason::Finished);
+}
+
+#[tokio::test]
+async fn pause_without_process_is_rejected() {
+    let spawner = TaskSpawner::new("pause".to_string(), TaskConfig::new("true"));
+
+    assert!(matches!(
+        spawner.pause_process().await,
+        Err(TaskError::Handle(_))
+    ));
+    assert!(matches!(
+        spawner.resume_process().await,
+        Err(TaskError::Handle(_))
+    ));
+}
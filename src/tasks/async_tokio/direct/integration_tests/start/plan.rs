@@ -0,0 +1,118 @@
+use std::os::unix::fs::PermissionsExt;
+
+use tokio::sync::mpsc;
+
+use crate::tasks::async_tokio::direct::integration_tests::support::{run_output, unique_temp_dir};
+use crate::tasks::{
+    async_tokio::spawner::TaskSpawner, config::TaskConfig, event::TaskEvent,
+    validator::REDACTED_ENV_VALUE,
+};
+
+#[tokio::test]
+async fn plan_matches_actual_run() {
+    let config = TaskConfig::new("sh")
+        .args(["-c", "pwd -P; command -v sh; echo \"$TCRM_PLAN\""])
+        .working_dir(std::env::temp_dir().to_str().unwrap())
+        .env([("TCRM_PLAN", "planned"), ("TCRM_PLAN_SECRET", "hidden")])
+        .timeout_ms(10_000);
+
+    let plan = TaskSpawner::new("plan".to_string(), config.clone())
+        .plan()
+        .unwrap();
+    let lines = run_output(config).await;
+
+    assert_eq!(lines[0], plan.working_dir);
+    assert!(std::path::Path::new(&plan.program).is_absolute());
+    assert!(plan.program.ends_with("/sh"), "{}", plan.program);
+    assert_eq!(plan.env_overrides["TCRM_PLAN"], lines[2]);
+    assert_eq!(plan.env_overrides["TCRM_PLAN_SECRET"], REDACTED_ENV_VALUE);
+    assert_eq!(plan.args.len(), 2);
+    assert_eq!(plan.timeout_ms, Some(10_000));
+    assert!(!plan.enable_stdin);
+}
+
+#[tokio::test]
+async fn plan_resolves_relative_command_against_working_dir() {
+    let dir = unique_temp_dir("plan_relative");
+    let script = dir.join("hello.sh");
+    std::fs::write(&script, "#!/bin/sh\necho from-script\n").unwrap();
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let config = TaskConfig::new("./hello.sh").working_dir(dir.to_str().unwrap());
+
+    let plan = TaskSpawner::new("plan".to_string(), config.clone())
+        .plan()
+        .unwrap();
+    let lines = run_output(config).await;
+
+    assert_eq!(
+        std::path::PathBuf::from(&plan.program),
+        std::fs::canonicalize(&script).unwrap()
+    );
+    assert_eq!(lines, vec!["from-script"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn plan_reports_start_error_for_missing_command() {
+    let config = TaskConfig::new("tcrm_task_no_such_command_12345");
+
+    let plan_error = TaskSpawner::new("plan".to_string(), config.clone())
+        .plan()
+        .unwrap_err();
+    let (tx, _rx) = mpsc::channel::<TaskEvent>(10);
+    let start_error = TaskSpawner::new("plan".to_string(), config)
+        .start_direct(tx)
+        .await
+        .unwrap_err();
+
+    assert_eq!(plan_error, start_error);
+}
+
+#[tokio::test]
+async fn plan_reports_start_error_for_non_executable_file() {
+    let dir = unique_temp_dir("plan_not_executable");
+    let script = dir.join("data.txt");
+    std::fs::write(&script, "not a program\n").unwrap();
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o644)).unwrap();
+    let config = TaskConfig::new("./data.txt").working_dir(dir.to_str().unwrap());
+
+    let plan_error = TaskSpawner::new("plan".to_string(), config.clone())
+        .plan()
+        .unwrap_err();
+    let (tx, _rx) = mpsc::channel::<TaskEvent>(10);
+    let start_error = TaskSpawner::new("plan".to_string(), config)
+        .start_direct(tx)
+        .await
+        .unwrap_err();
+
+    assert_eq!(plan_error, start_error);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn plan_reports_start_error_for_invalid_config() {
+    let config = TaskConfig::new("echo").working_dir("/tcrm_task_no_such_dir_12345");
+
+    let plan_error = TaskSpawner::new("plan".to_string(), config.clone())
+        .plan()
+        .unwrap_err();
+    let (tx, _rx) = mpsc::channel::<TaskEvent>(10);
+    let start_error = TaskSpawner::new("plan".to_string(), config)
+        .start_direct(tx)
+        .await
+        .unwrap_err();
+
+    assert_eq!(plan_error, start_error);
+}
+
+#[tokio::test]
+async fn plan_leaves_task_pending() {
+    let spawner = TaskSpawner::new("plan".to_string(), TaskConfig::new("echo"));
+
+    spawner.plan().unwrap();
+
+    assert_eq!(
+        spawner.get_state().await,
+        crate::tasks::state::TaskState::Pending
+    );
+}
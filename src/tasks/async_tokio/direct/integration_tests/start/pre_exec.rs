@@ -0,0 +1,125 @@
+use std::{io, time::Duration};
+
+use tokio::sync::mpsc;
+
+use crate::tasks::{
+    async_tokio::spawner::TaskSpawner,
+    config::{StreamSource, TaskConfig},
+    error::TaskError,
+    event::TaskEvent,
+};
+
+/// Sets the open file limits of the calling process
+fn set_nofile_limit(soft: libc::rlim_t, hard: libc::rlim_t) -> io::Result<()> {
+    let limit = libc::rlimit {
+        rlim_cur: soft,
+        rlim_max: hard,
+    };
+    if unsafe { libc::setrlimit(libc::RLIMIT_NOFILE, &raw const limit) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Fails with `EINVAL`, as the soft limit may not exceed the hard one
+fn invalid_rlimit() -> io::Result<()> {
+    set_nofile_limit(2, 1)
+}
+
+/// Starts the task and returns its result with all its events
+async fn run(spawner: &mut TaskSpawner) -> (Result<u32, TaskError>, Vec<TaskEvent>) {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(100);
+    let result = spawner.start_direct(tx).await;
+
+    let mut events = vec![];
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while let Some(event) = rx.recv().await {
+            events.push(event);
+        }
+    })
+    .await
+    .expect("Task should stop within the timeout");
+    (result, events)
+}
+
+fn error_events(events: &[TaskEvent]) -> Vec<&TaskError> {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            TaskEvent::Error { error, .. } => Some(error),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn failing_step_is_named() {
+    let mut spawner = unsafe {
+        TaskSpawner::new("pre_exec".to_string(), TaskConfig::new("true"))
+            .add_pre_exec_step("rlimit", invalid_rlimit)
+    };
+
+    let (result, events) = run(&mut spawner).await;
+
+    let expected = TaskError::PreExec {
+        step: "rlimit".to_string(),
+        error: "EINVAL".to_string(),
+    };
+    let error = result.unwrap_err();
+    assert_eq!(error, expected);
+    assert_eq!(error.to_string(), "pre_exec step 'rlimit' failed: EINVAL");
+    assert_eq!(error_events(&events), [&expected]);
+}
+
+#[tokio::test]
+async fn failing_step_is_told_from_earlier_ones() {
+    let mut spawner = unsafe {
+        TaskSpawner::new("pre_exec".to_string(), TaskConfig::new("true"))
+            .add_pre_exec_step("noop", || Ok(()))
+            .add_pre_exec_step("rlimit", invalid_rlimit)
+    };
+
+    let (result, _) = run(&mut spawner).await;
+
+    assert!(matches!(
+        result,
+        Err(TaskError::PreExec { step, .. }) if step == "rlimit"
+    ));
+}
+
+#[tokio::test]
+async fn steps_run_before_the_command() {
+    let config = TaskConfig::new("sh").args(["-c", "ulimit -n"]);
+    let mut spawner = unsafe {
+        TaskSpawner::new("pre_exec".to_string(), config)
+            .add_pre_exec_step("rlimit", || set_nofile_limit(64, 64))
+    };
+
+    let (result, events) = run(&mut spawner).await;
+
+    result.unwrap();
+    let lines: Vec<_> = events
+        .iter()
+        .filter_map(|event| match event {
+            TaskEvent::Output {
+                line,
+                src: StreamSource::Stdout,
+                ..
+            } => Some(line.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(lines, ["64"]);
+}
+
+#[tokio::test]
+async fn other_spawn_failures_are_not_blamed_on_steps() {
+    let config = TaskConfig::new("tcrm_task_pre_exec_missing_command");
+    let mut spawner = unsafe {
+        TaskSpawner::new("pre_exec".to_string(), config).add_pre_exec_step("noop", || Ok(()))
+    };
+
+    let (result, _) = run(&mut spawner).await;
+
+    assert!(matches!(result, Err(TaskError::CommandNotFound { .. })));
+}
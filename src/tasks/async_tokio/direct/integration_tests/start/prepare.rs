@@ -0,0 +1,57 @@
+use tokio::sync::mpsc;
+
+use crate::tasks::async_tokio::direct::integration_tests::support::commands;
+use crate::tasks::{
+    async_tokio::spawner::TaskSpawner, config::TaskConfig, error::TaskError, event::TaskEvent,
+    state::TaskState,
+};
+
+#[tokio::test]
+async fn prepare_returns_validation_error_without_channel() {
+    let mut spawner = TaskSpawner::new("invalid".to_string(), TaskConfig::new(""));
+
+    let error = spawner.prepare().await.unwrap_err();
+
+    assert!(
+        matches!(error, TaskError::InvalidConfiguration(_)),
+        "{error:?}"
+    );
+    assert_eq!(spawner.get_state().await, TaskState::Finished);
+}
+
+#[tokio::test]
+async fn launch_sends_started_with_returned_process_id() {
+    let mut spawner = TaskSpawner::new("prepared".to_string(), commands::trivial());
+    let prepared = spawner.prepare().await.unwrap();
+
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(64);
+    let process_id = prepared.launch(tx).await.unwrap();
+
+    assert!(matches!(
+        rx.recv().await,
+        Some(TaskEvent::Started { process_id: id, .. }) if id == process_id
+    ));
+    while let Some(event) = rx.recv().await {
+        if matches!(event, TaskEvent::Stopped { .. }) {
+            break;
+        }
+    }
+    assert_eq!(spawner.get_state().await, TaskState::Finished);
+}
+
+#[tokio::test]
+async fn dropped_prepared_task_spawns_nothing() {
+    let mut spawner = TaskSpawner::new("dropped".to_string(), commands::trivial());
+
+    drop(spawner.prepare().await.unwrap());
+
+    assert_eq!(spawner.get_state().await, TaskState::Finished);
+    assert_eq!(spawner.get_process_id().await, None);
+    // The spawner cannot be started again
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(64);
+    assert!(matches!(
+        spawner.start_direct(tx).await,
+        Err(TaskError::Handle(_))
+    ));
+    assert!(rx.recv().await.is_none());
+}
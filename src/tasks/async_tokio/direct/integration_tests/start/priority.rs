@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use crate::tasks::{
+    async_tokio::{process_start::proc_stat, spawner::TaskSpawner},
+    config::{TaskConfig, TaskPriority},
+    error::TaskError,
+    event::{TaskEvent, TaskTerminateReason},
+};
+
+/// Nice value of a running process, the 19th field of `/proc/<pid>/stat`
+fn nice_value(pid: u32) -> i32 {
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).unwrap();
+    let (_, mut fields) = proc_stat(&stat).unwrap();
+    fields.nth(16).unwrap().parse().unwrap()
+}
+
+#[tokio::test]
+async fn child_runs_with_nice_value_of_priority() {
+    for (priority, nice) in [(TaskPriority::Low, 10), (TaskPriority::BelowNormal, 5)] {
+        let config = TaskConfig::new("sleep").args(["5"]).priority(priority);
+        let mut spawner = TaskSpawner::new("priority".to_string(), config);
+        let (tx, _rx) = mpsc::channel(64);
+
+        let pid = spawner.start_direct(tx).await.unwrap();
+
+        assert_eq!(nice_value(pid), nice, "{priority:?}");
+        spawner
+            .send_terminate_signal(TaskTerminateReason::Cleanup { message: None })
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn raising_priority_without_rights_is_an_error() {
+    let config = TaskConfig::new("sleep")
+        .args(["5"])
+        .priority(TaskPriority::High);
+    let mut spawner = TaskSpawner::new("priority".to_string(), config);
+    let (tx, mut rx) = mpsc::channel(64);
+
+    // Whether this process may lower nice values depends on where the tests run
+    match spawner.start_direct(tx).await {
+        Ok(pid) => {
+            assert_eq!(nice_value(pid), -5);
+            spawner
+                .send_terminate_signal(TaskTerminateReason::Cleanup { message: None })
+                .await
+                .unwrap();
+        }
+        Err(error) => {
+            let expected = TaskError::Handle("Failed to set priority: EACCES".to_string());
+            assert_eq!(error, expected);
+            let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+                .await
+                .unwrap();
+            assert!(matches!(
+                event,
+                Some(TaskEvent::Error { error, .. }) if error == expected
+            ));
+        }
+    }
+}
@@ -5,7 +5,8 @@ use crate::tasks::{async_tokio::spawner::TaskSpawner, event::TaskEvent};
 
 #[tokio::test]
 async fn process_id_returns_none_after_stopped() {
-    let (tx, mut rx) = mpsc::channel::<TaskEvent>(1024);
+    // Capacity 1: Stopped can only be sent after every earlier event was received
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(1);
     #[cfg(windows)]
     let config = TaskConfig::new("powershell").args(["-Command", "echo done"]);
     #[cfg(unix)]
@@ -17,16 +18,30 @@ async fn process_id_returns_none_after_stopped() {
     let result = spawner.start_direct(tx).await;
     assert!(result.is_ok());
 
+    // Let the process exit while its events are still waiting to be received
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+
     let mut stopped = false;
-    while let Some(event) = rx.recv().await {
+    loop {
+        let pid_before_recv = spawner.get_process_id().await;
+        let Some(event) = rx.recv().await else {
+            break;
+        };
         if let TaskEvent::Stopped { task_name, .. } = event {
-            assert_eq!(task_name, "pid_test_task");
+            assert_eq!(&*task_name, "pid_test_task");
             stopped = true;
-            break;
+            continue;
         }
+        assert!(!stopped, "No events should follow TaskEvent::Stopped");
+        // The event was still in flight, so Stopped has not been sent yet
+        assert!(
+            pid_before_recv.is_some(),
+            "process_id should be Some while {:?} is in flight",
+            event
+        );
     }
     assert!(stopped, "Task should emit Stopped event");
-    // process_id should be None after stopped
+    // Channel closes once the result watcher is done, after clearing the pid
     let pid = spawner.get_process_id().await;
     assert!(
         pid.is_none(),
@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use crate::tasks::{
+    async_tokio::{process_tree::ProcessNode, spawner::TaskSpawner},
+    config::TaskConfig,
+    event::TaskEvent,
+};
+
+/// Polls the tree until it holds `names`, in tree order
+async fn wait_for_tree(spawner: &TaskSpawner, names: &[&str]) -> Vec<ProcessNode> {
+    let mut tree = vec![];
+    for _ in 0..250 {
+        tree = spawner.process_tree().await;
+        let found: Vec<_> = tree
+            .iter()
+            .flat_map(ProcessNode::iter)
+            .map(|node| node.name.as_str())
+            .collect();
+        if found == names {
+            return tree;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("Process tree never held {names:?}: {tree:#?}");
+}
+
+/// Kills `nodes` and waits for the task to stop
+async fn kill<'a>(
+    nodes: impl IntoIterator<Item = &'a ProcessNode>,
+    mut rx: mpsc::Receiver<TaskEvent>,
+) {
+    for node in nodes {
+        unsafe {
+            libc::kill(node.pid.cast_signed(), libc::SIGKILL);
+        }
+    }
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while rx.recv().await.is_some() {}
+    })
+    .await
+    .expect("Task should stop once killed");
+}
+
+async fn shell_with_two_children(use_process_group: bool) {
+    let config = TaskConfig::new("sh")
+        .args(["-c", "sleep 30 & sleep 30 & wait"])
+        .use_process_group(use_process_group);
+    let mut spawner = TaskSpawner::new("process_tree".to_string(), config);
+    assert!(spawner.process_tree().await.is_empty());
+
+    let (tx, rx) = mpsc::channel(100);
+    let pid = spawner.start_direct(tx).await.unwrap();
+    let tree = wait_for_tree(&spawner, &["sh", "sleep", "sleep"]).await;
+
+    assert_eq!(tree.len(), 1);
+    let root = &tree[0];
+    assert_eq!(root.pid, pid);
+    assert_eq!(root.parent_pid, Some(std::process::id()));
+    assert!(root.state.is_some());
+    assert!(root.started_at.is_some());
+    assert_eq!(root.children.len(), 2);
+    for child in &root.children {
+        assert_eq!(child.parent_pid, Some(pid));
+        assert!(child.started_at >= root.started_at);
+        assert!(child.children.is_empty());
+    }
+
+    // The shell reaps its children and exits
+    kill(&root.children, rx).await;
+    assert!(spawner.process_tree().await.is_empty());
+}
+
+#[tokio::test]
+async fn shell_with_two_children_in_process_group() {
+    shell_with_two_children(true).await;
+}
+
+#[tokio::test]
+async fn shell_with_two_children_without_process_group() {
+    shell_with_two_children(false).await;
+}
+
+#[tokio::test]
+async fn reparented_group_members_are_listed() {
+    // The subshell exits right away, leaving its child to be reparented
+    let config = TaskConfig::new("sh")
+        .args(["-c", "(sleep 30 &); exec sleep 30"])
+        .use_process_group(true);
+    let mut spawner = TaskSpawner::new("process_tree".to_string(), config);
+
+    let (tx, rx) = mpsc::channel(100);
+    let pid = spawner.start_direct(tx).await.unwrap();
+    let tree = wait_for_tree(&spawner, &["sleep", "sleep"]).await;
+
+    assert_eq!(tree.len(), 2);
+    assert_eq!(tree[0].pid, pid);
+    assert_ne!(tree[1].parent_pid, Some(pid));
+
+    kill(tree.iter().flat_map(ProcessNode::iter), rx).await;
+}
@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use crate::tasks::async_tokio::direct::integration_tests::support::commands;
+use crate::tasks::{
+    async_tokio::spawner::TaskSpawner,
+    config::TaskConfig,
+    event::{TaskEvent, TaskTerminateReason},
+    state::TaskState,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[tokio::test]
+async fn quiescent_after_normal_exit() {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(64);
+    let mut spawner = TaskSpawner::new("trivial".to_string(), commands::lines(3));
+    spawner.start_direct(tx).await.unwrap();
+
+    // Without draining the events first
+    let report = spawner.wait_for_quiescence(TIMEOUT).await;
+
+    assert!(report.is_quiescent(), "{report:?}");
+    assert_eq!(spawner.get_state().await, TaskState::Finished);
+    let mut last = None;
+    while let Some(event) = rx.recv().await {
+        last = Some(event);
+    }
+    assert!(matches!(last, Some(TaskEvent::Stopped { .. })));
+}
+
+#[tokio::test]
+async fn quiescent_after_terminate() {
+    let (tx, _rx) = mpsc::channel::<TaskEvent>(64);
+    let mut spawner = TaskSpawner::new("sleep".to_string(), commands::sleep(30));
+    spawner.start_direct(tx).await.unwrap();
+
+    spawner
+        .send_terminate_signal(TaskTerminateReason::UserRequested { message: None })
+        .await
+        .unwrap();
+    let report = spawner.wait_for_quiescence(TIMEOUT).await;
+
+    assert!(report.is_quiescent(), "{report:?}");
+}
+
+#[tokio::test]
+async fn quiescent_after_spawn_failure() {
+    let (tx, _rx) = mpsc::channel::<TaskEvent>(64);
+    let mut spawner = TaskSpawner::new(
+        "missing".to_string(),
+        TaskConfig::new("tcrm_task_no_such_command"),
+    );
+    spawner.start_direct(tx).await.unwrap_err();
+
+    let report = spawner.wait_for_quiescence(Duration::ZERO).await;
+
+    assert!(report.is_quiescent(), "{report:?}");
+}
+
+#[tokio::test]
+async fn running_task_is_reported_pending() {
+    let (tx, _rx) = mpsc::channel::<TaskEvent>(64);
+    let mut spawner = TaskSpawner::new("sleep".to_string(), commands::sleep(30));
+    spawner.start_direct(tx).await.unwrap();
+
+    let report = spawner.wait_for_quiescence(Duration::from_millis(50)).await;
+
+    assert_eq!(report.pending_tasks, ["wait"]);
+    assert_eq!(report.unfinished_state, Some(TaskState::Running));
+    assert!(report.terminate_channel_open);
+
+    // The pending watcher is still waited for by a later call
+    spawner
+        .send_terminate_signal(TaskTerminateReason::Cleanup { message: None })
+        .await
+        .unwrap();
+    assert!(spawner.wait_for_quiescence(TIMEOUT).await.is_quiescent());
+}
@@ -1,6 +1,6 @@
 use tokio::sync::mpsc;
 
-use crate::tasks::config::{StreamSource, TaskConfig};
+use crate::tasks::config::{OutputTrigger, StreamSource, TaskConfig, TriggerAction};
 use crate::tasks::{async_tokio::spawner::TaskSpawner, event::TaskEvent};
 
 #[tokio::test]
@@ -26,7 +26,7 @@ async fn ready_indicator_on_stdout() {
     let mut ready_event = false;
     while let Some(event) = rx.recv().await {
         if let TaskEvent::Ready { task_name } = event {
-            assert_eq!(task_name, "ready_stdout_task");
+            assert_eq!(&*task_name, "ready_stdout_task");
             ready_event = true;
         }
     }
@@ -58,7 +58,7 @@ async fn ready_indicator_source_stderr() {
     let mut ready_event = false;
     while let Some(event) = rx.recv().await {
         if let TaskEvent::Ready { task_name } = event {
-            assert_eq!(task_name, "ready_stderr_task");
+            assert_eq!(&*task_name, "ready_stderr_task");
             ready_event = true;
         }
     }
@@ -99,3 +99,166 @@ async fn ready_indicator_source_mismatch() {
         "Should NOT emit Ready event if indicator is in wrong stream"
     );
 }
+
+async fn stopped_was_ready(spawner: &mut TaskSpawner) -> (bool, Option<i32>) {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(15);
+    spawner.start_direct(tx).await.unwrap();
+
+    while let Some(event) = rx.recv().await {
+        if let TaskEvent::Stopped {
+            was_ready,
+            exit_code,
+            ..
+        } = event
+        {
+            return (was_ready, exit_code);
+        }
+    }
+    panic!("Channel closed without a Stopped event");
+}
+
+#[tokio::test]
+async fn stopped_reports_was_ready_after_crash_past_readiness() {
+    #[cfg(windows)]
+    let config = TaskConfig::new("cmd")
+        .args(["/C", "echo READY_INDICATOR && exit 2"])
+        .ready_indicator("READY_INDICATOR".to_string())
+        .use_process_group(false);
+    #[cfg(unix)]
+    let config = TaskConfig::new("sh")
+        .args(["-c", "echo READY_INDICATOR; exit 2"])
+        .ready_indicator("READY_INDICATOR".to_string())
+        .use_process_group(false);
+
+    let mut spawner = TaskSpawner::new("crash_after_ready_task".to_string(), config);
+    let (was_ready, exit_code) = stopped_was_ready(&mut spawner).await;
+
+    assert!(was_ready, "Task reached Ready before exiting");
+    assert_eq!(exit_code, Some(2));
+}
+
+#[tokio::test]
+async fn stopped_reports_not_ready_after_crash_during_startup() {
+    #[cfg(windows)]
+    let config = TaskConfig::new("cmd")
+        .args(["/C", "echo starting && exit 2"])
+        .ready_indicator("READY_INDICATOR".to_string())
+        .use_process_group(false);
+    #[cfg(unix)]
+    let config = TaskConfig::new("sh")
+        .args(["-c", "echo starting; exit 2"])
+        .ready_indicator("READY_INDICATOR".to_string())
+        .use_process_group(false);
+
+    let mut spawner = TaskSpawner::new("crash_before_ready_task".to_string(), config);
+    let (was_ready, exit_code) = stopped_was_ready(&mut spawner).await;
+
+    assert!(!was_ready, "Task never reached Ready");
+    assert_eq!(exit_code, Some(2));
+}
+
+#[tokio::test]
+async fn started_precedes_ready_of_fast_process() {
+    for run in 0..50 {
+        #[cfg(windows)]
+        let config = TaskConfig::new("cmd").args(["/C", "echo READY"]);
+        #[cfg(unix)]
+        let config = TaskConfig::new("echo").args(["READY"]);
+        let config = config.ready_indicator("READY");
+
+        let (tx, mut rx) = mpsc::channel::<TaskEvent>(16);
+        let mut spawner = TaskSpawner::new("fast_ready_task".to_string(), config);
+        spawner.start_direct(tx).await.unwrap();
+
+        let mut events = vec![];
+        while let Some(event) = rx.recv().await {
+            events.push(event);
+        }
+        let position = |wanted: fn(&TaskEvent) -> bool| events.iter().position(wanted);
+        let started = position(|event| matches!(event, TaskEvent::Started { .. }));
+        let output = position(|event| matches!(event, TaskEvent::Output { .. }));
+        let ready = position(|event| matches!(event, TaskEvent::Ready { .. }));
+        let stopped = position(|event| matches!(event, TaskEvent::Stopped { .. }));
+
+        assert_eq!(started, Some(0), "run {run}: {events:?}");
+        assert!(output.is_some() && ready > output, "run {run}: {events:?}");
+        assert_eq!(stopped, Some(events.len() - 1), "run {run}: {events:?}");
+    }
+}
+
+/// Counts the `Ready` events of a task running `config`
+#[cfg(unix)]
+async fn ready_events(config: TaskConfig) -> usize {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(64);
+    let mut spawner = TaskSpawner::new("ready_once_task".to_string(), config);
+    spawner.start_direct(tx).await.unwrap();
+
+    let mut ready = 0;
+    while let Some(event) = rx.recv().await {
+        if matches!(event, TaskEvent::Ready { .. }) {
+            ready += 1;
+        }
+    }
+    ready
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn ready_fires_once_for_repeated_indicator() {
+    let config = TaskConfig::new("sh")
+        .args(["-c", "for i in 1 2 3 4 5; do echo READY; done"])
+        .ready_indicator("READY");
+    assert_eq!(ready_events(config).await, 1);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn ready_fires_once_across_streams() {
+    for _ in 0..20 {
+        let config = TaskConfig::new("sh")
+            .args([
+                "-c",
+                "echo READY; echo READY >&2; echo READY; echo READY >&2",
+            ])
+            .ready_indicator("READY")
+            .output_trigger(OutputTrigger::new(
+                "READY",
+                StreamSource::Stderr,
+                TriggerAction::Ready,
+            ));
+        assert_eq!(ready_events(config).await, 1);
+    }
+}
+
+#[cfg(all(unix, feature = "regex"))]
+#[tokio::test]
+async fn ready_indicator_regex_matches_line() {
+    let config = TaskConfig::new("sh")
+        .args([
+            "-c",
+            "echo 'Listening on 127.0.0.1:port'; echo 'Listening on 127.0.0.1:41234'; echo 'Listening on 127.0.0.1:41235'",
+        ])
+        .ready_indicator_regex(r"^Listening on 127\.0\.0\.1:\d+$");
+    assert_eq!(ready_events(config).await, 1);
+
+    let config = TaskConfig::new("sh")
+        .args(["-c", "echo 'Listening on 127.0.0.1:port'"])
+        .ready_indicator_regex(r"^Listening on 127\.0\.0\.1:\d+$");
+    assert_eq!(ready_events(config).await, 0);
+}
+
+#[cfg(all(unix, feature = "regex"))]
+#[tokio::test]
+async fn ready_indicator_regex_watches_its_source() {
+    let config = TaskConfig::new("sh")
+        .args(["-c", "echo 'ready in 12ms'"])
+        .ready_indicator_regex(r"ready in \d+ms")
+        .ready_indicator_source(StreamSource::Stderr);
+    assert_eq!(ready_events(config).await, 0);
+
+    let config = TaskConfig::new("sh")
+        .args(["-c", "echo 'ready in 12ms' >&2"])
+        .ready_indicator_regex(r"ready in \d+ms")
+        .ready_indicator_source(StreamSource::Stderr);
+    assert_eq!(ready_events(config).await, 1);
+}
@@ -0,0 +1,78 @@
+use crate::tasks::{
+    async_tokio::{direct::run::TaskOutput, spawner::TaskSpawner},
+    config::{OutputMode, TaskConfig},
+    error::TaskError,
+    event::{TaskEventStopReason, TaskTerminateReason},
+};
+
+async fn run(config: TaskConfig) -> Result<TaskOutput, TaskError> {
+    TaskSpawner::new("run".to_string(), config)
+        .run_to_completion()
+        .await
+}
+
+#[tokio::test]
+async fn success_returns_both_streams() {
+    let config = TaskConfig::new("sh").args(["-c", "echo one; echo oops >&2; echo two"]);
+
+    let output = run(config).await.unwrap();
+
+    assert!(output.success());
+    assert_eq!(output.exit_code, Some(0));
+    assert_eq!(output.stdout, ["one", "two"]);
+    assert_eq!(output.stderr, ["oops"]);
+    assert_eq!(output.dropped_lines, 0);
+    assert_eq!(output.stop_reason, TaskEventStopReason::Finished);
+}
+
+#[tokio::test]
+async fn non_zero_exit_is_not_an_error() {
+    let config = TaskConfig::new("sh").args(["-c", "echo failing >&2; exit 3"]);
+
+    let output = run(config).await.unwrap();
+
+    assert!(!output.success());
+    assert_eq!(output.exit_code, Some(3));
+    assert_eq!(output.stderr, ["failing"]);
+    assert_eq!(output.stop_reason, TaskEventStopReason::Finished);
+}
+
+#[tokio::test]
+async fn timeout_keeps_output_read_so_far() {
+    let config = TaskConfig::new("sh")
+        .args(["-c", "echo started; sleep 10"])
+        .timeout_ms(500);
+
+    let output = run(config).await.unwrap();
+
+    assert!(!output.success());
+    assert_eq!(output.exit_code, None);
+    assert_eq!(output.stdout, ["started"]);
+    assert_eq!(
+        output.stop_reason,
+        TaskEventStopReason::Terminated(TaskTerminateReason::Timeout)
+    );
+    assert!(output.duration.as_millis() >= 500);
+}
+
+#[tokio::test]
+async fn captured_lines_are_capped_per_stream() {
+    let config = TaskConfig::new("sh")
+        .args(["-c", "seq 1 10; echo err >&2"])
+        .max_captured_lines(3);
+
+    let output = run(config).await.unwrap();
+
+    assert_eq!(output.stdout, ["1", "2", "3"]);
+    assert_eq!(output.stderr, ["err"]);
+    assert_eq!(output.dropped_lines, 7);
+}
+
+#[tokio::test]
+async fn start_errors_are_returned() {
+    let result = run(TaskConfig::new("tcrm-no-such-command")).await;
+    assert!(matches!(result, Err(TaskError::CommandNotFound { .. })));
+
+    let result = run(TaskConfig::new("cat").output_mode(OutputMode::Bytes)).await;
+    assert!(matches!(result, Err(TaskError::InvalidConfiguration(_))));
+}
@@ -0,0 +1,75 @@
+use crate::tasks::async_tokio::direct::integration_tests::support::{collect_events, commands};
+use crate::tasks::event::{TaskEvent, TaskEventStopReason};
+
+#[tokio::test]
+async fn reports_running_long_once_without_terminating() {
+    let config = commands::sleep(1)
+        .expected_duration_ms(100)
+        .running_long_multiplier(1.5);
+
+    let events = collect_events(config).await;
+
+    let running_long: Vec<_> = events
+        .iter()
+        .filter_map(|event| match event {
+            TaskEvent::RunningLong {
+                expected_ms,
+                elapsed_ms,
+                ..
+            } => Some((*expected_ms, *elapsed_ms)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(running_long.len(), 1, "{events:?}");
+    let (expected_ms, elapsed_ms) = running_long[0];
+    assert_eq!(expected_ms, 100);
+    assert!(elapsed_ms >= 150, "elapsed {elapsed_ms}ms");
+
+    let Some(TaskEvent::Stopped {
+        reason,
+        exit_code,
+        duration_ms,
+        expected_duration_ms,
+        ..
+    }) = events.last()
+    else {
+        panic!("Stopped should be the last event: {events:?}");
+    };
+    assert_eq!(*reason, TaskEventStopReason::Finished);
+    assert_eq!(*exit_code, Some(0));
+    assert_eq!(*expected_duration_ms, Some(100));
+    assert!(*duration_ms >= 1000, "duration {duration_ms}ms");
+}
+
+#[tokio::test]
+async fn no_running_long_within_expectation() {
+    let config = commands::trivial().expected_duration_ms(60_000);
+
+    let events = collect_events(config).await;
+
+    assert!(
+        !events
+            .iter()
+            .any(|event| matches!(event, TaskEvent::RunningLong { .. }))
+    );
+    assert!(matches!(
+        events.last(),
+        Some(TaskEvent::Stopped {
+            expected_duration_ms: Some(60_000),
+            ..
+        })
+    ));
+}
+
+#[tokio::test]
+async fn stopped_reports_duration_without_expectation() {
+    let events = collect_events(commands::trivial()).await;
+
+    assert!(matches!(
+        events.last(),
+        Some(TaskEvent::Stopped {
+            expected_duration_ms: None,
+            ..
+        })
+    ));
+}
@@ -0,0 +1,118 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::runtime::Builder;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+
+use crate::tasks::config::TaskConfig;
+use crate::tasks::{
+    async_tokio::{spawner::TaskSpawner, transform::OutputTransform},
+    event::TaskEvent,
+};
+
+const WORKER_THREAD_NAME: &str = "tcrm-test-worker";
+
+fn echo_config() -> TaskConfig {
+    #[cfg(windows)]
+    let config = TaskConfig::new("powershell").args(["-Command", "echo hello"]);
+    #[cfg(unix)]
+    let config = TaskConfig::new("echo").args(["hello"]);
+    config
+}
+
+fn assert_send<T: Send>(_: &T) {}
+
+#[test]
+fn watchers_run_on_configured_runtime() {
+    let workers = Builder::new_multi_thread()
+        .worker_threads(1)
+        .thread_name(WORKER_THREAD_NAME)
+        .enable_all()
+        .build()
+        .unwrap();
+    let ui = Builder::new_current_thread().enable_all().build().unwrap();
+
+    // The transform runs inside the output watcher, so it sees the watcher's thread
+    let threads = Arc::new(Mutex::new(Vec::new()));
+    let transform = {
+        let threads = threads.clone();
+        OutputTransform::new(move |line, _src| {
+            let name = std::thread::current().name().map(str::to_owned);
+            threads.lock().unwrap().push(name);
+            Some(line.to_owned())
+        })
+    };
+
+    let (pid, events) = ui.block_on(async {
+        let (tx, mut rx) = mpsc::channel::<TaskEvent>(100);
+        let mut spawner = TaskSpawner::new("runtime".to_string(), echo_config())
+            .set_runtime(workers.handle().clone())
+            .set_output_transform(transform);
+
+        let start = spawner.start_direct(tx);
+        // Entering the runtime must not make the start future !Send
+        assert_send(&start);
+        let pid = start.await.unwrap();
+
+        let mut events = vec![];
+        timeout(Duration::from_secs(10), async {
+            while let Some(event) = rx.recv().await {
+                let stopped = matches!(event, TaskEvent::Stopped { .. });
+                events.push(event);
+                if stopped {
+                    break;
+                }
+            }
+        })
+        .await
+        .expect("Events should flow while the calling runtime only awaits them");
+        (pid, events)
+    });
+
+    assert!(matches!(
+        events.first(),
+        Some(TaskEvent::Started { process_id, .. }) if *process_id == pid
+    ));
+    assert!(
+        events
+            .iter()
+            .any(|event| matches!(event, TaskEvent::Output { line, .. } if line.trim() == "hello"))
+    );
+    assert!(matches!(
+        events.last(),
+        Some(TaskEvent::Stopped {
+            exit_code: Some(0),
+            ..
+        })
+    ));
+
+    let threads = threads.lock().unwrap();
+    assert!(!threads.is_empty());
+    assert!(
+        threads
+            .iter()
+            .all(|name| name.as_deref() == Some(WORKER_THREAD_NAME)),
+        "Output watcher ran outside the configured runtime: {threads:?}"
+    );
+}
+
+#[tokio::test]
+async fn defaults_to_calling_runtime() {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(100);
+    let mut spawner = TaskSpawner::new("ambient".to_string(), echo_config());
+    assert!(spawner.runtime.is_none());
+
+    spawner.start_direct(tx).await.unwrap();
+    let stopped = timeout(Duration::from_secs(10), async {
+        while let Some(event) = rx.recv().await {
+            if let TaskEvent::Stopped { exit_code, .. } = event {
+                return exit_code;
+            }
+        }
+        None
+    })
+    .await
+    .unwrap();
+    assert_eq!(stopped, Some(0));
+}
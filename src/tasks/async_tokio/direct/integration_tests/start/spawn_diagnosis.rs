@@ -0,0 +1,169 @@
+use tokio::sync::mpsc;
+
+use crate::tasks::async_tokio::direct::integration_tests::support::unique_temp_dir;
+use crate::tasks::{
+    async_tokio::{direct::diagnosis::diagnose, spawner::TaskSpawner},
+    config::TaskConfig,
+    error::{SpawnDiagnosis, TaskError},
+    event::TaskEvent,
+};
+
+/// Starts the task, expecting a spawn error, and returns its diagnosis
+///
+/// Also checks that the `TaskEvent::Error` carries the same error.
+async fn spawn_diagnosis(config: TaskConfig) -> SpawnDiagnosis {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(10);
+    let mut spawner = TaskSpawner::new("diagnosis".to_string(), config);
+    let error = spawner.start_direct(tx).await.unwrap_err();
+
+    match rx.recv().await {
+        Some(TaskEvent::Error {
+            error: event_error, ..
+        }) => assert_eq!(event_error, error),
+        other => panic!("Expected an Error event, got {other:?}"),
+    }
+    match error {
+        TaskError::Spawn { diagnosis, .. } => diagnosis,
+        other => panic!("Expected a spawn error, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn directory_as_command() {
+    let dir = unique_temp_dir("diagnosis_directory");
+    let command = dir.join("bin");
+    std::fs::create_dir_all(&command).unwrap();
+
+    let diagnosis = spawn_diagnosis(TaskConfig::new(command.to_str().unwrap())).await;
+
+    assert_eq!(
+        diagnosis,
+        SpawnDiagnosis::IsDirectory {
+            path: command.to_string_lossy().into_owned(),
+        }
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn file_without_execute_permission() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = unique_temp_dir("diagnosis_not_executable");
+    let script = dir.join("script.sh");
+    std::fs::write(&script, "#!/bin/sh\necho unreachable\n").unwrap();
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o644)).unwrap();
+    let config = TaskConfig::new("./script.sh").working_dir(dir.to_str().unwrap());
+
+    let diagnosis = spawn_diagnosis(config.clone()).await;
+
+    assert_eq!(
+        diagnosis,
+        SpawnDiagnosis::NotExecutable {
+            path: script.to_string_lossy().into_owned(),
+        }
+    );
+    assert_eq!(
+        diagnosis.to_string(),
+        format!("{} is not executable", script.display())
+    );
+    // A dry run reports the same error
+    let plan_error = TaskSpawner::new("diagnosis".to_string(), config)
+        .plan()
+        .unwrap_err();
+    assert!(matches!(plan_error, TaskError::Spawn { diagnosis: d, .. } if d == diagnosis));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn executable_for_other_architecture() {
+    use std::os::unix::fs::PermissionsExt;
+
+    // An ELF header whose machine is never the host's
+    let (machine, architecture): (u16, &str) = if std::env::consts::ARCH == "aarch64" {
+        (0x3E, "x86_64")
+    } else {
+        (0xB7, "aarch64")
+    };
+    let mut elf = b"\x7fELF\x02\x01\x01".to_vec();
+    elf.resize(16, 0);
+    elf.extend(2_u16.to_le_bytes());
+    elf.extend(machine.to_le_bytes());
+    elf.resize(64, 0);
+
+    let dir = unique_temp_dir("diagnosis_architecture");
+    let binary = dir.join("server");
+    std::fs::write(&binary, elf).unwrap();
+    std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    // glibc runs a file the kernel rejects with `ENOEXEC` through `/bin/sh`,
+    // so the spawn itself does not fail here; diagnose the file directly
+    let diagnosis = diagnose(binary.to_str().unwrap(), &dir, None);
+
+    assert_eq!(
+        diagnosis,
+        Some(SpawnDiagnosis::WrongArchitecture {
+            path: binary.to_string_lossy().into_owned(),
+            architecture: architecture.to_string(),
+        })
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(windows)]
+#[tokio::test]
+async fn file_that_is_not_an_executable() {
+    let dir = unique_temp_dir("diagnosis_not_executable");
+    let file = dir.join("data.exe");
+    std::fs::write(&file, "not a program\r\n").unwrap();
+
+    let diagnosis = spawn_diagnosis(TaskConfig::new(file.to_str().unwrap())).await;
+
+    assert_eq!(
+        diagnosis,
+        SpawnDiagnosis::NotExecutable {
+            path: file.to_string_lossy().into_owned(),
+        }
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn missing_command_has_no_diagnosis() {
+    let (tx, _rx) = mpsc::channel::<TaskEvent>(10);
+    let mut spawner = TaskSpawner::new(
+        "diagnosis".to_string(),
+        TaskConfig::new("tcrm_task_no_such_command"),
+    );
+
+    let error = spawner.start_direct(tx).await.unwrap_err();
+
+    assert!(
+        matches!(error, TaskError::CommandNotFound { .. }),
+        "{error:?}"
+    );
+}
+
+#[tokio::test]
+async fn missing_command_path_is_not_searched_in_path() {
+    let dir = unique_temp_dir("diagnosis_missing_path");
+    let config = TaskConfig::new("./tcrm_task_no_such_command").working_dir(dir.to_str().unwrap());
+    let (tx, _rx) = mpsc::channel::<TaskEvent>(10);
+    let mut spawner = TaskSpawner::new("diagnosis".to_string(), config.clone());
+
+    let error = spawner.start_direct(tx).await.unwrap_err();
+
+    let expected = TaskError::CommandNotFound {
+        command: "./tcrm_task_no_such_command".to_string(),
+        searched_path: None,
+    };
+    assert_eq!(error, expected);
+    // A dry run reports the same error
+    let plan_error = TaskSpawner::new("diagnosis".to_string(), config)
+        .plan()
+        .unwrap_err();
+    assert_eq!(plan_error, expected);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
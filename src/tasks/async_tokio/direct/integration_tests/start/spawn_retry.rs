@@ -0,0 +1,141 @@
+use std::{
+    io::ErrorKind,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use tokio::{sync::mpsc, time::Instant};
+
+use crate::tasks::{
+    async_tokio::spawner::TaskSpawner,
+    config::TaskConfig,
+    error::TaskError,
+    event::{TaskEvent, TaskEventStopReason},
+};
+
+/// Writes a script that is not executable yet into a fresh temporary directory
+fn script_without_permission(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("tcrm_task_{name}_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let script = dir.join("late.sh");
+    std::fs::write(&script, "#!/bin/sh\necho spawned\n").unwrap();
+    set_mode(&script, 0o644);
+    script
+}
+
+fn set_mode(path: &Path, mode: u32) {
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode)).unwrap();
+}
+
+/// Starts the task and returns its result with all its events
+async fn run(config: TaskConfig) -> (Result<u32, TaskError>, Vec<TaskEvent>) {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(100);
+    let mut spawner = TaskSpawner::new("spawn_retry".to_string(), config);
+    let result = spawner.start_direct(tx).await;
+
+    let mut events = vec![];
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while let Some(event) = rx.recv().await {
+            events.push(event);
+        }
+    })
+    .await
+    .expect("Task should stop within the timeout");
+    (result, events)
+}
+
+fn retry_attempts(events: &[TaskEvent]) -> Vec<u32> {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            TaskEvent::SpawnRetry { attempt, .. } => Some(*attempt),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn spawn_succeeds_once_the_command_becomes_executable() {
+    let script = script_without_permission("spawn_retry_success");
+    let config = TaskConfig::new(script.to_str().unwrap()).spawn_retry(
+        10,
+        100,
+        [ErrorKind::PermissionDenied],
+    );
+
+    let fix = {
+        let script = script.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(250)).await;
+            set_mode(&script, 0o755);
+        })
+    };
+    let (result, events) = run(config).await;
+    fix.await.unwrap();
+
+    assert!(
+        result.is_ok(),
+        "Expected a successful spawn, got {result:?}"
+    );
+    let attempts = retry_attempts(&events);
+    assert!(!attempts.is_empty(), "Expected retries, got {events:?}");
+    assert_eq!(attempts, (1..=attempts.len() as u32).collect::<Vec<_>>());
+    // Retries are reported before the process starts
+    let started = events
+        .iter()
+        .position(|event| matches!(event, TaskEvent::Started { .. }))
+        .unwrap();
+    assert_eq!(started, attempts.len());
+    assert!(matches!(
+        &events[0],
+        TaskEvent::SpawnRetry { error, .. } if error.contains("Permission denied")
+    ));
+    assert!(
+        events
+            .iter()
+            .any(|event| matches!(event, TaskEvent::Output { line, .. } if line == "spawned"))
+    );
+    assert!(matches!(
+        events.last(),
+        Some(TaskEvent::Stopped {
+            reason: TaskEventStopReason::Finished,
+            ..
+        })
+    ));
+    std::fs::remove_dir_all(script.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn last_failed_attempt_is_reported_as_without_retries() {
+    let script = script_without_permission("spawn_retry_exhausted");
+    let config =
+        TaskConfig::new(script.to_str().unwrap()).spawn_retry(3, 10, [ErrorKind::PermissionDenied]);
+
+    let (result, events) = run(config).await;
+
+    assert!(matches!(result, Err(TaskError::Spawn { .. })));
+    assert_eq!(retry_attempts(&events), vec![1, 2]);
+    match events.last() {
+        Some(TaskEvent::Error { error, .. }) => assert_eq!(Err(error), result.as_ref()),
+        other => panic!("Expected an Error event, got {other:?}"),
+    }
+    std::fs::remove_dir_all(script.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn other_errors_are_not_retried() {
+    let config = TaskConfig::new("/nonexistent/tcrm_task_spawn_retry").spawn_retry(
+        5,
+        1000,
+        [ErrorKind::PermissionDenied],
+    );
+
+    let start = Instant::now();
+    let (result, events) = run(config).await;
+
+    assert!(result.is_err());
+    assert!(start.elapsed() < Duration::from_secs(1));
+    assert!(retry_attempts(&events).is_empty());
+    assert!(matches!(events.as_slice(), [TaskEvent::Error { .. }]));
+}
@@ -0,0 +1,147 @@
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+use crate::tasks::async_tokio::direct::integration_tests::support::{collect, commands};
+use crate::tasks::{
+    async_tokio::{spawner::TaskSpawner, temp_dir},
+    error::TaskError,
+    event::{TaskEvent, TaskEventStopReason},
+    state::TaskState,
+};
+
+#[tokio::test]
+async fn exited_child_without_pid_reports_startup_failure() {
+    let spawner = TaskSpawner::new("startup_failure".to_string(), commands::trivial());
+    assert!(spawner.update_state(TaskState::Initiating).await);
+
+    // A reaped child no longer has a process ID, like one that died instantly
+    let config = commands::trivial();
+    let mut child = Command::new(&config.command)
+        .args(config.args.unwrap_or_default())
+        .spawn()
+        .unwrap();
+    child.wait().await.unwrap();
+    assert_eq!(child.id(), None);
+
+    let (tx, rx) = mpsc::channel::<TaskEvent>(10);
+    let error = spawner
+        .report_startup_failure(&mut child, None, tx, Instant::now())
+        .await;
+
+    let TaskError::Handle(msg) = &error else {
+        panic!("Expected Handle error, got {error:?}");
+    };
+    assert!(msg.contains("exited during startup"), "{msg}");
+
+    let events = collect(rx).await;
+    assert_eq!(events.len(), 1, "{events:?}");
+    match &events[0] {
+        TaskEvent::Stopped {
+            exit_code,
+            reason: TaskEventStopReason::StartupFailure(reason),
+            was_ready,
+            ..
+        } => {
+            assert_eq!(*exit_code, Some(0));
+            assert_eq!(reason, msg);
+            assert!(!was_ready);
+        }
+        other => panic!("Expected Stopped with StartupFailure, got {other:?}"),
+    }
+    assert_eq!(spawner.get_state().await, TaskState::Finished);
+}
+
+#[tokio::test]
+async fn startup_failure_without_status_removes_temp_dir() {
+    for keep_on_failure in [false, true] {
+        let config = commands::sleep(5)
+            .temp_working_dir(true)
+            .keep_temp_dir_on_failure(keep_on_failure);
+        let mut spawner = TaskSpawner::new("startup_failure".to_string(), config);
+        let dir = temp_dir::create("tcrm-startup-failure-").unwrap();
+        spawner.temp_dir = Some(dir.clone());
+        assert!(spawner.update_state(TaskState::Initiating).await);
+
+        // A running child has no exit status to report, so the task fails with an error
+        let config = commands::sleep(5);
+        let mut child = Command::new(&config.command)
+            .args(config.args.unwrap_or_default())
+            .kill_on_drop(true)
+            .spawn()
+            .unwrap();
+        let (tx, rx) = mpsc::channel::<TaskEvent>(10);
+        let error = spawner
+            .report_startup_failure(&mut child, None, tx, Instant::now())
+            .await;
+
+        assert_eq!(
+            error,
+            TaskError::Handle("Failed to get process id".to_string())
+        );
+        assert!(matches!(
+            collect(rx).await.as_slice(),
+            [TaskEvent::Error { .. }]
+        ));
+        assert_eq!(spawner.get_state().await, TaskState::Finished);
+        assert_eq!(
+            dir.is_dir(),
+            keep_on_failure,
+            "keep_on_failure: {keep_on_failure}"
+        );
+        if keep_on_failure {
+            temp_dir::remove(&dir);
+        }
+    }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn missing_interpreter_is_reported_as_error() {
+    use std::os::unix::fs::PermissionsExt;
+
+    use crate::tasks::error::SpawnDiagnosis;
+
+    let dir = std::env::temp_dir().join(format!(
+        "tcrm_task_missing_interpreter_{}",
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    let script = dir.join("script.sh");
+    std::fs::write(
+        &script,
+        "#!/tcrm_task/no/such/interpreter\necho unreachable\n",
+    )
+    .unwrap();
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let config = crate::tasks::config::TaskConfig::new(script.to_str().unwrap());
+    let mut spawner = TaskSpawner::new("missing_interpreter".to_string(), config);
+    let (tx, rx) = mpsc::channel::<TaskEvent>(10);
+    let result = spawner.start_direct(tx).await;
+
+    // The kernel rejects the exec, so no process is ever created
+    let Err(TaskError::Spawn { diagnosis, .. }) = &result else {
+        panic!("Expected a spawn error, got {result:?}");
+    };
+    assert_eq!(
+        *diagnosis,
+        SpawnDiagnosis::MissingInterpreter {
+            path: script.to_string_lossy().into_owned(),
+            interpreter: "/tcrm_task/no/such/interpreter".to_string(),
+        }
+    );
+    let events = collect(rx).await;
+    assert!(
+        matches!(
+            events.as_slice(),
+            [TaskEvent::Error {
+                error: TaskError::Spawn { .. },
+                ..
+            }]
+        ),
+        "{events:?}"
+    );
+    assert_eq!(spawner.get_state().await, TaskState::Finished);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
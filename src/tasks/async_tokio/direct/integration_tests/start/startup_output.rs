@@ -0,0 +1,93 @@
+use tokio::sync::mpsc;
+
+use crate::tasks::async_tokio::direct::integration_tests::support::commands;
+use crate::tasks::{
+    async_tokio::{spawner::TaskSpawner, startup_output::CapturedLine},
+    config::{StreamSource, TaskConfig},
+    event::TaskEvent,
+};
+
+/// Runs the task to completion, discarding its events
+async fn run(config: TaskConfig) -> TaskSpawner {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(100);
+    let mut spawner = TaskSpawner::new("startup_output".to_string(), config);
+    spawner.start_direct(tx).await.unwrap();
+    while rx.recv().await.is_some() {}
+    spawner
+}
+
+fn captured(src: StreamSource, line: &str) -> CapturedLine {
+    CapturedLine {
+        src,
+        line: line.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn failure_before_ready_includes_startup_output() {
+    #[cfg(windows)]
+    let config = TaskConfig::new("cmd").args(["/C", "echo starting& echo bad config 1>&2& exit 2"]);
+    #[cfg(unix)]
+    let config = TaskConfig::new("sh").args(["-c", "echo starting; echo bad config >&2; exit 2"]);
+
+    let spawner = run(config.ready_indicator("listening")).await;
+
+    let mut expected = vec![
+        captured(StreamSource::Stdout, "starting"),
+        captured(StreamSource::Stderr, "bad config"),
+    ];
+    // The order across streams depends on scheduling
+    let sorted = |mut lines: Vec<CapturedLine>| {
+        for captured in &mut lines {
+            captured.line = captured.line.trim_end().to_string();
+        }
+        lines.sort_by(|a, b| a.line.cmp(&b.line));
+        lines
+    };
+    expected.sort_by(|a, b| a.line.cmp(&b.line));
+    assert_eq!(sorted(spawner.startup_output()), expected);
+
+    let info = spawner.get_task_info().await;
+    assert_eq!(info.startup_output.map(sorted), Some(expected));
+}
+
+#[tokio::test]
+async fn buffer_is_capped_at_configured_lines() {
+    let spawner = run(commands::lines(100).startup_capture_lines(10)).await;
+
+    let lines: Vec<_> = spawner
+        .startup_output()
+        .into_iter()
+        .map(|captured| captured.line.trim_end().to_string())
+        .collect();
+    let expected: Vec<_> = (1..=10).map(|i| i.to_string()).collect();
+    assert_eq!(lines, expected);
+}
+
+#[tokio::test]
+async fn success_keeps_output_out_of_task_info() {
+    let spawner = run(commands::lines(3)).await;
+
+    assert_eq!(spawner.startup_output().len(), 3);
+    assert_eq!(spawner.get_task_info().await.startup_output, None);
+}
+
+#[tokio::test]
+async fn failure_after_ready_keeps_output_out_of_task_info() {
+    #[cfg(windows)]
+    let config = TaskConfig::new("cmd").args(["/C", "echo listening& exit 1"]);
+    #[cfg(unix)]
+    let config = TaskConfig::new("sh").args(["-c", "echo listening; exit 1"]);
+
+    let spawner = run(config.ready_indicator("listening")).await;
+
+    assert_eq!(spawner.startup_output().len(), 1);
+    assert_eq!(spawner.get_task_info().await.startup_output, None);
+}
+
+#[tokio::test]
+async fn zero_lines_disables_capture() {
+    let spawner = run(commands::lines(3).startup_capture_lines(0)).await;
+
+    assert!(spawner.startup_output().is_empty());
+}
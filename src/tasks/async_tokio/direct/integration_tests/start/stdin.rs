@@ -1,6 +1,5 @@
 use tokio::sync::mpsc;
 
-#[cfg(windows)]
 use crate::tasks::config::TaskConfig;
 use crate::tasks::{async_tokio::spawner::TaskSpawner, config::StreamSource, event::TaskEvent};
 
@@ -36,16 +35,17 @@ async fn stdin_valid() {
 
     while let Some(event) = rx.recv().await {
         match event {
-            TaskEvent::Started { task_name } => {
-                assert_eq!(task_name, "stdin_task");
+            TaskEvent::Started { task_name, .. } => {
+                assert_eq!(&*task_name, "stdin_task");
                 started = true;
             }
             TaskEvent::Output {
                 task_name,
                 line,
                 src,
+                ..
             } => {
-                assert_eq!(task_name, "stdin_task");
+                assert_eq!(&*task_name, "stdin_task");
                 assert_eq!(line, "hello world");
                 assert_eq!(src, StreamSource::Stdout);
                 output_ok = true;
@@ -55,7 +55,7 @@ async fn stdin_valid() {
                 exit_code,
                 ..
             } => {
-                assert_eq!(task_name, "stdin_task");
+                assert_eq!(&*task_name, "stdin_task");
                 assert_eq!(exit_code, Some(0));
                 stopped = true;
             }
@@ -103,8 +103,8 @@ async fn stdin_ignore() {
 
     while let Some(event) = rx.recv().await {
         match event {
-            TaskEvent::Started { task_name } => {
-                assert_eq!(task_name, "stdin_task");
+            TaskEvent::Started { task_name, .. } => {
+                assert_eq!(&*task_name, "stdin_task");
                 started = true;
             }
             TaskEvent::Output { .. } => {
@@ -116,7 +116,7 @@ async fn stdin_ignore() {
                 exit_code,
                 ..
             } => {
-                assert_eq!(task_name, "stdin_task");
+                assert_eq!(&*task_name, "stdin_task");
                 assert_eq!(exit_code, Some(0));
                 stopped = true;
             }
@@ -131,3 +131,111 @@ async fn stdin_ignore() {
     );
     assert!(stopped);
 }
+
+#[tokio::test]
+async fn terminate_with_queued_stdin_is_bounded() {
+    use crate::tasks::async_tokio::direct::integration_tests::support::commands;
+    use crate::tasks::event::TaskTerminateReason;
+
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(1024);
+    let (stdin_tx, stdin_rx) = mpsc::channel::<String>(128);
+    // The child never reads, so these fill its stdin pipe and stay queued
+    for _ in 0..100 {
+        stdin_tx.send("x".repeat(4096)).await.unwrap();
+    }
+
+    let config = commands::sleep(10).enable_stdin(true);
+    let mut spawner = TaskSpawner::new("stdin_task".to_string(), config).set_stdin(stdin_rx);
+    spawner.start_direct(tx).await.unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    spawner
+        .send_terminate_signal(TaskTerminateReason::UserRequested { message: None })
+        .await
+        .unwrap();
+
+    let stopped = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        while let Some(event) = rx.recv().await {
+            if matches!(event, TaskEvent::Stopped { .. }) {
+                return true;
+            }
+        }
+        false
+    })
+    .await;
+    assert_eq!(stopped, Ok(true));
+
+    let dropped = spawner.get_task_info().await.dropped_stdin_lines;
+    assert!(dropped > 0 && dropped <= 100, "dropped {dropped}");
+    assert!(stdin_tx.send("late".to_string()).await.is_err());
+}
+
+/// Log output of the test, read back to look for secrets
+#[cfg(feature = "tracing")]
+#[derive(Clone, Default)]
+struct LogBuffer(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+#[cfg(feature = "tracing")]
+impl std::io::Write for LogBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn secret_stdin_is_written_but_never_recorded() {
+    use crate::tasks::{async_tokio::stdin::StdinInput, event::format::LogFormatter};
+
+    const SECRET: &str = "hunter2";
+
+    // The current-thread test runtime runs the watchers on this thread
+    #[cfg(feature = "tracing")]
+    let logs = LogBuffer::default();
+    #[cfg(feature = "tracing")]
+    let _subscriber = tracing::subscriber::set_default(
+        tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::TRACE)
+            .with_ansi(false)
+            .with_writer({
+                let logs = logs.clone();
+                move || logs.clone()
+            })
+            .finish(),
+    );
+
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(1024);
+    let (stdin_tx, stdin_rx) = mpsc::channel::<StdinInput>(8);
+    // Split, so the secret is not in the arguments the audit record keeps
+    let config = TaskConfig::new("sh")
+        .args(["-c", "read pw; [ \"$pw\" = hunt\"er2\" ] && echo accepted"])
+        .enable_stdin(true);
+    let mut spawner = TaskSpawner::new("stdin_task".to_string(), config).set_stdin_input(stdin_rx);
+    spawner.start_direct(tx).await.unwrap();
+    stdin_tx.send(StdinInput::secret(SECRET)).await.unwrap();
+
+    let formatter = LogFormatter::new();
+    let mut output = vec![];
+    let mut formatted = String::new();
+    while let Some(event) = rx.recv().await {
+        formatted.push_str(&formatter.format(&event));
+        formatted.push_str(&format!("{event:?}\n"));
+        if let TaskEvent::Output { line, .. } = event {
+            output.push(line);
+        }
+    }
+
+    assert_eq!(output, ["accepted"]);
+    assert!(!formatted.contains(SECRET), "{formatted}");
+    let audit = format!("{:?}", spawner.audit().await);
+    assert!(!audit.contains(SECRET), "{audit}");
+    #[cfg(feature = "tracing")]
+    {
+        let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        assert!(!logs.contains(SECRET), "{logs}");
+    }
+}
@@ -0,0 +1,99 @@
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use crate::tasks::{
+    async_tokio::spawner::TaskSpawner,
+    config::{StdinScript, StdinScriptStep, TaskConfig},
+    event::{TaskEvent, TaskEventStopReason, TaskTerminateReason},
+};
+
+/// Runs `script` in the platform shell, with stdin enabled
+#[cfg(windows)]
+fn session(script: &str) -> TaskConfig {
+    TaskConfig::new("powershell")
+        .args(["-Command", script])
+        .enable_stdin(true)
+}
+
+/// Runs `script` in the platform shell, with stdin enabled
+#[cfg(unix)]
+fn session(script: &str) -> TaskConfig {
+    TaskConfig::new("sh")
+        .args(["-c", script])
+        .enable_stdin(true)
+}
+
+/// Collects the script steps, output lines and stop event of a task
+async fn run(config: TaskConfig) -> (Vec<u32>, Vec<String>, Option<i32>, TaskEventStopReason) {
+    let mut spawner = TaskSpawner::new("script".to_string(), config);
+    let (tx, mut rx) = mpsc::channel(100);
+    spawner.start_direct(tx).await.unwrap();
+
+    let mut steps = Vec::new();
+    let mut lines = Vec::new();
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while let Some(event) = rx.recv().await {
+            match event {
+                TaskEvent::ScriptStep { task_name, index } => {
+                    assert_eq!(&*task_name, "script");
+                    steps.push(index);
+                }
+                TaskEvent::Output { line, .. } => lines.push(line),
+                TaskEvent::Stopped {
+                    exit_code, reason, ..
+                } => return (steps, lines, exit_code, reason),
+                _ => {}
+            }
+        }
+        panic!("Event channel closed before Stopped");
+    })
+    .await
+    .expect("Task should stop within the timeout")
+}
+
+#[tokio::test]
+async fn answers_two_prompts_then_closes_stdin() {
+    #[cfg(windows)]
+    let config = session(
+        "Write-Output 'Name?'; $n = Read-Host; Write-Output 'Color?'; $c = Read-Host; Write-Output \"$n likes $c\"",
+    );
+    #[cfg(unix)]
+    let config = session(
+        "echo 'Name?'; read n; echo 'Color?'; read c; echo \"$n likes $c\"; read rest || echo eof",
+    );
+    let config = config.stdin_script(StdinScript::new([
+        StdinScriptStep::new("Name?", "Ada", 5000),
+        StdinScriptStep::new("Color?", "green", 5000),
+    ]));
+
+    let (steps, lines, exit_code, reason) = run(config).await;
+    assert_eq!(steps, [0, 1]);
+    assert_eq!(reason, TaskEventStopReason::Finished);
+    assert_eq!(exit_code, Some(0));
+    #[cfg(windows)]
+    assert_eq!(lines, ["Name?", "Color?", "Ada likes green"]);
+    // Stdin is closed once the script is done
+    #[cfg(unix)]
+    assert_eq!(lines, ["Name?", "Color?", "Ada likes green", "eof"]);
+}
+
+#[tokio::test]
+async fn missing_prompt_terminates_with_script_timeout() {
+    #[cfg(windows)]
+    let config = session("Write-Output 'Name?'; $n = Read-Host; Start-Sleep -Seconds 10");
+    #[cfg(unix)]
+    let config = session("echo 'Name?'; read n; sleep 10");
+    let config = config.stdin_script(StdinScript::new([
+        StdinScriptStep::new("Name?", "Ada", 5000),
+        StdinScriptStep::new("Color?", "green", 300),
+    ]));
+
+    let (steps, _, exit_code, reason) = run(config).await;
+    assert_eq!(steps, [0]);
+    assert_eq!(
+        reason,
+        TaskEventStopReason::Terminated(TaskTerminateReason::ScriptTimeout)
+    );
+    assert_eq!(exit_code, None);
+}
@@ -0,0 +1,36 @@
+use crate::tasks::async_tokio::direct::integration_tests::support::{collect_events, commands};
+use crate::tasks::event::{TaskEvent, TaskEventStopReason, TaskTerminateReason};
+
+#[tokio::test]
+async fn lifecycle_events_without_output() {
+    let events = collect_events(commands::trivial().stdio_inherit(true)).await;
+
+    assert!(matches!(events.first(), Some(TaskEvent::Started { .. })));
+    assert!(matches!(
+        events.last(),
+        Some(TaskEvent::Stopped {
+            exit_code: Some(0),
+            reason: TaskEventStopReason::Finished,
+            ..
+        })
+    ));
+    assert!(
+        !events
+            .iter()
+            .any(|event| matches!(event, TaskEvent::Output { .. }))
+    );
+}
+
+#[tokio::test]
+async fn timeout_terminates_inherited_task() {
+    let events = collect_events(commands::sleep(5).stdio_inherit(true).timeout_ms(200)).await;
+
+    assert!(matches!(events.first(), Some(TaskEvent::Started { .. })));
+    assert!(matches!(
+        events.last(),
+        Some(TaskEvent::Stopped {
+            reason: TaskEventStopReason::Terminated(TaskTerminateReason::Timeout),
+            ..
+        })
+    ));
+}
@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+
+use crate::tasks::{
+    async_tokio::spawner::TaskSpawner,
+    config::{StreamSource, TaskConfig},
+    event::{TaskEvent, TaskEventStopReason},
+};
+
+#[tokio::test]
+async fn echo_events_are_collected() {
+    #[cfg(windows)]
+    let config = TaskConfig::new("powershell").args(["-Command", "echo hello"]);
+    #[cfg(unix)]
+    let config = TaskConfig::new("echo").args(["hello"]);
+    let mut spawner = TaskSpawner::new("stream".to_string(), config);
+
+    let events = spawner.start_direct_stream(100).await.unwrap();
+    let events: Vec<TaskEvent> = tokio::time::timeout(Duration::from_secs(10), events.collect())
+        .await
+        .expect("Stream should end after the task stops");
+
+    assert_eq!(events.len(), 3, "{events:?}");
+    assert!(matches!(events[0], TaskEvent::Started { .. }));
+    assert!(matches!(
+        &events[1],
+        TaskEvent::Output { line, src: StreamSource::Stdout, seq: 0, .. } if line == "hello"
+    ));
+    assert!(matches!(
+        events[2],
+        TaskEvent::Stopped {
+            exit_code: Some(0),
+            reason: TaskEventStopReason::Finished,
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn invalid_config_fails_to_start() {
+    let mut spawner = TaskSpawner::new("stream".to_string(), TaskConfig::new(""));
+
+    assert!(spawner.start_direct_stream(100).await.is_err());
+}
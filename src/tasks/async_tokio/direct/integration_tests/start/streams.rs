@@ -0,0 +1,209 @@
+use std::time::Duration;
+
+use tokio::process::Command;
+use tokio::sync::{mpsc, watch};
+
+use crate::tasks::async_tokio::direct::command::setup_command;
+use crate::tasks::async_tokio::direct::integration_tests::support::commands;
+use crate::tasks::config::{StreamSource, TaskConfig};
+use crate::tasks::{async_tokio::spawner::TaskSpawner, event::TaskEvent};
+
+/// Spawns the process and its I/O watchers, returning the watcher names.
+async fn watcher_names(config: TaskConfig, with_stdin_rx: bool) -> Vec<&'static str> {
+    let mut spawner = TaskSpawner::new("streams_task".to_string(), config);
+    if with_stdin_rx {
+        let (_stdin_tx, stdin_rx) = mpsc::channel::<String>(1);
+        spawner = spawner.set_stdin(stdin_rx);
+    }
+
+    let mut cmd = Command::new(&spawner.config.command);
+    setup_command(&mut cmd, &spawner.config, spawner.stdin_rx.is_some());
+    let mut child = cmd.spawn().unwrap();
+
+    let (tx, _rx) = mpsc::channel::<TaskEvent>(16);
+    let (terminator_tx, terminator_rx) = watch::channel(false);
+    let (mut watchers, _) = spawner.spawn_io_watchers(&mut child, &tx, terminator_rx);
+
+    child.wait().await.unwrap();
+    terminator_tx.send_replace(true);
+    let names = watchers.pending();
+    watchers.join_all(Duration::from_secs(5)).await.unwrap();
+    names
+}
+
+fn config() -> TaskConfig {
+    #[cfg(windows)]
+    let config = TaskConfig::new("cmd").args(["/C", "exit 0"]);
+    #[cfg(unix)]
+    let config = TaskConfig::new("true");
+    config.use_process_group(false)
+}
+
+#[tokio::test]
+async fn watchers_match_captured_streams() {
+    let cases = [
+        (true, true, false, vec!["stdout", "stderr"]),
+        (true, false, false, vec!["stdout"]),
+        (false, true, false, vec!["stderr"]),
+        (false, false, false, vec![]),
+        (true, true, true, vec!["stdout", "stderr", "stdin"]),
+        (false, false, true, vec!["stdin"]),
+    ];
+    for (stdout, stderr, stdin, expected) in cases {
+        let config = config()
+            .capture_stdout(stdout)
+            .capture_stderr(stderr)
+            .enable_stdin(stdin);
+        assert_eq!(
+            watcher_names(config, stdin).await,
+            expected,
+            "stdout: {stdout}, stderr: {stderr}, stdin: {stdin}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn stdin_without_receiver_spawns_no_watcher() {
+    let config = config().enable_stdin(true);
+    assert_eq!(watcher_names(config, false).await, ["stdout", "stderr"]);
+}
+
+#[tokio::test]
+async fn stdin_receiver_without_enable_spawns_no_watcher() {
+    let config = config().enable_stdin(false);
+    assert_eq!(watcher_names(config, true).await, ["stdout", "stderr"]);
+}
+
+#[tokio::test]
+async fn uncaptured_stream_emits_no_output() {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(1024);
+    #[cfg(windows)]
+    let config = TaskConfig::new("powershell").args([
+        "-Command",
+        "Write-Output out; [Console]::Error.WriteLine('err')",
+    ]);
+    #[cfg(unix)]
+    let config = TaskConfig::new("sh").args(["-c", "echo out; echo err >&2"]);
+    let config = config.capture_stdout(false);
+
+    let mut spawner = TaskSpawner::new("uncaptured_task".to_string(), config);
+    spawner.start_direct(tx).await.unwrap();
+
+    let mut outputs = vec![];
+    while let Some(event) = rx.recv().await {
+        if let TaskEvent::Output { line, src, .. } = event {
+            outputs.push((line, src));
+        }
+    }
+    assert_eq!(outputs, [("err".to_string(), StreamSource::Stderr)]);
+}
+
+#[tokio::test]
+async fn small_output_buffer_keeps_long_lines_whole() {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(1024);
+    #[cfg(windows)]
+    let config = TaskConfig::new("powershell").args([
+        "-Command",
+        "Write-Output ('x' * 5000); Write-Output short",
+    ]);
+    #[cfg(unix)]
+    let config = TaskConfig::new("sh").args(["-c", "printf '%5000s\\nshort\\n' '' | tr ' ' x"]);
+    let config = config.output_buffer_bytes(256);
+
+    let mut spawner = TaskSpawner::new("small_buffer_task".to_string(), config);
+    spawner.start_direct(tx).await.unwrap();
+
+    let mut lines = vec![];
+    while let Some(event) = rx.recv().await {
+        if let TaskEvent::Output { line, .. } = event {
+            lines.push(line);
+        }
+    }
+    assert_eq!(lines, ["x".repeat(5000), "short".to_string()]);
+}
+
+#[tokio::test]
+async fn yielding_watcher_delivers_every_line_in_order() {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(1024);
+    let config = commands::lines(1000).yield_every_n_lines(3);
+    let mut spawner = TaskSpawner::new("yielding_task".to_string(), config);
+    spawner.start_direct(tx).await.unwrap();
+
+    let mut lines = vec![];
+    while let Some(event) = rx.recv().await {
+        if let TaskEvent::Output { line, .. } = event {
+            lines.push(line.trim().parse::<u64>().unwrap());
+        }
+    }
+    assert_eq!(lines, (1..=1000).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn preserve_interleaving_reads_both_streams_in_one_watcher() {
+    let cases = [
+        (true, true, vec!["output"]),
+        (true, false, vec!["stdout"]),
+        (false, true, vec!["stderr"]),
+    ];
+    for (stdout, stderr, expected) in cases {
+        let config = config()
+            .capture_stdout(stdout)
+            .capture_stderr(stderr)
+            .preserve_interleaving(true);
+        assert_eq!(
+            watcher_names(config, false).await,
+            expected,
+            "stdout: {stdout}, stderr: {stderr}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn sequence_numbers_restore_write_order_across_streams() {
+    #[cfg(windows)]
+    let config = TaskConfig::new("powershell").args([
+        "-Command",
+        "0..19 | % { if ($_ % 2) { [Console]::Error.WriteLine($_) } else { [Console]::Out.WriteLine($_) }; Start-Sleep -Milliseconds 10 }",
+    ]);
+    #[cfg(unix)]
+    let config = TaskConfig::new("sh").args([
+        "-c",
+        "i=0; while [ $i -lt 20 ]; do if [ $((i % 2)) -eq 0 ]; then echo $i; else echo $i >&2; fi; sleep 0.01; i=$((i + 1)); done",
+    ]);
+
+    for preserve in [false, true] {
+        let (tx, mut rx) = mpsc::channel::<TaskEvent>(1024);
+        let config = config.clone().preserve_interleaving(preserve);
+        let mut spawner = TaskSpawner::new("interleaving_task".to_string(), config);
+        spawner.start_direct(tx).await.unwrap();
+
+        let mut outputs = vec![];
+        while let Some(event) = rx.recv().await {
+            if let TaskEvent::Output { line, src, seq, .. } = event {
+                let written: usize = line.trim().parse().unwrap();
+                let expected_src = if written.is_multiple_of(2) {
+                    StreamSource::Stdout
+                } else {
+                    StreamSource::Stderr
+                };
+                assert_eq!(src, expected_src);
+                outputs.push((seq, written));
+            }
+        }
+        assert_eq!(outputs.len(), 20, "preserve: {preserve}");
+        if preserve {
+            // A single watcher sends lines in the order it numbers them
+            assert!(outputs.is_sorted(), "preserve: {preserve}, {outputs:?}");
+        }
+
+        outputs.sort_unstable();
+        let seqs: Vec<u64> = outputs.iter().map(|(seq, _)| *seq).collect();
+        assert_eq!(seqs, (0..20).collect::<Vec<_>>(), "preserve: {preserve}");
+        // Best-effort: allow the odd pair of lines read out of write order
+        let swapped = outputs
+            .windows(2)
+            .filter(|pair| pair[0].1 > pair[1].1)
+            .count();
+        assert!(swapped <= 2, "preserve: {preserve}, {outputs:?}");
+    }
+}
@@ -0,0 +1,77 @@
+use std::{sync::atomic::Ordering, time::Duration};
+
+use tokio::sync::mpsc;
+
+use crate::tasks::{
+    async_tokio::spawner::TaskSpawner, config::TaskConfig, event::TaskEvent, state::TaskState,
+};
+
+/// Receives events until every sender is gone, returning whether `Stopped` was among them
+async fn saw_stopped(rx: &mut mpsc::Receiver<TaskEvent>) -> bool {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        let mut stopped = false;
+        while let Some(event) = rx.recv().await {
+            stopped |= matches!(event, TaskEvent::Stopped { .. });
+        }
+        stopped
+    })
+    .await
+    .expect("Event channel should close once the watchers are aborted")
+}
+
+#[tokio::test]
+async fn dropping_spawner_cancels_all_watchers() {
+    let config = TaskConfig::new("cat").enable_stdin(true);
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(64);
+    let (_stdin_tx, stdin_rx) = mpsc::channel::<String>(8);
+    let mut spawner = TaskSpawner::new("structured".to_string(), config)
+        .set_stdin(stdin_rx)
+        .set_structured_concurrency(true);
+    spawner.start_direct(tx).await.unwrap();
+    assert!(matches!(rx.recv().await, Some(TaskEvent::Started { .. })));
+
+    let cancelled = spawner.watchers.cancelled();
+    drop(spawner);
+
+    // stdout, stderr, stdin and wait
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while cancelled.load(Ordering::Relaxed) < 4 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("Every watcher should observe the cancellation");
+    assert_eq!(cancelled.load(Ordering::Relaxed), 4);
+    assert!(!saw_stopped(&mut rx).await);
+}
+
+#[tokio::test]
+async fn shutdown_aborts_running_task() {
+    let config = TaskConfig::new("sleep").args(["30"]);
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(64);
+    let mut spawner = TaskSpawner::new("shutdown".to_string(), config);
+    spawner.start_direct(tx).await.unwrap();
+    let cancelled = spawner.watchers.cancelled();
+
+    spawner.shutdown().await.unwrap();
+
+    assert_eq!(spawner.get_state().await, TaskState::Finished);
+    assert_eq!(spawner.get_process_id().await, None);
+    assert!(!saw_stopped(&mut rx).await);
+    assert_eq!(cancelled.load(Ordering::Relaxed), 3);
+}
+
+#[tokio::test]
+async fn completed_task_is_not_cancelled() {
+    let config = TaskConfig::new("echo").args(["done"]);
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(64);
+    let mut spawner =
+        TaskSpawner::new("completed".to_string(), config).set_structured_concurrency(true);
+    spawner.start_direct(tx).await.unwrap();
+    let cancelled = spawner.watchers.cancelled();
+
+    assert!(saw_stopped(&mut rx).await);
+    spawner.shutdown().await.unwrap();
+    drop(spawner);
+    assert_eq!(cancelled.load(Ordering::Relaxed), 0);
+}
@@ -0,0 +1,172 @@
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use crate::tasks::async_tokio::direct::integration_tests::support::commands;
+use crate::tasks::{
+    async_tokio::{
+        spawner::TaskSpawner,
+        task_group::{FailurePolicy, TaskGroup},
+    },
+    config::TaskConfig,
+    error::TaskError,
+    event::{TaskEvent, TaskEventStopReason, TaskTerminateReason},
+};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A command that exits with code 1 after a short delay
+fn failing() -> TaskConfig {
+    if cfg!(windows) {
+        TaskConfig::new("cmd").args(["/C", "ping -n 2 127.0.0.1 >nul & exit 1"])
+    } else {
+        TaskConfig::new("sh").args(["-c", "sleep 0.2; exit 1"])
+    }
+}
+
+/// Termination requests in the audit record of a stopped task
+async fn terminate_requests(spawner: &TaskSpawner) -> Vec<TaskTerminateReason> {
+    spawner
+        .audit()
+        .await
+        .expect("Task should have stopped")
+        .terminate_requests
+}
+
+#[tokio::test]
+async fn failure_cancels_siblings() {
+    let group = TaskGroup::new().set_failure_policy(FailurePolicy::CancelSiblings);
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(64);
+
+    let mut spawners = [
+        TaskSpawner::new("first".to_string(), commands::sleep(30)),
+        TaskSpawner::new("failing".to_string(), failing()),
+        TaskSpawner::new("last".to_string(), commands::sleep(30)),
+    ];
+    for spawner in &mut spawners {
+        group.start(spawner, tx.clone()).await.unwrap();
+    }
+    drop(tx);
+
+    let summary = tokio::time::timeout(TIMEOUT, group.wait_all())
+        .await
+        .expect("Siblings should be cancelled");
+
+    let stopped: Vec<_> = summary
+        .per_task
+        .iter()
+        .map(|result| (&*result.task_name, result.exit_code, result.reason.clone()))
+        .collect();
+    let cancelled = TaskEventStopReason::Terminated(TaskTerminateReason::Cleanup { message: None });
+    assert_eq!(
+        stopped,
+        [
+            ("first", None, cancelled.clone()),
+            ("failing", Some(1), TaskEventStopReason::Finished),
+            ("last", None, cancelled),
+        ]
+    );
+    let failure = summary.first_failure.expect("A task failed");
+    assert_eq!(&*failure.task_name, "failing");
+
+    // Every event was forwarded, including the three Stopped events
+    let mut stopped_events = 0;
+    while let Some(event) = rx.recv().await {
+        if matches!(event, TaskEvent::Stopped { .. }) {
+            stopped_events += 1;
+        }
+    }
+    assert_eq!(stopped_events, 3);
+
+    // Cancellations are recorded like requests sent to the spawner
+    assert_eq!(
+        terminate_requests(&spawners[0]).await,
+        [TaskTerminateReason::Cleanup { message: None }]
+    );
+    assert!(terminate_requests(&spawners[1]).await.is_empty());
+
+    // The cancelled group starts no new task
+    let mut late = TaskSpawner::new("late".to_string(), commands::trivial());
+    let (tx, _rx) = mpsc::channel::<TaskEvent>(64);
+    assert!(matches!(
+        group.start(&mut late, tx).await,
+        Err(TaskError::Handle(_))
+    ));
+}
+
+#[tokio::test]
+async fn terminate_all_is_idempotent_without_process_groups() {
+    let group = TaskGroup::new();
+    let (tx, _rx) = mpsc::channel::<TaskEvent>(64);
+
+    let mut spawners = [
+        TaskSpawner::new(
+            "a".to_string(),
+            commands::sleep(30).use_process_group(false),
+        ),
+        TaskSpawner::new(
+            "b".to_string(),
+            commands::sleep(30).use_process_group(false),
+        ),
+    ];
+    for spawner in &mut spawners {
+        group.start(spawner, tx.clone()).await.unwrap();
+    }
+
+    let reason = TaskTerminateReason::UserRequested { message: None };
+    let (first, second) = tokio::join!(
+        group.terminate_all(reason.clone()),
+        group.terminate_all(reason.clone())
+    );
+    assert_eq!(first + second, 2);
+    assert_eq!(group.terminate_all(reason.clone()).await, 0);
+
+    let summary = tokio::time::timeout(TIMEOUT, group.wait_all())
+        .await
+        .expect("Tasks should be terminated");
+    assert!(summary.is_success(), "{summary:?}");
+    assert_eq!(summary.per_task.len(), 2);
+    assert!(
+        summary
+            .per_task
+            .iter()
+            .all(|result| result.reason == TaskEventStopReason::Terminated(reason.clone()))
+    );
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn members_are_terminated_concurrently() {
+    const GRACE: Duration = Duration::from_millis(1_000);
+
+    let group = TaskGroup::new();
+    let (tx, _rx) = mpsc::channel::<TaskEvent>(64);
+    // Both ignore the graceful signal, so each is killed after its grace period
+    let ignoring = || {
+        TaskConfig::new("sh")
+            .args(["-c", "trap '' TERM; while :; do sleep 0.1; done"])
+            .graceful_shutdown_ms(GRACE.as_millis().try_into().unwrap())
+    };
+    let mut spawners = [
+        TaskSpawner::new("first".to_string(), ignoring()),
+        TaskSpawner::new("second".to_string(), ignoring()),
+    ];
+    for spawner in &mut spawners {
+        group.start(spawner, tx.clone()).await.unwrap();
+    }
+    // Lets the shells install their traps
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let started = std::time::Instant::now();
+    let terminated = group
+        .terminate_all(TaskTerminateReason::UserRequested { message: None })
+        .await;
+    tokio::time::timeout(TIMEOUT, group.wait_all())
+        .await
+        .expect("Members should be killed after the grace period");
+    let took = started.elapsed();
+
+    assert_eq!(terminated, 2);
+    assert!(took >= GRACE, "{took:?}");
+    assert!(took < GRACE * 2 - GRACE / 4, "{took:?}");
+}
@@ -0,0 +1,93 @@
+use std::path::{Path, PathBuf};
+
+use tokio::sync::mpsc;
+
+use crate::tasks::{async_tokio::spawner::TaskSpawner, config::TaskConfig, event::TaskEvent};
+
+/// A command that prints its working directory, creates `marker` in it and exits with `code`
+fn print_dir_and_exit(code: i32) -> TaskConfig {
+    #[cfg(windows)]
+    let config = TaskConfig::new("cmd").args([
+        "/C".to_string(),
+        format!("cd & echo x> marker & exit {code}"),
+    ]);
+    #[cfg(unix)]
+    let config =
+        TaskConfig::new("sh").args(["-c".to_string(), format!("pwd; touch marker; exit {code}")]);
+    config
+}
+
+/// Runs the task to completion, returning the directory from `Started` and
+/// the directory the process printed, both canonicalized while they exist
+async fn run(config: TaskConfig) -> (TaskSpawner, PathBuf, PathBuf) {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(64);
+    let mut spawner = TaskSpawner::new("scratch".to_string(), config);
+    spawner.start_direct(tx).await.unwrap();
+
+    let mut started_dir = None;
+    let mut cwd = None;
+    while let Some(event) = rx.recv().await {
+        match event {
+            TaskEvent::Started { temp_dir, .. } => {
+                let dir = PathBuf::from(temp_dir.expect("Started without temp_dir"));
+                assert!(dir.is_dir());
+                started_dir = Some(dir.canonicalize().unwrap());
+            }
+            TaskEvent::Output { line, .. } if cwd.is_none() => {
+                cwd = Some(Path::new(line.trim()).canonicalize().unwrap());
+            }
+            _ => {}
+        }
+    }
+    (spawner, started_dir.unwrap(), cwd.unwrap())
+}
+
+#[tokio::test]
+async fn process_runs_in_temp_dir_removed_on_success() {
+    let config = print_dir_and_exit(0).temp_working_dir_prefix("tcrm-test-");
+    let (spawner, started_dir, cwd) = run(config).await;
+
+    assert_eq!(cwd, started_dir);
+    assert!(
+        started_dir
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("tcrm-test-")
+    );
+    assert!(!started_dir.exists(), "{started_dir:?} was not removed");
+    let info = spawner.get_task_info().await;
+    assert!(info.temp_dir.is_some_and(|dir| !dir.exists()));
+}
+
+#[tokio::test]
+async fn temp_dir_kept_on_failure_when_configured() {
+    let config = print_dir_and_exit(3)
+        .temp_working_dir(true)
+        .keep_temp_dir_on_failure(true);
+    let (_spawner, started_dir, _) = run(config).await;
+
+    assert!(started_dir.join("marker").is_file());
+    std::fs::remove_dir_all(&started_dir).unwrap();
+}
+
+#[tokio::test]
+async fn temp_dir_removed_on_failure_by_default() {
+    let config = print_dir_and_exit(3).temp_working_dir(true);
+    let (_spawner, started_dir, _) = run(config).await;
+
+    assert!(!started_dir.exists(), "{started_dir:?} was not removed");
+}
+
+#[tokio::test]
+async fn dropped_prepared_task_removes_temp_dir() {
+    let mut spawner = TaskSpawner::new(
+        "scratch".to_string(),
+        print_dir_and_exit(0).temp_working_dir(true),
+    );
+    let prepared = spawner.prepare().await.unwrap();
+    drop(prepared);
+
+    let dir = spawner.get_task_info().await.temp_dir.unwrap();
+    assert!(!dir.exists(), "{dir:?} was not removed");
+}
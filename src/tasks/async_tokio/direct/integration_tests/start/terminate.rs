@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use crate::tasks::{
+    async_tokio::spawner::{TaskSpawner, TerminateOutcome},
+    config::TaskConfig,
+    event::{TaskEvent, TaskEventStopReason, TaskTerminateReason},
+};
+
+async fn wait_for_stop(rx: &mut mpsc::Receiver<TaskEvent>) -> TaskEventStopReason {
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while let Some(event) = rx.recv().await {
+            if let TaskEvent::Stopped { reason, .. } = event {
+                return reason;
+            }
+        }
+        panic!("Channel closed without a Stopped event");
+    })
+    .await
+    .expect("Task should stop within the timeout")
+}
+
+#[tokio::test]
+async fn first_request_is_sent() {
+    let config = TaskConfig::new("sleep").args(["10"]);
+    let mut spawner = TaskSpawner::new("terminate".to_string(), config);
+    let (tx, mut rx) = mpsc::channel(100);
+    spawner.start_direct(tx).await.unwrap();
+
+    let outcome = spawner
+        .send_terminate_signal(TaskTerminateReason::UserRequested { message: None })
+        .await
+        .unwrap();
+    assert_eq!(outcome, TerminateOutcome::SignalSent);
+    assert_eq!(
+        wait_for_stop(&mut rx).await,
+        TaskEventStopReason::Terminated(TaskTerminateReason::UserRequested { message: None })
+    );
+}
+
+#[tokio::test]
+async fn repeated_request_keeps_first_reason() {
+    let config = TaskConfig::new("sleep").args(["10"]);
+    let mut spawner = TaskSpawner::new("terminate".to_string(), config);
+    let (tx, mut rx) = mpsc::channel(100);
+    spawner.start_direct(tx).await.unwrap();
+
+    spawner
+        .send_terminate_signal(TaskTerminateReason::UserRequested { message: None })
+        .await
+        .unwrap();
+    let outcome = spawner
+        .send_terminate_signal(TaskTerminateReason::Cleanup { message: None })
+        .await
+        .unwrap();
+    assert_eq!(outcome, TerminateOutcome::AlreadyTerminating);
+    assert_eq!(
+        wait_for_stop(&mut rx).await,
+        TaskEventStopReason::Terminated(TaskTerminateReason::UserRequested { message: None })
+    );
+}
+
+#[tokio::test]
+async fn request_after_timeout_is_already_terminating() {
+    // Ignores SIGTERM for the grace period, so the task is still stopping
+    let config = TaskConfig::new("sh")
+        .args(["-c", "trap '' TERM; echo ready; sleep 10"])
+        .timeout_ms(100)
+        .graceful_shutdown_ms(5_000);
+    let mut spawner = TaskSpawner::new("terminate".to_string(), config);
+    let (tx, _rx) = mpsc::channel(100);
+    spawner.start_direct(tx).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let outcome = spawner
+        .send_terminate_signal(TaskTerminateReason::UserRequested { message: None })
+        .await
+        .unwrap();
+    assert_eq!(outcome, TerminateOutcome::AlreadyTerminating);
+    spawner.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn exited_task_is_already_finished() {
+    let config = TaskConfig::new("echo").args(["done"]);
+    let mut spawner = TaskSpawner::new("terminate".to_string(), config);
+    let (tx, mut rx) = mpsc::channel(100);
+    spawner.start_direct(tx).await.unwrap();
+    assert_eq!(wait_for_stop(&mut rx).await, TaskEventStopReason::Finished);
+
+    let outcome = spawner
+        .send_terminate_signal(TaskTerminateReason::UserRequested { message: None })
+        .await
+        .unwrap();
+    assert_eq!(outcome, TerminateOutcome::AlreadyFinished);
+}
+
+#[tokio::test]
+async fn terminated_task_is_already_finished() {
+    let config = TaskConfig::new("sleep").args(["10"]);
+    let mut spawner = TaskSpawner::new("terminate".to_string(), config);
+    let (tx, mut rx) = mpsc::channel(100);
+    spawner.start_direct(tx).await.unwrap();
+    spawner
+        .send_terminate_signal(TaskTerminateReason::UserRequested { message: None })
+        .await
+        .unwrap();
+    wait_for_stop(&mut rx).await;
+
+    let outcome = spawner
+        .send_terminate_signal(TaskTerminateReason::UserRequested { message: None })
+        .await
+        .unwrap();
+    assert_eq!(outcome, TerminateOutcome::AlreadyFinished);
+}
+
+#[tokio::test]
+async fn message_is_reported_in_stopped() {
+    let config = TaskConfig::new("sleep").args(["10"]);
+    let mut spawner = TaskSpawner::new("terminate".to_string(), config);
+    let (tx, mut rx) = mpsc::channel(100);
+    spawner.start_direct(tx).await.unwrap();
+
+    let reason = TaskTerminateReason::UserRequested {
+        message: Some("stopped by alice: redeploying".to_string()),
+    };
+    spawner.send_terminate_signal(reason.clone()).await.unwrap();
+    let TaskEventStopReason::Terminated(stopped) = wait_for_stop(&mut rx).await else {
+        panic!("Task should be terminated");
+    };
+    assert_eq!(stopped, reason);
+    assert_eq!(stopped.message(), Some("stopped by alice: redeploying"));
+}
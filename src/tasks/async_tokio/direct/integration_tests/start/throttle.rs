@@ -0,0 +1,82 @@
+use std::time::Instant;
+
+use tokio::sync::mpsc;
+
+use crate::tasks::async_tokio::direct::integration_tests::support::commands;
+use crate::tasks::{async_tokio::spawner::TaskSpawner, config::TaskConfig, event::TaskEvent};
+
+const LINES: u64 = 50_000;
+const BUDGET: u32 = 100;
+
+/// Runs the task to completion and returns all of its events, with the seconds it took
+async fn run(config: TaskConfig) -> (Vec<TaskEvent>, u64) {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(1024);
+    let mut spawner = TaskSpawner::new("throttle".to_string(), config);
+    let started = Instant::now();
+    spawner.start_direct(tx).await.unwrap();
+
+    let mut events = vec![];
+    while let Some(event) = rx.recv().await {
+        events.push(event);
+    }
+    (events, started.elapsed().as_secs())
+}
+
+#[tokio::test]
+async fn burst_is_limited_to_budget_and_summarized() {
+    let config = commands::lines(LINES)
+        .max_output_events_per_sec(BUDGET)
+        .ready_indicator("49999");
+    let (events, secs) = run(config).await;
+
+    let outputs = events
+        .iter()
+        .filter(|event| matches!(event, TaskEvent::Output { .. }))
+        .count() as u64;
+    let summaries: Vec<(u64, u64)> = events
+        .iter()
+        .filter_map(|event| match event {
+            TaskEvent::OutputSuppressed { count, bytes, .. } => Some((*count, *bytes)),
+            _ => None,
+        })
+        .collect();
+    let suppressed: u64 = summaries.iter().map(|(count, _)| count).sum();
+
+    // Each second, including the partial ones at both ends, sends at most the budget
+    assert!(outputs >= u64::from(BUDGET));
+    assert!(
+        outputs <= u64::from(BUDGET) * (secs + 2),
+        "{outputs} outputs in {secs}s"
+    );
+    assert!(!summaries.is_empty());
+    assert!(summaries.len() as u64 <= secs + 2);
+    assert!(
+        summaries
+            .iter()
+            .all(|(count, bytes)| *count > 0 && bytes >= count)
+    );
+    assert_eq!(outputs + suppressed, LINES);
+
+    // The ready indicator near the end of the burst is still matched
+    assert!(
+        events
+            .iter()
+            .any(|event| matches!(event, TaskEvent::Ready { .. }))
+    );
+}
+
+#[tokio::test]
+async fn every_line_is_sent_without_limit() {
+    let (events, _) = run(commands::lines(1000)).await;
+
+    let outputs = events
+        .iter()
+        .filter(|event| matches!(event, TaskEvent::Output { .. }))
+        .count();
+    assert_eq!(outputs, 1000);
+    assert!(
+        !events
+            .iter()
+            .any(|event| matches!(event, TaskEvent::OutputSuppressed { .. }))
+    );
+}
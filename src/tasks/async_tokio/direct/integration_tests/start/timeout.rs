@@ -1,6 +1,5 @@
 use tokio::sync::mpsc;
 
-#[cfg(windows)]
 use crate::tasks::config::TaskConfig;
 use crate::tasks::{
     async_tokio::spawner::TaskSpawner,
@@ -31,8 +30,8 @@ async fn terminated_task() {
     let mut stopped = false;
     while let Some(event) = rx.recv().await {
         match event {
-            TaskEvent::Started { task_name } => {
-                assert_eq!(task_name, "sleep_with_timeout_task");
+            TaskEvent::Started { task_name, .. } => {
+                assert_eq!(&*task_name, "sleep_with_timeout_task");
                 started = true;
             }
 
@@ -40,8 +39,9 @@ async fn terminated_task() {
                 task_name,
                 exit_code,
                 reason,
+                ..
             } => {
-                assert_eq!(task_name, "sleep_with_timeout_task");
+                assert_eq!(&*task_name, "sleep_with_timeout_task");
                 assert_eq!(exit_code, None);
                 assert_eq!(
                     reason,
@@ -75,7 +75,7 @@ async fn error_zero_timeout() {
 
     // Should receive an error event
     if let Some(TaskEvent::Error { task_name, error }) = rx.recv().await {
-        assert_eq!(task_name, "timeout_task");
+        assert_eq!(&*task_name, "timeout_task");
         assert!(matches!(error, TaskError::InvalidConfiguration(_)));
     } else {
         panic!("Expected TaskEvent::Error with InvalidConfiguration");
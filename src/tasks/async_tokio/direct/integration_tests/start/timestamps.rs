@@ -0,0 +1,42 @@
+use std::time::{Duration, SystemTime};
+
+use tokio::sync::mpsc;
+
+use crate::tasks::{
+    async_tokio::spawner::TaskSpawner,
+    config::{StreamSource, TaskConfig},
+    event::TaskEvent,
+};
+
+#[tokio::test]
+async fn lines_are_timestamped_when_read() {
+    let config = TaskConfig::new("sh").args(["-c", "echo one; sleep 0.3; echo two; echo err >&2"]);
+    let mut spawner = TaskSpawner::new("timestamps".to_string(), config);
+    let (tx, mut rx) = mpsc::channel(100);
+    let before_start = SystemTime::now();
+    spawner.start_direct(tx).await.unwrap();
+
+    // The consumer only drains the channel once the task is done
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    let drained_at = SystemTime::now();
+
+    let mut stdout = vec![];
+    let mut stderr = vec![];
+    while let Some(event) = rx.recv().await {
+        if let TaskEvent::Output { src, timestamp, .. } = event {
+            match src {
+                StreamSource::Stdout => stdout.push(timestamp),
+                StreamSource::Stderr => stderr.push(timestamp),
+            }
+        }
+    }
+
+    assert_eq!(stdout.len(), 2);
+    assert_eq!(stderr.len(), 1);
+    assert!(stdout.is_sorted());
+    assert!(before_start <= stdout[0]);
+    let gap = stdout[1].duration_since(stdout[0]).unwrap();
+    assert!(gap >= Duration::from_millis(250), "{gap:?}");
+    let waited = drained_at.duration_since(stdout[1]).unwrap();
+    assert!(waited >= Duration::from_millis(500), "{waited:?}");
+}
@@ -0,0 +1,94 @@
+use tokio::sync::mpsc;
+
+use crate::tasks::config::{StreamSource, TaskConfig};
+use crate::tasks::{
+    async_tokio::{spawner::TaskSpawner, transform::OutputTransform},
+    event::TaskEvent,
+};
+
+/// Runs the task to completion, returning its output lines and whether it became ready
+async fn run(config: TaskConfig, transform: OutputTransform) -> (Vec<(StreamSource, String)>, bool) {
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(100);
+    let mut spawner =
+        TaskSpawner::new("transform".to_string(), config).set_output_transform(transform);
+    spawner.start_direct(tx).await.unwrap();
+
+    let mut lines = vec![];
+    let mut ready = false;
+    while let Some(event) = rx.recv().await {
+        match event {
+            TaskEvent::Output { line, src, .. } => lines.push((src, line)),
+            TaskEvent::Ready { .. } => ready = true,
+            TaskEvent::Stopped { .. } => break,
+            _ => {}
+        }
+    }
+    (lines, ready)
+}
+
+fn redact_secret() -> OutputTransform {
+    OutputTransform::new(|line, _src| {
+        if line.starts_with("debug") {
+            None
+        } else {
+            Some(line.replace("s3cr3t", "[REDACTED]"))
+        }
+    })
+}
+
+#[tokio::test]
+async fn transform_redacts_and_drops_lines() {
+    let config = TaskConfig::new("sh").args([
+        "-c",
+        "echo token=s3cr3t; echo debug s3cr3t; echo s3cr3t on stderr >&2; echo plain",
+    ]);
+
+    let (lines, _) = run(config, redact_secret()).await;
+
+    assert!(lines.iter().all(|(_, line)| !line.contains("s3cr3t")));
+    let stdout: Vec<_> = lines
+        .iter()
+        .filter(|(src, _)| *src == StreamSource::Stdout)
+        .map(|(_, line)| line.as_str())
+        .collect();
+    assert_eq!(stdout, ["token=[REDACTED]", "plain"]);
+    assert!(lines.contains(&(StreamSource::Stderr, "[REDACTED] on stderr".to_string())));
+}
+
+#[tokio::test]
+async fn ready_indicator_matches_original_line_by_default() {
+    let config = TaskConfig::new("sh")
+        .args(["-c", "echo debug listening; echo done"])
+        .ready_indicator("listening");
+
+    let (lines, ready) = run(config, redact_secret()).await;
+
+    assert!(ready, "Dropped line should still mark the task ready");
+    assert_eq!(lines, [(StreamSource::Stdout, "done".to_string())]);
+}
+
+#[tokio::test]
+async fn ready_indicator_matches_transformed_line_before_ready_check() {
+    let config = TaskConfig::new("sh")
+        .args(["-c", "echo debug listening; echo done"])
+        .ready_indicator("listening");
+
+    let (lines, ready) = run(config, redact_secret().before_ready_check(true)).await;
+
+    assert!(!ready, "Dropped line should not mark the task ready");
+    assert_eq!(lines, [(StreamSource::Stdout, "done".to_string())]);
+}
+
+#[tokio::test]
+async fn panicking_transform_is_disabled() {
+    let config = TaskConfig::new("sh").args(["-c", "echo one; echo boom; echo three"]);
+    let transform = OutputTransform::new(|line, _src| {
+        assert_ne!(line, "boom", "transform panics on boom");
+        Some(line.to_uppercase())
+    });
+
+    let (lines, _) = run(config, transform).await;
+
+    let lines: Vec<_> = lines.iter().map(|(_, line)| line.as_str()).collect();
+    assert_eq!(lines, ["ONE", "boom", "three"]);
+}
@@ -0,0 +1,150 @@
+use std::io;
+
+use tokio::sync::mpsc;
+
+use crate::tasks::async_tokio::direct::integration_tests::support::unique_temp_dir;
+use crate::tasks::async_tokio::direct::plan::CommandLookup;
+use crate::tasks::async_tokio::direct::start::spawn_error;
+use crate::tasks::config::TaskConfig;
+use crate::tasks::error::TaskError;
+use crate::tasks::{async_tokio::spawner::TaskSpawner, event::TaskEvent};
+
+#[tokio::test]
+async fn removed_working_dir_is_reported_by_name() {
+    let dir = unique_temp_dir("removed_working_dir");
+    let dir_str = dir.to_str().unwrap().to_string();
+
+    #[cfg(windows)]
+    let config = TaskConfig::new("cmd")
+        .args(["/C", "echo hello"])
+        .working_dir(&dir_str);
+    #[cfg(unix)]
+    let config = TaskConfig::new("echo").args(["hello"]).working_dir(&dir_str);
+
+    let mut spawner = TaskSpawner::new("removed_dir_task".to_string(), config);
+    std::fs::remove_dir(&dir).unwrap();
+
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(10);
+    let result = spawner.start_direct(tx).await;
+    match result {
+        Err(TaskError::InvalidConfiguration(msg)) => assert!(msg.contains(&dir_str)),
+        other => panic!("Expected InvalidConfiguration naming the directory, got {other:?}"),
+    }
+
+    match rx.recv().await {
+        Some(TaskEvent::Error {
+            error: TaskError::InvalidConfiguration(msg),
+            ..
+        }) => assert!(msg.contains(&dir_str)),
+        other => panic!("Expected Error event, got {other:?}"),
+    }
+}
+
+#[test]
+fn spawn_error_names_missing_working_dir() {
+    let dir = unique_temp_dir("spawn_error_missing");
+    let dir_str = dir.to_str().unwrap().to_string();
+    std::fs::remove_dir(&dir).unwrap();
+
+    let os_error = io::Error::from(io::ErrorKind::NotFound);
+    match spawn_error(&os_error, Some(&dir_str), None, None) {
+        TaskError::InvalidConfiguration(msg) => {
+            assert!(msg.contains(&dir_str));
+            assert!(msg.contains("Working directory does not exist"));
+        }
+        other => panic!("Expected InvalidConfiguration, got {other:?}"),
+    }
+}
+
+#[test]
+fn spawn_error_keeps_io_error_when_working_dir_exists() {
+    let dir = unique_temp_dir("spawn_error_present");
+    let dir_str = dir.to_str().unwrap().to_string();
+
+    let os_error = io::Error::from(io::ErrorKind::NotFound);
+    let error = spawn_error(&os_error, Some(&dir_str), None, None);
+    std::fs::remove_dir(&dir).unwrap();
+
+    assert_eq!(error, TaskError::IO(os_error.to_string()));
+    assert_eq!(
+        spawn_error(&os_error, None, None, None),
+        TaskError::IO(os_error.to_string())
+    );
+}
+
+#[test]
+fn spawn_error_reports_missing_command() {
+    let lookup = CommandLookup {
+        command: "tcrm_task_missing",
+        path_var: Some("/usr/bin"),
+    };
+
+    let not_found = io::Error::from(io::ErrorKind::NotFound);
+    assert_eq!(
+        spawn_error(&not_found, None, None, Some(lookup)),
+        TaskError::CommandNotFound {
+            command: "tcrm_task_missing".to_string(),
+            searched_path: Some("/usr/bin".to_string()),
+        }
+    );
+    let denied = io::Error::from(io::ErrorKind::PermissionDenied);
+    assert_eq!(
+        spawn_error(&denied, None, None, Some(lookup)),
+        TaskError::IO(denied.to_string())
+    );
+}
+
+#[tokio::test]
+async fn first_existing_working_dir_candidate_is_used() {
+    let present = unique_temp_dir("working_dir_candidates");
+    let present_str = present.to_str().unwrap().to_string();
+    let missing_str = present.join("build").to_str().unwrap().to_string();
+    std::fs::write(present.join("marker.txt"), "chosen\n").unwrap();
+
+    #[cfg(windows)]
+    let config = TaskConfig::new("cmd").args(["/C", "type marker.txt"]);
+    #[cfg(unix)]
+    let config = TaskConfig::new("cat").args(["marker.txt"]);
+    let config = config.working_dir_candidates([&missing_str, &present_str]);
+
+    let mut spawner = TaskSpawner::new("candidates_task".to_string(), config);
+    let plan = spawner.plan().unwrap();
+    assert_eq!(
+        plan.working_dir,
+        std::fs::canonicalize(&present).unwrap().to_string_lossy()
+    );
+
+    let (tx, mut rx) = mpsc::channel::<TaskEvent>(10);
+    spawner.start_direct(tx).await.unwrap();
+    let mut chosen = None;
+    let mut output = vec![];
+    while let Some(event) = rx.recv().await {
+        match event {
+            TaskEvent::Started { working_dir, .. } => chosen = working_dir,
+            TaskEvent::Output { line, .. } => output.push(line),
+            _ => {}
+        }
+    }
+    std::fs::remove_dir_all(&present).unwrap();
+
+    assert_eq!(chosen, Some(present_str));
+    assert_eq!(output, ["chosen"]);
+}
+
+#[tokio::test]
+async fn no_existing_working_dir_candidate_is_rejected() {
+    let dir = unique_temp_dir("working_dir_candidates_missing");
+    let missing =
+        [dir.join("build"), dir.join("out")].map(|path| path.to_str().unwrap().to_string());
+    std::fs::remove_dir(&dir).unwrap();
+
+    let config = TaskConfig::new("echo").working_dir_candidates(&missing);
+    let mut spawner = TaskSpawner::new("candidates_task".to_string(), config);
+    let (tx, _rx) = mpsc::channel::<TaskEvent>(10);
+    match spawner.start_direct(tx).await {
+        Err(TaskError::InvalidConfiguration(msg)) => {
+            assert!(missing.iter().all(|dir| msg.contains(dir)), "{msg}");
+        }
+        other => panic!("Expected InvalidConfiguration naming the candidates, got {other:?}"),
+    }
+}
@@ -0,0 +1,50 @@
+//! Helpers shared by the integration tests, and command helpers shared with
+//! the benchmarks in `benches/support`.
+
+use std::path::PathBuf;
+
+use tokio::sync::mpsc;
+
+pub(crate) use crate::tasks::config::TaskConfig;
+use crate::tasks::{async_tokio::spawner::TaskSpawner, event::TaskEvent};
+
+#[path = "../../../../../benches/support/commands.rs"]
+pub(crate) mod commands;
+
+/// Runs the task to completion and returns all of its events
+pub(crate) async fn collect_events(config: TaskConfig) -> Vec<TaskEvent> {
+    let (tx, rx) = mpsc::channel::<TaskEvent>(100);
+    let mut spawner = TaskSpawner::new("task".to_string(), config);
+    spawner.start_direct(tx).await.unwrap();
+    collect(rx).await
+}
+
+/// Collects every event until the channel closes
+pub(crate) async fn collect(mut rx: mpsc::Receiver<TaskEvent>) -> Vec<TaskEvent> {
+    let mut events = vec![];
+    while let Some(event) = rx.recv().await {
+        events.push(event);
+    }
+    events
+}
+
+/// Runs the task to completion and returns its output lines
+pub(crate) async fn run_output(config: TaskConfig) -> Vec<String> {
+    collect_events(config)
+        .await
+        .into_iter()
+        .filter_map(|event| match event {
+            TaskEvent::Output { line, .. } => Some(line),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Creates a directory under the system temp dir, unique to `name` and this process
+///
+/// The path is canonical, as diagnoses and plans name the resolved path.
+pub(crate) fn unique_temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("tcrm_task_{name}_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::canonicalize(dir).unwrap()
+}
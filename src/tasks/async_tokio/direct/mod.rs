@@ -1,5 +1,10 @@
+pub mod cached;
 pub(crate) mod command;
+pub(crate) mod diagnosis;
+pub mod plan;
+pub mod run;
 pub mod start;
+pub mod stream;
 pub(crate) mod watchers;
 
 #[cfg(test)]
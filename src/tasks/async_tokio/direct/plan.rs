@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use tokio::process::Command;
+
+use crate::tasks::async_tokio::direct::command::{env_snapshot, setup_command};
+use crate::tasks::async_tokio::direct::diagnosis::diagnose;
+use crate::tasks::async_tokio::direct::start::spawn_error;
+use crate::tasks::async_tokio::spawner::TaskSpawner;
+use crate::tasks::error::TaskError;
+use crate::tasks::plan::ExecutionPlan;
+use crate::tasks::validator::ConfigValidator;
+
+/// OS error code for a missing file (`ENOENT` on Unix, `ERROR_FILE_NOT_FOUND` on Windows)
+const NOT_FOUND_OS_ERROR: i32 = 2;
+/// OS error code for a file that cannot be executed (`EACCES` on Unix)
+const PERMISSION_DENIED_OS_ERROR: i32 = if cfg!(windows) { 5 } else { 13 };
+
+impl TaskSpawner {
+    /// Resolve what `start_direct` would execute, without spawning anything
+    ///
+    /// Validates the configuration, canonicalizes the working directory,
+    /// merges the configured environment into the inherited one and resolves
+    /// the command against the resulting `PATH`. Nothing is spawned and the
+    /// task state is left untouched.
+    ///
+    /// Resolution follows the platform's process spawning: a command with a
+    /// path separator is taken relative to the working directory, a bare name
+    /// is searched in `PATH` (with `.exe` appended on Windows when the name has
+    /// no extension).
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(ExecutionPlan)` - The resolved program, arguments, working directory and environment
+    /// - `Err(TaskError)` - The error `start_direct` would return for this configuration
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tcrm_task::tasks::{config::TaskConfig, async_tokio::spawner::TaskSpawner};
+    ///
+    /// let config = TaskConfig::new("ls").args(["-la"]).working_dir("/tmp");
+    /// let spawner = TaskSpawner::new("list".to_string(), config);
+    ///
+    /// let plan = spawner.plan().unwrap();
+    /// println!("Would run {} in {}", plan.program, plan.working_dir);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TaskError`] if:
+    /// - Task configuration validation fails
+    /// - The working directory cannot be resolved
+    /// - The command cannot be found or is not executable
+    pub fn plan(&self) -> Result<ExecutionPlan, TaskError> {
+        self.config.validate()?;
+        let working_dir = self.config.resolve_working_dir()?;
+
+        let canonical_dir = working_dir
+            .map_or_else(std::env::current_dir, |dir| Ok(PathBuf::from(dir)))
+            .and_then(std::fs::canonicalize)
+            .map_err(|e| spawn_error(&e, working_dir, None, None))?;
+
+        let mut cmd = Command::new(&self.config.command);
+        let enable_stdin = self.stdin_rx.is_some();
+        setup_command(&mut cmd, &self.config, enable_stdin);
+        if let Some(customizer) = &self.customize_command {
+            customizer.apply(&mut cmd);
+        }
+        let env = env_snapshot(&cmd, self.config.clear_env.unwrap_or_default());
+        let path_var = path_var(&env);
+
+        let program =
+            resolve_program(&self.config.command, &canonical_dir, path_var).map_err(|e| {
+                let diagnosis = diagnose(&self.config.command, &canonical_dir, path_var);
+                let lookup = CommandLookup {
+                    command: &self.config.command,
+                    path_var,
+                };
+                spawn_error(&e, working_dir, diagnosis, Some(lookup))
+            })?;
+
+        let env_overrides = self
+            .config
+            .env
+            .iter()
+            .flatten()
+            .filter(|(key, value)| std::env::var(key).ok().as_ref() != Some(*value))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+
+        Ok(ExecutionPlan {
+            command: self.config.command.clone(),
+            program: program.to_string_lossy().into_owned(),
+            args: self.config.args.clone().unwrap_or_default(),
+            working_dir: canonical_dir.to_string_lossy().into_owned(),
+            env_overrides: ConfigValidator::redact_env(&env_overrides),
+            timeout_ms: self.config.timeout_ms,
+            use_process_group: self.config.is_process_group_enabled(),
+            enable_stdin,
+        })
+    }
+}
+
+/// Command a spawn tried to run, with the `PATH` it was looked up in
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CommandLookup<'a> {
+    /// The configured command
+    pub(crate) command: &'a str,
+    /// `PATH` of the process environment
+    pub(crate) path_var: Option<&'a str>,
+}
+
+impl CommandLookup<'_> {
+    /// Returns the error for a command that resolves to no existing file
+    pub(crate) fn not_found(self) -> TaskError {
+        TaskError::CommandNotFound {
+            command: self.command.to_string(),
+            searched_path: self
+                .path_var
+                .filter(|_| !is_path(self.command))
+                .map(str::to_string),
+        }
+    }
+}
+
+/// Resolves `command` to the absolute path of the program that would run
+///
+/// Fails with the OS error spawning would report: not found when no candidate
+/// exists, permission denied when candidates exist but none is executable.
+pub(crate) fn resolve_program(
+    command: &str,
+    working_dir: &Path,
+    path_var: Option<&str>,
+) -> Result<PathBuf, io::Error> {
+    let mut denied = false;
+    for candidate in program_candidates(command, working_dir, path_var) {
+        if !candidate.exists() {
+            continue;
+        }
+        if is_executable(&candidate) {
+            return Ok(candidate);
+        }
+        denied = true;
+    }
+    Err(io::Error::from_raw_os_error(if denied {
+        PERMISSION_DENIED_OS_ERROR
+    } else {
+        NOT_FOUND_OS_ERROR
+    }))
+}
+
+/// Returns the paths `command` may resolve to, in the order they are tried
+///
+/// A command with a path separator resolves relative to `working_dir`, a bare
+/// name to each `PATH` entry.
+pub(crate) fn program_candidates(
+    command: &str,
+    working_dir: &Path,
+    path_var: Option<&str>,
+) -> Vec<PathBuf> {
+    let program = Path::new(command);
+    let candidates: Vec<PathBuf> = if is_path(command) {
+        vec![working_dir.join(program)]
+    } else {
+        path_var
+            .map(|paths| {
+                std::env::split_paths(paths)
+                    .map(|dir| working_dir.join(dir).join(program))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    candidates
+        .into_iter()
+        .map(with_executable_extension)
+        // Drop `.` components left over from joining relative paths
+        .map(|candidate| candidate.components().collect())
+        .collect()
+}
+
+/// Whether `command` is a path, taken relative to the working directory instead of looked up in `PATH`
+fn is_path(command: &str) -> bool {
+    let program = Path::new(command);
+    program.components().count() > 1 || program.is_absolute()
+}
+
+/// Returns the `PATH` variable of `env`, matched case-insensitively on Windows
+pub(crate) fn path_var(env: &HashMap<String, String>) -> Option<&str> {
+    env.iter()
+        .find(|(key, _)| {
+            if cfg!(windows) {
+                key.eq_ignore_ascii_case("PATH")
+            } else {
+                key.as_str() == "PATH"
+            }
+        })
+        .map(|(_, value)| value.as_str())
+}
+
+/// Adds `.exe` to `candidate` on Windows when it has no extension
+fn with_executable_extension(candidate: PathBuf) -> PathBuf {
+    if cfg!(windows) && candidate.extension().is_none() {
+        candidate.with_extension("exe")
+    } else {
+        candidate
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    path.metadata()
+        .is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
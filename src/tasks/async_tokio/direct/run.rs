@@ -0,0 +1,133 @@
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use crate::tasks::async_tokio::spawner::TaskSpawner;
+use crate::tasks::config::{OutputMode, StreamSource};
+use crate::tasks::error::{ChannelKind, ChannelOp, TaskError};
+use crate::tasks::event::{TaskEvent, TaskEventStopReason};
+
+/// Default number of lines of each stream kept by `run_to_completion`
+pub(crate) const DEFAULT_MAX_CAPTURED_LINES: usize = 10_000;
+
+/// Capacity of the event channel of `run_to_completion`, drained as events arrive
+const EVENT_CAPACITY: usize = 128;
+
+/// Output and outcome of a task run with [`TaskSpawner::run_to_completion`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskOutput {
+    /// Exit code, as in `TaskEvent::Stopped`
+    pub exit_code: Option<i32>,
+    /// Lines of stdout, in the order they were read
+    pub stdout: Vec<String>,
+    /// Lines of stderr, in the order they were read
+    pub stderr: Vec<String>,
+    /// Number of lines left out of `stdout` and `stderr` once they were full
+    pub dropped_lines: u64,
+    /// Why the task stopped
+    pub stop_reason: TaskEventStopReason,
+    /// Time from spawning the process until it stopped
+    pub duration: Duration,
+}
+
+impl TaskOutput {
+    /// Whether the task exited on its own with exit code 0
+    #[must_use]
+    pub fn success(&self) -> bool {
+        self.stop_reason == TaskEventStopReason::Finished && self.exit_code == Some(0)
+    }
+}
+
+impl TaskSpawner {
+    /// Run the task until it stops and return its output
+    ///
+    /// Starts the task as with `start_direct`, on a channel of its own, and
+    /// collects the output lines of each stream until `TaskEvent::Stopped`.
+    /// Each stream keeps its first `TaskConfig::max_captured_lines` lines
+    /// (default: 10000); later ones are only counted in
+    /// [`TaskOutput::dropped_lines`]. Lines parsed as JSON are kept as text.
+    ///
+    /// A task that fails or times out still returns its output; only a task
+    /// that cannot start is an error. Bound the run with `TaskConfig::timeout_ms`,
+    /// as the spawner is borrowed until the task stops.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tcrm_task::tasks::{config::TaskConfig, async_tokio::spawner::TaskSpawner};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let config = TaskConfig::new("git").args(["rev-parse", "HEAD"]);
+    ///     let output = TaskSpawner::new("rev".to_string(), config)
+    ///         .run_to_completion()
+    ///         .await?;
+    ///
+    ///     if output.success() {
+    ///         println!("HEAD is {}", output.stdout[0]);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TaskError`] for any error of
+    /// [`start_direct`](Self::start_direct), [`TaskError::InvalidConfiguration`]
+    /// with `OutputMode::Bytes`, whose output has no lines, or
+    /// [`TaskError::Channel`] if the events end without `TaskEvent::Stopped`.
+    pub async fn run_to_completion(&mut self) -> Result<TaskOutput, TaskError> {
+        if self.config.output_mode == Some(OutputMode::Bytes) {
+            return Err(TaskError::InvalidConfiguration(
+                "run_to_completion cannot be used with OutputMode::Bytes".to_string(),
+            ));
+        }
+        let max_lines = self
+            .config
+            .max_captured_lines
+            .unwrap_or(DEFAULT_MAX_CAPTURED_LINES);
+
+        let (tx, mut rx) = mpsc::channel(EVENT_CAPACITY);
+        self.start_direct(tx).await?;
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut dropped_lines = 0;
+        while let Some(event) = rx.recv().await {
+            let (line, src) = match event {
+                TaskEvent::Output { line, src, .. } | TaskEvent::OutputJson { line, src, .. } => {
+                    (line, src)
+                }
+                TaskEvent::Stopped {
+                    exit_code,
+                    reason,
+                    duration_ms,
+                    ..
+                } => {
+                    return Ok(TaskOutput {
+                        exit_code,
+                        stdout,
+                        stderr,
+                        dropped_lines,
+                        stop_reason: reason,
+                        duration: Duration::from_millis(duration_ms),
+                    });
+                }
+                _ => continue,
+            };
+            let lines = match src {
+                StreamSource::Stdout => &mut stdout,
+                StreamSource::Stderr => &mut stderr,
+            };
+            if lines.len() < max_lines {
+                lines.push(line);
+            } else {
+                dropped_lines += 1;
+            }
+        }
+        Err(TaskError::Channel {
+            kind: ChannelKind::Event,
+            op: ChannelOp::Recv,
+            detail: "Event channel closed before TaskEvent::Stopped".to_string(),
+        })
+    }
+}
@@ -1,18 +1,42 @@
-use tokio::process::Command;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use tokio::process::{Child, Command};
+use tokio::runtime::Handle;
 use tokio::sync::{mpsc, oneshot, watch};
 use tokio::time::Instant;
 
-use crate::tasks::async_tokio::direct::command::setup_command;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use crate::tasks::async_tokio::direct::command::set_parent_death_signal;
+#[cfg(windows)]
+use crate::tasks::async_tokio::direct::command::apply_scheduling;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use crate::tasks::async_tokio::direct::command::set_cpu_affinity;
+#[cfg(unix)]
+use crate::tasks::async_tokio::direct::command::{priority_error, set_priority};
+use crate::tasks::async_tokio::direct::command::{env_snapshot, setup_command};
+use crate::tasks::async_tokio::direct::diagnosis::diagnose_spawn;
+use crate::tasks::async_tokio::direct::plan::{CommandLookup, path_var, resolve_program};
+use crate::tasks::async_tokio::direct::watchers::health::HealthMonitor;
 use crate::tasks::async_tokio::direct::watchers::input::spawn_stdin_watcher;
 use crate::tasks::async_tokio::direct::watchers::output::spawn_output_watchers;
-use crate::tasks::async_tokio::direct::watchers::result::spawn_result_watcher;
-use crate::tasks::async_tokio::direct::watchers::timeout::spawn_timeout_watcher;
-use crate::tasks::async_tokio::direct::watchers::wait::spawn_wait_watcher;
+use crate::tasks::async_tokio::direct::watchers::result::{ResultReporter, SpawnedCommand};
+use crate::tasks::async_tokio::direct::watchers::script::{ScriptDriver, ScriptProgress};
+use crate::tasks::async_tokio::direct::watchers::wait::{WaitWatcherConfig, spawn_wait_watcher};
+#[cfg(unix)]
+use crate::tasks::async_tokio::pre_exec::PreExecSteps;
 use crate::tasks::async_tokio::process_group::ProcessGroup;
-use crate::tasks::async_tokio::spawner::TaskSpawner;
-use crate::tasks::error::TaskError;
-use crate::tasks::event::{TaskEvent, TaskEventStopReason, TaskTerminateReason};
+use crate::tasks::async_tokio::process_start;
+use crate::tasks::async_tokio::spawner::{StopRequest, TaskSpawner};
+use crate::tasks::async_tokio::stdin::StdinReceiver;
+use crate::tasks::async_tokio::temp_dir::{self, DEFAULT_TEMP_DIR_PREFIX};
+use crate::tasks::async_tokio::watcher_set::WatcherSet;
+use crate::tasks::config::SpawnRetry;
+use crate::tasks::error::{SpawnDiagnosis, TaskError};
+use crate::tasks::event::{TaskEvent, TaskEventStopReason};
 use crate::tasks::state::TaskState;
+use crate::tasks::validator::ConfigValidator;
 
 impl TaskSpawner {
     /// Start the task and execute it directly with real-time event monitoring
@@ -23,9 +47,10 @@ impl TaskSpawner {
     ///
     /// # Process Lifecycle
     ///
-    /// 1. **Validation**: Configuration is validated for security and correctness
-    /// 2. **Process Spawn**: System process is created with configured parameters
-    /// 3. **Monitoring Setup**: Watchers are spawned for stdout/stderr, stdin, timeouts, and process completion
+    /// 1. **Validation**: Configuration is validated for security and correctness, and the command is
+    ///    prepared; see [`prepare`](Self::prepare) to run this step on its own
+    /// 2. **Process Spawn**: System process is created with configured parameters, then the `on_spawned` hook runs (if set)
+    /// 3. **Monitoring Setup**: Watchers are spawned for stdout/stderr and stdin, plus one watcher handling process completion, timeout and the result
     /// 4. **Event Emission**: Real-time events are sent as the process executes
     /// 5. **Cleanup**: Process and resources are cleaned up when execution completes
     ///
@@ -35,17 +60,26 @@ impl TaskSpawner {
     ///
     /// # Returns
     ///
-    /// - `Ok(process_id)` - The system process ID if the task was started successfully
+    /// - `Ok(process_id)` - The system process ID if the task was started successfully.
+    ///   `TaskEvent::Started` has already been sent with the same ID when this returns.
     /// - `Err(TaskError)` - Configuration validation error, spawn failure, or other issues
     ///
     /// # Events Emitted
     ///
-    /// - `TaskEvent::Started` - Process has been spawned and is running
+    /// - `TaskEvent::SpawnRetry` - A spawn attempt failed and is retried (if configured)
+    /// - `TaskEvent::Started` - Process has been spawned and is running, with its process ID
     /// - `TaskEvent::Output` - Output line received from stdout/stderr
+    /// - `TaskEvent::OutputChunk` - Raw output received, instead of lines with `OutputMode::Bytes`
     /// - `TaskEvent::Ready` - Ready indicator detected (for long-running processes)
+    /// - `TaskEvent::RunningLong` - Expected duration exceeded (if configured)
     /// - `TaskEvent::Stopped` - Process has completed with exit code and reason
     /// - `TaskEvent::Error` - An error occurred during execution
     ///
+    /// Events of a run arrive in order: `Started` before any `Output` or
+    /// `Ready`, and `Stopped` last. `Started` is queued before the watchers
+    /// that read output are spawned, so this holds even for a process that
+    /// prints its ready indicator and exits right away.
+    ///
     /// # Examples
     ///
     /// ## Simple Command
@@ -55,7 +89,11 @@ impl TaskSpawner {
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    ///     let config = TaskConfig::new("cmd").args(["/C", "echo", "Hello, World!"]);
+    ///     let config = if cfg!(windows) {
+    ///         TaskConfig::new("cmd").args(["/C", "echo", "Hello, World!"])
+    ///     } else {
+    ///         TaskConfig::new("echo").args(["Hello, World!"])
+    ///     };
     ///     let mut spawner = TaskSpawner::new("greeting".to_string(), config);
     ///     
     ///     let (tx, mut rx) = mpsc::channel(100);
@@ -91,8 +129,12 @@ impl TaskSpawner {
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    ///     let config = TaskConfig::new("cmd")
-    ///         .args(["/C", "echo", "Server listening on"])
+    ///     let command = if cfg!(windows) {
+    ///         TaskConfig::new("cmd").args(["/C", "echo", "Server listening on"])
+    ///     } else {
+    ///         TaskConfig::new("echo").args(["Server listening on"])
+    ///     };
+    ///     let config = command
     ///         .ready_indicator("Server listening on")
     ///         .ready_indicator_source(StreamSource::Stdout)
     ///         .timeout_ms(30000); // 30 second timeout
@@ -176,29 +218,31 @@ impl TaskSpawner {
     /// - Process completion watcher
     /// - Result aggregation watcher
     ///
-    /// All watchers run concurrently for responsiveness.
+    /// All watchers run concurrently for responsiveness. They run on the runtime
+    /// set with `set_runtime`, or on the calling runtime by default.
     ///
     /// # Errors
     ///
     /// Returns a [`TaskError`] if:
     /// - Task configuration validation fails
-    /// - Process fails to start due to invalid command or working directory
-    /// - Unable to obtain process ID from started child process
+    /// - Process fails to start due to invalid command or working directory;
+    ///   [`TaskError::CommandNotFound`] names a missing command and the `PATH`
+    ///   searched, [`TaskError::Spawn`] tells why a command that exists cannot be executed
+    /// - A `pre_exec` step fails in the child process (Unix only); [`TaskError::PreExec`]
+    ///   names the step
+    /// - Unable to obtain process ID from started child process, e.g. because
+    ///   it already exited; `TaskEvent::Stopped` then reports its exit status
+    /// - The `on_spawned` hook fails; the hook's error is returned
+    /// - The task has already been started
     #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, event_tx), fields(task_name = %self.task_name)))]
-    #[allow(clippy::too_many_lines)]
     pub async fn start_direct(
         &mut self,
         event_tx: mpsc::Sender<TaskEvent>,
     ) -> Result<u32, TaskError> {
-        self.update_state(TaskState::Initiating).await;
-
-        match self.config.validate() {
-            Ok(()) => {}
+        self.begin_start().await?;
+        let (cmd, process_group) = match self.prepare_command().await {
+            Ok(prepared) => prepared,
             Err(e) => {
-                #[cfg(feature = "tracing")]
-                tracing::error!(error = %e, "Invalid task configuration");
-
-                self.update_state(TaskState::Finished).await;
                 let error_event = TaskEvent::Error {
                     task_name: self.task_name.clone(),
                     error: e.clone(),
@@ -210,32 +254,136 @@ impl TaskSpawner {
                 }
                 return Err(e);
             }
+        };
+        let prepared = PreparedTask {
+            spawner: self,
+            cmd,
+            process_group,
+        };
+        prepared.launch(event_tx).await
+    }
+
+    /// Prepare the task for a later [`PreparedTask::launch`], without spawning anything
+    ///
+    /// Performs the first half of `start_direct`: validates the configuration,
+    /// builds the command and creates the process group if enabled. What is
+    /// left for `launch` is the spawn itself, the process group assignment and
+    /// the watcher setup, so the process starts with as little delay as possible
+    /// once the caller decides to launch it.
+    ///
+    /// No event is sent, and errors are only returned. The task is in
+    /// `TaskState::Initiating` until the prepared task is launched or dropped.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(PreparedTask)` - The prepared task, borrowing this spawner until it is launched or dropped
+    /// - `Err(TaskError)` - Configuration validation error, or other issues
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tcrm_task::tasks::{config::TaskConfig, async_tokio::spawner::TaskSpawner};
+    /// use tokio::sync::mpsc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let config = TaskConfig::new("echo").args(["hello"]);
+    ///     let mut spawner = TaskSpawner::new("greeting".to_string(), config);
+    ///
+    ///     // Configuration errors surface here, before any channel exists
+    ///     let prepared = spawner.prepare().await?;
+    ///
+    ///     let (tx, mut rx) = mpsc::channel(100);
+    ///     let process_id = prepared.launch(tx).await?;
+    ///     println!("Started process with ID: {}", process_id);
+    ///
+    ///     while let Some(event) = rx.recv().await {
+    ///         println!("{:?}", event);
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TaskError`] if:
+    /// - Task configuration validation fails
+    /// - The process group cannot be created
+    /// - The task has already been started
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(task_name = %self.task_name)))]
+    pub async fn prepare(&mut self) -> Result<PreparedTask<'_>, TaskError> {
+        self.begin_start().await?;
+        let (cmd, process_group) = self.prepare_command().await?;
+        Ok(PreparedTask {
+            spawner: self,
+            cmd,
+            process_group,
+        })
+    }
+
+    /// Moves the task to `TaskState::Initiating`, failing if it was already started
+    pub(crate) async fn begin_start(&self) -> Result<(), TaskError> {
+        if !self.update_state(TaskState::Initiating).await {
+            let msg = "Task has already been started";
+            #[cfg(feature = "tracing")]
+            tracing::error!(msg);
+            return Err(TaskError::Handle(msg.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Validates the configuration and builds the command to spawn, with its process group
+    ///
+    /// Creates the temporary working directory if configured, and applies the
+    /// command customizer last. Moves the task to `TaskState::Finished` on error.
+    async fn prepare_command(&mut self) -> Result<(Command, Option<ProcessGroup>), TaskError> {
+        if let Err(e) = self.config.validate() {
+            #[cfg(feature = "tracing")]
+            tracing::error!(error = %e, "Invalid task configuration");
+
+            self.update_state(TaskState::Finished).await;
+            return Err(e);
         }
 
         let mut cmd = Command::new(&self.config.command);
-        cmd.kill_on_drop(true);
+        cmd.kill_on_drop(self.cleanup_on_drop);
 
-        setup_command(&mut cmd, &self.config);
+        // stdin_rx is only kept when stdin is enabled, which a stdin script requires
+        let pipe_stdin = self.stdin_rx.is_some() || self.config.stdin_script.is_some();
+        setup_command(&mut cmd, &self.config, pipe_stdin);
+
+        #[cfg(unix)]
+        let mut pre_exec = PreExecSteps::reporting();
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        if self.config.kill_child_on_parent_death.unwrap_or_default() {
+            set_parent_death_signal(&mut cmd, &mut pre_exec);
+        }
+        #[cfg(unix)]
+        if let Some(priority) = self.config.priority {
+            set_priority(&mut cmd, &mut pre_exec, priority);
+        }
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        if let Some(cores) = &self.config.cpu_affinity {
+            set_cpu_affinity(&mut cmd, &mut pre_exec, cores);
+        }
 
         // Conditionally create process group for cross-platform process tree management
-        let (mut configured_cmd, process_group) = if self.config.is_process_group_enabled() {
-            match ProcessGroup::create_with_command(cmd) {
+        let (mut cmd, process_group) = if self.config.is_process_group_enabled() {
+            let name = self.config.job_object_name.as_deref();
+            #[cfg(unix)]
+            let created = {
+                let _ = name;
+                ProcessGroup::create_with_steps(cmd, &mut pre_exec)
+            };
+            #[cfg(not(unix))]
+            let created = ProcessGroup::create_with_command_and_name(cmd, name);
+            match created {
                 Ok((cmd, group)) => (cmd, Some(group)),
                 Err(e) => {
                     #[cfg(feature = "tracing")]
                     tracing::error!(error = %e, "Failed to create process group");
 
                     self.update_state(TaskState::Finished).await;
-                    let error_event = TaskEvent::Error {
-                        task_name: self.task_name.clone(),
-                        error: TaskError::Handle(format!("Failed to create process group: {}", e)),
-                    };
-
-                    if (event_tx.send(error_event).await).is_err() {
-                        #[cfg(feature = "tracing")]
-                        tracing::warn!("Event channel closed while sending TaskEvent::Error");
-                    }
-
                     return Err(TaskError::Handle(format!(
                         "Failed to create process group: {}",
                         e
@@ -248,16 +396,136 @@ impl TaskSpawner {
             (cmd, None)
         };
 
-        let mut child = match configured_cmd.spawn() {
+        #[cfg(unix)]
+        {
+            for hook in &self.pre_exec_hooks {
+                let func = hook.func.clone();
+                unsafe { pre_exec.register(&mut cmd, hook.name.clone(), move || func()) };
+            }
+            self.pre_exec = pre_exec;
+        }
+
+        if let Some(temp) = &self.config.temp_working_dir {
+            let prefix = temp.prefix.as_deref().unwrap_or(DEFAULT_TEMP_DIR_PREFIX);
+            match temp_dir::create(prefix) {
+                Ok(dir) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(path = %dir.display(), "Created temporary working directory");
+                    cmd.current_dir(&dir);
+                    self.temp_dir = Some(dir);
+                }
+                Err(e) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::error!(error = %e, "Failed to create temporary working directory");
+
+                    self.update_state(TaskState::Finished).await;
+                    return Err(TaskError::IO(format!(
+                        "Failed to create temporary working directory: {e}"
+                    )));
+                }
+            }
+        }
+
+        if let Some(customizer) = &self.customize_command {
+            customizer.apply(&mut cmd);
+        }
+
+        Ok((cmd, process_group))
+    }
+}
+
+/// A task ready to be spawned, returned by [`TaskSpawner::prepare`]
+///
+/// Holds the built command and the process group the process will join, if
+/// enabled. Dropping it without calling [`launch`](Self::launch) spawns
+/// nothing: the process group and the temporary working directory are
+/// released and the task moves to `TaskState::Finished`, without any event.
+#[derive(Debug)]
+pub struct PreparedTask<'a> {
+    spawner: &'a mut TaskSpawner,
+    cmd: Command,
+    process_group: Option<ProcessGroup>,
+}
+
+impl PreparedTask<'_> {
+    /// Spawn the prepared process and start monitoring it
+    ///
+    /// Performs the second half of `start_direct`: spawns the process, assigns
+    /// it to the process group, runs the `on_spawned` hook (if set) and sets up
+    /// the watchers. Events are sent through `event_tx` as for `start_direct`,
+    /// starting with `TaskEvent::Started`.
+    ///
+    /// # Arguments
+    ///
+    /// * `event_tx` - Channel sender for receiving task events in real-time
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(process_id)` - The system process ID if the task was started successfully
+    /// - `Err(TaskError)` - Spawn failure, or other issues
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TaskError`] if:
+    /// - Process fails to start due to invalid command or working directory;
+    ///   [`TaskError::CommandNotFound`] names a missing command and the `PATH`
+    ///   searched, [`TaskError::Spawn`] tells why a command that exists cannot be executed
+    /// - A `pre_exec` step fails in the child process (Unix only); [`TaskError::PreExec`]
+    ///   names the step
+    /// - The process cannot be assigned to the process group
+    /// - Unable to obtain process ID from started child process, e.g. because
+    ///   it already exited; `TaskEvent::Stopped` then reports its exit status
+    /// - The `on_spawned` hook fails; the hook's error is returned
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(task_name = %self.spawner.task_name)))]
+    #[allow(clippy::too_many_lines)]
+    pub async fn launch(mut self, event_tx: mpsc::Sender<TaskEvent>) -> Result<u32, TaskError> {
+        let process_group = self.process_group.take();
+        let configured_cmd = &mut self.cmd;
+        let spawner = &mut *self.spawner;
+        let clear_env = spawner.config.clear_env.unwrap_or_default();
+
+        let runtime = spawner.runtime.clone();
+        let spawned = spawn_with_retry(
+            configured_cmd,
+            runtime.as_ref(),
+            spawner.config.spawn_retry.as_ref(),
+            &spawner.task_name,
+            &event_tx,
+            #[cfg(unix)]
+            &spawner.pre_exec,
+        )
+        .await;
+        // Closes the pipe the steps report failures through
+        #[cfg(unix)]
+        let pre_exec = std::mem::take(&mut spawner.pre_exec);
+        let mut child = match spawned {
             Ok(c) => c,
             Err(e) => {
                 #[cfg(feature = "tracing")]
                 tracing::error!(error = %e, "Failed to spawn child process");
 
-                self.update_state(TaskState::Finished).await;
+                spawner.update_state(TaskState::Finished).await;
+                let working_dir = configured_cmd
+                    .as_std()
+                    .get_current_dir()
+                    .and_then(Path::to_str);
+                #[cfg(unix)]
+                let pre_exec_error = pre_exec.error(&e).map(priority_error);
+                #[cfg(not(unix))]
+                let pre_exec_error = None;
+                let error = pre_exec_error.unwrap_or_else(|| {
+                    let diagnosis = diagnose_spawn(configured_cmd, working_dir, clear_env);
+                    let env = env_snapshot(configured_cmd, clear_env);
+                    let lookup = CommandLookup {
+                        command: &spawner.config.command,
+                        path_var: path_var(&env),
+                    };
+                    spawn_error(&e, working_dir, diagnosis, Some(lookup))
+                });
+                spawner.remove_temp_dir_after_failure();
                 let error_event = TaskEvent::Error {
-                    task_name: self.task_name.clone(),
-                    error: TaskError::IO(e.to_string()),
+                    task_name: spawner.task_name.clone(),
+                    error: error.clone(),
                 };
 
                 if (event_tx.send(error_event).await).is_err() {
@@ -265,62 +533,119 @@ impl TaskSpawner {
                     tracing::warn!("Event channel closed while sending TaskEvent::Error");
                 }
 
-                return Err(TaskError::IO(e.to_string()));
+                return Err(error);
             }
         };
-        self.running_at = Some(Instant::now());
+        let running_at = Instant::now();
+        spawner.running_at = Some(running_at);
+        spawner.spawned = Some(spawned_command(configured_cmd, clear_env));
+        if spawner.config.capture_env_snapshot.unwrap_or_default() {
+            let env = env_snapshot(configured_cmd, clear_env);
+            #[cfg(feature = "tracing")]
+            tracing::debug!(env = ?crate::tasks::validator::ConfigValidator::redact_env(&env), "Captured environment snapshot");
+            spawner.spawned_env = Some(env);
+        }
 
-        // Assign the child process to the process group if enabled
-        if let Some(ref pg) = process_group {
-            if let Err(e) = pg.assign_child(&child).await {
+        // Set before the process is resumed by joining its process group
+        #[cfg(windows)]
+        if let Err(msg) = apply_scheduling(&child, &spawner.config) {
+            #[cfg(feature = "tracing")]
+            tracing::error!(error = %msg, "Failed to apply scheduling settings");
+
+            kill_child(&mut child, None).await;
+            spawner.update_state(TaskState::Finished).await;
+            spawner.remove_temp_dir_after_failure();
+            let error = TaskError::Handle(msg);
+            let error_event = TaskEvent::Error {
+                task_name: spawner.task_name.clone(),
+                error: error.clone(),
+            };
+
+            if (event_tx.send(error_event).await).is_err() {
                 #[cfg(feature = "tracing")]
-                tracing::error!(error = %e, "Failed to assign child to process group");
+                tracing::warn!("Event channel closed while sending TaskEvent::Error");
+            }
 
-                self.update_state(TaskState::Finished).await;
-                let error_event = TaskEvent::Error {
-                    task_name: self.task_name.clone(),
-                    error: TaskError::Handle(format!(
-                        "Failed to assign child to process group: {}",
-                        e
-                    )),
-                };
+            return Err(error);
+        }
 
-                if (event_tx.send(error_event).await).is_err() {
-                    #[cfg(feature = "tracing")]
-                    tracing::warn!("Event channel closed while sending TaskEvent::Error");
-                }
+        // Assign the child process to the process group if enabled
+        if let Some(ref pg) = process_group
+            && let Err(e) = pg.assign_child(&child).await
+        {
+            #[cfg(feature = "tracing")]
+            tracing::error!(error = %e, "Failed to assign child to process group");
 
-                return Err(TaskError::Handle(format!(
+            // On Windows the child is still suspended and may be outside the
+            // job, so kill it directly rather than through the group
+            kill_child(&mut child, None).await;
+            spawner.update_state(TaskState::Finished).await;
+            spawner.remove_temp_dir_after_failure();
+            let error_event = TaskEvent::Error {
+                task_name: spawner.task_name.clone(),
+                error: TaskError::Handle(format!(
                     "Failed to assign child to process group: {}",
                     e
-                )));
+                )),
+            };
+
+            if (event_tx.send(error_event).await).is_err() {
+                #[cfg(feature = "tracing")]
+                tracing::warn!("Event channel closed while sending TaskEvent::Error");
             }
+
+            return Err(TaskError::Handle(format!(
+                "Failed to assign child to process group: {}",
+                e
+            )));
         }
         let Some(child_id) = child.id() else {
-            let msg = "Failed to get process id";
+            return Err(spawner
+                .report_startup_failure(&mut child, process_group.as_ref(), event_tx, running_at)
+                .await);
+        };
+        *spawner.process_id.write().await = Some(child_id);
+        spawner.process_start_time = process_start::start_time(child_id);
 
+        if let Some(hook) = &spawner.on_spawned
+            && let Err(e) = hook.call(child_id).await
+        {
             #[cfg(feature = "tracing")]
-            tracing::error!(msg);
+            tracing::error!(error = %e, "on_spawned hook failed, killing child process");
 
-            self.update_state(TaskState::Finished).await;
+            kill_child(&mut child, process_group.as_ref()).await;
             let error_event = TaskEvent::Error {
-                task_name: self.task_name.clone(),
-                error: TaskError::Handle(msg.to_string()),
+                task_name: spawner.task_name.clone(),
+                error: e.clone(),
             };
-
             if (event_tx.send(error_event).await).is_err() {
                 #[cfg(feature = "tracing")]
                 tracing::warn!("Event channel closed while sending TaskEvent::Error");
             }
 
-            return Err(TaskError::Handle(msg.to_string()));
-        };
-        *self.process_id.write().await = Some(child_id);
-        let mut task_handles = vec![];
-        self.update_state(TaskState::Running).await;
+            let reason = TaskEventStopReason::Error(format!("on_spawned hook failed: {e}"));
+            spawner
+                .result_reporter(event_tx, spawner.watchers.nested(), running_at)
+                .report(None, reason)
+                .await;
+            return Err(e);
+        }
+
+        spawner.update_state(TaskState::Running).await;
         if (event_tx
             .send(TaskEvent::Started {
-                task_name: self.task_name.clone(),
+                task_name: spawner.task_name.clone(),
+                process_id: child_id,
+                temp_dir: spawner
+                    .temp_dir
+                    .as_ref()
+                    .map(|dir| dir.to_string_lossy().into_owned()),
+                working_dir: spawner
+                    .config
+                    .working_dir_candidates
+                    .as_ref()
+                    .and_then(|_| configured_cmd.as_std().get_current_dir())
+                    .map(|dir| dir.to_string_lossy().into_owned()),
             })
             .await)
             .is_err()
@@ -329,60 +654,381 @@ impl TaskSpawner {
             tracing::warn!("Event channel closed while sending TaskEvent::Started");
         }
 
-        let (result_tx, result_rx) = oneshot::channel::<(Option<i32>, TaskEventStopReason)>();
-        let (terminate_tx, terminate_rx) = oneshot::channel::<TaskTerminateReason>();
+        let (terminate_tx, terminate_rx) = oneshot::channel::<StopRequest>();
         let (handle_terminator_tx, handle_terminator_rx) = watch::channel(false);
 
+        *spawner.terminate_tx.lock().await = Some(terminate_tx);
+
+        // Watchers are spawned on the configured runtime, so no await may follow
+        let _runtime = runtime.as_ref().map(Handle::enter);
+        let (io_watchers, stdin_script) =
+            spawner.spawn_io_watchers(&mut child, &event_tx, handle_terminator_rx);
+
+        // Spawn child wait watcher, which also handles the timeout and reports the result
+        let config = WaitWatcherConfig {
+            task_name: spawner.task_name.clone(),
+            terminate_tx: spawner.terminate_tx.clone(),
+            terminate_rx,
+            handle_terminator_tx,
+            config: spawner.config.clone(),
+            kill_on_abort: spawner.cleanup_on_drop,
+            on_channel_closed: spawner.on_channel_closed,
+            stdin_script,
+            health: spawner.health_check.clone().map(|check| HealthMonitor {
+                check,
+                healthy: spawner.healthy.clone(),
+            }),
+            pause_clock: spawner.pause_clock.clone(),
+        };
+        let reporter = spawner.result_reporter(event_tx, io_watchers, running_at);
+        spawner.process_group.clone_from(&process_group);
+        spawn_wait_watcher(
+            &mut spawner.watchers,
+            child,
+            process_group,
+            config,
+            reporter,
+        );
+
+        Ok(child_id)
+    }
+}
+
+impl Drop for PreparedTask<'_> {
+    fn drop(&mut self) {
+        // Left in `Initiating` only if `launch` was never called or was cancelled before spawning
+        if let Ok(mut state) = self.spawner.state.try_write()
+            && *state == TaskState::Initiating
+        {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(task_name = %self.spawner.task_name, "Prepared task dropped without launching");
+            *state = TaskState::Finished;
+            if let Some(dir) = &self.spawner.temp_dir {
+                temp_dir::remove(dir);
+            }
+        }
+    }
+}
+
+impl TaskSpawner {
+    /// Spawns the output watchers and the stdin watcher for the piped streams of `child`
+    ///
+    /// Streams that were not piped by `setup_command` (uncaptured output, unused
+    /// stdin) get no watcher, so the returned set holds exactly the watchers
+    /// the wait watcher has to join. With a stdin script, also returns its
+    /// driver, whose lines the stdin watcher writes.
+    pub(crate) fn spawn_io_watchers(
+        &mut self,
+        child: &mut Child,
+        event_tx: &mpsc::Sender<TaskEvent>,
+        handle_terminator_rx: watch::Receiver<bool>,
+    ) -> (WatcherSet, Option<ScriptDriver>) {
+        let mut watchers = self.watchers.nested();
+        let (stdin_script, script_rx) = self
+            .config
+            .stdin_script
+            .clone()
+            .filter(|_| child.stdin.is_some())
+            .map(|script| {
+                let match_mode = self.config.ready_match_mode.unwrap_or_default();
+                let progress = Arc::new(ScriptProgress::new(script, match_mode));
+                let (stdin_tx, script_rx) = mpsc::channel(1);
+                (ScriptDriver { progress, stdin_tx }, script_rx)
+            })
+            .unzip();
         // Spawn stdout and stderr watchers
-        let handles = spawn_output_watchers(
+        spawn_output_watchers(
+            &mut watchers,
             self.task_name.clone(),
             self.state.clone(),
             event_tx.clone(),
-            &mut child,
+            child,
             handle_terminator_rx.clone(),
-            self.config.ready_indicator.clone(),
-            self.config.ready_indicator_source.clone(),
+            &self.config,
+            self.dropped_events.clone(),
+            self.send_stats.clone(),
+            self.ready_flag.clone(),
+            self.output_transform.clone(),
+            self.startup_output.clone(),
+            self.output_tail.clone(),
+            self.terminate_tx.clone(),
+            self.output_bytes.clone(),
+            &self.output_digests,
+            self.on_channel_closed,
+            stdin_script.as_ref().map(|driver| driver.progress.clone()),
         );
-        task_handles.extend(handles);
 
-        // Spawn stdin watcher if configured
-        if let Some((stdin, stdin_rx)) = child.stdin.take().zip(self.stdin_rx.take()) {
-            let handle = spawn_stdin_watcher(stdin, stdin_rx, handle_terminator_rx.clone());
-            task_handles.push(handle);
+        // Spawn stdin watcher if configured, writing the script alongside the stdin receiver
+        let script_rx = script_rx.map(StdinReceiver::Lines);
+        let stdin_sources = match (self.stdin_rx.take(), script_rx) {
+            (Some(stdin_rx), script_rx) => Some((stdin_rx, script_rx)),
+            (None, script_rx) => script_rx.map(|script_rx| (script_rx, None)),
+        };
+        if let Some((stdin, (stdin_rx, script_rx))) = child.stdin.take().zip(stdin_sources) {
+            spawn_stdin_watcher(
+                &mut watchers,
+                stdin,
+                stdin_rx,
+                script_rx,
+                handle_terminator_rx,
+                self.dropped_stdin_lines.clone(),
+            );
         }
 
-        // Spawn child wait watcher
-        *self.terminate_tx.lock().await = Some(terminate_tx);
+        (watchers, stdin_script)
+    }
 
-        let handle = spawn_wait_watcher(
-            self.task_name.clone(),
-            self.state.clone(),
-            child,
-            process_group,
-            terminate_rx,
-            handle_terminator_tx.clone(),
-            result_tx,
-            self.process_id.clone(),
-        );
-        task_handles.push(handle);
+    /// Removes the temporary working directory of a task that failed to start
+    ///
+    /// Kept with `keep_on_failure`, like the directory of a failed run. Used
+    /// on the early returns that bypass the result reporter, which otherwise
+    /// removes it.
+    pub(crate) fn remove_temp_dir_after_failure(&self) {
+        let Some(dir) = &self.temp_dir else {
+            return;
+        };
+        let keep_on_failure = self
+            .config
+            .temp_working_dir
+            .as_ref()
+            .is_some_and(|temp| temp.keep_on_failure);
+        if keep_on_failure {
+            #[cfg(feature = "tracing")]
+            tracing::info!(path = %dir.display(), "Keeping temporary working directory of failed task");
+        } else {
+            temp_dir::remove(dir);
+        }
+    }
+
+    /// Reports a child that exited before its process ID could be read
+    ///
+    /// Collects the exit status so `TaskEvent::Stopped` with
+    /// `TaskEventStopReason::StartupFailure` tells how the process died. Falls
+    /// back to a `TaskEvent::Error` if the status cannot be collected.
+    ///
+    /// Returns the error for `start_direct` to return.
+    pub(crate) async fn report_startup_failure(
+        &self,
+        child: &mut Child,
+        process_group: Option<&ProcessGroup>,
+        event_tx: mpsc::Sender<TaskEvent>,
+        running_at: Instant,
+    ) -> TaskError {
+        let status = match child.try_wait() {
+            Ok(Some(status)) => status,
+            #[allow(clippy::used_underscore_binding)]
+            _result => {
+                let msg = "Failed to get process id";
+
+                #[cfg(feature = "tracing")]
+                tracing::error!(result = ?_result, msg);
+
+                self.update_state(TaskState::Finished).await;
+                self.remove_temp_dir_after_failure();
+                let error_event = TaskEvent::Error {
+                    task_name: self.task_name.clone(),
+                    error: TaskError::Handle(msg.to_string()),
+                };
+
+                if (event_tx.send(error_event).await).is_err() {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!("Event channel closed while sending TaskEvent::Error");
+                }
 
-        // Spawn timeout watcher if configured
-        if let Some(timeout_ms) = self.config.timeout_ms {
-            let handle =
-                spawn_timeout_watcher(self.terminate_tx.clone(), timeout_ms, handle_terminator_rx);
-            task_handles.push(handle);
+                return TaskError::Handle(msg.to_string());
+            }
+        };
+
+        // Descendants may outlive the process that failed
+        if let Some(pg) = process_group
+            && let Err(_e) = pg.terminate_all().await
+        {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(error = %_e, "Failed to terminate process group after startup failure");
         }
 
-        // Spawn result watcher
-        let _handle = spawn_result_watcher(
-            self.task_name.clone(),
-            self.state.clone(),
-            self.finished_at.clone(),
+        let msg = format!("Process exited during startup ({status})");
+        #[cfg(feature = "tracing")]
+        tracing::error!(%status, "Process exited during startup");
+        self.result_reporter(event_tx, self.watchers.nested(), running_at)
+            .report(
+                status.code(),
+                TaskEventStopReason::StartupFailure(msg.clone()),
+            )
+            .await;
+        TaskError::Handle(msg)
+    }
+
+    /// Creates the reporter sending `TaskEvent::Stopped` for this task
+    fn result_reporter(
+        &self,
+        event_tx: mpsc::Sender<TaskEvent>,
+        task_handles: WatcherSet,
+        running_at: Instant,
+    ) -> ResultReporter {
+        ResultReporter {
+            task_name: self.task_name.clone(),
+            state: self.state.clone(),
+            finished_at: self.finished_at.clone(),
             event_tx,
-            result_rx,
             task_handles,
-        );
+            join_timeout: self.join_timeout,
+            process_id: self.process_id.clone(),
+            ready_flag: self.ready_flag.clone(),
+            running_at,
+            config: self.config.clone(),
+            exit_meaning: self.exit_meaning.clone(),
+            failed_before_ready: self.failed_before_ready.clone(),
+            temp_dir: self.temp_dir.clone(),
+            spawned: self.spawned.clone(),
+            signal: None,
+            output_bytes: self.output_bytes.clone(),
+            output_digests: self.output_digests.clone(),
+            terminate_requests: self.terminate_requests.clone(),
+            audit: self.audit.clone(),
+        }
+    }
+}
 
-        Ok(child_id)
+/// Records what `cmd` spawned, for the audit record of the run
+///
+/// Resolves the program the same way as [`TaskSpawner::plan`], from the
+/// working directory and `PATH` of `cmd`, falling back to the program as
+/// configured. Called right after spawning, so the start time is taken first.
+fn spawned_command(cmd: &Command, clear_env: bool) -> SpawnedCommand {
+    let started_at = SystemTime::now();
+    let std_cmd = cmd.as_std();
+    let working_dir = std_cmd
+        .get_current_dir()
+        .map_or_else(std::env::current_dir, |dir| Ok(dir.to_path_buf()))
+        .and_then(std::fs::canonicalize)
+        .unwrap_or_default();
+    let command = std_cmd.get_program().to_string_lossy();
+    let env = env_snapshot(cmd, clear_env);
+    let program = resolve_program(&command, &working_dir, path_var(&env)).map_or_else(
+        |_| command.to_string(),
+        |program| program.to_string_lossy().into_owned(),
+    );
+    let args: Vec<_> = std_cmd
+        .get_args()
+        .map(|arg| arg.to_string_lossy())
+        .collect();
+    let mut env_keys: Vec<String> = std_cmd
+        .get_envs()
+        .filter(|(_, value)| value.is_some())
+        .map(|(key, _)| key.to_string_lossy().into_owned())
+        .collect();
+    env_keys.sort();
+
+    SpawnedCommand {
+        program,
+        args: ConfigValidator::redact_args(&args),
+        working_dir: working_dir.to_string_lossy().into_owned(),
+        env_keys,
+        started_at,
+    }
+}
+
+/// Spawns `cmd`, attempting again on the errors `retry` names
+///
+/// Sends `TaskEvent::SpawnRetry` before each further attempt, and returns the
+/// result of the last one.
+async fn spawn_with_retry(
+    cmd: &mut Command,
+    runtime: Option<&Handle>,
+    retry: Option<&SpawnRetry>,
+    task_name: &Arc<str>,
+    event_tx: &mpsc::Sender<TaskEvent>,
+    #[cfg(unix)] pre_exec: &PreExecSteps,
+) -> std::io::Result<Child> {
+    let mut attempt = 1;
+    loop {
+        // Only a failing step of this attempt may be named
+        #[cfg(unix)]
+        pre_exec.clear();
+        // Spawned inside the configured runtime, which then drives its pipes and exit
+        let spawned = {
+            let _runtime = runtime.map(Handle::enter);
+            cmd.spawn()
+        };
+        let (Err(e), Some(retry)) = (&spawned, retry) else {
+            return spawned;
+        };
+        if attempt >= retry.attempts || !retry.retry_on.contains(&e.kind()) {
+            return spawned;
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::warn!(error = %e, attempt, "Failed to spawn child process, retrying");
+        let event = TaskEvent::SpawnRetry {
+            task_name: task_name.clone(),
+            attempt,
+            error: e.to_string(),
+        };
+        if event_tx.send(event).await.is_err() {
+            #[cfg(feature = "tracing")]
+            tracing::warn!("Event channel closed while sending TaskEvent::SpawnRetry");
+        }
+        tokio::time::sleep(Duration::from_millis(retry.backoff_ms)).await;
+        attempt += 1;
+    }
+}
+
+/// Kills a child process that no watcher owns yet, and waits for it
+///
+/// Kills the whole process group if one is used, falling back to killing the
+/// child alone if that fails.
+async fn kill_child(child: &mut Child, process_group: Option<&ProcessGroup>) {
+    if let Some(pg) = process_group {
+        match pg.terminate_all().await {
+            Ok(_) => {
+                if let Err(_e) = child.wait().await {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(error = %_e, "Failed to wait for child after process group termination");
+                }
+                return;
+            }
+            Err(_e) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(error = %_e, "Failed to terminate process group, killing child directly");
+            }
+        }
+    }
+    if let Err(_e) = child.kill().await {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(error = %_e, "Failed to kill child process");
+    }
+}
+
+/// Maps a spawn failure to a `TaskError`
+///
+/// The working directory is validated before spawning, but it may be removed in
+/// between. The OS then reports a bare "No such file or directory", which reads
+/// like a missing command, so the directory is re-checked and named instead.
+///
+/// Otherwise a `diagnosis` of the command's file turns the error into
+/// `TaskError::Spawn`. Without one, a not found error becomes
+/// `TaskError::CommandNotFound` if the `lookup` is known, and any other
+/// error stays `TaskError::IO`.
+pub(crate) fn spawn_error(
+    error: &std::io::Error,
+    working_dir: Option<&str>,
+    diagnosis: Option<SpawnDiagnosis>,
+    lookup: Option<CommandLookup<'_>>,
+) -> TaskError {
+    if let Some(dir) = working_dir
+        && !Path::new(dir).is_dir()
+    {
+        return TaskError::InvalidConfiguration(format!(
+            "Working directory does not exist: {dir} (removed before spawn: {error})"
+        ));
+    }
+    match (diagnosis, lookup) {
+        (Some(diagnosis), _) => TaskError::Spawn {
+            message: error.to_string(),
+            diagnosis,
+        },
+        (None, Some(lookup)) if error.kind() == std::io::ErrorKind::NotFound => lookup.not_found(),
+        (None, _) => TaskError::IO(error.to_string()),
     }
 }
@@ -0,0 +1,169 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use tokio::sync::mpsc;
+
+use crate::tasks::async_tokio::spawner::TaskSpawner;
+use crate::tasks::error::TaskError;
+use crate::tasks::event::TaskEvent;
+
+/// The events of a task as a [`Stream`]
+///
+/// A thin wrapper over the event channel: every event sent is yielded once,
+/// in order. The stream ends right after `TaskEvent::Stopped`, the last event
+/// of a task, without waiting for the watchers to drop their senders. It
+/// also ends if the channel closes before that, e.g. because the task failed
+/// to start.
+///
+/// # Examples
+/// ```rust,no_run
+/// use futures::StreamExt;
+/// use tcrm_task::tasks::{config::TaskConfig, async_tokio::spawner::TaskSpawner};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let config = TaskConfig::new("echo").args(["hello"]);
+///     let mut spawner = TaskSpawner::new("greeting".to_string(), config);
+///
+///     let events = spawner.start_direct_stream(100).await?;
+///     let lines: Vec<String> = events
+///         .filter_map(|event| async move {
+///             match event {
+///                 tcrm_task::tasks::event::TaskEvent::Output { line, .. } => Some(line),
+///                 _ => None,
+///             }
+///         })
+///         .collect()
+///         .await;
+///     assert_eq!(lines, ["hello"]);
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct TaskEventStream {
+    rx: mpsc::Receiver<TaskEvent>,
+    /// Set once `TaskEvent::Stopped` was yielded
+    stopped: bool,
+}
+
+impl TaskEventStream {
+    /// Wrap the receiving end of an event channel
+    ///
+    /// For tasks started with `start_direct` or `start_cached`.
+    #[must_use]
+    pub fn new(rx: mpsc::Receiver<TaskEvent>) -> Self {
+        Self { rx, stopped: false }
+    }
+
+    /// Return the wrapped receiver
+    #[must_use]
+    pub fn into_inner(self) -> mpsc::Receiver<TaskEvent> {
+        self.rx
+    }
+}
+
+impl From<mpsc::Receiver<TaskEvent>> for TaskEventStream {
+    fn from(rx: mpsc::Receiver<TaskEvent>) -> Self {
+        Self::new(rx)
+    }
+}
+
+impl Stream for TaskEventStream {
+    type Item = TaskEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<TaskEvent>> {
+        if self.stopped {
+            return Poll::Ready(None);
+        }
+        let event = std::task::ready!(self.rx.poll_recv(cx));
+        if matches!(event, Some(TaskEvent::Stopped { .. })) {
+            self.stopped = true;
+        }
+        Poll::Ready(event)
+    }
+}
+
+impl TaskSpawner {
+    /// Start the task as with `start_direct`, returning its events as a [`TaskEventStream`]
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Capacity of the event channel behind the stream
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(TaskEventStream)` - The events of the task, starting with `TaskEvent::Started`
+    /// - `Err(TaskError)` - The error `start_direct` would return
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TaskError`] for any error of
+    /// [`start_direct`](Self::start_direct).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0, like `tokio::sync::mpsc::channel`.
+    pub async fn start_direct_stream(
+        &mut self,
+        capacity: usize,
+    ) -> Result<TaskEventStream, TaskError> {
+        let (tx, rx) = mpsc::channel(capacity);
+        self.start_direct(tx).await?;
+        Ok(TaskEventStream::new(rx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use super::*;
+
+    fn ready(name: &str) -> TaskEvent {
+        TaskEvent::Ready {
+            task_name: name.into(),
+        }
+    }
+
+    fn stopped() -> TaskEvent {
+        TaskEvent::Stopped {
+            task_name: "stream".into(),
+            exit_code: Some(0),
+            signal: None,
+            exit_meaning: None,
+            reason: crate::tasks::event::TaskEventStopReason::Finished,
+            was_ready: true,
+            duration_ms: 1,
+            expected_duration_ms: None,
+            from_cache: false,
+            stdout_sha256: None,
+            stderr_sha256: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn ends_after_stopped_while_senders_are_alive() {
+        let (tx, rx) = mpsc::channel(8);
+        tx.send(ready("stream")).await.unwrap();
+        tx.send(stopped()).await.unwrap();
+        tx.send(ready("late")).await.unwrap();
+
+        let events: Vec<_> = TaskEventStream::new(rx).collect().await;
+
+        assert_eq!(events, [ready("stream"), stopped()]);
+        drop(tx);
+    }
+
+    #[tokio::test]
+    async fn ends_when_the_channel_closes() {
+        let (tx, rx) = mpsc::channel(8);
+        tx.send(ready("stream")).await.unwrap();
+        drop(tx);
+
+        let events: Vec<_> = TaskEventStream::from(rx).collect().await;
+
+        assert_eq!(events, [ready("stream")]);
+    }
+}
@@ -0,0 +1,29 @@
+use tokio::sync::{Mutex, mpsc, oneshot};
+
+use super::timeout::claim_terminate_channel;
+use crate::tasks::{async_tokio::spawner::StopRequest, event::TaskEvent};
+
+/// Resolves once the receiver of the task events is dropped and this claimed
+/// the terminate channel.
+///
+/// Polled as a branch of the wait watcher with
+/// `ChannelClosedPolicy::TerminateTask`. Never resolves if `terminate` is not
+/// set, or if a termination signal was sent before the channel closed; that
+/// signal is then received by the wait watcher instead.
+///
+/// # Arguments
+///
+/// * `event_tx` - Sender for task events, watched for its receiver being dropped.
+/// * `terminate_tx` - Shared sender for termination signals, taken once the channel closed.
+/// * `terminate` - Whether a closed channel terminates the task.
+pub(crate) async fn wait_for_channel_closed(
+    event_tx: &mpsc::Sender<TaskEvent>,
+    terminate_tx: &Mutex<Option<oneshot::Sender<StopRequest>>>,
+    terminate: bool,
+) {
+    if !terminate {
+        return std::future::pending().await;
+    }
+    event_tx.closed().await;
+    claim_terminate_channel(terminate_tx, "Event channel closed").await;
+}
@@ -0,0 +1,279 @@
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll, ready},
+};
+
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::tasks::config::OutputEncoding;
+
+const UTF16LE_BOM: &[u8] = &[0xFF, 0xFE];
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+
+/// Size of the buffer raw output is read into before decoding
+const RAW_CAPACITY: usize = 8 * 1024;
+
+/// Encoding of the stream once the start of it has been inspected
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    /// Waiting for enough bytes to tell whether the stream starts with a BOM
+    Start(OutputEncoding),
+    Utf8,
+    Utf16Le,
+}
+
+/// Transcodes an output stream to UTF-8 so lines can be split on decoded text.
+///
+/// UTF-16LE is decoded incrementally: an odd byte or a high surrogate at the
+/// end of a read is kept until the next read completes it. Invalid UTF-16 is
+/// replaced with U+FFFD. UTF-8 is passed through, after skipping a BOM in
+/// [`OutputEncoding::Auto`] mode.
+#[derive(Debug)]
+pub(crate) struct DecodingReader<R> {
+    reader: R,
+    mode: Mode,
+    /// Bytes read but not decoded yet
+    raw: Vec<u8>,
+    /// Decoded bytes not yet returned to the caller
+    decoded: Vec<u8>,
+    /// Number of bytes at the start of `decoded` already returned
+    pos: usize,
+    eof: bool,
+}
+
+impl<R> DecodingReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Creates a reader decoding `reader` from `encoding`.
+    pub(crate) fn new(reader: R, encoding: OutputEncoding) -> Self {
+        Self {
+            reader,
+            mode: Mode::Start(encoding),
+            raw: Vec::new(),
+            decoded: Vec::new(),
+            pos: 0,
+            eof: false,
+        }
+    }
+
+    /// Decodes as much of `raw` as possible into `decoded`.
+    fn decode(&mut self) {
+        if let Mode::Start(encoding) = self.mode {
+            match self.detect(encoding) {
+                Some(mode) => self.mode = mode,
+                None => return,
+            }
+        }
+        match self.mode {
+            Mode::Start(_) => {}
+            Mode::Utf8 => self.decoded.append(&mut self.raw),
+            Mode::Utf16Le => self.decode_utf16le(),
+        }
+    }
+
+    /// Picks the mode from the start of the stream, skipping a BOM
+    ///
+    /// Returns `None` while `raw` could still be the start of a BOM.
+    fn detect(&mut self, encoding: OutputEncoding) -> Option<Mode> {
+        let boms: &[(&[u8], Mode)] = match encoding {
+            OutputEncoding::Utf8 => return Some(Mode::Utf8),
+            OutputEncoding::Utf16Le => &[(UTF16LE_BOM, Mode::Utf16Le)],
+            OutputEncoding::Auto => &[(UTF16LE_BOM, Mode::Utf16Le), (UTF8_BOM, Mode::Utf8)],
+        };
+        for &(bom, mode) in boms {
+            if self.raw.starts_with(bom) {
+                self.raw.drain(..bom.len());
+                return Some(mode);
+            }
+            if !self.eof && bom.starts_with(&self.raw) {
+                return None;
+            }
+        }
+        Some(match encoding {
+            OutputEncoding::Utf16Le => Mode::Utf16Le,
+            _ => Mode::Utf8,
+        })
+    }
+
+    /// Decodes the complete UTF-16LE code units in `raw`
+    ///
+    /// Before EOF, a trailing odd byte and a trailing high surrogate are kept
+    /// for the next read; at EOF they decode to U+FFFD.
+    fn decode_utf16le(&mut self) {
+        let mut units: Vec<u16> = self
+            .raw
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        let mut consumed = units.len() * 2;
+        if !self.eof
+            && units
+                .last()
+                .is_some_and(|unit| (0xD800..0xDC00).contains(unit))
+        {
+            units.pop();
+            consumed -= 2;
+        }
+
+        let mut buf = [0; 4];
+        for c in char::decode_utf16(units) {
+            let c = c.unwrap_or(char::REPLACEMENT_CHARACTER);
+            self.decoded
+                .extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+        }
+        self.raw.drain(..consumed);
+        if self.eof && !self.raw.is_empty() {
+            self.raw.clear();
+            self.decoded
+                .extend_from_slice(char::REPLACEMENT_CHARACTER.encode_utf8(&mut buf).as_bytes());
+        }
+    }
+}
+
+impl<R> AsyncRead for DecodingReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            if this.pos < this.decoded.len() {
+                let n = buf.remaining().min(this.decoded.len() - this.pos);
+                buf.put_slice(&this.decoded[this.pos..this.pos + n]);
+                this.pos += n;
+                if this.pos == this.decoded.len() {
+                    this.decoded.clear();
+                    this.pos = 0;
+                }
+                return Poll::Ready(Ok(()));
+            }
+            if this.eof {
+                return Poll::Ready(Ok(()));
+            }
+            // Nothing to transcode, so read straight into the caller's buffer
+            if this.mode == Mode::Utf8 && this.raw.is_empty() {
+                return Pin::new(&mut this.reader).poll_read(cx, buf);
+            }
+
+            let mut chunk = [0; RAW_CAPACITY];
+            let mut chunk_buf = ReadBuf::new(&mut chunk);
+            ready!(Pin::new(&mut this.reader).poll_read(cx, &mut chunk_buf))?;
+            if chunk_buf.filled().is_empty() {
+                this.eof = true;
+            }
+            this.raw.extend_from_slice(chunk_buf.filled());
+            this.decode();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::AsyncWriteExt;
+
+    use super::*;
+    use crate::tasks::async_tokio::direct::watchers::line_reader::{
+        DEFAULT_CAPACITY, LineReader, line_str,
+    };
+
+    fn utf16le(text: &str) -> Vec<u8> {
+        text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+    }
+
+    /// Writes `chunks` through a small pipe and returns the decoded lines
+    async fn read_lines(chunks: Vec<Vec<u8>>, encoding: OutputEncoding) -> Vec<String> {
+        let (mut tx, rx) = tokio::io::duplex(3);
+        let writer = tokio::spawn(async move {
+            for chunk in chunks {
+                tx.write_all(&chunk).await.unwrap();
+                tokio::task::yield_now().await;
+            }
+        });
+
+        let mut reader =
+            LineReader::with_capacity(DecodingReader::new(rx, encoding), DEFAULT_CAPACITY);
+        let mut lines = vec![];
+        while let Some(line) = reader.next_line().await.unwrap() {
+            lines.push(line_str(&line.bytes).unwrap().to_owned());
+        }
+        writer.await.unwrap();
+        lines
+    }
+
+    #[tokio::test]
+    async fn decodes_utf16le_lines() {
+        let input = utf16le("hello\r\nw\u{00f6}rld\n\u{4e16}\u{754c}");
+        let lines = read_lines(vec![input], OutputEncoding::Utf16Le).await;
+        assert_eq!(lines, ["hello", "w\u{00f6}rld", "\u{4e16}\u{754c}"]);
+    }
+
+    #[tokio::test]
+    async fn surrogate_pairs_split_across_reads_are_joined() {
+        let input = utf16le("a\u{1f600}b\n\u{1f680}\n");
+        // One byte per write splits every code unit and surrogate pair
+        let chunks = input.iter().map(|&b| vec![b]).collect();
+        let lines = read_lines(chunks, OutputEncoding::Utf16Le).await;
+        assert_eq!(lines, ["a\u{1f600}b", "\u{1f680}"]);
+    }
+
+    #[tokio::test]
+    async fn invalid_utf16_is_replaced() {
+        let mut input = utf16le("lone ");
+        input.extend_from_slice(&0xD800_u16.to_le_bytes());
+        input.extend(utf16le("\nodd"));
+        input.push(b'x');
+        let lines = read_lines(vec![input], OutputEncoding::Utf16Le).await;
+        assert_eq!(lines, ["lone \u{fffd}", "odd\u{fffd}"]);
+    }
+
+    #[tokio::test]
+    async fn utf16le_bom_is_skipped() {
+        let mut input = UTF16LE_BOM.to_vec();
+        input.extend(utf16le("bom\n"));
+        for encoding in [OutputEncoding::Utf16Le, OutputEncoding::Auto] {
+            let lines = read_lines(vec![input.clone()], encoding).await;
+            assert_eq!(lines, ["bom"], "{encoding:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn auto_detects_bom_split_across_reads() {
+        let mut input = UTF16LE_BOM.to_vec();
+        input.extend(utf16le("split\n"));
+        let chunks = input.iter().map(|&b| vec![b]).collect();
+        let lines = read_lines(chunks, OutputEncoding::Auto).await;
+        assert_eq!(lines, ["split"]);
+    }
+
+    #[tokio::test]
+    async fn auto_without_bom_is_utf8() {
+        let lines = read_lines(
+            vec![b"plain\n".to_vec(), "\u{00e9}\n".as_bytes().to_vec()],
+            OutputEncoding::Auto,
+        )
+        .await;
+        assert_eq!(lines, ["plain", "\u{00e9}"]);
+
+        let mut input = UTF8_BOM.to_vec();
+        input.extend_from_slice(b"utf8 bom\n");
+        let lines = read_lines(vec![input], OutputEncoding::Auto).await;
+        assert_eq!(lines, ["utf8 bom"]);
+    }
+
+    #[tokio::test]
+    async fn partial_input_is_flushed_at_eof() {
+        let lines = read_lines(vec![vec![0xFF]], OutputEncoding::Utf16Le).await;
+        assert_eq!(lines, ["\u{fffd}"]);
+
+        // Starts like a UTF-8 BOM (EF BC 81)
+        let chunks = "\u{ff01}".bytes().map(|b| vec![b]).collect();
+        let lines = read_lines(chunks, OutputEncoding::Auto).await;
+        assert_eq!(lines, ["\u{ff01}"]);
+    }
+}
@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::tasks::config::OutputDedupe;
+
+/// Repeats of a line that were collapsed, as `(line, count)`
+pub(crate) type RepeatSummary = (String, u64);
+
+/// Collapses runs of identical lines on a single output stream
+///
+/// The first `max_repeats` lines of a run are sent. Later repeats are only
+/// counted until [`take_summary`](Self::take_summary) is called, which the
+/// output watcher does once `deadline` has passed or the stream ends. A
+/// different line ends the run and returns its pending summary from
+/// [`push`](Self::push).
+#[derive(Debug)]
+pub(crate) struct LineDeduper {
+    window: Duration,
+    max_repeats: u32,
+    /// Last line seen, kept to compare the next one against
+    last: Option<String>,
+    /// Number of lines in the current run, saturating
+    repeats: u32,
+    /// Repeats collapsed since the last summary
+    collapsed: u64,
+    /// When the collapsed repeats are summarized, set by the first of them
+    deadline: Option<Instant>,
+}
+
+impl LineDeduper {
+    /// Creates a deduper from the configured settings.
+    pub(crate) fn new(dedupe: OutputDedupe) -> Self {
+        Self {
+            window: Duration::from_millis(dedupe.window_ms),
+            max_repeats: dedupe.max_repeats,
+            last: None,
+            repeats: 0,
+            collapsed: 0,
+            deadline: None,
+        }
+    }
+
+    /// Records a line read at `now`
+    ///
+    /// # Returns
+    ///
+    /// The summary of the previous run if `line` ended it, and whether `line`
+    /// should be sent.
+    pub(crate) fn push(&mut self, line: &str, now: Instant) -> (Option<RepeatSummary>, bool) {
+        if self.last.as_deref() == Some(line) {
+            self.repeats = self.repeats.saturating_add(1);
+            if self.repeats <= self.max_repeats {
+                return (None, true);
+            }
+            self.collapsed += 1;
+            self.deadline.get_or_insert(now + self.window);
+            return (None, false);
+        }
+
+        let summary = self.take_summary();
+        // Reuses the allocation of the previous line
+        let last = self.last.get_or_insert_with(String::new);
+        last.clear();
+        last.push_str(line);
+        self.repeats = 1;
+        (summary, true)
+    }
+
+    /// When the pending summary is due, if repeats were collapsed
+    pub(crate) fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Takes the summary of repeats collapsed so far, if any
+    ///
+    /// The run itself continues: further repeats are still collapsed, and
+    /// counted towards the next summary.
+    pub(crate) fn take_summary(&mut self) -> Option<RepeatSummary> {
+        if self.collapsed == 0 {
+            return None;
+        }
+        let count = std::mem::take(&mut self.collapsed);
+        self.deadline = None;
+        Some((self.last.clone().unwrap_or_default(), count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deduper(max_repeats: u32) -> LineDeduper {
+        LineDeduper::new(OutputDedupe {
+            window_ms: 1000,
+            max_repeats,
+        })
+    }
+
+    #[test]
+    fn repeats_beyond_threshold_are_collapsed_until_line_changes() {
+        let mut dedupe = deduper(2);
+        let now = Instant::now();
+
+        assert_eq!(dedupe.push("error", now), (None, true));
+        assert_eq!(dedupe.push("error", now), (None, true));
+        for _ in 0..5 {
+            assert_eq!(dedupe.push("error", now), (None, false));
+        }
+        assert_eq!(dedupe.deadline(), Some(now + Duration::from_secs(1)));
+
+        assert_eq!(
+            dedupe.push("done", now),
+            (Some(("error".to_string(), 5)), true)
+        );
+        assert_eq!(dedupe.deadline(), None);
+        assert_eq!(dedupe.take_summary(), None);
+    }
+
+    #[test]
+    fn summary_keeps_run_collapsed() {
+        let mut dedupe = deduper(1);
+        let now = Instant::now();
+
+        assert_eq!(dedupe.push("spam", now), (None, true));
+        assert_eq!(dedupe.push("spam", now), (None, false));
+        assert_eq!(dedupe.take_summary(), Some(("spam".to_string(), 1)));
+        assert_eq!(dedupe.deadline(), None);
+
+        // The window restarts with the next collapsed repeat
+        let later = now + Duration::from_secs(5);
+        assert_eq!(dedupe.push("spam", later), (None, false));
+        assert_eq!(dedupe.deadline(), Some(later + Duration::from_secs(1)));
+        assert_eq!(dedupe.take_summary(), Some(("spam".to_string(), 1)));
+    }
+
+    #[test]
+    fn alternating_lines_are_never_collapsed() {
+        let mut dedupe = deduper(1);
+        let now = Instant::now();
+
+        for line in ["a", "b", "a", "b", ""] {
+            assert_eq!(dedupe.push(line, now), (None, true));
+        }
+        assert_eq!(dedupe.take_summary(), None);
+    }
+}
@@ -0,0 +1,114 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+use tokio::{
+    sync::{Mutex, oneshot},
+    time::{Instant, MissedTickBehavior, interval_at},
+};
+
+use super::{result::ResultReporter, timeout::claim_terminate_channel};
+use crate::tasks::{
+    async_tokio::{
+        health::{HealthCheck, HealthCheckMode, TaskStatusInfo, UnhealthyAction},
+        spawner::StopRequest,
+    },
+    event::TaskEvent,
+};
+
+/// Health check of a started task, run by the wait watcher
+#[derive(Debug)]
+pub(crate) struct HealthMonitor {
+    /// The configured health check
+    pub(crate) check: HealthCheck,
+    /// Shared health flag of the task, read by `TaskSpawner::is_healthy`
+    pub(crate) healthy: Arc<AtomicBool>,
+}
+
+/// Probes the task while it is Ready, resolving once it became unhealthy
+/// with `UnhealthyAction::Terminate` and this claimed the terminate channel.
+///
+/// Polled as a branch of the wait watcher, so probing stops as soon as the
+/// process stops. Sends `TaskEvent::Unhealthy` and clears the health flag
+/// once `failure_threshold` consecutive probes failed; a passing probe sets
+/// the flag again. Never resolves without a health check, with
+/// `UnhealthyAction::Report`, or if a termination signal was sent before the
+/// task became unhealthy.
+///
+/// # Arguments
+///
+/// * `monitor` - The health check and the health flag, if a check is configured.
+/// * `reporter` - Provides the event sender and the shared state of the task.
+/// * `pid` - Process ID of the child, if it is still known.
+/// * `terminate_tx` - Shared sender for termination signals, taken when terminating.
+pub(crate) async fn watch_health(
+    monitor: Option<HealthMonitor>,
+    reporter: &ResultReporter,
+    pid: Option<u32>,
+    terminate_tx: &Mutex<Option<oneshot::Sender<StopRequest>>>,
+) {
+    let Some(HealthMonitor { check, healthy }) = monitor else {
+        return std::future::pending().await;
+    };
+    let period = Duration::from_millis(check.interval_ms.max(1));
+    let threshold = check.failure_threshold.max(1);
+    let mut ticker = interval_at(Instant::now() + period, period);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    // Output read until the previous probe, `None` until the task was seen Ready
+    let mut probed_bytes = None;
+    let mut failures = 0_u32;
+
+    loop {
+        ticker.tick().await;
+        if !reporter.ready_flag.load(Ordering::Relaxed) {
+            continue;
+        }
+        let output_bytes = reporter.output_bytes.load(Ordering::Relaxed);
+        let Some(previous_bytes) = probed_bytes.replace(output_bytes) else {
+            continue;
+        };
+
+        let passed = match &check.mode {
+            HealthCheckMode::OutputActivity => output_bytes > previous_bytes,
+            HealthCheckMode::Custom(probe) => probe(&TaskStatusInfo {
+                process_id: pid,
+                state: reporter.state.read().await.clone(),
+                uptime: reporter.running_at.elapsed(),
+                output_bytes,
+            }),
+        };
+        if passed {
+            if failures >= threshold {
+                #[cfg(feature = "tracing")]
+                tracing::info!("Task is healthy again");
+                healthy.store(true, Ordering::Relaxed);
+            }
+            failures = 0;
+            continue;
+        }
+
+        failures = failures.saturating_add(1);
+        #[cfg(feature = "tracing")]
+        tracing::debug!(failures, "Health probe failed");
+        if failures != threshold {
+            continue;
+        }
+        healthy.store(false, Ordering::Relaxed);
+        let event = TaskEvent::Unhealthy {
+            task_name: reporter.task_name.clone(),
+            consecutive_failures: failures,
+        };
+        if reporter.event_tx.send(event).await.is_err() {
+            #[cfg(feature = "tracing")]
+            tracing::warn!("Event channel closed while sending TaskEvent::Unhealthy");
+        }
+        if check.unhealthy_action != UnhealthyAction::Terminate {
+            continue;
+        }
+        return claim_terminate_channel(terminate_tx, "Task is unhealthy").await;
+    }
+}
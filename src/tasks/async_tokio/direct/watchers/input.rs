@@ -1,74 +1,158 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
 use tokio::{
-    io::AsyncWriteExt,
-    process::ChildStdin,
-    sync::{mpsc, watch},
-    task::JoinHandle,
+    io::{AsyncWrite, AsyncWriteExt},
+    sync::watch,
 };
 
-use crate::helper::tracing::MaybeInstrument;
+use crate::{
+    helper::tracing::MaybeInstrument,
+    tasks::async_tokio::{
+        stdin::{StdinInput, StdinReceiver},
+        watcher_set::WatcherSet,
+    },
+};
+
+/// Most queued lines written to stdin after the termination signal
+const STDIN_FLUSH_MAX_LINES: usize = 16;
+
+/// How long writing queued lines may take after the termination signal
+const STDIN_FLUSH_TIMEOUT: Duration = Duration::from_millis(100);
+
 /// Spawns an asynchronous watcher for task stdin
 ///
 /// Listens for lines from a channel and writes them to the child process's stdin
 ///
-/// Terminates when the channel is closed or a termination signal is received
+/// Secret inputs are only logged redacted, see [`StdinInput`].
+///
+/// Lines of the stdin script, if any, come from a second channel and are
+/// written as they arrive. The watcher then terminates once both channels
+/// are closed or a termination signal is received.
+/// On the termination signal the channel is closed to new lines, and at most
+/// `STDIN_FLUSH_MAX_LINES` lines already queued are written within
+/// `STDIN_FLUSH_TIMEOUT`. Lines that are never written, including the rest of
+/// the queue, are counted in `dropped_stdin_lines`. Stdin is closed last.
 ///
 /// # Arguments
 ///
+/// * `watchers` - Set the watcher is spawned into.
 /// * `stdin` - The stdin handle of the child process.
-/// * `stdin_rx` - Receiver channel for stdin input strings.
+/// * `stdin_rx` - Receiver channel for stdin inputs.
+/// * `script_rx` - Receiver channel for the lines of the stdin script, if configured.
 /// * `handle_terminator_rx` - Receiver to listen for termination signals.
-///
-/// # Returns
-///
-/// A `JoinHandle` for the spawned stdin watcher task.
-/// ```
+/// * `dropped_stdin_lines` - Shared counter of lines that were never written.
 #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
-pub(crate) fn spawn_stdin_watcher(
-    mut stdin: ChildStdin,
-    mut stdin_rx: mpsc::Receiver<String>,
+pub(crate) fn spawn_stdin_watcher<W>(
+    watchers: &mut WatcherSet,
+    mut stdin: W,
+    mut stdin_rx: StdinReceiver,
+    mut script_rx: Option<StdinReceiver>,
     mut handle_terminator_rx: watch::Receiver<bool>,
-) -> JoinHandle<()> {
-    let handle = tokio::spawn(
+    dropped_stdin_lines: Arc<AtomicU64>,
+) where
+    W: AsyncWrite + Unpin + Send + 'static,
+{
+    watchers.spawn(
+        "stdin",
         async move {
+            let mut dropped = 0;
+            // Whether queued lines should still be flushed once the loop ends
+            let mut flush = false;
             loop {
-                tokio::select! {
-                    // New line from stdin channel
-                    maybe_line = stdin_rx.recv() => {
-                        if let Some(mut line) = maybe_line {
+                let input = tokio::select! {
+                    // Checked first, so no new line is taken once terminated
+                    biased;
 
+                    // Termination signal
+                    changed = handle_terminator_rx.changed() => {
+                        #[cfg(feature = "tracing")]
+                        tracing::trace!("Task handle termination signal received");
+
+                        if changed.is_err() || *handle_terminator_rx.borrow() {
                             #[cfg(feature = "tracing")]
-                            tracing::trace!(line, "Received line for stdin");
+                            tracing::debug!("Termination signal received, closing stdin watcher");
+                            flush = true;
+                            break;
+                        }
+                        continue;
+                    }
 
-                            if !line.ends_with('\n') {
-                                line.push('\n');
-                            }
-                            #[allow(clippy::used_underscore_binding)]
-                            if let Err(_e) = stdin.write_all(line.as_bytes()).await {
-                                    #[cfg(feature = "tracing")]
-                                    tracing::warn!(error=%_e, "Failed to write to child stdin");
-                                break;
-                            }
-                        } else {
+                    // New line from stdin channel
+                    maybe_line = stdin_rx.recv() => {
+                        let Some(line) = maybe_line else {
                             #[cfg(feature = "tracing")]
                             tracing::trace!("Stdin channel closed");
+                            // The script may still be running
+                            if let Some(script_rx) = script_rx.take() {
+                                stdin_rx = script_rx;
+                                continue;
+                            }
                             // Channel closed, stop watcher
                             break;
-                        }
+                        };
+                        line
                     }
 
-                    // Termination signal
-                    _ = handle_terminator_rx.changed() => {
+                    // New line from the stdin script
+                    maybe_line = recv_script(&mut script_rx), if script_rx.is_some() => {
+                        let Some(line) = maybe_line else {
                             #[cfg(feature = "tracing")]
-                            tracing::trace!("Task handle termination signal received");
+                            tracing::trace!("Stdin script finished");
+                            script_rx = None;
+                            continue;
+                        };
+                        line
+                    }
+                };
 
-                        if *handle_terminator_rx.borrow() {
+                #[cfg(feature = "tracing")]
+                tracing::trace!(?input, "Received input for stdin");
+
+                // A write blocked on a full pipe must not outlive the process
+                tokio::select! {
+                    result = write_line(&mut stdin, input) => {
+                        #[allow(clippy::used_underscore_binding)]
+                        if let Err(_e) = result {
                             #[cfg(feature = "tracing")]
-                            tracing::debug!("Termination signal received, closing stdin watcher");
+                            tracing::warn!(error=%_e, "Failed to write to child stdin");
+                            dropped += 1;
                             break;
                         }
                     }
+                    _ = terminated(&mut handle_terminator_rx) => {
+                        #[cfg(feature = "tracing")]
+                        tracing::debug!("Termination signal received while writing to stdin");
+                        // Part of the line may have been written, so nothing can follow it
+                        dropped += 1;
+                        break;
+                    }
+                }
+            }
+
+            stdin_rx.close();
+            if flush {
+                dropped += flush_queued(&mut stdin, &mut stdin_rx).await;
+            }
+            while stdin_rx.try_recv().is_ok() {
+                dropped += 1;
+            }
+            if let Some(script_rx) = &mut script_rx {
+                script_rx.close();
+                while script_rx.try_recv().is_ok() {
+                    dropped += 1;
                 }
             }
+            if dropped > 0 {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(dropped, "Dropped stdin lines that were not written");
+                dropped_stdin_lines.fetch_add(dropped, Ordering::Relaxed);
+            }
 
             // Close stdin when channel is closed
             #[allow(clippy::used_underscore_binding)]
@@ -81,11 +165,223 @@ pub(crate) fn spawn_stdin_watcher(
         }
         .maybe_instrument("spawn"),
     );
-    #[cfg(feature = "tracing")]
-    tracing::debug!(
-        handle_id = %handle.id(),
-        "Spawned stdin watcher handle"
-    );
+}
+
+/// Receives the next line of the stdin script, `None` once it is done
+async fn recv_script(script_rx: &mut Option<StdinReceiver>) -> Option<StdinInput> {
+    match script_rx {
+        Some(script_rx) => script_rx.recv().await,
+        None => None,
+    }
+}
+
+/// Writes an input to stdin, adding the trailing newline if missing
+async fn write_line<W>(stdin: &mut W, input: StdinInput) -> std::io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let mut line = input.data;
+    if !line.ends_with('\n') {
+        line.push('\n');
+    }
+    stdin.write_all(line.as_bytes()).await
+}
+
+/// Resolves once the termination signal is received
+async fn terminated(handle_terminator_rx: &mut watch::Receiver<bool>) {
+    // Also resolves once the sender is gone, as no signal can follow
+    let _ = handle_terminator_rx
+        .wait_for(|terminated| *terminated)
+        .await;
+}
+
+/// Writes lines already queued when the task was terminated
+///
+/// Stops after `STDIN_FLUSH_MAX_LINES` lines, at `STDIN_FLUSH_TIMEOUT`, or on
+/// the first failed write.
+///
+/// # Returns
+///
+/// The number of lines taken from the queue but not written.
+async fn flush_queued<W>(stdin: &mut W, stdin_rx: &mut StdinReceiver) -> u64
+where
+    W: AsyncWrite + Unpin,
+{
+    // Set when a line is taken, cleared once it is written
+    let mut pending = 0;
+    let flush = async {
+        for _ in 0..STDIN_FLUSH_MAX_LINES {
+            let Ok(input) = stdin_rx.try_recv() else {
+                return;
+            };
+            pending = 1;
+            if write_line(stdin, input).await.is_err() {
+                return;
+            }
+            pending = 0;
+        }
+    };
+    if tokio::time::timeout(STDIN_FLUSH_TIMEOUT, flush)
+        .await
+        .is_err()
+    {
+        #[cfg(feature = "tracing")]
+        tracing::debug!("Stdin flush timeout elapsed");
+    }
+    pending
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{io::AsyncReadExt, sync::mpsc};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn terminate_flushes_bounded_number_of_queued_lines() {
+        let (writer, mut reader) = tokio::io::duplex(64 * 1024);
+        let (stdin_tx, stdin_rx) = mpsc::channel(128);
+        for i in 0..100 {
+            stdin_tx.send(format!("line {i}")).await.unwrap();
+        }
+        let (terminator_tx, terminator_rx) = watch::channel(false);
+        terminator_tx.send(true).unwrap();
+        let dropped = Arc::new(AtomicU64::new(0));
+
+        let mut watchers = WatcherSet::default();
+        spawn_stdin_watcher(
+            &mut watchers,
+            writer,
+            StdinReceiver::Lines(stdin_rx),
+            None,
+            terminator_rx,
+            dropped.clone(),
+        );
+        watchers.join_all(Duration::from_secs(1)).await.unwrap();
+
+        let mut written = String::new();
+        reader.read_to_string(&mut written).await.unwrap();
+        let expected: String = (0..STDIN_FLUSH_MAX_LINES)
+            .map(|i| format!("line {i}\n"))
+            .collect();
+        assert_eq!(written, expected);
+        assert_eq!(
+            dropped.load(Ordering::Relaxed),
+            100 - STDIN_FLUSH_MAX_LINES as u64
+        );
+        assert!(stdin_tx.send("late".to_string()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn blocked_write_is_abandoned_on_terminate() {
+        // Nothing reads the other end, so the first line fills the pipe
+        let (writer, _reader) = tokio::io::duplex(16);
+        let (stdin_tx, stdin_rx) = mpsc::channel(8);
+        let (terminator_tx, terminator_rx) = watch::channel(false);
+        let dropped = Arc::new(AtomicU64::new(0));
+        let mut watchers = WatcherSet::default();
+        spawn_stdin_watcher(
+            &mut watchers,
+            writer,
+            StdinReceiver::Lines(stdin_rx),
+            None,
+            terminator_rx,
+            dropped.clone(),
+        );
+
+        stdin_tx.send("x".repeat(1024)).await.unwrap();
+        stdin_tx.send("queued".to_string()).await.unwrap();
+        tokio::task::yield_now().await;
+        terminator_tx.send(true).unwrap();
+
+        watchers
+            .join_all(Duration::from_secs(1))
+            .await
+            .expect("watcher should stop despite the blocked write");
+        assert_eq!(dropped.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn closed_channel_writes_everything() {
+        let (writer, mut reader) = tokio::io::duplex(64 * 1024);
+        let (stdin_tx, stdin_rx) = mpsc::channel(8);
+        let (_terminator_tx, terminator_rx) = watch::channel(false);
+        let dropped = Arc::new(AtomicU64::new(0));
+        stdin_tx.send("one".to_string()).await.unwrap();
+        stdin_tx.send("two\n".to_string()).await.unwrap();
+        drop(stdin_tx);
+
+        let mut watchers = WatcherSet::default();
+        spawn_stdin_watcher(
+            &mut watchers,
+            writer,
+            StdinReceiver::Lines(stdin_rx),
+            None,
+            terminator_rx,
+            dropped.clone(),
+        );
+        watchers.join_all(Duration::from_secs(1)).await.unwrap();
+
+        let mut written = String::new();
+        reader.read_to_string(&mut written).await.unwrap();
+        assert_eq!(written, "one\ntwo\n");
+        assert_eq!(dropped.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn script_lines_are_written_until_both_channels_close() {
+        let (writer, mut reader) = tokio::io::duplex(64 * 1024);
+        let (stdin_tx, stdin_rx) = mpsc::channel(8);
+        let (script_tx, script_rx) = mpsc::channel(8);
+        let (_terminator_tx, terminator_rx) = watch::channel(false);
+        let dropped = Arc::new(AtomicU64::new(0));
+        let mut watchers = WatcherSet::default();
+        spawn_stdin_watcher(
+            &mut watchers,
+            writer,
+            StdinReceiver::Lines(stdin_rx),
+            Some(StdinReceiver::Lines(script_rx)),
+            terminator_rx,
+            dropped.clone(),
+        );
+
+        stdin_tx.send("user".to_string()).await.unwrap();
+        drop(stdin_tx);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!watchers.is_finished("stdin"));
+        script_tx.send("script".to_string()).await.unwrap();
+        drop(script_tx);
+        watchers.join_all(Duration::from_secs(1)).await.unwrap();
+
+        let mut written = String::new();
+        reader.read_to_string(&mut written).await.unwrap();
+        assert_eq!(written, "user\nscript\n");
+        assert_eq!(dropped.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn secret_inputs_are_written() {
+        let (writer, mut reader) = tokio::io::duplex(64 * 1024);
+        let (stdin_tx, stdin_rx) = mpsc::channel(8);
+        let (_terminator_tx, terminator_rx) = watch::channel(false);
+        let dropped = Arc::new(AtomicU64::new(0));
+        stdin_tx.send(StdinInput::line("user")).await.unwrap();
+        stdin_tx.send(StdinInput::secret("hunter2")).await.unwrap();
+        drop(stdin_tx);
+
+        let mut watchers = WatcherSet::default();
+        spawn_stdin_watcher(
+            &mut watchers,
+            writer,
+            StdinReceiver::Inputs(stdin_rx),
+            None,
+            terminator_rx,
+            dropped.clone(),
+        );
+        watchers.join_all(Duration::from_secs(1)).await.unwrap();
 
-    handle
+        let mut written = String::new();
+        reader.read_to_string(&mut written).await.unwrap();
+        assert_eq!(written, "user\nhunter2\n");
+    }
 }
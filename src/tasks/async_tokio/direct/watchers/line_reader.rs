@@ -0,0 +1,320 @@
+use std::io;
+
+use bytes::{Bytes, BytesMut};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// Default capacity of the per-stream read buffer
+pub(crate) const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+/// Default longest output line, longer lines are truncated
+pub(crate) const DEFAULT_MAX_LINE_BYTES: usize = 1024 * 1024;
+
+/// A line split off by [`LineReader`]
+#[derive(Debug)]
+pub(crate) struct Line {
+    /// The line, without its newline
+    pub(crate) bytes: Bytes,
+    /// Whether the line was longer than the maximum and its end was discarded
+    pub(crate) truncated: bool,
+}
+
+/// Splits an async byte stream into lines without copying them.
+///
+/// Data is read into a single reusable `BytesMut`, and each line is split off
+/// as a `Bytes` slice of that buffer. Once the caller drops the line, the
+/// buffer space is reclaimed by the next read.
+///
+/// Lines are split on `\n` and a `\r` before it is removed, matching
+/// `tokio::io::Lines`. A final line without a newline is returned as is at EOF.
+///
+/// A line longer than the maximum is returned as soon as the maximum is read,
+/// cut on a UTF-8 character boundary and marked as truncated; the rest of it is
+/// discarded as it is read. This bounds the buffer however long a line is.
+///
+/// `next_line` is cancel safe: data already read stays in the buffer.
+#[derive(Debug)]
+pub(crate) struct LineReader<R> {
+    reader: R,
+    buf: BytesMut,
+    /// Capacity the buffer is topped up to before a read
+    capacity: usize,
+    /// Number of bytes at the start of `buf` known not to contain `\n`
+    searched: usize,
+    /// Longest line returned whole
+    max_line: usize,
+    /// Set while the rest of a truncated line is skipped
+    discarding: bool,
+    /// Bytes discarded since the last call to `take_discarded`
+    discarded: usize,
+    eof: bool,
+}
+
+impl<R> LineReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Creates a reader whose buffer holds `capacity` bytes per read.
+    ///
+    /// Lines are not truncated unless [`max_line_bytes`](Self::max_line_bytes) is set.
+    pub(crate) fn with_capacity(reader: R, capacity: usize) -> Self {
+        Self {
+            reader,
+            buf: BytesMut::with_capacity(capacity),
+            capacity,
+            searched: 0,
+            max_line: usize::MAX,
+            discarding: false,
+            discarded: 0,
+            eof: false,
+        }
+    }
+
+    /// Truncates lines longer than `max` bytes.
+    pub(crate) fn max_line_bytes(mut self, max: usize) -> Self {
+        self.max_line = max;
+        self
+    }
+
+    /// Returns the number of bytes discarded from truncated lines since the last call.
+    pub(crate) fn take_discarded(&mut self) -> usize {
+        std::mem::take(&mut self.discarded)
+    }
+
+    /// Returns the next line, or `None` at EOF.
+    pub(crate) async fn next_line(&mut self) -> io::Result<Option<Line>> {
+        loop {
+            if self.discarding {
+                // Skip the rest of the truncated line, up to and including its newline
+                match self.buf.iter().position(|&b| b == b'\n') {
+                    Some(pos) => {
+                        // Like the newline of a line that is returned, it is not counted
+                        self.discarded += pos;
+                        let _ = self.buf.split_to(pos + 1);
+                        self.discarding = false;
+                    }
+                    None => {
+                        self.discarded += self.buf.len();
+                        self.buf.clear();
+                    }
+                }
+                self.searched = 0;
+            }
+
+            if !self.discarding {
+                if let Some(pos) = self.buf[self.searched..].iter().position(|&b| b == b'\n') {
+                    let end = self.searched + pos;
+                    let mut line = self.buf.split_to(end + 1);
+                    self.searched = 0;
+                    line.truncate(end);
+                    if line.last() == Some(&b'\r') {
+                        line.truncate(end - 1);
+                    }
+                    return Ok(Some(self.cut(line)));
+                }
+                self.searched = self.buf.len();
+
+                // Room for a `\r` that may still be followed by the newline
+                if self.buf.len() > self.max_line.saturating_add(1) {
+                    let end = char_boundary(&self.buf, self.max_line);
+                    let line = self.buf.split_to(end);
+                    self.searched = 0;
+                    self.discarding = true;
+                    return Ok(Some(Line {
+                        bytes: line.freeze(),
+                        truncated: true,
+                    }));
+                }
+            }
+
+            if self.eof {
+                if self.buf.is_empty() || self.discarding {
+                    self.discarded += self.buf.len();
+                    self.buf.clear();
+                    self.discarding = false;
+                    return Ok(None);
+                }
+                self.searched = 0;
+                let line = self.buf.split();
+                return Ok(Some(self.cut(line)));
+            }
+
+            // Splitting lines off the front shrinks the spare capacity; keep each
+            // read large so a chatty pipe is not drained a few bytes at a time
+            if self.buf.capacity() - self.buf.len() < self.capacity / 2 {
+                self.buf.reserve(self.capacity);
+            }
+            if self.reader.read_buf(&mut self.buf).await? == 0 {
+                self.eof = true;
+            }
+        }
+    }
+
+    /// Truncates a complete line to the maximum
+    fn cut(&mut self, mut line: BytesMut) -> Line {
+        let truncated = line.len() > self.max_line;
+        if truncated {
+            let end = char_boundary(&line, self.max_line);
+            self.discarded += line.len() - end;
+            line.truncate(end);
+        }
+        Line {
+            bytes: line.freeze(),
+            truncated,
+        }
+    }
+}
+
+/// Largest index of at most `max` in `bytes` that does not split a UTF-8 character
+///
+/// `bytes` must be longer than `max`. Bytes that are not UTF-8 are cut at `max`.
+fn char_boundary(bytes: &[u8], max: usize) -> usize {
+    // A UTF-8 character is at most 4 bytes, so at most 3 continuation bytes are skipped
+    (max.saturating_sub(3)..=max)
+        .rev()
+        .find(|&end| bytes[end] & 0xc0 != 0x80)
+        .unwrap_or(max)
+}
+
+/// Borrows a line as `&str` without copying it.
+///
+/// # Errors
+///
+/// Returns `InvalidData` if the line is not valid UTF-8, like `tokio::io::Lines`.
+pub(crate) fn line_str(line: &[u8]) -> io::Result<&str> {
+    std::str::from_utf8(line).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    use super::*;
+
+    async fn read_all(input: &[u8]) -> Vec<String> {
+        read_all_with_capacity(input, DEFAULT_CAPACITY).await
+    }
+
+    async fn read_all_with_capacity(input: &[u8], capacity: usize) -> Vec<String> {
+        let mut reader = LineReader::with_capacity(input, capacity);
+        let mut lines = vec![];
+        while let Some(line) = reader.next_line().await.unwrap() {
+            lines.push(line_str(&line.bytes).unwrap().to_owned());
+        }
+        lines
+    }
+
+    async fn read_all_tokio(input: &[u8]) -> Vec<String> {
+        let mut reader = BufReader::new(input).lines();
+        let mut lines = vec![];
+        while let Some(line) = reader.next_line().await.unwrap() {
+            lines.push(line);
+        }
+        lines
+    }
+
+    #[tokio::test]
+    async fn matches_tokio_lines() {
+        let inputs: [&[u8]; 7] = [
+            b"",
+            b"one\ntwo\nthree\n",
+            b"no trailing newline",
+            b"crlf\r\nline\r\n",
+            b"\n\n\nempty lines\n",
+            b"trailing cr at eof\r",
+            "utf8 \u{00e9}\u{4e16}\n\u{1f600}\n".as_bytes(),
+        ];
+        for input in inputs {
+            assert_eq!(read_all(input).await, read_all_tokio(input).await);
+        }
+    }
+
+    #[tokio::test]
+    async fn buffer_size_does_not_change_lines() {
+        let long = "x".repeat(100_000);
+        let input = format!("short\r\n{long}\n\nmid\r\n{long}\ntail");
+        let expected = read_all_tokio(input.as_bytes()).await;
+        for capacity in [1, 7, 256, 1024, 64 * 1024, 4 * 1024 * 1024] {
+            assert_eq!(
+                read_all_with_capacity(input.as_bytes(), capacity).await,
+                expected,
+                "capacity {capacity}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn lines_split_across_reads_are_joined() {
+        let (mut tx, rx) = tokio::io::duplex(4);
+        let writer = tokio::spawn(async move {
+            for chunk in ["hel", "lo\r", "\nwor", "ld\n", "tail"] {
+                tx.write_all(chunk.as_bytes()).await.unwrap();
+                tokio::task::yield_now().await;
+            }
+        });
+
+        let mut reader = LineReader::with_capacity(rx, DEFAULT_CAPACITY);
+        let mut lines = vec![];
+        while let Some(line) = reader.next_line().await.unwrap() {
+            lines.push(line_str(&line.bytes).unwrap().to_owned());
+        }
+        writer.await.unwrap();
+
+        assert_eq!(lines, ["hello", "world", "tail"]);
+    }
+
+    #[tokio::test]
+    async fn invalid_utf8_is_rejected() {
+        let mut reader = LineReader::with_capacity(&b"\xff\xfe\n"[..], DEFAULT_CAPACITY);
+        let line = reader.next_line().await.unwrap().unwrap();
+        let err = line_str(&line.bytes).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    async fn read_truncated(input: &[u8], capacity: usize, max: usize) -> Vec<(String, bool)> {
+        let mut reader = LineReader::with_capacity(input, capacity).max_line_bytes(max);
+        let mut lines = vec![];
+        while let Some(line) = reader.next_line().await.unwrap() {
+            lines.push((line_str(&line.bytes).unwrap().to_owned(), line.truncated));
+        }
+        lines
+    }
+
+    #[tokio::test]
+    async fn long_lines_are_truncated() {
+        let input = b"short\n0123456789abc\n0123456789\r\nno newline at eof";
+        for capacity in [1, 4, 256] {
+            assert_eq!(
+                read_truncated(input, capacity, 10).await,
+                [
+                    ("short".to_owned(), false),
+                    ("0123456789".to_owned(), true),
+                    ("0123456789".to_owned(), false),
+                    ("no newline".to_owned(), true),
+                ],
+                "capacity {capacity}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn truncation_keeps_characters_whole() {
+        // `\u{00e9}` is 2 bytes and `\u{1f600}` 4, so both straddle the cut at 5 bytes
+        let input = "abcd\u{00e9}f\nabc\u{1f600}\n".as_bytes();
+        assert_eq!(
+            read_truncated(input, 256, 5).await,
+            [("abcd".to_owned(), true), ("abc".to_owned(), true)]
+        );
+    }
+
+    #[tokio::test]
+    async fn discarded_bytes_are_counted() {
+        let input = format!("{}\nafter\n{}", "x".repeat(100_000), "y".repeat(50));
+        let mut reader = LineReader::with_capacity(input.as_bytes(), 1024).max_line_bytes(10);
+        let mut read = 0;
+        while let Some(line) = reader.next_line().await.unwrap() {
+            read += line.bytes.len() + reader.take_discarded();
+        }
+        read += reader.take_discarded();
+        assert_eq!(read, 100_000 + "after".len() + 50);
+    }
+}
@@ -0,0 +1,154 @@
+use std::{convert::Infallible, sync::Arc, time::Duration};
+
+use tokio::{
+    sync::mpsc,
+    time::{MissedTickBehavior, interval},
+};
+
+use crate::tasks::{
+    async_tokio::{process_group::ProcessGroup, process_memory::RssSampler},
+    event::TaskEvent,
+};
+
+/// Interval between memory samples when `sample_interval_ms` is not configured
+const DEFAULT_SAMPLE_INTERVAL_MS: u64 = 1000;
+
+/// Share of the watermark, in percent, the usage must fall below to re-arm it
+const REARM_PERCENT: u64 = 90;
+
+/// Tells when sampled memory crosses the watermark
+///
+/// Armed at first. A sample at or above the watermark is a crossing and
+/// disarms it; it is armed again once a sample falls below `REARM_PERCENT`
+/// of the watermark, so usage hovering around the watermark reports once.
+#[derive(Debug)]
+pub(crate) struct Watermark {
+    bytes: u64,
+    rearm_below: u64,
+    armed: bool,
+}
+
+impl Watermark {
+    pub(crate) fn new(bytes: u64) -> Self {
+        Self {
+            bytes,
+            rearm_below: u64::try_from(u128::from(bytes) * u128::from(REARM_PERCENT) / 100)
+                .unwrap_or(bytes),
+            armed: true,
+        }
+    }
+
+    /// Whether `rss_bytes` crosses the watermark
+    pub(crate) fn crossed(&mut self, rss_bytes: u64) -> bool {
+        if self.armed && rss_bytes >= self.bytes {
+            self.armed = false;
+            return true;
+        }
+        if !self.armed && rss_bytes < self.rearm_below {
+            self.armed = true;
+        }
+        false
+    }
+}
+
+/// Emits `TaskEvent::MemoryWatermarkExceeded` each time the sampled memory of
+/// the task crosses its watermark, and never resolves.
+///
+/// Polled as a branch of the wait watcher, so sampling stops as soon as the
+/// process stops. Never emits anything if no watermark is configured, or if
+/// the platform reports no memory usage. Samples the whole process group on
+/// Linux when one is used.
+///
+/// # Arguments
+///
+/// * `event_tx` - Sender for task events.
+/// * `task_name` - Name of the task.
+/// * `pid` - Process ID of the child, if it is still known.
+/// * `process_group` - Process group of the task, if used.
+/// * `watermark_bytes` - Watermark in bytes, if configured.
+/// * `interval_ms` - Interval between samples in milliseconds, if configured.
+pub(crate) async fn report_memory_watermark(
+    event_tx: &mpsc::Sender<TaskEvent>,
+    task_name: &Arc<str>,
+    pid: Option<u32>,
+    process_group: Option<&ProcessGroup>,
+    watermark_bytes: Option<u64>,
+    interval_ms: Option<u64>,
+) -> Infallible {
+    let (Some(pid), Some(watermark_bytes)) = (pid, watermark_bytes) else {
+        return std::future::pending().await;
+    };
+    #[cfg(unix)]
+    let group = match process_group {
+        Some(process_group) => process_group.id().await.map(i32::cast_unsigned),
+        None => None,
+    };
+    #[cfg(not(unix))]
+    let group = {
+        let _ = process_group;
+        None
+    };
+    let mut sampler = RssSampler::new(pid, group);
+    let mut watermark = Watermark::new(watermark_bytes);
+
+    let period = Duration::from_millis(interval_ms.unwrap_or(DEFAULT_SAMPLE_INTERVAL_MS));
+    let mut ticks = interval(period);
+    ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        ticks.tick().await;
+        let Some(rss_bytes) = sampler.sample() else {
+            continue;
+        };
+        if !watermark.crossed(rss_bytes) {
+            continue;
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            rss_bytes,
+            watermark_bytes,
+            "Task memory is above its watermark"
+        );
+        let event = TaskEvent::MemoryWatermarkExceeded {
+            task_name: task_name.clone(),
+            rss_bytes,
+            watermark_bytes,
+        };
+        if event_tx.send(event).await.is_err() {
+            #[cfg(feature = "tracing")]
+            tracing::warn!("Event channel closed while sending TaskEvent::MemoryWatermarkExceeded");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crossing_reported_once_until_usage_falls_below_rearm_level() {
+        let mut watermark = Watermark::new(1000);
+
+        assert!(!watermark.crossed(999));
+        assert!(watermark.crossed(1000));
+        // Hovering around the watermark does not flap
+        assert!(!watermark.crossed(950));
+        assert!(!watermark.crossed(1200));
+        assert!(!watermark.crossed(900));
+        // Re-armed below 90%
+        assert!(!watermark.crossed(899));
+        assert!(!watermark.crossed(999));
+        assert!(watermark.crossed(1001));
+    }
+
+    #[test]
+    fn rearm_level_does_not_overflow() {
+        let mut watermark = Watermark::new(u64::MAX);
+
+        assert!(watermark.crossed(u64::MAX));
+        assert!(!watermark.crossed(u64::MAX - u64::MAX / 10));
+        assert!(!watermark.crossed(u64::MAX));
+        assert!(!watermark.crossed(u64::MAX / 10 * 8));
+        assert!(watermark.crossed(u64::MAX));
+    }
+}
@@ -1,5 +1,17 @@
+pub(crate) mod channel_closed;
+pub(crate) mod decoder;
+pub(crate) mod dedupe;
+pub(crate) mod health;
 pub(crate) mod input;
+pub(crate) mod line_reader;
+pub(crate) mod memory_watermark;
 pub(crate) mod output;
+pub(crate) mod output_hash;
+pub(crate) mod pause;
 pub(crate) mod result;
+pub(crate) mod running_long;
+pub(crate) mod script;
+pub(crate) mod send_stats;
+pub(crate) mod throttle;
 pub(crate) mod timeout;
 pub(crate) mod wait;
@@ -1,112 +1,642 @@
-use std::sync::Arc;
+use std::{
+    borrow::Cow,
+    pin::Pin,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    time::{Duration, SystemTime},
+};
 
 use tokio::{
-    io::{AsyncBufReadExt, BufReader},
+    io::AsyncReadExt,
     process::Child,
-    sync::{RwLock, mpsc},
-    task::JoinHandle,
+    sync::{
+        RwLock,
+        mpsc::{self, error::TrySendError},
+    },
+    time::{Instant, sleep_until},
 };
 
+use super::decoder::DecodingReader;
+use super::dedupe::{LineDeduper, RepeatSummary};
+use super::line_reader::{DEFAULT_CAPACITY, DEFAULT_MAX_LINE_BYTES, Line, LineReader, line_str};
+#[cfg(feature = "sha2")]
+use super::output_hash::HashingReader;
+use super::output_hash::SharedOutputDigests;
+use super::script::SharedScriptProgress;
+use super::send_stats::EventSendStats;
+use super::throttle::{OutputThrottle, SuppressedSummary};
 use crate::{
     helper::tracing::MaybeInstrument,
-    tasks::{config::StreamSource, event::TaskEvent, state::TaskState},
+    tasks::{
+        async_tokio::{
+            output_tail::SharedOutputTail,
+            spawner::{ChannelClosedPolicy, SharedStopSender, StopRequest},
+            startup_output::SharedStartupOutput,
+            transform::OutputTransform,
+            watcher_set::WatcherSet,
+        },
+        config::{
+            OutputEncoding, OutputMode, OutputTrigger, SharedTaskConfig, StreamSource,
+            TriggerAction,
+        },
+        event::{TaskEvent, TaskTerminateReason},
+        state::TaskState,
+    },
 };
 
+/// How long an output watcher keeps reading after the termination signal
+///
+/// Gives the watcher a chance to read the pipe to EOF once the process has exited.
+const OUTPUT_DRAIN_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Longest line that is parsed as JSON when `parse_json_lines` is enabled
+///
+/// Longer lines are sent as plain output, bounding the memory a single
+/// parsed value can take.
+#[cfg(feature = "serde")]
+const MAX_JSON_LINE_BYTES: usize = 1024 * 1024;
+
 /// Configuration for spawning output watchers
 #[derive(Debug)]
 struct OutputWatcherConfig {
-    task_name: String,
+    task_name: Arc<str>,
     state: Arc<RwLock<TaskState>>,
     event_tx: mpsc::Sender<TaskEvent>,
     src: StreamSource,
     handle_terminator_rx: tokio::sync::watch::Receiver<bool>,
-    ready_indicator: Option<String>,
-    ready_indicator_source: StreamSource,
+    config: SharedTaskConfig,
+    dropped_events: Arc<AtomicU64>,
+    send_stats: Arc<EventSendStats>,
+    ready_flag: Arc<AtomicBool>,
+    output_transform: Option<OutputTransform>,
+    startup_output: SharedStartupOutput,
+    output_tail: SharedOutputTail,
+    terminate_tx: SharedStopSender,
+    output_seq: Arc<AtomicU64>,
+    output_bytes: Arc<AtomicU64>,
+    on_channel_closed: ChannelClosedPolicy,
+    script: Option<SharedScriptProgress>,
+}
+
+/// Future running the watcher of one output stream
+type WatcherFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Outcome of delivering a single event from an output watcher
+#[derive(Debug, PartialEq)]
+enum SendOutcome {
+    /// Event was delivered to the channel
+    Sent,
+    /// Event was discarded because the consumer stalled
+    Dropped,
+    /// Event channel receiver has been dropped
+    Closed,
 }
 
 /// Spawns watchers for stdout and stderr of a child process.
 ///
-/// Sends output lines as `TaskEvent::Output` events.
+/// Sends output lines as `TaskEvent::Output` events and acts on the output triggers.
 ///
 /// # Arguments
 ///
+/// * `watchers` - Set the watchers are spawned into.
 /// * `task_name` - Name of the task.
 /// * `state` - Shared state of the task.
 /// * `event_tx` - Sender for task events.
 /// * `child` - The child process to monitor.
 /// * `handle_terminator_rx` - Receiver to listen for termination signals.
-/// * `ready_indicator` - Optional string indicating readiness.
-/// * `ready_indicator_source` - Optional source for the ready indicator (stdout or stderr).
-///
-/// # Returns
-///
-/// A vector of `JoinHandle` for the spawned watcher tasks.
+/// * `config` - Shared task configuration providing the output triggers and event send timeout.
+/// * `dropped_events` - Shared counter of events dropped due to a stalled consumer.
+/// * `send_stats` - Shared statistics of the sends to the event channel.
+/// * `ready_flag` - Set once the task reaches the Ready state; never cleared.
+/// * `output_transform` - Optional transform applied to each line before it is sent.
+/// * `startup_output` - Shared buffer of the first output lines of both streams.
+/// * `output_tail` - Shared ring buffer of the last output lines of both streams.
+/// * `terminate_tx` - Shared sender used to stop the task when a `FailTask` trigger matches.
+/// * `output_bytes` - Shared counter of the bytes read from both streams.
+/// * `output_digests` - Shared digests of the streams, set if `hash_output` is enabled.
+/// * `on_channel_closed` - Whether the watchers keep reading once the event channel is closed.
+/// * `script` - Progress of the stdin script, whose awaited line the watchers look for.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn spawn_output_watchers(
-    task_name: String,
+    watchers: &mut WatcherSet,
+    task_name: Arc<str>,
     state: Arc<RwLock<TaskState>>,
     event_tx: mpsc::Sender<TaskEvent>,
     child: &mut Child,
     handle_terminator_rx: tokio::sync::watch::Receiver<bool>,
-    ready_indicator: Option<String>,
-    ready_indicator_source: Option<StreamSource>,
-) -> Vec<JoinHandle<()>> {
-    let mut handles: Vec<JoinHandle<()>> = vec![];
-    // Spawn stdout watcher
-    if let Some(stdout) = child.stdout.take() {
-        let config = OutputWatcherConfig {
-            task_name: task_name.clone(),
-            state: state.clone(),
-            event_tx: event_tx.clone(),
-            src: StreamSource::Stdout,
-            handle_terminator_rx: handle_terminator_rx.clone(),
-            ready_indicator: ready_indicator.clone(),
-            ready_indicator_source: ready_indicator_source.clone().unwrap_or_default(),
+    config: &SharedTaskConfig,
+    dropped_events: Arc<AtomicU64>,
+    send_stats: Arc<EventSendStats>,
+    ready_flag: Arc<AtomicBool>,
+    output_transform: Option<OutputTransform>,
+    startup_output: SharedStartupOutput,
+    output_tail: SharedOutputTail,
+    terminate_tx: SharedStopSender,
+    output_bytes: Arc<AtomicU64>,
+    output_digests: &SharedOutputDigests,
+    on_channel_closed: ChannelClosedPolicy,
+    script: Option<SharedScriptProgress>,
+) {
+    // Shared by both streams so the sequence numbers order lines across them
+    let output_seq = Arc::new(AtomicU64::new(0));
+    let watcher_config = |src| OutputWatcherConfig {
+        task_name: task_name.clone(),
+        state: state.clone(),
+        event_tx: event_tx.clone(),
+        src,
+        handle_terminator_rx: handle_terminator_rx.clone(),
+        config: config.clone(),
+        dropped_events: dropped_events.clone(),
+        send_stats: send_stats.clone(),
+        ready_flag: ready_flag.clone(),
+        output_transform: output_transform.clone(),
+        startup_output: startup_output.clone(),
+        output_tail: output_tail.clone(),
+        terminate_tx: terminate_tx.clone(),
+        output_seq: output_seq.clone(),
+        output_bytes: output_bytes.clone(),
+        on_channel_closed,
+        script: script.clone(),
+    };
+    let stdout = child
+        .stdout
+        .take()
+        .map(|stdout| hashed_watcher(stdout, watcher_config(StreamSource::Stdout), output_digests));
+    let stderr = child
+        .stderr
+        .take()
+        .map(|stderr| hashed_watcher(stderr, watcher_config(StreamSource::Stderr), output_digests));
+
+    match (stdout, stderr) {
+        (Some(stdout), Some(stderr)) if config.preserve_interleaving.unwrap_or_default() => {
+            // One task reads both pipes, so neither stream runs ahead of the other
+            // on a different worker thread
+            watchers.spawn("output", async move {
+                tokio::join!(stdout, stderr);
+            });
+        }
+        (stdout, stderr) => {
+            if let Some(stdout) = stdout {
+                watchers.spawn("stdout", stdout);
+            }
+            if let Some(stderr) = stderr {
+                watchers.spawn("stderr", stderr);
+            }
+        }
+    }
+}
+
+/// Delivers an event from an output watcher to the event channel.
+///
+/// The event is first offered with `try_send`, so a consumer that keeps up costs
+/// no await point per line. Only when the channel is full is the slow path taken,
+/// counted in `send_stats`. Without a send timeout the slow path waits for channel
+/// capacity like a plain `send`.
+/// With a send timeout, a send that does not complete in time switches the stream
+/// into drop mode (`drop_mode` is set), after which events are only delivered when
+/// the channel has room. This keeps the watcher draining the child's pipe even when
+/// the consumer stops polling.
+///
+/// # Arguments
+///
+/// * `event_tx` - Channel sender for emitting task events.
+/// * `event` - The event to deliver.
+/// * `send_timeout` - Optional maximum time to wait for channel capacity.
+/// * `drop_mode` - Whether the stream has already switched to drop mode.
+/// * `send_stats` - Statistics of the sends, which may call for `TaskEvent::SlowConsumer`.
+///
+/// # Returns
+///
+/// The [`SendOutcome`] of the delivery attempt.
+async fn send_event(
+    event_tx: &mpsc::Sender<TaskEvent>,
+    event: TaskEvent,
+    send_timeout: Option<Duration>,
+    drop_mode: &mut bool,
+    send_stats: &EventSendStats,
+) -> SendOutcome {
+    let outcome = deliver(event_tx, event, send_timeout, drop_mode, send_stats).await;
+    if outcome != SendOutcome::Closed
+        && let Some(warning) = send_stats.take_warning()
+    {
+        // Delivered like output, so it can be dropped in drop mode too
+        let _ = deliver(event_tx, warning, send_timeout, drop_mode, send_stats).await;
+    }
+    outcome
+}
+
+/// Delivers a single event for [`send_event`], recording the wait in `send_stats`
+async fn deliver(
+    event_tx: &mpsc::Sender<TaskEvent>,
+    event: TaskEvent,
+    send_timeout: Option<Duration>,
+    drop_mode: &mut bool,
+    send_stats: &EventSendStats,
+) -> SendOutcome {
+    if *drop_mode {
+        return match event_tx.try_send(event) {
+            Ok(()) => {
+                send_stats.record_send(event_tx, Duration::ZERO);
+                SendOutcome::Sent
+            }
+            Err(TrySendError::Full(_)) => SendOutcome::Dropped,
+            Err(TrySendError::Closed(_)) => SendOutcome::Closed,
         };
-        let handle = spawn_std_watcher(stdout, config);
-        handles.push(handle);
     }
 
-    // Spawn stderr watcher
-    if let Some(stderr) = child.stderr.take() {
-        let config = OutputWatcherConfig {
-            task_name,
-            state,
-            event_tx,
-            src: StreamSource::Stderr,
-            handle_terminator_rx,
-            ready_indicator,
-            ready_indicator_source: ready_indicator_source.unwrap_or_default(),
+    let event = match event_tx.try_send(event) {
+        Ok(()) => {
+            send_stats.record_send(event_tx, Duration::ZERO);
+            return SendOutcome::Sent;
+        }
+        Err(TrySendError::Closed(_)) => return SendOutcome::Closed,
+        Err(TrySendError::Full(event)) => event,
+    };
+    send_stats.record_slow();
+    let started = Instant::now();
+
+    let Some(send_timeout) = send_timeout else {
+        return match event_tx.send(event).await {
+            Ok(()) => {
+                send_stats.record_send(event_tx, started.elapsed());
+                SendOutcome::Sent
+            }
+            Err(_) => SendOutcome::Closed,
+        };
+    };
+
+    match tokio::time::timeout(send_timeout, event_tx.send(event)).await {
+        Ok(Ok(())) => {
+            send_stats.record_send(event_tx, started.elapsed());
+            SendOutcome::Sent
+        }
+        Ok(Err(_)) => SendOutcome::Closed,
+        Err(_) => {
+            send_stats.record_send(event_tx, started.elapsed());
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                send_timeout = ?send_timeout,
+                "Event channel consumer stalled, switching output stream to drop mode"
+            );
+            *drop_mode = true;
+            SendOutcome::Dropped
+        }
+    }
+}
+
+/// Builds the event for an output line
+///
+/// With `parse_json` set, a line holding a JSON object or array becomes
+/// `TaskEvent::OutputJson`; any other line, and a truncated one, becomes
+/// `TaskEvent::Output`.
+fn output_event(
+    task_name: &Arc<str>,
+    line: Cow<'_, str>,
+    src: &StreamSource,
+    seq: u64,
+    truncated: bool,
+    timestamp: SystemTime,
+    parse_json: bool,
+) -> TaskEvent {
+    #[cfg(feature = "serde")]
+    if parse_json
+        && !truncated
+        && let Some(value) = parse_json_line(&line)
+    {
+        return TaskEvent::OutputJson {
+            task_name: task_name.clone(),
+            line: line.into_owned(),
+            value,
+            src: src.clone(),
+            seq,
+            timestamp,
         };
-        let handle = spawn_std_watcher(stderr, config);
-        handles.push(handle);
     }
+    #[cfg(not(feature = "serde"))]
+    let _ = parse_json;
+    TaskEvent::Output {
+        task_name: task_name.clone(),
+        line: line.into_owned(),
+        src: src.clone(),
+        seq,
+        truncated,
+        timestamp,
+    }
+}
+
+/// Sends a summary of output not sent, `TaskEvent::OutputRepeated` or `TaskEvent::OutputSuppressed`
+///
+/// A summary dropped because the consumer stalled is counted in `dropped_events`.
+///
+/// # Returns
+///
+/// `false` once the event channel is closed.
+async fn send_summary(
+    event_tx: &mpsc::Sender<TaskEvent>,
+    event: TaskEvent,
+    send_timeout: Option<Duration>,
+    drop_mode: &mut bool,
+    send_stats: &EventSendStats,
+    dropped_events: &AtomicU64,
+) -> bool {
+    match send_event(event_tx, event, send_timeout, drop_mode, send_stats).await {
+        SendOutcome::Sent => true,
+        SendOutcome::Dropped => {
+            dropped_events.fetch_add(1, Ordering::Relaxed);
+            true
+        }
+        SendOutcome::Closed => {
+            #[cfg(feature = "tracing")]
+            tracing::warn!("Event channel closed while sending output summary");
+            false
+        }
+    }
+}
+
+/// Builds the `TaskEvent::OutputRepeated` event for a summary
+fn repeated_event(
+    task_name: &Arc<str>,
+    (line, count): RepeatSummary,
+    src: &StreamSource,
+) -> TaskEvent {
+    TaskEvent::OutputRepeated {
+        task_name: task_name.clone(),
+        line,
+        src: src.clone(),
+        count,
+    }
+}
+
+/// Builds the `TaskEvent::OutputSuppressed` event for a summary
+fn suppressed_event(
+    task_name: &Arc<str>,
+    (count, bytes): SuppressedSummary,
+    src: &StreamSource,
+) -> TaskEvent {
+    TaskEvent::OutputSuppressed {
+        task_name: task_name.clone(),
+        src: src.clone(),
+        count,
+        bytes,
+    }
+}
+
+/// Asks the wait watcher to stop the task because a `FailTask` trigger matched
+///
+/// The stop reason names the pattern but not the line, which may hold secrets
+/// the output transform would have redacted. Does nothing if the task is
+/// already being stopped.
+async fn request_failure(terminate_tx: &SharedStopSender, pattern: &str, src: &StreamSource) {
+    if let Some(tx) = terminate_tx.lock().await.take() {
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            pattern,
+            "Failure pattern found in output stream, terminating task"
+        );
+        let message = format!("Output matched failure pattern {pattern:?} on {src:?}");
+        // The wait watcher is gone once the process has exited on its own
+        let _ = tx.send(StopRequest::Fail(message));
+    }
+}
+
+/// Asks the wait watcher to stop the task because a `Complete` trigger matched
+///
+/// Does nothing if the task is already being stopped.
+async fn request_completion(terminate_tx: &SharedStopSender, _pattern: &str) {
+    if let Some(tx) = terminate_tx.lock().await.take() {
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            pattern = _pattern,
+            "Completion indicator found in output stream, terminating task"
+        );
+        // The wait watcher is gone once the process has exited on its own
+        let _ = tx.send(StopRequest::Terminate(
+            TaskTerminateReason::CompletionIndicator,
+        ));
+    }
+}
+
+/// Moves the task from Running to Ready
+///
+/// Returns `false` if the task is not running: the watcher of the other stream
+/// made it ready first, or it finished. Checked under the state lock, so only
+/// one watcher sends `TaskEvent::Ready`.
+async fn mark_ready(state: &RwLock<TaskState>) -> bool {
+    let mut state = state.write().await;
+    if *state != TaskState::Running {
+        return false;
+    }
+    *state = TaskState::Ready;
+    true
+}
+
+/// Removes the one-shot triggers whose action already ran or can no longer run
+///
+/// Once the task is ready, a server may log for hours; its lines are then only
+/// matched against the triggers that can still fire.
+fn disarm_triggers(triggers: &mut Vec<OutputTrigger>, ready: bool, failed: bool, completed: bool) {
+    triggers.retain(|trigger| match trigger.action {
+        TriggerAction::Ready => !ready,
+        TriggerAction::FailTask => !failed,
+        TriggerAction::Complete => !completed,
+        TriggerAction::EmitEvent(_) => true,
+    });
+}
 
-    handles
+/// Parses a line as JSON if it looks like an object or array
+///
+/// Scalars are not parsed, so plain output such as `42` or `true` stays text.
+#[cfg(feature = "serde")]
+fn parse_json_line(line: &str) -> Option<serde_json::Value> {
+    if line.len() > MAX_JSON_LINE_BYTES {
+        return None;
+    }
+    let trimmed = line.trim_start();
+    if !trimmed.starts_with('{') && !trimmed.starts_with('[') {
+        return None;
+    }
+    serde_json::from_str(trimmed).ok()
+}
+
+/// Builds the watcher for a single output stream, hashing its raw bytes first
+/// if `hash_output` is enabled.
+#[cfg_attr(not(feature = "sha2"), allow(unused_variables))]
+fn hashed_watcher<T>(
+    std: T,
+    config: OutputWatcherConfig,
+    output_digests: &SharedOutputDigests,
+) -> WatcherFuture
+where
+    T: tokio::io::AsyncRead + Unpin + Send + 'static,
+{
+    #[cfg(feature = "sha2")]
+    if config.config.hash_output.unwrap_or_default() {
+        let std = HashingReader::new(std, config.src.clone(), output_digests.clone());
+        return decoded_watcher(std, config);
+    }
+    decoded_watcher(std, config)
+}
+
+/// Builds the watcher for a single output stream, decoding it to UTF-8 first
+/// unless the configured encoding already is UTF-8.
+///
+/// In `OutputMode::Bytes` the stream is not decoded but sent in chunks.
+fn decoded_watcher<T>(std: T, config: OutputWatcherConfig) -> WatcherFuture
+where
+    T: tokio::io::AsyncRead + Unpin + Send + 'static,
+{
+    if config.config.output_mode == Some(OutputMode::Bytes) {
+        return chunk_watcher(std, config);
+    }
+    match config.config.output_encoding.unwrap_or_default() {
+        OutputEncoding::Utf8 => std_watcher(std, config),
+        encoding => std_watcher(DecodingReader::new(std, encoding), config),
+    }
 }
 
-/// Spawns a watcher for a single output stream (stdout or stderr).
+/// Builds the watcher for a single output stream in `OutputMode::Bytes`.
 ///
-/// Monitors the specified stream for output lines and ready indicators.
-/// Each line is sent as a `TaskEvent::Output` event. If a ready indicator
-/// is configured and matches the stream source, the task state is updated
-/// to `Ready` when the indicator text is found.
+/// Sends the bytes of each read, up to `output_buffer_bytes`, as a
+/// `TaskEvent::OutputChunk` without splitting or decoding them. Validation
+/// rules out the options that look at lines, so there is nothing to match.
+///
+/// Drop mode, the channel closed policy and draining on the termination
+/// signal work as in [`std_watcher`].
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(stream = ?config.src)))]
+fn chunk_watcher<T>(mut std: T, config: OutputWatcherConfig) -> WatcherFuture
+where
+    T: tokio::io::AsyncRead + Unpin + Send + 'static,
+{
+    let OutputWatcherConfig {
+        task_name,
+        event_tx,
+        src,
+        mut handle_terminator_rx,
+        config,
+        dropped_events,
+        send_stats,
+        output_bytes,
+        on_channel_closed,
+        ..
+    } = config;
+    let event_send_timeout = config.event_send_timeout_ms.map(Duration::from_millis);
+    let detach_on_close = on_channel_closed == ChannelClosedPolicy::DetachAndContinue;
+    let buffer_bytes = config.output_buffer_bytes.unwrap_or(DEFAULT_CAPACITY);
+    Box::pin(
+        async move {
+            let mut buf = vec![0; buffer_bytes];
+            let mut drop_mode = false;
+            let mut detached = false;
+            let mut drain_deadline: Option<Instant> = None;
+            loop {
+                tokio::select! {
+                    read_result = std.read(&mut buf) => {
+                        match read_result {
+                            Ok(0) => {
+                                // EOF
+                                break;
+                            }
+                            Ok(len) => {
+                                output_bytes.fetch_add(len as u64, Ordering::Relaxed);
+                                if detached {
+                                    continue;
+                                }
+                                // In drop mode a full channel would discard the event anyway,
+                                // so skip copying the chunk
+                                let outcome = if drop_mode && event_tx.capacity() == 0 && !event_tx.is_closed() {
+                                    SendOutcome::Dropped
+                                } else {
+                                    let event = TaskEvent::OutputChunk {
+                                        task_name: task_name.clone(),
+                                        data: buf[..len].to_vec(),
+                                        src: src.clone(),
+                                    };
+                                    send_event(&event_tx, event, event_send_timeout, &mut drop_mode, &send_stats).await
+                                };
+                                match outcome {
+                                    SendOutcome::Sent => {}
+                                    SendOutcome::Dropped => {
+                                        dropped_events.fetch_add(1, Ordering::Relaxed);
+                                    }
+                                    SendOutcome::Closed => {
+                                        #[cfg(feature = "tracing")]
+                                        tracing::warn!("Event channel closed while sending TaskEvent::OutputChunk");
+                                        if !detach_on_close {
+                                            break;
+                                        }
+                                        detached = true;
+                                    }
+                                }
+                            }
+                            #[allow(clippy::used_underscore_binding)]
+                            Err(_e) => {
+                                #[cfg(feature = "tracing")]
+                                tracing::warn!(error=%_e, "Error reading chunk from output stream");
+                                break;
+                            }
+                        }
+                    }
+                    changed = handle_terminator_rx.changed(), if drain_deadline.is_none() => {
+                        if changed.is_err() || *handle_terminator_rx.borrow() {
+                            #[cfg(feature = "tracing")]
+                            tracing::debug!("Termination signal received, draining output watcher");
+                            drain_deadline = Some(Instant::now() + OUTPUT_DRAIN_TIMEOUT);
+                        }
+                    }
+                    () = sleep_until(drain_deadline.unwrap_or_else(Instant::now)), if drain_deadline.is_some() => {
+                        #[cfg(feature = "tracing")]
+                        tracing::debug!("Output drain timeout elapsed, closing output watcher");
+                        break;
+                    }
+                }
+            }
+            #[cfg(feature = "tracing")]
+            tracing::debug!("Watcher finished");
+        }
+        .maybe_instrument("spawn"),
+    )
+}
+
+/// Builds the watcher for a single output stream (stdout or stderr).
+///
+/// Monitors the specified stream for output lines and output triggers.
+/// Each line is sent as a `TaskEvent::Output` event, then the triggers of
+/// this stream that match it act in order: `Ready` (including the legacy
+/// ready indicator) updates the task state to `Ready` once, `FailTask`
+/// stops the task once, and `EmitEvent` sends `TaskEvent::Triggered`.
+///
+/// If an event send timeout is configured and the consumer stalls, events are
+/// dropped and counted in `dropped_events` instead of blocking the stream.
+///
+/// With `dedupe_output` configured, repeats of a line beyond the threshold are
+/// not sent but summarized in `TaskEvent::OutputRepeated`. Triggers are
+/// matched before deduplication, so a collapsed line can still make the
+/// task ready.
+///
+/// With `max_output_events_per_sec` configured, lines beyond the budget of a
+/// second are not sent but summarized in `TaskEvent::OutputSuppressed` once
+/// the second is over. As with deduplication, triggers are matched first.
+///
+/// A line that is not sent still consumes tokio's cooperative budget, so a
+/// burst of skipped lines gives way to other tasks like one of sent lines.
+/// With `yield_every_n_lines` configured, the watcher also yields after that
+/// many lines.
+///
+/// On the termination signal the watcher keeps reading until EOF, bounded by
+/// `OUTPUT_DRAIN_TIMEOUT`, so output still buffered in the pipe is delivered.
 ///
 /// # Arguments
 ///
 /// * `std` - The async readable stream to monitor.
-/// * `task_name` - Name of the task for event identification.
-/// * `state` - Shared task state for ready indicator updates.
-/// * `event_tx` - Channel sender for emitting task events.
-/// * `src` - Source stream type (stdout or stderr).
-/// * `handle_terminator_rx` - Receiver for termination signals.
-/// * `ready_indicator` - Optional text to watch for readiness.
-/// * `ready_indicator_source` - Stream source where ready indicator is expected.
+/// * `config` - Watcher configuration (task name, state, channels, triggers, send timeout).
 ///
 /// # Returns
 ///
-/// A `JoinHandle` for the spawned watcher task.
+/// The watcher future, to be spawned on its own or joined with the other stream.
 #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(stream = ?config.src)))]
-fn spawn_std_watcher<T>(std: T, config: OutputWatcherConfig) -> JoinHandle<()>
+fn std_watcher<T>(std: T, config: OutputWatcherConfig) -> WatcherFuture
 where
     T: tokio::io::AsyncRead + Unpin + Send + 'static,
 {
@@ -116,101 +646,357 @@ where
         event_tx,
         src,
         mut handle_terminator_rx,
-        ready_indicator,
-        ready_indicator_source,
+        config,
+        dropped_events,
+        send_stats,
+        ready_flag,
+        output_transform,
+        startup_output,
+        output_tail,
+        terminate_tx,
+        output_seq,
+        output_bytes,
+        on_channel_closed,
+        script,
     } = config;
-    let handle = tokio::spawn(
+    let event_send_timeout = config.event_send_timeout_ms.map(Duration::from_millis);
+    let mut triggers = config.stream_triggers(&src);
+    let ready_regex = config.stream_ready_regex(&src);
+    let match_mode = config.ready_match_mode.unwrap_or_default();
+    let parse_json = config.parse_json_lines.unwrap_or_default();
+    let detach_on_close = on_channel_closed == ChannelClosedPolicy::DetachAndContinue;
+    let retain_output = config.retain_output_lines.is_some_and(|lines| lines > 0);
+    let transform_before_ready = output_transform
+        .as_ref()
+        .is_some_and(OutputTransform::runs_before_ready_check);
+    Box::pin(
         async move {
-            let reader = BufReader::new(std);
-            let mut lines = reader.lines();
+            let buffer_bytes = config.output_buffer_bytes.unwrap_or(DEFAULT_CAPACITY);
+            let max_line_bytes = config.max_output_line_bytes.unwrap_or(DEFAULT_MAX_LINE_BYTES);
+            let mut lines = LineReader::with_capacity(std, buffer_bytes).max_line_bytes(max_line_bytes);
             let mut ready_found = false;
-            loop {
+            let mut failure_requested = false;
+            let mut completion_requested = false;
+            let mut drop_mode = false;
+            // Set once the event channel closed with `DetachAndContinue`: the stream
+            // is still read to EOF, but nothing is sent anymore
+            let mut detached = false;
+            let mut drain_deadline: Option<Instant> = None;
+            let mut deduper = config.dedupe_output.map(LineDeduper::new);
+            let mut throttle = config.max_output_events_per_sec.map(OutputThrottle::new);
+            let yield_every = config.yield_every_n_lines;
+            let mut lines_since_yield = 0;
+            // Cleared once the startup buffer is full, so later lines skip the lock
+            let mut capture_startup = true;
+            let mut last_timestamp = SystemTime::UNIX_EPOCH;
+            'read: loop {
+                if detached {
+                    // Summaries would not be sent either
+                    deduper = None;
+                    throttle = None;
+                }
+                let summary_deadline = deduper.as_ref().and_then(LineDeduper::deadline);
+                let throttle_deadline = throttle.as_ref().and_then(OutputThrottle::deadline);
                 tokio::select! {
                     line_result = lines.next_line() => {
                         match line_result {
-                            Ok(Some(line)) => {
+                            Ok(Some(Line { bytes, truncated })) => {
+                                // Kept in order if the system clock steps back
+                                let timestamp = SystemTime::now().max(last_timestamp);
+                                last_timestamp = timestamp;
+                                // Including the end of the previous line, if it was truncated
+                                let read = bytes.len() + lines.take_discarded();
+                                output_bytes.fetch_add(read as u64, Ordering::Relaxed);
+                                let line = match line_str(&bytes) {
+                                    Ok(line) => line,
+                                    #[allow(clippy::used_underscore_binding)]
+                                    Err(_e) => {
+                                        #[cfg(feature = "tracing")]
+                                        tracing::warn!(error=%_e, "Error reading line from output stream");
+                                        break;
+                                    }
+                                };
                                 #[cfg(feature = "tracing")]
-                                tracing::trace!(line = %line);
+                                tracing::trace!(line);
+                                // Taken at read time, so dropped lines leave gaps
+                                let seq = output_seq.fetch_add(1, Ordering::Relaxed);
 
-                                let line_for_ready = if ready_indicator_source == src && !ready_found {
-                                    line.clone()
-                                } else {
-                                    String::new()
+                                // `None` once the transform dropped the line
+                                let mut output = Some(Cow::Borrowed(line));
+                                if transform_before_ready && let Some(transform) = &output_transform {
+                                    output = transform.apply(line, &src);
+                                }
+
+                                // The task may have become ready on the other stream
+                                if !ready_found && (!triggers.is_empty() || ready_regex.is_some()) && ready_flag.load(Ordering::Relaxed) {
+                                    ready_found = true;
+                                    disarm_triggers(&mut triggers, ready_found, failure_requested, completion_requested);
+                                }
+
+                                // Checked on the borrowed line, before it is moved into the event
+                                let matched: Vec<&OutputTrigger> = match output.as_deref() {
+                                    Some(text) if !triggers.is_empty() || ready_regex.is_some() => triggers
+                                        .iter()
+                                        .filter(|trigger| match_mode.matches(text, &trigger.pattern))
+                                        .chain(ready_regex.as_ref().filter(|_| !ready_found).and_then(|regex| regex.find(text)))
+                                        .collect(),
+                                    _ => Vec::new(),
                                 };
+                                let mut fired = false;
 
-                                if (event_tx
-                                    .send(TaskEvent::Output {
-                                        task_name: task_name.clone(),
-                                        line,
-                                        src: src.clone(),
-                                    })
-                                    .await).is_err()
-                                {
-                                    #[cfg(feature = "tracing")]
-                                    tracing::warn!("Event channel closed while sending TaskEvent::Output");
-                                    break;
+                                if let Some(script) = &script && let Some(text) = output.as_deref() {
+                                    script.observe(text, &src);
                                 }
 
-                                // Check for ready indicator
-                                if ready_indicator_source != src || ready_found {
-                                    continue;
+                                if !transform_before_ready && let Some(transform) = &output_transform {
+                                    output = transform.apply(line, &src);
                                 }
-                                let Some(ready_indicator) = &ready_indicator else { continue };
 
-                                if line_for_ready.contains(ready_indicator) {
-                                    ready_found = true;
-                                    #[cfg(feature = "tracing")]
-                                    tracing::debug!(stream=?src, "Ready indicator found in output stream");
-                               
-                                    #[cfg(feature = "tracing")]
-                                    tracing::debug!("Updating task state to Ready");
-                                    *state.write().await = TaskState::Ready;
-                                    if (event_tx
-                                        .send(TaskEvent::Ready {
-                                            task_name: task_name.clone(),
-                                        })
-                                        .await).is_err()
-                                    {
-                                        #[cfg(feature = "tracing")]
-                                        tracing::warn!("Event channel closed while sending TaskEvent::Ready");
-                                        break;
+                                // `TaskEvent::Triggered` carries the line as sent, so a dropped line emits nothing
+                                let triggered_line = output
+                                    .as_deref()
+                                    .filter(|_| matched.iter().any(|trigger| matches!(trigger.action, TriggerAction::EmitEvent(_))))
+                                    .map(str::to_owned);
+
+                                if capture_startup && let Some(output) = &output {
+                                    capture_startup = startup_output
+                                        .lock()
+                                        .unwrap_or_else(std::sync::PoisonError::into_inner)
+                                        .push(&src, output);
+                                }
+
+                                if retain_output && let Some(output) = &output {
+                                    output_tail
+                                        .lock()
+                                        .unwrap_or_else(std::sync::PoisonError::into_inner)
+                                        .push(&src, output);
+                                }
+
+                                let mut send_output = true;
+                                if let Some(deduper) = &mut deduper && let Some(output) = &output {
+                                    let (summary, send) = deduper.push(output, Instant::now());
+                                    send_output = send;
+                                    if let Some(summary) = summary {
+                                        let event = repeated_event(&task_name, summary, &src);
+                                        if !send_summary(&event_tx, event, event_send_timeout, &mut drop_mode, &send_stats, &dropped_events).await {
+                                            if !detach_on_close {
+                                                break;
+                                            }
+                                            detached = true;
+                                        }
+                                    }
+                                }
+
+                                if send_output && let Some(throttle) = &mut throttle && let Some(output) = &output {
+                                    let (summary, send) = throttle.push(output.len(), Instant::now());
+                                    send_output = send;
+                                    if let Some(summary) = summary {
+                                        let event = suppressed_event(&task_name, summary, &src);
+                                        if !send_summary(&event_tx, event, event_send_timeout, &mut drop_mode, &send_stats, &dropped_events).await {
+                                            if !detach_on_close {
+                                                break;
+                                            }
+                                            detached = true;
+                                        }
+                                    }
+                                }
+
+                                if let Some(output) = output.filter(|_| send_output && !detached) {
+                                    // In drop mode a full channel would discard the event anyway,
+                                    // so skip allocating it
+                                    let outcome = if drop_mode && event_tx.capacity() == 0 && !event_tx.is_closed() {
+                                        SendOutcome::Dropped
+                                    } else {
+                                        let event = output_event(&task_name, output, &src, seq, truncated, timestamp, parse_json);
+                                        send_event(&event_tx, event, event_send_timeout, &mut drop_mode, &send_stats).await
+                                    };
+                                    match outcome {
+                                        SendOutcome::Sent => {}
+                                        SendOutcome::Dropped => {
+                                            dropped_events.fetch_add(1, Ordering::Relaxed);
+                                        }
+                                        SendOutcome::Closed => {
+                                            #[cfg(feature = "tracing")]
+                                            tracing::warn!("Event channel closed while sending TaskEvent::Output");
+                                            if !detach_on_close {
+                                                break;
+                                            }
+                                            detached = true;
+                                        }
+                                    }
+                                } else {
+                                    // No send consumed the budget for this line
+                                    tokio::task::coop::consume_budget().await;
+                                }
+
+                                for trigger in matched {
+                                    match &trigger.action {
+                                        TriggerAction::Ready => {
+                                            if ready_found {
+                                                continue;
+                                            }
+                                            ready_found = true;
+                                            fired = true;
+                                            #[cfg(feature = "tracing")]
+                                            tracing::debug!(stream=?src, "Ready indicator found in output stream");
+
+                                            #[cfg(feature = "tracing")]
+                                            tracing::debug!("Updating task state to Ready");
+                                            if !mark_ready(&state).await {
+                                                // Ready on the other stream, or already finished, e.g.
+                                                // indicator read while draining
+                                                continue;
+                                            }
+                                            ready_flag.store(true, Ordering::Relaxed);
+                                            if detached {
+                                                continue;
+                                            }
+                                            let event = TaskEvent::Ready {
+                                                task_name: task_name.clone(),
+                                            };
+                                            match send_event(&event_tx, event, event_send_timeout, &mut drop_mode, &send_stats).await {
+                                                SendOutcome::Sent => {}
+                                                SendOutcome::Dropped => {
+                                                    dropped_events.fetch_add(1, Ordering::Relaxed);
+                                                }
+                                                SendOutcome::Closed => {
+                                                    #[cfg(feature = "tracing")]
+                                                    tracing::warn!("Event channel closed while sending TaskEvent::Ready");
+                                                    if !detach_on_close {
+                                                        break 'read;
+                                                    }
+                                                    detached = true;
+                                                }
+                                            }
+                                        }
+                                        TriggerAction::FailTask => {
+                                            if !failure_requested {
+                                                failure_requested = true;
+                                                fired = true;
+                                                request_failure(&terminate_tx, &trigger.pattern, &src).await;
+                                            }
+                                        }
+                                        TriggerAction::Complete => {
+                                            if !completion_requested {
+                                                completion_requested = true;
+                                                fired = true;
+                                                request_completion(&terminate_tx, &trigger.pattern).await;
+                                            }
+                                        }
+                                        TriggerAction::EmitEvent(name) => {
+                                            let Some(line) = triggered_line.as_ref().filter(|_| !detached) else {
+                                                continue;
+                                            };
+                                            let event = TaskEvent::Triggered {
+                                                task_name: task_name.clone(),
+                                                name: name.clone(),
+                                                line: line.clone(),
+                                                src: src.clone(),
+                                            };
+                                            match send_event(&event_tx, event, event_send_timeout, &mut drop_mode, &send_stats).await {
+                                                SendOutcome::Sent => {}
+                                                SendOutcome::Dropped => {
+                                                    dropped_events.fetch_add(1, Ordering::Relaxed);
+                                                }
+                                                SendOutcome::Closed => {
+                                                    #[cfg(feature = "tracing")]
+                                                    tracing::warn!("Event channel closed while sending TaskEvent::Triggered");
+                                                    if !detach_on_close {
+                                                        break 'read;
+                                                    }
+                                                    detached = true;
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                                // Fired one-shot triggers are not matched against later lines
+                                if fired {
+                                    disarm_triggers(&mut triggers, ready_found, failure_requested, completion_requested);
+                                }
+
+                                if let Some(every) = yield_every {
+                                    lines_since_yield += 1;
+                                    if lines_since_yield >= every {
+                                        lines_since_yield = 0;
+                                        tokio::task::yield_now().await;
                                     }
                                 }
                             }
                             Ok(None) => {
                                 // EOF
+                                output_bytes.fetch_add(lines.take_discarded() as u64, Ordering::Relaxed);
                                 break;
                             }
                             #[allow(clippy::used_underscore_binding)]
                             Err(_e) => {
-                                    #[cfg(feature = "tracing")]
-                                    tracing::warn!(error=%_e, "Error reading line from output stream");
+                                #[cfg(feature = "tracing")]
+                                tracing::warn!(error=%_e, "Error reading line from output stream");
                                 break;
                             }
                         }
                     }
-                    _ = handle_terminator_rx.changed() => {
+                    changed = handle_terminator_rx.changed(), if drain_deadline.is_none() => {
                         #[cfg(feature = "tracing")]
                         tracing::trace!("Task handle termination signal received");
-                        if *handle_terminator_rx.borrow() {
-                                #[cfg(feature = "tracing")]
-                                tracing::debug!("Termination signal received, closing output watcher");
-                            break;
+                        if changed.is_err() || *handle_terminator_rx.borrow() {
+                            // Keep reading until EOF so the last lines of a fast-exiting
+                            // process are delivered before `Stopped`, but do not wait
+                            // forever on a pipe held open by a grandchild
+                            #[cfg(feature = "tracing")]
+                            tracing::debug!("Termination signal received, draining output watcher");
+                            drain_deadline = Some(Instant::now() + OUTPUT_DRAIN_TIMEOUT);
+                            // The task is stopping, failing or completing it would do nothing
+                            failure_requested = true;
+                            completion_requested = true;
+                            disarm_triggers(&mut triggers, ready_found, failure_requested, completion_requested);
+                        }
+                    }
+                    () = sleep_until(summary_deadline.unwrap_or_else(Instant::now)), if summary_deadline.is_some() => {
+                        // A run still going is summarized once per window
+                        if let Some(summary) = deduper.as_mut().and_then(LineDeduper::take_summary) {
+                            let event = repeated_event(&task_name, summary, &src);
+                            if !send_summary(&event_tx, event, event_send_timeout, &mut drop_mode, &send_stats, &dropped_events).await {
+                                if !detach_on_close {
+                                    break;
+                                }
+                                detached = true;
+                            }
                         }
                     }
+                    () = sleep_until(throttle_deadline.unwrap_or_else(Instant::now)), if throttle_deadline.is_some() => {
+                        if let Some(summary) = throttle.as_mut().and_then(OutputThrottle::take_summary) {
+                            let event = suppressed_event(&task_name, summary, &src);
+                            if !send_summary(&event_tx, event, event_send_timeout, &mut drop_mode, &send_stats, &dropped_events).await {
+                                if !detach_on_close {
+                                    break;
+                                }
+                                detached = true;
+                            }
+                        }
+                    }
+                    () = sleep_until(drain_deadline.unwrap_or_else(Instant::now)), if drain_deadline.is_some() => {
+                        #[cfg(feature = "tracing")]
+                        tracing::debug!("Output drain timeout elapsed, closing output watcher");
+                        break;
+                    }
                 }
             }
-                #[cfg(feature = "tracing")]
-                tracing::debug!("Watcher finished");
+            // Repeats collapsed before the stream ended
+            if let Some(summary) = deduper.as_mut().and_then(LineDeduper::take_summary) {
+                let event = repeated_event(&task_name, summary, &src);
+                send_summary(&event_tx, event, event_send_timeout, &mut drop_mode, &send_stats, &dropped_events).await;
+            }
+            // Lines suppressed in the last window
+            if let Some(summary) = throttle.as_mut().and_then(OutputThrottle::take_summary) {
+                let event = suppressed_event(&task_name, summary, &src);
+                send_summary(&event_tx, event, event_send_timeout, &mut drop_mode, &send_stats, &dropped_events).await;
+            }
+            #[cfg(feature = "tracing")]
+            tracing::debug!("Watcher finished");
         }
         .maybe_instrument("spawn"),
-    );
-    #[cfg(feature = "tracing")]
-    tracing::debug!(
-        handle_id = %handle.id(),
-        "Spawned std output watcher handle"
-    );
-    handle
+    )
 }
 #[cfg(test)]
 mod tests {
@@ -220,8 +1006,8 @@ mod tests {
         let cursor = std::io::Cursor::new(&data[..]);
         let (tx, mut rx) = mpsc::channel::<TaskEvent>(10);
         let (_term_tx, term_rx) = watch::channel(false);
-        let ready_indicator = Some("READY_INDICATOR".to_string());
-        let task_name = "test_task_mismatch".to_string();
+        let ready_indicator = "READY_INDICATOR";
+        let task_name: Arc<str> = "test_task_mismatch".into();
         let state = Arc::new(RwLock::new(TaskState::Running));
 
         // src is Stdout, ready_indicator_source is Stderr (should NOT emit Ready)
@@ -231,8 +1017,22 @@ mod tests {
             event_tx: tx,
             src: StreamSource::Stdout,
             handle_terminator_rx: term_rx,
-            ready_indicator: ready_indicator.clone(),
-            ready_indicator_source: StreamSource::Stderr,
+            config: Arc::new(
+                TaskConfig::new("test")
+                    .ready_indicator(ready_indicator)
+                    .ready_indicator_source(StreamSource::Stderr),
+            ),
+            dropped_events: Arc::new(AtomicU64::new(0)),
+            send_stats: Arc::new(EventSendStats::new("test".into(), None)),
+            ready_flag: Arc::new(AtomicBool::new(false)),
+            output_transform: None,
+            startup_output: Arc::default(),
+            output_tail: Arc::default(),
+            terminate_tx: Arc::default(),
+            output_seq: Arc::default(),
+            output_bytes: Arc::default(),
+            on_channel_closed: ChannelClosedPolicy::default(),
+            script: None,
         };
         let handle = spawn_std_watcher(cursor, config);
 
@@ -243,8 +1043,8 @@ mod tests {
             }
         }
         handle.await.unwrap();
-        assert_eq!(
-            ready_event, false,
+        assert!(
+            !ready_event,
             "Should NOT emit Ready event if ready_indicator_source does not match src"
         );
         let state_val = state.read().await.clone();
@@ -256,19 +1056,27 @@ mod tests {
     }
 
     use super::*;
-    use crate::tasks::config::StreamSource;
+    use crate::tasks::config::{StreamSource, TaskConfig};
     use crate::tasks::event::TaskEvent;
     use std::io::Cursor;
+    use tokio::io::AsyncWriteExt;
     use tokio::sync::{mpsc, watch};
 
+    fn spawn_std_watcher<T>(std: T, config: OutputWatcherConfig) -> tokio::task::JoinHandle<()>
+    where
+        T: tokio::io::AsyncRead + Unpin + Send + 'static,
+    {
+        tokio::spawn(std_watcher(std, config))
+    }
+
     #[tokio::test]
     async fn emits_ready_and_output_events() {
         let data = b"first line\nREADY_INDICATOR\nlast line\n";
         let cursor = Cursor::new(&data[..]);
         let (tx, mut rx) = mpsc::channel::<TaskEvent>(10);
         let (_term_tx, term_rx) = watch::channel(false);
-        let ready_indicator = Some("READY_INDICATOR".to_string());
-        let task_name = "test_task".to_string();
+        let ready_indicator = "READY_INDICATOR";
+        let task_name: Arc<str> = "test_task".into();
         let state = Arc::new(RwLock::new(TaskState::Running));
 
         let config = OutputWatcherConfig {
@@ -277,8 +1085,22 @@ mod tests {
             event_tx: tx,
             src: StreamSource::Stdout,
             handle_terminator_rx: term_rx,
-            ready_indicator: ready_indicator.clone(),
-            ready_indicator_source: StreamSource::Stdout,
+            config: Arc::new(
+                TaskConfig::new("test")
+                    .ready_indicator(ready_indicator)
+                    .ready_indicator_source(StreamSource::Stdout),
+            ),
+            dropped_events: Arc::new(AtomicU64::new(0)),
+            send_stats: Arc::new(EventSendStats::new("test".into(), None)),
+            ready_flag: Arc::new(AtomicBool::new(false)),
+            output_transform: None,
+            startup_output: Arc::default(),
+            output_tail: Arc::default(),
+            terminate_tx: Arc::default(),
+            output_seq: Arc::default(),
+            output_bytes: Arc::default(),
+            on_channel_closed: ChannelClosedPolicy::default(),
+            script: None,
         };
         let handle = spawn_std_watcher(cursor, config);
 
@@ -290,6 +1112,7 @@ mod tests {
                     task_name: tn,
                     line,
                     src,
+                    ..
                 } => {
                     assert_eq!(tn, task_name);
                     assert_eq!(src, StreamSource::Stdout);
@@ -318,4 +1141,368 @@ mod tests {
             "State should be set to Ready when ready indicator is found"
         );
     }
+
+    #[tokio::test]
+    async fn drops_and_counts_events_when_consumer_stalls() {
+        let data = "line\n".repeat(100);
+        let cursor = Cursor::new(data.into_bytes());
+        // Receiver is kept alive but never polled
+        let (tx, mut rx) = mpsc::channel::<TaskEvent>(1);
+        let (_term_tx, term_rx) = watch::channel(false);
+        let dropped_events = Arc::new(AtomicU64::new(0));
+
+        let config = OutputWatcherConfig {
+            task_name: "stalled_consumer".into(),
+            state: Arc::new(RwLock::new(TaskState::Running)),
+            event_tx: tx,
+            src: StreamSource::Stdout,
+            handle_terminator_rx: term_rx,
+            config: Arc::new(TaskConfig::new("test").event_send_timeout_ms(10)),
+            dropped_events: dropped_events.clone(),
+            send_stats: Arc::new(EventSendStats::new("test".into(), None)),
+            ready_flag: Arc::new(AtomicBool::new(false)),
+            output_transform: None,
+            startup_output: Arc::default(),
+            output_tail: Arc::default(),
+            terminate_tx: Arc::default(),
+            output_seq: Arc::default(),
+            output_bytes: Arc::default(),
+            on_channel_closed: ChannelClosedPolicy::default(),
+            script: None,
+        };
+        let handle = spawn_std_watcher(cursor, config);
+
+        tokio::time::timeout(Duration::from_secs(5), handle)
+            .await
+            .expect("Watcher should not block on a stalled consumer")
+            .unwrap();
+
+        // One event fits in the channel, the rest are dropped
+        assert_eq!(dropped_events.load(Ordering::Relaxed), 99);
+        assert!(matches!(rx.recv().await, Some(TaskEvent::Output { .. })));
+        assert!(rx.recv().await.is_none());
+    }
+
+    async fn collect_output(data: Vec<u8>, ready_indicator: Option<&str>) -> (Vec<String>, bool) {
+        let (tx, mut rx) = mpsc::channel::<TaskEvent>(10);
+        let (_term_tx, term_rx) = watch::channel(false);
+        let config = OutputWatcherConfig {
+            task_name: "test_task".into(),
+            state: Arc::new(RwLock::new(TaskState::Running)),
+            event_tx: tx,
+            src: StreamSource::Stdout,
+            handle_terminator_rx: term_rx,
+            config: Arc::new(TaskConfig {
+                ready_indicator: ready_indicator.map(str::to_string),
+                ..TaskConfig::new("test")
+            }),
+            dropped_events: Arc::new(AtomicU64::new(0)),
+            send_stats: Arc::new(EventSendStats::new("test".into(), None)),
+            ready_flag: Arc::new(AtomicBool::new(false)),
+            output_transform: None,
+            startup_output: Arc::default(),
+            output_tail: Arc::default(),
+            terminate_tx: Arc::default(),
+            output_seq: Arc::default(),
+            output_bytes: Arc::default(),
+            on_channel_closed: ChannelClosedPolicy::default(),
+            script: None,
+        };
+        let handle = spawn_std_watcher(Cursor::new(data), config);
+
+        let mut lines = vec![];
+        let mut ready = false;
+        while let Some(event) = rx.recv().await {
+            match event {
+                TaskEvent::Output { line, .. } => lines.push(line),
+                TaskEvent::Ready { .. } => ready = true,
+                _ => {}
+            }
+        }
+        handle.await.unwrap();
+        (lines, ready)
+    }
+
+    #[tokio::test]
+    async fn long_lines_are_emitted_whole() {
+        let long = "x".repeat(1024 * 1024);
+        let data = format!("short\n{long}\nafter\n").into_bytes();
+
+        let (lines, _) = collect_output(data, None).await;
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "short");
+        assert_eq!(lines[1], long);
+        assert_eq!(lines[2], "after");
+    }
+
+    #[tokio::test]
+    async fn ready_indicator_at_end_of_long_line_is_found() {
+        let data = format!("{}READY\n", "x".repeat(64 * 1024)).into_bytes();
+
+        let (lines, ready) = collect_output(data, Some("READY")).await;
+        assert_eq!(lines.len(), 1);
+        assert!(ready);
+    }
+
+    #[tokio::test]
+    async fn partial_last_line_and_crlf_are_kept_as_before() {
+        let data = b"first\r\nsecond\n\npartial".to_vec();
+
+        let (lines, _) = collect_output(data, None).await;
+        assert_eq!(lines, ["first", "second", "", "partial"]);
+    }
+
+    #[tokio::test]
+    async fn ready_indicator_in_partial_last_line_is_found() {
+        let data = b"starting\nREADY".to_vec();
+
+        let (lines, ready) = collect_output(data, Some("READY")).await;
+        assert_eq!(lines, ["starting", "READY"]);
+        assert!(ready);
+    }
+
+    #[tokio::test]
+    async fn full_channel_falls_back_without_losing_events() {
+        let data = "line\n".repeat(200);
+        let (tx, mut rx) = mpsc::channel::<TaskEvent>(1);
+        let (_term_tx, term_rx) = watch::channel(false);
+        let send_stats = Arc::new(EventSendStats::new("test".into(), None));
+        let dropped_events = Arc::new(AtomicU64::new(0));
+
+        let config = OutputWatcherConfig {
+            task_name: "slow_consumer".into(),
+            state: Arc::new(RwLock::new(TaskState::Running)),
+            event_tx: tx,
+            src: StreamSource::Stdout,
+            handle_terminator_rx: term_rx,
+            config: Arc::new(TaskConfig::new("test")),
+            dropped_events: dropped_events.clone(),
+            send_stats: send_stats.clone(),
+            ready_flag: Arc::new(AtomicBool::new(false)),
+            output_transform: None,
+            startup_output: Arc::default(),
+            output_tail: Arc::default(),
+            terminate_tx: Arc::default(),
+            output_seq: Arc::default(),
+            output_bytes: Arc::default(),
+            on_channel_closed: ChannelClosedPolicy::default(),
+            script: None,
+        };
+        let handle = spawn_std_watcher(Cursor::new(data.into_bytes()), config);
+
+        let mut lines = 0;
+        while let Some(event) = rx.recv().await {
+            if matches!(event, TaskEvent::Output { .. }) {
+                lines += 1;
+            }
+            tokio::task::yield_now().await;
+        }
+        handle.await.unwrap();
+
+        assert_eq!(lines, 200);
+        assert!(send_stats.slow_sends() > 0);
+        assert_eq!(dropped_events.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn roomy_channel_takes_fast_path() {
+        let data = "line\n".repeat(200);
+        let (tx, mut rx) = mpsc::channel::<TaskEvent>(1024);
+        let (_term_tx, term_rx) = watch::channel(false);
+        let send_stats = Arc::new(EventSendStats::new("test".into(), None));
+
+        let config = OutputWatcherConfig {
+            task_name: "fast_consumer".into(),
+            state: Arc::new(RwLock::new(TaskState::Running)),
+            event_tx: tx,
+            src: StreamSource::Stdout,
+            handle_terminator_rx: term_rx,
+            config: Arc::new(TaskConfig::new("test")),
+            dropped_events: Arc::new(AtomicU64::new(0)),
+            send_stats: send_stats.clone(),
+            ready_flag: Arc::new(AtomicBool::new(false)),
+            output_transform: None,
+            startup_output: Arc::default(),
+            output_tail: Arc::default(),
+            terminate_tx: Arc::default(),
+            output_seq: Arc::default(),
+            output_bytes: Arc::default(),
+            on_channel_closed: ChannelClosedPolicy::default(),
+            script: None,
+        };
+        spawn_std_watcher(Cursor::new(data.into_bytes()), config)
+            .await
+            .unwrap();
+
+        let mut lines = 0;
+        while let Some(event) = rx.recv().await {
+            if matches!(event, TaskEvent::Output { .. }) {
+                lines += 1;
+            }
+        }
+        assert_eq!(lines, 200);
+        assert_eq!(send_stats.slow_sends(), 0);
+    }
+
+    fn dedupe_config(
+        event_tx: mpsc::Sender<TaskEvent>,
+        handle_terminator_rx: watch::Receiver<bool>,
+        config: TaskConfig,
+    ) -> OutputWatcherConfig {
+        OutputWatcherConfig {
+            task_name: "dedupe".into(),
+            state: Arc::new(RwLock::new(TaskState::Running)),
+            event_tx,
+            src: StreamSource::Stdout,
+            handle_terminator_rx,
+            config: Arc::new(config),
+            dropped_events: Arc::new(AtomicU64::new(0)),
+            send_stats: Arc::new(EventSendStats::new("test".into(), None)),
+            ready_flag: Arc::new(AtomicBool::new(false)),
+            output_transform: None,
+            startup_output: Arc::default(),
+            output_tail: Arc::default(),
+            terminate_tx: Arc::default(),
+            output_seq: Arc::default(),
+            output_bytes: Arc::default(),
+            on_channel_closed: ChannelClosedPolicy::default(),
+            script: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn repeated_lines_are_collapsed_and_ready_still_found() {
+        let mut data = "starting\n".to_string();
+        data.push_str(&"retrying\n".repeat(500));
+        data.push_str("READY\n");
+        data.push_str(&"READY\n".repeat(9));
+        let (tx, mut rx) = mpsc::channel::<TaskEvent>(1024);
+        let (_term_tx, term_rx) = watch::channel(false);
+        let config = TaskConfig::new("test")
+            .ready_indicator("READY")
+            .dedupe_output(60_000, 2);
+
+        spawn_std_watcher(
+            Cursor::new(data.into_bytes()),
+            dedupe_config(tx, term_rx, config),
+        )
+        .await
+        .unwrap();
+
+        let mut events = vec![];
+        while let Some(event) = rx.recv().await {
+            events.push(match event {
+                TaskEvent::Output { line, .. } => format!("output {line}"),
+                TaskEvent::OutputRepeated { line, count, .. } => format!("repeated {line} {count}"),
+                TaskEvent::Ready { .. } => "ready".to_string(),
+                other => panic!("Unexpected event {other:?}"),
+            });
+        }
+        assert_eq!(
+            events,
+            [
+                "output starting",
+                "output retrying",
+                "output retrying",
+                "repeated retrying 498",
+                "output READY",
+                "ready",
+                "output READY",
+                "repeated READY 8",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn repeats_are_summarized_when_window_expires() {
+        let (mut writer, reader) = tokio::io::duplex(1024);
+        let (tx, mut rx) = mpsc::channel::<TaskEvent>(16);
+        let (_term_tx, term_rx) = watch::channel(false);
+        let handle = spawn_std_watcher(
+            reader,
+            dedupe_config(tx, term_rx, TaskConfig::new("test").dedupe_output(50, 1)),
+        );
+
+        // The writer stays open, so only the window can flush the summary
+        writer.write_all(b"loop\nloop\nloop\n").await.unwrap();
+        let events = tokio::time::timeout(Duration::from_secs(5), async {
+            let mut events = vec![];
+            while events.len() < 2 {
+                events.push(rx.recv().await.unwrap());
+            }
+            events
+        })
+        .await
+        .expect("Summary should be sent once the window expires");
+        assert!(matches!(&events[0], TaskEvent::Output { line, .. } if line == "loop"));
+        assert!(matches!(
+            &events[1],
+            TaskEvent::OutputRepeated { line, count: 2, .. } if line == "loop"
+        ));
+
+        drop(writer);
+        handle.await.unwrap();
+        assert!(rx.recv().await.is_none());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_lines_are_parsed_and_others_kept_as_text() {
+        let task_name: Arc<str> = "json".into();
+        let event = |line: &str| {
+            output_event(
+                &task_name,
+                Cow::Borrowed(line),
+                &StreamSource::Stdout,
+                0,
+                false,
+                SystemTime::UNIX_EPOCH,
+                true,
+            )
+        };
+
+        assert!(matches!(
+            event(r#"{"reason":"compiler-artifact","fresh":true}"#),
+            TaskEvent::OutputJson { value, timestamp, .. }
+                if value["fresh"] == true && timestamp == SystemTime::UNIX_EPOCH
+        ));
+        assert!(matches!(event("[1, 2, 3]"), TaskEvent::OutputJson { .. }));
+        for line in ["Compiling foo v0.1.0", "42", "true", "{not json", ""] {
+            assert!(
+                matches!(event(line), TaskEvent::Output { line: ref l, .. } if l == line),
+                "{line:?} should stay text"
+            );
+        }
+
+        let too_long = format!(r#"{{"pad":"{}"}}"#, "x".repeat(MAX_JSON_LINE_BYTES));
+        assert!(matches!(event(&too_long), TaskEvent::Output { .. }));
+
+        let disabled = output_event(
+            &task_name,
+            Cow::Borrowed("{}"),
+            &StreamSource::Stdout,
+            0,
+            false,
+            SystemTime::UNIX_EPOCH,
+            false,
+        );
+        assert!(matches!(disabled, TaskEvent::Output { .. }));
+
+        let truncated = output_event(
+            &task_name,
+            Cow::Borrowed("[1, 2]"),
+            &StreamSource::Stdout,
+            0,
+            true,
+            SystemTime::UNIX_EPOCH,
+            true,
+        );
+        assert!(matches!(
+            truncated,
+            TaskEvent::Output {
+                truncated: true,
+                ..
+            }
+        ));
+    }
 }
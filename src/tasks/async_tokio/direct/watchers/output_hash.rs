@@ -0,0 +1,149 @@
+use std::sync::{Arc, Mutex, PoisonError};
+#[cfg(feature = "sha2")]
+use std::{
+    io,
+    pin::Pin,
+    task::{Context, Poll, ready},
+};
+
+#[cfg(feature = "sha2")]
+use sha2::{Digest, Sha256};
+#[cfg(feature = "sha2")]
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::tasks::config::StreamSource;
+
+/// SHA-256 digests of the output streams, as lowercase hex
+///
+/// Each digest is set once the watcher of its stream has finished, before
+/// `TaskEvent::Stopped` is built.
+#[derive(Debug, Default)]
+pub(crate) struct OutputDigests {
+    stdout: Mutex<Option<String>>,
+    stderr: Mutex<Option<String>>,
+}
+
+/// Digests shared between the output watchers and the result reporter
+pub(crate) type SharedOutputDigests = Arc<OutputDigests>;
+
+impl OutputDigests {
+    fn slot(&self, src: &StreamSource) -> &Mutex<Option<String>> {
+        match src {
+            StreamSource::Stdout => &self.stdout,
+            StreamSource::Stderr => &self.stderr,
+        }
+    }
+
+    /// Digest of `src`, `None` if the stream was not hashed
+    pub(crate) fn get(&self, src: &StreamSource) -> Option<String> {
+        self.slot(src)
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    #[cfg(feature = "sha2")]
+    fn set(&self, src: &StreamSource, digest: String) {
+        *self
+            .slot(src)
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(digest);
+    }
+}
+
+/// Hashes the bytes read from an output stream with SHA-256
+///
+/// Passes the bytes through unchanged. The digest covers every byte read
+/// until the reader is dropped, and is stored in the shared digests then, so
+/// it is set whether the watcher read to EOF or stopped early.
+#[cfg(feature = "sha2")]
+#[derive(Debug)]
+pub(crate) struct HashingReader<R> {
+    reader: R,
+    hasher: Sha256,
+    src: StreamSource,
+    digests: SharedOutputDigests,
+}
+
+#[cfg(feature = "sha2")]
+impl<R> HashingReader<R> {
+    /// Creates a reader hashing `reader`, storing the digest of `src` in `digests`.
+    pub(crate) fn new(reader: R, src: StreamSource, digests: SharedOutputDigests) -> Self {
+        Self {
+            reader,
+            hasher: Sha256::new(),
+            src,
+            digests,
+        }
+    }
+}
+
+#[cfg(feature = "sha2")]
+impl<R> AsyncRead for HashingReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        let start = buf.filled().len();
+        ready!(Pin::new(&mut this.reader).poll_read(cx, buf))?;
+        this.hasher.update(&buf.filled()[start..]);
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(feature = "sha2")]
+impl<R> Drop for HashingReader<R> {
+    fn drop(&mut self) {
+        let digest = std::mem::take(&mut self.hasher).finalize();
+        self.digests.set(&self.src, format!("{digest:x}"));
+    }
+}
+
+#[cfg(all(test, feature = "sha2"))]
+mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn digest_covers_every_byte_read() {
+        let (mut tx, rx) = tokio::io::duplex(3);
+        let writer = tokio::spawn(async move {
+            tx.write_all(b"hello\r\nworld").await.unwrap();
+        });
+
+        let digests = SharedOutputDigests::default();
+        let mut reader = HashingReader::new(rx, StreamSource::Stderr, digests.clone());
+        let mut read = vec![];
+        reader.read_to_end(&mut read).await.unwrap();
+        writer.await.unwrap();
+        assert_eq!(digests.get(&StreamSource::Stderr), None);
+
+        drop(reader);
+        assert_eq!(read, b"hello\r\nworld");
+        assert_eq!(
+            digests.get(&StreamSource::Stderr),
+            Some(format!("{:x}", Sha256::digest(b"hello\r\nworld")))
+        );
+        assert_eq!(digests.get(&StreamSource::Stdout), None);
+    }
+
+    #[test]
+    fn empty_stream_has_digest_of_nothing() {
+        let digests = SharedOutputDigests::default();
+        drop(HashingReader::new(
+            tokio::io::empty(),
+            StreamSource::Stdout,
+            digests.clone(),
+        ));
+        assert_eq!(
+            digests.get(&StreamSource::Stdout).as_deref(),
+            Some("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+        );
+    }
+}
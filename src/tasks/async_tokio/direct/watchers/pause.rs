@@ -0,0 +1,133 @@
+use std::time::Duration;
+
+use tokio::{sync::watch, time::Instant};
+
+/// Pause periods of a task, as recorded by its clock
+#[derive(Debug, Clone, Copy, Default)]
+struct PauseState {
+    /// When the current pause began, `None` while the task runs
+    since: Option<Instant>,
+    /// Total duration of the pauses that ended
+    total: Duration,
+}
+
+/// Time a task spent paused, shared by `TaskSpawner` and the wait watcher
+///
+/// Pausing a paused task, or resuming a running one, is ignored, so a pause
+/// may be reported both by `TaskSpawner::pause_process` and by job control
+/// detection without being counted twice.
+#[derive(Debug)]
+pub(crate) struct PauseClock {
+    state: watch::Sender<PauseState>,
+}
+
+impl Default for PauseClock {
+    fn default() -> Self {
+        Self {
+            state: watch::Sender::new(PauseState::default()),
+        }
+    }
+}
+
+impl PauseClock {
+    /// Records that the task was paused
+    pub(crate) fn pause(&self) {
+        self.state.send_if_modified(|state| {
+            if state.since.is_some() {
+                return false;
+            }
+            state.since = Some(Instant::now());
+            true
+        });
+    }
+
+    /// Records that the task was resumed
+    pub(crate) fn resume(&self) {
+        self.state.send_if_modified(|state| {
+            let Some(since) = state.since.take() else {
+                return false;
+            };
+            state.total += since.elapsed();
+            true
+        });
+    }
+
+    /// Resolves once the task has run for `duration`, not counting the time it was paused
+    ///
+    /// Only pauses from the first poll on are left out. Never resolves while
+    /// the task is paused.
+    pub(crate) async fn sleep_unpaused(&self, duration: Duration) {
+        let started = Instant::now();
+        let mut rx = self.state.subscribe();
+        let paused_before = rx.borrow().total;
+        loop {
+            let state = *rx.borrow_and_update();
+            // The sender outlives `self`, so `changed` cannot fail
+            if state.since.is_some() {
+                let _ = rx.changed().await;
+                continue;
+            }
+            let deadline = started + duration + (state.total - paused_before);
+            tokio::select! {
+                () = tokio::time::sleep_until(deadline) => return,
+                _ = rx.changed() => {}
+            }
+        }
+    }
+}
+
+/// Records stops and continues of the child process in `clock`, never resolving
+///
+/// Catches job control signals sent from outside, such as `kill -STOP` or a
+/// terminal's Ctrl+Z, by polling `waitid` with `WNOHANG`. Without `WEXITED`,
+/// this never reaps the child, so the wait watcher still sees it exit.
+///
+/// # Arguments
+///
+/// * `pid` - Process ID of the child, if it is still known.
+/// * `clock` - The clock pauses are recorded in.
+#[cfg(unix)]
+pub(crate) async fn watch_job_control(pid: Option<u32>, clock: &PauseClock) {
+    /// Interval between checks of the child's job control state
+    const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+    let Some(pid) = pid.and_then(|pid| libc::id_t::try_from(pid).ok()) else {
+        return std::future::pending().await;
+    };
+    let mut ticker = tokio::time::interval(POLL_INTERVAL);
+    loop {
+        ticker.tick().await;
+        // SAFETY: `info` is a valid, zeroed `siginfo_t` for `waitid` to fill
+        let (result, code) = unsafe {
+            let mut info: libc::siginfo_t = std::mem::zeroed();
+            let result = libc::waitid(
+                libc::P_PID,
+                pid,
+                &raw mut info,
+                libc::WSTOPPED | libc::WCONTINUED | libc::WNOHANG,
+            );
+            (result, info.si_code)
+        };
+        if result == -1 {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                error = %std::io::Error::last_os_error(),
+                "Cannot watch the child for job control stops"
+            );
+            return std::future::pending().await;
+        }
+        match code {
+            libc::CLD_STOPPED => clock.pause(),
+            libc::CLD_CONTINUED => clock.resume(),
+            _ => {}
+        }
+    }
+}
+
+/// Records stops and continues of the child process in `clock`, never resolving
+///
+/// Job control only exists on Unix, so this only waits.
+#[cfg(not(unix))]
+pub(crate) async fn watch_job_control(_pid: Option<u32>, _clock: &PauseClock) {
+    std::future::pending().await
+}
@@ -1,92 +1,207 @@
-use std::sync::Arc;
+use std::{
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    time::{Duration, SystemTime},
+};
 
 use tokio::{
-    sync::{RwLock, mpsc, oneshot},
-    task::JoinHandle,
+    sync::{RwLock, mpsc},
     time::Instant,
 };
 
-use crate::{
-    helper::tracing::MaybeInstrument,
-    tasks::{
-        async_tokio::spawner::join_all_handles,
-        event::{TaskEvent, TaskEventStopReason},
-        state::TaskState,
+use crate::tasks::{
+    async_tokio::{
+        direct::watchers::output_hash::SharedOutputDigests,
+        spawner::{SharedAuditRecord, SharedTerminateRequests, set_state},
+        temp_dir,
+        watcher_set::WatcherSet,
     },
+    audit::AuditRecord,
+    config::{SharedTaskConfig, StreamSource},
+    event::{TaskEvent, TaskEventStopReason, TaskTerminateReason},
+    state::TaskState,
 };
 
-/// Spawns a watcher that waits for the task result and updates state
-///
-/// Joins all watcher handles and sends a `TaskEvent::Stopped` event.
-///
-/// # Arguments
-///
-/// * `task_name` - Name of the task.
-/// * `state` - Shared state of the task.
-/// * `finished_arc` - Shared reference to the task's finished time.
-/// * `event_tx` - Sender for task events.
-/// * `result_rx` - Receiver for the process exit code and stop reason.
-/// * `task_handles` - Vector of watcher task handles to join.
-///
-/// # Returns
+/// What was spawned, recorded in the audit record of the run
+#[derive(Debug, Clone)]
+pub(crate) struct SpawnedCommand {
+    /// Absolute path of the program, or the command as configured if unresolved.
+    pub(crate) program: String,
+    /// Arguments passed to the program, already redacted.
+    pub(crate) args: Vec<String>,
+    /// Canonical working directory of the process.
+    pub(crate) working_dir: String,
+    /// Names of the environment variables set for the process, sorted.
+    pub(crate) env_keys: Vec<String>,
+    /// When the process was spawned.
+    pub(crate) started_at: SystemTime,
+}
+
+/// Reports the result of a task once its process has stopped
 ///
-/// A `JoinHandle` for the spawned result watcher task.
-#[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
-pub(crate) fn spawn_result_watcher(
-    task_name: String,
-    state: Arc<RwLock<TaskState>>,
-    finished_arc: Arc<RwLock<Option<Instant>>>,
-    event_tx: mpsc::Sender<TaskEvent>,
-    result_rx: oneshot::Receiver<(Option<i32>, TaskEventStopReason)>,
-    mut task_handles: Vec<JoinHandle<()>>,
-) -> JoinHandle<()> {
-    let handle = tokio::spawn(
-        async move {
-            let (exit_code, stop_reason) = if let Ok(result) = result_rx.await {
-                result
-            } else {
-                // Somehow, all tx has been dropped, this is unexpected
-                let msg = "All result senders dropped unexpectedly";
-                #[cfg(feature = "tracing")]
-                tracing::warn!(msg);
-                (None, TaskEventStopReason::Error(msg.to_string()))
+/// Owned by the wait watcher, which calls [`ResultReporter::report`] after the
+/// process exited or was terminated.
+#[derive(Debug)]
+pub(crate) struct ResultReporter {
+    /// Name of the task.
+    pub(crate) task_name: Arc<str>,
+    /// Shared state of the task.
+    pub(crate) state: Arc<RwLock<TaskState>>,
+    /// Shared reference to the task's finished time.
+    pub(crate) finished_at: Arc<RwLock<Option<Instant>>>,
+    /// Sender for task events.
+    pub(crate) event_tx: mpsc::Sender<TaskEvent>,
+    /// Output and stdin watchers to join, aborted if the reporter is dropped.
+    pub(crate) task_handles: WatcherSet,
+    /// Maximum total time to wait for the watcher handles.
+    pub(crate) join_timeout: Duration,
+    /// Shared process ID, cleared once `Stopped` is sent.
+    pub(crate) process_id: Arc<RwLock<Option<u32>>>,
+    /// Whether the task reached Ready, reported in `Stopped`.
+    pub(crate) ready_flag: Arc<AtomicBool>,
+    /// When the process was spawned, used for the duration reported in `Stopped`.
+    pub(crate) running_at: Instant,
+    /// Task configuration, for the expected runtime and exit code meanings reported in `Stopped`.
+    pub(crate) config: SharedTaskConfig,
+    /// Shared meaning of the exit code, set before `Stopped` is sent.
+    pub(crate) exit_meaning: Arc<RwLock<Option<String>>>,
+    /// Set before `Stopped` is sent if the task failed without reaching Ready.
+    pub(crate) failed_before_ready: Arc<AtomicBool>,
+    /// Temporary working directory, removed after `Stopped` is sent.
+    pub(crate) temp_dir: Option<PathBuf>,
+    /// What was spawned, for the audit record.
+    pub(crate) spawned: Option<SpawnedCommand>,
+    /// Signal that killed the process, reported in `Stopped` and the audit record.
+    pub(crate) signal: Option<i32>,
+    /// Shared counter of the bytes read from stdout and stderr.
+    pub(crate) output_bytes: Arc<AtomicU64>,
+    /// Shared digests of stdout and stderr, reported in `Stopped`.
+    pub(crate) output_digests: SharedOutputDigests,
+    /// Shared list of the termination requests received.
+    pub(crate) terminate_requests: SharedTerminateRequests,
+    /// Shared audit record of the run, set before `Stopped` is sent.
+    pub(crate) audit: SharedAuditRecord,
+}
+
+impl ResultReporter {
+    /// Joins all watcher handles, sends `TaskEvent::Stopped` and marks the task finished
+    ///
+    /// The audit record is stored before `Stopped` is sent. The process ID is
+    /// cleared only after `Stopped` has been sent, so it stays
+    /// queryable while any earlier event is still being handled. The temporary
+    /// working directory, if any, is removed after `Stopped` as well, unless
+    /// the task failed and it is configured to be kept.
+    pub(crate) async fn report(mut self, exit_code: Option<i32>, stop_reason: TaskEventStopReason) {
+        // Measured before joining the watchers, which may wait for output to drain
+        let duration_ms = u64::try_from(self.running_at.elapsed().as_millis()).unwrap_or(u64::MAX);
+        let stopped_at = SystemTime::now();
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            exit_code = ?exit_code,
+            stop_reason = ?stop_reason,
+            "Task stopped"
+        );
+        #[allow(clippy::used_underscore_binding)]
+        if let Err(_e) = self.task_handles.join_all(self.join_timeout).await {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(error = %_e, "One or more task handles failed to join cleanly");
+        }
+
+        let exit_meaning = exit_code
+            .and_then(|code| self.config.exit_code_meaning(code))
+            .map(str::to_string);
+        self.exit_meaning.write().await.clone_from(&exit_meaning);
+        let was_ready = self.ready_flag.load(Ordering::Relaxed);
+        let failed = is_failure(exit_code, &stop_reason);
+        if !was_ready && failed {
+            self.failed_before_ready.store(true, Ordering::Relaxed);
+        }
+        if let Some(spawned) = self.spawned.take() {
+            let record = AuditRecord {
+                task_name: self.task_name.to_string(),
+                command: spawned.program,
+                args: spawned.args,
+                working_dir: spawned.working_dir,
+                env_keys: spawned.env_keys,
+                process_id: *self.process_id.read().await,
+                started_at: spawned.started_at,
+                stopped_at,
+                exit_code,
+                signal: self.signal,
+                reason: stop_reason.clone(),
+                output_bytes: self.output_bytes.load(Ordering::Relaxed),
+                terminate_requests: self
+                    .terminate_requests
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner)
+                    .clone(),
             };
+            *self.audit.write().await = Some(record);
+        }
+
+        if (self
+            .event_tx
+            .send(TaskEvent::Stopped {
+                task_name: self.task_name,
+                exit_code,
+                signal: self.signal,
+                exit_meaning,
+                reason: stop_reason,
+                was_ready,
+                duration_ms,
+                expected_duration_ms: self.config.expected_duration_ms,
+                from_cache: false,
+                stdout_sha256: self.output_digests.get(&StreamSource::Stdout),
+                stderr_sha256: self.output_digests.get(&StreamSource::Stderr),
+            })
+            .await)
+            .is_err()
+        {
             #[cfg(feature = "tracing")]
-            tracing::info!(
-                exit_code = ?exit_code,
-                stop_reason = ?stop_reason,
-                "Task stopped"
-            );
-            #[allow(clippy::used_underscore_binding)]
-            if let Err(_e) = join_all_handles(&mut task_handles).await {
-                #[cfg(feature = "tracing")]
-                tracing::warn!(error = %_e, "One or more task handles failed to join cleanly");
-            }
+            tracing::warn!("Event channel closed while sending TaskEvent::Stopped");
+        }
+        self.process_id.write().await.take();
 
-            if (event_tx
-                .send(TaskEvent::Stopped {
-                    task_name: task_name.clone(),
-                    exit_code,
-                    reason: stop_reason.clone(),
-                })
-                .await)
-                .is_err()
-            {
+        if let Some(dir) = self.temp_dir.take() {
+            let keep_on_failure = self
+                .config
+                .temp_working_dir
+                .as_ref()
+                .is_some_and(|temp| temp.keep_on_failure);
+            if failed && keep_on_failure {
                 #[cfg(feature = "tracing")]
-                tracing::warn!("Event channel closed while sending TaskEvent::Stopped");
+                tracing::info!(path = %dir.display(), "Keeping temporary working directory of failed task");
+            } else {
+                #[allow(clippy::used_underscore_binding)]
+                if let Err(_e) = tokio::task::spawn_blocking(move || temp_dir::remove(&dir)).await {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(error = %_e, "Temporary working directory removal panicked");
+                }
             }
+        }
 
-            *state.write().await = TaskState::Finished;
-            *finished_arc.write().await = Some(Instant::now());
+        set_state(&self.state, TaskState::Finished).await;
+        *self.finished_at.write().await = Some(Instant::now());
+    }
+}
 
-            #[cfg(feature = "tracing")]
-            tracing::debug!("Watcher finished");
+/// Whether a task that stopped this way failed
+///
+/// A task terminated on request (rather than by its timeout) did not fail. A
+/// task stopped by its completion indicator failed unless it was reported
+/// with exit code 0.
+///
+/// Shared with `TaskResult::is_failure` of task groups.
+pub(crate) fn is_failure(exit_code: Option<i32>, stop_reason: &TaskEventStopReason) -> bool {
+    match stop_reason {
+        TaskEventStopReason::Finished
+        | TaskEventStopReason::Terminated(TaskTerminateReason::CompletionIndicator) => {
+            exit_code != Some(0)
         }
-        .maybe_instrument("spawn"),
-    );
-    #[cfg(feature = "tracing")]
-    tracing::debug!(
-        handle_id = %handle.id(),
-        "Spawned result watcher handle");
-    handle
+        TaskEventStopReason::Terminated(reason) => *reason == TaskTerminateReason::Timeout,
+        TaskEventStopReason::Error(_) | TaskEventStopReason::StartupFailure(_) => true,
+    }
 }
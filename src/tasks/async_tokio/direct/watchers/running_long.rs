@@ -0,0 +1,58 @@
+use std::{convert::Infallible, sync::Arc, time::Duration};
+
+use tokio::{sync::mpsc, time::Instant};
+
+use crate::tasks::event::TaskEvent;
+
+/// Multiplier used when `running_long_multiplier` is not configured
+const DEFAULT_RUNNING_LONG_MULTIPLIER: f64 = 2.0;
+
+/// Emits `TaskEvent::RunningLong` once the task has run for the configured
+/// multiple of its expected duration, then never resolves.
+///
+/// Polled as a branch of the wait watcher, so it is dropped as soon as the
+/// process stops and never terminates the task. Never emits anything if no
+/// expected duration is configured.
+///
+/// # Arguments
+///
+/// * `event_tx` - Sender for task events.
+/// * `task_name` - Name of the task.
+/// * `running_at` - When the process was spawned.
+/// * `expected_ms` - Expected runtime in milliseconds, if configured.
+/// * `multiplier` - Multiple of the expected runtime to wait for, if configured.
+pub(crate) async fn report_running_long(
+    event_tx: &mpsc::Sender<TaskEvent>,
+    task_name: &Arc<str>,
+    running_at: Instant,
+    expected_ms: Option<u64>,
+    multiplier: Option<f64>,
+) -> Infallible {
+    let threshold = expected_ms.and_then(|expected_ms| {
+        let multiplier = multiplier.unwrap_or(DEFAULT_RUNNING_LONG_MULTIPLIER);
+        Duration::try_from_secs_f64(Duration::from_millis(expected_ms).as_secs_f64() * multiplier)
+            .ok()
+    });
+    let (Some(expected_ms), Some(threshold)) = (expected_ms, threshold) else {
+        return std::future::pending().await;
+    };
+    tokio::time::sleep_until(running_at + threshold).await;
+
+    let elapsed_ms = u64::try_from(running_at.elapsed().as_millis()).unwrap_or(u64::MAX);
+    #[cfg(feature = "tracing")]
+    tracing::info!(
+        expected_ms,
+        elapsed_ms,
+        "Task is running longer than expected"
+    );
+    let event = TaskEvent::RunningLong {
+        task_name: task_name.clone(),
+        expected_ms,
+        elapsed_ms,
+    };
+    if event_tx.send(event).await.is_err() {
+        #[cfg(feature = "tracing")]
+        tracing::warn!("Event channel closed while sending TaskEvent::RunningLong");
+    }
+    std::future::pending().await
+}
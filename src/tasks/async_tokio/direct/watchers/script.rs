@@ -0,0 +1,138 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use tokio::sync::{Mutex, Notify, mpsc, oneshot};
+
+use super::timeout::claim_terminate_channel;
+use crate::tasks::{
+    async_tokio::spawner::StopRequest,
+    config::{ReadyMatchMode, StdinScript, StreamSource},
+    event::TaskEvent,
+};
+
+/// Value of `ScriptProgress::awaiting` while no step waits for a line
+const NOT_AWAITING: usize = usize::MAX;
+
+/// Step of a stdin script waiting for its line, shared by the output watchers and the script driver
+#[derive(Debug)]
+pub(crate) struct ScriptProgress {
+    script: StdinScript,
+    match_mode: ReadyMatchMode,
+    /// Index of the step whose line is awaited, or `NOT_AWAITING`
+    awaiting: AtomicUsize,
+    /// Notified once the awaited line was read
+    matched: Notify,
+}
+
+/// Script progress shared by the output watchers and the script driver
+pub(crate) type SharedScriptProgress = Arc<ScriptProgress>;
+
+impl ScriptProgress {
+    /// Creates the progress of `script`, with its first step waiting
+    ///
+    /// The first line may be read before the driver is first polled, so the
+    /// first step waits from the start.
+    pub(crate) fn new(script: StdinScript, match_mode: ReadyMatchMode) -> Self {
+        Self {
+            script,
+            match_mode,
+            awaiting: AtomicUsize::new(0),
+            matched: Notify::new(),
+        }
+    }
+
+    /// Checks an output line of `src` against the awaited step
+    ///
+    /// Called by the output watchers for every line. The step stops waiting
+    /// on the first match, so a line completes at most one step.
+    pub(crate) fn observe(&self, line: &str, src: &StreamSource) {
+        let index = self.awaiting.load(Ordering::Acquire);
+        let Some(step) = self.script.steps.get(index) else {
+            return;
+        };
+        if step.source == *src
+            && self.match_mode.matches(line, &step.wait_for)
+            && self
+                .awaiting
+                .compare_exchange(index, NOT_AWAITING, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+        {
+            self.matched.notify_one();
+        }
+    }
+}
+
+/// Stdin script of a started task, run by the wait watcher
+#[derive(Debug)]
+pub(crate) struct ScriptDriver {
+    /// Progress shared with the output watchers
+    pub(crate) progress: SharedScriptProgress,
+    /// Sender into the stdin watcher, dropped once the script is done
+    pub(crate) stdin_tx: mpsc::Sender<String>,
+}
+
+/// Runs the stdin script, resolving once a step timed out and this claimed
+/// the terminate channel.
+///
+/// Polled as a branch of the wait watcher. Each step waits for its line,
+/// queues its input for the stdin watcher and emits `TaskEvent::ScriptStep`.
+/// Once every step has run, the stdin sender is dropped so stdin can close,
+/// and the future never resolves. It also never resolves without a script,
+/// if stdin stopped accepting lines, or if a termination signal was sent
+/// before a step timed out.
+///
+/// # Arguments
+///
+/// * `driver` - The script and its stdin sender, if a script is configured.
+/// * `event_tx` - Sender for task events.
+/// * `task_name` - Name of the task.
+/// * `terminate_tx` - Shared sender for termination signals, taken when a step times out.
+pub(crate) async fn run_stdin_script(
+    driver: Option<ScriptDriver>,
+    event_tx: &mpsc::Sender<TaskEvent>,
+    task_name: &Arc<str>,
+    terminate_tx: &Mutex<Option<oneshot::Sender<StopRequest>>>,
+) {
+    let Some(ScriptDriver { progress, stdin_tx }) = driver else {
+        return std::future::pending().await;
+    };
+
+    for (index, step) in progress.script.steps.iter().enumerate() {
+        let timeout = Duration::from_millis(step.timeout_ms);
+        if tokio::time::timeout(timeout, progress.matched.notified())
+            .await
+            .is_err()
+        {
+            progress.awaiting.store(NOT_AWAITING, Ordering::Release);
+            #[cfg(feature = "tracing")]
+            tracing::debug!(index, "Stdin script step timed out");
+            return claim_terminate_channel(terminate_tx, "Stdin script step timed out").await;
+        }
+
+        if stdin_tx.send(step.then_send.clone()).await.is_err() {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(index, "Stdin closed before the stdin script finished");
+            return std::future::pending().await;
+        }
+        // Past the last step, no line is awaited anymore
+        progress.awaiting.store(index + 1, Ordering::Release);
+        let event = TaskEvent::ScriptStep {
+            task_name: task_name.clone(),
+            index: u32::try_from(index).unwrap_or(u32::MAX),
+        };
+        if event_tx.send(event).await.is_err() {
+            #[cfg(feature = "tracing")]
+            tracing::warn!("Event channel closed while sending TaskEvent::ScriptStep");
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!("Stdin script finished");
+    drop(stdin_tx);
+    std::future::pending().await
+}
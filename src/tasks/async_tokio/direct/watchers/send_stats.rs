@@ -0,0 +1,194 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        Arc, Mutex, PoisonError,
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use tokio::sync::mpsc;
+
+use crate::tasks::event::TaskEvent;
+
+/// Number of recent sends whose average wait is compared with the slow consumer threshold
+pub(crate) const SLOW_CONSUMER_WINDOW: usize = 16;
+
+/// How output events of a task fare on their way into the event channel
+///
+/// Shared by the output watchers of both streams and `TaskSpawner`, which
+/// reports the numbers in `TaskInfo`. With `slow_consumer_threshold_ms`
+/// configured, the waits of the last [`SLOW_CONSUMER_WINDOW`] sends are kept
+/// to tell when the consumer does not keep up.
+#[derive(Debug)]
+pub(crate) struct EventSendStats {
+    task_name: Arc<str>,
+    /// Average wait above which `TaskEvent::SlowConsumer` is sent
+    threshold: Option<Duration>,
+    /// Events that found the channel full
+    slow_sends: AtomicU64,
+    /// Total time spent waiting for room in the channel, in microseconds
+    wait_micros: AtomicU64,
+    /// Most events queued in the channel at once
+    high_water: AtomicUsize,
+    /// Waits of the last sends in microseconds, only kept with a threshold
+    window: Mutex<VecDeque<u64>>,
+    /// Set once `TaskEvent::SlowConsumer` was taken, so it is sent only once
+    warned: AtomicBool,
+}
+
+impl EventSendStats {
+    pub(crate) fn new(task_name: Arc<str>, threshold_ms: Option<u64>) -> Self {
+        Self {
+            task_name,
+            threshold: threshold_ms.map(Duration::from_millis),
+            slow_sends: AtomicU64::new(0),
+            wait_micros: AtomicU64::new(0),
+            high_water: AtomicUsize::new(0),
+            window: Mutex::new(VecDeque::with_capacity(SLOW_CONSUMER_WINDOW)),
+            warned: AtomicBool::new(false),
+        }
+    }
+
+    /// Counts an event that found the channel full
+    pub(crate) fn record_slow(&self) {
+        self.slow_sends.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records a send that waited `wait` for room in the channel of `event_tx`
+    pub(crate) fn record_send(&self, event_tx: &mpsc::Sender<TaskEvent>, wait: Duration) {
+        let wait = u64::try_from(wait.as_micros()).unwrap_or(u64::MAX);
+        if wait > 0 {
+            self.wait_micros.fetch_add(wait, Ordering::Relaxed);
+        }
+        let queued = event_tx.max_capacity() - event_tx.capacity();
+        self.high_water.fetch_max(queued, Ordering::Relaxed);
+
+        if self.threshold.is_some() && !self.warned.load(Ordering::Relaxed) {
+            let mut window = self.window.lock().unwrap_or_else(PoisonError::into_inner);
+            if window.len() == SLOW_CONSUMER_WINDOW {
+                window.pop_front();
+            }
+            window.push_back(wait);
+        }
+    }
+
+    /// Returns `TaskEvent::SlowConsumer` the first time the average wait of
+    /// the last sends exceeds the threshold
+    pub(crate) fn take_warning(&self) -> Option<TaskEvent> {
+        let threshold = self.threshold?;
+        if self.warned.load(Ordering::Relaxed) {
+            return None;
+        }
+        let avg_wait = {
+            let window = self.window.lock().unwrap_or_else(PoisonError::into_inner);
+            if window.len() < SLOW_CONSUMER_WINDOW {
+                return None;
+            }
+            Duration::from_micros(window.iter().sum::<u64>() / SLOW_CONSUMER_WINDOW as u64)
+        };
+        if avg_wait <= threshold || self.warned.swap(true, Ordering::Relaxed) {
+            return None;
+        }
+        let avg_wait_ms = u64::try_from(avg_wait.as_millis()).unwrap_or(u64::MAX);
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            avg_wait_ms,
+            sends = SLOW_CONSUMER_WINDOW,
+            "Event channel consumer is not keeping up"
+        );
+        Some(TaskEvent::SlowConsumer {
+            task_name: self.task_name.clone(),
+            avg_wait_ms,
+        })
+    }
+
+    /// Number of events that found the channel full
+    pub(crate) fn slow_sends(&self) -> u64 {
+        self.slow_sends.load(Ordering::Relaxed)
+    }
+
+    /// Total time spent waiting for room in the channel
+    pub(crate) fn wait(&self) -> Duration {
+        Duration::from_micros(self.wait_micros.load(Ordering::Relaxed))
+    }
+
+    /// Most events queued in the channel at once
+    pub(crate) fn high_water(&self) -> usize {
+        self.high_water.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(threshold_ms: Option<u64>) -> EventSendStats {
+        EventSendStats::new("send_stats".into(), threshold_ms)
+    }
+
+    #[test]
+    fn warns_once_when_average_exceeds_threshold() {
+        let (tx, _rx) = mpsc::channel(4);
+        let stats = stats(Some(5));
+
+        for _ in 0..SLOW_CONSUMER_WINDOW - 1 {
+            stats.record_send(&tx, Duration::from_millis(10));
+            assert_eq!(stats.take_warning(), None);
+        }
+        stats.record_send(&tx, Duration::from_millis(10));
+        assert_eq!(
+            stats.take_warning(),
+            Some(TaskEvent::SlowConsumer {
+                task_name: "send_stats".into(),
+                avg_wait_ms: 10,
+            })
+        );
+
+        stats.record_send(&tx, Duration::from_millis(10));
+        assert_eq!(stats.take_warning(), None);
+    }
+
+    #[test]
+    fn fast_sends_keep_the_average_below_threshold() {
+        let (tx, _rx) = mpsc::channel(4);
+        let stats = stats(Some(5));
+
+        stats.record_send(&tx, Duration::from_millis(50));
+        for _ in 0..SLOW_CONSUMER_WINDOW * 2 {
+            stats.record_send(&tx, Duration::ZERO);
+            assert_eq!(stats.take_warning(), None);
+        }
+        assert_eq!(stats.wait(), Duration::from_millis(50));
+    }
+
+    #[test]
+    fn no_warning_without_threshold() {
+        let (tx, _rx) = mpsc::channel(4);
+        let stats = stats(None);
+
+        for _ in 0..SLOW_CONSUMER_WINDOW {
+            stats.record_send(&tx, Duration::from_secs(1));
+        }
+        assert_eq!(stats.take_warning(), None);
+        assert_eq!(stats.wait(), Duration::from_secs(16));
+    }
+
+    #[tokio::test]
+    async fn high_water_tracks_queued_events() {
+        let (tx, mut rx) = mpsc::channel(4);
+        let stats = stats(None);
+        let event = TaskEvent::Ready {
+            task_name: "send_stats".into(),
+        };
+
+        for _ in 0..3 {
+            tx.send(event.clone()).await.unwrap();
+            stats.record_send(&tx, Duration::ZERO);
+        }
+        rx.recv().await.unwrap();
+        stats.record_send(&tx, Duration::ZERO);
+
+        assert_eq!(stats.high_water(), 3);
+    }
+}
@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+/// Length of a throttling window
+const WINDOW: Duration = Duration::from_secs(1);
+
+/// Lines suppressed in a window, as `(count, bytes)`
+pub(crate) type SuppressedSummary = (u64, u64);
+
+/// Limits the output events sent per second on a single output stream
+///
+/// The first `budget` lines of a window are sent. Later lines are only
+/// counted until [`take_summary`](Self::take_summary) is called, which the
+/// output watcher does once `deadline` has passed or the stream ends. The
+/// first line after the window starts a new one, and returns the pending
+/// summary from [`push`](Self::push).
+#[derive(Debug)]
+pub(crate) struct OutputThrottle {
+    budget: u32,
+    /// Start of the current window, set by its first line
+    window_start: Option<Instant>,
+    /// Lines sent in the current window
+    sent: u32,
+    /// Lines suppressed since the last summary
+    suppressed: u64,
+    /// Bytes of the suppressed lines
+    suppressed_bytes: u64,
+}
+
+impl OutputThrottle {
+    /// Creates a throttle sending at most `budget` lines per second.
+    pub(crate) fn new(budget: u32) -> Self {
+        Self {
+            budget,
+            window_start: None,
+            sent: 0,
+            suppressed: 0,
+            suppressed_bytes: 0,
+        }
+    }
+
+    /// Records a line of `len` bytes read at `now`
+    ///
+    /// # Returns
+    ///
+    /// The summary of the previous window if `now` is past it, and whether
+    /// the line should be sent.
+    pub(crate) fn push(&mut self, len: usize, now: Instant) -> (Option<SuppressedSummary>, bool) {
+        let mut summary = None;
+        if self.window_start.is_none_or(|start| now >= start + WINDOW) {
+            summary = self.take_summary();
+            self.window_start = Some(now);
+            self.sent = 0;
+        }
+        if self.sent < self.budget {
+            self.sent += 1;
+            return (summary, true);
+        }
+        self.suppressed += 1;
+        self.suppressed_bytes += len as u64;
+        (summary, false)
+    }
+
+    /// When the pending summary is due, if lines were suppressed
+    pub(crate) fn deadline(&self) -> Option<Instant> {
+        self.window_start
+            .filter(|_| self.suppressed > 0)
+            .map(|start| start + WINDOW)
+    }
+
+    /// Takes the summary of lines suppressed so far, if any
+    pub(crate) fn take_summary(&mut self) -> Option<SuppressedSummary> {
+        if self.suppressed == 0 {
+            return None;
+        }
+        let count = std::mem::take(&mut self.suppressed);
+        Some((count, std::mem::take(&mut self.suppressed_bytes)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_beyond_budget_are_suppressed_until_window_ends() {
+        let mut throttle = OutputThrottle::new(2);
+        let now = Instant::now();
+
+        assert_eq!(throttle.push(5, now), (None, true));
+        assert_eq!(throttle.push(5, now), (None, true));
+        assert_eq!(throttle.deadline(), None);
+        for _ in 0..3 {
+            assert_eq!(
+                throttle.push(4, now + Duration::from_millis(500)),
+                (None, false)
+            );
+        }
+        assert_eq!(throttle.deadline(), Some(now + WINDOW));
+
+        assert_eq!(throttle.push(5, now + WINDOW), (Some((3, 12)), true));
+        assert_eq!(throttle.deadline(), None);
+        assert_eq!(throttle.take_summary(), None);
+    }
+
+    #[test]
+    fn summary_taken_at_deadline_is_not_repeated() {
+        let mut throttle = OutputThrottle::new(1);
+        let now = Instant::now();
+
+        throttle.push(1, now);
+        throttle.push(2, now);
+        assert_eq!(throttle.take_summary(), Some((1, 2)));
+        assert_eq!(throttle.deadline(), None);
+
+        assert_eq!(throttle.push(1, now + WINDOW * 3), (None, true));
+        assert_eq!(throttle.push(1, now + WINDOW * 3), (None, false));
+        assert_eq!(throttle.deadline(), Some(now + WINDOW * 4));
+    }
+}
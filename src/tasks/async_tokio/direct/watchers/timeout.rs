@@ -1,70 +1,75 @@
-use std::{sync::Arc, time::Duration};
+use std::time::Duration;
 
-use tokio::{
-    sync::{Mutex, oneshot, watch},
-    task::JoinHandle,
-};
+use tokio::sync::{Mutex, oneshot};
 
-use crate::{helper::tracing::MaybeInstrument, tasks::event::TaskTerminateReason};
+use super::pause::{PauseClock, watch_job_control};
+use crate::tasks::async_tokio::spawner::StopRequest;
 
-/// Spawns a watcher that triggers a timeout after the specified duration.
+/// Resolves once the task timeout has elapsed and the timeout claimed the
+/// terminate channel.
 ///
-/// Sends a termination signal if the timeout elapses.
+/// Polled as a branch of the wait watcher rather than as its own task. Never
+/// resolves if no timeout is configured, or if a termination signal was sent
+/// before the timeout elapsed; that signal is then received by the wait
+/// watcher instead.
 ///
-/// # Arguments
-///
-/// * `terminate_tx` - Sender for termination signals.
-/// * `timeout_ms` - Timeout duration in milliseconds.
-/// * `handle_terminator_rx` - Receiver to listen for termination signals.
+/// With a `pause_clock`, time the task spent paused does not count towards
+/// the timeout, and on Unix stops of the child are detected while waiting.
 ///
-/// # Returns
+/// # Arguments
 ///
-/// A `JoinHandle` for the spawned timeout watcher task.
-#[cfg_attr(feature = "tracing", tracing::instrument(skip(terminate_tx)))]
-pub(crate) fn spawn_timeout_watcher(
-    terminate_tx: Arc<Mutex<Option<oneshot::Sender<TaskTerminateReason>>>>,
-    timeout_ms: u64,
-    mut handle_terminator_rx: watch::Receiver<bool>,
-) -> JoinHandle<()> {
-    let handle = tokio::spawn(
-        async move {
-            #[cfg(feature = "tracing")]
-            tracing::trace!(timeout_ms, "Starting timeout watcher");
-            let sleep = tokio::time::sleep(Duration::from_millis(timeout_ms));
-            tokio::pin!(sleep);
-            loop {
-                tokio::select! {
-                    () = &mut sleep => {
-                        #[cfg(feature = "tracing")]
-                        tracing::info!("Task timeout reached, sending termination signal");
-                        if let Some(tx) = terminate_tx.lock().await.take()
-                            && tx.send(TaskTerminateReason::Timeout).is_err() {
-                                #[cfg(feature = "tracing")]
-                                tracing::warn!("Event channel closed while sending TaskEvent::Timeout");
-                            }
-                        break;
-                    }
-                    _ = handle_terminator_rx.changed() => {
-                        #[cfg(feature = "tracing")]
-                        tracing::trace!("Task handle termination signal received");
-
-                        if *handle_terminator_rx.borrow() {
-                            #[cfg(feature = "tracing")]
-                            tracing::debug!("Termination signal received, closing timeout watcher");
-                            break;
-                        }
-                    }
-                }
+/// * `terminate_tx` - Shared sender for termination signals, taken on timeout.
+/// * `timeout_ms` - Timeout duration in milliseconds, if configured.
+/// * `pause_clock` - Clock of the paused time left out of the timeout, if enabled.
+/// * `pid` - Process ID of the child, watched for job control stops.
+pub(crate) async fn wait_for_timeout(
+    terminate_tx: &Mutex<Option<oneshot::Sender<StopRequest>>>,
+    timeout_ms: Option<u64>,
+    pause_clock: Option<&PauseClock>,
+    pid: Option<u32>,
+) {
+    let Some(timeout_ms) = timeout_ms else {
+        return std::future::pending().await;
+    };
+    #[cfg(feature = "tracing")]
+    tracing::trace!(timeout_ms, "Starting timeout");
+    let timeout = Duration::from_millis(timeout_ms);
+    match pause_clock {
+        Some(clock) => {
+            tokio::select! {
+                () = clock.sleep_unpaused(timeout) => {}
+                () = watch_job_control(pid, clock) => {}
             }
-                #[cfg(feature = "tracing")]
-                tracing::debug!("Watcher finished");
         }
-        .maybe_instrument("spawn"),
-    );
-    #[cfg(feature = "tracing")]
-    tracing::debug!(
-        handle_id = %handle.id(),
-        "Spawned timeout watcher handle"
-    );
-    handle
+        None => tokio::time::sleep(timeout).await,
+    }
+
+    claim_terminate_channel(terminate_tx, "Task timeout reached").await;
+}
+
+/// Resolves once a watcher that stops the task, like the timeout, claimed the
+/// terminate channel.
+///
+/// Taking the sender makes later terminate signals fail, as they would once
+/// any other reason has been sent. The wait watcher then stops the task with
+/// the reason of the branch that resolved. Never resolves if a termination
+/// signal was sent first; that signal is then received by the wait watcher
+/// instead.
+///
+/// # Arguments
+///
+/// * `terminate_tx` - Shared sender for termination signals, taken if still there.
+/// * `_cause` - What stops the task, for tracing.
+pub(crate) async fn claim_terminate_channel(
+    terminate_tx: &Mutex<Option<oneshot::Sender<StopRequest>>>,
+    _cause: &str,
+) {
+    if terminate_tx.lock().await.take().is_some() {
+        #[cfg(feature = "tracing")]
+        tracing::info!("{_cause}, terminating task");
+    } else {
+        #[cfg(feature = "tracing")]
+        tracing::debug!("{_cause} after a termination signal was sent");
+        std::future::pending::<()>().await;
+    }
 }
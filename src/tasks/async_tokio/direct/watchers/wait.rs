@@ -1,176 +1,505 @@
-use std::sync::Arc;
+use std::{process::ExitStatus, sync::Arc, time::Duration};
 
 use tokio::{
     process::Child,
-    sync::{RwLock, oneshot, watch},
-    task::JoinHandle,
+    sync::{oneshot, watch},
 };
 
+use super::{
+    channel_closed::wait_for_channel_closed,
+    health::{HealthMonitor, watch_health},
+    memory_watermark::report_memory_watermark,
+    pause::PauseClock,
+    result::ResultReporter,
+    running_long::report_running_long,
+    script::{ScriptDriver, run_stdin_script},
+    timeout::wait_for_timeout,
+};
 use crate::{
     helper::tracing::MaybeInstrument,
     tasks::{
-        async_tokio::process_group::{ProcessGroup, ProcessGroupError}, event::{TaskEventStopReason, TaskTerminateReason}, state::TaskState
+        async_tokio::{
+            process_group::{ProcessGroup, ProcessGroupError, request_shutdown},
+            reaper::ChildGuard,
+            spawner::{ChannelClosedPolicy, SharedStopSender, StopRequest},
+            watcher_set::WatcherSet,
+        },
+        config::{SharedTaskConfig, TaskConfig},
+        event::{TaskEventStopReason, TaskTerminateReason},
     },
 };
 
-/// Spawns a watcher that waits for the child process to exit or be terminated.
+/// NTSTATUS of a process ended by Ctrl+C or by closing its console
+const STATUS_CONTROL_C_EXIT: u32 = 0xC000_013A;
+/// Status of a process ended by Ctrl+Break
+const DBG_CONTROL_BREAK: u32 = 0x4001_0008;
+
+/// Configuration for spawning the wait watcher
+#[derive(Debug)]
+pub(crate) struct WaitWatcherConfig {
+    /// Name of the task.
+    pub(crate) task_name: Arc<str>,
+    /// Shared sender for termination signals, taken when the timeout elapses.
+    pub(crate) terminate_tx: SharedStopSender,
+    /// Receiver for termination signals.
+    pub(crate) terminate_rx: oneshot::Receiver<StopRequest>,
+    /// Sender to signal other watchers to terminate.
+    pub(crate) handle_terminator_tx: watch::Sender<bool>,
+    /// Shared task configuration providing the timeout.
+    pub(crate) config: SharedTaskConfig,
+    /// Kill the child if the watcher is aborted, see `TaskSpawner::set_cleanup_on_drop`.
+    pub(crate) kill_on_abort: bool,
+    /// What to do once the event receiver is dropped, see `TaskSpawner::set_on_channel_closed`.
+    pub(crate) on_channel_closed: ChannelClosedPolicy,
+    /// Stdin script to run, if configured.
+    pub(crate) stdin_script: Option<ScriptDriver>,
+    /// Health check to run, see `TaskSpawner::set_health_check`.
+    pub(crate) health: Option<HealthMonitor>,
+    /// Time the task spent paused, see `TaskConfig::timeout_excludes_paused`.
+    pub(crate) pause_clock: Arc<PauseClock>,
+}
+
+/// Maps the exit status of a process that exited without being terminated by us
+/// to the exit code and stop reason reported in `TaskEvent::Stopped`.
 ///
-/// Sends stop reason and signals other watchers to terminate.
-/// Uses cross-platform process group termination to kill entire process trees.
+/// A normal exit reports `Some(code)` with `Finished`. A Unix process killed by a
+/// signal, or a Windows process ending with an NTSTATUS failure code, reports
+/// `None` with an `Error` reason describing how it died. Windows console
+/// interrupts are mapped by [`classify_windows_exit_code`].
+fn classify_exit_status(status: ExitStatus) -> (Option<i32>, TaskEventStopReason) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+
+        if let Some(signal) = status.signal() {
+            let name = nix::sys::signal::Signal::try_from(signal)
+                .map_or_else(|_| "unknown signal".to_string(), |s| s.as_str().to_string());
+            return (
+                None,
+                TaskEventStopReason::Error(format!("Process killed by signal {name} ({signal})")),
+            );
+        }
+    }
+
+    match status.code() {
+        #[cfg(windows)]
+        Some(code) => classify_windows_exit_code(code),
+        #[cfg(not(windows))]
+        Some(code) => (Some(code), TaskEventStopReason::Finished),
+        None => (
+            None,
+            TaskEventStopReason::Error(format!("Process exited with unknown status: {status}")),
+        ),
+    }
+}
+
+/// Signal that killed a process, always `None` outside Unix
+fn exit_signal(status: ExitStatus) -> Option<i32> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+
+        status.signal()
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = status;
+        None
+    }
+}
+
+/// Maps a Windows process exit code to the exit code and stop reason.
+///
+/// A process ended by Ctrl+C, Ctrl+Break or closing its console reports
+/// `Terminated(UserRequested)` and keeps the raw status as its exit code.
+/// Other NTSTATUS failure codes (negative as `i32`) report `None` with an
+/// `Error` reason naming the status.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn classify_windows_exit_code(code: i32) -> (Option<i32>, TaskEventStopReason) {
+    match code.cast_unsigned() {
+        STATUS_CONTROL_C_EXIT | DBG_CONTROL_BREAK => (
+            Some(code),
+            TaskEventStopReason::Terminated(TaskTerminateReason::UserRequested { message: None }),
+        ),
+        status if code < 0 => (
+            None,
+            TaskEventStopReason::Error(format!(
+                "Process terminated abnormally with status 0x{status:08X}"
+            )),
+        ),
+        _ => (Some(code), TaskEventStopReason::Finished),
+    }
+}
+
+/// Spawns the watcher that owns the child process until the task has stopped.
+///
+/// Waits for the child to exit, for a termination signal, for the task
+/// timeout, for a stdin script step to time out or, if so configured, for
+/// the event receiver to be dropped or the task to become unhealthy,
+/// whichever comes first. Meanwhile it runs the stdin script and the health
+/// check, and reports a task running longer
+/// than expected and memory usage crossing the watermark. On termination it kills the entire process
+/// tree through the process group if one is used. It then signals the other
+/// watchers to terminate and reports the result through `reporter`.
+///
+/// Timeout and result handling run inside this task rather than as separate
+/// tasks, so each task spawns at most one tokio task besides its output and
+/// stdin watchers.
+///
+/// If the watcher is aborted before the child has been waited on, the child is
+/// killed, unless the task detaches it, and reaped in the background (see
+/// [`ChildGuard`]).
 ///
 /// # Arguments
 ///
-/// * `task_name` - Name of the task.
-/// * `state` - Shared state of the task.
+/// * `watchers` - Set the watcher is spawned into.
 /// * `child` - The child process to monitor.
 /// * `process_group` - Process group for killing entire process trees.
-/// * `terminate_rx` - Receiver for termination signals.
-/// * `handle_terminator_tx` - Sender to signal other watchers to terminate.
-/// * `result_tx` - Sender for the process exit code and stop reason.
-/// * `process_id` - Shared process ID.
-///
-/// # Returns
-///
-/// A `JoinHandle` for the spawned watcher task.
+/// * `config` - Termination channels, shared state and task configuration.
+/// * `reporter` - Reports `TaskEvent::Stopped` once the process has stopped.
 #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
 pub(crate) fn spawn_wait_watcher(
-    task_name: String,
-    state: Arc<RwLock<TaskState>>,
-    mut child: Child,
+    watchers: &mut WatcherSet,
+    child: Child,
     process_group: Option<ProcessGroup>,
-    terminate_rx: oneshot::Receiver<TaskTerminateReason>,
-    handle_terminator_tx: watch::Sender<bool>,
-    result_tx: oneshot::Sender<(Option<i32>, TaskEventStopReason)>,
-    process_id: Arc<RwLock<Option<u32>>>,
-) -> JoinHandle<()> {
-    let handle = tokio::spawn(
+    config: WaitWatcherConfig,
+    mut reporter: ResultReporter,
+) {
+    watchers.spawn(
+        "wait",
         async move {
-            tokio::select! {
+            let WaitWatcherConfig {
+                task_name,
+                terminate_tx,
+                mut terminate_rx,
+                handle_terminator_tx,
+                config,
+                kill_on_abort,
+                on_channel_closed,
+                stdin_script,
+                health,
+                pause_clock,
+            } = config;
+            let mut child = ChildGuard::new(child, kill_on_abort);
+            let pid = child.id();
+            let timeout_pause_clock = config
+                .timeout_excludes_paused
+                .unwrap_or_default()
+                .then_some(&*pause_clock);
+
+            let stop_request = tokio::select! {
                 result = child.wait() => {
                     #[cfg(feature = "tracing")]
                     tracing::trace!("child process finished");
-                    
-                    // When main process exits, it must terminate any remaining child processes
-                    // to prevent orphaned processes from continuing to run
-                    if let Some(ref pg) = process_group {
-                        #[cfg(feature = "tracing")]
-                        tracing::debug!("Main process finished, terminating remaining child processes in group");
-                        if let Err(_e) = pg.terminate_all().await {
-                            #[cfg(feature = "tracing")]
-                            tracing::warn!(error = %_e, "Failed to terminate remaining child processes after main process exit");
-                        }
-                    }
-                    
-                    match result {
-                        Ok(status) => {
-                            let exit_code = status.code();
-                            if result_tx.send((
-                                exit_code,
-                                TaskEventStopReason::Finished,
-                            )).is_err() {
-                                    #[cfg(feature = "tracing")]
-                                    tracing::warn!(exit_code, "Result channel closed while sending TaskEventStopReason::Finished");
-                            }
-                                #[cfg(feature = "tracing")]
-                                tracing::debug!(exit_code = ?exit_code, "Child process finished normally, child processes terminated");
-                        }
-                        Err(e) => {
-                            // Expected OS level error
-                            if result_tx.send((
-                                None,
-                                TaskEventStopReason::Error(e.to_string()),
-                            )).is_err() {
-                                    #[cfg(feature = "tracing")]
-                                    tracing::warn!(error = %e, "Result channel closed while sending TaskEventStopReason::Error");
-                            }
-                                #[cfg(feature = "tracing")]
-                                tracing::error!(error = %e, "Child process wait failed");
-                        }
-                    }
+                    Err(result)
                 }
-                reason = terminate_rx => {
+                request = &mut terminate_rx => {
                     #[cfg(feature = "tracing")]
                     tracing::trace!("Termination signal received");
+                    Ok(request.unwrap_or(StopRequest::Terminate(TaskTerminateReason::Cleanup {
+                        message: None,
+                    })))
+                }
+                () = wait_for_timeout(&terminate_tx, config.timeout_ms, timeout_pause_clock, pid) => {
+                    Ok(StopRequest::Terminate(TaskTerminateReason::Timeout))
+                }
+                () = wait_for_channel_closed(
+                    &reporter.event_tx,
+                    &terminate_tx,
+                    on_channel_closed == ChannelClosedPolicy::TerminateTask,
+                ) => {
+                    Ok(StopRequest::Terminate(TaskTerminateReason::Cleanup { message: None }))
+                }
+                () = run_stdin_script(stdin_script, &reporter.event_tx, &task_name, &terminate_tx) => {
+                    Ok(StopRequest::Terminate(TaskTerminateReason::ScriptTimeout))
+                }
+                () = watch_health(health, &reporter, pid, &terminate_tx) => {
+                    Ok(StopRequest::Terminate(TaskTerminateReason::Unhealthy))
+                }
+                never = report_running_long(
+                    &reporter.event_tx,
+                    &task_name,
+                    reporter.running_at,
+                    config.expected_duration_ms,
+                    config.running_long_multiplier,
+                ) => match never {},
+                never = report_memory_watermark(
+                    &reporter.event_tx,
+                    &task_name,
+                    pid,
+                    process_group.as_ref(),
+                    config.memory_watermark_bytes,
+                    config.sample_interval_ms,
+                ) => match never {},
+            };
 
-                    // Try to terminate the entire process group if enabled, otherwise just the individual process
-                    let termination_result = if let Some(ref pg) = process_group {
-                        #[cfg(feature = "tracing")]
-                        tracing::trace!("Terminating process group");
-                        pg.terminate_all().await
-                    } else {
-                        #[cfg(feature = "tracing")]
-                        tracing::trace!("Process group disabled, terminating individual process");
-                        child.kill().await.map_err(|e| ProcessGroupError::TerminationFailed(e.to_string()))
-                    };
-
-                    if let Err(e) = termination_result {
-                        #[cfg(feature = "tracing")]
-                        tracing::warn!(error = %e, "Process termination failed");
-                        
-                        // If process group termination failed and we're using process groups, fallback to individual kill
-                        if process_group.is_some() {
-                            if let Err(e2) = child.kill().await {
-                                // Expected OS level error
-                                if result_tx.send((
-                                    None,
-                                    TaskEventStopReason::Error(format!(
-                                        "Failed to terminate task {task_name}: process group: {e}, individual: {e2}"
-                                    )),
-                                )).is_err() {
-                                    #[cfg(feature = "tracing")]
-                                    tracing::warn!(error = %e2, "Result channel closed while sending TaskEventStopReason::Error");
-                                }
-                                #[cfg(feature = "tracing")]
-                                tracing::error!(error = %e2, "Failed to kill child process after process group failure");
-                                return;
-                            }
-                        } else {
-                            // Process group not available and individual termination failed
-                            if result_tx.send((
-                                None,
-                                TaskEventStopReason::Error(format!(
-                                    "Failed to terminate task {task_name}: {e}"
-                                )),
-                            )).is_err() {
-                                #[cfg(feature = "tracing")]
-                                tracing::warn!(error = %e, "Result channel closed while sending TaskEventStopReason::Error");
-                            }
-                            #[cfg(feature = "tracing")]
-                            tracing::error!(error = %e, "Failed to kill child process");
-                            return;
-                        }
-                    }
-
-                    *state.write().await = TaskState::Finished;
-                    let reason = reason.unwrap_or(TaskTerminateReason::Cleanup);
-                    if result_tx.send((
-                        None,
-                        TaskEventStopReason::Terminated(reason.clone()),
-                    )).is_err() {
-                            #[cfg(feature = "tracing")]
-                            tracing::warn!(reason = ?reason, "Result channel closed while sending TaskEventStopReason::Terminated");
-                    }
-                        #[cfg(feature = "tracing")]
-                        tracing::debug!(reason = ?reason, "Process group terminated via watcher");
+            let (exit_code, stop_reason) = match stop_request {
+                Err(result) => {
+                    // Later termination requests find the task finished
+                    drop(terminate_rx);
+                    reporter.signal = result.as_ref().ok().copied().and_then(exit_signal);
+                    exited(result, process_group.as_ref()).await
                 }
-            }
+                Ok(request) => {
+                    let (exit_code, stop_reason, signal) = terminate(
+                        &task_name,
+                        &mut child,
+                        process_group.as_ref(),
+                        request,
+                        &config,
+                    )
+                    .await;
+                    reporter.signal = signal;
+                    (exit_code, stop_reason)
+                }
+            };
+
             // Task finished, send handle terminate signal
             if handle_terminator_tx.send(true).is_err() {
-                    #[cfg(feature = "tracing")]
-                    tracing::warn!("Handle terminate channels closed while sending signal");
+                #[cfg(feature = "tracing")]
+                tracing::warn!("Handle terminate channels closed while sending signal");
             }
-                process_id.write().await.take();
 
-                #[cfg(feature = "tracing")]
-                tracing::debug!("Watcher finished");
+            reporter.report(exit_code, stop_reason).await;
+
+            #[cfg(feature = "tracing")]
+            tracing::debug!("Watcher finished");
         }
         .maybe_instrument("spawn"),
     );
+}
+
+/// Handles a child process that exited on its own
+///
+/// When the main process exits, any remaining processes in its group are
+/// terminated to prevent orphans from continuing to run.
+async fn exited(
+    result: std::io::Result<ExitStatus>,
+    process_group: Option<&ProcessGroup>,
+) -> (Option<i32>, TaskEventStopReason) {
+    terminate_remaining(process_group).await;
+
+    match result {
+        Ok(status) => {
+            let (exit_code, stop_reason) = classify_exit_status(status);
+            #[cfg(feature = "tracing")]
+            tracing::debug!(exit_code = ?exit_code, reason = ?stop_reason, "Child process finished, child processes terminated");
+            (exit_code, stop_reason)
+        }
+        Err(e) => {
+            // Expected OS level error
+            #[cfg(feature = "tracing")]
+            tracing::error!(error = %e, "Child process wait failed");
+            (None, TaskEventStopReason::Error(e.to_string()))
+        }
+    }
+}
+
+/// Terminates the processes left in the group once the main process has exited
+async fn terminate_remaining(process_group: Option<&ProcessGroup>) {
+    let Some(pg) = process_group else {
+        return;
+    };
     #[cfg(feature = "tracing")]
-    tracing::debug!(
-        handle_id = %handle.id(),
-        "Spawned wait watcher handle"
-    );
+    tracing::debug!("Main process finished, terminating remaining child processes in group");
+    match pg.terminate_all().await {
+        Ok(_outcome) => {
+            #[cfg(feature = "tracing")]
+            if _outcome.already_exited {
+                tracing::trace!("No child processes left in group");
+            }
+        }
+        Err(_e) => {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(error = %_e, "Failed to terminate remaining child processes after main process exit");
+        }
+    }
+}
+
+/// Asks the child, or its whole process group, to shut down and waits up to `grace` for it to exit
+///
+/// Returns the exit status if the child exited in time, `None` if it has to
+/// be killed: it is still running, or the request could not be sent.
+async fn shut_down_gracefully(
+    child: &mut ChildGuard,
+    group: bool,
+    grace: Duration,
+) -> Option<ExitStatus> {
+    let pid = child.id()?;
+    // The child is not reaped before it is waited on, so its ID cannot be reused
+    match request_shutdown(pid, None, group) {
+        Ok(_outcome) => {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(pid, outcome = ?_outcome, ?grace, "Shutdown requested, waiting for the process to exit");
+        }
+        Err(_e) => {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(pid, error = %_e, "Failed to request shutdown, killing the process");
+            return None;
+        }
+    }
+    match tokio::time::timeout(grace, child.wait()).await {
+        Ok(Ok(status)) => Some(status),
+        Ok(Err(_e)) => {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(pid, error = %_e, "Failed to wait for the process to shut down");
+            None
+        }
+        Err(_) => {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                pid,
+                ?grace,
+                "Process still running after the grace period, killing it"
+            );
+            None
+        }
+    }
+}
+
+/// Terminates the child process, or its whole process group if enabled
+///
+/// With `graceful_shutdown_ms`, the child is first asked to shut down and
+/// only killed if it does not exit in time. Falls back to killing the
+/// individual process if the process group could not be terminated. A `Fail`
+/// request is reported as an `Error` stop reason. A completion indicator is
+/// reported with exit code 0 if `completion_success` is set; other
+/// terminations carry the exit code of a process that shut down in time, if
+/// any.
+///
+/// Also returns the signal that ended the process on Unix.
+async fn terminate(
+    task_name: &str,
+    child: &mut ChildGuard,
+    process_group: Option<&ProcessGroup>,
+    request: StopRequest,
+    config: &TaskConfig,
+) -> (Option<i32>, TaskEventStopReason, Option<i32>) {
+    let grace = config.graceful_shutdown_ms.map(Duration::from_millis);
+    let shut_down = match grace {
+        Some(grace) => shut_down_gracefully(child, process_group.is_some(), grace).await,
+        None => None,
+    };
+    let termination_result = if shut_down.is_some() {
+        terminate_remaining(process_group).await;
+        Ok(())
+    } else if let Some(pg) = process_group {
+        #[cfg(feature = "tracing")]
+        tracing::trace!("Terminating process group");
+        match pg.terminate_all().await {
+            Ok(_outcome) => {
+                #[cfg(feature = "tracing")]
+                tracing::trace!(outcome = ?_outcome, "Process group termination signal sent");
+                // Collect the exit status so the child does not linger as a zombie
+                if let Err(_e) = child.wait().await {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(error = %_e, "Failed to wait for child after process group termination");
+                }
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    } else {
+        #[cfg(feature = "tracing")]
+        tracing::trace!("Process group disabled, terminating individual process");
+        child
+            .kill()
+            .await
+            .map_err(|e| ProcessGroupError::TerminationFailed(e.to_string()))
+    };
+
+    if let Err(e) = termination_result {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(error = %e, "Process termination failed");
+
+        // If process group termination failed, fallback to individual kill
+        if process_group.is_none() {
+            #[cfg(feature = "tracing")]
+            tracing::error!(error = %e, "Failed to kill child process");
+            return (
+                None,
+                TaskEventStopReason::Error(format!("Failed to terminate task {task_name}: {e}")),
+                None,
+            );
+        }
+        if let Err(e2) = child.kill().await {
+            // Expected OS level error
+            #[cfg(feature = "tracing")]
+            tracing::error!(error = %e2, "Failed to kill child process after process group failure");
+            return (
+                None,
+                TaskEventStopReason::Error(format!(
+                    "Failed to terminate task {task_name}: process group: {e}, individual: {e2}"
+                )),
+                None,
+            );
+        }
+    }
+
+    // Moved to Finished by the result reporter, like a task that exits on its own
+    #[cfg(feature = "tracing")]
+    tracing::debug!(request = ?request, "Process group terminated via watcher");
+    let reason = match request {
+        StopRequest::Terminate(reason) => TaskEventStopReason::Terminated(reason),
+        StopRequest::Fail(message) => TaskEventStopReason::Error(message),
+    };
+    let completed =
+        reason == TaskEventStopReason::Terminated(TaskTerminateReason::CompletionIndicator);
+    let exit_code = if completed && config.completion_success.unwrap_or(true) {
+        Some(0)
+    } else {
+        shut_down.and_then(|status| status.code())
+    };
+    // Already reaped after a kill, so this returns the status it was killed with
+    let status = match shut_down {
+        Some(status) => Some(status),
+        None => child.wait().await.ok(),
+    };
+    (exit_code, reason, status.and_then(exit_signal))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    handle
+    #[test]
+    fn windows_console_interrupts_map_to_user_requested() {
+        for status in [STATUS_CONTROL_C_EXIT, DBG_CONTROL_BREAK] {
+            let code = status.cast_signed();
+            assert_eq!(
+                classify_windows_exit_code(code),
+                (
+                    Some(code),
+                    TaskEventStopReason::Terminated(TaskTerminateReason::UserRequested {
+                        message: None
+                    })
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn windows_ntstatus_failure_maps_to_error() {
+        // STATUS_ACCESS_VIOLATION
+        let code = 0xC000_0005_u32.cast_signed();
+        assert_eq!(
+            classify_windows_exit_code(code),
+            (
+                None,
+                TaskEventStopReason::Error(
+                    "Process terminated abnormally with status 0xC0000005".to_string()
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn windows_regular_exit_codes_are_finished() {
+        for code in [0, 1, 255] {
+            assert_eq!(
+                classify_windows_exit_code(code),
+                (Some(code), TaskEventStopReason::Finished)
+            );
+        }
+    }
 }
@@ -0,0 +1,137 @@
+//! Liveness probing of ready tasks.
+//!
+//! A task stays [`Ready`](crate::tasks::state::TaskState::Ready) until it
+//! stops, even if the server it runs deadlocks later on. A [`HealthCheck`]
+//! set with [`TaskSpawner::set_health_check`](crate::tasks::async_tokio::spawner::TaskSpawner::set_health_check)
+//! probes the task at a fixed interval once it is Ready. After
+//! `failure_threshold` consecutive failed probes, `TaskEvent::Unhealthy` is
+//! sent, [`TaskSpawner::is_healthy`](crate::tasks::async_tokio::spawner::TaskSpawner::is_healthy)
+//! turns `false` and, with [`UnhealthyAction::Terminate`], the task is
+//! terminated with `TaskTerminateReason::Unhealthy`.
+
+use std::{fmt, sync::Arc, time::Duration};
+
+use crate::tasks::state::TaskState;
+
+/// Consecutive failed probes after which a task is unhealthy, by default
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+
+/// What a custom health probe is told about the task
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskStatusInfo {
+    /// System process ID, `None` if it could not be read after spawning
+    pub process_id: Option<u32>,
+    /// Current state of the task
+    pub state: TaskState,
+    /// Time since the process was spawned
+    pub uptime: Duration,
+    /// Bytes read from stdout and stderr so far, without line terminators
+    pub output_bytes: u64,
+}
+
+/// Function deciding whether a task is healthy
+pub type HealthProbeFn = dyn Fn(&TaskStatusInfo) -> bool + Send + Sync;
+
+/// How a [`HealthCheck`] probes the task
+#[derive(Clone)]
+pub enum HealthCheckMode {
+    /// The probe passes if output was read since the previous probe
+    ///
+    /// Empty lines add no bytes, so they do not count as activity.
+    OutputActivity,
+    /// The probe passes if the function returns `true`
+    ///
+    /// Called from the task's wait watcher, so it must not block.
+    Custom(Arc<HealthProbeFn>),
+}
+
+impl fmt::Debug for HealthCheckMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutputActivity => f.write_str("OutputActivity"),
+            Self::Custom(_) => f.debug_tuple("Custom").finish_non_exhaustive(),
+        }
+    }
+}
+
+/// What happens once a task is unhealthy
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnhealthyAction {
+    /// Only report it; the task keeps running and probing goes on (default)
+    #[default]
+    Report,
+    /// Terminate the task with `TaskTerminateReason::Unhealthy`
+    Terminate,
+}
+
+/// Liveness probe of a ready task
+///
+/// The first probe runs one interval after the task was seen Ready. A probe
+/// that passes again after the task was reported unhealthy makes it healthy,
+/// and the task is reported again after another `failure_threshold` failures.
+///
+/// # Examples
+///
+/// ```rust
+/// use tcrm_task::tasks::{
+///     async_tokio::{
+///         health::{HealthCheck, HealthCheckMode, UnhealthyAction},
+///         spawner::TaskSpawner,
+///     },
+///     config::TaskConfig,
+/// };
+///
+/// let config = TaskConfig::new("my-server").ready_indicator("listening");
+/// let check = HealthCheck::new(5000, HealthCheckMode::OutputActivity)
+///     .failure_threshold(2)
+///     .unhealthy_action(UnhealthyAction::Terminate);
+/// let spawner = TaskSpawner::new("server".to_string(), config).set_health_check(check);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HealthCheck {
+    /// Interval between probes in milliseconds
+    ///
+    /// 0 is treated as 1.
+    pub interval_ms: u64,
+    /// How the task is probed
+    pub mode: HealthCheckMode,
+    /// Consecutive failed probes after which the task is unhealthy (default: 3)
+    ///
+    /// 0 is treated as 1.
+    pub failure_threshold: u32,
+    /// What happens once the task is unhealthy (default: report only)
+    pub unhealthy_action: UnhealthyAction,
+}
+
+impl HealthCheck {
+    /// Create a health check probing every `interval_ms` milliseconds
+    ///
+    /// # Arguments
+    ///
+    /// * `interval_ms` - Interval between probes in milliseconds
+    /// * `mode` - How the task is probed
+    #[must_use]
+    pub fn new(interval_ms: u64, mode: HealthCheckMode) -> Self {
+        Self {
+            interval_ms,
+            mode,
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+            unhealthy_action: UnhealthyAction::default(),
+        }
+    }
+
+    /// Set the number of consecutive failed probes after which the task is unhealthy
+    #[must_use]
+    pub fn failure_threshold(mut self, failures: u32) -> Self {
+        self.failure_threshold = failures;
+        self
+    }
+
+    /// Set what happens once the task is unhealthy
+    #[must_use]
+    pub fn unhealthy_action(mut self, action: UnhealthyAction) -> Self {
+        self.unhealthy_action = action;
+        self
+    }
+}
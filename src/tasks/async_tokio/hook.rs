@@ -0,0 +1,148 @@
+//! Hooks run by the spawner at points of the task lifecycle.
+//!
+//! An [`OnSpawnedHook`] runs once the process exists but before anything is
+//! reported about it, for example to register its pid with an external
+//! system before the process gets to do any work the system should observe.
+//! A [`CommandCustomizer`] runs before that, on the command about to be
+//! spawned. On Unix, `pre_exec` steps run in the child process itself,
+//! before it executes the command.
+
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Mutex, PoisonError},
+};
+
+use tokio::process::Command;
+
+use crate::tasks::error::TaskError;
+
+/// Future returned by a hook
+pub type HookFuture = Pin<Box<dyn Future<Output = Result<(), TaskError>> + Send>>;
+
+/// Function called with the process ID of the spawned process
+pub type OnSpawnedFn = dyn Fn(u32) -> HookFuture + Send + Sync;
+
+/// Hook run right after the process has been spawned
+///
+/// Runs once the process ID is known and the process has been assigned to its
+/// process group, before `TaskEvent::Started` is sent and before any output is
+/// read. Output written by the process meanwhile stays buffered in its pipes.
+///
+/// If the hook returns an error, the process (or its process group) is
+/// killed, `TaskEvent::Error` with the hook's error is sent, followed by
+/// `TaskEvent::Stopped`, and `start_direct` returns the error.
+///
+/// # Examples
+/// ```rust
+/// use tcrm_task::tasks::{
+///     async_tokio::{hook::OnSpawnedHook, spawner::TaskSpawner},
+///     config::TaskConfig,
+/// };
+///
+/// let hook = OnSpawnedHook::new(|pid| async move {
+///     println!("registering pid {pid}");
+///     Ok(())
+/// });
+/// let spawner = TaskSpawner::new("audited".to_string(), TaskConfig::new("my-server"))
+///     .set_on_spawned(hook);
+/// ```
+pub struct OnSpawnedHook {
+    func: Box<OnSpawnedFn>,
+}
+
+impl OnSpawnedHook {
+    /// Create a hook from an async function called with the process ID
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - Called with the process ID; an error aborts the task
+    pub fn new<F, Fut>(func: F) -> Self
+    where
+        F: Fn(u32) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), TaskError>> + Send + 'static,
+    {
+        Self {
+            func: Box::new(move |pid| Box::pin(func(pid))),
+        }
+    }
+
+    /// Runs the hook for the process with the given ID
+    pub(crate) fn call(&self, pid: u32) -> HookFuture {
+        (self.func)(pid)
+    }
+}
+
+impl fmt::Debug for OnSpawnedHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OnSpawnedHook").finish_non_exhaustive()
+    }
+}
+
+/// Function adjusting the command of a task before it is spawned
+pub type CustomizeCommandFn = dyn FnMut(&mut Command) + Send;
+
+/// Escape hatch for `Command` options that `TaskConfig` does not offer
+///
+/// Called with the fully configured command, after every option of the task
+/// configuration, the process group and the temporary working directory have
+/// been applied, right before spawning. It also runs for
+/// `TaskSpawner::plan`, so the plan reflects its changes.
+///
+/// This is unsupported territory: the customizer can undo what the spawner set
+/// up, e.g. the stdio pipes the watchers read from or the process group the
+/// task is terminated through, and such conflicts are not detected. A panic
+/// in the customizer propagates to the caller of `start_direct`.
+pub struct CommandCustomizer {
+    func: Mutex<Box<CustomizeCommandFn>>,
+}
+
+impl CommandCustomizer {
+    /// Create a customizer from a function called with the command
+    pub(crate) fn new<F>(func: F) -> Self
+    where
+        F: FnMut(&mut Command) + Send + 'static,
+    {
+        Self {
+            func: Mutex::new(Box::new(func)),
+        }
+    }
+
+    /// Applies the customizer to `cmd`
+    pub(crate) fn apply(&self, cmd: &mut Command) {
+        let mut func = self.func.lock().unwrap_or_else(PoisonError::into_inner);
+        func(cmd);
+    }
+}
+
+impl fmt::Debug for CommandCustomizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CommandCustomizer").finish_non_exhaustive()
+    }
+}
+
+/// Function run in the child process right before the command is executed
+#[cfg(unix)]
+pub type PreExecFn = dyn Fn() -> std::io::Result<()> + Send + Sync;
+
+/// Named step run in the child process between `fork` and `exec`
+///
+/// Added with `TaskSpawner::add_pre_exec_step`. Steps run after the ones the
+/// spawner registers itself, such as `setsid` for the process group, in the
+/// order they were added. If a step fails, the spawn fails with
+/// [`TaskError::PreExec`] naming the step.
+#[cfg(unix)]
+pub(crate) struct PreExecHook {
+    pub(crate) name: String,
+    pub(crate) func: std::sync::Arc<PreExecFn>,
+}
+
+#[cfg(unix)]
+impl fmt::Debug for PreExecHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PreExecHook")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
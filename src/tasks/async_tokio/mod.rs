@@ -1,6 +1,24 @@
+pub mod cache;
+pub mod crash_loop;
 pub mod direct;
-pub(crate) mod process_group;
+pub mod health;
+pub mod hook;
+pub mod output_tail;
+pub mod pooled;
+#[cfg(unix)]
+pub(crate) mod pre_exec;
+pub mod process_group;
+pub(crate) mod process_memory;
+pub(crate) mod process_start;
+pub mod process_tree;
+pub mod reaper;
 pub mod spawner;
+pub mod startup_output;
+pub mod stdin;
+pub mod task_group;
+pub(crate) mod temp_dir;
+pub mod transform;
+pub(crate) mod watcher_set;
 
 #[cfg(test)]
 mod unit_tests;
@@ -0,0 +1,147 @@
+//! Retention of the last output lines of a task.
+//!
+//! With `TaskConfig::retain_output_lines` set, the output watchers keep the
+//! most recent lines of both streams in an [`OutputTail`] ring buffer, read
+//! with `TaskSpawner::tail`. A consumer can then show the last lines of a
+//! task, e.g. when it fails, without having kept every `TaskEvent::Output`
+//! since the start.
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use crate::tasks::config::StreamSource;
+
+/// Most bytes of output retained, across both streams
+pub(crate) const RETAINED_OUTPUT_MAX_BYTES: usize = 1024 * 1024;
+
+/// A retained output line
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputLine {
+    /// Source stream (stdout or stderr)
+    pub src: StreamSource,
+    /// The output line (without trailing newline), as sent in `TaskEvent::Output`
+    pub line: String,
+    /// When the line was read
+    pub timestamp: SystemTime,
+}
+
+/// The last output lines of a task, from both streams in the order read
+///
+/// Holds at most `max_lines` lines and `RETAINED_OUTPUT_MAX_BYTES` bytes; the
+/// oldest lines are evicted first. A single line longer than the byte cap is
+/// cut at a character boundary.
+#[derive(Debug, Default)]
+pub(crate) struct OutputTail {
+    lines: VecDeque<OutputLine>,
+    max_lines: usize,
+    bytes: usize,
+}
+
+/// Output tail shared by the output watchers of a task
+pub(crate) type SharedOutputTail = Arc<Mutex<OutputTail>>;
+
+impl OutputTail {
+    /// Creates an empty buffer retaining up to `max_lines` lines
+    pub(crate) fn new(max_lines: usize) -> Self {
+        Self {
+            lines: VecDeque::new(),
+            max_lines,
+            bytes: 0,
+        }
+    }
+
+    /// Retains `line`, evicting the oldest lines to stay within the limits
+    pub(crate) fn push(&mut self, src: &StreamSource, line: &str) {
+        if self.max_lines == 0 {
+            return;
+        }
+        let mut end = line.len().min(RETAINED_OUTPUT_MAX_BYTES);
+        while !line.is_char_boundary(end) {
+            end -= 1;
+        }
+        while self.lines.len() >= self.max_lines || self.bytes + end > RETAINED_OUTPUT_MAX_BYTES {
+            let Some(evicted) = self.lines.pop_front() else {
+                break;
+            };
+            self.bytes -= evicted.line.len();
+        }
+        self.bytes += end;
+        self.lines.push_back(OutputLine {
+            src: src.clone(),
+            line: line[..end].to_string(),
+            timestamp: SystemTime::now(),
+        });
+    }
+
+    /// The last `n` retained lines, oldest first
+    pub(crate) fn last(&self, n: usize) -> Vec<OutputLine> {
+        let skip = self.lines.len().saturating_sub(n);
+        self.lines.iter().skip(skip).cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(tail: &OutputTail) -> Vec<String> {
+        tail.last(usize::MAX)
+            .into_iter()
+            .map(|retained| retained.line)
+            .collect()
+    }
+
+    #[test]
+    fn evicts_oldest_lines_beyond_max_lines() {
+        let mut tail = OutputTail::new(3);
+        for line in ["one", "two", "three", "four", "five"] {
+            tail.push(&StreamSource::Stdout, line);
+        }
+
+        assert_eq!(lines(&tail), ["three", "four", "five"]);
+        assert_eq!(tail.bytes, "threefourfive".len());
+        let last = tail.last(2);
+        assert_eq!(last[0].line, "four");
+        assert_eq!(last[1].line, "five");
+        assert!(last[0].timestamp <= last[1].timestamp);
+    }
+
+    #[test]
+    fn evicts_oldest_lines_beyond_byte_cap() {
+        let mut tail = OutputTail::new(10);
+        let half = "x".repeat(RETAINED_OUTPUT_MAX_BYTES / 2);
+
+        tail.push(&StreamSource::Stdout, &half);
+        tail.push(&StreamSource::Stderr, &half);
+        tail.push(&StreamSource::Stdout, "last");
+
+        let retained = tail.last(10);
+        assert_eq!(retained.len(), 2);
+        assert_eq!(retained[0].src, StreamSource::Stderr);
+        assert_eq!(retained[1].line, "last");
+        assert!(tail.bytes <= RETAINED_OUTPUT_MAX_BYTES);
+    }
+
+    #[test]
+    fn line_longer_than_byte_cap_is_cut_at_char_boundary() {
+        let mut tail = OutputTail::new(10);
+        let line = format!("{}\u{e9}", "x".repeat(RETAINED_OUTPUT_MAX_BYTES - 1));
+
+        tail.push(&StreamSource::Stdout, "before");
+        tail.push(&StreamSource::Stdout, &line);
+
+        assert_eq!(lines(&tail), ["x".repeat(RETAINED_OUTPUT_MAX_BYTES - 1)]);
+    }
+
+    #[test]
+    fn zero_lines_retains_nothing() {
+        let mut tail = OutputTail::new(0);
+
+        tail.push(&StreamSource::Stdout, "line");
+        assert!(tail.last(10).is_empty());
+    }
+}
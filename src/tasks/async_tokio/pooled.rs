@@ -0,0 +1,549 @@
+//! Warm pool of shells for short shell-wrapped commands.
+//!
+//! When every task is `sh -c ...` or `cmd /C ...` and commands finish in
+//! milliseconds, creating the shell process dominates their latency,
+//! especially on Windows. A [`ShellPool`] spawns its shells up front and
+//! keeps them idle with stdin open. [`ShellPool::run`] writes a command to an
+//! idle shell, followed by an echo of a sentinel and the exit code on each
+//! stream, and synthesizes `TaskEvent::Started`, `Output` and `Stopped` from
+//! the output up to the sentinels.
+//!
+//! Pooled commands trade the features of `TaskSpawner` for latency:
+//!
+//! - The environment and working directory are those of the pool, fixed when
+//!   its shells are spawned. With [`PoolShell::Posix`], each command runs in
+//!   a subshell, so `cd` or `export` do not reach later commands; with
+//!   [`PoolShell::Cmd`], `cd` and `set` persist in the shell that ran them.
+//! - Commands read stdin from the null device, and cannot be terminated or
+//!   timed out. Processes a command leaves running in the background keep
+//!   the pipes of the shell open and may write into the output of later
+//!   commands.
+//! - `TaskConfig` options such as `hash_output` do not apply.
+//! - `Started` reports the process ID of the shell, which runs many commands.
+//!
+//! Use `TaskSpawner` for anything else.
+
+use std::{
+    collections::HashMap,
+    io,
+    process::Stdio,
+    sync::{
+        Arc, Mutex, PoisonError,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tokio::{
+    io::AsyncWriteExt,
+    process::{Child, ChildStderr, ChildStdin, ChildStdout, Command},
+    sync::mpsc,
+    time::Instant,
+};
+
+use crate::tasks::{
+    async_tokio::direct::watchers::line_reader::{DEFAULT_CAPACITY, LineReader},
+    config::StreamSource,
+    error::TaskError,
+    event::{TaskEvent, TaskEventStopReason},
+};
+
+/// Number of shells a pool keeps idle by default
+pub const DEFAULT_POOL_SIZE: usize = 4;
+
+/// Kind of shell a [`ShellPool`] runs commands in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoolShell {
+    /// A POSIX shell such as `sh`, `bash` or `dash` (default on Unix)
+    Posix,
+    /// The Windows command interpreter `cmd` (default on Windows)
+    ///
+    /// Commands run in a parenthesized block, so a closing parenthesis in a
+    /// command must be escaped as `^)`.
+    Cmd,
+}
+
+impl Default for PoolShell {
+    fn default() -> Self {
+        if cfg!(windows) {
+            Self::Cmd
+        } else {
+            Self::Posix
+        }
+    }
+}
+
+impl PoolShell {
+    fn default_program(self) -> &'static str {
+        match self {
+            Self::Posix => "sh",
+            Self::Cmd => "cmd",
+        }
+    }
+
+    /// Arguments that make the shell read commands from stdin without echoing them
+    fn args(self) -> &'static [&'static str] {
+        match self {
+            Self::Posix => &[],
+            Self::Cmd => &["/D", "/Q"],
+        }
+    }
+
+    /// A command that does nothing, run to check a new shell works
+    fn noop(self) -> &'static str {
+        match self {
+            Self::Posix => ":",
+            Self::Cmd => "type NUL",
+        }
+    }
+
+    /// Input that runs `command`, then prints `sentinel` with the exit code on
+    /// stdout and `sentinel` alone on stderr
+    fn frame(self, command: &str, sentinel: &str) -> String {
+        match self {
+            Self::Posix => format!(
+                "( eval {} ) </dev/null\nprintf '%s %s\\n' {sentinel} \"$?\"\nprintf '%s\\n' {sentinel} >&2\n",
+                posix_quote(command)
+            ),
+            Self::Cmd => format!(
+                "({command}) <NUL\r\necho {sentinel} %ERRORLEVEL%\r\n1>&2 echo {sentinel}\r\n"
+            ),
+        }
+    }
+}
+
+/// Quotes `value` as a single word for a POSIX shell
+fn posix_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Configuration of a [`ShellPool`]
+///
+/// # Examples
+/// ```rust
+/// use tcrm_task::tasks::async_tokio::pooled::{PoolShell, ShellPoolConfig};
+///
+/// let config = ShellPoolConfig::new()
+///     .size(2)
+///     .shell(PoolShell::Posix)
+///     .program("bash")
+///     .working_dir("/tmp")
+///     .env([("LC_ALL", "C")]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShellPoolConfig {
+    /// Number of shells kept idle (default: [`DEFAULT_POOL_SIZE`])
+    pub size: usize,
+    /// Kind of shell (default: `Cmd` on Windows, `Posix` elsewhere)
+    pub shell: PoolShell,
+    /// Shell executable (default: `sh` or `cmd`, per `shell`)
+    pub program: Option<String>,
+    /// Working directory of the shells
+    pub working_dir: Option<String>,
+    /// Environment variables added to those of the shells
+    pub env: Option<HashMap<String, String>>,
+}
+
+impl Default for ShellPoolConfig {
+    fn default() -> Self {
+        Self {
+            size: DEFAULT_POOL_SIZE,
+            shell: PoolShell::default(),
+            program: None,
+            working_dir: None,
+            env: None,
+        }
+    }
+}
+
+impl ShellPoolConfig {
+    /// Create a configuration with the defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the number of shells kept idle
+    #[must_use]
+    pub fn size(mut self, size: usize) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the kind of shell
+    #[must_use]
+    pub fn shell(mut self, shell: PoolShell) -> Self {
+        self.shell = shell;
+        self
+    }
+
+    /// Set the shell executable
+    #[must_use]
+    pub fn program(mut self, program: impl Into<String>) -> Self {
+        self.program = Some(program.into());
+        self
+    }
+
+    /// Set the working directory of the shells
+    #[must_use]
+    pub fn working_dir(mut self, dir: impl Into<String>) -> Self {
+        self.working_dir = Some(dir.into());
+        self
+    }
+
+    /// Set environment variables of the shells
+    #[must_use]
+    pub fn env<K, V, I>(mut self, env: I) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+        I: IntoIterator<Item = (K, V)>,
+    {
+        self.env = Some(env.into_iter().map(|(k, v)| (k.into(), v.into())).collect());
+        self
+    }
+}
+
+/// A shell of the pool, waiting for commands on stdin
+#[derive(Debug)]
+struct PooledShell {
+    child: Child,
+    pid: u32,
+    stdin: ChildStdin,
+    stdout: LineReader<ChildStdout>,
+    stderr: LineReader<ChildStderr>,
+}
+
+/// Where [`PooledShell::exec`] sends the output of a command
+struct OutputSink<'a> {
+    task_name: &'a Arc<str>,
+    event_tx: &'a mpsc::Sender<TaskEvent>,
+}
+
+impl PooledShell {
+    /// Writes `input` to the shell and reads both streams up to `sentinel`
+    ///
+    /// Returns the exit code printed after the sentinel on stdout. Fails if
+    /// the shell exits before printing both sentinels.
+    async fn exec(
+        &mut self,
+        input: &str,
+        sentinel: &str,
+        sink: Option<OutputSink<'_>>,
+    ) -> io::Result<Option<i32>> {
+        self.stdin.write_all(input.as_bytes()).await?;
+        self.stdin.flush().await?;
+
+        let mut exit_code = None;
+        let (mut stdout_done, mut stderr_done) = (false, false);
+        let mut seq = 0;
+        let mut last_timestamp = UNIX_EPOCH;
+        while !(stdout_done && stderr_done) {
+            let (src, line) = tokio::select! {
+                line = self.stdout.next_line(), if !stdout_done => (StreamSource::Stdout, line?),
+                line = self.stderr.next_line(), if !stderr_done => (StreamSource::Stderr, line?),
+            };
+            let Some(line) = line else {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "shell exited while running the command",
+                ));
+            };
+            // Kept in order if the system clock steps back
+            let timestamp = SystemTime::now().max(last_timestamp);
+            last_timestamp = timestamp;
+            let truncated = line.truncated;
+            let mut line = String::from_utf8_lossy(&line.bytes).into_owned();
+            // Output without a trailing newline ends up before the sentinel
+            if let Some(pos) = line.find(sentinel) {
+                if src == StreamSource::Stdout {
+                    exit_code = line[pos + sentinel.len()..].trim().parse().ok();
+                    stdout_done = true;
+                } else {
+                    stderr_done = true;
+                }
+                line.truncate(pos);
+                if line.is_empty() {
+                    continue;
+                }
+            }
+            if let Some(sink) = &sink {
+                let event = TaskEvent::Output {
+                    task_name: sink.task_name.clone(),
+                    line,
+                    src,
+                    seq,
+                    truncated,
+                    timestamp,
+                };
+                // Keep reading up to the sentinels, so the shell stays usable
+                let _ = sink.event_tx.send(event).await;
+                seq += 1;
+            }
+        }
+        Ok(exit_code)
+    }
+}
+
+/// Shells kept running to run short commands without spawning a process each
+///
+/// See the [module documentation](self) for what pooled commands cannot do.
+/// The pool can be shared between tasks, e.g. in an `Arc`; each
+/// [`run`](Self::run) takes an idle shell, or spawns one if all are busy, and
+/// gives it back once the command is done. Shells are killed when the pool
+/// is dropped.
+///
+/// # Examples
+#[cfg_attr(unix, doc = "```rust")]
+#[cfg_attr(not(unix), doc = "```ignore")]
+/// use tcrm_task::tasks::{
+///     async_tokio::pooled::{ShellPool, ShellPoolConfig},
+///     event::TaskEvent,
+/// };
+/// use tokio::sync::mpsc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let pool = ShellPool::new(ShellPoolConfig::new().size(2)).await?;
+///
+///     let (tx, mut rx) = mpsc::channel(100);
+///     let exit_code = pool.run("greet", "echo hello", tx).await?;
+///     assert_eq!(exit_code, Some(0));
+///
+///     while let Some(event) = rx.recv().await {
+///         if let TaskEvent::Output { line, .. } = event {
+///             assert_eq!(line, "hello");
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ShellPool {
+    config: ShellPoolConfig,
+    idle: Mutex<Vec<PooledShell>>,
+    /// Makes the sentinels of this pool unlikely to appear in output
+    nonce: u64,
+    commands: AtomicU64,
+}
+
+impl ShellPool {
+    /// Spawns the shells of a pool
+    ///
+    /// Each shell runs a command that does nothing before it is added, so a
+    /// pool that is returned has `size` working shells.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TaskError::InvalidConfiguration`] if `size` is 0, and
+    /// [`TaskError::IO`] if a shell cannot be spawned or does not respond.
+    pub async fn new(config: ShellPoolConfig) -> Result<Self, TaskError> {
+        if config.size == 0 {
+            return Err(TaskError::InvalidConfiguration(
+                "Shell pool size must be greater than 0".to_string(),
+            ));
+        }
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos() as u64)
+            ^ u64::from(std::process::id());
+        let pool = Self {
+            idle: Mutex::new(Vec::with_capacity(config.size)),
+            config,
+            nonce,
+            commands: AtomicU64::new(0),
+        };
+        for _ in 0..pool.config.size {
+            let shell = pool.spawn_shell().await?;
+            pool.idle().push(shell);
+        }
+        Ok(pool)
+    }
+
+    /// Runs `command` in a shell of the pool and sends its events to `event_tx`
+    ///
+    /// Sends `TaskEvent::Started` with the process ID of the shell, then each
+    /// output line as `TaskEvent::Output`, numbered by `seq` in the order they
+    /// were read, then `TaskEvent::Stopped` with the exit code and
+    /// `TaskEventStopReason::Finished`. If the shell dies while running the
+    /// command, `Stopped` has no exit code and `TaskEventStopReason::Error`,
+    /// and the shell is replaced on a later run.
+    ///
+    /// Returns once the command is done, even if `event_tx` is closed.
+    ///
+    /// # Arguments
+    ///
+    /// * `task_name` - Name of the task in its events
+    /// * `command` - The command line, in the syntax of the shell
+    /// * `event_tx` - Channel to send the events to
+    ///
+    /// # Returns
+    ///
+    /// The exit code of the command, `None` if the shell died.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TaskError::IO`] if all shells are busy and a new one cannot
+    /// be spawned. No event is sent then.
+    pub async fn run(
+        &self,
+        task_name: &str,
+        command: &str,
+        event_tx: mpsc::Sender<TaskEvent>,
+    ) -> Result<Option<i32>, TaskError> {
+        let mut shell = match self.take_idle() {
+            Some(shell) => shell,
+            None => self.spawn_shell().await?,
+        };
+        let task_name: Arc<str> = task_name.into();
+        let running_at = Instant::now();
+        let started = TaskEvent::Started {
+            task_name: task_name.clone(),
+            process_id: shell.pid,
+            temp_dir: None,
+            working_dir: self.config.working_dir.clone(),
+        };
+        let _ = event_tx.send(started).await;
+
+        let sentinel = self.next_sentinel();
+        let input = self.config.shell.frame(command, &sentinel);
+        let sink = OutputSink {
+            task_name: &task_name,
+            event_tx: &event_tx,
+        };
+        let (exit_code, reason) = match shell.exec(&input, &sentinel, Some(sink)).await {
+            Ok(exit_code) => {
+                self.give_back(shell);
+                (exit_code, TaskEventStopReason::Finished)
+            }
+            Err(error) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(pid = shell.pid, %error, "Pooled shell failed, discarding it");
+                (None, TaskEventStopReason::Error(error.to_string()))
+            }
+        };
+
+        let stopped = TaskEvent::Stopped {
+            task_name,
+            exit_code,
+            signal: None,
+            exit_meaning: None,
+            reason,
+            was_ready: false,
+            duration_ms: u64::try_from(running_at.elapsed().as_millis()).unwrap_or(u64::MAX),
+            expected_duration_ms: None,
+            from_cache: false,
+            stdout_sha256: None,
+            stderr_sha256: None,
+        };
+        let _ = event_tx.send(stopped).await;
+        Ok(exit_code)
+    }
+
+    /// Number of shells waiting for a command
+    pub fn idle_count(&self) -> usize {
+        self.idle().len()
+    }
+
+    fn idle(&self) -> std::sync::MutexGuard<'_, Vec<PooledShell>> {
+        self.idle.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Takes an idle shell that is still running
+    fn take_idle(&self) -> Option<PooledShell> {
+        let mut idle = self.idle();
+        while let Some(mut shell) = idle.pop() {
+            if matches!(shell.child.try_wait(), Ok(None)) {
+                return Some(shell);
+            }
+            #[cfg(feature = "tracing")]
+            tracing::debug!(pid = shell.pid, "Idle pooled shell exited, discarding it");
+        }
+        None
+    }
+
+    /// Keeps `shell` for later runs, unless enough shells are idle
+    fn give_back(&self, shell: PooledShell) {
+        let mut idle = self.idle();
+        if idle.len() < self.config.size {
+            idle.push(shell);
+        }
+    }
+
+    fn next_sentinel(&self) -> String {
+        let command = self.commands.fetch_add(1, Ordering::Relaxed);
+        format!("__tcrm_pool_{:x}_{command}__", self.nonce)
+    }
+
+    async fn spawn_shell(&self) -> Result<PooledShell, TaskError> {
+        let shell = self.config.shell;
+        let program = self
+            .config
+            .program
+            .as_deref()
+            .unwrap_or(shell.default_program());
+        let mut cmd = Command::new(program);
+        cmd.args(shell.args())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        if let Some(dir) = &self.config.working_dir {
+            cmd.current_dir(dir);
+        }
+        if let Some(env) = &self.config.env {
+            cmd.envs(env);
+        }
+
+        let mut child = cmd.spawn().map_err(|e| TaskError::IO(e.to_string()))?;
+        let pid = child.id().unwrap_or_default();
+        let (Some(stdin), Some(stdout), Some(stderr)) =
+            (child.stdin.take(), child.stdout.take(), child.stderr.take())
+        else {
+            return Err(TaskError::IO("Pooled shell has no piped stdio".to_string()));
+        };
+        let mut pooled = PooledShell {
+            child,
+            pid,
+            stdin,
+            stdout: LineReader::with_capacity(stdout, DEFAULT_CAPACITY),
+            stderr: LineReader::with_capacity(stderr, DEFAULT_CAPACITY),
+        };
+
+        // Also skips anything the shell prints on startup, like the banner of cmd
+        let sentinel = self.next_sentinel();
+        pooled
+            .exec(&shell.frame(shell.noop(), &sentinel), &sentinel, None)
+            .await
+            .map_err(|e| TaskError::IO(format!("Pooled shell {program} did not start: {e}")))?;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(pid, program, "Spawned pooled shell");
+        Ok(pooled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn posix_quote_escapes_single_quotes() {
+        assert_eq!(posix_quote("echo hi"), "'echo hi'");
+        assert_eq!(posix_quote("echo 'a b'"), r"'echo '\''a b'\'''");
+        assert_eq!(posix_quote(""), "''");
+    }
+
+    #[test]
+    fn frames_end_with_both_sentinels() {
+        let posix = PoolShell::Posix.frame("exit 3", "__s__");
+        assert_eq!(
+            posix,
+            "( eval 'exit 3' ) </dev/null\nprintf '%s %s\\n' __s__ \"$?\"\nprintf '%s\\n' __s__ >&2\n"
+        );
+
+        let cmd = PoolShell::Cmd.frame("dir", "__s__");
+        assert_eq!(
+            cmd,
+            "(dir) <NUL\r\necho __s__ %ERRORLEVEL%\r\n1>&2 echo __s__\r\n"
+        );
+    }
+}
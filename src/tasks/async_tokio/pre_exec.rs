@@ -0,0 +1,158 @@
+//! Named `pre_exec` steps of the command to spawn (Unix only).
+//!
+//! A `pre_exec` closure failing in the forked child can only hand its errno
+//! back to the parent, through the pipe of the standard library, so the spawn
+//! fails with a bare OS error such as "Operation not permitted". Steps
+//! registered through [`PreExecSteps`] also write their index to a pipe of
+//! their own before failing, which lets the spawner name the failing step in
+//! `TaskError::PreExec`.
+
+use std::{
+    io,
+    os::fd::{AsRawFd, FromRawFd, OwnedFd},
+    sync::Arc,
+};
+
+use tokio::process::Command;
+
+use crate::tasks::error::TaskError;
+
+/// Pipe a failing step writes its index to
+#[derive(Debug)]
+struct FailurePipe {
+    /// Read by the parent, non-blocking
+    read: OwnedFd,
+    /// Written by the child; closed on exec
+    write: OwnedFd,
+}
+
+impl FailurePipe {
+    fn new() -> io::Result<Self> {
+        let mut fds = [0; 2];
+        // SAFETY: `fds` has room for the two descriptors `pipe` returns
+        if unsafe { libc::pipe(fds.as_mut_ptr()) } == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: both descriptors were just created and nothing else owns them
+        let (read, write) = unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+        for fd in [&read, &write] {
+            set_flag(fd, libc::F_GETFD, libc::F_SETFD, libc::FD_CLOEXEC)?;
+        }
+        set_flag(&read, libc::F_GETFL, libc::F_SETFL, libc::O_NONBLOCK)?;
+        Ok(Self { read, write })
+    }
+}
+
+/// Adds `flag` to the descriptor or status flags of `fd`
+fn set_flag(fd: &OwnedFd, get: i32, set: i32, flag: i32) -> io::Result<()> {
+    // SAFETY: `fd` is an open descriptor for as long as it is borrowed
+    unsafe {
+        let flags = libc::fcntl(fd.as_raw_fd(), get);
+        if flags == -1 || libc::fcntl(fd.as_raw_fd(), set, flags | flag) == -1 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+/// The `pre_exec` steps registered on a command, by name
+///
+/// Steps run in the order they were registered, and the first failing one
+/// aborts the spawn.
+#[derive(Debug, Default)]
+pub(crate) struct PreExecSteps {
+    /// Names of the registered steps, by index
+    names: Vec<String>,
+    /// Whether failing steps report their index, see `reporting`
+    report: bool,
+    /// Created with the first step registered while reporting
+    pipe: Option<Arc<FailurePipe>>,
+}
+
+impl PreExecSteps {
+    /// Creates a registry whose failing steps can be named with [`Self::error`]
+    pub(crate) fn reporting() -> Self {
+        Self {
+            report: true,
+            ..Self::default()
+        }
+    }
+
+    /// Registers `step` on `cmd` under `name`
+    ///
+    /// If the pipe failing steps report through cannot be created, the step
+    /// still runs, but its failure is not named.
+    ///
+    /// # Safety
+    ///
+    /// `step` runs in the forked child like a closure passed to
+    /// [`Command::pre_exec`], with the same restrictions.
+    pub(crate) unsafe fn register<F>(&mut self, cmd: &mut Command, name: impl Into<String>, step: F)
+    where
+        F: Fn() -> io::Result<()> + Send + Sync + 'static,
+    {
+        if self.report && self.pipe.is_none() {
+            match FailurePipe::new() {
+                Ok(pipe) => self.pipe = Some(Arc::new(pipe)),
+                Err(_e) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(error = %_e, "Cannot create pipe to report failing pre_exec steps");
+                    self.report = false;
+                }
+            }
+        }
+        let index = u8::try_from(self.names.len()).unwrap_or(u8::MAX);
+        self.names.push(name.into());
+        let pipe = self.pipe.clone();
+        unsafe {
+            cmd.pre_exec(move || {
+                let result = step();
+                if result.is_err()
+                    && let Some(pipe) = &pipe
+                {
+                    // `write` is async-signal-safe; if it fails, the step is just not named
+                    libc::write(pipe.write.as_raw_fd(), (&raw const index).cast(), 1);
+                }
+                result
+            });
+        }
+    }
+
+    /// Discards the failures reported by earlier spawns
+    pub(crate) fn clear(&self) {
+        let _ = self.failed_index();
+    }
+
+    /// Returns the error for a spawn that failed with `error`, if a step failed it
+    ///
+    /// Reads the failures reported since the last call, so each is only
+    /// reported once.
+    pub(crate) fn error(&self, error: &io::Error) -> Option<TaskError> {
+        let step = self.names.get(usize::from(self.failed_index()?))?;
+        let error = match error.raw_os_error().map(nix::errno::Errno::from_raw) {
+            Some(errno) if errno != nix::errno::Errno::UnknownErrno => format!("{errno:?}"),
+            _ => error.to_string(),
+        };
+        Some(TaskError::PreExec {
+            step: step.clone(),
+            error,
+        })
+    }
+
+    /// Drains the pipe, returning the index written last
+    fn failed_index(&self) -> Option<u8> {
+        let pipe = self.pipe.as_ref()?;
+        let mut last = None;
+        let mut buf = [0_u8; 64];
+        loop {
+            // SAFETY: `buf` is valid for writes of its length
+            let read =
+                unsafe { libc::read(pipe.read.as_raw_fd(), buf.as_mut_ptr().cast(), buf.len()) };
+            // Empty (`EAGAIN`) or closed
+            match usize::try_from(read) {
+                Ok(0) | Err(_) => return last,
+                Ok(read) => last = Some(buf[read - 1]),
+            }
+        }
+    }
+}
@@ -1,5 +1,6 @@
 //! Cross-platform process group management for killing entire process trees
-//! and propagating signals like pause/resume.//!
+//! and propagating signals like pause/resume.
+//!
 //! This module provides utilities to manage process groups on Unix systems
 //! and job objects on Windows to ensure that when a parent process is killed,
 //! all its children and grandchildren are also terminated.
@@ -11,6 +12,11 @@ use tokio::{
     sync::Mutex,
 };
 
+#[cfg(unix)]
+use crate::tasks::async_tokio::pre_exec::PreExecSteps;
+use crate::tasks::async_tokio::process_start;
+use crate::tasks::async_tokio::process_tree::GroupMembers;
+
 /// A cross-platform wrapper for managing process groups/jobs.
 ///
 /// On Unix systems, this uses process groups with `setsid()`.
@@ -20,6 +26,22 @@ use tokio::{
 /// - **Unix/Linux**: Full process group support using `setsid()` and `killpg()`
 /// - **Windows**: Full process tree support using Job Objects
 /// - **Other platforms**: No special handling
+///
+/// # Sharing a group
+///
+/// [`id`](Self::id) returns a [`GroupId`] that another `ProcessGroup`, possibly
+/// in another process, can attach to with [`from_existing`](Self::from_existing)
+/// to signal the same processes. Anyone holding the id can kill, pause or
+/// interrupt every process in the group, so only hand it to trusted code:
+///
+/// - **Unix**: the id is the process group ID, which is not a capability. Any
+///   process allowed to signal the group's processes (same user, or root) can
+///   use it. Once every process in the group has exited, the ID can be reused
+///   by an unrelated group, so an attached group must not outlive the one it
+///   was taken from.
+/// - **Windows**: the id owns a duplicate of the Job Object handle with the
+///   same full access, which keeps the job alive until it is closed. Closing the
+///   last handle kills every process left in the job.
 #[derive(Clone, Debug)]
 pub struct ProcessGroup {
     inner: Arc<Mutex<ProcessGroupInner>>,
@@ -29,6 +51,9 @@ pub struct ProcessGroup {
 struct ProcessGroupInner {
     #[cfg(unix)]
     process_group_id: Option<i32>,
+    /// Start time of the group leader, whose process ID is the group ID
+    #[cfg(unix)]
+    leader_start_time: Option<u64>,
     #[cfg(windows)]
     job_handle: Option<SendHandle>,
     #[cfg(not(any(unix, windows)))]
@@ -39,6 +64,85 @@ struct ProcessGroupInner {
 #[derive(Debug)]
 struct SendHandle(windows::Win32::Foundation::HANDLE);
 
+/// Identifier of a process group, to attach another [`ProcessGroup`] to it
+///
+/// The process group ID (pgid) on Unix. With [`ProcessGroup::create_with_command`],
+/// it is the process ID of the process spawned with the command.
+#[cfg(not(windows))]
+pub type GroupId = i32;
+
+/// Identifier of a process group, to attach another [`ProcessGroup`] to it
+///
+/// Owns a handle to the Job Object, closed when this value is dropped.
+/// [`try_clone`](Self::try_clone) duplicates it, and
+/// [`into_raw_handle`](Self::into_raw_handle) hands it to another process,
+/// e.g. through `DuplicateHandle`.
+#[cfg(windows)]
+#[derive(Debug)]
+pub struct GroupId(SendHandle);
+
+#[cfg(windows)]
+impl GroupId {
+    /// Takes ownership of a Job Object handle
+    ///
+    /// # Safety
+    ///
+    /// `handle` must be an open Job Object handle that nothing else closes.
+    pub unsafe fn from_raw_handle(handle: std::os::windows::io::RawHandle) -> Self {
+        Self(SendHandle(windows::Win32::Foundation::HANDLE(handle)))
+    }
+
+    /// Releases ownership of the Job Object handle, which the caller must close
+    #[must_use]
+    pub fn into_raw_handle(self) -> std::os::windows::io::RawHandle {
+        let id = std::mem::ManuallyDrop::new(self);
+        id.0.0.0
+    }
+
+    /// Duplicates the Job Object handle with the same access
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProcessGroupError::AttachFailed`] if the handle cannot be duplicated.
+    pub fn try_clone(&self) -> Result<Self, ProcessGroupError> {
+        duplicate_handle(&self.0).map(Self)
+    }
+}
+
+#[cfg(windows)]
+impl Drop for GroupId {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = windows::Win32::Foundation::CloseHandle(self.0.0);
+        }
+    }
+}
+
+/// Duplicates a handle within the current process, with the same access
+#[cfg(windows)]
+fn duplicate_handle(handle: &SendHandle) -> Result<SendHandle, ProcessGroupError> {
+    use windows::Win32::Foundation::{DUPLICATE_SAME_ACCESS, DuplicateHandle, HANDLE};
+    use windows::Win32::System::Threading::GetCurrentProcess;
+
+    let mut duplicate = HANDLE::default();
+    unsafe {
+        let process = GetCurrentProcess();
+        DuplicateHandle(
+            process,
+            handle.0,
+            process,
+            &mut duplicate,
+            0,
+            false,
+            DUPLICATE_SAME_ACCESS,
+        )
+    }
+    .map_err(|e| {
+        ProcessGroupError::AttachFailed(format!("Failed to duplicate job handle: {}", e))
+    })?;
+    Ok(SendHandle(duplicate))
+}
+
 #[cfg(windows)]
 unsafe impl Send for SendHandle {}
 
@@ -47,23 +151,80 @@ unsafe impl Sync for SendHandle {}
 
 /// Error type for process group operations.
 #[derive(Error, Debug)]
+#[allow(clippy::enum_variant_names)]
+#[cfg_attr(unix, allow(dead_code))]
 pub enum ProcessGroupError {
+    /// The process group/job could not be created
     #[error("Failed to create process group/job: {0}")]
     CreationFailed(String),
+    /// The spawned process could not be added to the group/job
     #[error("Failed to assign process to group/job: {0}")]
     AssignmentFailed(String),
+    /// The processes of the group/job could not be terminated
     #[error("Failed to terminate process group/job: {0}")]
     TerminationFailed(String),
+    /// A signal could not be sent to the group/job
     #[error("Failed to send signal to process group: {0}")]
     SignalFailed(String),
+    /// The group/job to attach to does not exist or cannot be used
+    #[error("Failed to attach to process group/job: {0}")]
+    AttachFailed(String),
 
+    /// Process groups are not supported on this platform
     #[cfg(not(any(unix, windows)))]
     #[error("Unsupported platform: {0}")]
     UnsupportedPlatform(String),
 }
 
+/// Result of sending a signal to a process group/job
+///
+/// Distinguishes a delivered signal from one that found no process left to
+/// receive it, or whose process ID was reused by another process, neither of
+/// which is an error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ControlOutcome {
+    /// The signal reached at least one process in the group
+    pub delivered: bool,
+    /// The group no longer had any process (`ESRCH` on Unix)
+    pub already_exited: bool,
+    /// The process ID now belongs to a process started after the one it
+    /// identified, so no signal was sent
+    ///
+    /// Only detected on Linux and Windows, where the start time of the
+    /// process is captured when it is spawned.
+    pub stale_process: bool,
+}
+
+impl ControlOutcome {
+    /// The signal was delivered
+    pub const DELIVERED: Self = Self {
+        delivered: true,
+        already_exited: false,
+        stale_process: false,
+    };
+    /// Every process in the group had already exited
+    pub const ALREADY_EXITED: Self = Self {
+        delivered: false,
+        already_exited: true,
+        stale_process: false,
+    };
+    /// Process group management is disabled, so there was nothing to signal
+    pub const NO_GROUP: Self = Self {
+        delivered: false,
+        already_exited: false,
+        stale_process: false,
+    };
+    /// The process ID was reused by another process, which was not signaled
+    pub const STALE_PROCESS: Self = Self {
+        delivered: false,
+        already_exited: false,
+        stale_process: true,
+    };
+}
+
 /// Signal types that can be sent to process groups
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(dead_code)]
 pub enum ProcessSignal {
     /// Terminate the process group (SIGKILL on Unix, TerminateJobObject on Windows)
     Terminate,
@@ -77,46 +238,105 @@ pub enum ProcessSignal {
 
 impl ProcessGroup {
     /// Creates a new process group and configures the command to use it.
-    pub fn create_with_command(
+    ///
+    /// On Windows the command is spawned suspended: it only starts running
+    /// once [`assign_child`](Self::assign_child) has put it in the job.
+    pub fn create_with_command(command: Command) -> Result<(Command, Self), ProcessGroupError> {
+        Self::create_with_command_and_name(command, None)
+    }
+
+    /// Creates a new process group, registering its `setsid` as a step in `steps`
+    ///
+    /// Lets the spawner name the step if it fails the spawn.
+    #[cfg(unix)]
+    pub(crate) fn create_with_steps(
+        mut command: Command,
+        steps: &mut PreExecSteps,
+    ) -> Result<(Command, Self), ProcessGroupError> {
+        // Configure the command to create a new session and process group
+        unsafe {
+            steps.register(&mut command, "setsid", || {
+                // Create a new session, making this process the session leader
+                // and creating a new process group
+                if libc::setsid() == -1 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+        let inner = ProcessGroupInner {
+            process_group_id: None,
+            leader_start_time: None,
+        };
+
+        Ok((
+            command,
+            ProcessGroup {
+                inner: Arc::new(Mutex::new(inner)),
+            },
+        ))
+    }
+
+    /// Creates a new process group, naming its Job Object on Windows, and
+    /// configures the command to use it.
+    ///
+    /// Other processes can open a named job with [`open_named`](Self::open_named).
+    /// The name is ignored on Unix. As with
+    /// [`create_with_command`](Self::create_with_command), the command is
+    /// spawned suspended on Windows until [`assign_child`](Self::assign_child).
+    ///
+    /// # Arguments
+    /// * `command` - Command to run in the group
+    /// * `name` - Name of the Job Object, or `None` for an anonymous job
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProcessGroupError::CreationFailed`], with the Windows error
+    /// code, if the job cannot be created, including when a job with `name`
+    /// already exists or the caller may not create objects in its namespace.
+    pub fn create_with_command_and_name(
         #[allow(unused_mut)] mut command: Command,
+        name: Option<&str>,
     ) -> Result<(Command, Self), ProcessGroupError> {
         #[cfg(unix)]
         {
-            // Configure the command to create a new session and process group
-            unsafe {
-                command.pre_exec(|| {
-                    // Create a new session, making this process the session leader
-                    // and creating a new process group
-                    if libc::setsid() == -1 {
-                        return Err(std::io::Error::last_os_error());
-                    }
-                    Ok(())
-                });
-            }
-            let inner = ProcessGroupInner {
-                process_group_id: None,
-            };
-
-            Ok((
-                command,
-                ProcessGroup {
-                    inner: Arc::new(Mutex::new(inner)),
-                },
-            ))
+            let _ = name;
+            Self::create_with_steps(command, &mut PreExecSteps::default())
         }
         #[cfg(windows)]
         {
+            use windows::Win32::Foundation::{CloseHandle, ERROR_ALREADY_EXISTS, GetLastError};
             use windows::Win32::System::JobObjects::JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
             use windows::Win32::System::JobObjects::{
                 CreateJobObjectW, JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
                 JobObjectExtendedLimitInformation, SetInformationJobObject,
             };
-            use windows::core::PCWSTR;
+            use windows::core::{HSTRING, PCWSTR};
 
             // Create a Job Object for the process group
-            let job_handle = unsafe { CreateJobObjectW(None, PCWSTR::null()) }.map_err(|e| {
-                ProcessGroupError::CreationFailed(format!("Failed to create Job Object: {}", e))
+            let job_handle = match name {
+                Some(name) => unsafe { CreateJobObjectW(None, &HSTRING::from(name)) },
+                None => unsafe { CreateJobObjectW(None, PCWSTR::null()) },
+            }
+            .map_err(|e| {
+                ProcessGroupError::CreationFailed(format!(
+                    "Failed to create Job Object: {} (code {:#010x})",
+                    e,
+                    e.code().0
+                ))
             })?;
+            // Creating a job with the name of an existing one opens that job instead
+            if let Some(name) = name
+                && unsafe { GetLastError() } == ERROR_ALREADY_EXISTS
+            {
+                unsafe {
+                    let _ = CloseHandle(job_handle);
+                }
+                return Err(ProcessGroupError::CreationFailed(format!(
+                    "Job Object {:?} already exists (code {})",
+                    name, ERROR_ALREADY_EXISTS.0
+                )));
+            }
 
             // Configure the job to kill all processes when the job handle is closed
             let mut job_info = JOBOBJECT_EXTENDED_LIMIT_INFORMATION::default();
@@ -132,11 +352,19 @@ impl ProcessGroup {
             }
             .map_err(|e| {
                 unsafe {
-                    let _ = windows::Win32::Foundation::CloseHandle(job_handle);
+                    let _ = CloseHandle(job_handle);
                 }
                 ProcessGroupError::CreationFailed(format!("Failed to configure Job Object: {}", e))
             })?;
 
+            // Make the child the root of its own console process group, so
+            // `interrupt_process` can target it with CTRL_BREAK_EVENT, and keep
+            // it from running until `assign_child` has put it in the job
+            command.creation_flags(
+                windows::Win32::System::Threading::CREATE_NEW_PROCESS_GROUP.0
+                    | windows::Win32::System::Threading::CREATE_SUSPENDED.0,
+            );
+
             let inner = ProcessGroupInner {
                 job_handle: Some(SendHandle(job_handle)),
             };
@@ -149,35 +377,262 @@ impl ProcessGroup {
         }
         #[cfg(not(any(unix, windows)))]
         {
+            let _ = name;
             Err(ProcessGroupError::UnsupportedPlatform(
                 "Process group management not available on this platform".to_string(),
             ))
         }
     }
 
-    /// Assigns a spawned child process to this process group/job.
+    /// Checks that process groups/jobs can be created, without spawning anything
+    ///
+    /// On Unix, a process group is created by the child itself and cannot be
+    /// refused. On Windows, an anonymous Job Object is created and closed,
+    /// which fails where this process may not create jobs.
+    pub(crate) fn probe() -> Result<(), ProcessGroupError> {
+        #[cfg(unix)]
+        {
+            Ok(())
+        }
+        #[cfg(windows)]
+        {
+            use windows::Win32::Foundation::CloseHandle;
+            use windows::Win32::System::JobObjects::CreateJobObjectW;
+            use windows::core::PCWSTR;
+
+            let job_handle = unsafe { CreateJobObjectW(None, PCWSTR::null()) }.map_err(|e| {
+                ProcessGroupError::CreationFailed(format!(
+                    "Failed to create Job Object: {} (code {:#010x})",
+                    e,
+                    e.code().0
+                ))
+            })?;
+            unsafe {
+                let _ = CloseHandle(job_handle);
+            }
+            Ok(())
+        }
+        #[cfg(not(any(unix, windows)))]
+        {
+            Err(ProcessGroupError::UnsupportedPlatform(
+                "Process group management not available on this platform".to_string(),
+            ))
+        }
+    }
+
+    /// Attaches to an existing process group/job, to signal its processes
+    ///
+    /// The attached group can send signals like the group `id` was taken
+    /// from, but is not meant to have children assigned. Dropping it leaves
+    /// the group untouched, except on Windows where it closes the job handle it
+    /// owns; see [Sharing a group](Self#sharing-a-group) for the security caveats.
+    ///
+    /// # Arguments
+    /// * `id` - Identifier returned by [`id`](Self::id)
+    ///
+    /// # Errors
     ///
-    /// # Windows Race Condition Warning
-    /// On Windows, there is an unavoidable race condition: if the spawned process creates child processes
-    /// before it is assigned to the job object, those children will not be part of the job and can escape
-    /// containment. This is a limitation of the Windows API. To minimize the risk, assign the process to the job
-    /// immediately after spawning, before it can create children. There is no supported way to make this atomic
-    /// with standard Rust APIs.
+    /// Returns [`ProcessGroupError::AttachFailed`] if, on Unix, `id` is not a
+    /// positive process group ID or no such group can be signaled.
     ///
-    /// After assignment, all future children of the process will be contained in the job, unless the process has
-    /// breakaway privileges (which are not enabled by default in this implementation).
+    /// # Examples
+    /// ```rust,no_run
+    /// use tcrm_task::tasks::async_tokio::process_group::ProcessGroup;
     ///
-    /// For malware analysis or strong containment, be aware of this limitation.
+    /// # async fn monitor(pgid: i32) -> Result<(), Box<dyn std::error::Error>> {
+    /// // Process group ID received from the process that owns the group
+    /// let group = ProcessGroup::from_existing(pgid)?;
+    /// group.terminate_all().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_existing(id: GroupId) -> Result<Self, ProcessGroupError> {
+        #[cfg(unix)]
+        {
+            use nix::sys::signal::killpg;
+            use nix::unistd::Pid;
+
+            // killpg with 0 or a negative ID would target the caller's own group
+            if id <= 0 {
+                return Err(ProcessGroupError::AttachFailed(format!(
+                    "Invalid process group id: {id}"
+                )));
+            }
+            // Signal 0 only checks that the group exists and may be signaled
+            killpg(Pid::from_raw(id), None).map_err(|e| {
+                ProcessGroupError::AttachFailed(format!("Process group {id}: {e}"))
+            })?;
+            let inner = ProcessGroupInner {
+                process_group_id: Some(id),
+                leader_start_time: process_start::start_time(id.cast_unsigned()),
+            };
+            Ok(ProcessGroup {
+                inner: Arc::new(Mutex::new(inner)),
+            })
+        }
+        #[cfg(windows)]
+        {
+            let handle = SendHandle(windows::Win32::Foundation::HANDLE(id.into_raw_handle()));
+            let inner = ProcessGroupInner {
+                job_handle: Some(handle),
+            };
+            Ok(ProcessGroup {
+                inner: Arc::new(Mutex::new(inner)),
+            })
+        }
+        #[cfg(not(any(unix, windows)))]
+        {
+            let _ = id;
+            Err(ProcessGroupError::UnsupportedPlatform(
+                "Process group management not available on this platform".to_string(),
+            ))
+        }
+    }
+
+    /// Opens the named Job Object of a group created by another process
     ///
-    /// See: https://devblogs.microsoft.com/oldnewthing/20130405-00/?p=4743
+    /// The job is opened with the rights to query and terminate its
+    /// processes only, so children cannot be assigned to the returned group,
+    /// and pausing or resuming it fails unless the caller can open the
+    /// processes themselves. Dropping the group closes the handle without
+    /// terminating the job, unless it was the last handle to it.
+    ///
+    /// # Arguments
+    /// * `name` - Name given to [`create_with_command_and_name`](Self::create_with_command_and_name)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProcessGroupError::AttachFailed`], with the Windows error code,
+    /// if no job has this name or the caller may not open it. Named groups do
+    /// not exist on Unix, where this always fails.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tcrm_task::tasks::async_tokio::process_group::ProcessGroup;
+    ///
+    /// # async fn cancel_build() -> Result<(), Box<dyn std::error::Error>> {
+    /// let group = ProcessGroup::open_named("Local\\tcrm-build")?;
+    /// group.terminate_all().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn open_named(name: &str) -> Result<Self, ProcessGroupError> {
+        #[cfg(windows)]
+        {
+            use windows::Win32::System::JobObjects::OpenJobObjectW;
+            use windows::core::HSTRING;
+
+            /// `JOB_OBJECT_QUERY | JOB_OBJECT_TERMINATE`, from `Win32_System_SystemServices`
+            const JOB_OBJECT_QUERY_TERMINATE: u32 = 0x0004 | 0x0008;
+
+            let handle =
+                unsafe { OpenJobObjectW(JOB_OBJECT_QUERY_TERMINATE, false, &HSTRING::from(name)) }
+                    .map_err(|e| {
+                        ProcessGroupError::AttachFailed(format!(
+                            "Failed to open Job Object {:?}: {} (code {:#010x})",
+                            name,
+                            e,
+                            e.code().0
+                        ))
+                    })?;
+            let inner = ProcessGroupInner {
+                job_handle: Some(SendHandle(handle)),
+            };
+            Ok(ProcessGroup {
+                inner: Arc::new(Mutex::new(inner)),
+            })
+        }
+        #[cfg(not(windows))]
+        {
+            Err(ProcessGroupError::AttachFailed(format!(
+                "Named process groups are only supported on Windows: {name:?}"
+            )))
+        }
+    }
+
+    /// Returns the identifier other groups attach to with [`from_existing`](Self::from_existing)
+    ///
+    /// `None` until a child has been assigned on Unix. On Windows the id owns
+    /// a duplicate of the job handle, and is `None` if duplicating it failed.
+    pub async fn id(&self) -> Option<GroupId> {
+        let inner = self.inner.lock().await;
+        #[cfg(unix)]
+        {
+            inner.process_group_id
+        }
+        #[cfg(windows)]
+        {
+            match inner.job_handle.as_ref().map(duplicate_handle) {
+                Some(Ok(handle)) => Some(GroupId(handle)),
+                Some(Err(_e)) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(error = %_e, "Failed to duplicate job handle");
+                    None
+                }
+                None => None,
+            }
+        }
+        #[cfg(not(any(unix, windows)))]
+        {
+            let _ = inner;
+            None
+        }
+    }
+
+    /// Returns the processes of this group/job, for the process tree of a task
+    pub(crate) async fn members(&self) -> GroupMembers {
+        let inner = self.inner.lock().await;
+        #[cfg(unix)]
+        {
+            inner
+                .process_group_id
+                .map(|id| GroupMembers::Group(id.cast_unsigned()))
+                .unwrap_or_default()
+        }
+        #[cfg(windows)]
+        {
+            inner
+                .job_handle
+                .as_ref()
+                .and_then(job_process_ids)
+                .map(GroupMembers::Listed)
+                .unwrap_or_default()
+        }
+        #[cfg(not(any(unix, windows)))]
+        {
+            let _ = inner;
+            GroupMembers::None
+        }
+    }
+
+    /// Assigns a spawned child process to this process group/job.
+    ///
+    /// # Windows
+    /// A command configured by [`create_with_command`](Self::create_with_command)
+    /// is spawned suspended, so it cannot create child processes before it is
+    /// in the job. Once assigned, its threads are resumed; every child it
+    /// creates from then on is contained in the job, unless the process has
+    /// breakaway privileges (which are not enabled by default in this
+    /// implementation).
+    ///
+    /// If assigning or resuming fails, the process is left suspended and the
+    /// caller must kill it.
+    ///
+    /// See: <https://devblogs.microsoft.com/oldnewthing/20130405-00/?p=4743>
     ///
     /// # Arguments
     /// * `child` - The spawned child process to assign
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProcessGroupError::AssignmentFailed`] if the process cannot be
+    /// assigned to the job or, on Windows, resumed.
     pub async fn assign_child(&self, child: &Child) -> Result<(), ProcessGroupError> {
         #[cfg(unix)]
         {
             let mut inner = self.inner.lock().await;
             inner.process_group_id = child.id().map(|id| id as i32);
+            inner.leader_start_time = child.id().and_then(process_start::start_time);
             Ok(())
         }
         #[cfg(windows)]
@@ -222,10 +677,12 @@ impl ProcessGroup {
 
                 result.map_err(|e| {
                     ProcessGroupError::AssignmentFailed(format!(
-                        "Failed to assign process to Job Object: {}",
-                        e
+                        "Failed to assign process to Job Object: {} (code {:#010x})",
+                        e,
+                        e.code().0
                     ))
                 })?;
+                resume_process_threads(pid)?;
             }
             Ok(())
         }
@@ -243,65 +700,38 @@ impl ProcessGroup {
     /// # Arguments
     /// * `signal` - The signal to send to all processes in the group
     ///
+    /// # Returns
+    /// A [`ControlOutcome`] telling whether the signal was delivered or the
+    /// group had already exited.
+    ///
     /// # Platform-specific behavior
     /// - **Unix**: Uses `killpg()` with appropriate signal
     /// - **Windows**: Uses appropriate Windows API calls for job/process manipulation
     /// - **Other platforms**: Returns an error indicating unsupported functionality
-    pub async fn send_signal(&self, signal: ProcessSignal) -> Result<(), ProcessGroupError> {
+    pub async fn send_signal(
+        &self,
+        signal: ProcessSignal,
+    ) -> Result<ControlOutcome, ProcessGroupError> {
         #[cfg(unix)]
         {
             use nix::sys::signal::Signal;
-            use nix::sys::signal::killpg;
-            use nix::unistd::Pid;
 
-            let inner = self.inner.lock().await;
-            if let Some(pgid) = inner.process_group_id {
-                let unix_signal = match signal {
-                    ProcessSignal::Terminate => Signal::SIGKILL,
-                    ProcessSignal::Pause => Signal::SIGSTOP,
-                    ProcessSignal::Resume => Signal::SIGCONT,
-                    ProcessSignal::Interrupt => Signal::SIGINT,
-                };
-
-                match killpg(Pid::from_raw(pgid), unix_signal) {
-                    Ok(()) => {}
-                    Err(nix::errno::Errno::ESRCH) => {
-                        // Process group no longer exists - this is fine, nothing to kill
-                    }
-                    Err(e) => {
-                        return Err(ProcessGroupError::SignalFailed(format!(
-                            "killpg failed: {}",
-                            e
-                        )));
-                    }
-                }
-            }
-            Ok(())
+            let unix_signal = match signal {
+                ProcessSignal::Terminate => Signal::SIGKILL,
+                ProcessSignal::Pause => Signal::SIGSTOP,
+                ProcessSignal::Resume => Signal::SIGCONT,
+                ProcessSignal::Interrupt => Signal::SIGINT,
+            };
+            self.inner.lock().await.killpg(unix_signal)
         }
         #[cfg(windows)]
         {
             match signal {
-                ProcessSignal::Terminate => {
-                    let inner = self.inner.lock().await;
-                    if let Some(SendHandle(job_handle)) = &inner.job_handle {
-                        unsafe {
-                            use windows::Win32::System::JobObjects::TerminateJobObject;
-                            TerminateJobObject(*job_handle, 1).map_err(|e| {
-                                ProcessGroupError::TerminationFailed(format!(
-                                    "Failed to terminate job object: {}",
-                                    e
-                                ))
-                            })
-                        }
-                    } else {
-                        // No job object handle means process group is disabled
-                        // Return Ok() since there's nothing to terminate
-                        Ok(())
-                    }
-                }
+                ProcessSignal::Terminate => self.inner.lock().await.terminate(),
                 ProcessSignal::Pause | ProcessSignal::Resume => {
                     self.suspend_resume_job_processes(signal == ProcessSignal::Pause)
                         .await
+                        .map(|()| ControlOutcome::DELIVERED)
                 }
                 ProcessSignal::Interrupt => {
                     // Send Ctrl+C to all processes in the job
@@ -319,25 +749,43 @@ impl ProcessGroup {
     }
 
     /// Convenience method for terminating the entire process group/job
-    pub async fn terminate_all(&self) -> Result<(), ProcessGroupError> {
+    pub async fn terminate_all(&self) -> Result<ControlOutcome, ProcessGroupError> {
         self.send_signal(ProcessSignal::Terminate).await
     }
 
+    /// Terminates the entire process group/job without waiting for the lock
+    ///
+    /// For cleanup where no runtime may be available, such as `Drop`.
+    /// Returns `None` if another task holds the group.
+    pub(crate) fn try_terminate_all(&self) -> Option<Result<ControlOutcome, ProcessGroupError>> {
+        let inner = self.inner.try_lock().ok()?;
+        Some(inner.terminate())
+    }
+
     /// Convenience method for pausing the entire process group/job
-    pub async fn pause_all(&self) -> Result<(), ProcessGroupError> {
+    #[allow(dead_code)]
+    pub async fn pause_all(&self) -> Result<ControlOutcome, ProcessGroupError> {
         self.send_signal(ProcessSignal::Pause).await
     }
 
     /// Convenience method for resuming the entire process group/job
-    pub async fn resume_all(&self) -> Result<(), ProcessGroupError> {
+    #[allow(dead_code)]
+    pub async fn resume_all(&self) -> Result<ControlOutcome, ProcessGroupError> {
         self.send_signal(ProcessSignal::Resume).await
     }
 
     /// Convenience method for interrupting the entire process group/job
-    pub async fn interrupt_all(&self) -> Result<(), ProcessGroupError> {
+    #[allow(dead_code)]
+    pub async fn interrupt_all(&self) -> Result<ControlOutcome, ProcessGroupError> {
         self.send_signal(ProcessSignal::Interrupt).await
     }
 
+    /// Replaces the start time the group leader is checked against
+    #[cfg(all(test, unix))]
+    pub(crate) async fn set_leader_start_time(&self, start_time: Option<u64>) {
+        self.inner.lock().await.leader_start_time = start_time;
+    }
+
     #[cfg(windows)]
     async fn suspend_resume_job_processes(&self, suspend: bool) -> Result<(), ProcessGroupError> {
         use windows::Win32::Foundation::{CloseHandle, GetLastError, HANDLE};
@@ -471,7 +919,7 @@ impl ProcessGroup {
     }
 
     #[cfg(windows)]
-    async fn send_ctrl_c_to_job(&self) -> Result<(), ProcessGroupError> {
+    async fn send_ctrl_c_to_job(&self) -> Result<ControlOutcome, ProcessGroupError> {
         use windows::Win32::System::Console::{CTRL_C_EVENT, GenerateConsoleCtrlEvent};
 
         let inner = self.inner.lock().await;
@@ -479,13 +927,352 @@ impl ProcessGroup {
             unsafe {
                 // Send Ctrl+C to all processes in the console session
                 // Note: This affects all processes sharing the same console
-                GenerateConsoleCtrlEvent(CTRL_C_EVENT, 0).map_err(|e| {
-                    ProcessGroupError::SignalFailed(format!("Failed to send Ctrl+C: {}", e))
-                })
+                GenerateConsoleCtrlEvent(CTRL_C_EVENT, 0)
+                    .map(|()| ControlOutcome::DELIVERED)
+                    .map_err(|e| {
+                        ProcessGroupError::SignalFailed(format!("Failed to send Ctrl+C: {}", e))
+                    })
             }
         } else {
             // No job handle - process group is disabled, which is fine
-            Ok(())
+            Ok(ControlOutcome::NO_GROUP)
+        }
+    }
+}
+
+/// Process IDs of the processes in a job, up to 1024 of them
+#[cfg(windows)]
+fn job_process_ids(job: &SendHandle) -> Option<std::collections::HashSet<u32>> {
+    use windows::Win32::System::JobObjects::{
+        JOBOBJECT_BASIC_PROCESS_ID_LIST, JobObjectBasicProcessIdList, QueryInformationJobObject,
+    };
+
+    const CAPACITY: usize = 1024;
+    // The list starts with two `u32` counts, which take the room of one `usize`
+    let mut buf = vec![0_usize; CAPACITY + 1];
+    let size = u32::try_from(std::mem::size_of_val(buf.as_slice())).ok()?;
+    unsafe {
+        QueryInformationJobObject(
+            Some(job.0),
+            JobObjectBasicProcessIdList,
+            buf.as_mut_ptr().cast(),
+            size,
+            None,
+        )
+    }
+    .ok()?;
+    // SAFETY: the buffer is aligned for the list and was filled by the call
+    let list = unsafe { &*buf.as_ptr().cast::<JOBOBJECT_BASIC_PROCESS_ID_LIST>() };
+    let count = usize::try_from(list.NumberOfProcessIdsInList)
+        .unwrap_or(CAPACITY)
+        .min(CAPACITY);
+    Some(
+        buf[1..=count]
+            .iter()
+            .filter_map(|pid| u32::try_from(*pid).ok())
+            .collect(),
+    )
+}
+
+/// Resumes the threads of a process spawned with `CREATE_SUSPENDED`
+///
+/// A suspended process only has its main thread, which tokio does not expose,
+/// so its threads are found with a thread snapshot. Resuming a thread that is
+/// not suspended leaves it running, so this is safe to call on a process that
+/// was not spawned suspended.
+#[cfg(windows)]
+fn resume_process_threads(pid: u32) -> Result<(), ProcessGroupError> {
+    use windows::Win32::Foundation::{CloseHandle, ERROR_NO_MORE_FILES, GetLastError};
+    use windows::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, TH32CS_SNAPTHREAD, THREADENTRY32, Thread32First, Thread32Next,
+    };
+    use windows::Win32::System::Threading::{OpenThread, ResumeThread, THREAD_SUSPEND_RESUME};
+
+    let mut thread_ids = Vec::new();
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0).map_err(|e| {
+            ProcessGroupError::AssignmentFailed(format!("Failed to create thread snapshot: {}", e))
+        })?;
+
+        let mut thread_entry = THREADENTRY32 {
+            dwSize: std::mem::size_of::<THREADENTRY32>() as u32,
+            ..Default::default()
+        };
+
+        if Thread32First(snapshot, &mut thread_entry).is_ok() {
+            loop {
+                if thread_entry.th32OwnerProcessID == pid {
+                    thread_ids.push(thread_entry.th32ThreadID);
+                }
+                if Thread32Next(snapshot, &mut thread_entry).is_err() {
+                    let err = GetLastError();
+                    if err == ERROR_NO_MORE_FILES {
+                        break;
+                    }
+                    CloseHandle(snapshot).ok();
+                    return Err(ProcessGroupError::AssignmentFailed(format!(
+                        "Thread32Next failed: error {}",
+                        err.0
+                    )));
+                }
+            }
+        }
+        CloseHandle(snapshot).ok();
+    }
+
+    if thread_ids.is_empty() {
+        return Err(ProcessGroupError::AssignmentFailed(format!(
+            "No threads found to resume for process {}",
+            pid
+        )));
+    }
+    for thread_id in thread_ids {
+        unsafe {
+            let handle = OpenThread(THREAD_SUSPEND_RESUME, false, thread_id).map_err(|e| {
+                ProcessGroupError::AssignmentFailed(format!(
+                    "Failed to open thread {}: {}",
+                    thread_id, e
+                ))
+            })?;
+            let result = ResumeThread(handle);
+            if result == u32::MAX {
+                let err = GetLastError();
+                CloseHandle(handle).ok();
+                return Err(ProcessGroupError::AssignmentFailed(format!(
+                    "Failed to resume thread {}: error {}",
+                    thread_id, err.0
+                )));
+            }
+            CloseHandle(handle).ok();
+        }
+    }
+    Ok(())
+}
+
+/// Sends an interrupt (Ctrl+C) to a single process or to its process group
+///
+/// Unlike [`ProcessGroup::send_signal`], this only needs the process ID, so it
+/// can be used while the [`ProcessGroup`] is owned by the wait watcher.
+/// Nothing is sent if `pid` no longer has `start_time`, captured when the
+/// process was spawned, which returns [`ControlOutcome::STALE_PROCESS`].
+///
+/// # Platform-specific behavior
+/// - **Unix**: Sends `SIGINT` with `killpg()` if `group` is set, since the
+///   child is a session leader, otherwise with `kill()`
+/// - **Windows**: Sends `CTRL_BREAK_EVENT` to the console process group rooted
+///   at `pid`. That group only exists if the child was created by
+///   [`ProcessGroup::create_with_command`], so `group` must be set; processes
+///   without a console, or attached to a different console, do not receive it
+/// - **Other platforms**: Returns an error indicating unsupported functionality
+pub(crate) fn interrupt_process(
+    pid: u32,
+    start_time: Option<u64>,
+    group: bool,
+) -> Result<ControlOutcome, ProcessGroupError> {
+    if process_start::is_stale(pid, start_time) {
+        return Ok(ControlOutcome::STALE_PROCESS);
+    }
+    #[cfg(unix)]
+    {
+        signal_process(pid, group, nix::sys::signal::Signal::SIGINT)
+    }
+    #[cfg(windows)]
+    {
+        use windows::Win32::System::Console::{CTRL_BREAK_EVENT, GenerateConsoleCtrlEvent};
+
+        if !group {
+            return Err(ProcessGroupError::SignalFailed(
+                "Interrupting a process requires process group management on Windows"
+                    .to_string(),
+            ));
+        }
+        // CTRL_C_EVENT can only be broadcast to the whole console (group 0),
+        // which would interrupt this process as well
+        unsafe { GenerateConsoleCtrlEvent(CTRL_BREAK_EVENT, pid) }
+            .map(|()| ControlOutcome::DELIVERED)
+            .map_err(|e| {
+                ProcessGroupError::SignalFailed(format!("Failed to send Ctrl+Break: {}", e))
+            })
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = (pid, group);
+        Err(ProcessGroupError::UnsupportedPlatform(
+            "Process interrupts not available on this platform".to_string(),
+        ))
+    }
+}
+
+/// Asks a process, or its whole process group, to shut down
+///
+/// Sent before killing a task configured with a graceful shutdown period.
+/// Nothing is sent if `pid` no longer has `start_time`, which returns
+/// [`ControlOutcome::STALE_PROCESS`].
+///
+/// # Platform-specific behavior
+/// - **Unix**: Sends `SIGTERM` with `killpg()` if `group` is set, otherwise
+///   with `kill()`
+/// - **Windows**: Sends `CTRL_BREAK_EVENT` like [`interrupt_process`], so
+///   `group` must be set
+/// - **Other platforms**: Returns an error indicating unsupported functionality
+pub(crate) fn request_shutdown(
+    pid: u32,
+    start_time: Option<u64>,
+    group: bool,
+) -> Result<ControlOutcome, ProcessGroupError> {
+    #[cfg(unix)]
+    {
+        if process_start::is_stale(pid, start_time) {
+            return Ok(ControlOutcome::STALE_PROCESS);
+        }
+        signal_process(pid, group, nix::sys::signal::Signal::SIGTERM)
+    }
+    #[cfg(not(unix))]
+    {
+        interrupt_process(pid, start_time, group)
+    }
+}
+
+/// Sends `signal` to a process with `kill()`, or to its group with `killpg()`
+#[cfg(unix)]
+fn signal_process(
+    pid: u32,
+    group: bool,
+    signal: nix::sys::signal::Signal,
+) -> Result<ControlOutcome, ProcessGroupError> {
+    use nix::sys::signal::{kill, killpg};
+    use nix::unistd::Pid;
+
+    let raw = i32::try_from(pid)
+        .map_err(|e| ProcessGroupError::SignalFailed(format!("Invalid process ID: {}", e)))?;
+    let result = if group {
+        killpg(Pid::from_raw(raw), signal)
+    } else {
+        kill(Pid::from_raw(raw), signal)
+    };
+    match result {
+        Ok(()) => Ok(ControlOutcome::DELIVERED),
+        Err(nix::errno::Errno::ESRCH) => Ok(ControlOutcome::ALREADY_EXITED),
+        Err(e) => Err(ProcessGroupError::SignalFailed(format!(
+            "Failed to send {}: {}",
+            signal.as_str(),
+            e
+        ))),
+    }
+}
+
+/// Suspends or resumes a single process
+///
+/// Used instead of [`ProcessGroup::send_signal`] when process group management
+/// is disabled. Nothing is sent if `pid` no longer has `start_time`, captured
+/// when the process was spawned, which returns [`ControlOutcome::STALE_PROCESS`].
+///
+/// # Platform-specific behavior
+/// - **Unix**: Sends `SIGSTOP` to suspend or `SIGCONT` to resume with `kill()`
+/// - **Windows**: Returns an error; processes are only suspended through
+///   their job object, which requires process group management
+/// - **Other platforms**: Returns an error indicating unsupported functionality
+pub(crate) fn suspend_process(
+    pid: u32,
+    start_time: Option<u64>,
+    suspend: bool,
+) -> Result<ControlOutcome, ProcessGroupError> {
+    if process_start::is_stale(pid, start_time) {
+        return Ok(ControlOutcome::STALE_PROCESS);
+    }
+    #[cfg(unix)]
+    {
+        use nix::sys::signal::{Signal, kill};
+        use nix::unistd::Pid;
+
+        let raw = i32::try_from(pid)
+            .map_err(|e| ProcessGroupError::SignalFailed(format!("Invalid process ID: {}", e)))?;
+        let signal = if suspend {
+            Signal::SIGSTOP
+        } else {
+            Signal::SIGCONT
+        };
+        match kill(Pid::from_raw(raw), signal) {
+            Ok(()) => Ok(ControlOutcome::DELIVERED),
+            Err(nix::errno::Errno::ESRCH) => Ok(ControlOutcome::ALREADY_EXITED),
+            Err(e) => Err(ProcessGroupError::SignalFailed(format!(
+                "Failed to send {}: {}",
+                signal, e
+            ))),
+        }
+    }
+    #[cfg(windows)]
+    {
+        let _ = suspend;
+        Err(ProcessGroupError::SignalFailed(
+            "Suspending a process requires process group management on Windows".to_string(),
+        ))
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = suspend;
+        Err(ProcessGroupError::UnsupportedPlatform(
+            "Process suspension not available on this platform".to_string(),
+        ))
+    }
+}
+
+impl ProcessGroupInner {
+    /// Sends `signal` to the process group, if a child has been assigned
+    #[cfg(unix)]
+    fn killpg(&self, signal: nix::sys::signal::Signal) -> Result<ControlOutcome, ProcessGroupError> {
+        use nix::sys::signal::killpg;
+        use nix::unistd::Pid;
+
+        let Some(pgid) = self.process_group_id else {
+            return Ok(ControlOutcome::NO_GROUP);
+        };
+        // Once the leader and every other member exited, the group ID can be
+        // reused as the process ID, and group ID, of an unrelated process
+        if process_start::is_stale(pgid.cast_unsigned(), self.leader_start_time) {
+            return Ok(ControlOutcome::STALE_PROCESS);
+        }
+        match killpg(Pid::from_raw(pgid), signal) {
+            Ok(()) => Ok(ControlOutcome::DELIVERED),
+            // Process group no longer exists - nothing left to signal
+            Err(nix::errno::Errno::ESRCH) => Ok(ControlOutcome::ALREADY_EXITED),
+            Err(e) => Err(ProcessGroupError::SignalFailed(format!(
+                "killpg failed: {}",
+                e
+            ))),
+        }
+    }
+
+    /// Kills every process in the group/job
+    fn terminate(&self) -> Result<ControlOutcome, ProcessGroupError> {
+        #[cfg(unix)]
+        {
+            self.killpg(nix::sys::signal::Signal::SIGKILL)
+        }
+        #[cfg(windows)]
+        {
+            if let Some(SendHandle(job_handle)) = &self.job_handle {
+                unsafe {
+                    use windows::Win32::System::JobObjects::TerminateJobObject;
+                    TerminateJobObject(*job_handle, 1)
+                        .map(|()| ControlOutcome::DELIVERED)
+                        .map_err(|e| {
+                            ProcessGroupError::TerminationFailed(format!(
+                                "Failed to terminate job object: {}",
+                                e
+                            ))
+                        })
+                }
+            } else {
+                // No job object handle means process group is disabled
+                Ok(ControlOutcome::NO_GROUP)
+            }
+        }
+        #[cfg(not(any(unix, windows)))]
+        {
+            Err(ProcessGroupError::UnsupportedPlatform(
+                "Process signal sending not available on this platform".to_string(),
+            ))
         }
     }
 }
@@ -638,7 +1425,7 @@ impl Drop for ProcessGroupInner {
 //                         // Terminate after seeing some output
 //                         let _ = spawner
 //                             .send_terminate_signal(
-//                                 crate::tasks::event::TaskTerminateReason::UserRequested,
+//                                 crate::tasks::event::TaskTerminateReason::UserRequested { message: None },
 //                             )
 //                             .await;
 //                     }
@@ -796,7 +1583,7 @@ impl Drop for ProcessGroupInner {
 
 //         // Manually terminate the task (this should kill the process tree)
 //         let terminate_result = spawner
-//             .send_terminate_signal(crate::tasks::event::TaskTerminateReason::UserRequested)
+//             .send_terminate_signal(crate::tasks::event::TaskTerminateReason::UserRequested { message: None })
 //             .await;
 //         assert!(
 //             terminate_result.is_ok(),
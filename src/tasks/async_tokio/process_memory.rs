@@ -0,0 +1,155 @@
+//! Resident memory of running processes, sampled for the memory watermark.
+//!
+//! A [`RssSampler`] is created once per task and read on every sample, so it
+//! keeps what it can reuse between samples: the path of the process and the
+//! buffer its statistics are read into.
+//!
+//! - **Linux**: the resident set size from `/proc/<pid>/stat` (field 24, in
+//!   pages). For a process group, the sizes of all processes whose `pgrp` is
+//!   the group ID are summed, which means scanning `/proc`.
+//! - **Windows**: the working set size from `GetProcessMemoryInfo`, for the
+//!   child alone.
+//! - **Other platforms**: no sample is available.
+
+/// Reads the resident memory of a process, or of the process group it leads
+#[derive(Debug)]
+pub(crate) struct RssSampler {
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    pid: u32,
+    /// Process group whose members are summed instead of the process alone
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    group: Option<u32>,
+    /// Path of the stat file being read, reused between samples
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    path: String,
+    /// Contents of the stat file being read, reused between samples
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    buf: String,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    page_size: u64,
+}
+
+impl RssSampler {
+    /// Samples `pid`, or every member of the process group `group` if set
+    ///
+    /// Only Linux samples process groups; elsewhere `group` is ignored.
+    pub(crate) fn new(pid: u32, group: Option<u32>) -> Self {
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        let _ = group;
+        Self {
+            #[cfg(not(any(target_os = "linux", target_os = "android")))]
+            pid,
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            group,
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            path: format!("/proc/{pid}/stat"),
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            buf: String::with_capacity(512),
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            page_size: page_size(),
+        }
+    }
+
+    /// Resident memory in bytes, `None` if it cannot be read
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub(crate) fn sample(&mut self) -> Option<u64> {
+        use std::fmt::Write;
+
+        let Some(group) = self.group else {
+            let (_, pages) = read_stat(&self.path, &mut self.buf)?;
+            return Some(pages * self.page_size);
+        };
+
+        let mut pages = 0;
+        let mut found = false;
+        for entry in std::fs::read_dir("/proc").ok()?.flatten() {
+            let name = entry.file_name();
+            let Some(pid) = name
+                .to_str()
+                .filter(|name| name.bytes().all(|b| b.is_ascii_digit()))
+            else {
+                continue;
+            };
+            self.path.clear();
+            let _ = write!(self.path, "/proc/{pid}/stat");
+            // Processes may exit while the group is scanned
+            if let Some((pgrp, rss)) = read_stat(&self.path, &mut self.buf)
+                && pgrp == group
+            {
+                pages += rss;
+                found = true;
+            }
+        }
+        found.then_some(pages * self.page_size)
+    }
+
+    /// Resident memory in bytes, `None` if it cannot be read
+    #[cfg(windows)]
+    pub(crate) fn sample(&mut self) -> Option<u64> {
+        use windows::Win32::Foundation::CloseHandle;
+        use windows::Win32::System::ProcessStatus::{
+            GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS,
+        };
+        use windows::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+
+        let handle =
+            unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, self.pid) }.ok()?;
+        let mut counters = PROCESS_MEMORY_COUNTERS::default();
+        let size = u32::try_from(std::mem::size_of::<PROCESS_MEMORY_COUNTERS>()).ok()?;
+        let result = unsafe { GetProcessMemoryInfo(handle, &mut counters, size) };
+        unsafe {
+            let _ = CloseHandle(handle);
+        }
+        result.ok()?;
+        u64::try_from(counters.WorkingSetSize).ok()
+    }
+
+    /// Resident memory in bytes, `None` if it cannot be read
+    #[cfg(not(any(target_os = "linux", target_os = "android", windows)))]
+    pub(crate) fn sample(&mut self) -> Option<u64> {
+        let _ = self.pid;
+        None
+    }
+}
+
+/// Size of a memory page, for the resident set size reported in pages
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn page_size() -> u64 {
+    let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    u64::try_from(size).unwrap_or(4096)
+}
+
+/// Process group and resident set size in pages from the stat file at `path`
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn read_stat(path: &str, buf: &mut String) -> Option<(u32, u64)> {
+    use std::io::Read;
+
+    buf.clear();
+    std::fs::File::open(path).ok()?.read_to_string(buf).ok()?;
+    let (_, mut fields) = super::process_start::proc_stat(buf)?;
+    let pgrp = fields.nth(2)?.parse().ok()?;
+    let rss = fields.nth(18)?.parse().ok()?;
+    Some((pgrp, rss))
+}
+
+#[cfg(all(test, any(target_os = "linux", target_os = "android")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_own_process_and_group() {
+        let pid = std::process::id();
+        let own = RssSampler::new(pid, None).sample().unwrap();
+        assert!(own > 0);
+
+        let pgrp = unsafe { libc::getpgrp() }.cast_unsigned();
+        let group = RssSampler::new(pid, Some(pgrp)).sample().unwrap();
+        assert!(group >= own / 2, "group {group} < own {own} / 2");
+    }
+
+    #[test]
+    fn missing_process_has_no_sample() {
+        assert_eq!(RssSampler::new(u32::MAX, None).sample(), None);
+        assert_eq!(RssSampler::new(u32::MAX, Some(u32::MAX)).sample(), None);
+    }
+}
@@ -0,0 +1,108 @@
+//! Start times of processes, to tell a process from a later one reusing its ID.
+//!
+//! Once a child has exited and been reaped, the OS may hand its process ID to
+//! an unrelated process. The start time read right after spawning is kept
+//! with the ID, and actions taken by ID first check that the process holding
+//! it still has that start time.
+//!
+//! Start times are opaque values, only compared for equality: clock ticks
+//! since boot on Linux (`/proc/<pid>/stat` field 22) and the creation time
+//! from `GetProcessTimes` on Windows. Other platforms have no start time, so
+//! nothing is checked there.
+//!
+//! `proc_stat` splits `/proc/<pid>/stat` for the other readers of it too.
+
+/// Command name and the fields after it from the contents of `/proc/<pid>/stat`
+///
+/// The command name in field 2 may contain spaces and parentheses, so it ends
+/// at the last `)`. The fields iterator starts with field 3, the state.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn proc_stat(stat: &str) -> Option<(&str, std::str::SplitWhitespace<'_>)> {
+    let (head, fields) = stat.rsplit_once(')')?;
+    let (_, name) = head.split_once('(')?;
+    Some((name, fields.split_whitespace()))
+}
+
+/// Start time of the process `pid`, if it exists and the platform reports one
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn start_time(pid: u32) -> Option<u64> {
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    let (_, mut fields) = proc_stat(&stat)?;
+    fields.nth(19)?.parse().ok()
+}
+
+/// Start time of the process `pid`, if it exists and the platform reports one
+#[cfg(windows)]
+pub(crate) fn start_time(pid: u32) -> Option<u64> {
+    use windows::Win32::Foundation::{CloseHandle, FILETIME};
+    use windows::Win32::System::Threading::{
+        GetProcessTimes, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    let handle = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) }.ok()?;
+    let mut creation = FILETIME::default();
+    let mut exit = FILETIME::default();
+    let mut kernel = FILETIME::default();
+    let mut user = FILETIME::default();
+    let result =
+        unsafe { GetProcessTimes(handle, &mut creation, &mut exit, &mut kernel, &mut user) };
+    unsafe {
+        let _ = CloseHandle(handle);
+    }
+    result.ok()?;
+    Some((u64::from(creation.dwHighDateTime) << 32) | u64::from(creation.dwLowDateTime))
+}
+
+/// Start time of the process `pid`, if it exists and the platform reports one
+#[cfg(not(any(target_os = "linux", target_os = "android", windows)))]
+pub(crate) fn start_time(_pid: u32) -> Option<u64> {
+    None
+}
+
+/// Whether `pid` now belongs to a different process than the one started at `expected`
+///
+/// `false` if no start time was captured or none can be read now, e.g.
+/// because the process is gone; signaling it then reports that it exited.
+pub(crate) fn is_stale(pid: u32, expected: Option<u64>) -> bool {
+    match (expected, start_time(pid)) {
+        (Some(expected), Some(current)) => expected != current,
+        _ => false,
+    }
+}
+
+#[cfg(all(test, any(target_os = "linux", target_os = "android")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stat_fields_start_after_the_last_parenthesis() {
+        let stat = "42 (a (b) c) S 1 42 42 0";
+
+        let (name, fields) = proc_stat(stat).unwrap();
+
+        assert_eq!(name, "a (b) c");
+        assert_eq!(fields.collect::<Vec<_>>(), ["S", "1", "42", "42", "0"]);
+    }
+
+    #[test]
+    fn start_time_identifies_running_process() {
+        let pid = std::process::id();
+        let own = start_time(pid);
+
+        assert!(own.is_some());
+        assert_eq!(start_time(pid), own);
+        assert!(!is_stale(pid, own));
+        assert!(!is_stale(pid, None));
+    }
+
+    #[test]
+    fn different_start_time_is_stale() {
+        let pid = std::process::id();
+        let own = start_time(pid).unwrap();
+
+        assert!(is_stale(pid, Some(own + 1)));
+        // A missing process is reported by the signal itself
+        assert_eq!(start_time(u32::MAX), None);
+        assert!(!is_stale(u32::MAX, Some(own)));
+    }
+}
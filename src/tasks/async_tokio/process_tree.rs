@@ -0,0 +1,423 @@
+//! Snapshot of the processes of a task, for diagnostics such as bug reports.
+//!
+//! The tree holds the task process and its descendants, found by walking the
+//! parent process IDs of every process on the system. With process group
+//! management, members of the group are included too, so processes that were
+//! reparented after their parent exited are still listed, as extra roots.
+//!
+//! - **Linux**: `/proc/<pid>/stat` of every process, which gives the name,
+//!   state, parent, process group and start time. Group members are the
+//!   processes whose `pgrp` is the group ID.
+//! - **Windows**: a Toolhelp32 process snapshot for names and parents, and
+//!   `GetProcessTimes` for start times. Group members are the processes of the
+//!   Job Object. No state is reported.
+//! - **Other platforms**: no process can be listed, so the tree is empty.
+
+use std::{
+    collections::{HashMap, HashSet},
+    time::SystemTime,
+};
+
+/// A process in the tree of a task, see `TaskSpawner::process_tree`
+///
+/// # Examples
+/// ```rust
+/// use tcrm_task::tasks::async_tokio::process_tree::ProcessNode;
+///
+/// let tree = ProcessNode {
+///     pid: 100,
+///     parent_pid: Some(1),
+///     name: "sh".to_string(),
+///     state: Some('S'),
+///     started_at: None,
+///     children: vec![ProcessNode {
+///         pid: 101,
+///         parent_pid: Some(100),
+///         name: "sleep".to_string(),
+///         state: Some('S'),
+///         started_at: None,
+///         children: vec![],
+///     }],
+/// };
+///
+/// let pids: Vec<u32> = tree.iter().map(|node| node.pid).collect();
+/// assert_eq!(pids, [100, 101]);
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessNode {
+    /// Process ID
+    pub pid: u32,
+    /// Process ID of the parent, which may be outside the tree
+    pub parent_pid: Option<u32>,
+    /// Name of the executable, truncated to 15 bytes on Linux
+    pub name: String,
+    /// State as shown by `ps`, e.g. `R` (running), `S` (sleeping), `D`
+    /// (uninterruptible wait), `T` (stopped) or `Z` (zombie); `None` on Windows
+    pub state: Option<char>,
+    /// When the process started, if known
+    pub started_at: Option<SystemTime>,
+    /// Processes started by this one, by process ID
+    pub children: Vec<ProcessNode>,
+}
+
+impl ProcessNode {
+    /// Iterates over this process and its descendants, parents before children
+    pub fn iter(&self) -> impl Iterator<Item = &ProcessNode> {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.children.iter().rev());
+            Some(node)
+        })
+    }
+}
+
+/// Processes listed in the tree whether or not they descend from the task process
+#[derive(Debug, Default)]
+pub(crate) enum GroupMembers {
+    /// Only descendants of the task process are listed
+    #[default]
+    None,
+    /// Processes of the Unix process group with this ID
+    #[cfg(unix)]
+    Group(u32),
+    /// Processes of the Windows Job Object, by process ID
+    #[cfg(windows)]
+    Listed(HashSet<u32>),
+}
+
+impl GroupMembers {
+    fn contains(&self, _entry: &ProcessEntry) -> bool {
+        match self {
+            GroupMembers::None => false,
+            #[cfg(unix)]
+            GroupMembers::Group(group) => _entry.group == Some(*group),
+            #[cfg(windows)]
+            GroupMembers::Listed(pids) => pids.contains(&_entry.pid),
+        }
+    }
+}
+
+/// A process of the system, before it is placed in a tree
+#[derive(Debug)]
+struct ProcessEntry {
+    pid: u32,
+    parent_pid: Option<u32>,
+    /// Process group ID
+    #[cfg(unix)]
+    group: Option<u32>,
+    name: String,
+    state: Option<char>,
+    started_at: Option<SystemTime>,
+}
+
+/// Takes a snapshot of the tree of `root` and of the members of `group`
+///
+/// `root` is `None` if the task process is gone or its ID was reused; the
+/// group members are then listed alone.
+pub(crate) fn snapshot(root: Option<u32>, group: &GroupMembers) -> Vec<ProcessNode> {
+    build_tree(list_processes(), root, group)
+}
+
+/// Arranges the selected processes of `entries` into trees
+///
+/// Returns the tree of `root` first, then those of group members whose parent
+/// was not selected, by process ID.
+fn build_tree(
+    entries: Vec<ProcessEntry>,
+    root: Option<u32>,
+    group: &GroupMembers,
+) -> Vec<ProcessNode> {
+    let mut entries: HashMap<u32, ProcessEntry> = entries
+        .into_iter()
+        .map(|entry| (entry.pid, entry))
+        .collect();
+
+    // A parent ID may have been reused since the child started, so children
+    // started before their parent are not linked to it
+    let mut parents: HashMap<u32, u32> = HashMap::new();
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for entry in entries.values() {
+        if let Some(parent) = entry.parent_pid.and_then(|pid| entries.get(&pid))
+            && parent.pid != entry.pid
+            && !matches!(
+                (parent.started_at, entry.started_at),
+                (Some(parent), Some(child)) if child < parent
+            )
+        {
+            parents.insert(entry.pid, parent.pid);
+            children.entry(parent.pid).or_default().push(entry.pid);
+        }
+    }
+    for pids in children.values_mut() {
+        pids.sort_unstable();
+    }
+
+    let mut starts: Vec<u32> = entries
+        .values()
+        .filter(|entry| group.contains(entry))
+        .map(|entry| entry.pid)
+        .collect();
+    starts.sort_unstable();
+    if let Some(root) = root.filter(|root| entries.contains_key(root)) {
+        starts.retain(|pid| *pid != root);
+        starts.insert(0, root);
+    }
+
+    // Everything reachable from the starting processes
+    let mut selected = HashSet::new();
+    let mut stack = starts.clone();
+    while let Some(pid) = stack.pop() {
+        if selected.insert(pid) {
+            stack.extend(children.get(&pid).into_iter().flatten());
+        }
+    }
+
+    starts
+        .into_iter()
+        .filter(|pid| {
+            !parents
+                .get(pid)
+                .is_some_and(|parent| selected.contains(parent))
+        })
+        .filter_map(|pid| take_node(pid, &mut entries, &children))
+        .collect()
+}
+
+/// Removes `pid` and its descendants from `entries` as a tree
+///
+/// `None` if `pid` was already placed, which stops cycles of reused IDs.
+fn take_node(
+    pid: u32,
+    entries: &mut HashMap<u32, ProcessEntry>,
+    children: &HashMap<u32, Vec<u32>>,
+) -> Option<ProcessNode> {
+    let entry = entries.remove(&pid)?;
+    let children = children
+        .get(&pid)
+        .into_iter()
+        .flatten()
+        .filter_map(|child| take_node(*child, entries, children))
+        .collect();
+    Some(ProcessNode {
+        pid: entry.pid,
+        parent_pid: entry.parent_pid,
+        name: entry.name,
+        state: entry.state,
+        started_at: entry.started_at,
+        children,
+    })
+}
+
+/// Every process of the system, skipping those that exit while listed
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn list_processes() -> Vec<ProcessEntry> {
+    let Ok(dir) = std::fs::read_dir("/proc") else {
+        return vec![];
+    };
+    let boot_time = boot_time();
+    let ticks_per_second = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    let ticks_per_second = u64::try_from(ticks_per_second).unwrap_or(100).max(1);
+
+    dir.flatten()
+        .filter_map(|entry| {
+            let pid: u32 = entry.file_name().to_str()?.parse().ok()?;
+            let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+            parse_stat(pid, &stat, boot_time, ticks_per_second)
+        })
+        .collect()
+}
+
+/// Parses `/proc/<pid>/stat`
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn parse_stat(
+    pid: u32,
+    stat: &str,
+    boot_time: Option<SystemTime>,
+    ticks_per_second: u64,
+) -> Option<ProcessEntry> {
+    let (name, mut fields) = super::process_start::proc_stat(stat)?;
+    let state = fields.next()?.chars().next();
+    let parent_pid = fields.next()?.parse().ok().filter(|ppid| *ppid != 0);
+    let group = fields.next()?.parse().ok();
+    let started_at = fields
+        .nth(16)
+        .and_then(|ticks| ticks.parse::<u64>().ok())
+        .zip(boot_time)
+        .map(|(ticks, boot)| {
+            boot + std::time::Duration::from_millis(ticks * 1000 / ticks_per_second)
+        });
+    Some(ProcessEntry {
+        pid,
+        parent_pid,
+        group,
+        name: name.to_string(),
+        state,
+        started_at,
+    })
+}
+
+/// Time the system booted, from the `btime` line of `/proc/stat`
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn boot_time() -> Option<SystemTime> {
+    let stat = std::fs::read_to_string("/proc/stat").ok()?;
+    let seconds = stat.lines().find_map(|line| line.strip_prefix("btime "))?;
+    let seconds = seconds.trim().parse().ok()?;
+    SystemTime::UNIX_EPOCH.checked_add(std::time::Duration::from_secs(seconds))
+}
+
+/// Every process of the system, skipping those that exit while listed
+#[cfg(windows)]
+fn list_processes() -> Vec<ProcessEntry> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, PROCESSENTRY32W, Process32FirstW, Process32NextW,
+        TH32CS_SNAPPROCESS,
+    };
+
+    let mut entries = Vec::new();
+    let Ok(snapshot) = (unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) }) else {
+        return entries;
+    };
+    let mut process = PROCESSENTRY32W {
+        dwSize: u32::try_from(std::mem::size_of::<PROCESSENTRY32W>()).unwrap_or(0),
+        ..Default::default()
+    };
+    let mut found = unsafe { Process32FirstW(snapshot, &mut process) }.is_ok();
+    while found {
+        let len = process
+            .szExeFile
+            .iter()
+            .position(|c| *c == 0)
+            .unwrap_or(process.szExeFile.len());
+        let pid = process.th32ProcessID;
+        entries.push(ProcessEntry {
+            pid,
+            parent_pid: Some(process.th32ParentProcessID).filter(|ppid| *ppid != 0),
+            name: String::from_utf16_lossy(&process.szExeFile[..len]),
+            state: None,
+            started_at: super::process_start::start_time(pid).and_then(file_time_to_system_time),
+        });
+        found = unsafe { Process32NextW(snapshot, &mut process) }.is_ok();
+    }
+    unsafe {
+        let _ = CloseHandle(snapshot);
+    }
+    entries
+}
+
+/// Converts a `FILETIME`, in 100 ns intervals since 1601, to a system time
+#[cfg(windows)]
+fn file_time_to_system_time(file_time: u64) -> Option<SystemTime> {
+    const SECONDS_FROM_1601_TO_1970: u64 = 11_644_473_600;
+
+    let since_1601 = std::time::Duration::from_nanos(file_time.checked_mul(100)?);
+    let since_1970 =
+        since_1601.checked_sub(std::time::Duration::from_secs(SECONDS_FROM_1601_TO_1970))?;
+    SystemTime::UNIX_EPOCH.checked_add(since_1970)
+}
+
+/// Every process of the system, skipping those that exit while listed
+#[cfg(not(any(target_os = "linux", target_os = "android", windows)))]
+fn list_processes() -> Vec<ProcessEntry> {
+    vec![]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn entry(pid: u32, parent_pid: u32, started: u64) -> ProcessEntry {
+        ProcessEntry {
+            pid,
+            parent_pid: Some(parent_pid),
+            #[cfg(unix)]
+            group: None,
+            name: format!("p{pid}"),
+            state: None,
+            started_at: SystemTime::UNIX_EPOCH.checked_add(Duration::from_secs(started)),
+        }
+    }
+
+    fn pids(tree: &[ProcessNode]) -> Vec<(u32, Option<u32>)> {
+        tree.iter()
+            .flat_map(ProcessNode::iter)
+            .map(|node| (node.pid, node.parent_pid))
+            .collect()
+    }
+
+    #[test]
+    fn descendants_of_the_root_are_nested() {
+        let entries = vec![
+            entry(1, 0, 0),
+            entry(10, 1, 1),
+            entry(12, 10, 2),
+            entry(11, 10, 2),
+            entry(13, 11, 3),
+            entry(20, 1, 1),
+        ];
+
+        let tree = build_tree(entries, Some(10), &GroupMembers::None);
+
+        assert_eq!(tree.len(), 1);
+        assert_eq!(
+            pids(&tree),
+            [
+                (10, Some(1)),
+                (11, Some(10)),
+                (13, Some(11)),
+                (12, Some(10))
+            ]
+        );
+    }
+
+    #[test]
+    fn children_started_before_their_parent_are_not_linked() {
+        // 10 exited and its ID was reused by a process started after 11
+        let entries = vec![entry(10, 1, 5), entry(11, 10, 2)];
+
+        let tree = build_tree(entries, Some(10), &GroupMembers::None);
+
+        assert_eq!(pids(&tree), [(10, Some(1))]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn tree_roundtrips_through_json() {
+        let entries = vec![entry(10, 1, 1), entry(11, 10, 2)];
+        let tree = build_tree(entries, Some(10), &GroupMembers::None);
+
+        let json = serde_json::to_string(&tree).unwrap();
+        let parsed: Vec<ProcessNode> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, tree);
+    }
+
+    #[test]
+    fn missing_root_has_no_tree() {
+        let tree = build_tree(vec![entry(1, 0, 0)], Some(10), &GroupMembers::None);
+        assert!(tree.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn reparented_group_members_are_extra_roots() {
+        let mut orphan = entry(12, 1, 3);
+        orphan.group = Some(10);
+        let mut member = entry(10, 1, 1);
+        member.group = Some(10);
+        let entries = vec![entry(1, 0, 0), member, entry(11, 10, 2), orphan];
+
+        let tree = build_tree(entries, Some(10), &GroupMembers::Group(10));
+
+        assert_eq!(tree.len(), 2);
+        assert_eq!(pids(&tree), [(10, Some(1)), (11, Some(10)), (12, Some(1))]);
+        // Without the root, the group members are listed alone
+        let mut orphan = entry(12, 1, 3);
+        orphan.group = Some(10);
+        let tree = build_tree(vec![orphan], None, &GroupMembers::Group(10));
+        assert_eq!(pids(&tree), [(12, Some(1))]);
+    }
+}
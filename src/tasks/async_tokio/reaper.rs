@@ -0,0 +1,256 @@
+//! Safety net for child processes that are abandoned before being waited on.
+//!
+//! A watcher may be aborted (for example when `join_all_handles` times out)
+//! or leave through a path that never awaits `Child::wait()`. On Unix such a
+//! child stays a zombie until someone reaps it. [`ChildGuard`] owns the child
+//! for the lifetime of the watcher and, if it is dropped while the child has
+//! not been reaped yet, kills it and hands it to a detached task that awaits
+//! its exit status. A task set up to detach its process on drop leaves the
+//! child running and only reaps it once it exits.
+//!
+//! Killing the child does not reach the rest of its process group; the
+//! `TaskSpawner` terminates the group with [`terminate_stray_group`] when it
+//! is dropped after its wait watcher was aborted.
+//!
+//! Abandoned process IDs are tracked until they are reaped and can be
+//! inspected with [`reap_orphans`].
+
+use std::{
+    collections::HashSet,
+    ops::{Deref, DerefMut},
+    sync::{Mutex, OnceLock},
+};
+
+use tokio::process::Child;
+
+use crate::tasks::async_tokio::process_group::ProcessGroup;
+
+fn orphans() -> &'static Mutex<HashSet<u32>> {
+    static ORPHANS: OnceLock<Mutex<HashSet<u32>>> = OnceLock::new();
+    ORPHANS.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+fn register(pid: u32) {
+    if let Ok(mut set) = orphans().lock() {
+        set.insert(pid);
+    }
+}
+
+fn unregister(pid: u32) {
+    if let Ok(mut set) = orphans().lock() {
+        set.remove(&pid);
+    }
+}
+
+/// Returns the process IDs of abandoned children that have not been reaped yet.
+///
+/// On Unix, each tracked process is first polled with a non-blocking
+/// `waitpid`, so zombies left behind (for example because the runtime shut
+/// down before the detached reaper could run) are collected here. Processes
+/// that are still running, or could not be reaped, are returned.
+///
+/// An empty vector means no abandoned child is left as a zombie.
+///
+/// # Examples
+///
+/// ```rust
+/// use tcrm_task::tasks::async_tokio::reaper::reap_orphans;
+///
+/// let unreaped = reap_orphans();
+/// assert!(unreaped.is_empty());
+/// ```
+pub fn reap_orphans() -> Vec<u32> {
+    let Ok(mut set) = orphans().lock() else {
+        return Vec::new();
+    };
+
+    #[cfg(unix)]
+    set.retain(|&pid| {
+        use nix::{
+            errno::Errno,
+            sys::wait::{WaitPidFlag, WaitStatus, waitpid},
+            unistd::Pid,
+        };
+
+        let Ok(raw) = i32::try_from(pid) else {
+            return false;
+        };
+        match waitpid(Pid::from_raw(raw), Some(WaitPidFlag::WNOHANG)) {
+            Ok(WaitStatus::StillAlive) => true,
+            // Reaped now, or already reaped elsewhere
+            Ok(_) | Err(Errno::ECHILD) => false,
+            Err(_) => true,
+        }
+    });
+
+    let mut pids: Vec<u32> = set.iter().copied().collect();
+    pids.sort_unstable();
+    pids
+}
+
+/// Owns a child process and makes sure it is reaped even if the owner is
+/// dropped before awaiting it.
+#[derive(Debug)]
+pub(crate) struct ChildGuard {
+    child: Option<Child>,
+    /// Kill the child when dropped before being waited, instead of detaching it
+    kill_on_drop: bool,
+}
+
+impl ChildGuard {
+    pub(crate) fn new(child: Child, kill_on_drop: bool) -> Self {
+        Self {
+            child: Some(child),
+            kill_on_drop,
+        }
+    }
+}
+
+impl Deref for ChildGuard {
+    type Target = Child;
+
+    fn deref(&self) -> &Child {
+        self.child.as_ref().expect("child is only taken on drop")
+    }
+}
+
+impl DerefMut for ChildGuard {
+    fn deref_mut(&mut self) -> &mut Child {
+        self.child.as_mut().expect("child is only taken on drop")
+    }
+}
+
+impl Drop for ChildGuard {
+    fn drop(&mut self) {
+        let Some(mut child) = self.child.take() else {
+            return;
+        };
+        // `id()` returns `None` once the exit status has been collected
+        let Some(pid) = child.id() else {
+            return;
+        };
+
+        if self.kill_on_drop {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                pid,
+                "Child process abandoned before being waited, reaping in background"
+            );
+
+            if let Err(_e) = child.start_kill() {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(pid, error = %_e, "Failed to kill abandoned child process");
+            }
+        } else {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                pid,
+                "Child process detached, reaping in background once it exits"
+            );
+        }
+        register(pid);
+
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn(async move {
+                    if let Err(_e) = child.wait().await {
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!(pid, error = %_e, "Failed to reap abandoned child process");
+                        return;
+                    }
+                    unregister(pid);
+                });
+            }
+            Err(_) => {
+                // No runtime to reap on; leave it to tokio's orphan queue
+                // or to a later `reap_orphans` call
+                drop(child);
+            }
+        }
+    }
+}
+
+/// Terminates the process group left behind by an aborted wait watcher
+///
+/// The child itself was already killed by its [`ChildGuard`], but its own
+/// children may still be running in the group. Works without a runtime, so it
+/// can run from `Drop` during runtime shutdown.
+pub(crate) fn terminate_stray_group(_pid: u32, process_group: &ProcessGroup) {
+    match process_group.try_terminate_all() {
+        Some(Ok(_outcome)) => {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(pid = _pid, outcome = ?_outcome, "Wait watcher did not complete, terminated stray process group");
+        }
+        Some(Err(_e)) => {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(pid = _pid, error = %_e, "Failed to terminate stray process group");
+        }
+        None => {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(pid = _pid, "Stray process group is in use, not terminated");
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::process::Command;
+
+    use super::{ChildGuard, reap_orphans};
+
+    /// Returns `true` while `/proc/<pid>` exists and is not a zombie.
+    fn is_present(pid: u32) -> bool {
+        match std::fs::read_to_string(format!("/proc/{pid}/stat")) {
+            Ok(stat) => stat
+                .rsplit(") ")
+                .next()
+                .is_some_and(|rest| !rest.starts_with('Z')),
+            Err(_) => false,
+        }
+    }
+
+    fn is_zombie_or_present(pid: u32) -> bool {
+        std::path::Path::new(&format!("/proc/{pid}")).exists()
+    }
+
+    #[tokio::test]
+    async fn aborted_owner_leaves_no_zombie() {
+        let child = Command::new("sleep").arg("30").spawn().unwrap();
+        let pid = child.id().unwrap();
+
+        let handle = tokio::spawn(async move {
+            let _guard = ChildGuard::new(child, true);
+            std::future::pending::<()>().await;
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(is_present(pid), "Child should be running before abort");
+
+        handle.abort();
+        let _ = handle.await;
+
+        let reaped = tokio::time::timeout(Duration::from_secs(5), async {
+            while is_zombie_or_present(pid) {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        })
+        .await;
+        assert!(reaped.is_ok(), "Abandoned child {pid} was left as a zombie");
+        assert!(!reap_orphans().contains(&pid));
+    }
+
+    #[tokio::test]
+    async fn waited_child_is_not_tracked() {
+        let child = Command::new("true").spawn().unwrap();
+        let pid = child.id().unwrap();
+
+        let mut guard = ChildGuard::new(child, true);
+        let status = guard.wait().await.unwrap();
+        assert!(status.success());
+        drop(guard);
+
+        assert!(!reap_orphans().contains(&pid));
+        assert!(!is_zombie_or_present(pid));
+    }
+}
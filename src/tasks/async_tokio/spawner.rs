@@ -1,12 +1,138 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::time::Duration;
+use tokio::runtime::Handle;
 use tokio::sync::{Mutex, RwLock, mpsc, oneshot};
-use tokio::task::JoinHandle;
-use tokio::time::{Instant, timeout};
+use tokio::time::Instant;
 
-use crate::tasks::error::TaskError;
+use crate::tasks::async_tokio::direct::watchers::output_hash::SharedOutputDigests;
+use crate::tasks::async_tokio::direct::watchers::pause::PauseClock;
+use crate::tasks::async_tokio::direct::watchers::result::SpawnedCommand;
+use crate::tasks::async_tokio::direct::watchers::send_stats::EventSendStats;
+use crate::tasks::async_tokio::health::HealthCheck;
+#[cfg(unix)]
+use crate::tasks::async_tokio::hook::PreExecHook;
+use crate::tasks::async_tokio::hook::{CommandCustomizer, OnSpawnedHook};
+use crate::tasks::async_tokio::output_tail::{OutputLine, OutputTail, SharedOutputTail};
+#[cfg(unix)]
+use crate::tasks::async_tokio::pre_exec::PreExecSteps;
+use crate::tasks::async_tokio::process_group::{
+    ProcessGroup, ProcessSignal, interrupt_process, suspend_process,
+};
+use crate::tasks::async_tokio::process_start;
+use crate::tasks::async_tokio::process_tree::{self, GroupMembers, ProcessNode};
+use crate::tasks::async_tokio::reaper::terminate_stray_group;
+use crate::tasks::async_tokio::startup_output::{
+    CapturedLine, DEFAULT_STARTUP_CAPTURE_LINES, SharedStartupOutput, StartupOutput,
+};
+use crate::tasks::async_tokio::stdin::{StdinInput, StdinReceiver};
+use crate::tasks::async_tokio::temp_dir;
+use crate::tasks::async_tokio::transform::OutputTransform;
+use crate::tasks::async_tokio::watcher_set::WatcherSet;
+use crate::tasks::audit::AuditRecord;
+use crate::tasks::error::{ChannelKind, ChannelOp, TaskError};
 use crate::tasks::event::TaskTerminateReason;
-use crate::tasks::{config::TaskConfig, state::TaskState};
+use crate::tasks::validator::{ConfigValidator, ValidationWarning};
+use crate::tasks::{
+    config::{SharedTaskConfig, TaskConfig},
+    state::TaskState,
+};
+
+/// Default time to wait for watcher handles to finish once the process has stopped
+pub(crate) const DEFAULT_JOIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Request sent to the wait watcher to stop the process
+#[derive(Debug)]
+pub(crate) enum StopRequest {
+    /// Terminate the process, stopping with `TaskEventStopReason::Terminated`
+    Terminate(TaskTerminateReason),
+    /// Terminate the process because it failed, stopping with `TaskEventStopReason::Error`
+    Fail(String),
+}
+
+/// Sender of the stop request, taken by whichever request comes first
+pub(crate) type SharedStopSender = Arc<Mutex<Option<oneshot::Sender<StopRequest>>>>;
+
+/// What a running task does once the receiver of its events is dropped
+///
+/// Set with [`TaskSpawner::set_on_channel_closed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChannelClosedPolicy {
+    /// Keep the process running; each output watcher stops reading its
+    /// stream at the first event it cannot send
+    ///
+    /// A process that writes more output after that may be killed by
+    /// `SIGPIPE` or fail its writes.
+    #[default]
+    ContinueSilently,
+    /// Stop the process as if terminated with `TaskTerminateReason::Cleanup`
+    ///
+    /// Noticed as soon as the receiver is dropped, even if the task is not
+    /// sending anything.
+    TerminateTask,
+    /// Keep the process running and keep reading its output, without
+    /// attempting to send any further output event
+    ///
+    /// Output triggers still update the state and stop the task.
+    DetachAndContinue,
+}
+
+/// What [`TaskSpawner::send_terminate_signal`] did with a termination request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminateOutcome {
+    /// The request was delivered; the task stops with its reason
+    SignalSent,
+    /// The task was already stopping, e.g. after an earlier request or its
+    /// timeout, and stops with the reason of that first request
+    AlreadyTerminating,
+    /// The task had already stopped
+    AlreadyFinished,
+}
+
+/// Reasons of the termination requests received while the process ran
+pub(crate) type SharedTerminateRequests = Arc<std::sync::Mutex<Vec<TaskTerminateReason>>>;
+
+/// What a termination request needs of a task, shared with a `TaskGroup` it joined
+///
+/// Every request goes through [`terminate`](Self::terminate), so requests of
+/// the group are recorded for the audit record like those of the spawner.
+#[derive(Debug, Clone)]
+pub(crate) struct TerminateHandle {
+    terminate_tx: SharedStopSender,
+    terminate_requests: SharedTerminateRequests,
+    state: Arc<RwLock<TaskState>>,
+}
+
+impl TerminateHandle {
+    /// Records the request and sends it to the wait watcher, if it was not stopped yet
+    pub(crate) async fn terminate(&self, reason: TaskTerminateReason) -> TerminateOutcome {
+        self.terminate_requests
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push(reason.clone());
+
+        // The receiver is dropped once the process has exited on its own
+        let tx = self.terminate_tx.lock().await.take();
+        let outcome = match tx {
+            Some(tx) => match tx.send(StopRequest::Terminate(reason)) {
+                Ok(()) => TerminateOutcome::SignalSent,
+                Err(_) => TerminateOutcome::AlreadyFinished,
+            },
+            None if *self.state.read().await == TaskState::Finished => {
+                TerminateOutcome::AlreadyFinished
+            }
+            None => TerminateOutcome::AlreadyTerminating,
+        };
+        #[cfg(feature = "tracing")]
+        tracing::debug!(?outcome, "Terminate signal handled");
+        outcome
+    }
+}
+
+/// Audit record of the run, set once the process has stopped
+pub(crate) type SharedAuditRecord = Arc<RwLock<Option<AuditRecord>>>;
 
 /// Information about a running or completed task
 ///
@@ -45,6 +171,78 @@ pub struct TaskInfo {
     /// When the task finished (if completed)
     #[cfg_attr(feature = "serde", serde(skip, default))]
     pub finished_at: Option<Instant>,
+    /// Number of events dropped because the event channel consumer stalled
+    ///
+    /// Only non-zero when `event_send_timeout_ms` is configured.
+    pub dropped_events: u64,
+    /// Number of output events that found the event channel full
+    ///
+    /// Each of these waited for the consumer (or was dropped, see `dropped_events`)
+    /// instead of being delivered immediately. A high count relative to the output
+    /// volume means the consumer is the bottleneck.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub slow_event_sends: u64,
+    /// Total time output events waited for room in the event channel
+    ///
+    /// Divided by `slow_event_sends`, gives how long the consumer kept a full
+    /// channel waiting on average.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub event_send_wait: Duration,
+    /// Most events queued in the event channel at once, as seen by the output watchers
+    ///
+    /// Reaching the channel capacity means the consumer fell behind at some point.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub event_channel_high_water: usize,
+    /// Number of stdin lines that were never written to the process
+    ///
+    /// Counts lines still queued when the task stopped beyond the few that are
+    /// flushed on termination, and lines whose write failed or was cut short.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dropped_stdin_lines: u64,
+    /// Meaning of the exit code configured with `TaskConfig::exit_code_meanings`
+    ///
+    /// Set once the task has stopped with a mapped exit code.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub exit_meaning: Option<String>,
+    /// First output lines of the task, see `TaskConfig::startup_capture_lines`
+    ///
+    /// Set once the task has stopped before reaching Ready because it failed:
+    /// a non-zero exit code, an error, a startup failure, or a timeout.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub startup_output: Option<Vec<CapturedLine>>,
+    /// Temporary working directory of the run, see `TaskConfig::temp_working_dir`
+    ///
+    /// Still set after the directory was removed once the task stopped.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub temp_dir: Option<PathBuf>,
+}
+
+/// What was still pending when [`TaskSpawner::wait_for_quiescence`] returned
+///
+/// Nothing is pending, as reported by [`is_quiescent`](Self::is_quiescent),
+/// once every tokio task started for the task has finished, the task is
+/// finished (or was never started) and its termination channel is closed.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct QuiescenceReport {
+    /// Names of the background tasks still running (`"wait"`, `"cache"`)
+    ///
+    /// The wait watcher joins the output and stdin watchers and sends
+    /// `TaskEvent::Stopped`; the cache forwarder belongs to `start_cached`.
+    pub pending_tasks: Vec<&'static str>,
+    /// State of the task if it is started but not finished yet
+    pub unfinished_state: Option<TaskState>,
+    /// Whether a termination request could still reach the task
+    pub terminate_channel_open: bool,
+}
+
+impl QuiescenceReport {
+    /// Whether nothing related to the task is pending anymore
+    #[must_use]
+    pub fn is_quiescent(&self) -> bool {
+        self.pending_tasks.is_empty()
+            && self.unfinished_state.is_none()
+            && !self.terminate_channel_open
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -77,8 +275,11 @@ fn default_instant() -> Instant {
 ///
 /// #[tokio::main]
 /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
-///     let config = TaskConfig::new("cmd")
-///         .args(["/C", "echo", "Hello World"]);
+///     let config = if cfg!(windows) {
+///         TaskConfig::new("cmd").args(["/C", "echo", "Hello World"])
+///     } else {
+///         TaskConfig::new("echo").args(["Hello World"])
+///     };
 ///
 ///     let (tx, mut rx) = mpsc::channel(100);
 ///     let mut spawner = TaskSpawner::new("hello".to_string(), config);
@@ -104,8 +305,12 @@ fn default_instant() -> Instant {
 ///
 /// #[tokio::main]
 /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
-///     let config = TaskConfig::new("cmd")
-///         .args(["/C", "echo", "Server listening"])
+///     let command = if cfg!(windows) {
+///         TaskConfig::new("cmd").args(["/C", "echo", "Server listening"])
+///     } else {
+///         TaskConfig::new("echo").args(["Server listening"])
+///     };
+///     let config = command
 ///         .ready_indicator("Server listening")
 ///         .ready_indicator_source(StreamSource::Stdout)
 ///         .timeout_ms(30000);
@@ -135,9 +340,13 @@ fn default_instant() -> Instant {
 ///
 /// #[tokio::main]
 /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
-///     let config = TaskConfig::new("cmd")
-///         .args(["/C", "echo", "Hello"])
-///         .enable_stdin(true);
+///     // Echoes its input back
+///     let command = if cfg!(windows) {
+///         TaskConfig::new("findstr").args(["^"])
+///     } else {
+///         TaskConfig::new("cat")
+///     };
+///     let config = command.enable_stdin(true);
 ///
 ///     let (tx, mut rx) = mpsc::channel(100);
 ///     let (stdin_tx, stdin_rx) = mpsc::channel(10);
@@ -151,6 +360,8 @@ fn default_instant() -> Instant {
 ///     // Send input to the process
 ///     stdin_tx.send("print('Hello from stdin!')".to_string()).await?;
 ///     stdin_tx.send("exit()".to_string()).await?;
+///     // Closing stdin ends the input, so the process exits
+///     drop(stdin_tx);
 ///
 ///     // Process events
 ///     while let Some(event) = rx.recv().await {
@@ -168,15 +379,54 @@ fn default_instant() -> Instant {
 /// ```
 #[derive(Debug)]
 pub struct TaskSpawner {
-    pub(crate) config: TaskConfig,
-    pub(crate) task_name: String,
+    pub(crate) config: SharedTaskConfig,
+    pub(crate) task_name: Arc<str>,
     pub(crate) state: Arc<RwLock<TaskState>>,
-    pub(crate) terminate_tx: Arc<Mutex<Option<oneshot::Sender<TaskTerminateReason>>>>,
+    pub(crate) terminate_tx: SharedStopSender,
     pub(crate) process_id: Arc<RwLock<Option<u32>>>,
+    /// Start time of the process, to detect reuse of `process_id`
+    pub(crate) process_start_time: Option<u64>,
     pub(crate) created_at: Instant,
     pub(crate) running_at: Option<Instant>,
     pub(crate) finished_at: Arc<RwLock<Option<Instant>>>,
-    pub(crate) stdin_rx: Option<mpsc::Receiver<String>>,
+    pub(crate) exit_meaning: Arc<RwLock<Option<String>>>,
+    pub(crate) stdin_rx: Option<StdinReceiver>,
+    pub(crate) dropped_events: Arc<AtomicU64>,
+    pub(crate) send_stats: Arc<EventSendStats>,
+    pub(crate) dropped_stdin_lines: Arc<AtomicU64>,
+    pub(crate) ready_flag: Arc<AtomicBool>,
+    pub(crate) startup_output: SharedStartupOutput,
+    pub(crate) output_tail: SharedOutputTail,
+    pub(crate) failed_before_ready: Arc<AtomicBool>,
+    pub(crate) join_timeout: Duration,
+    pub(crate) output_transform: Option<OutputTransform>,
+    pub(crate) spawned_env: Option<HashMap<String, String>>,
+    pub(crate) temp_dir: Option<PathBuf>,
+    pub(crate) spawned: Option<SpawnedCommand>,
+    pub(crate) output_bytes: Arc<AtomicU64>,
+    pub(crate) output_digests: SharedOutputDigests,
+    pub(crate) terminate_requests: SharedTerminateRequests,
+    pub(crate) audit: SharedAuditRecord,
+    pub(crate) on_spawned: Option<OnSpawnedHook>,
+    pub(crate) customize_command: Option<CommandCustomizer>,
+    pub(crate) runtime: Option<Handle>,
+    /// The wait watcher and the event forwarder of `start_cached`
+    pub(crate) watchers: WatcherSet,
+    pub(crate) process_group: Option<ProcessGroup>,
+    pub(crate) cleanup_on_drop: bool,
+    pub(crate) structured_concurrency: bool,
+    pub(crate) on_channel_closed: ChannelClosedPolicy,
+    pub(crate) health_check: Option<HealthCheck>,
+    /// Cleared while the task fails its health check
+    pub(crate) healthy: Arc<AtomicBool>,
+    /// Time the task spent paused, left out of the timeout if configured
+    pub(crate) pause_clock: Arc<PauseClock>,
+    /// Steps added with `add_pre_exec_step`
+    #[cfg(unix)]
+    pub(crate) pre_exec_hooks: Vec<PreExecHook>,
+    /// The `pre_exec` steps registered on the prepared command, until it is spawned
+    #[cfg(unix)]
+    pub(crate) pre_exec: PreExecSteps,
 }
 
 impl TaskSpawner {
@@ -187,7 +437,7 @@ impl TaskSpawner {
     ///
     /// # Arguments
     ///
-    /// * `task_name` - Unique identifier for this task instance
+    /// * `task_name` - Unique identifier for this task instance, shared by every event it emits
     /// * `config` - Task configuration defining command, arguments, environment, etc.
     ///
     /// # Examples
@@ -199,24 +449,95 @@ impl TaskSpawner {
     /// ```
     #[must_use]
     pub fn new(task_name: String, config: TaskConfig) -> Self {
+        let startup_capture_lines = config
+            .startup_capture_lines
+            .unwrap_or(DEFAULT_STARTUP_CAPTURE_LINES);
+        let retain_output_lines = config.retain_output_lines.unwrap_or(0);
+        let task_name: Arc<str> = task_name.into();
+        let send_stats = EventSendStats::new(task_name.clone(), config.slow_consumer_threshold_ms);
         Self {
             task_name,
-            config,
+            config: Arc::new(config),
             state: Arc::new(RwLock::new(TaskState::Pending)),
             terminate_tx: Arc::new(Mutex::new(None)),
             process_id: Arc::new(RwLock::new(None)),
+            process_start_time: None,
             created_at: Instant::now(),
             running_at: None,
             finished_at: Arc::new(RwLock::new(None)),
+            exit_meaning: Arc::new(RwLock::new(None)),
             stdin_rx: None,
+            dropped_events: Arc::new(AtomicU64::new(0)),
+            send_stats: Arc::new(send_stats),
+            dropped_stdin_lines: Arc::new(AtomicU64::new(0)),
+            ready_flag: Arc::new(AtomicBool::new(false)),
+            startup_output: Arc::new(std::sync::Mutex::new(StartupOutput::new(
+                startup_capture_lines,
+            ))),
+            output_tail: Arc::new(std::sync::Mutex::new(OutputTail::new(retain_output_lines))),
+            failed_before_ready: Arc::new(AtomicBool::new(false)),
+            join_timeout: DEFAULT_JOIN_TIMEOUT,
+            output_transform: None,
+            spawned_env: None,
+            temp_dir: None,
+            spawned: None,
+            output_bytes: Arc::new(AtomicU64::new(0)),
+            output_digests: Arc::default(),
+            terminate_requests: Arc::default(),
+            audit: Arc::new(RwLock::new(None)),
+            on_spawned: None,
+            customize_command: None,
+            runtime: None,
+            watchers: WatcherSet::default(),
+            process_group: None,
+            cleanup_on_drop: true,
+            structured_concurrency: false,
+            on_channel_closed: ChannelClosedPolicy::default(),
+            health_check: None,
+            healthy: Arc::new(AtomicBool::new(true)),
+            pause_clock: Arc::default(),
+            #[cfg(unix)]
+            pre_exec_hooks: Vec::new(),
+            #[cfg(unix)]
+            pre_exec: PreExecSteps::default(),
         }
     }
 
+    /// Set how long to wait for watcher tasks to finish after the process stops
+    ///
+    /// Once the process has exited or been terminated, the stdout/stderr, stdin,
+    /// wait and timeout watchers are joined before `TaskEvent::Stopped` is sent.
+    /// Watchers are joined concurrently and any watcher still running when the
+    /// timeout elapses is aborted. Defaults to 5 seconds.
+    ///
+    /// # Arguments
+    ///
+    /// * `join_timeout` - Maximum total time to wait for all watchers
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::{config::TaskConfig, async_tokio::spawner::TaskSpawner};
+    /// use std::time::Duration;
+    ///
+    /// let config = TaskConfig::new("echo").args(["hello"]);
+    /// let spawner = TaskSpawner::new("my-task".to_string(), config)
+    ///     .set_join_timeout(Duration::from_secs(1));
+    /// ```
+    #[must_use]
+    pub fn set_join_timeout(mut self, join_timeout: Duration) -> Self {
+        self.join_timeout = join_timeout;
+        self
+    }
+
     /// Set the stdin receiver for the task, enabling asynchronous input
     ///
     /// Configures a channel for sending input to the process stdin. This method
     /// has no effect if `enable_stdin` is false in the task configuration.
     ///
+    /// When the task stops, the channel is closed. Up to 16 lines still queued
+    /// are written within 100 ms; the rest are dropped and counted in
+    /// [`TaskInfo::dropped_stdin_lines`].
+    ///
     /// # Arguments
     ///
     /// * `stdin_rx` - Receiver channel for stdin input strings
@@ -237,11 +558,354 @@ impl TaskSpawner {
     #[must_use]
     pub fn set_stdin(mut self, stdin_rx: mpsc::Receiver<String>) -> Self {
         if self.config.enable_stdin.unwrap_or_default() {
-            self.stdin_rx = Some(stdin_rx);
+            self.stdin_rx = Some(StdinReceiver::Lines(stdin_rx));
         }
         self
     }
 
+    /// Set a receiver of [`StdinInput`] for the task, which can mark inputs as secret
+    ///
+    /// Like [`set_stdin`](Self::set_stdin), which it replaces, but a secret
+    /// input such as a password is only ever logged as
+    /// [`REDACTED_STDIN`](crate::tasks::async_tokio::stdin::REDACTED_STDIN)
+    /// with its length in bytes. Has no effect if `enable_stdin` is false in
+    /// the task configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `stdin_rx` - Receiver channel for stdin inputs
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::{
+    ///     async_tokio::{spawner::TaskSpawner, stdin::StdinInput},
+    ///     config::TaskConfig,
+    /// };
+    /// use tokio::sync::mpsc;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = TaskConfig::new("sudo").args(["-S", "true"]).enable_stdin(true);
+    ///
+    /// let (stdin_tx, stdin_rx) = mpsc::channel(10);
+    /// let spawner = TaskSpawner::new("sudo".to_string(), config).set_stdin_input(stdin_rx);
+    /// stdin_tx.send(StdinInput::secret("hunter2")).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn set_stdin_input(mut self, stdin_rx: mpsc::Receiver<StdinInput>) -> Self {
+        if self.config.enable_stdin.unwrap_or_default() {
+            self.stdin_rx = Some(StdinReceiver::Inputs(stdin_rx));
+        }
+        self
+    }
+
+    /// Set a transform applied to each output line before it is sent
+    ///
+    /// The transform can replace a line or drop it, for example to redact
+    /// secrets before they reach the event channel. See [`OutputTransform`]
+    /// for how it interacts with the ready indicator and what happens if it
+    /// panics.
+    ///
+    /// # Arguments
+    ///
+    /// * `transform` - Transform applied to stdout and stderr lines
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::{
+    ///     async_tokio::{spawner::TaskSpawner, transform::OutputTransform},
+    ///     config::TaskConfig,
+    /// };
+    ///
+    /// let config = TaskConfig::new("echo").args(["token=abc123"]);
+    /// let spawner = TaskSpawner::new("redacted".to_string(), config)
+    ///     .set_output_transform(OutputTransform::new(|line, _src| {
+    ///         Some(line.replace("abc123", "***"))
+    ///     }));
+    /// ```
+    #[must_use]
+    pub fn set_output_transform(mut self, transform: OutputTransform) -> Self {
+        self.output_transform = Some(transform.for_task());
+        self
+    }
+
+    /// Set a hook run right after the process has been spawned
+    ///
+    /// The hook receives the process ID before `TaskEvent::Started` is sent
+    /// and before any output is read, for example to register the process with
+    /// a cgroup manager or audit service. See [`OnSpawnedHook`] for what
+    /// happens if it fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `hook` - Hook called with the process ID
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::{
+    ///     async_tokio::{hook::OnSpawnedHook, spawner::TaskSpawner},
+    ///     config::TaskConfig,
+    /// };
+    ///
+    /// let config = TaskConfig::new("echo").args(["hello"]);
+    /// let spawner = TaskSpawner::new("registered".to_string(), config)
+    ///     .set_on_spawned(OnSpawnedHook::new(|pid| async move {
+    ///         println!("spawned {pid}");
+    ///         Ok(())
+    ///     }));
+    /// ```
+    #[must_use]
+    pub fn set_on_spawned(mut self, hook: OnSpawnedHook) -> Self {
+        self.on_spawned = Some(hook);
+        self
+    }
+
+    /// Adjust the `Command` of the task right before it is spawned
+    ///
+    /// For process options `TaskConfig` does not cover, such as platform
+    /// specific creation flags. The function is called last, with the command
+    /// as configured by the task, and also by [`plan`](Self::plan). Nothing
+    /// checks what it changes, so it can break stdio capture or process group
+    /// termination; see [`CommandCustomizer`].
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - Called with the command to spawn
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::{async_tokio::spawner::TaskSpawner, config::TaskConfig};
+    ///
+    /// let spawner = TaskSpawner::new("build".to_string(), TaskConfig::new("cargo"))
+    ///     .customize_command(|cmd| {
+    ///         cmd.env_remove("RUSTFLAGS");
+    ///     });
+    /// ```
+    #[must_use]
+    pub fn customize_command<F>(mut self, func: F) -> Self
+    where
+        F: FnMut(&mut tokio::process::Command) + Send + 'static,
+    {
+        self.customize_command = Some(CommandCustomizer::new(func));
+        self
+    }
+
+    /// Add a step run in the child process before it executes the command (Unix only)
+    ///
+    /// For process setup `TaskConfig` does not cover, such as resource limits.
+    /// Steps run between `fork` and `exec`, after the steps of the spawner
+    /// itself such as `setsid` for the process group, in the order they were
+    /// added. If a step fails, spawning fails with [`TaskError::PreExec`]
+    /// naming it, e.g. "pre_exec step 'rlimit' failed: EINVAL", which
+    /// `TaskEvent::Error` carries as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the step in errors
+    /// * `func` - Run in the child process
+    ///
+    /// # Safety
+    ///
+    /// `func` runs in the forked child, with the restrictions of
+    /// [`CommandExt::pre_exec`](std::os::unix::process::CommandExt::pre_exec):
+    /// as the parent is multi-threaded, only async-signal-safe operations may
+    /// be used, so no allocation and no locks.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::{async_tokio::spawner::TaskSpawner, config::TaskConfig};
+    ///
+    /// let spawner = unsafe {
+    ///     TaskSpawner::new("build".to_string(), TaskConfig::new("cargo")).add_pre_exec_step(
+    ///         "rlimit",
+    ///         || {
+    ///             let limit = libc::rlimit { rlim_cur: 256, rlim_max: 256 };
+    ///             if libc::setrlimit(libc::RLIMIT_NOFILE, &limit) == -1 {
+    ///                 return Err(std::io::Error::last_os_error());
+    ///             }
+    ///             Ok(())
+    ///         },
+    ///     )
+    /// };
+    /// ```
+    #[cfg(unix)]
+    #[must_use]
+    pub unsafe fn add_pre_exec_step<F>(mut self, name: impl Into<String>, func: F) -> Self
+    where
+        F: Fn() -> std::io::Result<()> + Send + Sync + 'static,
+    {
+        self.pre_exec_hooks.push(PreExecHook {
+            name: name.into(),
+            func: Arc::new(func),
+        });
+        self
+    }
+
+    /// Set the tokio runtime the process and its watchers run on
+    ///
+    /// By default the process is spawned and its watchers run on the runtime
+    /// that calls `start_direct`. With a runtime handle set, the process is
+    /// spawned inside that runtime, so its pipes and exit notification are
+    /// driven by it, and the stdout/stderr, stdin and wait watchers are spawned
+    /// on it. This keeps task I/O off a latency-sensitive runtime, e.g. a
+    /// current-thread UI runtime can start tasks whose watchers run on a
+    /// multi-thread worker runtime.
+    ///
+    /// The runtime must keep running for as long as the task does, or no
+    /// events are delivered.
+    ///
+    /// # Arguments
+    ///
+    /// * `runtime` - Handle of the runtime to spawn the process and watchers on
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::{config::TaskConfig, async_tokio::spawner::TaskSpawner};
+    ///
+    /// let workers = tokio::runtime::Builder::new_multi_thread()
+    ///     .enable_all()
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let config = TaskConfig::new("echo").args(["hello"]);
+    /// let spawner = TaskSpawner::new("isolated".to_string(), config)
+    ///     .set_runtime(workers.handle().clone());
+    /// ```
+    #[must_use]
+    pub fn set_runtime(mut self, runtime: Handle) -> Self {
+        self.runtime = Some(runtime);
+        self
+    }
+
+    /// Set whether the process is cleaned up when its wait watcher is aborted
+    ///
+    /// The wait watcher owns the child process. If it is aborted before the
+    /// process exits, e.g. because the runtime shuts down, the process is
+    /// killed (enabled, the default), or detached and left running (disabled).
+    /// When enabled and the task uses a process group, dropping the spawner
+    /// after such an abort also terminates the rest of the group.
+    ///
+    /// Dropping the spawner while its wait watcher is still running does not
+    /// stop the task either way, unless
+    /// [`set_structured_concurrency`](Self::set_structured_concurrency) is
+    /// enabled: the watcher keeps owning the process until it exits. On
+    /// Windows, the processes of a Job Object are killed once its last handle
+    /// is closed, so only processes outside a process group stay running when
+    /// detached.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to kill the process instead of detaching it
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::{config::TaskConfig, async_tokio::spawner::TaskSpawner};
+    ///
+    /// // A daemon that must outlive the supervising runtime
+    /// let config = TaskConfig::new("my-daemon").use_process_group(false);
+    /// let spawner = TaskSpawner::new("daemon".to_string(), config)
+    ///     .set_cleanup_on_drop(false);
+    /// ```
+    #[must_use]
+    pub fn set_cleanup_on_drop(mut self, enabled: bool) -> Self {
+        self.cleanup_on_drop = enabled;
+        self
+    }
+
+    /// Set whether dropping the spawner aborts the watchers of the task
+    ///
+    /// The watchers of a started task (output, stdin, wait and the event
+    /// forwarder of `start_cached`) run in sets owned by the spawner. By
+    /// default (disabled), dropping the spawner detaches them, and the task
+    /// keeps running and sending events until its process exits.
+    ///
+    /// When enabled, dropping the spawner aborts every watcher instead, like
+    /// [`shutdown`](Self::shutdown) without waiting: no further event is sent,
+    /// and the process is killed or detached as set with
+    /// [`set_cleanup_on_drop`](Self::set_cleanup_on_drop).
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to abort the watchers when the spawner is dropped
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::{config::TaskConfig, async_tokio::spawner::TaskSpawner};
+    ///
+    /// // Nothing of the task outlives the spawner
+    /// let config = TaskConfig::new("my-server");
+    /// let spawner = TaskSpawner::new("server".to_string(), config)
+    ///     .set_structured_concurrency(true);
+    /// ```
+    #[must_use]
+    pub fn set_structured_concurrency(mut self, enabled: bool) -> Self {
+        self.structured_concurrency = enabled;
+        self
+    }
+
+    /// Set what the task does once the receiver of its events is dropped
+    ///
+    /// By default ([`ChannelClosedPolicy::ContinueSilently`]) the process keeps
+    /// running with nobody listening. See [`ChannelClosedPolicy`] for stopping
+    /// it instead, or for keeping it running without the cost of failed sends.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - Behavior once the event channel is closed
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::{
+    ///     config::TaskConfig,
+    ///     async_tokio::spawner::{ChannelClosedPolicy, TaskSpawner},
+    /// };
+    ///
+    /// // The task is only useful while someone watches its output
+    /// let config = TaskConfig::new("tail").args(["-f", "app.log"]);
+    /// let spawner = TaskSpawner::new("follow".to_string(), config)
+    ///     .set_on_channel_closed(ChannelClosedPolicy::TerminateTask);
+    /// ```
+    #[must_use]
+    pub fn set_on_channel_closed(mut self, policy: ChannelClosedPolicy) -> Self {
+        self.on_channel_closed = policy;
+        self
+    }
+
+    /// Set a liveness probe run while the task is Ready
+    ///
+    /// Once the probe failed `failure_threshold` times in a row,
+    /// `TaskEvent::Unhealthy` is sent and [`is_healthy`](Self::is_healthy)
+    /// returns `false`; the task is only terminated with
+    /// [`UnhealthyAction::Terminate`](crate::tasks::async_tokio::health::UnhealthyAction::Terminate).
+    /// Tasks that never become Ready are never probed.
+    ///
+    /// # Arguments
+    ///
+    /// * `check` - Interval, probe and consequences of the health check
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::{
+    ///     config::TaskConfig,
+    ///     async_tokio::{
+    ///         health::{HealthCheck, HealthCheckMode},
+    ///         spawner::TaskSpawner,
+    ///     },
+    /// };
+    /// use std::sync::Arc;
+    ///
+    /// let config = TaskConfig::new("my-server").ready_indicator("listening");
+    /// let probe = HealthCheckMode::Custom(Arc::new(|status| status.output_bytes < 1 << 30));
+    /// let spawner = TaskSpawner::new("server".to_string(), config)
+    ///     .set_health_check(HealthCheck::new(1000, probe));
+    /// ```
+    #[must_use]
+    pub fn set_health_check(mut self, check: HealthCheck) -> Self {
+        self.health_check = Some(check);
+        self
+    }
+
     /// Get the current state of the task
     ///
     /// Returns the current execution state of the task. States progress through:
@@ -309,6 +973,25 @@ impl TaskSpawner {
         state == TaskState::Ready
     }
 
+    /// Check if the task passes its health check
+    ///
+    /// Returns false from the moment `TaskEvent::Unhealthy` is sent until a
+    /// probe passes again, and true otherwise, including without a health
+    /// check. See [`set_health_check`](Self::set_health_check).
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::{config::TaskConfig, async_tokio::spawner::TaskSpawner};
+    ///
+    /// let config = TaskConfig::new("my-server");
+    /// let spawner = TaskSpawner::new("server".to_string(), config);
+    /// assert!(spawner.is_healthy());
+    /// ```
+    #[must_use]
+    pub fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Relaxed)
+    }
+
     /// Get the uptime of the task since creation
     ///
     /// Returns the duration since the `TaskSpawner` was created, regardless
@@ -357,12 +1040,23 @@ impl TaskSpawner {
     /// ```
     pub async fn get_task_info(&self) -> TaskInfo {
         TaskInfo {
-            name: self.task_name.clone(),
+            name: self.task_name.to_string(),
             state: self.get_state().await,
             uptime: self.uptime(),
             created_at: self.created_at,
             running_at: self.running_at,
             finished_at: *self.finished_at.read().await,
+            dropped_events: self.dropped_events.load(Ordering::Relaxed),
+            slow_event_sends: self.send_stats.slow_sends(),
+            event_send_wait: self.send_stats.wait(),
+            event_channel_high_water: self.send_stats.high_water(),
+            dropped_stdin_lines: self.dropped_stdin_lines.load(Ordering::Relaxed),
+            exit_meaning: self.exit_meaning.read().await.clone(),
+            startup_output: self
+                .failed_before_ready
+                .load(Ordering::Relaxed)
+                .then(|| self.startup_output()),
+            temp_dir: self.temp_dir.clone(),
         }
     }
 
@@ -371,6 +1065,9 @@ impl TaskSpawner {
     /// Returns the system process ID if the task is currently running,
     /// or None if the task hasn't started or has finished.
     ///
+    /// The process ID is cleared only after `TaskEvent::Stopped` has been sent,
+    /// so it remains available while handling any earlier event of the task.
+    ///
     /// # Examples
     /// ```rust
     /// use tcrm_task::tasks::{config::TaskConfig, async_tokio::spawner::TaskSpawner};
@@ -378,7 +1075,11 @@ impl TaskSpawner {
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    ///     let config = TaskConfig::new("cmd").args(["/C", "ping", "127.0.0.1", "-n", "2"]);
+    ///     let config = if cfg!(windows) {
+    ///         TaskConfig::new("cmd").args(["/C", "ping", "127.0.0.1", "-n", "2"])
+    ///     } else {
+    ///         TaskConfig::new("sleep").args(["1"])
+    ///     };
     ///     let mut spawner = TaskSpawner::new("pid-test".to_string(), config);
     ///     
     ///     assert_eq!(spawner.get_process_id().await, None); // Not started yet
@@ -397,12 +1098,379 @@ impl TaskSpawner {
         *self.process_id.read().await
     }
 
+    /// Take a snapshot of the processes of the task, for diagnostics
+    ///
+    /// Returns the task process and its descendants as a tree with their
+    /// names, states and start times, e.g. to attach to a bug report when a
+    /// task hangs. Descendants are found through their parent process IDs, so
+    /// this works without process group management. With it, members of the
+    /// group whose parent is not in the tree, such as processes reparented
+    /// after their parent exited, follow as extra roots; they are listed even
+    /// once the task process itself has exited.
+    ///
+    /// Empty if no process is found, and on platforms other than Linux and
+    /// Windows. Processes are read one by one, so ones starting or exiting
+    /// meanwhile may be missed.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tcrm_task::tasks::{config::TaskConfig, async_tokio::spawner::TaskSpawner};
+    /// use tokio::sync::mpsc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let config = TaskConfig::new("make").args(["-j4"]);
+    ///     let mut spawner = TaskSpawner::new("build".to_string(), config);
+    ///
+    ///     let (tx, _rx) = mpsc::channel(100);
+    ///     spawner.start_direct(tx).await?;
+    ///
+    ///     for root in spawner.process_tree().await {
+    ///         for node in root.iter() {
+    ///             println!("{} {} {:?}", node.pid, node.name, node.state);
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn process_tree(&self) -> Vec<ProcessNode> {
+        let root = self
+            .process_id
+            .read()
+            .await
+            .filter(|pid| !process_start::is_stale(*pid, self.process_start_time));
+        let group = match &self.process_group {
+            Some(process_group) => process_group.members().await,
+            None => GroupMembers::None,
+        };
+        process_tree::snapshot(root, &group)
+    }
+
+    /// Get the environment the process was spawned with
+    ///
+    /// Returns the inherited environment merged with the configured `env`, as
+    /// recorded when the process was spawned. `None` unless
+    /// [`capture_env_snapshot`](crate::tasks::config::TaskConfig::capture_env_snapshot)
+    /// is enabled and the task has been started.
+    ///
+    /// The snapshot holds the values of sensitive variables as they are; use
+    /// [`spawned_env_redacted`](Self::spawned_env_redacted) for anything that
+    /// is displayed or logged.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tcrm_task::tasks::{config::TaskConfig, async_tokio::spawner::TaskSpawner};
+    /// use tokio::sync::mpsc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let config = TaskConfig::new("env")
+    ///         .env([("GREETING", "hello")])
+    ///         .capture_env_snapshot(true);
+    ///     let mut spawner = TaskSpawner::new("env-test".to_string(), config);
+    ///     assert!(spawner.spawned_env().is_none());
+    ///
+    ///     let (tx, _rx) = mpsc::channel(100);
+    ///     spawner.start_direct(tx).await?;
+    ///
+    ///     let env = spawner.spawned_env().unwrap();
+    ///     assert_eq!(env.get("GREETING").map(String::as_str), Some("hello"));
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    pub fn spawned_env(&self) -> Option<&HashMap<String, String>> {
+        self.spawned_env.as_ref()
+    }
+
+    /// Get the environment the process was spawned with, with sensitive values masked
+    ///
+    /// Like [`spawned_env`](Self::spawned_env), but values of variables
+    /// matched by [`ConfigValidator::is_sensitive_env_key`] are replaced with
+    /// [`REDACTED_ENV_VALUE`](crate::tasks::validator::REDACTED_ENV_VALUE).
+    #[must_use]
+    pub fn spawned_env_redacted(&self) -> Option<HashMap<String, String>> {
+        self.spawned_env.as_ref().map(ConfigValidator::redact_env)
+    }
+
+    /// Get the audit record of the run
+    ///
+    /// Summarizes the execution once the process has stopped: the resolved
+    /// program, redacted arguments, working directory, names of the configured
+    /// environment variables, process ID, start and stop times, exit code or
+    /// signal, stop reason, output volume and termination requests. Set before
+    /// `TaskEvent::Stopped` is sent, so it is available as soon as `Stopped` is
+    /// received.
+    ///
+    /// `None` until the process has stopped, and for tasks that never spawned a
+    /// process: the spawn failed, the result came from the cache, or the task
+    /// was shut down before its result was reported.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tcrm_task::tasks::{config::TaskConfig, async_tokio::spawner::TaskSpawner};
+    /// use tokio::sync::mpsc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let config = TaskConfig::new("echo").args(["hello"]);
+    ///     let mut spawner = TaskSpawner::new("audited".to_string(), config);
+    ///     assert!(spawner.audit().await.is_none());
+    ///
+    ///     let (tx, mut rx) = mpsc::channel(100);
+    ///     spawner.start_direct(tx).await?;
+    ///     while let Some(event) = rx.recv().await {
+    ///         if matches!(event, tcrm_task::tasks::event::TaskEvent::Stopped { .. }) {
+    ///             break;
+    ///         }
+    ///     }
+    ///
+    ///     let audit = spawner.audit().await.unwrap();
+    ///     println!("{} exited with {:?}", audit.command, audit.exit_code);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn audit(&self) -> Option<AuditRecord> {
+        self.audit.read().await.clone()
+    }
+
+    /// Get the first output lines of the task
+    ///
+    /// Holds up to `TaskConfig::startup_capture_lines` lines (50 by default)
+    /// from stdout and stderr in the order they were read, kept even if the
+    /// event consumer discarded them. Empty until the task has produced output.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tcrm_task::tasks::{config::TaskConfig, async_tokio::spawner::TaskSpawner};
+    /// use tokio::sync::mpsc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let config = TaskConfig::new("my-server").startup_capture_lines(20);
+    ///     let mut spawner = TaskSpawner::new("server".to_string(), config);
+    ///
+    ///     let (tx, mut rx) = mpsc::channel(100);
+    ///     spawner.start_direct(tx).await?;
+    ///     while rx.recv().await.is_some() {}
+    ///
+    ///     for captured in spawner.startup_output() {
+    ///         eprintln!("[{:?}] {}", captured.src, captured.line);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    pub fn startup_output(&self) -> Vec<CapturedLine> {
+        self.startup_output
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .lines()
+            .to_vec()
+    }
+
+    /// Get the last `n` retained output lines, oldest first
+    ///
+    /// Lines from stdout and stderr are retained in the order they were read
+    /// when `TaskConfig::retain_output_lines` is set, and stay available after
+    /// the task stopped. Returns fewer than `n` lines if fewer are retained,
+    /// and none if retention is disabled.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tcrm_task::tasks::{config::TaskConfig, async_tokio::spawner::TaskSpawner};
+    /// use tokio::sync::mpsc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let config = TaskConfig::new("cargo").args(["build"]).retain_output_lines(100);
+    ///     let mut spawner = TaskSpawner::new("build".to_string(), config);
+    ///
+    ///     let (tx, mut rx) = mpsc::channel(100);
+    ///     spawner.start_direct(tx).await?;
+    ///     while rx.recv().await.is_some() {}
+    ///
+    ///     for retained in spawner.tail(20) {
+    ///         eprintln!("[{:?}] {}", retained.src, retained.line);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    #[must_use]
+    pub fn tail(&self, n: usize) -> Vec<OutputLine> {
+        self.output_tail
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .last(n)
+    }
+
+    /// Validate the configuration and report settings that are likely mistakes
+    ///
+    /// Reports the warnings of [`TaskConfig::validate_with_warnings`], plus
+    /// [`ValidationWarning::StdinWithoutSource`] when stdin is enabled but no
+    /// receiver was set with [`set_stdin`](Self::set_stdin) or
+    /// [`set_stdin_input`](Self::set_stdin_input) and no stdin script is
+    /// configured.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::{
+    ///     async_tokio::spawner::TaskSpawner, config::TaskConfig, validator::ValidationWarning,
+    /// };
+    ///
+    /// let config = TaskConfig::new("python").enable_stdin(true);
+    /// let spawner = TaskSpawner::new("repl".to_string(), config);
+    ///
+    /// let (result, warnings) = spawner.validate_with_warnings();
+    /// assert!(result.is_ok());
+    /// assert!(warnings.contains(&ValidationWarning::StdinWithoutSource));
+    /// ```
+    pub fn validate_with_warnings(&self) -> (Result<(), TaskError>, Vec<ValidationWarning>) {
+        let (result, mut warnings) = self.config.validate_with_warnings();
+        if self.config.enable_stdin.unwrap_or_default()
+            && self.stdin_rx.is_none()
+            && self.config.stdin_script.is_none()
+        {
+            warnings.push(ValidationWarning::StdinWithoutSource);
+        }
+        (result, warnings)
+    }
+
+    /// Wait until nothing related to this task is running anymore
+    ///
+    /// Resolves once the wait watcher (which joins the output and stdin
+    /// watchers and sends `TaskEvent::Stopped`) and the event forwarder of
+    /// `start_cached` have finished, the task is finished and its termination
+    /// channel is closed. Returns at once for a task that was never started or
+    /// failed to spawn.
+    ///
+    /// Meant for deterministic teardown, e.g. so that no tokio task of one
+    /// test outlives it. Background tasks still running at the timeout are
+    /// left running, and can be waited for again.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum time to wait
+    ///
+    /// # Returns
+    ///
+    /// What is still pending at the timeout; see [`QuiescenceReport::is_quiescent`].
+    ///
+    /// # Examples
+    /// ```rust
+    /// use std::time::Duration;
+    ///
+    /// use tcrm_task::tasks::{config::TaskConfig, async_tokio::spawner::TaskSpawner};
+    /// use tokio::sync::mpsc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let config = if cfg!(windows) {
+    ///         TaskConfig::new("cmd").args(["/C", "echo done"])
+    ///     } else {
+    ///         TaskConfig::new("echo").args(["done"])
+    ///     };
+    ///     let mut spawner = TaskSpawner::new("quiet".to_string(), config);
+    ///
+    ///     let (tx, _rx) = mpsc::channel(100);
+    ///     spawner.start_direct(tx).await?;
+    ///
+    ///     let report = spawner.wait_for_quiescence(Duration::from_secs(5)).await;
+    ///     assert!(report.is_quiescent(), "{report:?}");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn wait_for_quiescence(&mut self, timeout: Duration) -> QuiescenceReport {
+        let deadline = Instant::now() + timeout;
+        let mut report = QuiescenceReport::default();
+
+        #[allow(clippy::used_underscore_binding)]
+        while let Some((_name, _, _result)) = self.watchers.join_next_until(deadline).await {
+            #[cfg(feature = "tracing")]
+            if let Err(e) = _result {
+                tracing::warn!(error = %e, "{_name} watcher failed");
+            }
+        }
+        report.pending_tasks = self.watchers.pending();
+
+        let state = self.get_state().await;
+        if !matches!(state, TaskState::Pending | TaskState::Finished) {
+            report.unfinished_state = Some(state);
+        }
+        report.terminate_channel_open = self
+            .terminate_tx
+            .lock()
+            .await
+            .as_ref()
+            .is_some_and(|tx| !tx.is_closed());
+        report
+    }
+
+    /// Abort every watcher of the task and wait until they have stopped
+    ///
+    /// Unlike [`send_terminate_signal`](Self::send_terminate_signal), the task
+    /// is cancelled where it is, and no further event, not even
+    /// `TaskEvent::Stopped`, is sent. A running process is killed together
+    /// with its process group, or detached, as set with
+    /// [`set_cleanup_on_drop`](Self::set_cleanup_on_drop); its temporary
+    /// working directory is removed and the task is marked finished. Returns
+    /// at once for a task that was never started.
+    ///
+    /// # Errors
+    ///
+    /// [`TaskError::Handle`] naming each watcher that had failed, e.g. by
+    /// panicking, before it was aborted.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::{config::TaskConfig, async_tokio::spawner::TaskSpawner, state::TaskState};
+    /// use tokio::sync::mpsc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let config = if cfg!(windows) {
+    ///         TaskConfig::new("cmd").args(["/C", "timeout /t 10"])
+    ///     } else {
+    ///         TaskConfig::new("sleep").args(["10"])
+    ///     };
+    ///     let mut spawner = TaskSpawner::new("cancelled".to_string(), config);
+    ///
+    ///     let (tx, _rx) = mpsc::channel(100);
+    ///     spawner.start_direct(tx).await?;
+    ///     spawner.shutdown().await?;
+    ///
+    ///     assert_eq!(spawner.get_state().await, TaskState::Finished);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn shutdown(&mut self) -> Result<(), TaskError> {
+        let result = self.watchers.shutdown().await;
+
+        // Still set if the wait watcher was aborted before the task stopped
+        if let Some(pid) = self.process_id.write().await.take() {
+            if self.cleanup_on_drop
+                && let Some(process_group) = &self.process_group
+            {
+                terminate_stray_group(pid, process_group);
+            }
+            if let Some(dir) = &self.temp_dir {
+                temp_dir::remove(dir);
+            }
+            if self.update_state(TaskState::Finished).await {
+                *self.finished_at.write().await = Some(Instant::now());
+            }
+        }
+        result
+    }
+
     /// Update the state of the task
     ///
     /// Internal method used by the spawner to update task state during execution.
-    pub(crate) async fn update_state(&self, new_state: TaskState) {
-        let mut state = self.state.write().await;
-        *state = new_state;
+    /// Illegal transitions are ignored; see [`set_state`].
+    ///
+    /// Returns whether the transition was applied.
+    pub(crate) async fn update_state(&self, new_state: TaskState) -> bool {
+        set_state(&self.state, new_state).await
     }
 
     /// Send a termination signal to the running task
@@ -410,19 +1478,24 @@ impl TaskSpawner {
     /// Requests graceful termination of the running process with the specified reason.
     /// The process may take some time to respond to the termination signal.
     ///
+    /// The process is killed at once, unless
+    /// [`TaskConfig::graceful_shutdown_ms`](crate::tasks::config::TaskConfig::graceful_shutdown_ms)
+    /// gives it time to shut down on its own first.
+    ///
     /// # Arguments
     ///
     /// * `reason` - The reason for termination (Timeout, Cleanup, etc.)
     ///
     /// # Returns
     ///
-    /// - `Ok(())` if the termination signal was sent successfully
-    /// - `Err(TaskError::Channel)` if the signal could not be sent
+    /// A [`TerminateOutcome`]. Requesting termination again, or after the task
+    /// stopped, is not an error: only the first request that reaches a running
+    /// task decides its stop reason.
     ///
     /// # Errors
     ///
-    /// Returns a [`TaskError::Channel`] if the internal termination channel
-    /// has been closed and the signal cannot be delivered to the task.
+    /// Returns a [`TaskError::Channel`] of kind [`ChannelKind::Terminate`] and op
+    /// [`ChannelOp::Closed`] if the task was never started.
     ///
     /// # Examples
     /// ```rust
@@ -435,7 +1508,12 @@ impl TaskSpawner {
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    ///     let config = TaskConfig::new("cmd").args(["/C", "ping", "127.0.0.1", "-n", "10"]); // Long-running task
+    ///     // Long-running task
+    ///     let config = if cfg!(windows) {
+    ///         TaskConfig::new("cmd").args(["/C", "ping", "127.0.0.1", "-n", "10"])
+    ///     } else {
+    ///         TaskConfig::new("sleep").args(["10"])
+    ///     };
     ///     let mut spawner = TaskSpawner::new("terminate-test".to_string(), config);
     ///     
     ///     let (tx, mut rx) = mpsc::channel(100);
@@ -443,7 +1521,7 @@ impl TaskSpawner {
     ///     
     ///     // Wait a bit, then terminate
     ///     tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-    ///     spawner.send_terminate_signal(TaskTerminateReason::Cleanup).await?;
+    ///     spawner.send_terminate_signal(TaskTerminateReason::Cleanup { message: None }).await?;
     ///     
     ///     // Process events until stopped
     ///     while let Some(event) = rx.recv().await {
@@ -459,60 +1537,255 @@ impl TaskSpawner {
     pub async fn send_terminate_signal(
         &self,
         reason: TaskTerminateReason,
-    ) -> Result<(), TaskError> {
-        if let Some(tx) = self.terminate_tx.lock().await.take() {
-            if tx.send(reason.clone()).is_err() {
-                let msg = "Terminate channel closed while sending signal";
-                #[cfg(feature = "tracing")]
-                tracing::warn!(terminate_reason=?reason, msg);
-                return Err(TaskError::Channel(msg.to_string()));
-            }
-        } else {
-            let msg = "Terminate signal already sent or channel missing";
+    ) -> Result<TerminateOutcome, TaskError> {
+        if self.running_at.is_none() {
+            let msg = "Terminate signal sent to a task that was never started";
             #[cfg(feature = "tracing")]
             tracing::warn!(msg);
-            return Err(TaskError::Channel(msg.to_string()));
+            return Err(TaskError::Channel {
+                kind: ChannelKind::Terminate,
+                op: ChannelOp::Closed,
+                detail: msg.to_string(),
+            });
         }
+        Ok(self.terminate_handle().terminate(reason).await)
+    }
 
-        Ok(())
+    /// Handle sending termination requests to this task from elsewhere
+    pub(crate) fn terminate_handle(&self) -> TerminateHandle {
+        TerminateHandle {
+            terminate_tx: self.terminate_tx.clone(),
+            terminate_requests: self.terminate_requests.clone(),
+            state: self.state.clone(),
+        }
     }
-}
 
-/// Waits for all spawned task handles to complete, with a timeout
-///
-/// Returns an error if any handle fails or times out
-pub(crate) async fn join_all_handles(
-    task_handles: &mut Vec<JoinHandle<()>>,
-) -> Result<(), TaskError> {
-    if task_handles.is_empty() {
-        return Ok(());
+    /// Send an interrupt (Ctrl+C) to the running task
+    ///
+    /// Unlike [`send_terminate_signal`](Self::send_terminate_signal), the process is
+    /// not killed: it receives `SIGINT` on Unix, or `CTRL_BREAK_EVENT` on Windows, and
+    /// may clean up before exiting, or ignore it. The task stops through the normal
+    /// exit path: a process that handles the interrupt and exits is reported as
+    /// `Finished` with its own exit code, one killed by it is reported like any
+    /// other signal or console control exit.
+    ///
+    /// With process group management enabled, the whole process group receives the
+    /// interrupt, like pressing Ctrl+C in a terminal.
+    ///
+    /// On Linux and Windows, nothing is sent if the process ID now belongs to a
+    /// process started after the task's process, which already exited.
+    ///
+    /// # Errors
+    ///
+    /// - [`TaskError::Handle`] if the task has no running process, or the interrupt
+    ///   could not be sent
+    /// - On Windows, interrupting requires process group management; without it a
+    ///   [`TaskError::Handle`] is returned
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tcrm_task::tasks::{config::TaskConfig, async_tokio::spawner::TaskSpawner};
+    /// use tokio::sync::mpsc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let config = TaskConfig::new("sh").args(["-c", "trap 'exit 0' INT; sleep 10 & wait"]);
+    ///     let mut spawner = TaskSpawner::new("interrupt-test".to_string(), config);
+    ///
+    ///     let (tx, mut rx) = mpsc::channel(100);
+    ///     spawner.start_direct(tx).await?;
+    ///     spawner.send_interrupt_signal().await?;
+    ///
+    ///     while let Some(event) = rx.recv().await {
+    ///         if matches!(event, tcrm_task::tasks::event::TaskEvent::Stopped { .. }) {
+    ///             break;
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn send_interrupt_signal(&self) -> Result<(), TaskError> {
+        let Some(pid) = *self.process_id.read().await else {
+            let msg = "Task has no running process to interrupt";
+            #[cfg(feature = "tracing")]
+            tracing::warn!(msg);
+            return Err(TaskError::Handle(msg.to_string()));
+        };
+
+        match interrupt_process(
+            pid,
+            self.process_start_time,
+            self.config.is_process_group_enabled(),
+        ) {
+            #[allow(clippy::used_underscore_binding)]
+            Ok(_outcome) => {
+                #[cfg(feature = "tracing")]
+                if _outcome.stale_process {
+                    tracing::warn!(
+                        pid,
+                        "Process ID was reused by another process, interrupt not sent"
+                    );
+                } else {
+                    tracing::debug!(pid, outcome = ?_outcome, "Interrupt sent");
+                }
+                Ok(())
+            }
+            Err(e) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(pid, error = %e, "Failed to interrupt task");
+                Err(TaskError::Handle(e.to_string()))
+            }
+        }
     }
 
-    let handles = std::mem::take(task_handles);
-    let mut errors = Vec::new();
+    /// Pause the running task
+    ///
+    /// Suspends the whole process group if process group management is
+    /// enabled, otherwise only the process: with `SIGSTOP` on Unix, by
+    /// suspending the threads of the job object on Windows. The task state is
+    /// left untouched. With
+    /// [`TaskConfig::timeout_excludes_paused`](crate::tasks::config::TaskConfig::timeout_excludes_paused),
+    /// the timeout stops counting until [`resume_process`](Self::resume_process).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TaskError::Handle`] if:
+    /// - The task has no running process
+    /// - The process could not be suspended, or process group management is
+    ///   disabled on Windows
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tcrm_task::tasks::{config::TaskConfig, async_tokio::spawner::TaskSpawner};
+    /// use tokio::sync::mpsc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let config = TaskConfig::new("cargo")
+    ///         .args(["test"])
+    ///         .timeout_ms(60_000)
+    ///         .timeout_excludes_paused(true);
+    ///     let mut spawner = TaskSpawner::new("tests".to_string(), config);
+    ///
+    ///     let (tx, _rx) = mpsc::channel(100);
+    ///     spawner.start_direct(tx).await?;
+    ///     spawner.pause_process().await?;
+    ///     // The machine is busy with something else; the timeout waits too
+    ///     spawner.resume_process().await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn pause_process(&self) -> Result<(), TaskError> {
+        self.suspend(true).await
+    }
+
+    /// Resume the task after [`pause_process`](Self::pause_process)
+    ///
+    /// Resumes the whole process group if process group management is
+    /// enabled, otherwise only the process: with `SIGCONT` on Unix, by
+    /// resuming the threads of the job object on Windows.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TaskError::Handle`] if:
+    /// - The task has no running process
+    /// - The process could not be resumed, or process group management is
+    ///   disabled on Windows
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn resume_process(&self) -> Result<(), TaskError> {
+        self.suspend(false).await
+    }
 
-    for mut handle in handles {
-        match timeout(Duration::from_secs(5), &mut handle).await {
-            Ok(Ok(())) => {}
-            Ok(Err(join_err)) => {
-                let err_msg = format!("Handle [{}] join failed: {:?}", handle.id(), join_err);
+    /// Suspends or resumes the process or its group, recording the pause
+    async fn suspend(&self, suspend: bool) -> Result<(), TaskError> {
+        let action = if suspend { "pause" } else { "resume" };
+        let Some(pid) = *self.process_id.read().await else {
+            let msg = format!("Task has no running process to {action}");
+            #[cfg(feature = "tracing")]
+            tracing::warn!(msg);
+            return Err(TaskError::Handle(msg));
+        };
 
-                errors.push(err_msg);
+        let result = match &self.process_group {
+            Some(process_group) => {
+                let signal = if suspend {
+                    ProcessSignal::Pause
+                } else {
+                    ProcessSignal::Resume
+                };
+                process_group.send_signal(signal).await
+            }
+            None => suspend_process(pid, self.process_start_time, suspend),
+        };
+        match result {
+            Ok(outcome) => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(pid, ?outcome, "Sent {action}");
+                if outcome.delivered {
+                    if suspend {
+                        self.pause_clock.pause();
+                    } else {
+                        self.pause_clock.resume();
+                    }
+                }
+                Ok(())
             }
-            Err(_) => {
-                let err_msg = format!("Handle [{}] join timeout, aborting", handle.id());
-                handle.abort(); // ensure it’s killed
-                errors.push(err_msg);
+            Err(e) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(pid, error = %e, "Failed to {action} task");
+                Err(TaskError::Handle(e.to_string()))
             }
         }
     }
+}
 
-    if !errors.is_empty() {
-        return Err(TaskError::Handle(format!(
-            "Multiple task handles join failures: {}",
-            errors.join("; ")
-        )));
+impl Drop for TaskSpawner {
+    /// Detaches the watchers, or aborts them with structured concurrency, and
+    /// terminates the process group left behind if the wait watcher was aborted
+    ///
+    /// The wait watcher clears the process ID once it has sent
+    /// `TaskEvent::Stopped`, so a finished watcher with the ID still set was
+    /// aborted before the task stopped.
+    fn drop(&mut self) {
+        if !self.structured_concurrency {
+            self.watchers.detach_all();
+        }
+        if !self.cleanup_on_drop {
+            return;
+        }
+        let Some(process_group) = &self.process_group else {
+            return;
+        };
+        // With structured concurrency, the wait watcher is aborted with the set
+        let aborted = self.structured_concurrency || self.watchers.is_finished("wait");
+        if !aborted {
+            return;
+        }
+        if let Ok(pid) = self.process_id.try_read()
+            && let Some(pid) = *pid
+        {
+            terminate_stray_group(pid, process_group);
+        }
     }
+}
 
-    Ok(())
+/// Moves the shared task state to `new_state` if the transition is legal
+///
+/// Illegal transitions (e.g. `Finished → Ready` from a late output line) are
+/// logged and ignored, leaving the current state untouched.
+///
+/// Returns whether the transition was applied.
+pub(crate) async fn set_state(state: &RwLock<TaskState>, new_state: TaskState) -> bool {
+    let mut state = state.write().await;
+    if !state.can_transition_to(&new_state) {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(from = ?*state, to = ?new_state, "Ignoring illegal task state transition");
+        return false;
+    }
+    *state = new_state;
+    true
 }
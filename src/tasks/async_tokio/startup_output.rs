@@ -0,0 +1,133 @@
+//! Capture of the first output lines of a task.
+//!
+//! When a task fails quickly, its first lines are usually the most useful
+//! context, but the event consumer may already have discarded them. The
+//! output watchers keep a copy of them in a [`StartupOutput`] buffer, read
+//! with `TaskSpawner::startup_output` and included in `TaskInfo` when the
+//! task fails before becoming ready.
+
+use std::sync::{Arc, Mutex};
+
+use crate::tasks::config::StreamSource;
+
+/// Default number of output lines captured at startup
+pub(crate) const DEFAULT_STARTUP_CAPTURE_LINES: usize = 50;
+
+/// Most bytes of output captured at startup, across both streams
+pub(crate) const STARTUP_CAPTURE_MAX_BYTES: usize = 64 * 1024;
+
+/// A line of output captured at startup
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedLine {
+    /// Source stream (stdout or stderr)
+    pub src: StreamSource,
+    /// The output line (without trailing newline), as sent in `TaskEvent::Output`
+    pub line: String,
+}
+
+/// The first output lines of a task, from both streams in the order read
+///
+/// Holds at most `max_lines` lines and `STARTUP_CAPTURE_MAX_BYTES` bytes. The
+/// line crossing the byte cap is cut at a character boundary, and nothing is
+/// captured after it.
+#[derive(Debug, Default)]
+pub(crate) struct StartupOutput {
+    lines: Vec<CapturedLine>,
+    max_lines: usize,
+    bytes: usize,
+    full: bool,
+}
+
+/// Startup output shared by the output watchers of a task
+pub(crate) type SharedStartupOutput = Arc<Mutex<StartupOutput>>;
+
+impl StartupOutput {
+    /// Creates an empty buffer capturing up to `max_lines` lines
+    pub(crate) fn new(max_lines: usize) -> Self {
+        Self {
+            lines: Vec::new(),
+            max_lines,
+            bytes: 0,
+            full: max_lines == 0,
+        }
+    }
+
+    /// Captures `line` unless the buffer is full
+    ///
+    /// # Returns
+    ///
+    /// `false` once the buffer is full, so callers can stop offering lines.
+    pub(crate) fn push(&mut self, src: &StreamSource, line: &str) -> bool {
+        if self.full {
+            return false;
+        }
+        let remaining = STARTUP_CAPTURE_MAX_BYTES - self.bytes;
+        let mut end = line.len().min(remaining);
+        while !line.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.bytes += end;
+        self.lines.push(CapturedLine {
+            src: src.clone(),
+            line: line[..end].to_string(),
+        });
+        self.full = self.lines.len() >= self.max_lines || end < line.len();
+        !self.full
+    }
+
+    /// The lines captured so far
+    pub(crate) fn lines(&self) -> &[CapturedLine] {
+        &self.lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn captures_up_to_max_lines() {
+        let mut output = StartupOutput::new(2);
+
+        assert!(output.push(&StreamSource::Stdout, "first"));
+        assert!(!output.push(&StreamSource::Stderr, "second"));
+        assert!(!output.push(&StreamSource::Stdout, "third"));
+
+        assert_eq!(
+            output.lines(),
+            [
+                CapturedLine {
+                    src: StreamSource::Stdout,
+                    line: "first".to_string(),
+                },
+                CapturedLine {
+                    src: StreamSource::Stderr,
+                    line: "second".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn line_crossing_byte_cap_is_cut_at_char_boundary() {
+        let mut output = StartupOutput::new(10);
+        let filler = "x".repeat(STARTUP_CAPTURE_MAX_BYTES - 1);
+
+        assert!(output.push(&StreamSource::Stdout, &filler));
+        // Two-byte character that does not fit in the last byte
+        assert!(!output.push(&StreamSource::Stdout, "\u{e9}tat"));
+        assert!(!output.push(&StreamSource::Stdout, "after"));
+
+        assert_eq!(output.lines().len(), 2);
+        assert_eq!(output.lines()[1].line, "");
+    }
+
+    #[test]
+    fn zero_lines_captures_nothing() {
+        let mut output = StartupOutput::new(0);
+
+        assert!(!output.push(&StreamSource::Stdout, "line"));
+        assert!(output.lines().is_empty());
+    }
+}
@@ -0,0 +1,116 @@
+//! Input written to the stdin of a task.
+//!
+//! Lines sent through the channel of `TaskSpawner::set_stdin` are plain
+//! strings. The channel of `TaskSpawner::set_stdin_input` carries
+//! [`StdinInput`] instead, which can mark an input as secret, e.g. a password
+//! answering a prompt: it is written to the process like any other input, but
+//! logs only ever show [`REDACTED_STDIN`] and its length in bytes.
+
+use std::fmt;
+
+use tokio::sync::mpsc::{self, error::TryRecvError};
+
+/// Marker shown instead of the data of a secret [`StdinInput`]
+pub const REDACTED_STDIN: &str = "[REDACTED]";
+
+/// A line for the stdin of a task, which may be secret
+///
+/// A trailing newline is added when written if missing. The `Debug` output of
+/// a secret input, which tracing logs use, holds [`REDACTED_STDIN`] and the
+/// byte count in place of the data. Inputs never appear in task events, so a
+/// secret one is not recorded anywhere else.
+///
+/// # Examples
+/// ```rust
+/// use tcrm_task::tasks::async_tokio::stdin::StdinInput;
+///
+/// let input = StdinInput::secret("hunter2");
+/// assert_eq!(
+///     format!("{input:?}"),
+///     r#"StdinInput { data: "[REDACTED]", bytes: 7, secret: true }"#
+/// );
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct StdinInput {
+    /// The line to write
+    pub data: String,
+    /// Whether logs show [`REDACTED_STDIN`] instead of `data`
+    pub secret: bool,
+}
+
+impl StdinInput {
+    /// Create an input that may be logged
+    pub fn line(data: impl Into<String>) -> Self {
+        StdinInput {
+            data: data.into(),
+            secret: false,
+        }
+    }
+
+    /// Create an input that is never logged, such as a password
+    pub fn secret(data: impl Into<String>) -> Self {
+        StdinInput {
+            data: data.into(),
+            secret: true,
+        }
+    }
+}
+
+impl From<String> for StdinInput {
+    fn from(data: String) -> Self {
+        StdinInput::line(data)
+    }
+}
+
+impl From<&str> for StdinInput {
+    fn from(data: &str) -> Self {
+        StdinInput::line(data)
+    }
+}
+
+impl fmt::Debug for StdinInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let data = if self.secret {
+            REDACTED_STDIN
+        } else {
+            &self.data
+        };
+        f.debug_struct("StdinInput")
+            .field("data", &data)
+            .field("bytes", &self.data.len())
+            .field("secret", &self.secret)
+            .finish()
+    }
+}
+
+/// Channel the stdin watcher reads inputs from
+#[derive(Debug)]
+pub(crate) enum StdinReceiver {
+    /// Plain lines, from `TaskSpawner::set_stdin` or the stdin script
+    Lines(mpsc::Receiver<String>),
+    /// Inputs from `TaskSpawner::set_stdin_input`
+    Inputs(mpsc::Receiver<StdinInput>),
+}
+
+impl StdinReceiver {
+    pub(crate) async fn recv(&mut self) -> Option<StdinInput> {
+        match self {
+            StdinReceiver::Lines(rx) => rx.recv().await.map(StdinInput::from),
+            StdinReceiver::Inputs(rx) => rx.recv().await,
+        }
+    }
+
+    pub(crate) fn try_recv(&mut self) -> Result<StdinInput, TryRecvError> {
+        match self {
+            StdinReceiver::Lines(rx) => rx.try_recv().map(StdinInput::from),
+            StdinReceiver::Inputs(rx) => rx.try_recv(),
+        }
+    }
+
+    pub(crate) fn close(&mut self) {
+        match self {
+            StdinReceiver::Lines(rx) => rx.close(),
+            StdinReceiver::Inputs(rx) => rx.close(),
+        }
+    }
+}
@@ -0,0 +1,394 @@
+//! Groups of related tasks with shared cancellation.
+//!
+//! A [`TaskGroup`] orchestrates the `TaskSpawner`s started through it: it can
+//! terminate all of them at once, wait for all of them to stop, and
+//! summarizes how each one stopped in a [`GroupSummary`]. With
+//! [`FailurePolicy::CancelSiblings`], the first failing task terminates the
+//! others.
+//!
+//! This is independent of the OS-level [`ProcessGroup`](super::process_group::ProcessGroup):
+//! members are terminated through their spawners, so it works the same with
+//! `use_process_group(false)`.
+
+use std::sync::{
+    Arc, Mutex, PoisonError,
+    atomic::{AtomicBool, Ordering},
+};
+
+use tokio::{
+    runtime::Handle,
+    sync::mpsc,
+    task::{JoinHandle, JoinSet},
+};
+
+use crate::tasks::{
+    async_tokio::{
+        direct::watchers::result::is_failure,
+        spawner::{TaskSpawner, TerminateHandle, TerminateOutcome},
+    },
+    error::TaskError,
+    event::{TaskEvent, TaskEventStopReason, TaskTerminateReason},
+};
+
+/// What a [`TaskGroup`] does when one of its tasks fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FailurePolicy {
+    /// Let the other tasks run to completion (default)
+    #[default]
+    Continue,
+    /// Terminate the other tasks with `TaskTerminateReason::Cleanup`, and
+    /// refuse to start new ones
+    CancelSiblings,
+}
+
+/// How a task of a [`TaskGroup`] stopped
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskResult {
+    /// Name of the task
+    pub task_name: Arc<str>,
+    /// Exit code, as in `TaskEvent::Stopped`
+    pub exit_code: Option<i32>,
+    /// Reason the task stopped
+    ///
+    /// An `Error` with the error message if the task failed to start.
+    pub reason: TaskEventStopReason,
+    /// Time from spawning the process until it stopped, in milliseconds
+    pub duration_ms: u64,
+}
+
+impl TaskResult {
+    /// Whether the task failed
+    ///
+    /// A task fails if it exited with a non-zero exit code, stopped with an
+    /// error or a startup failure, or was terminated by its timeout. Tasks
+    /// terminated on request, including siblings cancelled by the group, did
+    /// not fail. A task stopped by its completion indicator fails unless
+    /// `TaskConfig::completion_success` reported it with exit code 0.
+    #[must_use]
+    pub fn is_failure(&self) -> bool {
+        is_failure(self.exit_code, &self.reason)
+    }
+}
+
+/// How the tasks of a [`TaskGroup`] stopped, returned by [`TaskGroup::wait_all`]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GroupSummary {
+    /// Results of the tasks that stopped, in the order they were started
+    pub per_task: Vec<TaskResult>,
+    /// The first task to fail, in the order they stopped
+    pub first_failure: Option<TaskResult>,
+}
+
+impl GroupSummary {
+    /// Whether no task of the group failed
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.first_failure.is_none()
+    }
+}
+
+/// A task started through the group
+#[derive(Debug)]
+struct Member {
+    terminate: TerminateHandle,
+    result: Option<TaskResult>,
+}
+
+/// State shared between the group and the event forwarders of its tasks
+#[derive(Debug, Default)]
+struct GroupShared {
+    members: Mutex<Vec<Member>>,
+    first_failure: Mutex<Option<TaskResult>>,
+    cancelled: AtomicBool,
+}
+
+impl GroupShared {
+    /// Records the result of member `index`, applying `policy` if it failed
+    async fn record(&self, index: usize, result: TaskResult, policy: FailurePolicy) {
+        let failed = result.is_failure();
+        if failed {
+            self.first_failure
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .get_or_insert_with(|| result.clone());
+        }
+        self.members.lock().unwrap_or_else(PoisonError::into_inner)[index].result = Some(result);
+
+        if failed && policy == FailurePolicy::CancelSiblings {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(index, "Task of group failed, cancelling siblings");
+            self.terminate(TaskTerminateReason::Cleanup { message: None }, Some(index))
+                .await;
+        }
+    }
+
+    /// Sends a termination request to every member except `except`
+    ///
+    /// The members are terminated concurrently, so a slow one does not hold
+    /// back its siblings. Returns how many members were still running and
+    /// received it.
+    async fn terminate(&self, reason: TaskTerminateReason, except: Option<usize>) -> usize {
+        self.cancelled.store(true, Ordering::SeqCst);
+        let handles: Vec<TerminateHandle> = self
+            .members
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .enumerate()
+            .filter(|(index, member)| Some(*index) != except && member.result.is_none())
+            .map(|(_, member)| member.terminate.clone())
+            .collect();
+
+        let mut requests = JoinSet::new();
+        for handle in handles {
+            let reason = reason.clone();
+            // Only the first request reaches a task, so repeated ones are a no-op
+            requests.spawn(async move { handle.terminate(reason).await });
+        }
+        let mut terminated = 0;
+        while let Some(outcome) = requests.join_next().await {
+            if matches!(outcome, Ok(TerminateOutcome::SignalSent)) {
+                terminated += 1;
+            }
+        }
+        terminated
+    }
+}
+
+/// A unit of related tasks with shared cancellation
+///
+/// Tasks join the group by being started with [`start`](Self::start), which
+/// forwards their events to the given channel and records how they stop.
+///
+/// # Examples
+/// ```rust,no_run
+/// use tcrm_task::tasks::{
+///     async_tokio::{
+///         spawner::TaskSpawner,
+///         task_group::{FailurePolicy, TaskGroup},
+///     },
+///     config::TaskConfig,
+/// };
+/// use tokio::sync::mpsc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let group = TaskGroup::new().set_failure_policy(FailurePolicy::CancelSiblings);
+///     let (tx, _rx) = mpsc::channel(100);
+///
+///     let mut api = TaskSpawner::new("api".to_string(), TaskConfig::new("api-server"));
+///     let mut worker = TaskSpawner::new("worker".to_string(), TaskConfig::new("worker"));
+///     group.start(&mut api, tx.clone()).await?;
+///     group.start(&mut worker, tx).await?;
+///
+///     // If either one fails, the other is terminated
+///     let summary = group.wait_all().await;
+///     if let Some(failure) = summary.first_failure {
+///         eprintln!("{} failed: {:?}", failure.task_name, failure.reason);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct TaskGroup {
+    policy: FailurePolicy,
+    shared: Arc<GroupShared>,
+    forwarders: Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl TaskGroup {
+    /// Create an empty group with [`FailurePolicy::Continue`]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set what the group does when one of its tasks fails
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - Policy applied when a task fails, see [`TaskResult::is_failure`]
+    #[must_use]
+    pub fn set_failure_policy(mut self, policy: FailurePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Start a task as a member of the group
+    ///
+    /// Starts the task as with `start_direct`. Its events are forwarded to
+    /// `event_tx` through a separate tokio task, which records the result of
+    /// the task when `TaskEvent::Stopped` arrives. A task that fails to start
+    /// is recorded as failed with the error message.
+    ///
+    /// # Arguments
+    ///
+    /// * `spawner` - Task to start; it must not have been started yet
+    /// * `event_tx` - Channel sender for receiving the task events
+    ///
+    /// # Returns
+    ///
+    /// The process ID of the started task.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TaskError::Handle`] without starting the task if the group
+    /// has been terminated or cancelled, or any error of `start_direct`.
+    pub async fn start(
+        &self,
+        spawner: &mut TaskSpawner,
+        event_tx: mpsc::Sender<TaskEvent>,
+    ) -> Result<u32, TaskError> {
+        if self.shared.cancelled.load(Ordering::SeqCst) {
+            return Err(TaskError::Handle(format!(
+                "Task group was cancelled, not starting task {}",
+                spawner.task_name
+            )));
+        }
+        let index = {
+            let mut members = self
+                .shared
+                .members
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            members.push(Member {
+                terminate: spawner.terminate_handle(),
+                result: None,
+            });
+            members.len() - 1
+        };
+
+        let (run_tx, run_rx) = mpsc::channel(event_tx.max_capacity());
+        let forwarder = {
+            let _runtime = spawner.runtime.as_ref().map(Handle::enter);
+            tokio::spawn(forward_and_record(
+                run_rx,
+                event_tx,
+                self.shared.clone(),
+                index,
+                self.policy,
+            ))
+        };
+        self.forwarders
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(forwarder);
+
+        match spawner.start_direct(run_tx).await {
+            Ok(pid) => {
+                // A sibling may have failed while the task was starting
+                if self.shared.cancelled.load(Ordering::SeqCst) {
+                    self.shared
+                        .terminate(TaskTerminateReason::Cleanup { message: None }, None)
+                        .await;
+                }
+                Ok(pid)
+            }
+            Err(e) => {
+                let result = TaskResult {
+                    task_name: spawner.task_name.clone(),
+                    exit_code: None,
+                    reason: TaskEventStopReason::Error(e.to_string()),
+                    duration_ms: 0,
+                };
+                self.shared.record(index, result, self.policy).await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Terminate every running task of the group
+    ///
+    /// Termination requests are sent to all members without waiting for any
+    /// of them to stop, so they are terminated concurrently. Members that
+    /// already stopped or were already asked to stop are skipped, which makes
+    /// repeated calls harmless. No task can be started in the group afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `reason` - Reason reported in the `TaskEvent::Stopped` of each member
+    ///
+    /// # Returns
+    ///
+    /// The number of members that received the request.
+    pub async fn terminate_all(&self, reason: TaskTerminateReason) -> usize {
+        self.shared.terminate(reason, None).await
+    }
+
+    /// Wait for every task of the group to stop and summarize their results
+    ///
+    /// Waits until the events of every member have been forwarded, so it can
+    /// wait indefinitely for tasks without a timeout. Members whose result was
+    /// already summarized by a previous call are included again.
+    pub async fn wait_all(&self) -> GroupSummary {
+        let forwarders = std::mem::take(
+            &mut *self
+                .forwarders
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+        for forwarder in forwarders {
+            if let Err(_e) = forwarder.await {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(error = %_e, "Task group event forwarder failed");
+            }
+        }
+
+        let per_task = self
+            .shared
+            .members
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .filter_map(|member| member.result.clone())
+            .collect();
+        let first_failure = self
+            .shared
+            .first_failure
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        GroupSummary {
+            per_task,
+            first_failure,
+        }
+    }
+}
+
+/// Forwards the events of a member, recording its result when it stops
+///
+/// The result is recorded before `TaskEvent::Stopped` is forwarded. Keeps
+/// draining `run_rx` after `event_tx` is closed, so the result is recorded
+/// either way.
+async fn forward_and_record(
+    mut run_rx: mpsc::Receiver<TaskEvent>,
+    event_tx: mpsc::Sender<TaskEvent>,
+    shared: Arc<GroupShared>,
+    index: usize,
+    policy: FailurePolicy,
+) {
+    while let Some(event) = run_rx.recv().await {
+        if let TaskEvent::Stopped {
+            task_name,
+            exit_code,
+            reason,
+            duration_ms,
+            ..
+        } = &event
+        {
+            let result = TaskResult {
+                task_name: task_name.clone(),
+                exit_code: *exit_code,
+                reason: reason.clone(),
+                duration_ms: *duration_ms,
+            };
+            shared.record(index, result, policy).await;
+        }
+        if event_tx.send(event).await.is_err() {
+            #[cfg(feature = "tracing")]
+            tracing::debug!("Event channel closed while forwarding events of a grouped task");
+        }
+    }
+}
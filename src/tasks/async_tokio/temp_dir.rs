@@ -0,0 +1,101 @@
+//! Temporary working directories of tasks.
+//!
+//! With `TaskConfig::temp_working_dir` set, a directory is created in the
+//! system temporary directory before the process is spawned and removed
+//! once the task stopped. Removal is best-effort: a failure is logged and
+//! leaves the directory behind, but never fails the task.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Prefix of the directory name when none is configured
+pub(crate) const DEFAULT_TEMP_DIR_PREFIX: &str = "tcrm-";
+
+/// Names tried before giving up on finding an unused one
+const MAX_ATTEMPTS: u32 = 16;
+
+/// Distinguishes directories created by this process
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Creates a new, empty directory named `prefix` and a unique suffix
+///
+/// # Errors
+///
+/// Returns the error of creating the directory, or `AlreadyExists` if no
+/// unused name was found.
+pub(crate) fn create(prefix: &str) -> io::Result<PathBuf> {
+    let base = std::env::temp_dir();
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.subsec_nanos());
+    for _ in 0..MAX_ATTEMPTS {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let path = base.join(format!("{prefix}{}-{id}-{nanos:08x}", std::process::id()));
+        match std::fs::create_dir(&path) {
+            Ok(()) => return Ok(path),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        "No unused temporary directory name found",
+    ))
+}
+
+/// Removes `path` with everything in it, logging a failure
+pub(crate) fn remove(path: &Path) {
+    #[allow(clippy::used_underscore_binding)]
+    match std::fs::remove_dir_all(path) {
+        Ok(()) => {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(path = %path.display(), "Removed temporary working directory");
+        }
+        Err(_e) => {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(path = %path.display(), error = %_e, "Failed to remove temporary working directory");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn creates_unique_empty_directories() {
+        let first = create("tcrm-unit-").unwrap();
+        let second = create("tcrm-unit-").unwrap();
+
+        assert_ne!(first, second);
+        for dir in [&first, &second] {
+            assert!(dir.is_dir());
+            assert_eq!(dir.parent(), Some(std::env::temp_dir().as_path()));
+            assert!(
+                dir.file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .starts_with("tcrm-unit-")
+            );
+            assert_eq!(std::fs::read_dir(dir).unwrap().count(), 0);
+        }
+
+        std::fs::write(first.join("file"), "content").unwrap();
+        remove(&first);
+        remove(&second);
+        assert!(!first.exists());
+        assert!(!second.exists());
+    }
+
+    #[test]
+    fn removing_missing_directory_is_harmless() {
+        let dir = create("tcrm-unit-").unwrap();
+        remove(&dir);
+        remove(&dir);
+        assert!(!dir.exists());
+    }
+}
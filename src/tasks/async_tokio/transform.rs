@@ -0,0 +1,137 @@
+//! Per-line transformation of task output.
+//!
+//! An [`OutputTransform`] rewrites or drops each output line before it is
+//! sent as `TaskEvent::Output`, for example to redact secrets so they never
+//! reach the event channel.
+
+use std::{
+    borrow::Cow,
+    fmt,
+    panic::{AssertUnwindSafe, catch_unwind},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use crate::tasks::config::StreamSource;
+
+/// Function applied to each output line
+///
+/// Returns `Some` to replace the line, or `None` to drop it.
+pub type OutputTransformFn = dyn Fn(&str, StreamSource) -> Option<String> + Send + Sync;
+
+/// Rewrites or drops output lines before they are sent as events
+///
+/// By default the ready indicator is matched against the original line, so a
+/// transform that redacts or drops lines cannot prevent the task from becoming
+/// ready. Use [`OutputTransform::before_ready_check`] to match the transformed
+/// line instead.
+///
+/// If the function panics, the panic is caught and the transform is disabled
+/// for the rest of that task: the offending line and every following line are
+/// sent unchanged.
+///
+/// # Examples
+/// ```rust
+/// use tcrm_task::tasks::{
+///     async_tokio::{spawner::TaskSpawner, transform::OutputTransform},
+///     config::TaskConfig,
+/// };
+///
+/// let transform = OutputTransform::new(|line, _src| {
+///     if line.starts_with("debug:") {
+///         None
+///     } else {
+///         Some(line.replace("hunter2", "[REDACTED]"))
+///     }
+/// });
+/// let spawner = TaskSpawner::new("server".to_string(), TaskConfig::new("my-server"))
+///     .set_output_transform(transform);
+/// ```
+#[derive(Clone)]
+pub struct OutputTransform {
+    func: Arc<OutputTransformFn>,
+    before_ready_check: bool,
+    disabled: Arc<AtomicBool>,
+}
+
+impl OutputTransform {
+    /// Create a transform from a function applied to each output line
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - Called with each line and its stream; returns the replacement line, or `None` to drop it
+    pub fn new<F>(func: F) -> Self
+    where
+        F: Fn(&str, StreamSource) -> Option<String> + Send + Sync + 'static,
+    {
+        Self::from_arc(Arc::new(func))
+    }
+
+    /// Create a transform from a shared function
+    pub fn from_arc(func: Arc<OutputTransformFn>) -> Self {
+        Self {
+            func,
+            before_ready_check: false,
+            disabled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Set whether the transform runs before the ready indicator is matched
+    ///
+    /// With `true`, the ready indicator is matched against the transformed
+    /// line, and a dropped line never marks the task ready. Defaults to `false`.
+    #[must_use]
+    pub fn before_ready_check(mut self, before_ready_check: bool) -> Self {
+        self.before_ready_check = before_ready_check;
+        self
+    }
+
+    /// Whether the transform runs before the ready indicator is matched
+    pub fn runs_before_ready_check(&self) -> bool {
+        self.before_ready_check
+    }
+
+    /// Returns a copy with its own disabled flag, so a panic in one task does
+    /// not disable the transform for other tasks sharing the function
+    pub(crate) fn for_task(&self) -> Self {
+        Self {
+            func: self.func.clone(),
+            before_ready_check: self.before_ready_check,
+            disabled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Whether the transform was disabled after its function panicked
+    pub(crate) fn is_disabled(&self) -> bool {
+        self.disabled.load(Ordering::Relaxed)
+    }
+
+    /// Applies the transform to a line
+    ///
+    /// Returns the line unchanged once the transform has been disabled.
+    pub(crate) fn apply<'a>(&self, line: &'a str, src: &StreamSource) -> Option<Cow<'a, str>> {
+        if self.is_disabled() {
+            return Some(Cow::Borrowed(line));
+        }
+        match catch_unwind(AssertUnwindSafe(|| (self.func)(line, src.clone()))) {
+            Ok(result) => result.map(Cow::Owned),
+            Err(_) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(stream = ?src, "Output transform panicked, disabling it for this task");
+                self.disabled.store(true, Ordering::Relaxed);
+                Some(Cow::Borrowed(line))
+            }
+        }
+    }
+}
+
+impl fmt::Debug for OutputTransform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OutputTransform")
+            .field("before_ready_check", &self.before_ready_check)
+            .field("disabled", &self.is_disabled())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,50 @@
+use crate::tasks::async_tokio::crash_loop::{AttemptRecord, CrashLoopDetector, CrashLoopPolicy};
+
+fn detector(max_consecutive: u32) -> CrashLoopDetector {
+    CrashLoopDetector::new(CrashLoopPolicy {
+        min_uptime_ms: 1000,
+        max_consecutive,
+    })
+}
+
+#[test]
+fn long_or_successful_attempt_resets_the_count() {
+    let mut detector = detector(3);
+    assert!(!detector.record(Some(1), 10));
+    assert!(!detector.record(None, 10));
+    assert!(!detector.record(Some(1), 5_000));
+    assert_eq!(detector.consecutive_crashes(), 0);
+    assert!(!detector.record(Some(1), 10));
+    assert!(!detector.record(Some(0), 10));
+    assert_eq!(detector.consecutive_crashes(), 0);
+
+    assert!(!detector.record(Some(2), 10));
+    assert!(!detector.record(Some(2), 10));
+    assert!(detector.record(Some(2), 10));
+    assert_eq!(detector.history().len(), 8);
+    assert_eq!(
+        detector.history().last(),
+        Some(&AttemptRecord {
+            exit_code: Some(2),
+            duration_ms: 10,
+        })
+    );
+}
+
+#[test]
+fn zero_threshold_is_treated_as_one() {
+    let mut detector = detector(0);
+    assert!(!detector.is_crash_loop());
+    assert!(detector.record(Some(1), 10));
+}
+
+#[test]
+fn history_is_bounded() {
+    let mut detector = detector(u32::MAX);
+    for duration_ms in 0..100 {
+        detector.record(Some(1), duration_ms);
+    }
+    assert_eq!(detector.history().len(), 64);
+    assert_eq!(detector.history()[0].duration_ms, 36);
+    assert_eq!(detector.consecutive_crashes(), 100);
+}
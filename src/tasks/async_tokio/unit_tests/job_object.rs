@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use tokio::process::Command;
+
+use crate::tasks::async_tokio::process_group::{ControlOutcome, ProcessGroup, ProcessGroupError};
+use crate::tasks::async_tokio::process_tree::GroupMembers;
+
+/// Job Object name unique to this test process
+fn unique_name(name: &str) -> String {
+    format!("Local\\tcrm_task_{name}_{}", std::process::id())
+}
+
+#[tokio::test]
+async fn named_job_terminated_through_opened_group() {
+    let name = unique_name("terminate");
+    let mut cmd = Command::new("ping");
+    cmd.args(["-n", "30", "127.0.0.1"]);
+    let (mut cmd, group) = ProcessGroup::create_with_command_and_name(cmd, Some(&name)).unwrap();
+    let mut child = cmd.spawn().unwrap();
+    group.assign_child(&child).await.unwrap();
+
+    let opened = ProcessGroup::open_named(&name).unwrap();
+    assert_eq!(
+        opened.terminate_all().await.unwrap(),
+        ControlOutcome::DELIVERED
+    );
+    assert!(!child.wait().await.unwrap().success());
+
+    // The opened group only has terminate and query rights
+    let other = Command::new("cmd").args(["/C", "exit 0"]).spawn().unwrap();
+    assert!(matches!(
+        opened.assign_child(&other).await,
+        Err(ProcessGroupError::AssignmentFailed(_))
+    ));
+}
+
+#[tokio::test]
+async fn named_job_collision_and_missing_job() {
+    let name = unique_name("collision");
+    let (_cmd, _group) =
+        ProcessGroup::create_with_command_and_name(Command::new("cmd"), Some(&name)).unwrap();
+
+    match ProcessGroup::create_with_command_and_name(Command::new("cmd"), Some(&name)) {
+        Err(ProcessGroupError::CreationFailed(message)) => {
+            assert!(message.contains("code 183"), "{message}");
+        }
+        other => panic!("Expected CreationFailed, got {:?}", other.map(|_| ())),
+    }
+    assert!(matches!(
+        ProcessGroup::open_named(&unique_name("missing")),
+        Err(ProcessGroupError::AttachFailed(_))
+    ));
+}
+
+#[tokio::test]
+async fn child_started_right_away_is_in_the_job() {
+    // The grandchild is started as the first thing the shell does, so it only
+    // lands in the job if the shell was suspended until it was assigned
+    let mut cmd = Command::new("cmd");
+    cmd.args([
+        "/C",
+        "start /B ping -n 30 127.0.0.1 >NUL & ping -n 30 127.0.0.1 >NUL",
+    ]);
+    let (mut cmd, group) = ProcessGroup::create_with_command(cmd).unwrap();
+    let mut child = cmd.spawn().unwrap();
+    group.assign_child(&child).await.unwrap();
+
+    let mut members = 0;
+    for _ in 0..50 {
+        if let GroupMembers::Listed(pids) = group.members().await {
+            members = pids.len();
+        }
+        if members >= 3 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert!(
+        members >= 3,
+        "Expected the shell and both pings in the job, got {members}"
+    );
+
+    assert_eq!(
+        group.terminate_all().await.unwrap(),
+        ControlOutcome::DELIVERED
+    );
+    assert!(!child.wait().await.unwrap().success());
+
+    // The pings die with the job
+    let mut members = usize::MAX;
+    for _ in 0..50 {
+        if let GroupMembers::Listed(pids) = group.members().await {
+            members = pids.len();
+        }
+        if members == 0 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(members, 0, "Processes left in the job after termination");
+}
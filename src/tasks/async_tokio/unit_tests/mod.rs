@@ -1 +1,6 @@
+mod crash_loop;
+#[cfg(windows)]
+mod job_object;
+#[cfg(unix)]
+mod process_group;
 mod spawner;
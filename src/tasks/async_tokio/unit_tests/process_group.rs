@@ -0,0 +1,118 @@
+use tokio::process::Command;
+
+use crate::tasks::async_tokio::process_group::{ControlOutcome, ProcessGroup, ProcessGroupError};
+
+async fn spawn_in_group(program: &str, args: &[&str]) -> (tokio::process::Child, ProcessGroup) {
+    let mut cmd = Command::new(program);
+    cmd.args(args);
+    let (mut cmd, group) = ProcessGroup::create_with_command(cmd).unwrap();
+    let child = cmd.spawn().unwrap();
+    group.assign_child(&child).await.unwrap();
+    (child, group)
+}
+
+#[tokio::test]
+async fn signal_to_running_group_is_delivered() {
+    let (mut child, group) = spawn_in_group("sleep", &["30"]).await;
+
+    assert_eq!(group.terminate_all().await.unwrap(), ControlOutcome::DELIVERED);
+    assert!(!child.wait().await.unwrap().success());
+}
+
+#[tokio::test]
+async fn signal_to_exited_group_reports_already_exited() {
+    let (mut child, group) = spawn_in_group("true", &[]).await;
+    assert!(child.wait().await.unwrap().success());
+
+    for outcome in [
+        group.terminate_all().await,
+        group.pause_all().await,
+        group.resume_all().await,
+    ] {
+        assert_eq!(outcome.unwrap(), ControlOutcome::ALREADY_EXITED);
+    }
+}
+
+#[tokio::test]
+async fn pause_and_resume_running_group() {
+    let (mut child, group) = spawn_in_group("sleep", &["30"]).await;
+
+    assert_eq!(group.pause_all().await.unwrap(), ControlOutcome::DELIVERED);
+    assert_eq!(group.resume_all().await.unwrap(), ControlOutcome::DELIVERED);
+
+    group.terminate_all().await.unwrap();
+    child.wait().await.unwrap();
+}
+
+#[tokio::test]
+async fn attached_group_signals_existing_pgid() {
+    let (mut child, group) = spawn_in_group("sleep", &["30"]).await;
+    let pgid = group.id().await.unwrap();
+    assert_eq!(Some(pgid), child.id().map(|pid| pid as i32));
+
+    let attached = ProcessGroup::from_existing(pgid).unwrap();
+    assert_eq!(attached.id().await, Some(pgid));
+    assert_eq!(
+        attached.terminate_all().await.unwrap(),
+        ControlOutcome::DELIVERED
+    );
+
+    assert!(!child.wait().await.unwrap().success());
+    // Dropping the attached group leaves the original usable
+    drop(attached);
+    assert_eq!(
+        group.terminate_all().await.unwrap(),
+        ControlOutcome::ALREADY_EXITED
+    );
+}
+
+#[tokio::test]
+async fn attach_rejects_invalid_or_missing_group() {
+    for pgid in [0, -1] {
+        assert!(matches!(
+            ProcessGroup::from_existing(pgid),
+            Err(ProcessGroupError::AttachFailed(_))
+        ));
+    }
+
+    let (mut child, group) = spawn_in_group("true", &[]).await;
+    let pgid = group.id().await.unwrap();
+    child.wait().await.unwrap();
+    assert!(matches!(
+        ProcessGroup::from_existing(pgid),
+        Err(ProcessGroupError::AttachFailed(_))
+    ));
+}
+
+#[tokio::test]
+async fn named_groups_are_windows_only() {
+    // The name is ignored when creating the group
+    let (mut cmd, group) =
+        ProcessGroup::create_with_command_and_name(Command::new("true"), Some("tcrm-unix"))
+            .unwrap();
+    let mut child = cmd.spawn().unwrap();
+    group.assign_child(&child).await.unwrap();
+    assert!(child.wait().await.unwrap().success());
+
+    assert!(matches!(
+        ProcessGroup::open_named("tcrm-unix"),
+        Err(ProcessGroupError::AttachFailed(_))
+    ));
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn signal_to_reused_group_id_reports_stale_process() {
+    let (mut child, group) = spawn_in_group("sleep", &["30"]).await;
+    // As if the group exited and its ID went to a process started at another time
+    group
+        .set_leader_start_time(crate::tasks::async_tokio::process_start::start_time(1))
+        .await;
+
+    for outcome in [group.pause_all().await, group.terminate_all().await] {
+        assert_eq!(outcome.unwrap(), ControlOutcome::STALE_PROCESS);
+    }
+    assert!(child.try_wait().unwrap().is_none());
+
+    child.kill().await.unwrap();
+}
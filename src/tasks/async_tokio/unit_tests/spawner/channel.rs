@@ -1,7 +1,9 @@
 use tokio::sync::mpsc;
 
 use crate::tasks::{
-    async_tokio::spawner::TaskSpawner, config::TaskConfig, error::TaskError,
+    async_tokio::spawner::TaskSpawner,
+    config::TaskConfig,
+    error::{ChannelKind, ChannelOp, TaskError},
     event::TaskTerminateReason,
 };
 
@@ -15,16 +17,28 @@ async fn stdin_disabled_ignores_channel_set() {
 }
 
 #[tokio::test]
-async fn reject_terminate_signal_on_no_channel_set() {
+async fn reject_terminate_signal_before_start() {
     let config = TaskConfig::new("echo");
     let spawner = TaskSpawner::new("no_channel".to_string(), config);
 
     let result = spawner
-        .send_terminate_signal(TaskTerminateReason::Cleanup)
+        .send_terminate_signal(TaskTerminateReason::Cleanup { message: None })
         .await;
-    assert!(result.is_err());
-    match result {
-        Err(TaskError::Channel(_)) => {} // pass
-        _ => panic!("Expected Channel error"),
-    }
+    let error = result.unwrap_err();
+    assert!(
+        matches!(
+            error,
+            TaskError::Channel {
+                kind: ChannelKind::Terminate,
+                op: ChannelOp::Closed,
+                ..
+            }
+        ),
+        "{error:?}"
+    );
+    // Display is unchanged by the kind and op
+    assert_eq!(
+        error.to_string(),
+        "Channel error: Terminate signal sent to a task that was never started"
+    );
 }
@@ -0,0 +1,93 @@
+use std::{sync::atomic::Ordering, time::Duration};
+
+use tokio::time::Instant;
+
+use crate::tasks::{
+    async_tokio::{
+        spawner::{DEFAULT_JOIN_TIMEOUT, TaskSpawner},
+        watcher_set::WatcherSet,
+    },
+    config::TaskConfig,
+    error::TaskError,
+};
+
+#[tokio::test]
+async fn join_timeout_defaults_and_is_configurable() {
+    let spawner = TaskSpawner::new("join".to_string(), TaskConfig::new("echo"));
+    assert_eq!(spawner.join_timeout, DEFAULT_JOIN_TIMEOUT);
+
+    let spawner = spawner.set_join_timeout(Duration::from_millis(250));
+    assert_eq!(spawner.join_timeout, Duration::from_millis(250));
+}
+
+#[tokio::test]
+async fn finished_handles_join_cleanly() {
+    let mut watchers = WatcherSet::default();
+    watchers.spawn("stdout", async {});
+    watchers.spawn("stderr", async {});
+
+    let result = watchers.join_all(Duration::from_secs(1)).await;
+    assert!(result.is_ok());
+    assert!(watchers.pending().is_empty());
+    assert_eq!(watchers.cancelled().load(Ordering::Relaxed), 0);
+}
+
+#[tokio::test]
+async fn stuck_handles_share_a_single_timeout() {
+    let join_timeout = Duration::from_millis(300);
+    let mut watchers = WatcherSet::default();
+    watchers.spawn("stdout", std::future::pending::<()>());
+    watchers.spawn("stderr", async {});
+    watchers.spawn("stdin", std::future::pending::<()>());
+
+    let start = Instant::now();
+    let result = watchers.join_all(join_timeout).await;
+    let elapsed = start.elapsed();
+
+    assert!(elapsed >= join_timeout);
+    assert!(
+        elapsed < join_timeout * 2,
+        "Expected a single timeout for all stuck handles, waited {elapsed:?}"
+    );
+
+    let Err(TaskError::Handle(msg)) = result else {
+        panic!("Expected Handle error, got {result:?}");
+    };
+    assert!(msg.contains("stdout watcher"));
+    assert!(msg.contains("stdin watcher"));
+    assert!(!msg.contains("stderr watcher"));
+}
+
+#[tokio::test]
+async fn panicked_handle_is_named() {
+    let mut watchers = WatcherSet::default();
+    watchers.spawn("stdin", async { panic!("boom") });
+
+    let result = watchers.join_all(Duration::from_secs(1)).await;
+    let Err(TaskError::Handle(msg)) = result else {
+        panic!("Expected Handle error, got {result:?}");
+    };
+    assert!(msg.contains("stdin watcher"));
+    assert!(msg.contains("join failed"));
+}
+
+#[tokio::test]
+async fn shutdown_names_failed_watcher_and_cancels_the_rest() {
+    let mut watchers = WatcherSet::default();
+    watchers.spawn("wait", std::future::pending::<()>());
+    watchers.spawn("cache", async { panic!("boom") });
+    let mut nested = watchers.nested();
+    nested.spawn("stdout", std::future::pending::<()>());
+    tokio::task::yield_now().await;
+
+    nested.shutdown().await.unwrap();
+    let result = watchers.shutdown().await;
+    let Err(TaskError::Handle(msg)) = result else {
+        panic!("Expected Handle error, got {result:?}");
+    };
+    assert!(msg.contains("cache watcher"));
+    assert!(!msg.contains("wait watcher"));
+    assert!(watchers.pending().is_empty());
+    // The panicked watcher failed, the other two were cancelled
+    assert_eq!(watchers.cancelled().load(Ordering::Relaxed), 2);
+}
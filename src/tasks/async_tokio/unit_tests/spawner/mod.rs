@@ -1,4 +1,5 @@
 mod channel;
 mod info;
+mod join;
 mod state;
 mod uptime;
@@ -1,4 +1,6 @@
-use crate::tasks::{async_tokio::spawner::TaskSpawner, config::TaskConfig, state::TaskState};
+use crate::tasks::{
+    async_tokio::spawner::TaskSpawner, config::TaskConfig, error::TaskError, state::TaskState,
+};
 
 #[tokio::test]
 async fn fn_is_running_returns_true_when_state_running() {
@@ -8,6 +10,7 @@ async fn fn_is_running_returns_true_when_state_running() {
         !spawner.is_running().await,
         "Should not be running initially"
     );
+    spawner.update_state(TaskState::Initiating).await;
     spawner.update_state(TaskState::Running).await;
     assert!(spawner.is_running().await, "Should be running after update");
 }
@@ -16,8 +19,10 @@ async fn fn_is_running_returns_true_when_state_running() {
 async fn fn_is_running_false_for_non_running_states() {
     let config = TaskConfig::new("echo");
     let spawner = TaskSpawner::new("not_running_task".to_string(), config);
-    for state in [TaskState::Pending, TaskState::Ready, TaskState::Finished] {
-        spawner.update_state(state.clone()).await;
+    spawner.update_state(TaskState::Initiating).await;
+    spawner.update_state(TaskState::Running).await;
+    for state in [TaskState::Ready, TaskState::Finished] {
+        assert!(spawner.update_state(state.clone()).await);
         assert!(
             !spawner.is_running().await,
             "Should not be running for state: {:?}",
@@ -31,6 +36,8 @@ async fn fn_is_ready_returns_true_when_state_ready() {
     let config = TaskConfig::new("echo");
     let spawner = TaskSpawner::new("ready_task".to_string(), config);
     assert!(!spawner.is_ready().await, "Should not be ready initially");
+    spawner.update_state(TaskState::Initiating).await;
+    spawner.update_state(TaskState::Running).await;
     spawner.update_state(TaskState::Ready).await;
     assert!(spawner.is_ready().await, "Should be ready after update");
 }
@@ -39,8 +46,13 @@ async fn fn_is_ready_returns_true_when_state_ready() {
 async fn fn_is_ready_false_for_non_ready_states() {
     let config = TaskConfig::new("echo");
     let spawner = TaskSpawner::new("not_ready_task".to_string(), config);
-    for state in [TaskState::Pending, TaskState::Running, TaskState::Finished] {
-        spawner.update_state(state.clone()).await;
+    assert!(!spawner.is_ready().await, "Should not be ready for Pending");
+    for state in [
+        TaskState::Initiating,
+        TaskState::Running,
+        TaskState::Finished,
+    ] {
+        assert!(spawner.update_state(state.clone()).await);
         assert!(
             !spawner.is_ready().await,
             "Should not be ready for state: {:?}",
@@ -62,13 +74,13 @@ async fn state_transitions() {
     let config = TaskConfig::new("echo");
     let spawner = TaskSpawner::new("transition_task".to_string(), config);
     let states = [
-        TaskState::Pending,
+        TaskState::Initiating,
         TaskState::Running,
         TaskState::Ready,
         TaskState::Finished,
     ];
     for state in states.iter() {
-        spawner.update_state(state.clone()).await;
+        assert!(spawner.update_state(state.clone()).await);
         let current = spawner.get_state().await;
         assert_eq!(current, *state, "State should transition to {:?}", state);
     }
@@ -78,6 +90,7 @@ async fn state_transitions() {
 async fn update_state_changes_state() {
     let config = TaskConfig::new("echo");
     let spawner = TaskSpawner::new("update_task".to_string(), config);
+    spawner.update_state(TaskState::Initiating).await;
     spawner.update_state(TaskState::Running).await;
     let state = spawner.get_state().await;
     assert_eq!(
@@ -86,3 +99,56 @@ async fn update_state_changes_state() {
         "State should be Running after update"
     );
 }
+
+#[tokio::test]
+async fn update_state_rejects_illegal_transition() {
+    let config = TaskConfig::new("echo");
+    let spawner = TaskSpawner::new("illegal_task".to_string(), config);
+    assert!(!spawner.update_state(TaskState::Running).await);
+    assert_eq!(spawner.get_state().await, TaskState::Pending);
+
+    spawner.update_state(TaskState::Initiating).await;
+    spawner.update_state(TaskState::Running).await;
+    spawner.update_state(TaskState::Finished).await;
+    assert!(!spawner.update_state(TaskState::Ready).await);
+    assert_eq!(spawner.get_state().await, TaskState::Finished);
+}
+
+#[tokio::test]
+async fn start_direct_rejects_second_start() {
+    let config = TaskConfig::new("echo");
+    let mut spawner = TaskSpawner::new("finished_task".to_string(), config);
+    spawner.update_state(TaskState::Initiating).await;
+    spawner.update_state(TaskState::Finished).await;
+
+    let (tx, _rx) = tokio::sync::mpsc::channel(10);
+    let result = spawner.start_direct(tx).await;
+    assert!(matches!(result, Err(TaskError::Handle(_))));
+    assert_eq!(spawner.get_state().await, TaskState::Finished);
+}
+
+#[tokio::test]
+async fn update_state_rejects_repeated_state() {
+    let config = TaskConfig::new("echo");
+    let spawner = TaskSpawner::new("repeated_task".to_string(), config);
+    assert!(spawner.update_state(TaskState::Initiating).await);
+    assert!(!spawner.update_state(TaskState::Initiating).await);
+
+    assert!(spawner.update_state(TaskState::Finished).await);
+    assert!(!spawner.update_state(TaskState::Finished).await);
+}
+
+#[tokio::test]
+async fn start_direct_rejects_start_after_abandoned_start() {
+    let config = TaskConfig::new("echo");
+    let mut spawner = TaskSpawner::new("abandoned_task".to_string(), config);
+    // A start future dropped after begin_start leaves the task Initiating
+    spawner.begin_start().await.unwrap();
+
+    let (tx, _rx) = tokio::sync::mpsc::channel(10);
+    let result = spawner.start_direct(tx).await;
+    assert!(matches!(result, Err(TaskError::Handle(_))));
+    assert!(matches!(spawner.prepare().await, Err(TaskError::Handle(_))));
+    assert_eq!(spawner.get_state().await, TaskState::Initiating);
+    assert_eq!(spawner.get_process_id().await, None);
+}
@@ -0,0 +1,219 @@
+//! Watcher tasks of a started task, joined and aborted together.
+//!
+//! Watchers are spawned into a [`WatcherSet`] instead of as free-floating
+//! tokio tasks. The `TaskSpawner` owns the set holding the wait watcher (and
+//! the event forwarder of `start_cached`), and the wait watcher owns the set
+//! holding the output and stdin watchers it joins before reporting the
+//! result. Dropping a set aborts every watcher in it, so aborting the wait
+//! watcher also cancels the watchers it would have joined.
+
+use std::{
+    future::Future,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use tokio::{
+    task::{AbortHandle, Id, JoinError, JoinSet},
+    time::{Instant, timeout_at},
+};
+
+use crate::tasks::error::TaskError;
+
+/// Named watcher tasks, aborted when the set is dropped
+#[derive(Debug, Default)]
+pub(crate) struct WatcherSet {
+    set: JoinSet<()>,
+    /// Names of the watchers not joined yet, in the order spawned
+    names: Vec<(&'static str, AbortHandle)>,
+    /// Watchers dropped before completing, shared with nested sets
+    cancelled: Arc<AtomicU64>,
+}
+
+/// Counts its watcher as cancelled when dropped before the watcher completed
+struct CancelGuard {
+    cancelled: Arc<AtomicU64>,
+    completed: bool,
+}
+
+impl CancelGuard {
+    /// Marks the watcher completed
+    fn disarm(&mut self) {
+        self.completed = true;
+    }
+}
+
+impl Drop for CancelGuard {
+    fn drop(&mut self) {
+        // A panicking watcher failed, it was not cancelled
+        if !self.completed && !std::thread::panicking() {
+            self.cancelled.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+impl WatcherSet {
+    /// Creates an empty set counting cancelled watchers with the same counter as this one
+    pub(crate) fn nested(&self) -> Self {
+        Self {
+            cancelled: self.cancelled.clone(),
+            ..Self::default()
+        }
+    }
+
+    /// Counter of watchers dropped before completing, in this set and its nested sets
+    #[cfg(test)]
+    pub(crate) fn cancelled(&self) -> Arc<AtomicU64> {
+        self.cancelled.clone()
+    }
+
+    /// Spawns `watcher` on the current runtime
+    ///
+    /// # Panics
+    ///
+    /// Outside of a tokio runtime, like `tokio::spawn`.
+    pub(crate) fn spawn<F>(&mut self, name: &'static str, watcher: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let guard = CancelGuard {
+            cancelled: self.cancelled.clone(),
+            completed: false,
+        };
+        let handle = self.set.spawn(async move {
+            let mut guard = guard;
+            watcher.await;
+            guard.disarm();
+        });
+        #[cfg(feature = "tracing")]
+        tracing::debug!(handle_id = %handle.id(), "Spawned {name} watcher handle");
+        self.names.push((name, handle));
+    }
+
+    /// Whether the watcher `name` was spawned and has stopped, but was not joined yet
+    pub(crate) fn is_finished(&self, name: &str) -> bool {
+        self.names
+            .iter()
+            .any(|(watcher, handle)| *watcher == name && handle.is_finished())
+    }
+
+    /// Names of the watchers not joined yet
+    pub(crate) fn pending(&self) -> Vec<&'static str> {
+        self.names.iter().map(|(name, _)| *name).collect()
+    }
+
+    /// Abort handle of the watcher `name`, if it was not joined yet
+    #[cfg(all(test, unix))]
+    pub(crate) fn abort_handle(&self, name: &str) -> Option<AbortHandle> {
+        self.names
+            .iter()
+            .find(|(watcher, _)| *watcher == name)
+            .map(|(_, handle)| handle.clone())
+    }
+
+    /// Removes the watcher `id` once joined, returning its name
+    fn take_name(&mut self, id: Id) -> &'static str {
+        let index = self.names.iter().position(|(_, handle)| handle.id() == id);
+        index.map_or("unknown", |index| self.names.remove(index).0)
+    }
+
+    /// Waits for the next watcher to stop until `deadline`
+    ///
+    /// # Returns
+    ///
+    /// `None` at the deadline or once the set is empty; otherwise the name
+    /// and result of the joined watcher.
+    pub(crate) async fn join_next_until(
+        &mut self,
+        deadline: Instant,
+    ) -> Option<(&'static str, Id, Result<(), JoinError>)> {
+        let joined = timeout_at(deadline, self.set.join_next_with_id())
+            .await
+            .ok()??;
+        let (id, result) = match joined {
+            Ok((id, ())) => (id, Ok(())),
+            Err(e) => (e.id(), Err(e)),
+        };
+        Some((self.take_name(id), id, result))
+    }
+
+    /// Waits for all watchers to complete, with a timeout
+    ///
+    /// The watchers run concurrently, so they are joined against a single
+    /// deadline: the total wait is bounded by `join_timeout` regardless of how
+    /// many are stuck. Watchers still running at the deadline are aborted.
+    ///
+    /// Returns an error naming each watcher that failed or timed out
+    pub(crate) async fn join_all(&mut self, join_timeout: Duration) -> Result<(), TaskError> {
+        let deadline = Instant::now() + join_timeout;
+        let mut errors = Vec::new();
+
+        while let Some((name, id, result)) = self.join_next_until(deadline).await {
+            if let Err(join_err) = result {
+                errors.push(format!("{name} watcher [{id}] join failed: {join_err:?}"));
+            }
+        }
+        for (name, handle) in &self.names {
+            errors.push(format!(
+                "{name} watcher [{}] join timeout, aborting",
+                handle.id()
+            ));
+        }
+        self.abort_all();
+
+        if !errors.is_empty() {
+            return Err(TaskError::Handle(format!(
+                "Task handles join failures: {}",
+                errors.join("; ")
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Aborts every watcher and waits until all of them have stopped
+    ///
+    /// Returns an error naming each watcher that failed before it was aborted
+    pub(crate) async fn shutdown(&mut self) -> Result<(), TaskError> {
+        self.set.abort_all();
+        let mut errors = Vec::new();
+        while let Some(joined) = self.set.join_next_with_id().await {
+            if let Err(join_err) = joined
+                && !join_err.is_cancelled()
+            {
+                let name = self.take_name(join_err.id());
+                errors.push(format!(
+                    "{name} watcher [{}] failed: {join_err}",
+                    join_err.id()
+                ));
+            }
+        }
+        self.names.clear();
+
+        if !errors.is_empty() {
+            return Err(TaskError::Handle(format!(
+                "Task handles failed: {}",
+                errors.join("; ")
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Aborts every watcher without waiting for them
+    fn abort_all(&mut self) {
+        self.set.abort_all();
+        // Aborted watchers stop at their next await point; the set no longer
+        // tracks them, and they are not reported as failures
+        self.set.detach_all();
+        self.names.clear();
+    }
+
+    /// Lets every watcher run to completion on its own, even after the set is dropped
+    pub(crate) fn detach_all(&mut self) {
+        self.set.detach_all();
+    }
+}
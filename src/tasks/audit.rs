@@ -0,0 +1,81 @@
+use std::time::SystemTime;
+
+use crate::tasks::event::{TaskEventStopReason, TaskTerminateReason};
+
+/// Summary of one execution of a task, for compliance and audit logs
+///
+/// Assembled once the process has stopped, just before `TaskEvent::Stopped`
+/// is sent, and returned by [`TaskSpawner::audit`](crate::tasks::async_tokio::spawner::TaskSpawner::audit).
+/// Arguments are redacted with [`ConfigValidator::redact_args`](crate::tasks::validator::ConfigValidator::redact_args)
+/// and only the names of environment variables are kept, so the record can
+/// be stored or shipped as is.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "tokio", doc = "```rust,no_run")]
+#[cfg_attr(not(feature = "tokio"), doc = "```ignore")]
+/// use tcrm_task::tasks::{
+///     config::TaskConfig,
+///     async_tokio::spawner::TaskSpawner,
+///     event::TaskEvent,
+/// };
+/// use tokio::sync::mpsc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let config = TaskConfig::new("curl").args(["--token", "s3cr3t", "https://example.com"]);
+///     let mut spawner = TaskSpawner::new("fetch".to_string(), config);
+///
+///     let (tx, mut rx) = mpsc::channel(100);
+///     spawner.start_direct(tx).await?;
+///     while let Some(event) = rx.recv().await {
+///         if matches!(event, TaskEvent::Stopped { .. }) {
+///             break;
+///         }
+///     }
+///
+///     let audit = spawner.audit().await.unwrap();
+///     assert_eq!(audit.args[1], "[REDACTED]");
+///     println!("{} ran {} with {:?}", audit.task_name, audit.command, audit.reason);
+///     Ok(())
+/// }
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditRecord {
+    /// Name of the task
+    pub task_name: String,
+    /// Absolute path of the program that was executed
+    ///
+    /// The command as configured if it could not be resolved.
+    pub command: String,
+    /// Arguments passed to the program, with sensitive values redacted
+    pub args: Vec<String>,
+    /// Canonical working directory of the process
+    pub working_dir: String,
+    /// Names of the environment variables set for the process, sorted
+    ///
+    /// Inherited variables are not listed.
+    pub env_keys: Vec<String>,
+    /// System process ID, `None` if the process exited before it could be read
+    pub process_id: Option<u32>,
+    /// When the process was spawned
+    pub started_at: SystemTime,
+    /// When the process was found stopped
+    pub stopped_at: SystemTime,
+    /// Exit code of the process, as reported in `TaskEvent::Stopped`
+    pub exit_code: Option<i32>,
+    /// Signal that ended the process, if it exited on its own or was terminated
+    /// with a graceful shutdown period (Unix only)
+    pub signal: Option<i32>,
+    /// Why the task stopped, as reported in `TaskEvent::Stopped`
+    pub reason: TaskEventStopReason,
+    /// Bytes read from stdout and stderr, without line terminators
+    ///
+    /// Counted after decoding, before any output transform.
+    pub output_bytes: u64,
+    /// Reasons of the termination requests received while the process ran, in order
+    ///
+    /// Includes requests made after the task was already being terminated.
+    pub terminate_requests: Vec<TaskTerminateReason>,
+}
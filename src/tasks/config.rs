@@ -1,6 +1,11 @@
 use std::{collections::HashMap, sync::Arc};
 
-use crate::tasks::{error::TaskError, validator::ConfigValidator};
+use crate::tasks::{
+    error::TaskError,
+    validator::{ConfigValidator, DEFAULT_RISKY_ENV_PATTERNS, ValidationWarning},
+};
+
+pub mod overrides;
 
 /// Configuration for a task to be executed.
 ///
@@ -46,26 +51,239 @@ pub struct TaskConfig {
     /// Working directory for the command
     pub working_dir: Option<String>,
 
+    /// Working directories to choose from, the first existing one is used (default: none)
+    ///
+    /// Cannot be combined with `working_dir` or `temp_working_dir`. See
+    /// [`TaskConfig::working_dir_candidates`].
+    pub working_dir_candidates: Option<Vec<String>>,
+
+    /// Run in a fresh temporary directory, removed once the task stops (default: disabled)
+    ///
+    /// Cannot be combined with `working_dir`.
+    pub temp_working_dir: Option<TempWorkingDir>,
+
     /// Environment variables for the command
     pub env: Option<HashMap<String, String>>,
 
+    /// Start the command with an empty environment instead of inheriting it (default: false)
+    ///
+    /// See [`TaskConfig::clear_env`].
+    pub clear_env: Option<bool>,
+
+    /// Variables still inherited from the current process when `clear_env` is set
+    ///
+    /// See [`TaskConfig::inherit_env_vars`].
+    pub inherit_env_vars: Option<Vec<String>>,
+
     /// Maximum allowed runtime in milliseconds
     pub timeout_ms: Option<u64>,
 
+    /// Leave the time the task spent paused out of `timeout_ms` (default: false)
+    ///
+    /// See [`TaskConfig::timeout_excludes_paused`].
+    pub timeout_excludes_paused: Option<bool>,
+
+    /// Time a terminated task gets to exit on its own before it is killed, in milliseconds
+    ///
+    /// See [`TaskConfig::graceful_shutdown_ms`].
+    pub graceful_shutdown_ms: Option<u64>,
+
     /// Allow providing input to the task via stdin
     pub enable_stdin: Option<bool>,
 
     /// Optional string to indicate the task is ready (for long-running processes like servers)
     pub ready_indicator: Option<String>,
 
+    /// Regular expression a line matches to indicate the task is ready
+    ///
+    /// Alternative to `ready_indicator`; requires the `regex` feature.
+    pub ready_indicator_regex: Option<String>,
+
     /// Source of the ready indicator string (stdout/stderr)
     pub ready_indicator_source: Option<StreamSource>,
 
+    /// How the ready indicator and output triggers match a line (default: contains)
+    pub ready_match_mode: Option<ReadyMatchMode>,
+
     /// Enable process group management for child process termination (default: true)
     ///
     /// When enabled, creates process groups (Unix) or Job Objects (Windows) to ensure
     /// all child processes and their descendants are terminated when the main process is killed.
     pub use_process_group: Option<bool>,
+
+    /// Name of the Job Object created for the task on Windows
+    ///
+    /// A named job can be opened by other processes with
+    /// `ProcessGroup::open_named`. Ignored on Unix and when process group
+    /// management is disabled.
+    pub job_object_name: Option<String>,
+
+    /// Kill the process when this process dies (default: false)
+    ///
+    /// Uses the parent-death signal on Linux and the Job Object of the process
+    /// group on Windows; rejected by validation on other platforms.
+    pub kill_child_on_parent_death: Option<bool>,
+
+    /// Scheduling priority of the process (default: inherited)
+    ///
+    /// See [`TaskConfig::priority`].
+    pub priority: Option<TaskPriority>,
+
+    /// Indices of the CPU cores the process may run on (default: inherited)
+    ///
+    /// See [`TaskConfig::cpu_affinity`].
+    pub cpu_affinity: Option<Vec<usize>>,
+
+    /// Retry spawning the process after transient errors (default: disabled)
+    ///
+    /// See [`TaskConfig::spawn_retry`].
+    pub spawn_retry: Option<SpawnRetry>,
+
+    /// Maximum time in milliseconds an output watcher waits for the event channel
+    ///
+    /// When set and a send of an output event takes longer than this, the stream switches
+    /// to drop-and-count mode: events are only delivered if the channel has room and are
+    /// otherwise discarded and counted, so a stalled consumer can't block the child on a full pipe.
+    /// When unset (default), output watchers wait for the consumer indefinitely.
+    pub event_send_timeout_ms: Option<u64>,
+
+    /// Average wait in milliseconds of output events for the event channel above which
+    /// `TaskEvent::SlowConsumer` is sent (default: disabled)
+    ///
+    /// See [`TaskConfig::slow_consumer_threshold_ms`].
+    pub slow_consumer_threshold_ms: Option<u64>,
+
+    /// Capture the process stdout as `TaskEvent::Output` events (default: true)
+    ///
+    /// When disabled, stdout is redirected to the null device and no watcher is spawned for it.
+    pub capture_stdout: Option<bool>,
+
+    /// Capture the process stderr as `TaskEvent::Output` events (default: true)
+    ///
+    /// When disabled, stderr is redirected to the null device and no watcher is spawned for it.
+    pub capture_stderr: Option<bool>,
+
+    /// Connect the process to the stdin, stdout and stderr of this process (default: false)
+    ///
+    /// When enabled, no stream is captured and no output or stdin watcher is spawned.
+    pub stdio_inherit: Option<bool>,
+
+    /// Size in bytes of the read buffer for each captured output stream (default: 8 KiB)
+    ///
+    /// Larger buffers mean fewer reads for chatty processes, smaller ones less
+    /// memory per task. The buffer still grows to hold a longer line.
+    pub output_buffer_bytes: Option<usize>,
+
+    /// Longest output line in bytes, longer lines are truncated (default: 1 MiB)
+    ///
+    /// See [`TaskConfig::max_output_line_bytes`].
+    pub max_output_line_bytes: Option<usize>,
+
+    /// Emit output lines holding a JSON object or array as `TaskEvent::OutputJson` (default: false)
+    ///
+    /// Requires the `serde` feature. Lines that are not valid JSON, or longer
+    /// than 1 MiB, are still sent as `TaskEvent::Output`.
+    pub parse_json_lines: Option<bool>,
+
+    /// Read stdout and stderr from a single watcher task (default: false)
+    ///
+    /// Best-effort: keeps the relative order of the two streams closer to the
+    /// order the process wrote them. `seq` of `TaskEvent::Output` orders lines
+    /// across both streams either way.
+    pub preserve_interleaving: Option<bool>,
+
+    /// Expected runtime in milliseconds, reported in `TaskEvent::Stopped`
+    ///
+    /// Unlike `timeout_ms`, exceeding it never terminates the task; it only
+    /// emits `TaskEvent::RunningLong` once `running_long_multiplier` times
+    /// this duration has elapsed.
+    pub expected_duration_ms: Option<u64>,
+
+    /// Multiple of `expected_duration_ms` after which `TaskEvent::RunningLong` is emitted (default: 2.0)
+    pub running_long_multiplier: Option<f64>,
+
+    /// Resident memory in bytes above which `TaskEvent::MemoryWatermarkExceeded` is emitted
+    ///
+    /// A soft limit: the task keeps running. See [`TaskConfig::memory_watermark_bytes`].
+    pub memory_watermark_bytes: Option<u64>,
+
+    /// Interval in milliseconds between memory samples for the watermark (default: 1000)
+    pub sample_interval_ms: Option<u64>,
+
+    /// Record the environment the process was spawned with (default: false)
+    ///
+    /// When enabled, the inherited environment merged with `env` is available from
+    /// `TaskSpawner::spawned_env` once the task has started.
+    pub capture_env_snapshot: Option<bool>,
+
+    /// Text encoding of the process stdout and stderr (default: UTF-8)
+    pub output_encoding: Option<OutputEncoding>,
+
+    /// Whether output is sent as lines or as raw byte chunks (default: lines)
+    ///
+    /// See [`TaskConfig::output_mode`].
+    pub output_mode: Option<OutputMode>,
+
+    /// Report a SHA-256 digest of each captured output stream in `TaskEvent::Stopped` (default: false)
+    ///
+    /// Requires the `sha2` feature. Digests cover the raw bytes of the stream,
+    /// before decoding.
+    pub hash_output: Option<bool>,
+
+    /// Human-readable meanings of exit codes, reported in `TaskEvent::Stopped`
+    pub exit_code_meanings: Option<HashMap<i32, String>>,
+
+    /// Collapse runs of identical output lines (default: disabled)
+    ///
+    /// Repeats beyond `max_repeats` are summarized in `TaskEvent::OutputRepeated`
+    /// instead of being sent as `TaskEvent::Output`.
+    pub dedupe_output: Option<OutputDedupe>,
+
+    /// Number of lines after which an output watcher yields to the runtime (default: never)
+    ///
+    /// See [`TaskConfig::yield_every_n_lines`].
+    pub yield_every_n_lines: Option<usize>,
+
+    /// Most `TaskEvent::Output` events sent per second on each stream (default: unlimited)
+    ///
+    /// Lines beyond the budget are summarized in `TaskEvent::OutputSuppressed`.
+    pub max_output_events_per_sec: Option<u32>,
+
+    /// Number of first output lines kept for error reporting (default: 50)
+    ///
+    /// Captured from both streams, up to 64 KiB in total. Set to 0 to disable.
+    pub startup_capture_lines: Option<usize>,
+
+    /// Number of last output lines retained for `TaskSpawner::tail` (default: none)
+    ///
+    /// Retained from both streams, up to 1 MiB in total; the oldest lines are
+    /// evicted first.
+    pub retain_output_lines: Option<usize>,
+
+    /// Number of output lines of each stream kept by `TaskSpawner::run_to_completion` (default: 10000)
+    pub max_captured_lines: Option<usize>,
+
+    /// Actions taken when output lines contain a pattern
+    ///
+    /// Evaluated together with `ready_indicator`, which acts as a `Ready` trigger.
+    pub output_triggers: Option<Vec<OutputTrigger>>,
+
+    /// Report a task stopped by a `Complete` trigger as successful (default: true)
+    ///
+    /// When enabled, `TaskEvent::Stopped` carries exit code 0; otherwise no
+    /// exit code, and the run counts as failed.
+    pub completion_success: Option<bool>,
+
+    /// Patterns of inherited environment variables reported as risky
+    ///
+    /// Used by `validate_with_warnings`; defaults to `DEFAULT_RISKY_ENV_PATTERNS`.
+    pub risky_env_patterns: Option<Vec<String>>,
+
+    /// Inputs written to stdin, each once the output reaches a prompt
+    ///
+    /// Requires `enable_stdin`. A step timing out terminates the task with
+    /// `TaskTerminateReason::ScriptTimeout`.
+    pub stdin_script: Option<StdinScript>,
 }
 
 pub type SharedTaskConfig = Arc<TaskConfig>;
@@ -75,12 +293,53 @@ impl Default for TaskConfig {
             command: String::new(),
             args: None,
             working_dir: None,
+            working_dir_candidates: None,
+            temp_working_dir: None,
             env: None,
+            clear_env: Some(false),
+            inherit_env_vars: None,
             timeout_ms: None,
+            timeout_excludes_paused: Some(false),
+            graceful_shutdown_ms: None,
             enable_stdin: Some(false),
             ready_indicator: None,
+            ready_indicator_regex: None,
             ready_indicator_source: Some(StreamSource::Stdout),
+            ready_match_mode: None,
             use_process_group: Some(true),
+            job_object_name: None,
+            kill_child_on_parent_death: Some(false),
+            priority: None,
+            cpu_affinity: None,
+            spawn_retry: None,
+            event_send_timeout_ms: None,
+            slow_consumer_threshold_ms: None,
+            capture_stdout: Some(true),
+            capture_stderr: Some(true),
+            stdio_inherit: Some(false),
+            output_buffer_bytes: None,
+            max_output_line_bytes: None,
+            parse_json_lines: Some(false),
+            preserve_interleaving: Some(false),
+            expected_duration_ms: None,
+            running_long_multiplier: None,
+            memory_watermark_bytes: None,
+            sample_interval_ms: None,
+            capture_env_snapshot: Some(false),
+            output_encoding: None,
+            output_mode: None,
+            hash_output: Some(false),
+            exit_code_meanings: None,
+            dedupe_output: None,
+            yield_every_n_lines: None,
+            max_output_events_per_sec: None,
+            startup_capture_lines: None,
+            retain_output_lines: None,
+            max_captured_lines: None,
+            output_triggers: None,
+            completion_success: Some(true),
+            risky_env_patterns: None,
+            stdin_script: None,
         }
     }
 }
@@ -156,6 +415,120 @@ impl TaskConfig {
         self
     }
 
+    /// Set working directories to try in order, instead of a single one
+    ///
+    /// The first candidate that exists and is a directory is used, checked
+    /// when the configuration is validated; validation fails if none is. The
+    /// chosen directory is reported in `TaskEvent::Started` and is the working
+    /// directory of `TaskSpawner::plan`.
+    ///
+    /// Cannot be combined with [`working_dir`](Self::working_dir) or
+    /// [`temp_working_dir`](Self::temp_working_dir).
+    ///
+    /// # Arguments
+    ///
+    /// * `candidates` - Paths to try, in order of preference
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let workspace = std::env::temp_dir().to_string_lossy().into_owned();
+    /// let config = TaskConfig::new("make")
+    ///     .working_dir_candidates([format!("{workspace}/build"), workspace.clone()]);
+    /// assert_eq!(config.resolve_working_dir().unwrap(), Some(workspace.as_str()));
+    /// ```
+    #[must_use]
+    pub fn working_dir_candidates<I, S>(mut self, candidates: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.working_dir_candidates = Some(candidates.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Run the task in a fresh temporary directory
+    ///
+    /// Before spawning, a directory with a unique name is created in the
+    /// system temporary directory and used as the working directory. Its path
+    /// is reported in `TaskEvent::Started` and `TaskInfo::temp_dir`. Once
+    /// `TaskEvent::Stopped` has been sent, the directory is removed with
+    /// everything in it; failing to remove it is logged, not reported as an
+    /// error. See [`temp_working_dir_prefix`](Self::temp_working_dir_prefix)
+    /// and [`keep_temp_dir_on_failure`](Self::keep_temp_dir_on_failure).
+    ///
+    /// Cannot be combined with [`working_dir`](Self::working_dir).
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to run in a temporary directory
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("cargo").args(["init"]).temp_working_dir(true);
+    /// assert!(config.validate().is_ok());
+    /// ```
+    #[must_use]
+    pub fn temp_working_dir(mut self, enabled: bool) -> Self {
+        self.temp_working_dir = enabled.then(|| self.temp_working_dir.unwrap_or_default());
+        self
+    }
+
+    /// Set the prefix of the temporary working directory name
+    ///
+    /// Enables [`temp_working_dir`](Self::temp_working_dir). The name is the
+    /// prefix followed by a unique suffix.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - Start of the directory name (1 to 64 characters, no path separators, default: `tcrm-`)
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("make").temp_working_dir_prefix("build-");
+    /// assert!(config.validate().is_ok());
+    /// ```
+    #[must_use]
+    pub fn temp_working_dir_prefix(mut self, prefix: impl Into<String>) -> Self {
+        let mut temp = self.temp_working_dir.unwrap_or_default();
+        temp.prefix = Some(prefix.into());
+        self.temp_working_dir = Some(temp);
+        self
+    }
+
+    /// Keep the temporary working directory when the task fails
+    ///
+    /// Enables [`temp_working_dir`](Self::temp_working_dir). A failed run
+    /// (a non-zero exit code, an error, a startup failure, or a timeout)
+    /// leaves its directory in place for inspection; successful runs still
+    /// remove it.
+    ///
+    /// # Arguments
+    ///
+    /// * `keep` - Whether to keep the directory of a failed run
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("./integration-test.sh")
+    ///     .temp_working_dir(true)
+    ///     .keep_temp_dir_on_failure(true);
+    /// assert!(config.validate().is_ok());
+    /// ```
+    #[must_use]
+    pub fn keep_temp_dir_on_failure(mut self, keep: bool) -> Self {
+        let mut temp = self.temp_working_dir.unwrap_or_default();
+        temp.keep_on_failure = keep;
+        self.temp_working_dir = Some(temp);
+        self
+    }
+
     /// Set environment variables for the command
     ///
     /// # Arguments
@@ -188,6 +561,59 @@ impl TaskConfig {
         self
     }
 
+    /// Start the command with an empty environment instead of inheriting it
+    ///
+    /// Keeps secrets of the current process, such as cloud credentials, away
+    /// from the command. Only the variables named in
+    /// [`inherit_env_vars`](Self::inherit_env_vars) are inherited, and those
+    /// set with [`env`](Self::env) are applied on top.
+    ///
+    /// # Arguments
+    ///
+    /// * `clear` - Whether the command starts with an empty environment
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("untrusted-tool")
+    ///     .clear_env(true)
+    ///     .inherit_env_vars(["PATH", "HOME"])
+    ///     .env([("RUST_LOG", "info")]);
+    /// ```
+    #[must_use]
+    pub fn clear_env(mut self, clear: bool) -> Self {
+        self.clear_env = Some(clear);
+        self
+    }
+
+    /// Set the variables inherited from the current process when the environment is cleared
+    ///
+    /// Variables not set in the current process are skipped. Requires
+    /// [`clear_env`](Self::clear_env).
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - Iterator of environment variable names
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("make")
+    ///     .clear_env(true)
+    ///     .inherit_env_vars(["PATH", "HOME", "LANG"]);
+    /// ```
+    #[must_use]
+    pub fn inherit_env_vars<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.inherit_env_vars = Some(names.into_iter().map(Into::into).collect());
+        self
+    }
+
     /// Set the maximum allowed runtime in milliseconds
     ///
     /// If the task runs longer than this timeout, it will be terminated.
@@ -214,6 +640,71 @@ impl TaskConfig {
         self
     }
 
+    /// Leave the time the task spent paused out of the timeout
+    ///
+    /// By default the timeout counts wall-clock time, so a task paused for
+    /// longer than its timeout is killed as soon as the timeout elapses. When
+    /// enabled, the time between
+    /// [`TaskSpawner::pause_process`](crate::tasks::async_tokio::spawner::TaskSpawner::pause_process)
+    /// and [`TaskSpawner::resume_process`](crate::tasks::async_tokio::spawner::TaskSpawner::resume_process)
+    /// extends the timeout instead, and a paused task is never timed out. On
+    /// Unix, stops and continues of the process sent from outside, such as
+    /// `kill -STOP`, are counted as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether paused time is left out of the timeout
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("long-running-task")
+    ///     .timeout_ms(30000)
+    ///     .timeout_excludes_paused(true);
+    /// ```
+    #[must_use]
+    pub fn timeout_excludes_paused(mut self, enabled: bool) -> Self {
+        self.timeout_excludes_paused = Some(enabled);
+        self
+    }
+
+    /// Let the task shut down on its own before it is killed
+    ///
+    /// By default, a terminated task is killed at once, with `SIGKILL` or by
+    /// terminating its Job Object, so it cannot flush logs or remove temporary
+    /// files. With a grace period, whatever the reason of the termination, the
+    /// process, or its whole group, is first sent `SIGTERM` on Unix or
+    /// `CTRL_BREAK_EVENT` on Windows. The task is killed only if it is still
+    /// running once the grace period is over.
+    ///
+    /// `TaskEvent::Stopped` still carries the reason of the termination, with
+    /// the exit code of a process that exited in time. The audit record holds
+    /// the signal that ended the process, `SIGTERM` or `SIGKILL`, if any.
+    ///
+    /// On Windows, requires process group management, since only the console
+    /// process group of the task can receive `CTRL_BREAK_EVENT`.
+    ///
+    /// # Arguments
+    ///
+    /// * `grace_ms` - Time to wait for the process to exit, in milliseconds (must be > 0)
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// // Give the server 5 seconds to close its connections when stopped
+    /// let config = TaskConfig::new("my-server")
+    ///     .timeout_ms(60_000)
+    ///     .graceful_shutdown_ms(5_000);
+    /// assert!(config.validate().is_ok());
+    /// ```
+    #[must_use]
+    pub fn graceful_shutdown_ms(mut self, grace_ms: u64) -> Self {
+        self.graceful_shutdown_ms = Some(grace_ms);
+        self
+    }
+
     /// Enable or disable stdin for the task
     ///
     /// When enabled, you can send input to the process via the stdin channel.
@@ -263,6 +754,40 @@ impl TaskConfig {
         self
     }
 
+    /// Set a regular expression that indicates the task is ready
+    ///
+    /// Like [`ready_indicator`](Self::ready_indicator), but a line matches when
+    /// the expression matches anywhere in it; anchor it with `^` and `$` to
+    /// match the whole line. Useful when the ready line carries a value that
+    /// changes between runs, such as a port. The expression watches
+    /// [`ready_indicator_source`](Self::ready_indicator_source);
+    /// [`ready_match_mode`](Self::ready_match_mode) does not apply to it.
+    ///
+    /// Requires the `regex` feature. Validation fails if the expression does
+    /// not compile, or if `ready_indicator` is set as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - Regular expression in the syntax of the `regex` crate
+    ///
+    /// # Examples
+    #[cfg_attr(feature = "regex", doc = "```rust")]
+    #[cfg_attr(not(feature = "regex"), doc = "```ignore")]
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("my-server")
+    ///     .ready_indicator_regex(r"^Listening on 127\.0\.0\.1:\d+$");
+    /// assert!(config.validate().is_ok());
+    ///
+    /// let invalid = TaskConfig::new("my-server").ready_indicator_regex("Listening on (");
+    /// assert!(invalid.validate().is_err());
+    /// ```
+    #[must_use]
+    pub fn ready_indicator_regex(mut self, pattern: impl Into<String>) -> Self {
+        self.ready_indicator_regex = Some(pattern.into());
+        self
+    }
+
     /// Set the source of the ready indicator
     ///
     /// Specifies whether to look for the ready indicator in stdout or stderr.
@@ -285,6 +810,35 @@ impl TaskConfig {
         self
     }
 
+    /// Set how the ready indicator matches an output line
+    ///
+    /// By default a line matches when it contains the indicator anywhere, so
+    /// a server echoing its configuration at startup may look ready before it
+    /// listens. Anchoring the match to the start of the line, or requiring the
+    /// whole line, avoids such false positives. The mode applies to the
+    /// patterns of all [`output_trigger`](Self::output_trigger)s as well,
+    /// including failure and completion indicators.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - How a pattern matches a line (default: `Contains`)
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::{ReadyMatchMode, TaskConfig};
+    ///
+    /// // "listen_addr = 0.0.0.0 # Listening on" no longer marks the server ready
+    /// let config = TaskConfig::new("my-server")
+    ///     .ready_indicator("Listening on")
+    ///     .ready_match_mode(ReadyMatchMode::StartsWith);
+    /// assert!(config.validate().is_ok());
+    /// ```
+    #[must_use]
+    pub fn ready_match_mode(mut self, mode: ReadyMatchMode) -> Self {
+        self.ready_match_mode = Some(mode);
+        self
+    }
+
     /// Enable or disable process group management
     ///
     /// When enabled (default), creates process groups on Unix or Job Objects on Windows
@@ -313,27 +867,1092 @@ impl TaskConfig {
         self
     }
 
-    /// Validate the configuration
+    /// Set the name of the Job Object created for the task on Windows
     ///
-    /// Validates all configuration parameters.
-    /// This method should be called before executing the task to ensure
-    /// safe operation.
-    ///
-    /// # Validation Checks
-    /// - all fields length limits
-    /// - **Command**: Must not be empty, contain shell injection patterns
-    /// - **Arguments**: Must not contain null bytes or shell injection patterns  
-    /// - **Working Directory**: Must exist and be a valid directory
-    /// - **Environment Variables**: Keys must not contain spaces, '=', or null bytes
-    /// - **Timeout**: Must be greater than 0 if specified
-    /// - **Ready Indicator**: Must not be empty if specified
+    /// Other processes can open the job by name with
+    /// [`ProcessGroup::open_named`](crate::tasks::async_tokio::process_group::ProcessGroup::open_named)
+    /// to query or terminate the task's process tree. Starting the task fails if
+    /// a job with this name already exists. The name may use the `Global\` or
+    /// `Local\` namespace prefixes. Ignored on Unix.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// - `Ok(())` if the configuration is valid
-    /// - `Err(TaskError::InvalidConfiguration)` with details if validation fails
+    /// * `name` - Job Object name (1 to 260 characters, no null bytes)
     ///
-    /// # Errors
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("cargo")
+    ///     .args(["build"])
+    ///     .job_object_name("Local\\tcrm-build");
+    /// assert!(config.validate().is_ok());
+    /// ```
+    #[must_use]
+    pub fn job_object_name(mut self, name: impl Into<String>) -> Self {
+        self.job_object_name = Some(name.into());
+        self
+    }
+
+    /// Kill the process if this process dies without stopping it
+    ///
+    /// Guards against leaking children when the supervisor is killed outright
+    /// (e.g. `SIGKILL`), which gives it no chance to terminate its tasks.
+    ///
+    /// - **Linux**: the process gets `SIGKILL` as its parent-death signal. The
+    ///   kernel sends it when the thread that spawned the process exits, so
+    ///   start such tasks from threads that live as long as the supervisor,
+    ///   such as runtime worker threads. Only the direct child is covered;
+    ///   descendants rely on the process group.
+    /// - **Windows**: the Job Object of the process group already kills its
+    ///   processes once its last handle is closed, which happens when this
+    ///   process dies. Requires `use_process_group`.
+    /// - **Other platforms**: rejected by validation.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to kill the process when this process dies
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("my-server").kill_child_on_parent_death(true);
+    /// # #[cfg(any(target_os = "linux", target_os = "android", windows))]
+    /// assert!(config.validate().is_ok());
+    /// ```
+    #[must_use]
+    pub fn kill_child_on_parent_death(mut self, enabled: bool) -> Self {
+        self.kill_child_on_parent_death = Some(enabled);
+        self
+    }
+
+    /// Run the process at a scheduling priority other than this process's
+    ///
+    /// Lets long batch tasks run below interactive work.
+    ///
+    /// - **Unix**: the nice value of [`TaskPriority::nice_value`] is set with
+    ///   `setpriority` before the command is executed. Lowering the nice value
+    ///   below the current one, as `High` usually does, needs privileges
+    ///   (`CAP_SYS_NICE` on Linux); without them the task fails to start with
+    ///   `TaskError::Handle`.
+    /// - **Windows**: the priority class is set with `SetPriorityClass` right
+    ///   after spawning, before the process runs when it is in a process
+    ///   group. If that fails, the process is killed and the task fails with
+    ///   `TaskError::Handle`.
+    /// - **Other platforms**: rejected by validation.
+    ///
+    /// # Arguments
+    ///
+    /// * `priority` - Priority of the process
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::{TaskConfig, TaskPriority};
+    ///
+    /// let config = TaskConfig::new("cargo").args(["build"]).priority(TaskPriority::Low);
+    /// assert_eq!(config.priority, Some(TaskPriority::Low));
+    /// ```
+    #[must_use]
+    pub fn priority(mut self, priority: TaskPriority) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Pin the process to the given CPU cores
+    ///
+    /// Isolates benchmarks from other work. Cores are numbered from 0, and
+    /// validation rejects an empty list and indices at or above
+    /// `std::thread::available_parallelism`, or beyond what an affinity mask
+    /// holds (`CPU_SETSIZE` on Linux, the bits of a `usize` on Windows). Cores
+    /// the process may not run on are left to the OS to reject when the task
+    /// starts. Child processes inherit the affinity.
+    ///
+    /// - **Linux**: set with `sched_setaffinity` before the command is
+    ///   executed; if that fails, the task fails to start with
+    ///   `TaskError::PreExec` naming the `cpu_affinity` step.
+    /// - **Windows**: set with `SetProcessAffinityMask` right after spawning,
+    ///   before the process runs when it is in a process group. If that fails,
+    ///   the process is killed and the task fails with `TaskError::Handle`.
+    /// - **Other platforms**: rejected by validation.
+    ///
+    /// # Arguments
+    ///
+    /// * `cores` - Indices of the cores the process may run on
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("cargo").args(["bench"]).cpu_affinity([0]);
+    /// assert_eq!(config.cpu_affinity, Some(vec![0]));
+    /// # #[cfg(any(target_os = "linux", target_os = "android", windows))]
+    /// assert!(config.validate().is_ok());
+    /// ```
+    #[must_use]
+    pub fn cpu_affinity<I>(mut self, cores: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        self.cpu_affinity = Some(cores.into_iter().collect());
+        self
+    }
+
+    /// Retry spawning the process when it fails with a transient error
+    ///
+    /// On loaded Windows machines, creating a process occasionally fails with
+    /// an access denied or sharing violation error, e.g. while an antivirus
+    /// scanner holds the executable, and succeeds when tried again. A spawn
+    /// failing with one of the `retry_on` error kinds is attempted again after
+    /// `backoff_ms`, up to `attempts` attempts in total, and each failed
+    /// attempt but the last emits `TaskEvent::SpawnRetry`. Other errors, and
+    /// the last failed attempt, are reported as without retries.
+    ///
+    /// # Arguments
+    ///
+    /// * `attempts` - Most attempts, including the first (1 to 10)
+    /// * `backoff_ms` - Delay between attempts in milliseconds (at most 60000)
+    /// * `retry_on` - Error kinds that are retried (at least one)
+    ///
+    /// # Examples
+    /// ```rust
+    /// use std::io::ErrorKind;
+    ///
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("my-tool.exe").spawn_retry(3, 100, [ErrorKind::PermissionDenied]);
+    /// assert!(config.validate().is_ok());
+    /// ```
+    #[must_use]
+    pub fn spawn_retry(
+        mut self,
+        attempts: u32,
+        backoff_ms: u64,
+        retry_on: impl IntoIterator<Item = std::io::ErrorKind>,
+    ) -> Self {
+        self.spawn_retry = Some(SpawnRetry {
+            attempts,
+            backoff_ms,
+            retry_on: retry_on.into_iter().collect(),
+        });
+        self
+    }
+
+    /// Set the maximum time to wait for the event channel when emitting output
+    ///
+    /// If the event receiver stops polling, waiting on the channel would eventually fill the
+    /// child's stdout/stderr pipe and block the child on write. Once a send exceeds this timeout,
+    /// the affected stream switches to drop-and-count mode: later events are delivered only
+    /// when the channel has capacity and are dropped otherwise. The number of dropped events
+    /// is reported in [`TaskInfo::dropped_events`](crate::tasks::async_tokio::spawner::TaskInfo).
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Send timeout in milliseconds (must be > 0)
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// // Never hold a chatty build hostage for more than 500ms
+    /// let config = TaskConfig::new("cargo")
+    ///     .args(["build"])
+    ///     .event_send_timeout_ms(500);
+    /// ```
+    #[must_use]
+    pub fn event_send_timeout_ms(mut self, timeout: u64) -> Self {
+        self.event_send_timeout_ms = Some(timeout);
+        self
+    }
+
+    /// Warn when the event channel consumer does not keep up with the output
+    ///
+    /// Once the output events of the last 16 sends waited longer than
+    /// `threshold` on average for room in the event channel, a single
+    /// `TaskEvent::SlowConsumer` is sent, and logged as a warning with the
+    /// `tracing` feature. Unlike
+    /// [`event_send_timeout_ms`](Self::event_send_timeout_ms), no event is
+    /// dropped. The total wait and the highest channel occupancy are always
+    /// reported in [`TaskInfo`](crate::tasks::async_tokio::spawner::TaskInfo).
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - Average wait in milliseconds (must be > 0)
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// // Tell when the UI falls more than 20ms behind per line
+    /// let config = TaskConfig::new("cargo")
+    ///     .args(["build"])
+    ///     .slow_consumer_threshold_ms(20);
+    /// ```
+    #[must_use]
+    pub fn slow_consumer_threshold_ms(mut self, threshold: u64) -> Self {
+        self.slow_consumer_threshold_ms = Some(threshold);
+        self
+    }
+
+    /// Enable or disable capturing stdout of the task
+    ///
+    /// When disabled, stdout is redirected to the null device, so no pipe is
+    /// created and no watcher task is spawned for it. Useful for tasks whose
+    /// output is not needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to emit stdout lines as output events
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// // Only errors are of interest
+    /// let config = TaskConfig::new("cargo")
+    ///     .args(["build"])
+    ///     .capture_stdout(false);
+    /// ```
+    #[must_use]
+    pub fn capture_stdout(mut self, enabled: bool) -> Self {
+        self.capture_stdout = Some(enabled);
+        self
+    }
+
+    /// Enable or disable capturing stderr of the task
+    ///
+    /// When disabled, stderr is redirected to the null device, so no pipe is
+    /// created and no watcher task is spawned for it.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to emit stderr lines as output events
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("my-server")
+    ///     .capture_stderr(false);
+    /// ```
+    #[must_use]
+    pub fn capture_stderr(mut self, enabled: bool) -> Self {
+        self.capture_stderr = Some(enabled);
+        self
+    }
+
+    /// Let the process use the terminal of this process directly
+    ///
+    /// For running a task interactively, such as a dev server started from a
+    /// CLI: stdin, stdout and stderr of the process are inherited instead of
+    /// piped, so it reads from and writes to the terminal itself. No output or
+    /// stdin watcher is spawned and no `TaskEvent::Output` is sent, but
+    /// lifecycle events, the timeout and termination (including the process
+    /// group) work as usual.
+    ///
+    /// Since output is not read, `ready_indicator`, output triggers and
+    /// `enable_stdin` are rejected by validation. On Unix, a process started
+    /// in its own process group is not in the foreground of the terminal and
+    /// is stopped if it reads from it; disable `use_process_group` for tasks
+    /// that read user input.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to inherit stdio instead of capturing it
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::{StreamSource, TaskConfig};
+    ///
+    /// let config = TaskConfig::new("npm").args(["start"]).stdio_inherit(true);
+    /// assert!(config.validate().is_ok());
+    /// assert!(!config.is_stream_captured(&StreamSource::Stdout));
+    /// ```
+    #[must_use]
+    pub fn stdio_inherit(mut self, enabled: bool) -> Self {
+        self.stdio_inherit = Some(enabled);
+        self
+    }
+
+    /// Set the read buffer size for each captured output stream
+    ///
+    /// Output is read from the process pipes into a buffer of this size
+    /// (default 8 KiB), from which lines are split. Raise it for very chatty
+    /// processes to reduce the number of reads, or lower it to save memory
+    /// when running many tasks. Lines longer than the buffer are still
+    /// delivered whole.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Buffer size in bytes (256 bytes to 4 MiB)
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("cargo")
+    ///     .args(["build", "-vv"])
+    ///     .output_buffer_bytes(64 * 1024);
+    /// assert!(config.validate().is_ok());
+    /// ```
+    #[must_use]
+    pub fn output_buffer_bytes(mut self, bytes: usize) -> Self {
+        self.output_buffer_bytes = Some(bytes);
+        self
+    }
+
+    /// Set the longest output line, in bytes
+    ///
+    /// A process that prints a huge line without a newline would otherwise have
+    /// the whole line buffered before it is sent. A line longer than `bytes` is
+    /// sent as soon as `bytes` of it are read, cut on a character boundary, with
+    /// `truncated: true` in `TaskEvent::Output`; the rest of it is discarded.
+    /// The ready indicator and output triggers only see the truncated line.
+    ///
+    /// Defaults to 1 MiB. Does not apply to `OutputMode::Bytes`, whose chunks
+    /// are bounded by [`output_buffer_bytes`](Self::output_buffer_bytes).
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Longest line sent whole, must be greater than 0
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("minified-build").max_output_line_bytes(64 * 1024);
+    /// assert!(config.validate().is_ok());
+    ///
+    /// let config = TaskConfig::new("minified-build").max_output_line_bytes(0);
+    /// assert!(config.validate().is_err());
+    /// ```
+    #[must_use]
+    pub fn max_output_line_bytes(mut self, bytes: usize) -> Self {
+        self.max_output_line_bytes = Some(bytes);
+        self
+    }
+
+    /// Enable or disable parsing of JSON-lines output
+    ///
+    /// Many tools print one JSON object per line (e.g. `cargo --message-format=json`).
+    /// When enabled, each line that starts with `{` or `[` is parsed and, if it is
+    /// valid JSON, sent as `TaskEvent::OutputJson` instead of `TaskEvent::Output`.
+    /// Other lines, and lines longer than 1 MiB, are sent as plain output. The ready
+    /// indicator is still matched against the raw line.
+    ///
+    /// Requires the `serde` feature; without it, validation rejects the configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to parse JSON lines
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("cargo")
+    ///     .args(["build", "--message-format=json"])
+    ///     .parse_json_lines(true);
+    /// ```
+    #[must_use]
+    pub fn parse_json_lines(mut self, enabled: bool) -> Self {
+        self.parse_json_lines = Some(enabled);
+        self
+    }
+
+    /// Read stdout and stderr from a single watcher task
+    ///
+    /// By default each captured stream has its own watcher task, and on a
+    /// multi-threaded runtime one of them can run ahead of the other. When
+    /// enabled, both pipes are read by one task, so lines are sent in the
+    /// order they are read.
+    ///
+    /// This is best-effort: the two pipes are separate kernel buffers, so
+    /// lines the process writes to both streams in quick succession can
+    /// still be read out of order. Each `TaskEvent::Output` carries a `seq`
+    /// number, taken when the line is read, that orders lines across both
+    /// streams whether or not this is enabled. For exact ordering, have the
+    /// process write both to a single stream.
+    ///
+    /// Has no effect unless both stdout and stderr are captured. The single
+    /// watcher task is reported as `output` in join errors.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to read both streams from one task
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("make")
+    ///     .args(["all"])
+    ///     .preserve_interleaving(true);
+    /// ```
+    #[must_use]
+    pub fn preserve_interleaving(mut self, enabled: bool) -> Self {
+        self.preserve_interleaving = Some(enabled);
+        self
+    }
+
+    /// Set the expected runtime in milliseconds
+    ///
+    /// A soft deadline for reporting: once the task has run for
+    /// `running_long_multiplier` times this duration (twice by default), a single
+    /// `TaskEvent::RunningLong` is emitted. The task keeps running; use
+    /// [`timeout_ms`](Self::timeout_ms) to stop it. The expectation is also
+    /// reported alongside the actual duration in `TaskEvent::Stopped`.
+    ///
+    /// # Arguments
+    ///
+    /// * `expected` - Expected runtime in milliseconds (must be > 0)
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// // Usually takes 30 seconds, report if it is still running after a minute
+    /// let config = TaskConfig::new("cargo")
+    ///     .args(["test"])
+    ///     .expected_duration_ms(30_000);
+    /// ```
+    #[must_use]
+    pub fn expected_duration_ms(mut self, expected: u64) -> Self {
+        self.expected_duration_ms = Some(expected);
+        self
+    }
+
+    /// Set the multiple of the expected runtime after which the task is reported as running long
+    ///
+    /// Only has an effect together with
+    /// [`expected_duration_ms`](Self::expected_duration_ms).
+    ///
+    /// # Arguments
+    ///
+    /// * `multiplier` - Positive, finite factor (default: 2.0)
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// // Report once the task took 50% longer than expected
+    /// let config = TaskConfig::new("cargo")
+    ///     .args(["test"])
+    ///     .expected_duration_ms(30_000)
+    ///     .running_long_multiplier(1.5);
+    /// assert!(config.validate().is_ok());
+    /// ```
+    #[must_use]
+    pub fn running_long_multiplier(mut self, multiplier: f64) -> Self {
+        self.running_long_multiplier = Some(multiplier);
+        self
+    }
+
+    /// Warn when the resident memory of the task crosses a watermark
+    ///
+    /// Unlike a hard memory limit, crossing the watermark never stops the
+    /// task: it gives the caller a chance to act before the OOM killer does.
+    /// While the process runs, its resident set size is sampled every
+    /// [`sample_interval_ms`](Self::sample_interval_ms), and
+    /// `TaskEvent::MemoryWatermarkExceeded` is emitted once per crossing. The
+    /// watermark is armed again only after the usage fell below 90% of it, so
+    /// usage hovering around the watermark does not flap.
+    ///
+    /// - **Linux**: the task's whole process group is sampled when
+    ///   [`use_process_group`](Self::use_process_group) is enabled, otherwise
+    ///   the child alone.
+    /// - **Windows**: the child alone is sampled.
+    /// - **Other platforms**: no usage is available, so nothing is emitted.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Resident memory in bytes (must be > 0)
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// // Warn once the build uses more than 2 GiB
+    /// let config = TaskConfig::new("cargo")
+    ///     .args(["build"])
+    ///     .memory_watermark_bytes(2 << 30);
+    /// assert!(config.validate().is_ok());
+    /// ```
+    #[must_use]
+    pub fn memory_watermark_bytes(mut self, bytes: u64) -> Self {
+        self.memory_watermark_bytes = Some(bytes);
+        self
+    }
+
+    /// Set the interval between memory samples for the watermark
+    ///
+    /// Only has an effect together with
+    /// [`memory_watermark_bytes`](Self::memory_watermark_bytes). Shorter
+    /// intervals catch short spikes, at the cost of reading the process
+    /// table more often.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval_ms` - Interval in milliseconds (at least 10, default: 1000)
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("cargo")
+    ///     .args(["build"])
+    ///     .memory_watermark_bytes(2 << 30)
+    ///     .sample_interval_ms(250);
+    /// assert!(config.validate().is_ok());
+    /// ```
+    #[must_use]
+    pub fn sample_interval_ms(mut self, interval_ms: u64) -> Self {
+        self.sample_interval_ms = Some(interval_ms);
+        self
+    }
+
+    /// Enable or disable recording the environment of the spawned process
+    ///
+    /// When enabled, the environment the process actually received (the inherited
+    /// environment with the configured [`env`](Self::env) applied on top) is
+    /// recorded at spawn. It can be read with
+    /// [`TaskSpawner::spawned_env`](crate::tasks::async_tokio::spawner::TaskSpawner::spawned_env),
+    /// or with sensitive values masked with
+    /// [`TaskSpawner::spawned_env_redacted`](crate::tasks::async_tokio::spawner::TaskSpawner::spawned_env_redacted).
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to record the environment at spawn
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("cargo")
+    ///     .args(["build"])
+    ///     .env([("RUSTFLAGS", "-Dwarnings")])
+    ///     .capture_env_snapshot(true);
+    /// ```
+    #[must_use]
+    pub fn capture_env_snapshot(mut self, enabled: bool) -> Self {
+        self.capture_env_snapshot = Some(enabled);
+        self
+    }
+
+    /// Set the text encoding of the process output
+    ///
+    /// Output is decoded before it is split into lines, so lines are always
+    /// sent as UTF-8. Use [`OutputEncoding::Utf16Le`] for tools that write
+    /// UTF-16 (e.g. `cmd /U` on Windows), or [`OutputEncoding::Auto`] to pick
+    /// the encoding from a byte order mark at the start of each stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `encoding` - Encoding of stdout and stderr
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::{OutputEncoding, TaskConfig};
+    ///
+    /// let config = TaskConfig::new("cmd")
+    ///     .args(["/U", "/C", "echo hello"])
+    ///     .output_encoding(OutputEncoding::Utf16Le);
+    /// ```
+    #[must_use]
+    pub fn output_encoding(mut self, encoding: OutputEncoding) -> Self {
+        self.output_encoding = Some(encoding);
+        self
+    }
+
+    /// Set whether output is sent as lines or as raw byte chunks
+    ///
+    /// With [`OutputMode::Bytes`], each stream is read in chunks of up to
+    /// [`output_buffer_bytes`](Self::output_buffer_bytes) and sent as
+    /// `TaskEvent::OutputChunk` exactly as the process wrote it, without
+    /// splitting lines or decoding, e.g. for binary or terminal output.
+    ///
+    /// Options that look at lines cannot be combined with bytes mode and fail
+    /// validation: ready indicators, output triggers, stdin scripts,
+    /// `parse_json_lines`, `dedupe_output`, `max_output_events_per_sec` and an
+    /// `output_encoding` other than UTF-8. The output transform is not
+    /// applied, and no lines are kept for the startup output or the output tail.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - How stdout and stderr are sent
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::{OutputMode, TaskConfig};
+    ///
+    /// let config = TaskConfig::new("tar")
+    ///     .args(["-cf", "-", "src"])
+    ///     .output_mode(OutputMode::Bytes);
+    /// assert!(config.validate().is_ok());
+    ///
+    /// let config = config.ready_indicator("done");
+    /// assert!(config.validate().is_err());
+    /// ```
+    #[must_use]
+    pub fn output_mode(mut self, mode: OutputMode) -> Self {
+        self.output_mode = Some(mode);
+        self
+    }
+
+    /// Enable or disable SHA-256 digests of the output
+    ///
+    /// When enabled, each captured stream is hashed as it is read, and
+    /// `TaskEvent::Stopped` reports the digests as lowercase hex in
+    /// `stdout_sha256` and `stderr_sha256`, e.g. to check that a build
+    /// reproduces its output without buffering it. The digest covers the raw
+    /// bytes the process wrote, before [`output_encoding`](Self::output_encoding)
+    /// decodes them, and every byte read, whether or not its line was sent as
+    /// an event. A stream that is not captured has no digest.
+    ///
+    /// Requires the `sha2` feature. Validation fails if no stream is captured.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to hash the output streams
+    ///
+    /// # Examples
+    #[cfg_attr(feature = "sha2", doc = "```rust")]
+    #[cfg_attr(not(feature = "sha2"), doc = "```ignore")]
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("cargo")
+    ///     .args(["metadata", "--format-version=1"])
+    ///     .hash_output(true);
+    /// assert!(config.validate().is_ok());
+    ///
+    /// let uncaptured = TaskConfig::new("cargo").stdio_inherit(true).hash_output(true);
+    /// assert!(uncaptured.validate().is_err());
+    /// ```
+    #[must_use]
+    pub fn hash_output(mut self, enabled: bool) -> Self {
+        self.hash_output = Some(enabled);
+        self
+    }
+
+    /// Set human-readable meanings for exit codes
+    ///
+    /// When the process exits with a mapped code, its meaning is reported as
+    /// `exit_meaning` in `TaskEvent::Stopped` and in `TaskInfo`, so consumers
+    /// can show it without matching on codes themselves. At most 256 codes can
+    /// be mapped.
+    ///
+    /// # Arguments
+    ///
+    /// * `meanings` - Iterator of (exit code, meaning) pairs
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("cargo")
+    ///     .args(["test"])
+    ///     .exit_code_meanings([(101, "test failure"), (137, "OOM killed")]);
+    ///
+    /// assert_eq!(config.exit_code_meaning(101), Some("test failure"));
+    /// assert_eq!(config.exit_code_meaning(0), None);
+    /// ```
+    #[must_use]
+    pub fn exit_code_meanings<V, I>(mut self, meanings: I) -> Self
+    where
+        V: Into<String>,
+        I: IntoIterator<Item = (i32, V)>,
+    {
+        self.exit_code_meanings = Some(
+            meanings
+                .into_iter()
+                .map(|(code, meaning)| (code, meaning.into()))
+                .collect(),
+        );
+        self
+    }
+
+    /// Collapse runs of identical output lines
+    ///
+    /// Guards consumers against children stuck in an error loop that print the
+    /// same line over and over. After `max_repeats` consecutive identical lines
+    /// on a stream, further repeats are not sent as `TaskEvent::Output` but
+    /// counted, and a `TaskEvent::OutputRepeated` with the count is sent once a
+    /// different line arrives, the stream ends, or `window_ms` has passed since
+    /// the first collapsed repeat. A run that keeps going is summarized once
+    /// per window.
+    ///
+    /// Lines are compared after the output transform. The ready indicator is
+    /// still matched against every line, including collapsed ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `window_ms` - Longest time repeats are collapsed before a summary is sent (must be > 0)
+    /// * `max_repeats` - Identical consecutive lines sent before repeats are collapsed (must be > 0)
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// // Send a line at most 3 times in a row, then summarize repeats every second
+    /// let config = TaskConfig::new("flaky-service").dedupe_output(1000, 3);
+    /// assert!(config.validate().is_ok());
+    /// ```
+    #[must_use]
+    pub fn dedupe_output(mut self, window_ms: u64, max_repeats: u32) -> Self {
+        self.dedupe_output = Some(OutputDedupe {
+            window_ms,
+            max_repeats,
+        });
+        self
+    }
+
+    /// Make the output watchers yield to the runtime every `lines` lines
+    ///
+    /// A chatty child keeps its output watchers busy: one read can hold many
+    /// lines, and the watcher handles them back to back. On a busy runtime this
+    /// delays the other tasks sharing a worker thread, such as timers of the
+    /// application. With this option, each watcher calls
+    /// `tokio::task::yield_now` after every `lines` lines it read, trading some
+    /// output throughput for the latency of co-located tasks.
+    ///
+    /// Without it, watchers rely on tokio's cooperative budget alone, which
+    /// sending an event and skipping a line (e.g. a collapsed repeat) both
+    /// consume.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - Lines handled between yields (must be > 0)
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("cargo").args(["build", "-vv"]).yield_every_n_lines(64);
+    /// assert!(config.validate().is_ok());
+    /// ```
+    #[must_use]
+    pub fn yield_every_n_lines(mut self, lines: usize) -> Self {
+        self.yield_every_n_lines = Some(lines);
+        self
+    }
+
+    /// Limit the rate of output events
+    ///
+    /// Protects consumers that render every line, such as a UI, from a child
+    /// that floods its output. Each stream sends at most `max` lines per
+    /// second as `TaskEvent::Output`; further lines in that second are not
+    /// sent but counted, and a `TaskEvent::OutputSuppressed` with their count
+    /// and size is sent once the second is over. Unlike
+    /// [`dedupe_output`](Self::dedupe_output), this applies to lines that
+    /// differ.
+    ///
+    /// Lines are throttled after deduplication. The ready indicator and output
+    /// triggers are still matched against every line, including suppressed
+    /// ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - Output events sent per second on each stream (must be > 0)
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("chatty-build").max_output_events_per_sec(200);
+    /// assert!(config.validate().is_ok());
+    /// ```
+    #[must_use]
+    pub fn max_output_events_per_sec(mut self, max: u32) -> Self {
+        self.max_output_events_per_sec = Some(max);
+        self
+    }
+
+    /// Set how many of the first output lines are kept for error reporting
+    ///
+    /// The first lines of stdout and stderr, in the order they were read, are
+    /// kept regardless of whether the event consumer still has them. They can be
+    /// read with
+    /// [`TaskSpawner::startup_output`](crate::tasks::async_tokio::spawner::TaskSpawner::startup_output),
+    /// and are included in `TaskInfo` when the task fails before it is ready.
+    /// At most 64 KiB of output is kept; lines are captured after the output
+    /// transform.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - Number of lines to keep (0 to 1000, default: 50, 0 disables capture)
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("my-server").startup_capture_lines(20);
+    /// assert!(config.validate().is_ok());
+    /// ```
+    #[must_use]
+    pub fn startup_capture_lines(mut self, lines: usize) -> Self {
+        self.startup_capture_lines = Some(lines);
+        self
+    }
+
+    /// Set how many of the last output lines are retained
+    ///
+    /// The most recent lines of stdout and stderr, in the order they were
+    /// read, are kept in a ring buffer that can be read at any time, including
+    /// after the task stopped, with
+    /// [`TaskSpawner::tail`](crate::tasks::async_tokio::spawner::TaskSpawner::tail).
+    /// At most 1 MiB of output is kept; lines are retained after the output
+    /// transform and before deduplication.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Number of lines to keep (0 to 10000, 0 disables retention)
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("cargo").args(["build"]).retain_output_lines(200);
+    /// assert!(config.validate().is_ok());
+    /// ```
+    #[must_use]
+    pub fn retain_output_lines(mut self, capacity: usize) -> Self {
+        self.retain_output_lines = Some(capacity);
+        self
+    }
+
+    /// Set how many output lines of each stream `run_to_completion` keeps
+    ///
+    /// Bounds the memory used by
+    /// [`TaskSpawner::run_to_completion`](crate::tasks::async_tokio::spawner::TaskSpawner::run_to_completion):
+    /// the first `lines` lines of stdout and of stderr are returned, later
+    /// ones are only counted. Has no effect on the events of `start_direct`.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - Number of lines kept per stream (0 keeps none)
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("cargo").args(["test"]).max_captured_lines(500);
+    /// ```
+    #[must_use]
+    pub fn max_captured_lines(mut self, lines: usize) -> Self {
+        self.max_captured_lines = Some(lines);
+        self
+    }
+
+    /// Add an action taken when an output line contains a pattern
+    ///
+    /// Each trigger watches a single stream and is checked against every line
+    /// of it, on the same text as the ready indicator. A `Ready` trigger works
+    /// like [`ready_indicator`](Self::ready_indicator), and the task becomes
+    /// ready on the first match of any of them. A `FailTask` trigger terminates
+    /// the task on its first match, stopping it with `TaskEventStopReason::Error`.
+    /// A `Complete` trigger terminates it on its first match as finished, see
+    /// [`exit_on_output_match`](Self::exit_on_output_match). An `EmitEvent`
+    /// trigger sends `TaskEvent::Triggered` for every matching line, after its
+    /// output event.
+    ///
+    /// # Arguments
+    ///
+    /// * `trigger` - The pattern, stream and action of the trigger
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::{OutputTrigger, StreamSource, TaskConfig, TriggerAction};
+    ///
+    /// let config = TaskConfig::new("my-server")
+    ///     .output_trigger(OutputTrigger::new("Listening", StreamSource::Stdout, TriggerAction::Ready))
+    ///     .output_trigger(OutputTrigger::new("FATAL", StreamSource::Stderr, TriggerAction::FailTask))
+    ///     .output_trigger(OutputTrigger::new(
+    ///         "Reloaded",
+    ///         StreamSource::Stdout,
+    ///         TriggerAction::EmitEvent("reload".to_string()),
+    ///     ));
+    /// assert!(config.validate().is_ok());
+    /// ```
+    #[must_use]
+    pub fn output_trigger(mut self, trigger: OutputTrigger) -> Self {
+        self.output_triggers
+            .get_or_insert_with(Vec::new)
+            .push(trigger);
+        self
+    }
+
+    /// Terminate the task once a stdout line contains a pattern
+    ///
+    /// For tools that finish their work and print a summary but then keep
+    /// running, for example waiting on a TTY. The inverse of the ready
+    /// indicator: on the first matching line the task is terminated and stops
+    /// with `TaskTerminateReason::CompletionIndicator`, reported as a
+    /// successful run unless disabled with
+    /// [`completion_success`](Self::completion_success).
+    ///
+    /// Adds a `Complete` trigger on stdout; use
+    /// [`output_trigger`](Self::output_trigger) to watch stderr instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - Text whose appearance in a line marks the work as done
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("my-tool")
+    ///     .exit_on_output_match("ALL DONE")
+    ///     .timeout_ms(60_000);
+    /// assert!(config.validate().is_ok());
+    /// ```
+    #[must_use]
+    pub fn exit_on_output_match(self, pattern: impl Into<String>) -> Self {
+        self.output_trigger(OutputTrigger::new(
+            pattern,
+            StreamSource::Stdout,
+            TriggerAction::Complete,
+        ))
+    }
+
+    /// Set whether a task stopped by a `Complete` trigger is reported as successful
+    ///
+    /// When enabled (the default), `TaskEvent::Stopped` carries exit code 0
+    /// along with `TaskTerminateReason::CompletionIndicator`. When disabled it
+    /// carries no exit code and the run counts as failed, e.g. in a
+    /// `TaskGroup` summary.
+    ///
+    /// # Arguments
+    ///
+    /// * `success` - Whether the completion indicator marks a successful run
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("my-tool")
+    ///     .exit_on_output_match("ALL DONE")
+    ///     .completion_success(false);
+    /// ```
+    #[must_use]
+    pub fn completion_success(mut self, success: bool) -> Self {
+        self.completion_success = Some(success);
+        self
+    }
+
+    /// Set the patterns of inherited environment variables that are warned about
+    ///
+    /// Replaces [`DEFAULT_RISKY_ENV_PATTERNS`] in
+    /// [`validate_with_warnings`](Self::validate_with_warnings). Patterns are
+    /// matched ignoring case, with `*` matching any run of characters.
+    ///
+    /// # Arguments
+    ///
+    /// * `patterns` - Iterator of variable name patterns
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("my-tool").risky_env_patterns(["LD_*", "*_PROXY", "JAVA_TOOL_OPTIONS"]);
+    /// ```
+    #[must_use]
+    pub fn risky_env_patterns<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.risky_env_patterns = Some(patterns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Drive an interactive program by answering its prompts on stdin
+    ///
+    /// Steps run in order: each waits for an output line matching its
+    /// pattern, as the ready indicator would with
+    /// [`ready_match_mode`](Self::ready_match_mode), then writes its input and
+    /// emits `TaskEvent::ScriptStep`. Each step only sees lines read after the
+    /// previous step ran. Once every step has run, stdin is closed
+    /// unless a receiver set with `TaskSpawner::set_stdin` is still open; lines
+    /// from that receiver are written alongside the script's.
+    ///
+    /// A step whose line does not appear within its timeout, counted from the
+    /// end of the previous step or from the start of the process, terminates
+    /// the task with `TaskTerminateReason::ScriptTimeout`. Patterns are
+    /// matched against whole lines, so a prompt must end with a line break to
+    /// be seen.
+    ///
+    /// Requires [`enable_stdin`](Self::enable_stdin).
+    ///
+    /// # Arguments
+    ///
+    /// * `script` - The steps of the script
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::{StdinScript, StdinScriptStep, StreamSource, TaskConfig};
+    ///
+    /// let config = TaskConfig::new("ftp")
+    ///     .enable_stdin(true)
+    ///     .stdin_script(StdinScript::new([
+    ///         StdinScriptStep::new("Name", "anonymous", 5_000),
+    ///         StdinScriptStep::new("Password", "guest", 5_000).source(StreamSource::Stderr),
+    ///     ]));
+    /// assert!(config.validate().is_ok());
+    /// ```
+    #[must_use]
+    pub fn stdin_script(mut self, script: StdinScript) -> Self {
+        self.stdin_script = Some(script);
+        self
+    }
+
+    /// Get the configured meaning of an exit code, if any
+    pub fn exit_code_meaning(&self, code: i32) -> Option<&str> {
+        self.exit_code_meanings
+            .as_ref()?
+            .get(&code)
+            .map(String::as_str)
+    }
+
+    /// Validate the configuration
+    ///
+    /// Validates all configuration parameters.
+    /// This method should be called before executing the task to ensure
+    /// safe operation.
+    ///
+    /// # Validation Checks
+    /// - all fields length limits
+    /// - **Command**: Must not be empty, contain shell injection patterns
+    /// - **Arguments**: Must not contain null bytes or shell injection patterns  
+    /// - **Working Directory**: Must exist and be a valid directory
+    /// - **Working Directory Candidates**: Not combined with `working_dir`, at least one must be a valid working directory
+    /// - **Temporary Working Directory**: Not combined with `working_dir` or `working_dir_candidates`, prefix of 1 to 64 characters without path separators
+    /// - **Environment Variables**: Keys must not contain spaces, '=', or null bytes
+    /// - **Timeout**: Must be greater than 0 if specified
+    /// - **Event Send Timeout**: Must be greater than 0 if specified
+    /// - **Job Object Name**: 1 to 260 characters without null bytes if specified
+    /// - **Parent Death**: Supported on Linux and Windows, where it requires process groups
+    /// - **Spawn Retry**: 1 to 10 attempts, at most 60 s apart, retrying at least one error kind
+    /// - **Output Buffer**: Must be between 256 bytes and 4 MiB if specified
+    /// - **Ready Indicator**: Must not be empty if specified, and its source stream must be captured
+    /// - **Inherited Stdio**: Not combined with a ready indicator, output triggers or `enable_stdin`
+    /// - **JSON Lines**: Parsing requires the `serde` feature
+    /// - **Expected Duration**: Must be greater than 0 if specified, with a positive, finite multiplier
+    /// - **Memory Watermark**: Must be greater than 0 bytes, sampled at least 10 ms apart
+    /// - **Output Dedupe**: Window and repeat threshold must be greater than 0 if specified
+    /// - **Output Rate Limit**: Must be greater than 0 if specified
+    /// - **Watcher Yielding**: Must be greater than 0 lines if specified
+    /// - **Startup Capture**: At most 1000 lines if specified
+    /// - **Retained Output**: At most 10000 lines if specified
+    /// - **Output Triggers**: At most 64, each with a non-empty pattern and event name, on a captured stream
+    /// - **Stdin Script**: 1 to 64 steps, each with a non-empty pattern on a captured stream and a timeout greater than 0; requires `enable_stdin`
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the configuration is valid
+    /// - `Err(TaskError::InvalidConfiguration)` with details if validation fails
+    ///
+    /// # Errors
     ///
     /// Returns a [`TaskError`] if any validation check fails:
     /// - [`TaskError::InvalidConfiguration`] for configuration errors
@@ -360,24 +1979,365 @@ impl TaskConfig {
     /// ```
     pub fn validate(&self) -> Result<(), TaskError> {
         ConfigValidator::validate_command(&self.command)?;
+        if self.stdio_inherit.unwrap_or_default() {
+            if self.ready_indicator.is_some() || self.ready_indicator_regex.is_some() {
+                return Err(TaskError::InvalidConfiguration(
+                    "Ready indicator cannot be used with inherited stdio".to_string(),
+                ));
+            }
+            if self.enable_stdin.unwrap_or_default() {
+                return Err(TaskError::InvalidConfiguration(
+                    "enable_stdin cannot be used with inherited stdio".to_string(),
+                ));
+            }
+        }
+        if self.output_mode == Some(OutputMode::Bytes) {
+            ConfigValidator::validate_bytes_output_mode(self)?;
+        }
         if let Some(ready_indicator) = &self.ready_indicator {
             ConfigValidator::validate_ready_indicator(ready_indicator)?;
         }
+        if let Some(pattern) = &self.ready_indicator_regex {
+            if self.ready_indicator.is_some() {
+                return Err(TaskError::InvalidConfiguration(
+                    "ready_indicator and ready_indicator_regex cannot both be set".to_string(),
+                ));
+            }
+            ConfigValidator::validate_ready_indicator_regex(pattern)?;
+        }
+        if self.ready_indicator.is_some() || self.ready_indicator_regex.is_some() {
+            let source = self.ready_indicator_source.clone().unwrap_or_default();
+            if !self.is_stream_captured(&source) {
+                return Err(TaskError::InvalidConfiguration(format!(
+                    "Ready indicator source {source:?} is not captured"
+                )));
+            }
+        }
         if let Some(args) = &self.args {
             ConfigValidator::validate_args(args)?;
         }
         if let Some(dir) = &self.working_dir {
             ConfigValidator::validate_working_dir(dir)?;
         }
+        if self.working_dir_candidates.is_some() {
+            if self.working_dir.is_some() {
+                return Err(TaskError::InvalidConfiguration(
+                    "working_dir_candidates cannot be combined with working_dir".to_string(),
+                ));
+            }
+            self.resolve_working_dir()?;
+        }
+        if let Some(temp) = &self.temp_working_dir {
+            if self.working_dir.is_some() {
+                return Err(TaskError::InvalidConfiguration(
+                    "temp_working_dir cannot be combined with working_dir".to_string(),
+                ));
+            }
+            if self.working_dir_candidates.is_some() {
+                return Err(TaskError::InvalidConfiguration(
+                    "temp_working_dir cannot be combined with working_dir_candidates".to_string(),
+                ));
+            }
+            if let Some(prefix) = &temp.prefix {
+                ConfigValidator::validate_temp_dir_prefix(prefix)?;
+            }
+        }
         if let Some(env) = &self.env {
             ConfigValidator::validate_env_vars(env)?;
         }
+        if let Some(names) = &self.inherit_env_vars {
+            if !self.clear_env.unwrap_or_default() {
+                return Err(TaskError::InvalidConfiguration(
+                    "inherit_env_vars requires clear_env".to_string(),
+                ));
+            }
+            ConfigValidator::validate_env_var_names(names)?;
+        }
         if let Some(timeout) = &self.timeout_ms {
             ConfigValidator::validate_timeout(timeout)?;
         }
+        if let Some(timeout) = &self.event_send_timeout_ms {
+            ConfigValidator::validate_timeout(timeout)?;
+        }
+        if let Some(grace) = &self.graceful_shutdown_ms {
+            ConfigValidator::validate_timeout(grace)?;
+            if cfg!(windows) && !self.is_process_group_enabled() {
+                return Err(TaskError::InvalidConfiguration(
+                    "graceful_shutdown_ms requires use_process_group on Windows".to_string(),
+                ));
+            }
+        }
+        if let Some(name) = &self.job_object_name {
+            ConfigValidator::validate_job_object_name(name)?;
+        }
+        if let Some(retry) = &self.spawn_retry {
+            ConfigValidator::validate_spawn_retry(retry)?;
+        }
+        if self.kill_child_on_parent_death.unwrap_or_default() {
+            if cfg!(not(any(
+                target_os = "linux",
+                target_os = "android",
+                windows
+            ))) {
+                return Err(TaskError::InvalidConfiguration(
+                    "kill_child_on_parent_death is not supported on this platform".to_string(),
+                ));
+            }
+            if cfg!(windows) && !self.is_process_group_enabled() {
+                return Err(TaskError::InvalidConfiguration(
+                    "kill_child_on_parent_death requires use_process_group on Windows".to_string(),
+                ));
+            }
+        }
+        if self.priority.is_some() && cfg!(not(any(unix, windows))) {
+            return Err(TaskError::InvalidConfiguration(
+                "priority is not supported on this platform".to_string(),
+            ));
+        }
+        if let Some(cores) = &self.cpu_affinity {
+            if cfg!(not(any(
+                target_os = "linux",
+                target_os = "android",
+                windows
+            ))) {
+                return Err(TaskError::InvalidConfiguration(
+                    "cpu_affinity is not supported on this platform".to_string(),
+                ));
+            }
+            ConfigValidator::validate_cpu_affinity(cores)?;
+        }
+        if let Some(bytes) = self.output_buffer_bytes {
+            ConfigValidator::validate_output_buffer_bytes(bytes)?;
+        }
+        if let Some(bytes) = self.max_output_line_bytes {
+            ConfigValidator::validate_max_output_line_bytes(bytes)?;
+        }
+        if cfg!(not(feature = "serde")) && self.parse_json_lines.unwrap_or_default() {
+            return Err(TaskError::InvalidConfiguration(
+                "parse_json_lines requires the serde feature".to_string(),
+            ));
+        }
+        if self.hash_output.unwrap_or_default() {
+            if cfg!(not(feature = "sha2")) {
+                return Err(TaskError::InvalidConfiguration(
+                    "hash_output requires the sha2 feature".to_string(),
+                ));
+            }
+            if !self.is_stream_captured(&StreamSource::Stdout)
+                && !self.is_stream_captured(&StreamSource::Stderr)
+            {
+                return Err(TaskError::InvalidConfiguration(
+                    "hash_output requires a captured output stream".to_string(),
+                ));
+            }
+        }
+        if let Some(expected) = &self.expected_duration_ms {
+            ConfigValidator::validate_timeout(expected)?;
+        }
+        if let Some(multiplier) = self.running_long_multiplier {
+            ConfigValidator::validate_running_long_multiplier(multiplier)?;
+        }
+        if self.slow_consumer_threshold_ms == Some(0) {
+            return Err(TaskError::InvalidConfiguration(
+                "slow_consumer_threshold_ms must be greater than 0".to_string(),
+            ));
+        }
+        if self.memory_watermark_bytes == Some(0) {
+            return Err(TaskError::InvalidConfiguration(
+                "memory_watermark_bytes must be greater than 0".to_string(),
+            ));
+        }
+        if let Some(interval_ms) = self.sample_interval_ms {
+            ConfigValidator::validate_sample_interval_ms(interval_ms)?;
+        }
+        if let Some(meanings) = &self.exit_code_meanings {
+            ConfigValidator::validate_exit_code_meanings(meanings)?;
+        }
+        if let Some(dedupe) = &self.dedupe_output {
+            ConfigValidator::validate_output_dedupe(dedupe)?;
+        }
+        if self.yield_every_n_lines == Some(0) {
+            return Err(TaskError::InvalidConfiguration(
+                "yield_every_n_lines must be greater than 0".to_string(),
+            ));
+        }
+        if let Some(max) = self.max_output_events_per_sec {
+            ConfigValidator::validate_max_output_events_per_sec(max)?;
+        }
+        if let Some(lines) = self.startup_capture_lines {
+            ConfigValidator::validate_startup_capture_lines(lines)?;
+        }
+        if let Some(lines) = self.retain_output_lines {
+            ConfigValidator::validate_retain_output_lines(lines)?;
+        }
+        if let Some(triggers) = &self.output_triggers {
+            ConfigValidator::validate_output_triggers(triggers)?;
+            if let Some(trigger) = triggers
+                .iter()
+                .find(|trigger| !self.is_stream_captured(&trigger.source))
+            {
+                return Err(TaskError::InvalidConfiguration(format!(
+                    "Output trigger source {:?} is not captured",
+                    trigger.source
+                )));
+            }
+        }
+        if let Some(script) = &self.stdin_script {
+            ConfigValidator::validate_stdin_script(script)?;
+            if !self.enable_stdin.unwrap_or_default() {
+                return Err(TaskError::InvalidConfiguration(
+                    "stdin_script requires enable_stdin".to_string(),
+                ));
+            }
+            if let Some(step) = script
+                .steps
+                .iter()
+                .find(|step| !self.is_stream_captured(&step.source))
+            {
+                return Err(TaskError::InvalidConfiguration(format!(
+                    "Stdin script source {:?} is not captured",
+                    step.source
+                )));
+            }
+        }
         Ok(())
     }
 
+    /// Triggers evaluated on the given stream
+    ///
+    /// Includes `ready_indicator` as a `Ready` trigger when it watches `src`.
+    #[cfg(feature = "tokio")]
+    pub(crate) fn stream_triggers(&self, src: &StreamSource) -> Vec<OutputTrigger> {
+        let legacy = self
+            .ready_indicator
+            .as_ref()
+            .map(|indicator| OutputTrigger {
+                pattern: indicator.clone(),
+                source: self.ready_indicator_source.clone().unwrap_or_default(),
+                action: TriggerAction::Ready,
+            });
+        legacy
+            .into_iter()
+            .chain(self.output_triggers.iter().flatten().cloned())
+            .filter(|trigger| trigger.source == *src)
+            .collect()
+    }
+
+    /// `ready_indicator_regex` compiled, when it watches the given stream
+    ///
+    /// Always `None` without the `regex` feature, which validation requires.
+    #[cfg(feature = "tokio")]
+    pub(crate) fn stream_ready_regex(&self, src: &StreamSource) -> Option<ReadyRegex> {
+        let pattern = self.ready_indicator_regex.as_ref()?;
+        let source = self.ready_indicator_source.clone().unwrap_or_default();
+        if source != *src {
+            return None;
+        }
+        Some(ReadyRegex {
+            #[cfg(feature = "regex")]
+            regex: regex::Regex::new(pattern).ok()?,
+            trigger: OutputTrigger {
+                pattern: pattern.clone(),
+                source,
+                action: TriggerAction::Ready,
+            },
+        })
+    }
+
+    /// Validate the configuration and report settings that are likely mistakes
+    ///
+    /// Runs [`validate`](Self::validate) and, whatever its outcome, collects
+    /// warnings that do not fail validation:
+    ///
+    /// # Warnings
+    /// - **Inherited Risky Env**: an inherited environment variable matching
+    ///   `risky_env_patterns` (default [`DEFAULT_RISKY_ENV_PATTERNS`]) that `env`
+    ///   does not override, and `clear_env` does not drop
+    /// - **Missing Timeout**: no timeout for a command that usually runs until
+    ///   stopped, such as `tail -f`
+    /// - **Ready Indicator Without Timeout**: a ready indicator or `Ready` output
+    ///   trigger without a timeout
+    ///
+    /// Whether stdin has a source is only known once a receiver is set, so
+    /// [`ValidationWarning::StdinWithoutSource`] is reported by
+    /// `TaskSpawner::validate_with_warnings`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tcrm_task::tasks::{config::TaskConfig, validator::ValidationWarning};
+    ///
+    /// let (result, warnings) = TaskConfig::new("tail").args(["-f", "app.log"]).validate_with_warnings();
+    /// assert!(result.is_ok());
+    /// assert!(warnings.contains(&ValidationWarning::MissingTimeout {
+    ///     command: "tail".to_string(),
+    /// }));
+    /// ```
+    pub fn validate_with_warnings(&self) -> (Result<(), TaskError>, Vec<ValidationWarning>) {
+        let mut warnings = self.inherited_risky_env();
+        if self.timeout_ms.is_none() {
+            let args = self.args.as_deref().unwrap_or_default();
+            if ConfigValidator::is_blocking_command(&self.command, args) {
+                warnings.push(ValidationWarning::MissingTimeout {
+                    command: self.command.clone(),
+                });
+            }
+            let has_ready_trigger = self
+                .output_triggers
+                .iter()
+                .flatten()
+                .any(|trigger| trigger.action == TriggerAction::Ready);
+            if self.ready_indicator.is_some()
+                || self.ready_indicator_regex.is_some()
+                || has_ready_trigger
+            {
+                warnings.push(ValidationWarning::ReadyIndicatorWithoutTimeout);
+            }
+        }
+        (self.validate(), warnings)
+    }
+
+    /// Warnings for inherited environment variables matching a risky pattern, sorted by name
+    fn inherited_risky_env(&self) -> Vec<ValidationWarning> {
+        let same_name = |name: &str, key: &str| {
+            if cfg!(windows) {
+                name.eq_ignore_ascii_case(key)
+            } else {
+                name == key
+            }
+        };
+        let overridden =
+            |key: &str| self.env.iter().flatten().any(|(name, _)| same_name(name, key));
+        let inherited = |key: &str| {
+            !self.clear_env.unwrap_or_default()
+                || self
+                    .inherit_env_vars
+                    .iter()
+                    .flatten()
+                    .any(|name| same_name(name, key))
+        };
+        let patterns: Vec<&str> = match &self.risky_env_patterns {
+            Some(patterns) => patterns.iter().map(String::as_str).collect(),
+            None => DEFAULT_RISKY_ENV_PATTERNS.to_vec(),
+        };
+        let mut keys: Vec<String> = std::env::vars_os()
+            .map(|(key, _)| key.to_string_lossy().into_owned())
+            .filter(|key| inherited(key) && !overridden(key))
+            .collect();
+        keys.sort();
+        keys.into_iter()
+            .filter_map(|key| {
+                let pattern = patterns
+                    .iter()
+                    .find(|pattern| ConfigValidator::matches_env_pattern(&key, pattern))?;
+                Some(ValidationWarning::InheritedRiskyEnv {
+                    key,
+                    pattern: (*pattern).to_string(),
+                })
+            })
+            .collect()
+    }
+
     /// Check if process group management is enabled
     ///
     /// Returns true if process group management should be used, false otherwise.
@@ -396,6 +2356,68 @@ impl TaskConfig {
     pub fn is_process_group_enabled(&self) -> bool {
         self.use_process_group.unwrap_or(true)
     }
+
+    /// Check if output of the given stream is captured
+    ///
+    /// Defaults to true if not explicitly set, and is false for every stream
+    /// when `stdio_inherit` is enabled.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::{StreamSource, TaskConfig};
+    ///
+    /// let config = TaskConfig::new("cmd").capture_stderr(false);
+    /// assert!(config.is_stream_captured(&StreamSource::Stdout));
+    /// assert!(!config.is_stream_captured(&StreamSource::Stderr));
+    /// ```
+    pub fn is_stream_captured(&self, src: &StreamSource) -> bool {
+        if self.stdio_inherit.unwrap_or_default() {
+            return false;
+        }
+        match src {
+            StreamSource::Stdout => self.capture_stdout,
+            StreamSource::Stderr => self.capture_stderr,
+        }
+        .unwrap_or(true)
+    }
+
+    /// Returns the configured working directory, choosing among the candidates if set
+    ///
+    /// That is `working_dir` if set, else the first of
+    /// [`working_dir_candidates`](Self::working_dir_candidates) that is a
+    /// valid working directory, else `None` for the current directory. The
+    /// candidates are checked on every call.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TaskError::InvalidConfiguration`] if candidates are set but
+    /// none of them is a valid working directory.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::TaskConfig;
+    ///
+    /// let config = TaskConfig::new("ls").working_dir_candidates(["/no/such/dir"]);
+    /// assert!(config.resolve_working_dir().is_err());
+    /// ```
+    pub fn resolve_working_dir(&self) -> Result<Option<&str>, TaskError> {
+        if let Some(dir) = &self.working_dir {
+            return Ok(Some(dir));
+        }
+        let Some(candidates) = &self.working_dir_candidates else {
+            return Ok(None);
+        };
+        candidates
+            .iter()
+            .find(|dir| ConfigValidator::validate_working_dir(dir).is_ok())
+            .map(|dir| Some(dir.as_str()))
+            .ok_or_else(|| {
+                TaskError::InvalidConfiguration(format!(
+                    "None of the working directory candidates is a directory: {}",
+                    candidates.join(", ")
+                ))
+            })
+    }
 }
 
 /// Specifies the source stream for output monitoring
@@ -420,15 +2442,425 @@ impl TaskConfig {
 /// ```
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub enum StreamSource {
     /// Standard output stream
+    #[default]
     Stdout = 0,
     /// Standard error stream  
     Stderr = 1,
 }
-impl Default for StreamSource {
-    fn default() -> Self {
-        Self::Stdout
+
+/// Text encoding of process output
+///
+/// Output that is not valid in the chosen encoding is handled per line: a line
+/// that is not valid UTF-8 ends the stream, while invalid UTF-16 (a lone
+/// surrogate or a trailing odd byte) is replaced with U+FFFD.
+///
+/// # Examples
+///
+/// ```rust
+/// use tcrm_task::tasks::config::{OutputEncoding, TaskConfig};
+///
+/// let config = TaskConfig::new("legacy-tool").output_encoding(OutputEncoding::Auto);
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum OutputEncoding {
+    /// UTF-8, passed through unchanged
+    #[default]
+    Utf8,
+    /// UTF-16 little endian; a leading byte order mark is skipped
+    Utf16Le,
+    /// UTF-16LE if the stream starts with its byte order mark, otherwise UTF-8
+    ///
+    /// A UTF-8 byte order mark is skipped.
+    Auto,
+}
+
+/// How the output of a task is sent
+///
+/// See [`TaskConfig::output_mode`].
+///
+/// # Examples
+///
+/// ```rust
+/// use tcrm_task::tasks::config::{OutputMode, TaskConfig};
+///
+/// let config = TaskConfig::new("cat").args(["image.png"]).output_mode(OutputMode::Bytes);
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum OutputMode {
+    /// Decoded lines, sent as `TaskEvent::Output`
+    #[default]
+    Lines,
+    /// Raw byte chunks, sent as `TaskEvent::OutputChunk`
+    Bytes,
+}
+
+/// Scheduling priority of a task
+///
+/// See [`TaskConfig::priority`].
+///
+/// # Examples
+///
+/// ```rust
+/// use tcrm_task::tasks::config::TaskPriority;
+///
+/// assert_eq!(TaskPriority::Low.nice_value(), 10);
+/// assert_eq!(TaskPriority::High.nice_value(), -5);
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TaskPriority {
+    /// Background work (Windows: `IDLE_PRIORITY_CLASS`)
+    Low,
+    /// Below interactive work (Windows: `BELOW_NORMAL_PRIORITY_CLASS`)
+    BelowNormal,
+    /// The default priority of processes (Windows: `NORMAL_PRIORITY_CLASS`)
+    #[default]
+    Normal,
+    /// Above normal processes, usually requiring privileges on Unix
+    /// (Windows: `ABOVE_NORMAL_PRIORITY_CLASS`)
+    High,
+}
+
+impl TaskPriority {
+    /// Unix nice value of this priority, from 10 for `Low` to -5 for `High`
+    #[must_use]
+    pub fn nice_value(self) -> i32 {
+        match self {
+            TaskPriority::Low => 10,
+            TaskPriority::BelowNormal => 5,
+            TaskPriority::Normal => 0,
+            TaskPriority::High => -5,
+        }
+    }
+}
+
+/// Settings for collapsing runs of identical output lines
+///
+/// See [`TaskConfig::dedupe_output`].
+///
+/// # Examples
+///
+/// ```rust
+/// use tcrm_task::tasks::config::{OutputDedupe, TaskConfig};
+///
+/// let config = TaskConfig::new("flaky-service").dedupe_output(1000, 3);
+/// assert_eq!(
+///     config.dedupe_output,
+///     Some(OutputDedupe { window_ms: 1000, max_repeats: 3 })
+/// );
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputDedupe {
+    /// Longest time collapsed repeats are held before a summary is sent, in milliseconds
+    pub window_ms: u64,
+    /// Number of identical consecutive lines sent before repeats are collapsed
+    pub max_repeats: u32,
+}
+
+/// Settings for running a task in a temporary working directory
+///
+/// See [`TaskConfig::temp_working_dir`].
+///
+/// # Examples
+///
+/// ```rust
+/// use tcrm_task::tasks::config::{TaskConfig, TempWorkingDir};
+///
+/// let config = TaskConfig::new("make").temp_working_dir_prefix("build-");
+/// assert_eq!(
+///     config.temp_working_dir,
+///     Some(TempWorkingDir { prefix: Some("build-".to_string()), keep_on_failure: false })
+/// );
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TempWorkingDir {
+    /// Start of the directory name (default: `tcrm-`)
+    pub prefix: Option<String>,
+    /// Keep the directory when the task fails
+    pub keep_on_failure: bool,
+}
+
+/// Settings for retrying a spawn that failed with a transient error
+///
+/// See [`TaskConfig::spawn_retry`].
+///
+/// # Examples
+///
+/// ```rust
+/// use std::io::ErrorKind;
+///
+/// use tcrm_task::tasks::config::{SpawnRetry, TaskConfig};
+///
+/// let config = TaskConfig::new("my-tool.exe").spawn_retry(3, 100, [ErrorKind::PermissionDenied]);
+/// assert_eq!(
+///     config.spawn_retry,
+///     Some(SpawnRetry { attempts: 3, backoff_ms: 100, retry_on: vec![ErrorKind::PermissionDenied] })
+/// );
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpawnRetry {
+    /// Most spawn attempts, including the first
+    pub attempts: u32,
+    /// Delay between attempts, in milliseconds
+    pub backoff_ms: u64,
+    /// Error kinds that are retried; other errors fail the spawn at once
+    ///
+    /// Serialized by name, e.g. `"PermissionDenied"`.
+    #[cfg_attr(feature = "serde", serde(with = "error_kind_names"))]
+    pub retry_on: Vec<std::io::ErrorKind>,
+}
+
+/// Serializes `io::ErrorKind`s by name, as they do not implement serde traits
+#[cfg(feature = "serde")]
+mod error_kind_names {
+    use std::io::ErrorKind;
+
+    use serde::{Deserialize, Deserializer, Serializer, de, ser};
+
+    /// Error kinds with a name, the ones a spawn may reasonably fail with
+    const NAMED: &[(ErrorKind, &str)] = &[
+        (ErrorKind::NotFound, "NotFound"),
+        (ErrorKind::PermissionDenied, "PermissionDenied"),
+        (ErrorKind::AlreadyExists, "AlreadyExists"),
+        (ErrorKind::WouldBlock, "WouldBlock"),
+        (ErrorKind::NotADirectory, "NotADirectory"),
+        (ErrorKind::IsADirectory, "IsADirectory"),
+        (ErrorKind::InvalidInput, "InvalidInput"),
+        (ErrorKind::InvalidData, "InvalidData"),
+        (ErrorKind::TimedOut, "TimedOut"),
+        (ErrorKind::ResourceBusy, "ResourceBusy"),
+        (ErrorKind::ExecutableFileBusy, "ExecutableFileBusy"),
+        (ErrorKind::ArgumentListTooLong, "ArgumentListTooLong"),
+        (ErrorKind::Interrupted, "Interrupted"),
+        (ErrorKind::Unsupported, "Unsupported"),
+        (ErrorKind::OutOfMemory, "OutOfMemory"),
+        (ErrorKind::Other, "Other"),
+    ];
+
+    pub(super) fn serialize<S: Serializer>(
+        kinds: &[ErrorKind],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let names = kinds
+            .iter()
+            .map(|kind| {
+                NAMED
+                    .iter()
+                    .find(|(named, _)| named == kind)
+                    .map(|(_, name)| *name)
+                    .ok_or_else(|| ser::Error::custom(format!("unsupported error kind {kind:?}")))
+            })
+            .collect::<Result<Vec<_>, S::Error>>()?;
+        serializer.collect_seq(names)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<ErrorKind>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|name| {
+                NAMED
+                    .iter()
+                    .find(|(_, named)| named == name)
+                    .map(|(kind, _)| *kind)
+                    .ok_or_else(|| de::Error::custom(format!("unsupported error kind {name:?}")))
+            })
+            .collect()
+    }
+}
+
+/// An action taken when an output line contains a pattern
+///
+/// See [`TaskConfig::output_trigger`].
+///
+/// # Examples
+///
+/// ```rust
+/// use tcrm_task::tasks::config::{OutputTrigger, StreamSource, TriggerAction};
+///
+/// let trigger = OutputTrigger::new("panicked at", StreamSource::Stderr, TriggerAction::FailTask);
+/// assert_eq!(trigger.pattern, "panicked at");
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputTrigger {
+    /// Text searched for in each line
+    pub pattern: String,
+    /// Stream the trigger watches
+    pub source: StreamSource,
+    /// What to do when a line contains `pattern`
+    pub action: TriggerAction,
+}
+
+impl OutputTrigger {
+    /// Create a trigger taking `action` when a line of `source` contains `pattern`
+    pub fn new(pattern: impl Into<String>, source: StreamSource, action: TriggerAction) -> Self {
+        OutputTrigger {
+            pattern: pattern.into(),
+            source,
+            action,
+        }
+    }
+}
+
+/// Action of an [`OutputTrigger`]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum TriggerAction {
+    /// Mark the task ready, like `ready_indicator`
+    Ready,
+    /// Terminate the task, which stops with `TaskEventStopReason::Error`
+    ///
+    /// The error names the pattern and stream, not the matching line.
+    FailTask,
+    /// Send `TaskEvent::Triggered` with this name for every matching line
+    EmitEvent(String),
+    /// Terminate the task as done, which stops with `TaskTerminateReason::CompletionIndicator`
+    ///
+    /// See [`TaskConfig::exit_on_output_match`].
+    Complete,
+}
+
+/// How the ready indicator and output triggers match an output line
+///
+/// See [`TaskConfig::ready_match_mode`]. Lines are compared without their line
+/// ending, after the output transform if it runs first.
+///
+/// # Examples
+///
+/// ```rust
+/// use tcrm_task::tasks::config::ReadyMatchMode;
+///
+/// let line = "Listening on 0.0.0.0:8080";
+/// assert!(ReadyMatchMode::Contains.matches(line, "0.0.0.0"));
+/// assert!(!ReadyMatchMode::StartsWith.matches(line, "0.0.0.0"));
+/// assert!(ReadyMatchMode::StartsWith.matches(line, "Listening"));
+/// assert!(!ReadyMatchMode::ExactLine.matches(line, "Listening"));
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReadyMatchMode {
+    /// The line contains the pattern anywhere
+    #[default]
+    Contains,
+    /// The line starts with the pattern
+    StartsWith,
+    /// The line is the pattern
+    ExactLine,
+}
+
+impl ReadyMatchMode {
+    /// Whether `line` matches `pattern` in this mode
+    pub fn matches(self, line: &str, pattern: &str) -> bool {
+        match self {
+            ReadyMatchMode::Contains => line.contains(pattern),
+            ReadyMatchMode::StartsWith => line.starts_with(pattern),
+            ReadyMatchMode::ExactLine => line == pattern,
+        }
+    }
+}
+
+/// A compiled `ready_indicator_regex`, matched like a `Ready` output trigger
+#[cfg(feature = "tokio")]
+#[derive(Debug)]
+pub(crate) struct ReadyRegex {
+    #[cfg(feature = "regex")]
+    regex: regex::Regex,
+    /// The `Ready` trigger a matching line fires
+    trigger: OutputTrigger,
+}
+
+#[cfg(feature = "tokio")]
+impl ReadyRegex {
+    /// The `Ready` trigger if `line` matches
+    pub(crate) fn find(&self, line: &str) -> Option<&OutputTrigger> {
+        #[cfg(feature = "regex")]
+        let matched = self.regex.is_match(line);
+        #[cfg(not(feature = "regex"))]
+        let matched = {
+            let _ = line;
+            false
+        };
+        matched.then_some(&self.trigger)
+    }
+}
+
+/// Inputs written to stdin as the output reaches each prompt
+///
+/// See [`TaskConfig::stdin_script`].
+///
+/// # Examples
+///
+/// ```rust
+/// use tcrm_task::tasks::config::{StdinScript, StdinScriptStep};
+///
+/// let script = StdinScript::new([
+///     StdinScriptStep::new("Username:", "admin", 5_000),
+///     StdinScriptStep::new("Continue? [y/N]", "y", 1_000),
+/// ]);
+/// assert_eq!(script.steps[1].then_send, "y");
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct StdinScript {
+    /// Steps run in order
+    pub steps: Vec<StdinScriptStep>,
+}
+
+impl StdinScript {
+    /// Create a script running `steps` in order
+    pub fn new(steps: impl IntoIterator<Item = StdinScriptStep>) -> Self {
+        StdinScript {
+            steps: steps.into_iter().collect(),
+        }
+    }
+}
+
+/// A step of a [`StdinScript`]: wait for a line, then answer it
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct StdinScriptStep {
+    /// Text the awaited line matches, per `TaskConfig::ready_match_mode`
+    pub wait_for: String,
+    /// Stream the awaited line is read from
+    pub source: StreamSource,
+    /// Line written to stdin once the awaited line was read
+    ///
+    /// A trailing newline is added if missing.
+    pub then_send: String,
+    /// How long the awaited line may take, in milliseconds
+    pub timeout_ms: u64,
+}
+
+impl StdinScriptStep {
+    /// Create a step sending `then_send` once a stdout line matches `wait_for`
+    pub fn new(wait_for: impl Into<String>, then_send: impl Into<String>, timeout_ms: u64) -> Self {
+        StdinScriptStep {
+            wait_for: wait_for.into(),
+            source: StreamSource::Stdout,
+            then_send: then_send.into(),
+            timeout_ms,
+        }
+    }
+
+    /// Wait for a line of `source` instead of stdout
+    #[must_use]
+    pub fn source(mut self, source: StreamSource) -> Self {
+        self.source = source;
+        self
     }
 }
@@ -0,0 +1,229 @@
+//! Per-invocation overrides of a base task configuration.
+//!
+//! A service typically keeps one [`TaskConfig`] with the environment, working
+//! directory, timeout and process group settings of a task, and only changes
+//! a few fields per run. [`TaskConfig::merged_with`] applies a
+//! [`TaskConfigOverrides`] to such a base, so every run starts from the same
+//! template instead of a clone that may still carry fields set for an earlier
+//! run.
+
+use std::collections::HashMap;
+
+use crate::tasks::config::TaskConfig;
+
+/// How a list of the overrides is combined with the one of the base configuration
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// The overrides replace the base list (default)
+    #[default]
+    Replace,
+    /// The overrides are added to the base list
+    ///
+    /// Arguments are appended after the base ones; environment variables are
+    /// added, replacing base variables of the same name.
+    Append,
+}
+
+/// Fields to change in a base [`TaskConfig`], see [`TaskConfig::merged_with`]
+///
+/// Fields left `None` keep the value of the base configuration. With the
+/// `serde` feature, missing fields deserialize to their default, so an
+/// override only needs the fields it changes.
+///
+/// # Examples
+/// ```rust
+/// use tcrm_task::tasks::config::{
+///     TaskConfig,
+///     overrides::{MergeStrategy, TaskConfigOverrides},
+/// };
+///
+/// let base = TaskConfig::new("cargo")
+///     .args(["build"])
+///     .env([("RUST_LOG", "info")])
+///     .timeout_ms(60_000);
+///
+/// let overrides = TaskConfigOverrides::new()
+///     .args(["--release"])
+///     .args_strategy(MergeStrategy::Append)
+///     .env([("RUST_LOG", "debug")]);
+/// let config = base.merged_with(overrides);
+///
+/// assert_eq!(config.args, Some(vec!["build".to_string(), "--release".to_string()]));
+/// assert_eq!(config.env.unwrap()["RUST_LOG"], "debug");
+/// assert_eq!(config.timeout_ms, Some(60_000));
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskConfigOverrides {
+    /// The command or executable to run
+    pub command: Option<String>,
+    /// Arguments, combined with the base ones per `args_strategy`
+    pub args: Option<Vec<String>>,
+    /// How `args` is combined with the base arguments
+    pub args_strategy: MergeStrategy,
+    /// Working directory for the command
+    pub working_dir: Option<String>,
+    /// Environment variables, combined with the base ones per `env_strategy`
+    pub env: Option<HashMap<String, String>>,
+    /// How `env` is combined with the base environment variables
+    pub env_strategy: MergeStrategy,
+    /// Maximum allowed runtime in milliseconds
+    pub timeout_ms: Option<u64>,
+    /// Allow providing input to the task via stdin
+    pub enable_stdin: Option<bool>,
+    /// String indicating the task is ready
+    pub ready_indicator: Option<String>,
+    /// Enable process group management
+    pub use_process_group: Option<bool>,
+}
+
+impl TaskConfigOverrides {
+    /// Create overrides that change nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override the command
+    #[must_use]
+    pub fn command(mut self, command: impl Into<String>) -> Self {
+        self.command = Some(command.into());
+        self
+    }
+
+    /// Override the arguments, or add to them with [`MergeStrategy::Append`]
+    #[must_use]
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args = Some(args.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Set how the arguments are combined with the base ones
+    #[must_use]
+    pub fn args_strategy(mut self, strategy: MergeStrategy) -> Self {
+        self.args_strategy = strategy;
+        self
+    }
+
+    /// Override the working directory
+    #[must_use]
+    pub fn working_dir(mut self, dir: impl Into<String>) -> Self {
+        self.working_dir = Some(dir.into());
+        self
+    }
+
+    /// Override the environment variables, or add to them with [`MergeStrategy::Append`]
+    #[must_use]
+    pub fn env<K, V, I>(mut self, env: I) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+        I: IntoIterator<Item = (K, V)>,
+    {
+        self.env = Some(env.into_iter().map(|(k, v)| (k.into(), v.into())).collect());
+        self
+    }
+
+    /// Set how the environment variables are combined with the base ones
+    #[must_use]
+    pub fn env_strategy(mut self, strategy: MergeStrategy) -> Self {
+        self.env_strategy = strategy;
+        self
+    }
+
+    /// Override the timeout in milliseconds
+    #[must_use]
+    pub fn timeout_ms(mut self, timeout: u64) -> Self {
+        self.timeout_ms = Some(timeout);
+        self
+    }
+
+    /// Override whether stdin is enabled
+    #[must_use]
+    pub fn enable_stdin(mut self, enabled: bool) -> Self {
+        self.enable_stdin = Some(enabled);
+        self
+    }
+
+    /// Override the ready indicator
+    #[must_use]
+    pub fn ready_indicator(mut self, indicator: impl Into<String>) -> Self {
+        self.ready_indicator = Some(indicator.into());
+        self
+    }
+
+    /// Override whether process group management is enabled
+    #[must_use]
+    pub fn use_process_group(mut self, enabled: bool) -> Self {
+        self.use_process_group = Some(enabled);
+        self
+    }
+}
+
+impl TaskConfig {
+    /// Returns a copy of this configuration with `overrides` applied
+    ///
+    /// Each field set in `overrides` replaces the one of this configuration,
+    /// except arguments and environment variables, which are combined per
+    /// their [`MergeStrategy`]. This configuration is left unchanged, so it
+    /// can serve as a template for every run. The result is not validated;
+    /// it is when the task starts, or with [`validate`](Self::validate).
+    ///
+    /// # Arguments
+    ///
+    /// * `overrides` - The fields to change
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::config::{TaskConfig, overrides::TaskConfigOverrides};
+    ///
+    /// let base = TaskConfig::new("pytest").args(["-q"]).timeout_ms(30_000);
+    /// let config = base.merged_with(TaskConfigOverrides::new().args(["tests/unit"]));
+    ///
+    /// assert_eq!(config.args, Some(vec!["tests/unit".to_string()]));
+    /// assert_eq!(base.args, Some(vec!["-q".to_string()]));
+    /// ```
+    #[must_use]
+    pub fn merged_with(&self, overrides: TaskConfigOverrides) -> TaskConfig {
+        let TaskConfigOverrides {
+            command,
+            args,
+            args_strategy,
+            working_dir,
+            env,
+            env_strategy,
+            timeout_ms,
+            enable_stdin,
+            ready_indicator,
+            use_process_group,
+        } = overrides;
+        let mut config = self.clone();
+
+        if let Some(command) = command {
+            config.command = command;
+        }
+        if let Some(args) = args {
+            match (args_strategy, &mut config.args) {
+                (MergeStrategy::Append, Some(base)) => base.extend(args),
+                _ => config.args = Some(args),
+            }
+        }
+        if let Some(env) = env {
+            match (env_strategy, &mut config.env) {
+                (MergeStrategy::Append, Some(base)) => base.extend(env),
+                _ => config.env = Some(env),
+            }
+        }
+        config.working_dir = working_dir.or(config.working_dir);
+        config.timeout_ms = timeout_ms.or(config.timeout_ms);
+        config.enable_stdin = enable_stdin.or(config.enable_stdin);
+        config.ready_indicator = ready_indicator.or(config.ready_indicator);
+        config.use_process_group = use_process_group.or(config.use_process_group);
+        config
+    }
+}
@@ -39,11 +39,14 @@ use thiserror::Error;
 ///                 TaskError::IO(msg) => {
 ///                     eprintln!("Task '{}' IO error: {}", task_name, msg);
 ///                 }
+///                 TaskError::CommandNotFound { command, .. } => {
+///                     eprintln!("Task '{}': is {} installed?", task_name, command);
+///                 }
 ///                 TaskError::InvalidConfiguration(msg) => {
 ///                     eprintln!("Task '{}' config error: {}", task_name, msg);
 ///                 }
-///                 TaskError::Channel(msg) => {
-///                     eprintln!("Task '{}' channel error: {}", task_name, msg);
+///                 TaskError::Channel { kind, detail, .. } => {
+///                     eprintln!("Task '{}' {:?} channel error: {}", task_name, kind, detail);
 ///                 }
 ///                 _ => {
 ///                     eprintln!("Task '{}' error: {}", task_name, error);
@@ -63,7 +66,6 @@ pub enum TaskError {
     /// and other system-level IO errors.
     ///
     /// # Common Causes
-    /// - Command not found in PATH
     /// - Permission denied when spawning process
     /// - Working directory doesn't exist or isn't accessible
     /// - File descriptor or pipe creation failures
@@ -77,6 +79,7 @@ pub enum TaskError {
     /// # Common Causes
     /// - Failed to obtain process ID after spawning
     /// - Watcher task join or abort errors
+    /// - Failed to set the priority of the process, see `TaskConfig::priority`
     #[error("Handle error: {0}")]
     Handle(String),
 
@@ -90,8 +93,15 @@ pub enum TaskError {
     /// - Stdin channel disconnected
     /// - Termination signal channel closed
     /// - Receiver dropped before sender finished
-    #[error("Channel error: {0}")]
-    Channel(String),
+    #[error("Channel error: {detail}")]
+    Channel {
+        /// Which channel failed
+        kind: ChannelKind,
+        /// What was being done with it
+        op: ChannelOp,
+        /// Description of the failure
+        detail: String,
+    },
 
     /// Task configuration validation failed
     ///
@@ -107,4 +117,153 @@ pub enum TaskError {
     /// - Security validation failures (command injection, etc.)
     #[error("Invalid configuration: {0}")]
     InvalidConfiguration(String),
+
+    /// The command exists but could not be executed
+    ///
+    /// Returned instead of [`TaskError::IO`] when a spawn failure could be
+    /// traced to the file the command resolves to. Commands that cannot be
+    /// found at all are reported as [`TaskError::CommandNotFound`].
+    ///
+    /// # Common Causes
+    /// - The file lacks execute permission
+    /// - The command names a directory
+    /// - A script's interpreter does not exist
+    /// - The executable was built for another CPU architecture
+    #[error("Spawn error: {message}: {diagnosis}")]
+    Spawn {
+        /// The OS error reported when spawning
+        message: String,
+        /// Why the command could not be executed
+        diagnosis: SpawnDiagnosis,
+    },
+
+    /// The command could not be found
+    ///
+    /// Returned instead of [`TaskError::IO`] when spawning fails because no
+    /// file the command resolves to exists.
+    ///
+    /// # Common Causes
+    /// - The program is not installed
+    /// - A typo in the command name
+    /// - The directory holding the program is missing from `PATH`
+    /// - A relative command path that does not exist in the working directory
+    #[error("Command not found: {command}")]
+    CommandNotFound {
+        /// The configured command
+        command: String,
+        /// The `PATH` the command was looked up in
+        ///
+        /// `None` if the command is a path, which is not looked up in `PATH`,
+        /// or if no `PATH` is set.
+        searched_path: Option<String>,
+    },
+
+    /// A step run in the child process before executing the command failed (Unix only)
+    ///
+    /// Returned instead of [`TaskError::IO`] when spawning fails because of a
+    /// `pre_exec` step, such as `setsid` for the process group, the
+    /// `parent_death_signal` of `kill_child_on_parent_death`, the
+    /// `cpu_affinity` step of `TaskConfig::cpu_affinity`, or a step added with
+    /// `TaskSpawner::add_pre_exec_step`. A failed `priority` step is reported
+    /// as [`TaskError::Handle`] instead.
+    #[error("pre_exec step '{step}' failed: {error}")]
+    PreExec {
+        /// Name of the step that failed
+        step: String,
+        /// Name of the OS error the step failed with, such as `EPERM`
+        error: String,
+    },
+}
+
+/// Why a command that exists could not be executed
+///
+/// Attached to [`TaskError::Spawn`] so frontends can tell the user what to fix
+/// instead of showing a bare OS error such as "Permission denied".
+///
+/// # Examples
+/// ```rust
+/// use tcrm_task::tasks::error::{SpawnDiagnosis, TaskError};
+///
+/// fn hint(error: &TaskError) -> Option<String> {
+///     match error {
+///         TaskError::Spawn { diagnosis: SpawnDiagnosis::NotExecutable { path }, .. } => {
+///             Some(format!("Run `chmod +x {path}`"))
+///         }
+///         TaskError::Spawn { diagnosis, .. } => Some(diagnosis.to_string()),
+///         _ => None,
+///     }
+/// }
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SpawnDiagnosis {
+    /// The command resolves to a directory
+    #[error("{path} is a directory")]
+    IsDirectory {
+        /// Resolved path of the command
+        path: String,
+    },
+
+    /// The file cannot be executed
+    ///
+    /// On Unix the file lacks execute permission. On Windows it is neither a
+    /// PE executable nor a batch script.
+    #[error("{path} is not executable")]
+    NotExecutable {
+        /// Resolved path of the command
+        path: String,
+    },
+
+    /// The interpreter named on the script's `#!` line does not exist
+    #[error("interpreter {interpreter} of script {path} does not exist")]
+    MissingInterpreter {
+        /// Resolved path of the script
+        path: String,
+        /// Interpreter named on the `#!` line
+        interpreter: String,
+    },
+
+    /// The executable was built for a different CPU architecture
+    #[error("{path} is built for {architecture}, not {}", std::env::consts::ARCH)]
+    WrongArchitecture {
+        /// Resolved path of the executable
+        path: String,
+        /// Architecture named in the ELF or PE header
+        architecture: String,
+    },
+}
+
+/// Channel named by a [`TaskError::Channel`]
+///
+/// # Examples
+/// ```rust
+/// use tcrm_task::tasks::error::{ChannelKind, TaskError};
+///
+/// fn already_stopping(error: &TaskError) -> bool {
+///     matches!(error, TaskError::Channel { kind: ChannelKind::Terminate, .. })
+/// }
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelKind {
+    /// The channel delivering `TaskEvent`s to the caller
+    Event,
+    /// The channel carrying termination requests to the running task
+    Terminate,
+    /// The channel carrying lines to the process stdin
+    Stdin,
+    /// A channel between the watchers of a task
+    Internal,
+}
+
+/// Operation that failed on the channel of a [`TaskError::Channel`]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelOp {
+    /// Sending a value failed
+    Send,
+    /// Receiving a value failed
+    Recv,
+    /// The channel was already closed or taken
+    Closed,
 }
@@ -1,5 +1,9 @@
+use std::{sync::Arc, time::SystemTime};
+
 use crate::tasks::{config::StreamSource, error::TaskError};
 
+pub mod format;
+
 /// Events emitted during task execution lifecycle
 ///
 /// `TaskEvent` represents all events that occur during task execution,
@@ -12,19 +16,25 @@ use crate::tasks::{config::StreamSource, error::TaskError};
 /// 1. `Started` - Process has been spawned
 /// 2. `Output` - Output lines from stdout/stderr (ongoing)
 /// 3. `Ready` - Ready indicator detected (optional, for long-running processes)
-/// 4. `Stopped` - Process has completed, with exit code and reason
-/// 5. `Error` - Error related to task execution
+/// 4. `RunningLong` - Expected duration exceeded (optional, at most once)
+/// 5. `Stopped` - Process has completed, with exit code and reason
+/// 6. `Error` - Error related to task execution
 ///
 /// # Examples
 ///
 /// ## Basic Event Processing
-/// ```rust
+#[cfg_attr(feature = "tokio", doc = "```rust")]
+#[cfg_attr(not(feature = "tokio"), doc = "```ignore")]
 /// use tcrm_task::tasks::{config::TaskConfig, async_tokio::spawner::TaskSpawner, event::TaskEvent};
 /// use tokio::sync::mpsc;
 ///
 /// #[tokio::main]
 /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
-///     let config = TaskConfig::new("cmd").args(["/C", "echo", "hello", "world"]);
+///     let config = if cfg!(windows) {
+///         TaskConfig::new("cmd").args(["/C", "echo", "hello", "world"])
+///     } else {
+///         TaskConfig::new("echo").args(["hello", "world"])
+///     };
 ///     let mut spawner = TaskSpawner::new("demo".to_string(), config);
 ///     
 ///     let (tx, mut rx) = mpsc::channel(100);
@@ -32,13 +42,13 @@ use crate::tasks::{config::StreamSource, error::TaskError};
 ///
 ///     while let Some(event) = rx.recv().await {
 ///         match event {
-///             TaskEvent::Started { task_name } => {
-///                 println!("Task '{}' started", task_name);
+///             TaskEvent::Started { task_name, process_id, .. } => {
+///                 println!("Task '{}' started with pid {}", task_name, process_id);
 ///             }
-///             TaskEvent::Output { task_name, line, src } => {
+///             TaskEvent::Output { task_name, line, src, .. } => {
 ///                 println!("Task '{}' output: {}", task_name, line);
 ///             }
-///             TaskEvent::Stopped { task_name, exit_code, reason } => {
+///             TaskEvent::Stopped { task_name, exit_code, reason, .. } => {
 ///                 println!("Task '{}' stopped with code {:?}", task_name, exit_code);
 ///                 break;
 ///             }
@@ -55,7 +65,8 @@ use crate::tasks::{config::StreamSource, error::TaskError};
 /// ```
 ///
 /// ## Server Ready Detection
-/// ```rust
+#[cfg_attr(feature = "tokio", doc = "```rust")]
+#[cfg_attr(not(feature = "tokio"), doc = "```ignore")]
 /// use tcrm_task::tasks::{
 ///     config::{TaskConfig, StreamSource},
 ///     async_tokio::spawner::TaskSpawner,
@@ -65,8 +76,12 @@ use crate::tasks::{config::StreamSource, error::TaskError};
 ///
 /// #[tokio::main]
 /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
-///     let config = TaskConfig::new("cmd")
-///         .args(["/C", "echo", "Server listening"])
+///     let command = if cfg!(windows) {
+///         TaskConfig::new("cmd").args(["/C", "echo", "Server listening"])
+///     } else {
+///         TaskConfig::new("echo").args(["Server listening"])
+///     };
+///     let config = command
 ///         .ready_indicator("Server listening")
 ///         .ready_indicator_source(StreamSource::Stdout);
 ///
@@ -100,7 +115,13 @@ pub enum TaskEvent {
     /// The process is now running and other events will follow.
     Started {
         /// Name of the task that started
-        task_name: String,
+        task_name: Arc<str>,
+        /// Process ID of the spawned process, the same value `start_direct` returns
+        process_id: u32,
+        /// Temporary working directory created for this run, see `TaskConfig::temp_working_dir`
+        temp_dir: Option<String>,
+        /// Working directory chosen for this run, see `TaskConfig::working_dir_candidates`
+        working_dir: Option<String>,
     },
 
     /// Output line received from the process
@@ -109,11 +130,113 @@ pub enum TaskEvent {
     /// Lines are buffered and emitted when complete (on newline).
     Output {
         /// Name of the task that produced the output
-        task_name: String,
+        task_name: Arc<str>,
         /// The output line (without trailing newline)
         line: String,
         /// Source stream (stdout or stderr)
         src: StreamSource,
+        /// Position of the line in the output of the task, across both streams
+        ///
+        /// Numbered from 0 in the order lines are read. The stdout and stderr
+        /// watchers send their events independently, so sorting by `seq`
+        /// restores the read order; see `TaskConfig::preserve_interleaving`.
+        /// Lines that were not sent (dropped by the output transform or
+        /// collapsed as repeats) leave gaps.
+        seq: u64,
+        /// Whether the line was longer than `TaskConfig::max_output_line_bytes`
+        /// and only its start is sent
+        truncated: bool,
+        /// When the line was read from the process, not when it was received
+        ///
+        /// Never earlier than the timestamp of the previous line of the same
+        /// stream, even if the system clock steps back.
+        timestamp: SystemTime,
+    },
+
+    /// Output line received from the process that was parsed as JSON
+    ///
+    /// Emitted instead of [`TaskEvent::Output`] when `parse_json_lines` is
+    /// enabled and the line holds a JSON object or array. The variant exists
+    /// without the `serde` feature, so matching on it does not depend on
+    /// features enabled elsewhere; only the parsed `value` needs `serde`.
+    OutputJson {
+        /// Name of the task that produced the output
+        task_name: Arc<str>,
+        /// The line as read (without trailing newline)
+        line: String,
+        /// The parsed line
+        #[cfg(feature = "serde")]
+        value: serde_json::Value,
+        /// Source stream (stdout or stderr)
+        src: StreamSource,
+        /// Position of the line in the output of the task, as in [`TaskEvent::Output`]
+        seq: u64,
+        /// When the line was read from the process, as in [`TaskEvent::Output`]
+        timestamp: SystemTime,
+    },
+
+    /// Raw bytes read from the process
+    ///
+    /// Emitted instead of [`TaskEvent::Output`] when `output_mode` is
+    /// [`OutputMode::Bytes`](crate::tasks::config::OutputMode::Bytes). Chunks
+    /// hold the bytes as the process wrote them, not decoded and not split on
+    /// lines; a chunk holds at most `output_buffer_bytes` bytes.
+    OutputChunk {
+        /// Name of the task that produced the output
+        task_name: Arc<str>,
+        /// The bytes read, never empty
+        data: Vec<u8>,
+        /// Source stream (stdout or stderr)
+        src: StreamSource,
+    },
+
+    /// Repeats of an output line that were collapsed
+    ///
+    /// Emitted when `dedupe_output` is configured and more than `max_repeats`
+    /// consecutive identical lines were read from a stream. Summarizes the
+    /// repeats that were not sent as [`TaskEvent::Output`], once a different
+    /// line arrives, the stream ends, or the dedupe window has passed.
+    OutputRepeated {
+        /// Name of the task that produced the output
+        task_name: Arc<str>,
+        /// The repeated line (without trailing newline)
+        line: String,
+        /// Source stream (stdout or stderr)
+        src: StreamSource,
+        /// Number of repeats that were not sent
+        count: u64,
+    },
+
+    /// Output lines were suppressed because a stream exceeded its event rate
+    ///
+    /// Emitted when `max_output_events_per_sec` is configured and more lines
+    /// were read from a stream within one second than the budget allows.
+    /// Summarizes the lines of that second that were not sent as
+    /// [`TaskEvent::Output`], once the second has passed or the stream ends.
+    OutputSuppressed {
+        /// Name of the task that produced the output
+        task_name: Arc<str>,
+        /// Source stream (stdout or stderr)
+        src: StreamSource,
+        /// Number of lines that were not sent
+        count: u64,
+        /// Total length in bytes of the lines that were not sent
+        bytes: u64,
+    },
+
+    /// Output line matched an `EmitEvent` output trigger
+    ///
+    /// Sent after the output event of the line, for every matching line. Not
+    /// sent for lines the output transform dropped.
+    Triggered {
+        /// Name of the task that produced the output
+        task_name: Arc<str>,
+        /// Event name configured on the trigger
+        name: String,
+        /// The matching line after the output transform (without trailing newline)
+        line: String,
+        /// Source stream (stdout or stderr)
+        src: StreamSource,
     },
 
     /// Process has signaled it's ready to accept requests
@@ -122,7 +245,36 @@ pub enum TaskEvent {
     /// Indicates the process has completed initialization and is ready for work.
     Ready {
         /// Name of the task that became ready
-        task_name: String,
+        task_name: Arc<str>,
+    },
+
+    /// Task has run longer than expected
+    ///
+    /// Emitted once when a task with `expected_duration_ms` configured is still
+    /// running after `running_long_multiplier` times that duration. The task
+    /// is not terminated.
+    RunningLong {
+        /// Name of the task that is running long
+        task_name: Arc<str>,
+        /// Configured expected runtime in milliseconds
+        expected_ms: u64,
+        /// Time since the process was spawned in milliseconds
+        elapsed_ms: u64,
+    },
+
+    /// Task has crossed its memory watermark
+    ///
+    /// Emitted when the sampled resident memory of a task with
+    /// `memory_watermark_bytes` configured reaches the watermark, and again
+    /// only after it fell below 90% of it in between. The task is not
+    /// terminated.
+    MemoryWatermarkExceeded {
+        /// Name of the task that crossed its watermark
+        task_name: Arc<str>,
+        /// Sampled resident memory in bytes, of the process group if sampled as one
+        rss_bytes: u64,
+        /// Configured watermark in bytes
+        watermark_bytes: u64,
     },
 
     /// Process has completed execution
@@ -130,20 +282,110 @@ pub enum TaskEvent {
     /// The process has exited and all resources have been cleaned up.
     Stopped {
         /// Name of the task that stopped
-        task_name: String,
-        /// Exit code from the process (None if terminated)
+        task_name: Arc<str>,
+        /// Exit code from the process
+        ///
+        /// `Some` when the process exited on its own. `None` when it was
+        /// terminated by this crate, killed by a signal (Unix) or ended with an
+        /// NTSTATUS failure code (Windows); see [`TaskEventStopReason`]. A Windows
+        /// console interrupt keeps its raw status here.
         exit_code: Option<i32>,
+        /// Signal that ended the process (Unix only)
+        ///
+        /// Set when the process was killed by a signal, whether it exited on
+        /// its own, e.g. `SIGSEGV`, or was terminated with a graceful shutdown
+        /// period. Always `None` on other platforms.
+        signal: Option<i32>,
+        /// Meaning of `exit_code` configured with `TaskConfig::exit_code_meanings`
+        exit_meaning: Option<String>,
         /// Reason the process stopped
         reason: TaskEventStopReason,
+        /// Whether the task reached the Ready state before it stopped
+        ///
+        /// Distinguishes a crash during startup from a failure after the
+        /// process became ready. Always `false` without a ready indicator.
+        was_ready: bool,
+        /// Time from spawning the process until it stopped, in milliseconds
+        duration_ms: u64,
+        /// Configured expected runtime in milliseconds, if any
+        expected_duration_ms: Option<u64>,
+        /// Whether the result was replayed from a cache instead of running the process
+        ///
+        /// Set by `TaskSpawner::start_cached` on a cache hit; see
+        /// [`CacheLayer`](crate::tasks::async_tokio::cache::CacheLayer).
+        from_cache: bool,
+        /// SHA-256 of the raw bytes read from stdout, as lowercase hex
+        ///
+        /// Set with `TaskConfig::hash_output` when stdout is captured.
+        stdout_sha256: Option<String>,
+        /// SHA-256 of the raw bytes read from stderr, as lowercase hex
+        ///
+        /// Set with `TaskConfig::hash_output` when stderr is captured.
+        stderr_sha256: Option<String>,
+    },
+
+    /// Spawning the process failed and will be attempted again
+    ///
+    /// Emitted with `TaskConfig::spawn_retry` configured, when an attempt fails
+    /// with one of its `retry_on` error kinds and attempts remain. The next
+    /// attempt follows after `backoff_ms`. A final failure is reported by
+    /// `Error` as without retries.
+    SpawnRetry {
+        /// Name of the task being spawned
+        task_name: Arc<str>,
+        /// Number of the attempt that failed, starting at 1
+        attempt: u32,
+        /// Error the attempt failed with
+        error: String,
+    },
+
+    /// A step of the stdin script has run
+    ///
+    /// Emitted with `TaskConfig::stdin_script` configured, once the line a
+    /// step waited for was read and its input was queued for stdin.
+    ScriptStep {
+        /// Name of the task running the script
+        task_name: Arc<str>,
+        /// Index of the step in the script, starting at 0
+        index: u32,
+    },
+
+    /// A ready task failed its health check
+    ///
+    /// Emitted with a health check set by `TaskSpawner::set_health_check`,
+    /// once `failure_threshold` consecutive probes failed. Emitted again only
+    /// after a probe passed in between. The task is terminated only with
+    /// `UnhealthyAction::Terminate`.
+    Unhealthy {
+        /// Name of the task that is unhealthy
+        task_name: Arc<str>,
+        /// Number of consecutive failed probes
+        consecutive_failures: u32,
+    },
+
+    /// The event channel consumer is not keeping up with the output
+    ///
+    /// Emitted once per run with `TaskConfig::slow_consumer_threshold_ms`
+    /// configured, when output events waited longer than the threshold on
+    /// average, over the last 16 sends, for room in the event channel. The
+    /// total wait and the highest channel occupancy are reported in
+    /// `TaskInfo::event_send_wait` and `TaskInfo::event_channel_high_water`.
+    SlowConsumer {
+        /// Name of the task whose events wait
+        task_name: Arc<str>,
+        /// Average wait of the last sends in milliseconds
+        avg_wait_ms: u64,
     },
 
     /// An error occurred before task execution
     ///
     /// Emitted when errors occur during configuration validation,
-    /// process spawning, and will not emit any further events.
+    /// process spawning, and will not emit any further events. The exception
+    /// is a failing `on_spawned` hook: the process already exists, so it is
+    /// killed and `Stopped` follows.
     Error {
         /// Name of the task that encountered an error
-        task_name: String,
+        task_name: Arc<str>,
         /// The specific error that occurred
         error: TaskError,
     },
@@ -154,6 +396,14 @@ pub enum TaskEvent {
 /// Provides detailed information about why a process completed,
 /// whether due to natural completion, termination, or error.
 ///
+/// A process that exits on its own, with any exit code, stops with `Finished`.
+/// A process that dies violently without being terminated by this crate stops
+/// with `Error` and no exit code: on Unix when killed by a signal (the message
+/// names the signal, e.g. `"Process killed by signal SIGKILL (9)"`), on Windows
+/// when the exit code is an NTSTATUS failure value (negative as `i32`).
+/// A Windows process ended by Ctrl+C, Ctrl+Break or closing its console stops
+/// with `Terminated(UserRequested)` and keeps the raw status as its exit code.
+///
 /// # Examples
 ///
 /// ```rust
@@ -171,14 +421,20 @@ pub enum TaskEvent {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq)]
 pub enum TaskEventStopReason {
-    /// Process completed normally with an exit code
+    /// Process exited on its own with an exit code
     Finished,
 
     /// Process was terminated for a specific reason
     Terminated(TaskTerminateReason),
 
-    /// Process stopped due to an error
+    /// Process stopped due to an error, including being killed by a signal
     Error(String),
+
+    /// Process exited before it could be reported as started
+    ///
+    /// Carries a description of its exit status; the exit code, if any, is
+    /// reported in `TaskEvent::Stopped`. No `Started` event precedes it.
+    StartupFailure(String),
 }
 
 /// Reason for terminating a running task
@@ -189,7 +445,8 @@ pub enum TaskEventStopReason {
 /// # Examples
 ///
 /// ## Timeout Termination
-/// ```rust
+#[cfg_attr(feature = "tokio", doc = "```rust")]
+#[cfg_attr(not(feature = "tokio"), doc = "```ignore")]
 /// use tcrm_task::tasks::{
 ///     config::TaskConfig,
 ///     async_tokio::spawner::TaskSpawner,
@@ -199,7 +456,12 @@ pub enum TaskEventStopReason {
 ///
 /// #[tokio::main]
 /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
-///     let config = TaskConfig::new("cmd").args(["/C", "ping", "127.0.0.1", "-n", "5"]); // 5 second sleep
+///     // Runs for about 5 seconds
+///     let config = if cfg!(windows) {
+///         TaskConfig::new("cmd").args(["/C", "ping", "127.0.0.1", "-n", "5"])
+///     } else {
+///         TaskConfig::new("sleep").args(["5"])
+///     };
 ///     let mut spawner = TaskSpawner::new("long-task".to_string(), config);
 ///     
 ///     let (tx, _rx) = mpsc::channel(100);
@@ -214,7 +476,8 @@ pub enum TaskEventStopReason {
 /// ```
 ///
 /// ## Cleanup Termination
-/// ```rust
+#[cfg_attr(feature = "tokio", doc = "```rust")]
+#[cfg_attr(not(feature = "tokio"), doc = "```ignore")]
 /// use tcrm_task::tasks::{
 ///     config::TaskConfig,
 ///     async_tokio::spawner::TaskSpawner,
@@ -224,14 +487,20 @@ pub enum TaskEventStopReason {
 ///
 /// #[tokio::main]
 /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
-///     let config = TaskConfig::new("cmd").args(["/C", "echo", "running"]);
+///     let config = if cfg!(windows) {
+///         TaskConfig::new("cmd").args(["/C", "echo", "running"])
+///     } else {
+///         TaskConfig::new("echo").args(["running"])
+///     };
 ///     let mut spawner = TaskSpawner::new("daemon".to_string(), config);
 ///     
 ///     let (tx, _rx) = mpsc::channel(100);
 ///     spawner.start_direct(tx).await?;
 ///     
-///     // Cleanup shutdown reason
-///     let reason = TaskTerminateReason::Cleanup;
+///     // Cleanup shutdown reason, with a message shown in `Stopped`
+///     let reason = TaskTerminateReason::Cleanup {
+///         message: Some("redeploying".to_string()),
+///     };
 ///     spawner.send_terminate_signal(reason).await?;
 ///     
 ///     Ok(())
@@ -250,7 +519,10 @@ pub enum TaskTerminateReason {
     ///
     /// Used when terminating tasks as part of application shutdown,
     /// resource cleanup, or dependency management.
-    Cleanup,
+    Cleanup {
+        /// Why the task was cleaned up, e.g. `"redeploying"`
+        message: Option<String>,
+    },
 
     /// Task was terminated because its dependencies finished
     ///
@@ -261,5 +533,49 @@ pub enum TaskTerminateReason {
     /// Task was terminated by explicit user request
     ///
     /// Used when user or external library requests the task to stop.
-    UserRequested,
+    UserRequested {
+        /// Who asked and why, e.g. `"stopped by alice: redeploying"`
+        message: Option<String>,
+    },
+
+    /// Task was terminated because it printed its completion indicator
+    ///
+    /// Used for tools that finish their work but do not exit on their own;
+    /// see `TaskConfig::exit_on_output_match`.
+    CompletionIndicator,
+
+    /// Task was terminated because a step of its stdin script timed out
+    ///
+    /// The line the step waited for was not read within its `timeout_ms`;
+    /// see `TaskConfig::stdin_script`.
+    ScriptTimeout,
+
+    /// Task was terminated because it failed its health check
+    ///
+    /// Used with `UnhealthyAction::Terminate`; see `TaskSpawner::set_health_check`.
+    Unhealthy,
+}
+
+impl TaskTerminateReason {
+    /// Message given with a `Cleanup` or `UserRequested` termination, if any
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tcrm_task::tasks::event::TaskTerminateReason;
+    ///
+    /// let reason = TaskTerminateReason::UserRequested {
+    ///     message: Some("stopped by alice: redeploying".to_string()),
+    /// };
+    /// assert_eq!(reason.message(), Some("stopped by alice: redeploying"));
+    /// assert_eq!(TaskTerminateReason::Timeout.message(), None);
+    /// ```
+    #[must_use]
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            TaskTerminateReason::Cleanup { message }
+            | TaskTerminateReason::UserRequested { message } => message.as_deref(),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,473 @@
+//! Line-oriented logs of task events.
+//!
+//! A [`LogFormatter`] renders each [`TaskEvent`] as one line: an optional
+//! timestamp and output sequence number, a prefix naming the task, a tag for
+//! the stream or kind of event, and its message.
+//!
+//! ```text
+//! 2026-10-16T09:30:00.250Z #3 [build] stderr: warning: unused variable
+//! 2026-10-16T09:30:01.000Z [build] stopped: finished, exit code 0 in 1000 ms
+//! ```
+//!
+//! With the `tokio` feature, a [`LogWriter`] writes formatted events to any
+//! `AsyncWrite`, such as a file or stdout.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::tasks::{
+    config::StreamSource,
+    event::{TaskEvent, TaskEventStopReason, TaskTerminateReason},
+};
+
+/// Placeholder in the prefix template replaced by the task name
+pub const TASK_PLACEHOLDER: &str = "{task}";
+
+/// Prefix template used by [`LogFormatter::new`]
+pub const DEFAULT_PREFIX: &str = "[{task}]";
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+/// Formats task events as log lines
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// use tcrm_task::tasks::{config::StreamSource, event::{TaskEvent, format::LogFormatter}};
+///
+/// let event = TaskEvent::Output {
+///     task_name: "build".into(),
+///     line: "Compiling tcrm-task".to_string(),
+///     src: StreamSource::Stdout,
+///     seq: 7,
+///     truncated: false,
+///     timestamp: UNIX_EPOCH + Duration::from_millis(1_700_000_000_250),
+/// };
+///
+/// let formatter = LogFormatter::new();
+/// assert_eq!(formatter.format(&event), "[build] stdout: Compiling tcrm-task");
+///
+/// // Output lines are timestamped when they were read
+/// let formatter = LogFormatter::new().prefix("{task} |").timestamps(true).sequence(true);
+/// assert_eq!(
+///     formatter.format(&event),
+///     "2023-11-14T22:13:20.250Z #7 build | stdout: Compiling tcrm-task"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct LogFormatter {
+    prefix: String,
+    timestamps: bool,
+    sequence: bool,
+    color: bool,
+}
+
+impl Default for LogFormatter {
+    fn default() -> Self {
+        LogFormatter {
+            prefix: DEFAULT_PREFIX.to_string(),
+            timestamps: false,
+            sequence: false,
+            color: false,
+        }
+    }
+}
+
+impl LogFormatter {
+    /// Create a formatter prefixing lines with `[<task>]`, without timestamps, sequence numbers or color
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the template of the prefix naming the task
+    ///
+    /// [`TASK_PLACEHOLDER`] is replaced by the task name. An empty template
+    /// leaves the prefix out.
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - Prefix template (default: `[{task}]`)
+    #[must_use]
+    pub fn prefix(mut self, template: impl Into<String>) -> Self {
+        self.prefix = template.into();
+        self
+    }
+
+    /// Start lines with the time they were formatted, in RFC 3339 UTC with milliseconds
+    #[must_use]
+    pub fn timestamps(mut self, enabled: bool) -> Self {
+        self.timestamps = enabled;
+        self
+    }
+
+    /// Include the sequence number of output lines, as `#<seq>`
+    ///
+    /// Only output events carry one; it orders lines across both streams.
+    #[must_use]
+    pub fn sequence(mut self, enabled: bool) -> Self {
+        self.sequence = enabled;
+        self
+    }
+
+    /// Color tags with ANSI escape codes, e.g. for a terminal
+    ///
+    /// Stderr and failures are red, readiness and success green, and
+    /// warnings yellow. Timestamps and sequence numbers are dimmed.
+    #[must_use]
+    pub fn color(mut self, enabled: bool) -> Self {
+        self.color = enabled;
+        self
+    }
+
+    /// Format `event` as a log line, without a line ending
+    ///
+    /// Output lines are timestamped when they were read, other events now.
+    pub fn format(&self, event: &TaskEvent) -> String {
+        let at = match event {
+            TaskEvent::Output { timestamp, .. } | TaskEvent::OutputJson { timestamp, .. } => {
+                *timestamp
+            }
+            _ => SystemTime::now(),
+        };
+        self.format_at(event, at)
+    }
+
+    /// Format `event` as a log line, without a line ending, timestamped `at`
+    pub fn format_at(&self, event: &TaskEvent, at: SystemTime) -> String {
+        let (task_name, tag, message) = describe(event);
+        let mut parts = Vec::with_capacity(4);
+        if self.timestamps {
+            parts.push(self.paint(Some(DIM), &rfc3339(at)));
+        }
+        if self.sequence
+            && let Some(seq) = sequence(event)
+        {
+            parts.push(self.paint(Some(DIM), &format!("#{seq}")));
+        }
+        if !self.prefix.is_empty() {
+            parts.push(self.prefix.replace(TASK_PLACEHOLDER, task_name));
+        }
+        let tag = self.paint(tag_color(event), tag);
+        parts.push(if message.is_empty() {
+            tag
+        } else {
+            format!("{tag}: {message}")
+        });
+        parts.join(" ")
+    }
+
+    fn paint(&self, color: Option<&str>, text: &str) -> String {
+        match color {
+            Some(color) if self.color => format!("{color}{text}{RESET}"),
+            _ => text.to_string(),
+        }
+    }
+}
+
+/// Task name, tag and message of `event`
+fn describe(event: &TaskEvent) -> (&str, &'static str, String) {
+    match event {
+        TaskEvent::Started {
+            task_name,
+            process_id,
+            temp_dir,
+            working_dir,
+        } => {
+            let message = match temp_dir.as_ref().or(working_dir.as_ref()) {
+                Some(dir) => format!("process {process_id} in {dir}"),
+                None => format!("process {process_id}"),
+            };
+            (task_name, "started", message)
+        }
+        TaskEvent::Output {
+            task_name,
+            line,
+            src,
+            ..
+        } => (task_name, stream_tag(src), line.clone()),
+        TaskEvent::OutputJson {
+            task_name,
+            line,
+            src,
+            ..
+        } => (task_name, stream_tag(src), line.clone()),
+        TaskEvent::OutputChunk {
+            task_name,
+            data,
+            src,
+        } => (task_name, stream_tag(src), format!("{} bytes", data.len())),
+        TaskEvent::OutputRepeated {
+            task_name,
+            line,
+            src,
+            count,
+        } => (
+            task_name,
+            stream_tag(src),
+            format!("last line repeated {count} more times: {line}"),
+        ),
+        TaskEvent::OutputSuppressed {
+            task_name,
+            src,
+            count,
+            bytes,
+        } => (
+            task_name,
+            stream_tag(src),
+            format!("{count} lines ({bytes} bytes) suppressed"),
+        ),
+        TaskEvent::Triggered {
+            task_name,
+            name,
+            line,
+            ..
+        } => (task_name, "triggered", format!("{name}: {line}")),
+        TaskEvent::Ready { task_name } => (task_name, "ready", String::new()),
+        TaskEvent::RunningLong {
+            task_name,
+            expected_ms,
+            elapsed_ms,
+        } => (
+            task_name,
+            "running long",
+            format!("{elapsed_ms} ms, expected {expected_ms} ms"),
+        ),
+        TaskEvent::MemoryWatermarkExceeded {
+            task_name,
+            rss_bytes,
+            watermark_bytes,
+        } => (
+            task_name,
+            "memory",
+            format!("{rss_bytes} bytes above watermark of {watermark_bytes} bytes"),
+        ),
+        TaskEvent::SpawnRetry {
+            task_name,
+            attempt,
+            error,
+        } => (
+            task_name,
+            "spawn retry",
+            format!("attempt {attempt} failed: {error}"),
+        ),
+        TaskEvent::ScriptStep { task_name, index } => {
+            (task_name, "script", format!("step {index} answered"))
+        }
+        TaskEvent::Unhealthy {
+            task_name,
+            consecutive_failures,
+        } => (
+            task_name,
+            "unhealthy",
+            format!("{consecutive_failures} health probes failed in a row"),
+        ),
+        TaskEvent::SlowConsumer {
+            task_name,
+            avg_wait_ms,
+        } => (
+            task_name,
+            "slow consumer",
+            format!("events waited {avg_wait_ms} ms on average to be sent"),
+        ),
+        TaskEvent::Stopped {
+            task_name,
+            exit_code,
+            exit_meaning,
+            reason,
+            duration_ms,
+            from_cache,
+            ..
+        } => {
+            let mut message = match reason {
+                TaskEventStopReason::Finished => "finished".to_string(),
+                TaskEventStopReason::Terminated(reason) => {
+                    format!("terminated ({})", terminate_reason(reason))
+                }
+                TaskEventStopReason::Error(error) => format!("error: {error}"),
+                TaskEventStopReason::StartupFailure(error) => format!("startup failure: {error}"),
+            };
+            if let Some(code) = exit_code {
+                message.push_str(&format!(", exit code {code}"));
+            }
+            if let Some(meaning) = exit_meaning {
+                message.push_str(&format!(" ({meaning})"));
+            }
+            message.push_str(&format!(" in {duration_ms} ms"));
+            if *from_cache {
+                message.push_str(", from cache");
+            }
+            (task_name, "stopped", message)
+        }
+        TaskEvent::Error { task_name, error } => (task_name, "error", error.to_string()),
+        // Variants added later are logged with their debug representation until formatted here
+        #[allow(unreachable_patterns)]
+        _ => ("", "event", format!("{event:?}")),
+    }
+}
+
+/// Sequence number of output events
+/// Names a termination reason, followed by its message if it has one
+fn terminate_reason(reason: &TaskTerminateReason) -> String {
+    match reason {
+        TaskTerminateReason::Cleanup {
+            message: Some(message),
+        } => format!("Cleanup: {message}"),
+        TaskTerminateReason::UserRequested {
+            message: Some(message),
+        } => format!("UserRequested: {message}"),
+        TaskTerminateReason::Cleanup { message: None } => "Cleanup".to_string(),
+        TaskTerminateReason::UserRequested { message: None } => "UserRequested".to_string(),
+        other => format!("{other:?}"),
+    }
+}
+
+fn sequence(event: &TaskEvent) -> Option<u64> {
+    match event {
+        TaskEvent::Output { seq, .. } => Some(*seq),
+        #[cfg(feature = "serde")]
+        TaskEvent::OutputJson { seq, .. } => Some(*seq),
+        _ => None,
+    }
+}
+
+fn stream_tag(src: &StreamSource) -> &'static str {
+    match src {
+        StreamSource::Stdout => "stdout",
+        StreamSource::Stderr => "stderr",
+    }
+}
+
+/// Color of the tag of `event`, `None` for plain stdout output
+fn tag_color(event: &TaskEvent) -> Option<&'static str> {
+    match event {
+        TaskEvent::Output { src, .. }
+        | TaskEvent::OutputChunk { src, .. }
+        | TaskEvent::OutputRepeated { src, .. }
+        | TaskEvent::OutputSuppressed { src, .. } => {
+            matches!(src, StreamSource::Stderr).then_some(RED)
+        }
+        #[cfg(feature = "serde")]
+        TaskEvent::OutputJson { src, .. } => matches!(src, StreamSource::Stderr).then_some(RED),
+        TaskEvent::Started { .. } | TaskEvent::Ready { .. } => Some(GREEN),
+        TaskEvent::Stopped {
+            reason: TaskEventStopReason::Finished,
+            exit_code: Some(0),
+            ..
+        } => Some(GREEN),
+        TaskEvent::Stopped { .. } | TaskEvent::Error { .. } => Some(RED),
+        _ => Some(YELLOW),
+    }
+}
+
+/// Formats `time` as RFC 3339 in UTC with milliseconds, e.g. `2026-10-16T09:30:00.250Z`
+///
+/// Times before the Unix epoch are formatted as the epoch.
+fn rfc3339(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs();
+    let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+
+    // Civil date from days since the epoch, after Howard Hinnant's `civil_from_days`
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+/// Writes task events as log lines to an `AsyncWrite`
+///
+/// Each event is formatted by a [`LogFormatter`] and written with a trailing
+/// newline.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use tcrm_task::tasks::{
+///     async_tokio::spawner::TaskSpawner,
+///     config::TaskConfig,
+///     event::format::{LogFormatter, LogWriter},
+/// };
+/// use tokio::sync::mpsc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let (tx, mut rx) = mpsc::channel(100);
+///     let mut spawner = TaskSpawner::new("build".to_string(), TaskConfig::new("cargo").args(["build"]));
+///     spawner.start_direct(tx).await?;
+///
+///     // Any `AsyncWrite`, e.g. a file or a socket
+///     let mut writer = LogWriter::new(Vec::new(), LogFormatter::new().timestamps(true));
+///     writer.write_events(&mut rx).await?;
+///     print!("{}", String::from_utf8_lossy(&writer.into_inner()));
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "tokio")]
+#[derive(Debug)]
+pub struct LogWriter<W> {
+    writer: W,
+    formatter: LogFormatter,
+}
+
+#[cfg(feature = "tokio")]
+impl<W: tokio::io::AsyncWrite + Unpin> LogWriter<W> {
+    /// Create a writer formatting events with `formatter`
+    pub fn new(writer: W, formatter: LogFormatter) -> Self {
+        LogWriter { writer, formatter }
+    }
+
+    /// Write `event` as a line
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the underlying writer.
+    pub async fn write_event(&mut self, event: &TaskEvent) -> std::io::Result<()> {
+        use tokio::io::AsyncWriteExt;
+
+        let mut line = self.formatter.format(event);
+        line.push('\n');
+        self.writer.write_all(line.as_bytes()).await
+    }
+
+    /// Write every event received from `rx` until all its senders are dropped, then flush
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of the underlying writer; events received
+    /// after it are left in `rx`.
+    pub async fn write_events(
+        &mut self,
+        rx: &mut tokio::sync::mpsc::Receiver<TaskEvent>,
+    ) -> std::io::Result<()> {
+        use tokio::io::AsyncWriteExt;
+
+        while let Some(event) = rx.recv().await {
+            self.write_event(&event).await?;
+        }
+        self.writer.flush().await
+    }
+
+    /// Consume the adapter, returning the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
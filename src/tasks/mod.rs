@@ -1,7 +1,10 @@
+#[cfg(feature = "tokio")]
 pub mod async_tokio;
+pub mod audit;
 pub mod config;
 pub mod error;
 pub mod event;
+pub mod plan;
 pub mod state;
 pub mod validator;
 
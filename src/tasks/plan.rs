@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+
+/// What starting a task would execute, resolved without spawning it
+///
+/// Returned by [`TaskSpawner::plan`](crate::tasks::async_tokio::spawner::TaskSpawner::plan),
+/// which performs the same validation as `start_direct` and resolves the
+/// program, working directory and environment the way the process would see
+/// them.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "tokio", doc = "```rust,no_run")]
+#[cfg_attr(not(feature = "tokio"), doc = "```ignore")]
+/// use tcrm_task::tasks::{config::TaskConfig, async_tokio::spawner::TaskSpawner};
+///
+/// let config = TaskConfig::new("echo").args(["hello"]).env([("GREETING", "hi")]);
+/// let spawner = TaskSpawner::new("dry-run".to_string(), config);
+///
+/// let plan = spawner.plan().unwrap();
+/// println!("Would run {} {:?} in {}", plan.program, plan.args, plan.working_dir);
+/// assert_eq!(plan.env_overrides.get("GREETING").map(String::as_str), Some("hi"));
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionPlan {
+    /// The command as configured
+    pub command: String,
+    /// Absolute path of the program that would be executed
+    pub program: String,
+    /// Arguments passed to the program
+    pub args: Vec<String>,
+    /// Canonical working directory of the process
+    ///
+    /// The current directory of this process when no working directory is configured.
+    pub working_dir: String,
+    /// Configured environment variables that are new or differ from the
+    /// inherited environment
+    ///
+    /// Values of sensitive variables are replaced with
+    /// [`REDACTED_ENV_VALUE`](crate::tasks::validator::REDACTED_ENV_VALUE).
+    pub env_overrides: HashMap<String, String>,
+    /// Timeout in milliseconds, if configured
+    pub timeout_ms: Option<u64>,
+    /// Whether the process would be started in its own process group
+    pub use_process_group: bool,
+    /// Whether stdin would be piped to the process
+    pub enable_stdin: bool,
+}
@@ -12,12 +12,15 @@
 /// ```
 ///
 /// The Ready state is optional and only occurs for long-running processes
-/// with a configured ready indicator.
+/// with a configured ready indicator. `Initiating → Finished` happens when the
+/// process fails to spawn. Any other transition is rejected by the spawner;
+/// see [`TaskState::can_transition_to`].
 ///
 /// # Examples
 ///
 /// ## State Monitoring
-/// ```rust
+#[cfg_attr(feature = "tokio", doc = "```rust")]
+#[cfg_attr(not(feature = "tokio"), doc = "```ignore")]
 /// use tcrm_task::tasks::{config::TaskConfig, async_tokio::spawner::TaskSpawner, state::TaskState};
 ///
 /// #[tokio::main]
@@ -34,7 +37,8 @@
 /// ```
 ///
 /// ## Basic State Checking
-/// ```rust
+#[cfg_attr(feature = "tokio", doc = "```rust")]
+#[cfg_attr(not(feature = "tokio"), doc = "```ignore")]
 /// use tcrm_task::tasks::{
 ///     config::TaskConfig,
 ///     async_tokio::spawner::TaskSpawner,
@@ -89,3 +93,34 @@ pub enum TaskState {
     /// or encounters an error. No further state transitions occur.
     Finished,
 }
+
+impl TaskState {
+    /// Returns whether moving from this state to `next` is a legal transition
+    ///
+    /// Staying in the same state is not a transition, so a second start of an
+    /// `Initiating` task or a second finish is rejected too. `Finished` is
+    /// terminal.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use tcrm_task::tasks::state::TaskState;
+    ///
+    /// assert!(TaskState::Running.can_transition_to(&TaskState::Ready));
+    /// assert!(TaskState::Ready.can_transition_to(&TaskState::Finished));
+    /// assert!(!TaskState::Finished.can_transition_to(&TaskState::Running));
+    /// assert!(!TaskState::Finished.can_transition_to(&TaskState::Finished));
+    /// ```
+    #[must_use]
+    pub fn can_transition_to(&self, next: &TaskState) -> bool {
+        use TaskState::{Finished, Initiating, Pending, Ready, Running};
+
+        matches!(
+            (self, next),
+            (Pending, Initiating)
+                | (Initiating, Running | Finished)
+                | (Running, Ready | Finished)
+                | (Ready, Finished)
+        )
+    }
+}
@@ -1,4 +1,4 @@
-use crate::tasks::config::TaskConfig;
+use crate::tasks::config::{StreamSource, TaskConfig};
 
 #[test]
 fn basic() {
@@ -120,3 +120,68 @@ fn ready_indicator_source_default() {
         Some(crate::tasks::config::StreamSource::Stdout)
     );
 }
+
+#[test]
+fn ready_match_mode() {
+    use crate::tasks::config::ReadyMatchMode;
+
+    assert_eq!(TaskConfig::new("ls").ready_match_mode, None);
+    let config = TaskConfig::new("ls").ready_match_mode(ReadyMatchMode::ExactLine);
+    assert_eq!(config.ready_match_mode, Some(ReadyMatchMode::ExactLine));
+
+    assert!(ReadyMatchMode::Contains.matches("x READY y", "READY"));
+    assert!(!ReadyMatchMode::StartsWith.matches("x READY", "READY"));
+    assert!(ReadyMatchMode::StartsWith.matches("READY y", "READY"));
+    assert!(!ReadyMatchMode::ExactLine.matches("READY y", "READY"));
+    assert!(ReadyMatchMode::ExactLine.matches("READY", "READY"));
+}
+
+#[test]
+fn capture_streams() {
+    let config = TaskConfig::new("echo");
+    assert!(config.is_stream_captured(&StreamSource::Stdout));
+    assert!(config.is_stream_captured(&StreamSource::Stderr));
+
+    let config = config.capture_stdout(false);
+    assert_eq!(config.capture_stdout, Some(false));
+    assert!(!config.is_stream_captured(&StreamSource::Stdout));
+    assert!(config.is_stream_captured(&StreamSource::Stderr));
+}
+
+#[test]
+fn stdio_inherit_captures_no_stream() {
+    let config = TaskConfig::new("echo");
+    assert_eq!(config.stdio_inherit, Some(false));
+
+    let config = config.stdio_inherit(true);
+    assert!(!config.is_stream_captured(&StreamSource::Stdout));
+    assert!(!config.is_stream_captured(&StreamSource::Stderr));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn spawn_retry_roundtrips_through_json() {
+    use std::io::ErrorKind;
+
+    let config = TaskConfig::new("tool.exe").spawn_retry(
+        3,
+        100,
+        [ErrorKind::PermissionDenied, ErrorKind::ResourceBusy],
+    );
+
+    let json = serde_json::to_value(&config).unwrap();
+    assert_eq!(
+        json["spawn_retry"],
+        serde_json::json!({
+            "attempts": 3,
+            "backoff_ms": 100,
+            "retry_on": ["PermissionDenied", "ResourceBusy"],
+        })
+    );
+    let parsed: TaskConfig = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(parsed.spawn_retry, config.spawn_retry);
+
+    let mut json = json;
+    json["spawn_retry"]["retry_on"] = serde_json::json!(["NoSuchKind"]);
+    assert!(serde_json::from_value::<TaskConfig>(json).is_err());
+}
@@ -1,2 +1,4 @@
 mod builder;
+mod overrides;
 mod validation;
+mod warnings;
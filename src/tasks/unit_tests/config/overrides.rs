@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+use crate::tasks::{
+    config::{
+        StreamSource, TaskConfig,
+        overrides::{MergeStrategy, TaskConfigOverrides},
+    },
+    error::TaskError,
+};
+
+fn base() -> TaskConfig {
+    TaskConfig::new("cargo")
+        .args(["build", "--locked"])
+        .working_dir("/srv/project")
+        .env([("RUST_LOG", "info"), ("CARGO_TERM_COLOR", "never")])
+        .timeout_ms(60_000)
+        .use_process_group(false)
+}
+
+fn env(pairs: &[(&str, &str)]) -> Option<HashMap<String, String>> {
+    Some(
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect(),
+    )
+}
+
+/// Asserts the fields overrides can change are equal
+fn assert_same_fields(config: &TaskConfig, expected: &TaskConfig) {
+    assert_eq!(config.command, expected.command);
+    assert_eq!(config.args, expected.args);
+    assert_eq!(config.working_dir, expected.working_dir);
+    assert_eq!(config.env, expected.env);
+    assert_eq!(config.timeout_ms, expected.timeout_ms);
+    assert_eq!(config.enable_stdin, expected.enable_stdin);
+    assert_eq!(config.ready_indicator, expected.ready_indicator);
+    assert_eq!(config.use_process_group, expected.use_process_group);
+}
+
+#[test]
+fn empty_overrides_keep_the_base() {
+    let base = base();
+    assert_same_fields(&base.merged_with(TaskConfigOverrides::new()), &base);
+}
+
+#[test]
+fn args_replace_by_default() {
+    let config = base().merged_with(TaskConfigOverrides::new().args(["test"]));
+    assert_eq!(config.args, Some(vec!["test".to_string()]));
+}
+
+#[test]
+fn args_append() {
+    let config = base().merged_with(
+        TaskConfigOverrides::new()
+            .args(["--release"])
+            .args_strategy(MergeStrategy::Append),
+    );
+    assert_eq!(
+        config.args,
+        Some(vec![
+            "build".to_string(),
+            "--locked".to_string(),
+            "--release".to_string()
+        ])
+    );
+}
+
+#[test]
+fn args_append_without_base_args() {
+    let config = TaskConfig::new("ls").merged_with(
+        TaskConfigOverrides::new()
+            .args(["-l"])
+            .args_strategy(MergeStrategy::Append),
+    );
+    assert_eq!(config.args, Some(vec!["-l".to_string()]));
+}
+
+#[test]
+fn env_replace_by_default() {
+    let config = base().merged_with(TaskConfigOverrides::new().env([("RUST_LOG", "debug")]));
+    assert_eq!(config.env, env(&[("RUST_LOG", "debug")]));
+}
+
+#[test]
+fn env_append_overrides_same_keys() {
+    let config = base().merged_with(
+        TaskConfigOverrides::new()
+            .env([("RUST_LOG", "debug"), ("RUST_BACKTRACE", "1")])
+            .env_strategy(MergeStrategy::Append),
+    );
+    assert_eq!(
+        config.env,
+        env(&[
+            ("RUST_LOG", "debug"),
+            ("CARGO_TERM_COLOR", "never"),
+            ("RUST_BACKTRACE", "1"),
+        ])
+    );
+}
+
+#[test]
+fn strategy_without_list_keeps_the_base() {
+    let base = base();
+    let config = base.merged_with(
+        TaskConfigOverrides::new()
+            .args_strategy(MergeStrategy::Replace)
+            .env_strategy(MergeStrategy::Append),
+    );
+    assert_same_fields(&config, &base);
+}
+
+#[test]
+fn scalars_override_and_others_are_kept() {
+    let base = base().enable_stdin(true);
+    let config = base.merged_with(
+        TaskConfigOverrides::new()
+            .command("cross")
+            .working_dir("/srv/other")
+            .timeout_ms(5_000)
+            .ready_indicator("Finished")
+            .use_process_group(true),
+    );
+
+    assert_eq!(config.command, "cross");
+    assert_eq!(config.working_dir, Some("/srv/other".to_string()));
+    assert_eq!(config.timeout_ms, Some(5_000));
+    assert_eq!(config.ready_indicator, Some("Finished".to_string()));
+    assert_eq!(config.use_process_group, Some(true));
+    assert_eq!(config.args, base.args);
+    assert_eq!(config.env, base.env);
+    assert_eq!(config.enable_stdin, Some(true));
+    assert_eq!(config.ready_indicator_source, Some(StreamSource::Stdout));
+}
+
+#[test]
+fn base_is_unchanged() {
+    let base = base();
+    let before = base.clone();
+    let _ = base.merged_with(
+        TaskConfigOverrides::new()
+            .args(["test"])
+            .args_strategy(MergeStrategy::Append)
+            .env([("RUST_LOG", "debug")])
+            .env_strategy(MergeStrategy::Append),
+    );
+    assert_same_fields(&base, &before);
+}
+
+#[test]
+fn merged_config_is_validated_on_demand() {
+    let config = base().merged_with(TaskConfigOverrides::new().command(""));
+    assert!(matches!(
+        config.validate(),
+        Err(TaskError::InvalidConfiguration(_))
+    ));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn overrides_deserialize_with_missing_fields() {
+    let overrides: TaskConfigOverrides = serde_json::from_value(serde_json::json!({
+        "args": ["--release"],
+        "args_strategy": "Append",
+        "timeout_ms": 1000,
+    }))
+    .unwrap();
+    assert_eq!(
+        overrides,
+        TaskConfigOverrides::new()
+            .args(["--release"])
+            .args_strategy(MergeStrategy::Append)
+            .timeout_ms(1000)
+    );
+
+    let json = serde_json::to_value(&overrides).unwrap();
+    let parsed: TaskConfigOverrides = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, overrides);
+}
@@ -1,6 +1,12 @@
 use std::{collections::HashMap, env::temp_dir};
 
-use crate::tasks::{config::TaskConfig, error::TaskError};
+use crate::tasks::{
+    config::{
+        OutputEncoding, OutputMode, OutputTrigger, StdinScript, StdinScriptStep, StreamSource,
+        TaskConfig, TaskPriority, TriggerAction,
+    },
+    error::TaskError,
+};
 
 #[test]
 fn accept_basic_echo_command() {
@@ -244,3 +250,619 @@ fn reject_empty_ready_indicator() {
         Err(TaskError::InvalidConfiguration(_))
     ));
 }
+
+#[test]
+fn reject_ready_indicator_on_uncaptured_stream() {
+    let config = TaskConfig::new("echo")
+        .ready_indicator("ready")
+        .ready_indicator_source(StreamSource::Stderr)
+        .capture_stderr(false);
+    assert!(matches!(
+        config.validate(),
+        Err(TaskError::InvalidConfiguration(_))
+    ));
+}
+
+#[test]
+fn ready_indicator_regex_requires_regex() {
+    let config = TaskConfig::new("echo").ready_indicator_regex(r"port \d+");
+    if cfg!(feature = "regex") {
+        assert!(config.validate().is_ok());
+    } else {
+        assert!(matches!(
+            config.validate(),
+            Err(TaskError::InvalidConfiguration(_))
+        ));
+    }
+}
+
+#[test]
+fn reject_invalid_ready_indicator_regex() {
+    let invalid = [
+        TaskConfig::new("echo").ready_indicator_regex(""),
+        TaskConfig::new("echo").ready_indicator_regex(r"port (\d+"),
+        TaskConfig::new("echo")
+            .ready_indicator("ready")
+            .ready_indicator_regex("ready"),
+        TaskConfig::new("echo")
+            .ready_indicator_regex("ready")
+            .capture_stdout(false),
+    ];
+    for config in invalid {
+        assert!(
+            matches!(config.validate(), Err(TaskError::InvalidConfiguration(_))),
+            "{config:?} should be rejected"
+        );
+    }
+}
+
+#[test]
+fn reject_inherited_stdio_with_output_matching_or_stdin() {
+    let invalid = [
+        TaskConfig::new("echo").ready_indicator("ready"),
+        TaskConfig::new("echo").ready_indicator_regex("ready"),
+        TaskConfig::new("echo").exit_on_output_match("done"),
+        TaskConfig::new("echo").enable_stdin(true),
+    ];
+    for config in invalid {
+        let config = config.stdio_inherit(true);
+        assert!(
+            matches!(config.validate(), Err(TaskError::InvalidConfiguration(_))),
+            "{config:?} should be rejected"
+        );
+    }
+    assert!(
+        TaskConfig::new("echo")
+            .stdio_inherit(true)
+            .timeout_ms(1000)
+            .validate()
+            .is_ok()
+    );
+}
+
+#[test]
+fn reject_bytes_output_mode_with_line_options() {
+    let invalid = [
+        TaskConfig::new("echo").ready_indicator("ready"),
+        TaskConfig::new("echo").ready_indicator_regex("ready"),
+        TaskConfig::new("echo").exit_on_output_match("done"),
+        TaskConfig::new("echo").parse_json_lines(true),
+        TaskConfig::new("echo").dedupe_output(1000, 3),
+        TaskConfig::new("echo").max_output_events_per_sec(100),
+        TaskConfig::new("echo").output_encoding(OutputEncoding::Utf16Le),
+    ];
+    for config in invalid {
+        let config = config.output_mode(OutputMode::Bytes);
+        assert!(
+            matches!(config.validate(), Err(TaskError::InvalidConfiguration(_))),
+            "{config:?} should be rejected"
+        );
+    }
+    assert!(
+        TaskConfig::new("echo")
+            .output_mode(OutputMode::Bytes)
+            .output_encoding(OutputEncoding::Utf8)
+            .output_buffer_bytes(4096)
+            .validate()
+            .is_ok()
+    );
+    assert!(
+        TaskConfig::new("echo")
+            .output_mode(OutputMode::Lines)
+            .ready_indicator("ready")
+            .validate()
+            .is_ok()
+    );
+}
+
+#[test]
+fn priority_support() {
+    let config = TaskConfig::new("echo").priority(TaskPriority::Low);
+    assert_eq!(config.validate().is_ok(), cfg!(any(unix, windows)));
+    assert_eq!(TaskConfig::new("echo").priority, None);
+}
+
+#[test]
+fn cpu_affinity_cores() {
+    let supported = cfg!(any(target_os = "linux", target_os = "android", windows));
+    let available = std::thread::available_parallelism().unwrap().get();
+    // Cores an affinity mask can hold, whether or not this machine has them
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    let mask = libc::CPU_SETSIZE as usize;
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    let mask = usize::BITS as usize;
+    let limit = available.min(mask);
+
+    let config = TaskConfig::new("echo").cpu_affinity([0, limit - 1]);
+    assert_eq!(config.validate().is_ok(), supported);
+
+    for cores in [vec![], vec![0, available], vec![mask]] {
+        assert!(matches!(
+            TaskConfig::new("echo").cpu_affinity(cores).validate(),
+            Err(TaskError::InvalidConfiguration(_))
+        ));
+    }
+}
+
+#[test]
+fn kill_child_on_parent_death_support() {
+    let config = TaskConfig::new("echo").kill_child_on_parent_death(true);
+    if cfg!(any(target_os = "linux", target_os = "android", windows)) {
+        assert!(config.validate().is_ok());
+    } else {
+        assert!(matches!(
+            config.validate(),
+            Err(TaskError::InvalidConfiguration(_))
+        ));
+    }
+
+    let config = config.use_process_group(false);
+    assert_eq!(
+        config.validate().is_ok(),
+        cfg!(any(target_os = "linux", target_os = "android"))
+    );
+}
+
+#[test]
+fn working_dir_candidates_choose_first_directory() {
+    let dir = temp_dir().to_string_lossy().into_owned();
+    let missing = format!("{dir}/tcrm_task_missing_candidate_{}", std::process::id());
+    let config = TaskConfig::new("echo").working_dir_candidates([&missing, &dir]);
+    assert!(config.validate().is_ok());
+    assert_eq!(config.resolve_working_dir().unwrap(), Some(dir.as_str()));
+
+    let config = TaskConfig::new("echo").working_dir(&dir);
+    assert_eq!(config.resolve_working_dir().unwrap(), Some(dir.as_str()));
+    assert_eq!(TaskConfig::new("echo").resolve_working_dir().unwrap(), None);
+}
+
+#[test]
+fn reject_invalid_working_dir_candidates() {
+    let dir = temp_dir().to_string_lossy().into_owned();
+    let missing = format!("{dir}/tcrm_task_missing_candidate_{}", std::process::id());
+    let invalid = [
+        TaskConfig::new("echo").working_dir_candidates([&missing]),
+        TaskConfig::new("echo").working_dir_candidates(Vec::<String>::new()),
+        TaskConfig::new("echo")
+            .working_dir(&dir)
+            .working_dir_candidates([&dir]),
+        TaskConfig::new("echo")
+            .working_dir_candidates([&dir])
+            .temp_working_dir(true),
+    ];
+    for config in invalid {
+        assert!(
+            matches!(config.validate(), Err(TaskError::InvalidConfiguration(_))),
+            "{:?} should be rejected",
+            config.working_dir_candidates
+        );
+    }
+}
+
+#[test]
+fn reject_invalid_temp_working_dir() {
+    let invalid = [
+        TaskConfig::new("echo")
+            .working_dir(temp_dir().to_string_lossy())
+            .temp_working_dir(true),
+        TaskConfig::new("echo").temp_working_dir_prefix(""),
+        TaskConfig::new("echo").temp_working_dir_prefix("x".repeat(65)),
+        TaskConfig::new("echo").temp_working_dir_prefix("../escape-"),
+        TaskConfig::new("echo").temp_working_dir_prefix("a\\b"),
+    ];
+    for config in invalid {
+        assert!(
+            matches!(config.validate(), Err(TaskError::InvalidConfiguration(_))),
+            "{:?} should be rejected",
+            config.temp_working_dir
+        );
+    }
+    let config = TaskConfig::new("echo")
+        .temp_working_dir_prefix("build-")
+        .keep_temp_dir_on_failure(true);
+    assert!(config.validate().is_ok());
+    assert!(
+        TaskConfig::new("echo")
+            .temp_working_dir(true)
+            .temp_working_dir(false)
+            .temp_working_dir
+            .is_none()
+    );
+}
+
+#[test]
+fn accept_output_buffer_bytes_in_range() {
+    for bytes in [256, 8 * 1024, 4 * 1024 * 1024] {
+        let config = TaskConfig::new("echo").output_buffer_bytes(bytes);
+        assert!(config.validate().is_ok(), "{bytes} bytes");
+    }
+}
+
+#[test]
+fn reject_output_buffer_bytes_out_of_range() {
+    for bytes in [0, 255, 4 * 1024 * 1024 + 1] {
+        let config = TaskConfig::new("echo").output_buffer_bytes(bytes);
+        assert!(
+            matches!(config.validate(), Err(TaskError::InvalidConfiguration(_))),
+            "{bytes} bytes"
+        );
+    }
+}
+
+#[test]
+fn max_output_line_bytes_must_be_positive() {
+    let config = TaskConfig::new("echo").max_output_line_bytes(0);
+    assert!(matches!(
+        config.validate(),
+        Err(TaskError::InvalidConfiguration(_))
+    ));
+    for bytes in [1, 1024 * 1024, usize::MAX] {
+        let config = TaskConfig::new("echo").max_output_line_bytes(bytes);
+        assert!(config.validate().is_ok(), "{bytes} bytes");
+    }
+}
+
+#[test]
+fn parse_json_lines_requires_serde() {
+    let config = TaskConfig::new("echo").parse_json_lines(true);
+    if cfg!(feature = "serde") {
+        assert!(config.validate().is_ok());
+    } else {
+        assert!(matches!(
+            config.validate(),
+            Err(TaskError::InvalidConfiguration(_))
+        ));
+    }
+}
+
+#[test]
+fn reject_zero_expected_duration() {
+    let config = TaskConfig::new("echo").expected_duration_ms(0);
+    assert!(matches!(
+        config.validate(),
+        Err(TaskError::InvalidConfiguration(_))
+    ));
+}
+
+#[test]
+fn reject_invalid_running_long_multiplier() {
+    for multiplier in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        let config = TaskConfig::new("echo")
+            .expected_duration_ms(1000)
+            .running_long_multiplier(multiplier);
+        assert!(
+            matches!(config.validate(), Err(TaskError::InvalidConfiguration(_))),
+            "multiplier {multiplier} should be rejected"
+        );
+    }
+}
+
+#[test]
+fn reject_invalid_exit_code_meanings() {
+    let config = TaskConfig::new("echo").exit_code_meanings([(2, "  ")]);
+    assert!(matches!(
+        config.validate(),
+        Err(TaskError::InvalidConfiguration(_))
+    ));
+
+    let config = TaskConfig::new("echo").exit_code_meanings([(2, "x".repeat(1025))]);
+    assert!(matches!(
+        config.validate(),
+        Err(TaskError::InvalidConfiguration(_))
+    ));
+
+    let config = TaskConfig::new("echo").exit_code_meanings((0..257).map(|code| (code, "meaning")));
+    assert!(matches!(
+        config.validate(),
+        Err(TaskError::InvalidConfiguration(_))
+    ));
+
+    let config = TaskConfig::new("echo").exit_code_meanings([(2, "usage error")]);
+    assert!(config.validate().is_ok());
+    assert_eq!(config.exit_code_meaning(2), Some("usage error"));
+    assert_eq!(config.exit_code_meaning(1), None);
+}
+
+#[test]
+fn reject_invalid_output_dedupe() {
+    for (window_ms, max_repeats) in [(0, 3), (1000, 0)] {
+        let config = TaskConfig::new("echo").dedupe_output(window_ms, max_repeats);
+        assert!(
+            matches!(config.validate(), Err(TaskError::InvalidConfiguration(_))),
+            "dedupe ({window_ms}, {max_repeats}) should be rejected"
+        );
+    }
+    assert!(
+        TaskConfig::new("echo")
+            .dedupe_output(1000, 1)
+            .validate()
+            .is_ok()
+    );
+}
+
+#[test]
+fn reject_invalid_spawn_retry() {
+    use std::io::ErrorKind;
+
+    for (attempts, backoff_ms, retry_on) in [
+        (0, 100, vec![ErrorKind::PermissionDenied]),
+        (11, 100, vec![ErrorKind::PermissionDenied]),
+        (3, 60_001, vec![ErrorKind::PermissionDenied]),
+        (3, 100, vec![]),
+    ] {
+        let config = TaskConfig::new("echo").spawn_retry(attempts, backoff_ms, retry_on.clone());
+        assert!(
+            matches!(config.validate(), Err(TaskError::InvalidConfiguration(_))),
+            "spawn retry ({attempts}, {backoff_ms}, {retry_on:?}) should be rejected"
+        );
+    }
+    assert!(
+        TaskConfig::new("echo")
+            .spawn_retry(10, 60_000, [ErrorKind::PermissionDenied])
+            .validate()
+            .is_ok()
+    );
+}
+
+#[test]
+fn reject_invalid_memory_watermark() {
+    let config = TaskConfig::new("echo").memory_watermark_bytes(0);
+    assert!(matches!(
+        config.validate(),
+        Err(TaskError::InvalidConfiguration(_))
+    ));
+    let config = TaskConfig::new("echo")
+        .memory_watermark_bytes(1 << 20)
+        .sample_interval_ms(9);
+    assert!(matches!(
+        config.validate(),
+        Err(TaskError::InvalidConfiguration(_))
+    ));
+    assert!(
+        TaskConfig::new("echo")
+            .memory_watermark_bytes(1 << 20)
+            .sample_interval_ms(10)
+            .validate()
+            .is_ok()
+    );
+}
+
+#[test]
+fn reject_zero_yield_every_n_lines() {
+    let config = TaskConfig::new("echo").yield_every_n_lines(0);
+    assert!(matches!(
+        config.validate(),
+        Err(TaskError::InvalidConfiguration(_))
+    ));
+    assert!(
+        TaskConfig::new("echo")
+            .yield_every_n_lines(1)
+            .validate()
+            .is_ok()
+    );
+}
+
+#[test]
+fn reject_zero_output_events_per_sec() {
+    let config = TaskConfig::new("echo").max_output_events_per_sec(0);
+    assert!(matches!(
+        config.validate(),
+        Err(TaskError::InvalidConfiguration(_))
+    ));
+    assert!(
+        TaskConfig::new("echo")
+            .max_output_events_per_sec(1)
+            .validate()
+            .is_ok()
+    );
+}
+
+#[test]
+fn reject_zero_slow_consumer_threshold() {
+    let config = TaskConfig::new("echo").slow_consumer_threshold_ms(0);
+    assert!(matches!(
+        config.validate(),
+        Err(TaskError::InvalidConfiguration(_))
+    ));
+    assert!(
+        TaskConfig::new("echo")
+            .slow_consumer_threshold_ms(1)
+            .validate()
+            .is_ok()
+    );
+}
+
+#[test]
+fn reject_too_many_startup_capture_lines() {
+    let config = TaskConfig::new("echo").startup_capture_lines(1001);
+    assert!(matches!(
+        config.validate(),
+        Err(TaskError::InvalidConfiguration(_))
+    ));
+    for lines in [0, 1000] {
+        assert!(
+            TaskConfig::new("echo")
+                .startup_capture_lines(lines)
+                .validate()
+                .is_ok()
+        );
+    }
+}
+
+#[test]
+fn reject_too_many_retained_output_lines() {
+    let config = TaskConfig::new("echo").retain_output_lines(10_001);
+    assert!(matches!(
+        config.validate(),
+        Err(TaskError::InvalidConfiguration(_))
+    ));
+    for lines in [0, 10_000] {
+        assert!(
+            TaskConfig::new("echo")
+                .retain_output_lines(lines)
+                .validate()
+                .is_ok()
+        );
+    }
+}
+
+#[test]
+fn reject_invalid_output_triggers() {
+    let invalid = [
+        OutputTrigger::new("", StreamSource::Stdout, TriggerAction::Ready),
+        OutputTrigger::new(
+            "x".repeat(1025),
+            StreamSource::Stdout,
+            TriggerAction::FailTask,
+        ),
+        OutputTrigger::new(
+            "reloaded",
+            StreamSource::Stdout,
+            TriggerAction::EmitEvent(String::new()),
+        ),
+    ];
+    for trigger in invalid {
+        let config = TaskConfig::new("echo").output_trigger(trigger.clone());
+        assert!(
+            matches!(config.validate(), Err(TaskError::InvalidConfiguration(_))),
+            "{trigger:?} should be rejected"
+        );
+    }
+
+    let config = (0..65).fold(TaskConfig::new("echo"), |config, i| {
+        config.output_trigger(OutputTrigger::new(
+            format!("pattern {i}"),
+            StreamSource::Stdout,
+            TriggerAction::FailTask,
+        ))
+    });
+    assert!(matches!(
+        config.validate(),
+        Err(TaskError::InvalidConfiguration(_))
+    ));
+
+    // The watched stream must be captured
+    let config = TaskConfig::new("echo")
+        .capture_stderr(false)
+        .output_trigger(OutputTrigger::new(
+            "FATAL",
+            StreamSource::Stderr,
+            TriggerAction::FailTask,
+        ));
+    assert!(matches!(
+        config.validate(),
+        Err(TaskError::InvalidConfiguration(_))
+    ));
+
+    let config = TaskConfig::new("echo").output_trigger(OutputTrigger::new(
+        "reloaded",
+        StreamSource::Stdout,
+        TriggerAction::EmitEvent("reload".to_string()),
+    ));
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn reject_invalid_stdin_script() {
+    let script = |steps: Vec<StdinScriptStep>| {
+        TaskConfig::new("cat")
+            .enable_stdin(true)
+            .stdin_script(StdinScript::new(steps))
+    };
+    let invalid = [
+        script(vec![]),
+        script(
+            (0..65)
+                .map(|i| StdinScriptStep::new(format!("prompt {i}"), "y", 1000))
+                .collect(),
+        ),
+        script(vec![StdinScriptStep::new("", "y", 1000)]),
+        script(vec![StdinScriptStep::new("x".repeat(1025), "y", 1000)]),
+        script(vec![StdinScriptStep::new("Continue?", "y", 0)]),
+        // The watched stream must be captured
+        script(vec![
+            StdinScriptStep::new("Password:", "secret", 1000).source(StreamSource::Stderr),
+        ])
+        .capture_stderr(false),
+        // The script writes to stdin
+        script(vec![StdinScriptStep::new("Continue?", "y", 1000)]).enable_stdin(false),
+    ];
+    for config in invalid {
+        assert!(
+            matches!(config.validate(), Err(TaskError::InvalidConfiguration(_))),
+            "{:?} should be rejected",
+            config.stdin_script
+        );
+    }
+
+    let config = script(vec![
+        StdinScriptStep::new("Username:", "admin", 1000),
+        StdinScriptStep::new("Password:", "secret", 1000).source(StreamSource::Stderr),
+    ]);
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn reject_invalid_job_object_name() {
+    for name in [String::new(), "job\0name".to_string(), "j".repeat(261)] {
+        let config = TaskConfig::new("echo").job_object_name(name.clone());
+        assert!(
+            matches!(config.validate(), Err(TaskError::InvalidConfiguration(_))),
+            "job object name {name:?} should be rejected"
+        );
+    }
+    for name in ["Local\\tcrm-build", &"j".repeat(260)] {
+        assert!(
+            TaskConfig::new("echo")
+                .job_object_name(name)
+                .validate()
+                .is_ok()
+        );
+    }
+}
+
+#[test]
+fn graceful_shutdown_period() {
+    let config = TaskConfig::new("echo").graceful_shutdown_ms(0);
+    assert!(matches!(
+        config.validate(),
+        Err(TaskError::InvalidConfiguration(_))
+    ));
+
+    let config = TaskConfig::new("echo").graceful_shutdown_ms(1000);
+    assert!(config.validate().is_ok());
+    let config = config.use_process_group(false);
+    if cfg!(windows) {
+        assert!(matches!(
+            config.validate(),
+            Err(TaskError::InvalidConfiguration(_))
+        ));
+    } else {
+        assert!(config.validate().is_ok());
+    }
+}
+
+#[test]
+fn inherited_env_var_names() {
+    let config = TaskConfig::new("env")
+        .clear_env(true)
+        .inherit_env_vars(["PATH", "HOME"]);
+    assert!(config.validate().is_ok());
+
+    for name in ["", "KEY WITH SPACE", "A=B", "NUL\0"] {
+        let config = TaskConfig::new("env")
+            .clear_env(true)
+            .inherit_env_vars([name]);
+        assert!(
+            matches!(config.validate(), Err(TaskError::InvalidConfiguration(_))),
+            "{name:?}"
+        );
+    }
+
+    // An allowlist without a cleared environment would have no effect
+    let config = TaskConfig::new("env").inherit_env_vars(["PATH"]);
+    assert!(matches!(
+        config.validate(),
+        Err(TaskError::InvalidConfiguration(_))
+    ));
+}
@@ -0,0 +1,151 @@
+#[cfg(feature = "tokio")]
+use tokio::sync::mpsc;
+
+#[cfg(feature = "tokio")]
+use crate::tasks::{
+    async_tokio::spawner::TaskSpawner,
+    config::{StdinScript, StdinScriptStep},
+};
+use crate::tasks::{
+    config::{OutputTrigger, StreamSource, TaskConfig, TriggerAction},
+    error::TaskError,
+    validator::{ConfigValidator, ValidationWarning},
+};
+
+/// Warnings other than inherited environment variables, which depend on the test environment
+fn config_warnings(config: &TaskConfig) -> Vec<ValidationWarning> {
+    let (_, warnings) = config.validate_with_warnings();
+    warnings
+        .into_iter()
+        .filter(|warning| !matches!(warning, ValidationWarning::InheritedRiskyEnv { .. }))
+        .collect()
+}
+
+#[test]
+fn inherited_env_matching_pattern_is_reported() {
+    // PATH is inherited in every test environment
+    let config = TaskConfig::new("echo").risky_env_patterns(["PAT*"]);
+
+    let (result, warnings) = config.validate_with_warnings();
+
+    assert!(result.is_ok());
+    assert!(
+        warnings.iter().any(|warning| matches!(
+            warning,
+            ValidationWarning::InheritedRiskyEnv { key, pattern }
+                if key.eq_ignore_ascii_case("PATH") && pattern == "PAT*"
+        )),
+        "{warnings:?}"
+    );
+}
+
+#[test]
+fn overridden_env_is_not_inherited() {
+    let config = TaskConfig::new("echo")
+        .risky_env_patterns(["PATH"])
+        .env([("PATH", "/usr/bin")]);
+
+    let (_, warnings) = config.validate_with_warnings();
+
+    assert_eq!(warnings, []);
+}
+
+#[test]
+fn cleared_env_is_not_inherited() {
+    let config = TaskConfig::new("echo")
+        .risky_env_patterns(["PATH"])
+        .clear_env(true);
+    let (_, warnings) = config.validate_with_warnings();
+    assert_eq!(warnings, []);
+
+    let (_, warnings) = config.inherit_env_vars(["PATH"]).validate_with_warnings();
+    assert_eq!(warnings.len(), 1, "{warnings:?}");
+}
+
+#[test]
+fn blocking_command_without_timeout() {
+    let config = TaskConfig::new("tail").args(["-f", "app.log"]);
+    assert_eq!(
+        config_warnings(&config),
+        [ValidationWarning::MissingTimeout {
+            command: "tail".to_string(),
+        }]
+    );
+
+    assert_eq!(config_warnings(&config.clone().timeout_ms(60_000)), []);
+    assert_eq!(
+        config_warnings(&TaskConfig::new("tail").args(["app.log"])),
+        []
+    );
+    assert!(ConfigValidator::is_blocking_command("/usr/bin/yes", &[]));
+    assert!(!ConfigValidator::is_blocking_command("echo", &[]));
+}
+
+#[test]
+fn ready_indicator_without_timeout() {
+    let legacy = TaskConfig::new("my-server").ready_indicator("listening");
+    let trigger = TaskConfig::new("my-server").output_trigger(OutputTrigger::new(
+        "listening",
+        StreamSource::Stderr,
+        TriggerAction::Ready,
+    ));
+
+    for config in [legacy, trigger] {
+        assert_eq!(
+            config_warnings(&config),
+            [ValidationWarning::ReadyIndicatorWithoutTimeout]
+        );
+        assert_eq!(config_warnings(&config.timeout_ms(30_000)), []);
+    }
+}
+
+#[test]
+fn warnings_are_reported_for_invalid_config() {
+    let config = TaskConfig::new("my-server").ready_indicator("");
+
+    let (result, warnings) = config.validate_with_warnings();
+
+    assert!(matches!(result, Err(TaskError::InvalidConfiguration(_))));
+    assert!(warnings.contains(&ValidationWarning::ReadyIndicatorWithoutTimeout));
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn stdin_without_source() {
+    let config = TaskConfig::new("cat").enable_stdin(true);
+    let spawner = TaskSpawner::new("stdin".to_string(), config.clone());
+    let (result, warnings) = spawner.validate_with_warnings();
+    assert!(result.is_ok());
+    assert!(warnings.contains(&ValidationWarning::StdinWithoutSource));
+
+    let (_tx, rx) = mpsc::channel(1);
+    let spawner = TaskSpawner::new("stdin".to_string(), config.clone()).set_stdin(rx);
+    let (_, warnings) = spawner.validate_with_warnings();
+    assert!(!warnings.contains(&ValidationWarning::StdinWithoutSource));
+
+    let config = config.stdin_script(StdinScript::new([StdinScriptStep::new(">", "quit", 1000)]));
+    let spawner = TaskSpawner::new("stdin".to_string(), config);
+    let (_, warnings) = spawner.validate_with_warnings();
+    assert!(!warnings.contains(&ValidationWarning::StdinWithoutSource));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn warnings_roundtrip_through_json() {
+    let warnings = vec![
+        ValidationWarning::InheritedRiskyEnv {
+            key: "HTTP_PROXY".to_string(),
+            pattern: "*_PROXY".to_string(),
+        },
+        ValidationWarning::MissingTimeout {
+            command: "tail".to_string(),
+        },
+        ValidationWarning::ReadyIndicatorWithoutTimeout,
+        ValidationWarning::StdinWithoutSource,
+    ];
+
+    let json = serde_json::to_string(&warnings).unwrap();
+    let parsed: Vec<ValidationWarning> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(parsed, warnings);
+}
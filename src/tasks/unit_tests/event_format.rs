@@ -0,0 +1,311 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::tasks::{
+    config::StreamSource,
+    error::TaskError,
+    event::{TaskEvent, TaskEventStopReason, TaskTerminateReason, format::LogFormatter},
+};
+
+fn output(line: &str, src: StreamSource, seq: u64) -> TaskEvent {
+    TaskEvent::Output {
+        task_name: "build".into(),
+        line: line.to_string(),
+        src,
+        seq,
+        truncated: false,
+        timestamp: UNIX_EPOCH,
+    }
+}
+
+fn stopped(reason: TaskEventStopReason, exit_code: Option<i32>) -> TaskEvent {
+    TaskEvent::Stopped {
+        task_name: "build".into(),
+        exit_code,
+        signal: None,
+        exit_meaning: None,
+        reason,
+        was_ready: false,
+        duration_ms: 1500,
+        expected_duration_ms: None,
+        from_cache: false,
+        stdout_sha256: None,
+        stderr_sha256: None,
+    }
+}
+
+#[test]
+fn representative_events() {
+    let events = [
+        TaskEvent::Started {
+            task_name: "build".into(),
+            process_id: 4242,
+            temp_dir: None,
+            working_dir: None,
+        },
+        output("Compiling tcrm-task", StreamSource::Stdout, 0),
+        output("warning: unused variable", StreamSource::Stderr, 1),
+        TaskEvent::OutputChunk {
+            task_name: "build".into(),
+            data: vec![0x7f, b'E', b'L', b'F'],
+            src: StreamSource::Stdout,
+        },
+        TaskEvent::OutputRepeated {
+            task_name: "build".into(),
+            line: "retrying".to_string(),
+            src: StreamSource::Stdout,
+            count: 12,
+        },
+        TaskEvent::OutputSuppressed {
+            task_name: "build".into(),
+            src: StreamSource::Stderr,
+            count: 300,
+            bytes: 24_000,
+        },
+        TaskEvent::Triggered {
+            task_name: "build".into(),
+            name: "reload".to_string(),
+            line: "Reloaded config".to_string(),
+            src: StreamSource::Stdout,
+        },
+        TaskEvent::Ready {
+            task_name: "build".into(),
+        },
+        TaskEvent::RunningLong {
+            task_name: "build".into(),
+            expected_ms: 1000,
+            elapsed_ms: 2000,
+        },
+        TaskEvent::MemoryWatermarkExceeded {
+            task_name: "build".into(),
+            rss_bytes: 3 << 20,
+            watermark_bytes: 2 << 20,
+        },
+        TaskEvent::SpawnRetry {
+            task_name: "build".into(),
+            attempt: 1,
+            error: "Access is denied. (os error 5)".to_string(),
+        },
+        TaskEvent::ScriptStep {
+            task_name: "build".into(),
+            index: 1,
+        },
+        TaskEvent::Unhealthy {
+            task_name: "build".into(),
+            consecutive_failures: 3,
+        },
+        TaskEvent::SlowConsumer {
+            task_name: "build".into(),
+            avg_wait_ms: 25,
+        },
+        stopped(TaskEventStopReason::Finished, Some(0)),
+        stopped(
+            TaskEventStopReason::Terminated(TaskTerminateReason::Timeout),
+            None,
+        ),
+        TaskEvent::Error {
+            task_name: "build".into(),
+            error: TaskError::IO("No such file or directory".to_string()),
+        },
+    ];
+
+    let lines: Vec<String> = events
+        .iter()
+        .map(|event| LogFormatter::new().format(event))
+        .collect();
+    assert_eq!(
+        lines,
+        [
+            "[build] started: process 4242",
+            "[build] stdout: Compiling tcrm-task",
+            "[build] stderr: warning: unused variable",
+            "[build] stdout: 4 bytes",
+            "[build] stdout: last line repeated 12 more times: retrying",
+            "[build] stderr: 300 lines (24000 bytes) suppressed",
+            "[build] triggered: reload: Reloaded config",
+            "[build] ready",
+            "[build] running long: 2000 ms, expected 1000 ms",
+            "[build] memory: 3145728 bytes above watermark of 2097152 bytes",
+            "[build] spawn retry: attempt 1 failed: Access is denied. (os error 5)",
+            "[build] script: step 1 answered",
+            "[build] unhealthy: 3 health probes failed in a row",
+            "[build] slow consumer: events waited 25 ms on average to be sent",
+            "[build] stopped: finished, exit code 0 in 1500 ms",
+            "[build] stopped: terminated (Timeout) in 1500 ms",
+            "[build] error: IO error: No such file or directory",
+        ]
+    );
+}
+
+#[test]
+fn stopped_with_meaning_from_cache() {
+    let event = TaskEvent::Stopped {
+        task_name: "build".into(),
+        exit_code: Some(2),
+        signal: None,
+        exit_meaning: Some("usage error".to_string()),
+        reason: TaskEventStopReason::Finished,
+        was_ready: false,
+        duration_ms: 0,
+        expected_duration_ms: None,
+        from_cache: true,
+        stdout_sha256: None,
+        stderr_sha256: None,
+    };
+    assert_eq!(
+        LogFormatter::new().format(&event),
+        "[build] stopped: finished, exit code 2 (usage error) in 0 ms, from cache"
+    );
+}
+
+#[test]
+fn stopped_with_terminate_message() {
+    let reason = TaskTerminateReason::UserRequested {
+        message: Some("stopped by alice: redeploying".to_string()),
+    };
+    assert_eq!(
+        LogFormatter::new().format(&stopped(TaskEventStopReason::Terminated(reason), None)),
+        "[build] stopped: terminated (UserRequested: stopped by alice: redeploying) in 1500 ms"
+    );
+}
+
+#[test]
+fn timestamps_sequence_and_prefix() {
+    let formatter = LogFormatter::new()
+        .prefix("{task}@ci |")
+        .timestamps(true)
+        .sequence(true);
+    let at = UNIX_EPOCH + Duration::from_millis(1_792_143_000_005);
+
+    assert_eq!(
+        formatter.format_at(&output("Compiling", StreamSource::Stdout, 42), at),
+        "2026-10-16T09:30:00.005Z #42 build@ci | stdout: Compiling"
+    );
+    // Only output carries a sequence number
+    assert_eq!(
+        formatter.format_at(
+            &TaskEvent::Ready {
+                task_name: "build".into()
+            },
+            at
+        ),
+        "2026-10-16T09:30:00.005Z build@ci | ready"
+    );
+    // Leap day, and times before the epoch
+    let leap_day = UNIX_EPOCH + Duration::from_secs(951_782_400);
+    assert_eq!(
+        LogFormatter::new()
+            .prefix("")
+            .timestamps(true)
+            .format_at(&output("x", StreamSource::Stdout, 0), leap_day),
+        "2000-02-29T00:00:00.000Z stdout: x"
+    );
+    assert!(
+        LogFormatter::new()
+            .timestamps(true)
+            .format_at(
+                &output("x", StreamSource::Stdout, 0),
+                UNIX_EPOCH - Duration::from_secs(1)
+            )
+            .starts_with("1970-01-01T00:00:00.000Z ")
+    );
+}
+
+#[test]
+fn output_is_timestamped_when_read() {
+    let event = TaskEvent::Output {
+        task_name: "build".into(),
+        line: "Compiling".to_string(),
+        src: StreamSource::Stdout,
+        seq: 0,
+        truncated: false,
+        timestamp: UNIX_EPOCH + Duration::from_millis(1_792_143_000_005),
+    };
+    let formatter = LogFormatter::new().timestamps(true);
+    assert_eq!(
+        formatter.format(&event),
+        "2026-10-16T09:30:00.005Z [build] stdout: Compiling"
+    );
+}
+
+#[test]
+fn color_tags() {
+    let formatter = LogFormatter::new().color(true);
+
+    assert_eq!(
+        formatter.format(&output("fine", StreamSource::Stdout, 0)),
+        "[build] stdout: fine"
+    );
+    assert_eq!(
+        formatter.format(&output("bad", StreamSource::Stderr, 0)),
+        "[build] \x1b[31mstderr\x1b[0m: bad"
+    );
+    assert_eq!(
+        formatter.format(&stopped(TaskEventStopReason::Finished, Some(0))),
+        "[build] \x1b[32mstopped\x1b[0m: finished, exit code 0 in 1500 ms"
+    );
+    assert_eq!(
+        formatter.format(&stopped(TaskEventStopReason::Finished, Some(1))),
+        "[build] \x1b[31mstopped\x1b[0m: finished, exit code 1 in 1500 ms"
+    );
+    assert_eq!(
+        formatter.sequence(true).format(&TaskEvent::RunningLong {
+            task_name: "build".into(),
+            expected_ms: 1,
+            elapsed_ms: 2,
+        }),
+        "[build] \x1b[33mrunning long\x1b[0m: 2 ms, expected 1 ms"
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json_output() {
+    let event = TaskEvent::OutputJson {
+        task_name: "build".into(),
+        line: r#"{"reason":"build-finished"}"#.to_string(),
+        value: serde_json::json!({"reason": "build-finished"}),
+        src: StreamSource::Stdout,
+        seq: 3,
+        timestamp: UNIX_EPOCH + Duration::from_millis(1_792_143_000_005),
+    };
+    assert_eq!(
+        LogFormatter::new().sequence(true).format(&event),
+        "#3 [build] stdout: {\"reason\":\"build-finished\"}"
+    );
+    // Timestamped when read, like plain output
+    assert_eq!(
+        LogFormatter::new().timestamps(true).format(&event),
+        "2026-10-16T09:30:00.005Z [build] stdout: {\"reason\":\"build-finished\"}"
+    );
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn writer_writes_a_line_per_event() {
+    use tokio::sync::mpsc;
+
+    use crate::tasks::event::format::LogWriter;
+
+    let (tx, mut rx) = mpsc::channel(8);
+    tx.send(output("one", StreamSource::Stdout, 0))
+        .await
+        .unwrap();
+    tx.send(output("two", StreamSource::Stderr, 1))
+        .await
+        .unwrap();
+    drop(tx);
+
+    let mut writer = LogWriter::new(Vec::new(), LogFormatter::new().prefix(""));
+    writer.write_events(&mut rx).await.unwrap();
+    writer
+        .write_event(&TaskEvent::Ready {
+            task_name: "build".into(),
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(
+        String::from_utf8(writer.into_inner()).unwrap(),
+        "stdout: one\nstderr: two\nready\n"
+    );
+}
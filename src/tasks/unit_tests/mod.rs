@@ -1,2 +1,4 @@
 mod config;
+mod event_format;
+mod state;
 mod validator;
@@ -0,0 +1,66 @@
+use crate::tasks::state::TaskState;
+
+const ALL_STATES: [TaskState; 5] = [
+    TaskState::Pending,
+    TaskState::Initiating,
+    TaskState::Running,
+    TaskState::Ready,
+    TaskState::Finished,
+];
+
+const LEGAL_TRANSITIONS: [(TaskState, TaskState); 6] = [
+    (TaskState::Pending, TaskState::Initiating),
+    (TaskState::Initiating, TaskState::Running),
+    (TaskState::Initiating, TaskState::Finished),
+    (TaskState::Running, TaskState::Ready),
+    (TaskState::Running, TaskState::Finished),
+    (TaskState::Ready, TaskState::Finished),
+];
+
+#[test]
+fn legal_transitions_are_allowed() {
+    for (from, to) in &LEGAL_TRANSITIONS {
+        assert!(
+            from.can_transition_to(to),
+            "{from:?} -> {to:?} should be legal"
+        );
+    }
+}
+
+#[test]
+fn same_state_is_rejected() {
+    for state in &ALL_STATES {
+        assert!(
+            !state.can_transition_to(state),
+            "{state:?} -> {state:?} should be illegal"
+        );
+    }
+}
+
+#[test]
+fn second_start_and_second_finish_are_rejected() {
+    assert!(!TaskState::Initiating.can_transition_to(&TaskState::Initiating));
+    assert!(!TaskState::Finished.can_transition_to(&TaskState::Finished));
+}
+
+#[test]
+fn every_other_transition_is_rejected() {
+    for from in &ALL_STATES {
+        for to in &ALL_STATES {
+            if LEGAL_TRANSITIONS.contains(&(from.clone(), to.clone())) {
+                continue;
+            }
+            assert!(
+                !from.can_transition_to(to),
+                "{from:?} -> {to:?} should be illegal"
+            );
+        }
+    }
+}
+
+#[test]
+fn finished_is_terminal() {
+    for to in [TaskState::Pending, TaskState::Running, TaskState::Ready] {
+        assert!(!TaskState::Finished.can_transition_to(&to));
+    }
+}
@@ -26,3 +26,35 @@ fn accepts_normal_args() {
 
     assert!(ConfigValidator::validate_args(&normal_args).is_ok());
 }
+
+#[test]
+fn redacts_values_of_sensitive_options() {
+    let args = [
+        "--password",
+        "hunter2",
+        "--client-secret=abc",
+        "GITHUB_TOKEN=ghp_123",
+        "--auth",
+        "--verbose",
+        "--user=admin",
+        "--output",
+        "out.txt",
+        "=value",
+    ];
+
+    assert_eq!(
+        ConfigValidator::redact_args(&args),
+        [
+            "--password",
+            "[REDACTED]",
+            "--client-secret=[REDACTED]",
+            "GITHUB_TOKEN=[REDACTED]",
+            "--auth",
+            "--verbose",
+            "--user=admin",
+            "--output",
+            "out.txt",
+            "=value",
+        ]
+    );
+}
@@ -1,4 +1,4 @@
-use crate::tasks::validator::ConfigValidator;
+use crate::tasks::validator::{ConfigValidator, REDACTED_ENV_VALUE};
 use std::collections::HashMap;
 
 #[test]
@@ -94,3 +94,52 @@ fn rejects_null_chars() {
     env.insert("KEY".to_string(), "value\0with\0nulls".to_string());
     assert!(ConfigValidator::validate_env_vars(&env).is_err());
 }
+
+#[test]
+fn detects_sensitive_keys() {
+    for key in [
+        "GITHUB_TOKEN",
+        "db_password",
+        "AWS_SECRET_ACCESS_KEY",
+        "Authorization",
+    ] {
+        assert!(ConfigValidator::is_sensitive_env_key(key), "{key}");
+    }
+    for key in ["PATH", "HOME", "RUST_LOG"] {
+        assert!(!ConfigValidator::is_sensitive_env_key(key), "{key}");
+    }
+}
+
+#[test]
+fn redacts_only_sensitive_values() {
+    let mut env = HashMap::new();
+    env.insert("API_KEY".to_string(), "abc123".to_string());
+    env.insert("PATH".to_string(), "/usr/bin".to_string());
+
+    let redacted = ConfigValidator::redact_env(&env);
+    assert_eq!(redacted["API_KEY"], REDACTED_ENV_VALUE);
+    assert_eq!(redacted["PATH"], "/usr/bin");
+}
+
+#[test]
+fn env_patterns_match_ignoring_case_with_wildcards() {
+    assert!(ConfigValidator::matches_env_pattern(
+        "HTTP_PROXY",
+        "*_PROXY"
+    ));
+    assert!(ConfigValidator::matches_env_pattern("no_proxy", "*_PROXY"));
+    assert!(ConfigValidator::matches_env_pattern(
+        "DYLD_INSERT_LIBRARIES",
+        "DYLD_*"
+    ));
+    assert!(ConfigValidator::matches_env_pattern(
+        "JAVA_TOOL_OPTIONS",
+        "*TOOL*"
+    ));
+    assert!(!ConfigValidator::matches_env_pattern("PROXY", "*_PROXY"));
+    assert!(!ConfigValidator::matches_env_pattern(
+        "LD_LIBRARY_PATH_X",
+        "LD_LIBRARY_PATH"
+    ));
+    assert!(!ConfigValidator::matches_env_pattern("ABA", "AB*BA"));
+}
@@ -1,12 +1,118 @@
 use std::collections::HashMap;
 use std::path::Path;
 
+use thiserror::Error;
+
+use crate::tasks::config::{
+    OutputDedupe, OutputEncoding, OutputTrigger, SpawnRetry, StdinScript, TaskConfig, TriggerAction,
+};
 use crate::tasks::error::TaskError;
 const MAX_COMMAND_LEN: usize = 4096;
 const MAX_ARG_LEN: usize = 4096;
 const MAX_WORKING_DIR_LEN: usize = 4096;
 const MAX_ENV_KEY_LEN: usize = 1024;
 const MAX_ENV_VALUE_LEN: usize = 4096;
+const MIN_OUTPUT_BUFFER_BYTES: usize = 256;
+const MAX_OUTPUT_BUFFER_BYTES: usize = 4 * 1024 * 1024;
+const MAX_EXIT_CODE_MEANINGS: usize = 256;
+const MAX_EXIT_CODE_MEANING_LEN: usize = 1024;
+const MAX_STARTUP_CAPTURE_LINES: usize = 1000;
+const MAX_RETAINED_OUTPUT_LINES: usize = 10_000;
+const MAX_OUTPUT_TRIGGERS: usize = 64;
+const MAX_TRIGGER_PATTERN_LEN: usize = 1024;
+const MAX_STDIN_SCRIPT_STEPS: usize = 64;
+/// `MAX_PATH`, the longest name of a Windows kernel object
+const MAX_JOB_OBJECT_NAME_LEN: usize = 260;
+const MAX_TEMP_DIR_PREFIX_LEN: usize = 64;
+/// Cores a CPU affinity mask can name: `CPU_SETSIZE` on Linux
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const MAX_AFFINITY_CORES: usize = libc::CPU_SETSIZE as usize;
+/// Cores a CPU affinity mask can name: the bits of the process affinity mask on Windows
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+const MAX_AFFINITY_CORES: usize = usize::BITS as usize;
+const MIN_SAMPLE_INTERVAL_MS: u64 = 10;
+const MAX_SPAWN_ATTEMPTS: u32 = 10;
+const MAX_SPAWN_BACKOFF_MS: u64 = 60_000;
+/// Substrings marking an environment variable as sensitive (matched case-insensitively)
+const SENSITIVE_ENV_KEY_MARKERS: &[&str] = &[
+    "PASSWORD",
+    "PASSWD",
+    "SECRET",
+    "TOKEN",
+    "API_KEY",
+    "APIKEY",
+    "PRIVATE_KEY",
+    "CREDENTIAL",
+    "AUTH",
+];
+/// Replacement for the values of sensitive environment variables
+pub const REDACTED_ENV_VALUE: &str = "[REDACTED]";
+/// Inherited environment variables reported by `TaskConfig::validate_with_warnings`
+///
+/// Patterns are matched ignoring case, with `*` matching any run of characters.
+pub const DEFAULT_RISKY_ENV_PATTERNS: &[&str] = &[
+    "LD_PRELOAD",
+    "LD_LIBRARY_PATH",
+    "DYLD_*",
+    "*_PROXY",
+    "PYTHONPATH",
+    "PYTHONHOME",
+    "NODE_OPTIONS",
+    "PERL5OPT",
+    "RUBYOPT",
+];
+/// Commands that run until they are stopped, whatever their arguments
+const BLOCKING_COMMANDS: &[&str] = &["top", "htop", "watch", "yes", "nc", "netcat"];
+
+/// A setting that is valid but likely a mistake
+///
+/// Reported by `TaskConfig::validate_with_warnings` without failing validation,
+/// so a frontend can show it before launching the task.
+///
+/// # Examples
+/// ```rust
+/// use tcrm_task::tasks::{config::TaskConfig, validator::ValidationWarning};
+///
+/// let (result, warnings) = TaskConfig::new("my-server")
+///     .ready_indicator("listening")
+///     .validate_with_warnings();
+/// assert!(result.is_ok());
+/// assert!(warnings.contains(&ValidationWarning::ReadyIndicatorWithoutTimeout));
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ValidationWarning {
+    /// An inherited environment variable matches a risky pattern
+    ///
+    /// The value is not included, as it may hold a secret.
+    #[error("inherited environment variable {key} matches risky pattern {pattern}")]
+    InheritedRiskyEnv {
+        /// Name of the inherited variable
+        key: String,
+        /// Pattern it matched
+        pattern: String,
+    },
+
+    /// The command usually runs until it is stopped, but no timeout is set
+    #[error("{command} usually runs until stopped, but no timeout is set")]
+    MissingTimeout {
+        /// The configured command
+        command: String,
+    },
+
+    /// A ready indicator is set without a timeout
+    ///
+    /// A task that never prints its ready indicator then runs forever.
+    #[error("ready indicator is set without a timeout")]
+    ReadyIndicatorWithoutTimeout,
+
+    /// stdin is enabled but no stdin source was set
+    ///
+    /// Only known once the task is set up, so reported by
+    /// `TaskSpawner::validate_with_warnings`.
+    #[error("stdin is enabled but no stdin source was set")]
+    StdinWithoutSource,
+}
 /// Security validation utilities for task configuration
 pub struct ConfigValidator;
 
@@ -222,29 +328,7 @@ impl ConfigValidator {
     /// ```
     pub fn validate_env_vars(env: &HashMap<String, String>) -> Result<(), TaskError> {
         for (key, value) in env {
-            // Validate key
-            if key.trim().is_empty() {
-                return Err(TaskError::InvalidConfiguration(
-                    "Environment variable key cannot be empty".to_string(),
-                ));
-            }
-            if key.contains('=') || key.contains('\0') || key.contains('\t') || key.contains('\n') {
-                return Err(TaskError::InvalidConfiguration(
-                    "Environment variable key contains invalid characters".to_string(),
-                ));
-            }
-
-            if key.contains(' ') {
-                return Err(TaskError::InvalidConfiguration(format!(
-                    "Environment variable key '{key}' cannot contain spaces"
-                )));
-            }
-
-            if key.len() > MAX_ENV_KEY_LEN {
-                return Err(TaskError::InvalidConfiguration(format!(
-                    "Environment variable key '{key}' exceeds maximum length"
-                )));
-            }
+            Self::validate_env_key(key)?;
 
             // Validate value
             if value.contains('\0') {
@@ -267,6 +351,203 @@ impl ConfigValidator {
         Ok(())
     }
 
+    /// Validates the names of environment variables inherited when the environment is cleared
+    ///
+    /// Names follow the same rules as the keys checked by
+    /// [`validate_env_vars`](Self::validate_env_vars).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TaskError::InvalidConfiguration`] if any name is invalid.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::validator::ConfigValidator;
+    ///
+    /// assert!(ConfigValidator::validate_env_var_names(&["PATH".to_string()]).is_ok());
+    /// assert!(ConfigValidator::validate_env_var_names(&["A=B".to_string()]).is_err());
+    /// ```
+    pub fn validate_env_var_names(names: &[String]) -> Result<(), TaskError> {
+        for name in names {
+            Self::validate_env_key(name)?;
+        }
+        Ok(())
+    }
+
+    /// Validates an environment variable name
+    fn validate_env_key(key: &str) -> Result<(), TaskError> {
+        if key.trim().is_empty() {
+            return Err(TaskError::InvalidConfiguration(
+                "Environment variable key cannot be empty".to_string(),
+            ));
+        }
+        if key.contains('=') || key.contains('\0') || key.contains('\t') || key.contains('\n') {
+            return Err(TaskError::InvalidConfiguration(
+                "Environment variable key contains invalid characters".to_string(),
+            ));
+        }
+
+        if key.contains(' ') {
+            return Err(TaskError::InvalidConfiguration(format!(
+                "Environment variable key '{key}' cannot contain spaces"
+            )));
+        }
+
+        if key.len() > MAX_ENV_KEY_LEN {
+            return Err(TaskError::InvalidConfiguration(format!(
+                "Environment variable key '{key}' exceeds maximum length"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks whether an environment variable likely holds a secret
+    ///
+    /// A key is sensitive if it contains, ignoring case, any of `PASSWORD`,
+    /// `PASSWD`, `SECRET`, `TOKEN`, `API_KEY`, `APIKEY`, `PRIVATE_KEY`,
+    /// `CREDENTIAL` or `AUTH`. Values of such keys must not be emitted or logged.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::validator::ConfigValidator;
+    ///
+    /// assert!(ConfigValidator::is_sensitive_env_key("GITHUB_TOKEN"));
+    /// assert!(ConfigValidator::is_sensitive_env_key("db_password"));
+    /// assert!(!ConfigValidator::is_sensitive_env_key("PATH"));
+    /// ```
+    #[must_use]
+    pub fn is_sensitive_env_key(key: &str) -> bool {
+        let key = key.to_ascii_uppercase();
+        SENSITIVE_ENV_KEY_MARKERS
+            .iter()
+            .any(|marker| key.contains(marker))
+    }
+
+    /// Returns a copy of `env` with the values of sensitive keys replaced
+    ///
+    /// Values of keys matched by [`is_sensitive_env_key`](Self::is_sensitive_env_key)
+    /// become [`REDACTED_ENV_VALUE`]; all other entries are kept as they are.
+    #[must_use]
+    pub fn redact_env(env: &HashMap<String, String>) -> HashMap<String, String> {
+        env.iter()
+            .map(|(key, value)| {
+                let value = if Self::is_sensitive_env_key(key) {
+                    REDACTED_ENV_VALUE.to_string()
+                } else {
+                    value.clone()
+                };
+                (key.clone(), value)
+            })
+            .collect()
+    }
+
+    /// Returns a copy of `args` with the values of sensitive options replaced
+    ///
+    /// An option is sensitive if its name, with leading dashes removed and
+    /// `-` read as `_`, is matched by [`is_sensitive_env_key`](Self::is_sensitive_env_key).
+    /// The value of `--name=value` or `NAME=value` becomes [`REDACTED_ENV_VALUE`],
+    /// as does the argument following a sensitive `--name` unless it is an
+    /// option itself.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::validator::ConfigValidator;
+    ///
+    /// let args = ["--token", "abc", "--api-key=xyz", "DB_PASSWORD=hunter2", "-v", "file"];
+    /// assert_eq!(
+    ///     ConfigValidator::redact_args(&args),
+    ///     ["--token", "[REDACTED]", "--api-key=[REDACTED]", "DB_PASSWORD=[REDACTED]", "-v", "file"]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn redact_args<S: AsRef<str>>(args: &[S]) -> Vec<String> {
+        let is_sensitive = |name: &str| {
+            let name = name.trim_start_matches('-').replace('-', "_");
+            !name.is_empty() && Self::is_sensitive_env_key(&name)
+        };
+        let mut redact_next = false;
+        args.iter()
+            .map(|arg| {
+                let arg = arg.as_ref();
+                let is_option = arg.starts_with('-');
+                if std::mem::take(&mut redact_next) && !is_option {
+                    return REDACTED_ENV_VALUE.to_string();
+                }
+                if let Some((name, _)) = arg.split_once('=') {
+                    if is_sensitive(name) {
+                        return format!("{name}={REDACTED_ENV_VALUE}");
+                    }
+                } else if is_option {
+                    redact_next = is_sensitive(arg);
+                }
+                arg.to_string()
+            })
+            .collect()
+    }
+
+    /// Checks whether an environment variable name matches a risky env pattern
+    ///
+    /// Matching ignores case, and `*` in `pattern` matches any run of characters.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::validator::ConfigValidator;
+    ///
+    /// assert!(ConfigValidator::matches_env_pattern("https_proxy", "*_PROXY"));
+    /// assert!(ConfigValidator::matches_env_pattern("LD_PRELOAD", "LD_PRELOAD"));
+    /// assert!(!ConfigValidator::matches_env_pattern("LD_PRELOADED", "LD_PRELOAD"));
+    /// ```
+    #[must_use]
+    pub fn matches_env_pattern(key: &str, pattern: &str) -> bool {
+        let key = key.to_ascii_uppercase();
+        let pattern = pattern.to_ascii_uppercase();
+        let mut parts = pattern.split('*');
+        // `split` always yields at least one part
+        let first = parts.next().unwrap_or_default();
+        let Some(mut rest) = key.strip_prefix(first) else {
+            return false;
+        };
+        let mut parts: Vec<&str> = parts.collect();
+        let Some(last) = parts.pop() else {
+            // No `*`: the whole key must match
+            return rest.is_empty();
+        };
+        for part in parts {
+            match rest.find(part) {
+                Some(index) => rest = &rest[index + part.len()..],
+                None => return false,
+            }
+        }
+        rest.len() >= last.len() && rest.ends_with(last)
+    }
+
+    /// Checks whether a command usually runs until it is stopped
+    ///
+    /// Covers interactive monitors and generators such as `top`, `watch` and
+    /// `yes`, `tail` following a file, and `ping` without a count on Unix.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use tcrm_task::tasks::validator::ConfigValidator;
+    ///
+    /// assert!(ConfigValidator::is_blocking_command("tail", &["-f".to_string(), "app.log".to_string()]));
+    /// assert!(!ConfigValidator::is_blocking_command("tail", &["app.log".to_string()]));
+    /// ```
+    #[must_use]
+    pub fn is_blocking_command(command: &str, args: &[String]) -> bool {
+        let name = Path::new(command)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_ascii_lowercase())
+            .unwrap_or_default();
+        let has_arg = |flags: &[&str]| args.iter().any(|arg| flags.contains(&arg.as_str()));
+        match name.as_str() {
+            "tail" => has_arg(&["-f", "-F", "--follow"]),
+            "ping" if cfg!(windows) => has_arg(&["-t", "/t"]),
+            "ping" => !has_arg(&["-c", "-w"]),
+            name => BLOCKING_COMMANDS.contains(&name),
+        }
+    }
+
     pub fn validate_ready_indicator(indicator: &str) -> Result<(), TaskError> {
         if indicator.is_empty() {
             return Err(TaskError::InvalidConfiguration(
@@ -276,6 +557,25 @@ impl ConfigValidator {
         Ok(())
     }
 
+    /// Validates a ready indicator regex (not empty, compiles; requires the `regex` feature)
+    pub fn validate_ready_indicator_regex(pattern: &str) -> Result<(), TaskError> {
+        if pattern.is_empty() {
+            return Err(TaskError::InvalidConfiguration(
+                "ready_indicator_regex cannot be empty string".to_string(),
+            ));
+        }
+        if cfg!(not(feature = "regex")) {
+            return Err(TaskError::InvalidConfiguration(
+                "ready_indicator_regex requires the regex feature".to_string(),
+            ));
+        }
+        #[cfg(feature = "regex")]
+        regex::Regex::new(pattern).map_err(|e| {
+            TaskError::InvalidConfiguration(format!("Invalid ready_indicator_regex: {e}"))
+        })?;
+        Ok(())
+    }
+
     /// Validates timeout value (must be greater than 0 if present)
     pub fn validate_timeout(timeout: &u64) -> Result<(), TaskError> {
         if *timeout == 0 {
@@ -286,6 +586,301 @@ impl ConfigValidator {
         Ok(())
     }
 
+    /// Validates a Job Object name (1 to 260 characters, no null bytes)
+    pub fn validate_job_object_name(name: &str) -> Result<(), TaskError> {
+        if name.is_empty() {
+            return Err(TaskError::InvalidConfiguration(
+                "Job Object name cannot be empty".to_string(),
+            ));
+        }
+        if name.chars().count() > MAX_JOB_OBJECT_NAME_LEN {
+            return Err(TaskError::InvalidConfiguration(format!(
+                "Job Object name exceeds maximum length of {MAX_JOB_OBJECT_NAME_LEN} characters"
+            )));
+        }
+        if name.contains('\0') {
+            return Err(TaskError::InvalidConfiguration(
+                "Job Object name cannot contain null characters".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validates CPU affinity core indices (not empty, each below the
+    /// available parallelism of this machine and within an affinity mask)
+    ///
+    /// Whether this process may use the cores is left to the OS when the task
+    /// starts.
+    pub fn validate_cpu_affinity(cores: &[usize]) -> Result<(), TaskError> {
+        if cores.is_empty() {
+            return Err(TaskError::InvalidConfiguration(
+                "cpu_affinity cannot be empty".to_string(),
+            ));
+        }
+        let available = std::thread::available_parallelism()
+            .map_err(|e| {
+                TaskError::InvalidConfiguration(format!(
+                    "Cannot check cpu_affinity, available parallelism is unknown: {e}"
+                ))
+            })?
+            .get();
+        if let Some(core) = cores.iter().find(|core| **core >= available) {
+            return Err(TaskError::InvalidConfiguration(format!(
+                "cpu_affinity core {core} is out of range, only {available} cores are available"
+            )));
+        }
+        if let Some(core) = cores.iter().find(|core| **core >= MAX_AFFINITY_CORES) {
+            return Err(TaskError::InvalidConfiguration(format!(
+                "cpu_affinity core {core} is out of range, an affinity mask holds {MAX_AFFINITY_CORES} cores"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Validates the output read buffer size (256 bytes to 4 MiB)
+    pub fn validate_output_buffer_bytes(bytes: usize) -> Result<(), TaskError> {
+        if !(MIN_OUTPUT_BUFFER_BYTES..=MAX_OUTPUT_BUFFER_BYTES).contains(&bytes) {
+            return Err(TaskError::InvalidConfiguration(format!(
+                "Output buffer size must be between {MIN_OUTPUT_BUFFER_BYTES} and {MAX_OUTPUT_BUFFER_BYTES} bytes, got {bytes}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Validates the longest output line (must be greater than 0)
+    pub fn validate_max_output_line_bytes(bytes: usize) -> Result<(), TaskError> {
+        if bytes == 0 {
+            return Err(TaskError::InvalidConfiguration(
+                "Maximum output line length must be greater than 0".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validates the running long multiplier (must be positive and finite)
+    pub fn validate_running_long_multiplier(multiplier: f64) -> Result<(), TaskError> {
+        if !multiplier.is_finite() || multiplier <= 0.0 {
+            return Err(TaskError::InvalidConfiguration(format!(
+                "Running long multiplier must be a positive, finite number, got {multiplier}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Validates the interval between memory samples (at least 10 ms)
+    pub fn validate_sample_interval_ms(interval_ms: u64) -> Result<(), TaskError> {
+        if interval_ms < MIN_SAMPLE_INTERVAL_MS {
+            return Err(TaskError::InvalidConfiguration(format!(
+                "Sample interval must be at least {MIN_SAMPLE_INTERVAL_MS} ms, got {interval_ms}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Validates exit code meanings (at most 256 codes, each meaning non-empty and at most 1024 bytes)
+    pub fn validate_exit_code_meanings(meanings: &HashMap<i32, String>) -> Result<(), TaskError> {
+        if meanings.len() > MAX_EXIT_CODE_MEANINGS {
+            return Err(TaskError::InvalidConfiguration(format!(
+                "At most {MAX_EXIT_CODE_MEANINGS} exit code meanings can be set, got {}",
+                meanings.len()
+            )));
+        }
+        for (code, meaning) in meanings {
+            if meaning.trim().is_empty() {
+                return Err(TaskError::InvalidConfiguration(format!(
+                    "Meaning of exit code {code} cannot be empty"
+                )));
+            }
+            if meaning.len() > MAX_EXIT_CODE_MEANING_LEN {
+                return Err(TaskError::InvalidConfiguration(format!(
+                    "Meaning of exit code {code} exceeds maximum length of {MAX_EXIT_CODE_MEANING_LEN}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Validates output dedupe settings (window and repeat threshold must be greater than 0)
+    pub fn validate_output_dedupe(dedupe: &OutputDedupe) -> Result<(), TaskError> {
+        if dedupe.window_ms == 0 {
+            return Err(TaskError::InvalidConfiguration(
+                "Output dedupe window must be greater than 0".to_string(),
+            ));
+        }
+        if dedupe.max_repeats == 0 {
+            return Err(TaskError::InvalidConfiguration(
+                "Output dedupe max_repeats must be greater than 0".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validates spawn retry settings (1 to 10 attempts, at most 60 s apart, at least one error kind)
+    pub fn validate_spawn_retry(retry: &SpawnRetry) -> Result<(), TaskError> {
+        if !(1..=MAX_SPAWN_ATTEMPTS).contains(&retry.attempts) {
+            return Err(TaskError::InvalidConfiguration(format!(
+                "Spawn attempts must be between 1 and {MAX_SPAWN_ATTEMPTS}, got {}",
+                retry.attempts
+            )));
+        }
+        if retry.backoff_ms > MAX_SPAWN_BACKOFF_MS {
+            return Err(TaskError::InvalidConfiguration(format!(
+                "Spawn retry backoff must be at most {MAX_SPAWN_BACKOFF_MS} ms, got {}",
+                retry.backoff_ms
+            )));
+        }
+        if retry.retry_on.is_empty() {
+            return Err(TaskError::InvalidConfiguration(
+                "Spawn retry must retry at least one error kind".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validates the prefix of a temporary working directory name
+    ///
+    /// Must be 1 to 64 characters, without path separators or null bytes, so
+    /// the directory is created directly in the system temporary directory.
+    pub fn validate_temp_dir_prefix(prefix: &str) -> Result<(), TaskError> {
+        if prefix.is_empty() || prefix.chars().count() > MAX_TEMP_DIR_PREFIX_LEN {
+            return Err(TaskError::InvalidConfiguration(format!(
+                "Temporary directory prefix must be 1 to {MAX_TEMP_DIR_PREFIX_LEN} characters"
+            )));
+        }
+        if prefix.contains(['/', '\\', '\0']) {
+            return Err(TaskError::InvalidConfiguration(
+                "Temporary directory prefix cannot contain path separators or null bytes"
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validates the output event rate limit (must be greater than 0)
+    pub fn validate_max_output_events_per_sec(max: u32) -> Result<(), TaskError> {
+        if max == 0 {
+            return Err(TaskError::InvalidConfiguration(
+                "Output events per second must be greater than 0".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validates the number of output lines captured at startup (at most 1000)
+    pub fn validate_startup_capture_lines(lines: usize) -> Result<(), TaskError> {
+        if lines > MAX_STARTUP_CAPTURE_LINES {
+            return Err(TaskError::InvalidConfiguration(format!(
+                "Startup capture lines must be at most {MAX_STARTUP_CAPTURE_LINES}, got {lines}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Validates the number of output lines retained for the tail (at most 10000)
+    pub fn validate_retain_output_lines(lines: usize) -> Result<(), TaskError> {
+        if lines > MAX_RETAINED_OUTPUT_LINES {
+            return Err(TaskError::InvalidConfiguration(format!(
+                "Retained output lines must be at most {MAX_RETAINED_OUTPUT_LINES}, got {lines}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Validates output triggers (at most 64, non-empty patterns and event names)
+    pub fn validate_output_triggers(triggers: &[OutputTrigger]) -> Result<(), TaskError> {
+        if triggers.len() > MAX_OUTPUT_TRIGGERS {
+            return Err(TaskError::InvalidConfiguration(format!(
+                "At most {MAX_OUTPUT_TRIGGERS} output triggers are allowed, got {}",
+                triggers.len()
+            )));
+        }
+        for trigger in triggers {
+            if trigger.pattern.is_empty() {
+                return Err(TaskError::InvalidConfiguration(
+                    "Output trigger pattern cannot be empty".to_string(),
+                ));
+            }
+            if trigger.pattern.len() > MAX_TRIGGER_PATTERN_LEN {
+                return Err(TaskError::InvalidConfiguration(format!(
+                    "Output trigger pattern exceeds maximum length of {MAX_TRIGGER_PATTERN_LEN}"
+                )));
+            }
+            if let TriggerAction::EmitEvent(name) = &trigger.action
+                && name.is_empty()
+            {
+                return Err(TaskError::InvalidConfiguration(
+                    "Output trigger event name cannot be empty".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Validates a stdin script (1 to 64 steps, non-empty patterns, timeouts greater than 0)
+    pub fn validate_stdin_script(script: &StdinScript) -> Result<(), TaskError> {
+        if !(1..=MAX_STDIN_SCRIPT_STEPS).contains(&script.steps.len()) {
+            return Err(TaskError::InvalidConfiguration(format!(
+                "Stdin script must have 1 to {MAX_STDIN_SCRIPT_STEPS} steps, got {}",
+                script.steps.len()
+            )));
+        }
+        for step in &script.steps {
+            if step.wait_for.is_empty() {
+                return Err(TaskError::InvalidConfiguration(
+                    "Stdin script pattern cannot be empty".to_string(),
+                ));
+            }
+            if step.wait_for.len() > MAX_TRIGGER_PATTERN_LEN {
+                return Err(TaskError::InvalidConfiguration(format!(
+                    "Stdin script pattern exceeds maximum length of {MAX_TRIGGER_PATTERN_LEN}"
+                )));
+            }
+            if step.timeout_ms == 0 {
+                return Err(TaskError::InvalidConfiguration(
+                    "Stdin script step timeout must be greater than 0".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Validates that no line-based option is combined with `OutputMode::Bytes`
+    pub fn validate_bytes_output_mode(config: &TaskConfig) -> Result<(), TaskError> {
+        let line_options = [
+            (
+                "Ready indicator",
+                config.ready_indicator.is_some() || config.ready_indicator_regex.is_some(),
+            ),
+            (
+                "output_triggers",
+                config
+                    .output_triggers
+                    .as_ref()
+                    .is_some_and(|triggers| !triggers.is_empty()),
+            ),
+            ("stdin_script", config.stdin_script.is_some()),
+            (
+                "parse_json_lines",
+                config.parse_json_lines.unwrap_or_default(),
+            ),
+            ("dedupe_output", config.dedupe_output.is_some()),
+            (
+                "max_output_events_per_sec",
+                config.max_output_events_per_sec.is_some(),
+            ),
+            (
+                "output_encoding",
+                config.output_encoding.unwrap_or_default() != OutputEncoding::Utf8,
+            ),
+        ];
+        if let Some((option, _)) = line_options.iter().find(|(_, set)| *set) {
+            return Err(TaskError::InvalidConfiguration(format!(
+                "{option} cannot be used with OutputMode::Bytes"
+            )));
+        }
+        Ok(())
+    }
+
     /// Checks for obvious injection attempts while allowing normal shell features.
     ///
     /// This internal method identifies clearly malicious patterns without blocking
@@ -0,0 +1,51 @@
+//! The runtime-independent surface, built without the `tokio` feature.
+//!
+//! Run with `cargo test --no-default-features --test minimal_core`; with
+//! `tokio` enabled this target is empty. Fails to compile if any of these
+//! modules starts depending on the runtime.
+#![cfg(not(feature = "tokio"))]
+
+use tcrm_task::tasks::{
+    config::{StreamSource, TaskConfig},
+    error::TaskError,
+    event::{TaskEvent, TaskEventStopReason, TaskTerminateReason},
+    state::TaskState,
+    validator::{ConfigValidator, ValidationWarning},
+};
+
+#[test]
+fn config_is_validated_without_runtime() {
+    let config = TaskConfig::new("echo")
+        .args(["hello"])
+        .ready_indicator("hello")
+        .ready_indicator_source(StreamSource::Stdout);
+
+    let (result, warnings) = config.validate_with_warnings();
+    assert!(result.is_ok());
+    assert!(warnings.contains(&ValidationWarning::ReadyIndicatorWithoutTimeout));
+
+    let invalid = TaskConfig::new("").validate();
+    assert!(matches!(invalid, Err(TaskError::InvalidConfiguration(_))));
+    assert!(ConfigValidator::validate_command("echo").is_ok());
+}
+
+#[test]
+fn events_and_states_are_plain_data() {
+    let event = TaskEvent::Stopped {
+        task_name: "minimal".into(),
+        exit_code: None,
+        signal: None,
+        exit_meaning: None,
+        reason: TaskEventStopReason::Terminated(TaskTerminateReason::Cleanup { message: None }),
+        was_ready: false,
+        duration_ms: 10,
+        expected_duration_ms: None,
+        from_cache: false,
+        stdout_sha256: None,
+        stderr_sha256: None,
+    };
+    assert_eq!(event.clone(), event);
+
+    assert!(TaskState::Pending.can_transition_to(&TaskState::Initiating));
+    assert!(!TaskState::Finished.can_transition_to(&TaskState::Running));
+}